@@ -23,19 +23,21 @@ use axum::{
 use tower_http::cors::{CorsLayer, Any};
 use tower_http::trace::TraceLayer;
 
-use fc_common::{RouterConfig, PoolConfig, QueueConfig};
+use fc_common::{RouterConfig, PoolConfig, QueueConfig, RateLimitScope, DEFAULT_POOL_CODE};
+use fc_common::http::ProxyConfig;
 use fc_router::{
-    QueueManager, HttpMediator, LifecycleManager, LifecycleConfig,
+    QueueManager, HttpMediator, HttpMediatorConfig, LifecycleManager, LifecycleConfig,
     WarningService, WarningServiceConfig, HealthService, HealthServiceConfig,
-    CircuitBreakerRegistry as RouterCircuitBreakerRegistry,
+    CircuitBreakerRegistry as RouterCircuitBreakerRegistry, DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
     api::create_router as create_api_router,
 };
-use fc_queue::sqlite::SqliteQueue;
+use fc_queue::sqlite::{connect_pool, SqliteQueue};
 use fc_queue::{QueuePublisher, EmbeddedQueue};
 use fc_outbox::{OutboxProcessor, OutboxRepository};
 
 // Platform imports
-use fc_platform::service::{AuthService, AuthConfig, AuthorizationService, AuditService};
+use fc_platform::service::{AuthService, AuthConfig, AuthorizationService, AuditService, DispatchJobProjectionWriter};
+use fc_platform::service::{BlockOnErrorChecker, DispatchConfig};
 use fc_platform::api::middleware::{AppState, AuthLayer};
 use fc_platform::api::{
     EventsState, events_router,
@@ -54,6 +56,8 @@ use fc_platform::api::{
     MonitoringState, monitoring_router, LeaderState, CircuitBreakerRegistry, InFlightTracker,
     DebugState, debug_events_router, debug_dispatch_jobs_router,
     ServiceAccountsState, service_accounts_router,
+    tsid_router,
+    WellKnownState, well_known_router,
 };
 use fc_platform::repository::{
     EventRepository, EventTypeRepository, DispatchJobRepository, DispatchPoolRepository,
@@ -77,6 +81,7 @@ use fc_platform::operations::{
 
 use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::postgres::PgPoolOptions;
+use sqlx::mysql::MySqlPoolOptions;
 
 /// FlowCatalyst Development Server
 #[derive(Parser, Debug)]
@@ -91,7 +96,7 @@ struct Args {
     #[arg(long, env = "FC_METRICS_PORT", default_value = "9090")]
     metrics_port: u16,
 
-    /// Outbox database type: sqlite, postgres, mongo
+    /// Outbox database type: sqlite, postgres, mysql, mongo
     #[arg(long, env = "FC_OUTBOX_DB_TYPE", default_value = "sqlite")]
     outbox_db_type: String,
 
@@ -111,6 +116,10 @@ struct Args {
     #[arg(long, env = "FC_POOL_CONCURRENCY", default_value = "10")]
     pool_concurrency: u32,
 
+    /// How long shutdown waits for in-flight messages, in seconds
+    #[arg(long, env = "FC_SHUTDOWN_DRAIN_TIMEOUT_SECS", default_value_t = DEFAULT_SHUTDOWN_DRAIN_TIMEOUT.as_secs())]
+    shutdown_drain_timeout_secs: u64,
+
     /// Enable outbox processor
     #[arg(long, env = "FC_OUTBOX_ENABLED", default_value = "false")]
     outbox_enabled: bool,
@@ -132,8 +141,8 @@ struct Args {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging (JSON if LOG_FORMAT=json, text otherwise)
-    fc_common::logging::init_logging("fc-dev");
+    // Initialize logging (JSON if FC_LOG_FORMAT=json, text otherwise)
+    let _logging = fc_common::logging::init_logging("fc-dev");
 
     let args = Args::parse();
 
@@ -144,10 +153,7 @@ async fn main() -> Result<()> {
     let (shutdown_tx, _) = broadcast::channel::<()>(1);
 
     // 1. Setup SQLite for embedded queue
-    let queue_pool = SqlitePoolOptions::new()
-        .max_connections(5)
-        .connect("sqlite::memory:")
-        .await?;
+    let queue_pool = connect_pool("sqlite::memory:", 5).await?;
 
     // 2. Initialize embedded queue (SQLite-based, mimics SQS FIFO)
     let queue = Arc::new(SqliteQueue::new(
@@ -159,26 +165,40 @@ async fn main() -> Result<()> {
     info!("Embedded SQLite queue initialized");
 
     // 3. Initialize HTTP Mediator (dev mode: HTTP/1.1, shorter timeout)
-    let mediator = Arc::new(HttpMediator::dev());
-
-    // 4. Create QueueManager (central orchestrator)
-    let queue_manager = Arc::new(QueueManager::new(mediator.clone()));
-    queue_manager.add_consumer(queue.clone()).await;
+    let mediator_config = HttpMediatorConfig {
+        proxy: ProxyConfig::from_env(),
+        ..HttpMediatorConfig::dev()
+    };
+    let router_circuit_breaker = Arc::new(RouterCircuitBreakerRegistry::new(mediator_config.circuit_breaker_config()));
+    let mediator = Arc::new(
+        HttpMediator::with_config(mediator_config)
+            .with_circuit_breaker_registry(router_circuit_breaker.clone())
+    );
 
-    // 4b. Create Warning and Health services
+    // 4. Create Warning and Health services
     let warning_service = Arc::new(WarningService::new(WarningServiceConfig::default()));
     let health_service = Arc::new(HealthService::new(
         HealthServiceConfig::default(),
         warning_service.clone(),
     ));
 
+    // 4b. Create QueueManager (central orchestrator)
+    let mut queue_manager = QueueManager::new(mediator.clone());
+    queue_manager.set_health_service(health_service.clone());
+    queue_manager.set_shutdown_drain_timeout(Duration::from_secs(args.shutdown_drain_timeout_secs));
+    let queue_manager = Arc::new(queue_manager);
+    queue_manager.add_consumer(queue.clone()).await;
+
     // 5. Apply router configuration
     let router_config = RouterConfig {
         processing_pools: vec![
             PoolConfig {
-                code: "DEFAULT".to_string(),
+                code: DEFAULT_POOL_CODE.to_string(),
                 concurrency: args.pool_concurrency,
                 rate_limit_per_minute: None,
+                rate_limit_scope: RateLimitScope::Pool,
+                rate_limit_burst: None,
+                ack_on_4xx: true,
             },
         ],
         queues: vec![
@@ -187,6 +207,8 @@ async fn main() -> Result<()> {
                 uri: "sqlite::memory:".to_string(),
                 connections: 1,
                 visibility_timeout: 30,
+                max_delivery_attempts: None,
+                poll_batch_size: None,
             },
         ],
     };
@@ -265,7 +287,7 @@ async fn main() -> Result<()> {
     let create_service_account_use_case = Arc::new(CreateServiceAccountUseCase::new(service_account_repo.clone(), unit_of_work.clone()));
     let update_service_account_use_case = Arc::new(UpdateServiceAccountUseCase::new(service_account_repo.clone(), unit_of_work.clone()));
     let delete_service_account_use_case = Arc::new(DeleteServiceAccountUseCase::new(service_account_repo.clone(), unit_of_work.clone()));
-    let assign_roles_use_case = Arc::new(AssignRolesUseCase::new(service_account_repo.clone(), unit_of_work.clone()));
+    let assign_roles_use_case = Arc::new(AssignRolesUseCase::new(service_account_repo.clone(), principal_repo.clone(), unit_of_work.clone()));
     let regenerate_token_use_case = Arc::new(RegenerateAuthTokenUseCase::new(service_account_repo.clone(), unit_of_work.clone()));
     let regenerate_secret_use_case = Arc::new(RegenerateSigningSecretUseCase::new(service_account_repo.clone(), unit_of_work.clone()));
 
@@ -292,6 +314,7 @@ async fn main() -> Result<()> {
     let auth_config = AuthConfig {
         rsa_private_key: Some(private_key),
         rsa_public_key: Some(public_key),
+        additional_rsa_public_keys: Vec::new(),
         secret_key: String::new(),
         issuer: "flowcatalyst".to_string(),
         audience: "flowcatalyst".to_string(),
@@ -299,7 +322,9 @@ async fn main() -> Result<()> {
         session_token_expiry_secs: 28800,    // 8 hours (PT8H)
         refresh_token_expiry_secs: 86400 * 30, // 30 days (P30D)
     };
-    let auth_service = Arc::new(AuthService::new(auth_config));
+    let auth_service = Arc::new(
+        AuthService::new(auth_config).with_principal_repo(principal_repo.clone())
+    );
     let authz_service = Arc::new(AuthorizationService::new(role_repo.clone()));
     info!("Auth services initialized");
 
@@ -312,7 +337,15 @@ async fn main() -> Result<()> {
     // 8e. Build API states
     let events_state = EventsState { event_repo: event_repo.clone() };
     let event_types_state = EventTypesState { event_type_repo: event_type_repo.clone() };
-    let dispatch_jobs_state = DispatchJobsState { dispatch_job_repo: dispatch_job_repo.clone() };
+    let dispatch_jobs_state = DispatchJobsState {
+        dispatch_job_repo: dispatch_job_repo.clone(),
+        subscription_repo: subscription_repo.clone(),
+        projection_writer: Arc::new(DispatchJobProjectionWriter::new(
+            dispatch_job_repo.clone(),
+            client_repo.clone(),
+            subscription_repo.clone(),
+        )),
+    };
     let filter_options_state = FilterOptionsState {
         client_repo: client_repo.clone(),
         event_type_repo: event_type_repo.clone(),
@@ -336,7 +369,7 @@ async fn main() -> Result<()> {
     };
     let roles_state = RolesState { role_repo: role_repo.clone(), application_repo: Some(application_repo.clone()) };
     let subscriptions_state = SubscriptionsState { subscription_repo: subscription_repo.clone() };
-    let oauth_clients_state = OAuthClientsState { oauth_client_repo: oauth_client_repo.clone() };
+    let oauth_clients_state = OAuthClientsState { oauth_client_repo: oauth_client_repo.clone(), secrets_provider: None };
     let auth_config_state = AuthConfigState {
         anchor_domain_repo: anchor_domain_repo.clone(),
         client_auth_config_repo: client_auth_config_repo.clone(),
@@ -376,12 +409,17 @@ async fn main() -> Result<()> {
         dispatch_job_repo: dispatch_job_repo.clone(),
     };
 
+    // Blocked message groups are probed again after a cooldown
+    let block_checker = Arc::new(BlockOnErrorChecker::new(dispatch_job_repo.clone(), DispatchConfig::default()));
+    block_checker.start().await;
+
     // Monitoring state with leader election and circuit breakers
     let monitoring_state = MonitoringState {
         leader_state: LeaderState::new(uuid::Uuid::new_v4().to_string()),
         circuit_breakers: CircuitBreakerRegistry::new(),
         in_flight: InFlightTracker::new(),
         dispatch_job_repo: dispatch_job_repo.clone(),
+        block_checker,
         start_time: std::time::Instant::now(),
     };
 
@@ -409,15 +447,20 @@ async fn main() -> Result<()> {
         .nest("/api/admin/applications", applications_router(applications_state).into())
         .nest("/api/admin/dispatch-pools", dispatch_pools_router(dispatch_pools_state).into())
         .nest("/api/admin/service-accounts", service_accounts_router(service_accounts_state).into())
+        .nest("/api/admin/tsid", tsid_router().into())
         // Monitoring APIs
         .nest("/api/monitoring", monitoring_router(monitoring_state).into())
+        // OIDC discovery and JWKS
+        .nest("/.well-known", well_known_router(WellKnownState {
+            auth_service: auth_service.clone(),
+            external_base_url: format!("http://localhost:{}", args.api_port),
+        }))
         // Add auth middleware
         .layer(AuthLayer::new(app_state));
 
     info!("Platform APIs configured");
 
     // 9. Start API server (merge router API with platform APIs)
-    let router_circuit_breaker = Arc::new(RouterCircuitBreakerRegistry::default());
     let router_api = create_api_router(
         queue.clone(),
         queue_manager.clone(),
@@ -511,8 +554,9 @@ async fn main() -> Result<()> {
     // Stop lifecycle manager
     lifecycle.shutdown().await;
 
-    // Wait for all handles with timeout
-    let shutdown_timeout = Duration::from_secs(30);
+    // Wait for all handles with timeout; the queue manager drains in-flight
+    // messages first, so allow for its drain timeout on top
+    let shutdown_timeout = Duration::from_secs(args.shutdown_drain_timeout_secs + 30);
     let _ = tokio::time::timeout(shutdown_timeout, async {
         let _ = api_handle.await;
         let _ = metrics_handle.await;
@@ -551,6 +595,18 @@ async fn create_outbox_repository(args: &Args) -> Result<Arc<dyn OutboxRepositor
             info!("Outbox using PostgreSQL");
             Ok(Arc::new(repo))
         }
+        "mysql" => {
+            let url = args.outbox_db_url.as_ref()
+                .ok_or_else(|| anyhow::anyhow!("FC_OUTBOX_DB_URL required for mysql"))?;
+            let pool = MySqlPoolOptions::new()
+                .max_connections(5)
+                .connect(url)
+                .await?;
+            let repo = fc_outbox::mysql::MySqlOutboxRepository::new(pool);
+            repo.init_schema().await?;
+            info!("Outbox using MySQL");
+            Ok(Arc::new(repo))
+        }
         "mongo" => {
             let url = args.outbox_db_url.as_ref()
                 .ok_or_else(|| anyhow::anyhow!("FC_OUTBOX_DB_URL required for mongo"))?;
@@ -563,7 +619,7 @@ async fn create_outbox_repository(args: &Args) -> Result<Arc<dyn OutboxRepositor
             Ok(Arc::new(repo))
         }
         other => {
-            Err(anyhow::anyhow!("Unknown outbox database type: {}. Use sqlite, postgres, or mongo", other))
+            Err(anyhow::anyhow!("Unknown outbox database type: {}. Use sqlite, postgres, mysql, or mongo", other))
         }
     }
 }
@@ -581,10 +637,23 @@ impl OutboxQueuePublisher {
 
 #[async_trait::async_trait]
 impl fc_outbox::QueuePublisher for OutboxQueuePublisher {
-    async fn publish(&self, message: fc_common::Message) -> Result<()> {
+    async fn publish(&self, message: fc_common::Message) -> Result<fc_outbox::PublishOutcome> {
         self.queue.publish(message).await
             .map_err(|e| anyhow::anyhow!("Queue publish error: {}", e))?;
-        Ok(())
+        Ok(fc_outbox::PublishOutcome::Accepted)
+    }
+
+    async fn publish_batch(&self, messages: Vec<fc_common::Message>) -> Result<Vec<Result<fc_outbox::PublishOutcome>>> {
+        let count = messages.len();
+        let result = self.queue.publish_batch(messages).await
+            .map_err(|e| anyhow::anyhow!("Queue publish error: {}", e))?;
+
+        Ok((0..count)
+            .map(|index| match result.failure(index) {
+                Some(failure) => Err(anyhow::anyhow!("Queue publish error: {}", failure.error)),
+                None => Ok(fc_outbox::PublishOutcome::Accepted),
+            })
+            .collect())
     }
 }
 
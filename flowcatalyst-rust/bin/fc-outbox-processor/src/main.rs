@@ -14,12 +14,15 @@
 //! | Variable | Default | Description |
 //! |----------|---------|-------------|
 //! | `FC_OUTBOX_MODE` | `enhanced` | Mode: `enhanced` (HTTP API) or `sqs` (direct SQS) |
-//! | `FC_OUTBOX_DB_TYPE` | `postgres` | Database type: `sqlite`, `postgres`, `mongo` |
+//! | `FC_OUTBOX_DB_TYPE` | `postgres` | Database type: `sqlite`, `postgres`, `mysql`, `mongo` |
 //! | `FC_OUTBOX_DB_URL` | - | Database connection URL (required) |
 //! | `FC_OUTBOX_MONGO_DB` | `flowcatalyst` | MongoDB database name |
 //! | `FC_OUTBOX_MONGO_COLLECTION` | `outbox` | MongoDB collection name |
 //! | `FC_OUTBOX_POLL_INTERVAL_MS` | `1000` | Poll interval in milliseconds |
 //! | `FC_OUTBOX_BATCH_SIZE` | `100` | Max messages per batch (SQS mode) |
+//! | `FC_OUTBOX_MAX_RETRIES` | `5` | Publish retries before an item is marked failed (SQS mode) |
+//! | `FC_OUTBOX_RETRY_BACKOFF_MS` | `1000` | Backoff before the first retry, doubled per retry and jittered (SQS mode) |
+//! | `FC_OUTBOX_RETRY_MAX_BACKOFF_MS` | `300000` | Upper bound on the retry backoff (SQS mode) |
 //! | `FC_QUEUE_URL` | - | SQS queue URL (required for SQS mode) |
 //! | `FC_API_BASE_URL` | `http://localhost:8080` | FlowCatalyst API URL (enhanced mode) |
 //! | `FC_API_TOKEN` | - | API Bearer token (optional) |
@@ -27,24 +30,40 @@
 //! | `FC_GLOBAL_BUFFER_SIZE` | `1000` | Buffer capacity (enhanced mode) |
 //! | `FC_MAX_CONCURRENT_GROUPS` | `10` | Max concurrent message groups (enhanced mode) |
 //! | `FC_METRICS_PORT` | `9090` | Metrics/health port |
+//! | `FC_OUTBOX_ADMIN_TOKEN` | - | Bearer token for the admin endpoints; they are disabled when unset |
 //! | `RUST_LOG` | `info` | Log level |
+//!
+//! ## Admin Endpoints
+//!
+//! Served on the metrics port only when `FC_OUTBOX_ADMIN_TOKEN` is set, and
+//! require `Authorization: Bearer <token>`:
+//!
+//! - `POST /admin/requeue-completed?since=<RFC 3339>&dryRun=false` resets items
+//!   completed at or after `since` to PENDING so they are published again.
+//!   Without `dryRun=false` it only reports how many items match.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use std::net::SocketAddr;
 use anyhow::Result;
-use tracing::info;
+use tracing::{error, info};
 use tokio::signal;
 use tokio::sync::broadcast;
 use async_trait::async_trait;
 
-use fc_outbox::{OutboxProcessor, repository::OutboxRepository};
+use fc_outbox::{OutboxProcessor, OutboxRepositoryExt, ProcessorMetrics, PublishOutcome, PublishStats, RetryPolicy, repository::OutboxRepository};
 use fc_outbox::{EnhancedOutboxProcessor, EnhancedProcessorConfig};
 use fc_outbox::http_dispatcher::HttpDispatcherConfig;
 use fc_common::Message;
+use fc_queue::BodyFormat;
+use fc_queue::codec::encode_body;
+use fc_queue::sqs::{send_batch_entry_results, SqsDeduplicationTracker, SqsQueueType, SqsSendAttributes};
+use aws_sdk_sqs::types::SendMessageBatchRequestEntry;
 
 use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::postgres::PgPoolOptions;
+use sqlx::mysql::MySqlPoolOptions;
 
 fn env_or(key: &str, default: &str) -> String {
     std::env::var(key).unwrap_or_else(|_| default.to_string())
@@ -63,7 +82,7 @@ fn env_required(key: &str) -> Result<String> {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    fc_common::logging::init_logging("fc-outbox-processor");
+    let _logging = fc_common::logging::init_logging("fc-outbox-processor");
 
     info!("Starting FlowCatalyst Outbox Processor");
 
@@ -72,6 +91,7 @@ async fn main() -> Result<()> {
     let db_type = env_or("FC_OUTBOX_DB_TYPE", "postgres");
     let poll_interval_ms: u64 = env_or_parse("FC_OUTBOX_POLL_INTERVAL_MS", 1000);
     let metrics_port: u16 = env_or_parse("FC_METRICS_PORT", 9090);
+    let admin_token = std::env::var("FC_OUTBOX_ADMIN_TOKEN").ok().filter(|t| !t.is_empty());
 
     // Setup shutdown signal
     let (shutdown_tx, _) = broadcast::channel::<()>(1);
@@ -79,13 +99,22 @@ async fn main() -> Result<()> {
     // Initialize outbox repository
     let outbox_repo = create_outbox_repository(&db_type).await?;
     info!("Outbox repository initialized ({})", db_type);
+    let admin_repo = Arc::clone(&outbox_repo);
 
-    // Start processor based on mode
-    let processor_handle = match mode.as_str() {
+    // Publish outcome counters, only reported in SQS mode
+    let mut publish_stats: Option<Arc<PublishStats>> = None;
+
+    // Start processor based on mode, keeping its metrics for the metrics endpoint
+    let (processor_handle, metrics) = match mode.as_str() {
         "sqs" => {
             // Legacy SQS mode
             let batch_size: u32 = env_or_parse("FC_OUTBOX_BATCH_SIZE", 100);
             let queue_url = env_required("FC_QUEUE_URL")?;
+            let retry_policy = RetryPolicy {
+                max_retries: env_or_parse("FC_OUTBOX_MAX_RETRIES", 5),
+                initial_backoff: Duration::from_millis(env_or_parse("FC_OUTBOX_RETRY_BACKOFF_MS", 1000)),
+                max_backoff: Duration::from_millis(env_or_parse("FC_OUTBOX_RETRY_MAX_BACKOFF_MS", 300_000)),
+            };
 
             let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
             let sqs_client = aws_sdk_sqs::Client::new(&config);
@@ -97,17 +126,20 @@ async fn main() -> Result<()> {
                 publisher,
                 Duration::from_millis(poll_interval_ms),
                 batch_size,
-            );
+            ).with_retry_policy(retry_policy);
+            publish_stats = Some(processor.publish_stats());
+            let metrics = processor.metrics();
 
             let mut shutdown_rx = shutdown_tx.subscribe();
-            tokio::spawn(async move {
+            let handle = tokio::spawn(async move {
                 tokio::select! {
                     _ = processor.start() => {}
                     _ = shutdown_rx.recv() => {
                         info!("Outbox processor shutting down");
                     }
                 }
-            })
+            });
+            (handle, metrics)
         }
         _ => {
             // Enhanced mode (HTTP API with message group ordering)
@@ -140,9 +172,11 @@ async fn main() -> Result<()> {
 
             let processor = Arc::new(EnhancedOutboxProcessor::new(config, outbox_repo)?);
 
+            let metrics = processor.metrics();
+
             let mut shutdown_rx = shutdown_tx.subscribe();
             let processor_clone = Arc::clone(&processor);
-            tokio::spawn(async move {
+            let handle = tokio::spawn(async move {
                 tokio::select! {
                     _ = processor_clone.start() => {}
                     _ = shutdown_rx.recv() => {
@@ -150,7 +184,8 @@ async fn main() -> Result<()> {
                         info!("Enhanced outbox processor shutting down");
                     }
                 }
-            })
+            });
+            (handle, metrics)
         }
     };
 
@@ -158,10 +193,17 @@ async fn main() -> Result<()> {
     let metrics_addr = SocketAddr::from(([0, 0, 0, 0], metrics_port));
     info!("Metrics server listening on http://{}/metrics", metrics_addr);
 
-    let metrics_app = axum::Router::new()
+    let mut metrics_app = axum::Router::new()
         .route("/metrics", axum::routing::get(metrics_handler))
         .route("/health", axum::routing::get(health_handler))
         .route("/ready", axum::routing::get(ready_handler));
+    // The metrics port is typically unauthenticated, so admin routes are opt-in
+    if admin_token.is_some() {
+        metrics_app = metrics_app.route("/admin/requeue-completed", axum::routing::post(requeue_completed_handler));
+        info!("Admin endpoints enabled on the metrics port");
+    }
+    let metrics_app = metrics_app
+        .with_state(ServerState { publish_stats, metrics, repository: admin_repo, admin_token });
 
     let metrics_listener = tokio::net::TcpListener::bind(metrics_addr).await?;
     let metrics_handle = {
@@ -218,6 +260,17 @@ async fn create_outbox_repository(db_type: &str) -> Result<Arc<dyn OutboxReposit
             info!("Using PostgreSQL outbox");
             Ok(Arc::new(repo))
         }
+        "mysql" => {
+            let url = env_required("FC_OUTBOX_DB_URL")?;
+            let pool = MySqlPoolOptions::new()
+                .max_connections(10)
+                .connect(&url)
+                .await?;
+            let repo = fc_outbox::mysql::MySqlOutboxRepository::new(pool);
+            repo.init_schema().await?;
+            info!("Using MySQL outbox");
+            Ok(Arc::new(repo))
+        }
         "mongo" => {
             let url = env_required("FC_OUTBOX_DB_URL")?;
             let db_name = env_or("FC_OUTBOX_MONGO_DB", "flowcatalyst");
@@ -227,7 +280,7 @@ async fn create_outbox_repository(db_type: &str) -> Result<Arc<dyn OutboxReposit
             Ok(Arc::new(repo))
         }
         other => {
-            Err(anyhow::anyhow!("Unknown database type: {}. Use sqlite, postgres, or mongo", other))
+            Err(anyhow::anyhow!("Unknown database type: {}. Use sqlite, postgres, mysql, or mongo", other))
         }
     }
 }
@@ -236,34 +289,150 @@ async fn create_outbox_repository(db_type: &str) -> Result<Arc<dyn OutboxReposit
 struct SqsPublisher {
     client: aws_sdk_sqs::Client,
     queue_url: String,
+    queue_type: SqsQueueType,
+    deduplication: SqsDeduplicationTracker,
 }
 
 impl SqsPublisher {
     fn new(client: aws_sdk_sqs::Client, queue_url: String) -> Self {
-        Self { client, queue_url }
+        let queue_type = SqsQueueType::from_queue_url(&queue_url);
+        Self { client, queue_url, queue_type, deduplication: SqsDeduplicationTracker::new() }
     }
 }
 
 #[async_trait]
 impl fc_outbox::QueuePublisher for SqsPublisher {
-    async fn publish(&self, message: Message) -> Result<()> {
-        let body = serde_json::to_string(&message)?;
+    async fn publish(&self, message: Message) -> Result<PublishOutcome> {
+        // Fails with QueueError::Serialization, logged with the message ID
+        let body = encode_body(&message, BodyFormat::Json)?;
+        let attrs = SqsSendAttributes::for_message(self.queue_type, &message);
+        let deduplication_id = attrs.message_deduplication_id.clone();
 
-        self.client.send_message()
+        let output = self.client.send_message()
             .queue_url(&self.queue_url)
             .message_body(body)
-            .message_group_id(message.message_group_id.as_deref().unwrap_or("default"))
-            .message_deduplication_id(&message.id)
+            .set_message_group_id(attrs.message_group_id)
+            .set_message_deduplication_id(attrs.message_deduplication_id)
             .send()
             .await
             .map_err(|e| anyhow::anyhow!("SQS send error: {}", e))?;
 
-        Ok(())
+        // Only FIFO queues deduplicate; SQS signals it by returning the original message ID
+        let deduplicated = match (deduplication_id, output.message_id()) {
+            (Some(dedup_id), Some(sqs_id)) => self.deduplication.record(&dedup_id, sqs_id),
+            _ => false,
+        };
+        Ok(if deduplicated { PublishOutcome::Deduplicated } else { PublishOutcome::Accepted })
+    }
+
+    /// Sends the batch in one `SendMessageBatch` request. Messages that fail to
+    /// encode are reported as failed without holding back the rest.
+    async fn publish_batch(&self, messages: Vec<Message>) -> Result<Vec<Result<PublishOutcome>>> {
+        let mut outcomes: Vec<Option<Result<PublishOutcome>>> = messages.iter().map(|_| None).collect();
+        let mut entries = Vec::with_capacity(messages.len());
+        let mut deduplication_ids = HashMap::new();
+
+        for (index, message) in messages.iter().enumerate() {
+            let body = match encode_body(message, BodyFormat::Json) {
+                Ok(body) => body,
+                Err(e) => {
+                    outcomes[index] = Some(Err(e.into()));
+                    continue;
+                }
+            };
+            let attrs = SqsSendAttributes::for_message(self.queue_type, message);
+            if let Some(deduplication_id) = &attrs.message_deduplication_id {
+                deduplication_ids.insert(index, deduplication_id.clone());
+            }
+            entries.push(SendMessageBatchRequestEntry::builder()
+                .id(index.to_string())
+                .message_body(body)
+                .set_message_group_id(attrs.message_group_id)
+                .set_message_deduplication_id(attrs.message_deduplication_id)
+                .build()?);
+        }
+
+        if !entries.is_empty() {
+            let output = self.client.send_message_batch()
+                .queue_url(&self.queue_url)
+                .set_entries(Some(entries))
+                .send()
+                .await
+                .map_err(|e| anyhow::anyhow!("SQS send batch error: {}", e))?;
+
+            let results = send_batch_entry_results(messages.len(), &output);
+            for (index, entry) in results.into_iter().enumerate() {
+                // Messages that failed to encode were never sent and already have an outcome
+                if outcomes[index].is_some() {
+                    continue;
+                }
+                outcomes[index] = Some(match entry {
+                    Ok(sqs_id) => {
+                        // Only FIFO queues deduplicate; SQS signals it by returning the original message ID
+                        let deduplicated = deduplication_ids.get(&index)
+                            .is_some_and(|dedup_id| self.deduplication.record(dedup_id, &sqs_id));
+                        Ok(if deduplicated { PublishOutcome::Deduplicated } else { PublishOutcome::Accepted })
+                    }
+                    Err(error) => Err(anyhow::anyhow!("SQS rejected message: {}", error)),
+                });
+            }
+        }
+
+        Ok(outcomes.into_iter()
+            .map(|outcome| outcome.unwrap_or_else(|| Err(anyhow::anyhow!("No result reported by SQS"))))
+            .collect())
     }
 }
 
-async fn metrics_handler() -> String {
-    "# HELP fc_outbox_up Outbox processor is up\n# TYPE fc_outbox_up gauge\nfc_outbox_up 1\n".to_string()
+/// State for the metrics/admin server
+#[derive(Clone)]
+struct ServerState {
+    publish_stats: Option<Arc<PublishStats>>,
+    metrics: Arc<ProcessorMetrics>,
+    repository: Arc<dyn OutboxRepository>,
+    admin_token: Option<String>,
+}
+
+/// Whether the request carries the configured admin bearer token
+fn is_admin_request(state: &ServerState, headers: &axum::http::HeaderMap) -> bool {
+    use subtle::ConstantTimeEq;
+
+    let Some(ref expected) = state.admin_token else {
+        return false;
+    };
+    headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|token| token.as_bytes().ct_eq(expected.as_bytes()).into())
+}
+
+async fn metrics_handler(
+    axum::extract::State(state): axum::extract::State<ServerState>,
+) -> String {
+    let mut out = "# HELP fc_outbox_up Outbox processor is up\n# TYPE fc_outbox_up gauge\nfc_outbox_up 1\n".to_string();
+
+    let metrics = &state.metrics;
+    for (name, help, value) in [
+        ("fc_outbox_items_fetched_total", "Outbox items fetched for processing", metrics.items_fetched()),
+        ("fc_outbox_items_published_total", "Outbox items published successfully", metrics.items_published()),
+        ("fc_outbox_items_failed_total", "Outbox items marked failed", metrics.items_failed()),
+        ("fc_outbox_items_retried_total", "Failed outbox publishes scheduled for a retry", metrics.items_retried()),
+        ("fc_outbox_items_recovered_total", "Stuck outbox items reset to pending", metrics.items_recovered()),
+    ] {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n{} {}\n", name, help, name, name, value));
+    }
+    out.push_str("# HELP fc_outbox_backlog_size Pending outbox items, sampled on each poll\n");
+    out.push_str("# TYPE fc_outbox_backlog_size gauge\n");
+    out.push_str(&format!("fc_outbox_backlog_size {}\n", metrics.backlog_size()));
+
+    if let Some(stats) = state.publish_stats {
+        out.push_str("# HELP fc_outbox_publish_total Outbox items published, by queue outcome\n");
+        out.push_str("# TYPE fc_outbox_publish_total counter\n");
+        out.push_str(&format!("fc_outbox_publish_total{{result=\"accepted\"}} {}\n", stats.accepted()));
+        out.push_str(&format!("fc_outbox_publish_total{{result=\"deduplicated\"}} {}\n", stats.deduplicated()));
+    }
+    out
 }
 
 async fn health_handler() -> axum::Json<serde_json::Value> {
@@ -279,6 +448,61 @@ async fn ready_handler() -> axum::Json<serde_json::Value> {
     }))
 }
 
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequeueCompletedQuery {
+    /// Requeue items completed at or after this time (RFC 3339)
+    since: String,
+    /// Only count matching items (default true)
+    dry_run: Option<bool>,
+}
+
+async fn requeue_completed_handler(
+    axum::extract::State(state): axum::extract::State<ServerState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<RequeueCompletedQuery>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    if !is_admin_request(&state, &headers) {
+        return (
+            axum::http::StatusCode::UNAUTHORIZED,
+            axum::Json(serde_json::json!({ "error": "admin token required" })),
+        ).into_response();
+    }
+
+    let since = match chrono::DateTime::parse_from_rfc3339(&query.since) {
+        Ok(since) => since.with_timezone(&chrono::Utc),
+        Err(e) => {
+            return (
+                axum::http::StatusCode::BAD_REQUEST,
+                axum::Json(serde_json::json!({ "error": format!("since must be an RFC 3339 timestamp: {}", e) })),
+            ).into_response();
+        }
+    };
+
+    let dry_run = query.dry_run.unwrap_or(true);
+    match state.repository.requeue_completed(since, dry_run).await {
+        Ok(summary) => {
+            info!(
+                since = %since,
+                dry_run,
+                events = summary.events,
+                dispatch_jobs = summary.dispatch_jobs,
+                "Requeue of completed outbox items requested"
+            );
+            axum::Json(summary).into_response()
+        }
+        Err(e) => {
+            error!("Failed to requeue completed outbox items: {}", e);
+            (
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(serde_json::json!({ "error": e.to_string() })),
+            ).into_response()
+        }
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c().await.expect("failed to install Ctrl+C handler");
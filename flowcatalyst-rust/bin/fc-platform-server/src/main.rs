@@ -17,6 +17,7 @@
 //! | `FC_JWT_PUBLIC_KEY_PATH` | - | Path to RSA public key PEM |
 //! | `FLOWCATALYST_JWT_PRIVATE_KEY` | - | RSA private key PEM content (env) |
 //! | `FLOWCATALYST_JWT_PUBLIC_KEY` | - | RSA public key PEM content (env) |
+//! | `FC_JWT_ADDITIONAL_PUBLIC_KEY_PATHS` | - | Comma-separated RSA public key PEMs still accepted for verification |
 //! | `FC_JWT_ISSUER` | `flowcatalyst` | JWT issuer claim |
 //! | `RUST_LOG` | `info` | Log level |
 
@@ -33,9 +34,13 @@ use anyhow::Result;
 use tracing::info;
 use tokio::{signal, net::TcpListener};
 use utoipa_swagger_ui::SwaggerUi;
+use fc_common::http::{HttpClientConfig, ProxyConfig};
+use fc_secrets::SecretsConfig;
+use fc_standby::{LeaderElection, LeaderElectionConfig, LeadershipStatus};
 
-use fc_platform::service::{AuthService, AuthConfig, AuthorizationService, AuditService};
-use fc_platform::api::middleware::{AppState, AuthLayer};
+use fc_platform::service::{AuthService, AuthConfig, AuthorizationService, AuditService, DispatchJobProjectionWriter};
+use fc_platform::service::{BlockOnErrorChecker, DispatchConfig};
+use fc_platform::api::middleware::{AppState, AuthLayer, TrustedProxies};
 use fc_platform::api::{
     EventsState, events_router,
     EventTypesState, event_types_router,
@@ -53,8 +58,10 @@ use fc_platform::api::{
     MonitoringState, monitoring_router, LeaderState, CircuitBreakerRegistry, InFlightTracker,
     DebugState, debug_events_router, debug_dispatch_jobs_router,
     AuthState, auth_router,
-    OAuthState, oauth_router,
+    OAuthState, oauth_router, ClientSecretVerification, ClientAuthConfigResolver,
     platform_config_router,
+    WellKnownState, well_known_router,
+    tsid_router,
     ServiceAccountsState, service_accounts_router,
 };
 use fc_platform::repository::{
@@ -63,7 +70,7 @@ use fc_platform::repository::{
     ApplicationRepository, RoleRepository, OAuthClientRepository,
     AnchorDomainRepository, ClientAuthConfigRepository, ClientAccessGrantRepository, IdpRoleMappingRepository,
     AuditLogRepository, ApplicationClientConfigRepository, OidcLoginStateRepository, RefreshTokenRepository,
-    AuthorizationCodeRepository,
+    AuthorizationCodeRepository, PendingAuthRepository,
 };
 use fc_platform::usecase::MongoUnitOfWork;
 use fc_platform::operations::{
@@ -77,7 +84,7 @@ use fc_platform::operations::{
     CreateDispatchPoolUseCase, UpdateDispatchPoolUseCase,
     ArchiveDispatchPoolUseCase, DeleteDispatchPoolUseCase,
 };
-use fc_platform::service::PasswordService;
+use fc_platform::service::{LockoutConfig, PasswordService, DEFAULT_LOCKOUT_PRUNE_INTERVAL};
 use fc_platform::service::OidcSyncService;
 use fc_platform::service::OidcService;
 use fc_platform::api::{OidcLoginApiState, oidc_login_router};
@@ -97,7 +104,7 @@ fn env_or_parse<T: std::str::FromStr>(key: &str, default: T) -> T {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    fc_common::logging::init_logging("fc-platform-server");
+    let _logging = fc_common::logging::init_logging("fc-platform-server");
 
     info!("Starting FlowCatalyst Platform Server");
 
@@ -145,6 +152,7 @@ async fn main() -> Result<()> {
     let oidc_login_state_repo = Arc::new(OidcLoginStateRepository::new(&db));
     let refresh_token_repo = Arc::new(RefreshTokenRepository::new(&db));
     let auth_code_repo = Arc::new(AuthorizationCodeRepository::new(&db));
+    let pending_auth_repo = Arc::new(PendingAuthRepository::new(&db));
     info!("Repositories initialized");
 
     // Sync code-defined roles to database (always, not just in dev mode)
@@ -166,9 +174,15 @@ async fn main() -> Result<()> {
         public_key_path.as_deref(),
     )?;
 
+    let additional_public_keys = match std::env::var("FC_JWT_ADDITIONAL_PUBLIC_KEY_PATHS") {
+        Ok(paths) => AuthConfig::load_additional_public_keys(&paths)?,
+        Err(_) => Vec::new(),
+    };
+
     let auth_config = AuthConfig {
         rsa_private_key: Some(private_key),
         rsa_public_key: Some(public_key),
+        additional_rsa_public_keys: additional_public_keys,
         secret_key: String::new(),
         issuer: jwt_issuer,
         audience: "flowcatalyst".to_string(),
@@ -176,14 +190,38 @@ async fn main() -> Result<()> {
         session_token_expiry_secs: 28800,
         refresh_token_expiry_secs: 86400 * 30,
     };
-    let auth_service = Arc::new(AuthService::new(auth_config));
+    let auth_service = Arc::new(
+        AuthService::new(auth_config).with_principal_repo(principal_repo.clone())
+    );
     let authz_service = Arc::new(AuthorizationService::new(role_repo.clone()));
-    let password_service = Arc::new(PasswordService::default());
+    let lockout_defaults = LockoutConfig::default();
+    let password_service = Arc::new(PasswordService::default().with_lockout_config(LockoutConfig {
+        max_failures: env_or_parse("FC_LOGIN_MAX_FAILURES", lockout_defaults.max_failures),
+        lockout_duration: std::time::Duration::from_secs(env_or_parse(
+            "FC_LOGIN_LOCKOUT_SECS",
+            lockout_defaults.lockout_duration.as_secs(),
+        )),
+        max_lockout_duration: std::time::Duration::from_secs(env_or_parse(
+            "FC_LOGIN_MAX_LOCKOUT_SECS",
+            lockout_defaults.max_lockout_duration.as_secs(),
+        )),
+        failure_window: std::time::Duration::from_secs(env_or_parse(
+            "FC_LOGIN_FAILURE_WINDOW_SECS",
+            lockout_defaults.failure_window.as_secs(),
+        )),
+    }));
+    let lockout_pruner_task = password_service.start_lockout_pruner(DEFAULT_LOCKOUT_PRUNE_INTERVAL);
+    // Admin password reset and account unlock are opt-in; lockouts expire on their own
+    let admin_password_management = env_or_parse("FC_ADMIN_PASSWORD_MANAGEMENT_ENABLED", false);
     let oidc_sync_service = Arc::new(OidcSyncService::new(
         principal_repo.clone(),
         idp_role_mapping_repo.clone(),
     ));
-    let oidc_service = Arc::new(OidcService::new());
+    // Shared outbound HTTP client (one connection pool for all IDP calls)
+    let http_client = HttpClientConfig::default()
+        .with_proxy(ProxyConfig::from_env())
+        .build_client()?;
+    let oidc_service = Arc::new(OidcService::new().with_http_client(http_client.clone()));
     info!("Auth services initialized");
 
     // Create AppState
@@ -195,7 +233,15 @@ async fn main() -> Result<()> {
     // Build API states
     let events_state = EventsState { event_repo: event_repo.clone() };
     let event_types_state = EventTypesState { event_type_repo: event_type_repo.clone() };
-    let dispatch_jobs_state = DispatchJobsState { dispatch_job_repo: dispatch_job_repo.clone() };
+    let dispatch_jobs_state = DispatchJobsState {
+        dispatch_job_repo: dispatch_job_repo.clone(),
+        subscription_repo: subscription_repo.clone(),
+        projection_writer: Arc::new(DispatchJobProjectionWriter::new(
+            dispatch_job_repo.clone(),
+            client_repo.clone(),
+            subscription_repo.clone(),
+        )),
+    };
     let debug_state = DebugState {
         event_repo,
         dispatch_job_repo: dispatch_job_repo.clone(),
@@ -216,14 +262,13 @@ async fn main() -> Result<()> {
     };
     let principals_state = PrincipalsState {
         principal_repo: principal_repo.clone(),
-        audit_service: Some(audit_service),
-        password_service: None,
+        audit_service: Some(audit_service.clone()),
+        password_service: admin_password_management.then(|| password_service.clone()),
         anchor_domain_repo: Some(anchor_domain_repo.clone()),
         client_auth_config_repo: Some(client_auth_config_repo.clone()),
     };
     let roles_state = RolesState { role_repo: role_repo.clone(), application_repo: Some(application_repo.clone()) };
     let subscriptions_state = SubscriptionsState { subscription_repo };
-    let oauth_clients_state = OAuthClientsState { oauth_client_repo: oauth_client_repo.clone() };
     let auth_config_state = AuthConfigState {
         anchor_domain_repo: anchor_domain_repo.clone(),
         client_auth_config_repo: client_auth_config_repo.clone(),
@@ -231,13 +276,29 @@ async fn main() -> Result<()> {
         principal_repo: Some(principal_repo.clone()),
     };
     let external_base_url = std::env::var("FC_EXTERNAL_BASE_URL").ok();
+    let well_known_state = WellKnownState {
+        auth_service: auth_service.clone(),
+        external_base_url: external_base_url.clone()
+            .unwrap_or_else(|| format!("http://localhost:{}", api_port)),
+    };
+    let oidc_principal_resolver = Arc::new(ClientAuthConfigResolver::new(
+        client_auth_config_repo.clone(),
+        oidc_sync_service.clone(),
+    ));
     let oidc_login_state = OidcLoginApiState::new(
         client_auth_config_repo,
         anchor_domain_repo,
         oidc_login_state_repo,
-        oidc_sync_service,
+        oidc_sync_service.clone(),
         auth_service.clone(),
-    ).with_session_cookie_settings("fc_session", false, "Lax", 86400);
+    )
+    .with_session_cookie_settings("fc_session", false, "Lax", 86400)
+    .with_http_client(http_client)
+    .with_audit_service(audit_service.clone())
+    .with_max_concurrent_token_exchanges(env_or_parse(
+        "FC_OIDC_MAX_CONCURRENT_TOKEN_EXCHANGES",
+        fc_platform::auth::oidc_login_api::DEFAULT_MAX_CONCURRENT_TOKEN_EXCHANGES,
+    ));
     let oidc_login_state = if let Some(url) = external_base_url {
         oidc_login_state.with_external_base_url(url)
     } else {
@@ -248,15 +309,54 @@ async fn main() -> Result<()> {
         principal_repo.clone(),
         password_service,
         refresh_token_repo.clone(),
-    );
+    )
+    .with_audit_service(audit_service.clone());
     let oauth_state = OAuthState::new(
         oauth_client_repo.clone(),
         principal_repo.clone(),
         auth_service.clone(),
         oidc_service,
+        oidc_principal_resolver,
         auth_code_repo,
+        pending_auth_repo,
         refresh_token_repo,
     );
+
+    // Forwarding headers are only believed from these peers when recording client IPs
+    let trusted_proxies = TrustedProxies::parse(&env_or("FC_TRUSTED_PROXIES", ""))
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    // Client secrets for the client_credentials grant are verified against
+    // hashes held by the secrets provider. Lenient mode is for dev only.
+    let client_secret_verification = std::env::var("FC_OAUTH_CLIENT_SECRET_VERIFICATION")
+        .ok()
+        .map(|v| v.parse::<ClientSecretVerification>())
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?
+        .unwrap_or_default();
+    let secrets_config = SecretsConfig {
+        provider: std::env::var("FLOWCATALYST_SECRETS_PROVIDER").unwrap_or_else(|_| "env".to_string()),
+        encryption_key: std::env::var("FLOWCATALYST_SECRETS_ENCRYPTION_KEY").ok(),
+        data_dir: std::env::var("FLOWCATALYST_SECRETS_DATA_DIR")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|_| SecretsConfig::default().data_dir),
+        ..SecretsConfig::default()
+    };
+    let secrets_provider = fc_secrets::create_provider(&secrets_config).await
+        .map_err(|e| anyhow::anyhow!("Failed to initialize secrets provider: {}", e))?;
+    info!(
+        provider = secrets_provider.name(),
+        mode = ?client_secret_verification,
+        "OAuth client secret verification configured"
+    );
+    let oauth_clients_state = OAuthClientsState {
+        oauth_client_repo: oauth_client_repo.clone(),
+        secrets_provider: Some(secrets_provider.clone()),
+    };
+    let oauth_state = oauth_state
+        .with_secrets_provider(secrets_provider)
+        .with_client_secret_verification(client_secret_verification)
+        .with_audit_service(audit_service);
     let audit_logs_state = AuditLogsState { audit_log_repo };
 
     // Create UnitOfWork for atomic commits with events and audit logs
@@ -277,6 +377,7 @@ async fn main() -> Result<()> {
     ));
     let assign_roles_use_case = Arc::new(AssignRolesUseCase::new(
         service_account_repo.clone(),
+        principal_repo.clone(),
         unit_of_work.clone(),
     ));
     let regenerate_token_use_case = Arc::new(RegenerateAuthTokenUseCase::new(
@@ -353,11 +454,63 @@ async fn main() -> Result<()> {
         delete_use_case: delete_pool_use_case,
     };
 
+    // Cluster-wide background work (probing blocked groups) runs on the leader
+    // only. Without standby mode this instance is always the leader.
+    let leader_state = LeaderState::new(uuid::Uuid::new_v4().to_string());
+    let leader_election = if env_or_parse("FLOWCATALYST_STANDBY_ENABLED", false) {
+        let redis_url = std::env::var("FLOWCATALYST_STANDBY_REDIS_URL")
+            .or_else(|_| std::env::var("FLOWCATALYST_REDIS_URL"))
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let election_config = LeaderElectionConfig::new(redis_url)
+            .with_lock_key(env_or("FLOWCATALYST_STANDBY_LOCK_KEY", "fc:platform:leader"))
+            .with_instance_id(leader_state.instance_id.clone());
+        let election = Arc::new(LeaderElection::new(election_config).await
+            .map_err(|e| anyhow::anyhow!("Failed to create leader election: {}", e))?);
+        election.clone().start().await
+            .map_err(|e| anyhow::anyhow!("Failed to start leader election: {}", e))?;
+
+        let mut status = election.subscribe();
+        let leader = leader_state.clone();
+        tokio::spawn(async move {
+            loop {
+                let is_leader = *status.borrow_and_update() == LeadershipStatus::Leader;
+                leader.set_leader(is_leader).await;
+                if status.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+        info!("Standby mode enabled - blocked group probing runs on the leader only");
+        Some(election)
+    } else {
+        leader_state.set_leader(true).await;
+        None
+    };
+
+    // Blocked message groups are probed again after a cooldown
+    let dispatch_defaults = DispatchConfig::default();
+    let block_checker = Arc::new(BlockOnErrorChecker::new(
+        dispatch_job_repo.clone(),
+        DispatchConfig {
+            block_resume_cooldown: std::time::Duration::from_secs(env_or_parse(
+                "FC_BLOCK_RESUME_COOLDOWN_SECS",
+                dispatch_defaults.block_resume_cooldown.as_secs(),
+            )),
+            block_resume_max_cooldown: std::time::Duration::from_secs(env_or_parse(
+                "FC_BLOCK_RESUME_MAX_COOLDOWN_SECS",
+                dispatch_defaults.block_resume_max_cooldown.as_secs(),
+            )),
+            ..dispatch_defaults
+        },
+    ).with_leader_state(leader_state.clone()));
+    let block_checker_task = block_checker.start().await;
+
     let monitoring_state = MonitoringState {
-        leader_state: LeaderState::new(uuid::Uuid::new_v4().to_string()),
+        leader_state,
         circuit_breakers: CircuitBreakerRegistry::new(),
         in_flight: InFlightTracker::new(),
         dispatch_job_repo,
+        block_checker: block_checker.clone(),
         start_time: std::time::Instant::now(),
     };
 
@@ -375,6 +528,7 @@ async fn main() -> Result<()> {
         .nest("/api/admin/subscriptions", subscriptions_router(subscriptions_state))
         .nest("/api/admin/oauth-clients", oauth_clients_router(oauth_clients_state))
         .nest("/api/admin/audit-logs", audit_logs_router(audit_logs_state))
+        .nest("/api/admin/tsid", tsid_router())
         // Monitoring APIs
         .nest("/api/monitoring", monitoring_router(monitoring_state))
         // Auth APIs
@@ -415,10 +569,12 @@ async fn main() -> Result<()> {
         .nest("/auth", oidc_login_router(oidc_login_state))
         .nest("/oauth", oauth_router(oauth_state))
         .nest("/api/config", platform_config_router())
+        .nest("/.well-known", well_known_router(well_known_state))
         // OpenAPI / Swagger UI with auto-collected paths
         .merge(SwaggerUi::new("/swagger-ui").url("/q/openapi", openapi))
         // Auth middleware
         .layer(AuthLayer::new(app_state))
+        .layer(axum::Extension(trusted_proxies))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any));
 
@@ -428,7 +584,10 @@ async fn main() -> Result<()> {
 
     let api_listener = TcpListener::bind(&api_addr).await?;
     let api_task = tokio::spawn(async move {
-        axum::serve(api_listener, app).await.unwrap();
+        // Connect info lets audit entries fall back to the peer address
+        axum::serve(api_listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
+            .await
+            .unwrap();
     });
 
     // Start metrics server
@@ -452,6 +611,12 @@ async fn main() -> Result<()> {
     shutdown_signal().await;
     info!("Shutdown signal received...");
 
+    block_checker.stop().await;
+    block_checker_task.abort();
+    lockout_pruner_task.abort();
+    if let Some(election) = leader_election {
+        election.shutdown().await;
+    }
     api_task.abort();
     metrics_task.abort();
 
@@ -18,21 +18,30 @@
 //! - Test endpoints for simulating various response scenarios
 //! - Message seeding endpoints
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use fc_router::{
-    QueueManager, HttpMediator, LifecycleManager, LifecycleConfig,
-    WarningService, WarningServiceConfig,
+    QueueManager, HttpMediator, HttpMediatorConfig, LifecycleManager, LifecycleConfig,
+    WarningService, WarningServiceConfig, HttpWarningSink,
     HealthService, HealthServiceConfig,
     CircuitBreakerRegistry,
     ConfigSyncService, ConfigSyncConfig,
     StandbyProcessor, StandbyRouterConfig,
-    NotificationConfig, create_notification_service_with_scheduler,
-    api::create_router,
+    NotificationConfig, NotificationService, create_notification_service_with_scheduler,
+    api::{create_router_with_options, RouterOptions, DEFAULT_MAX_IN_FLIGHT_PAGE_SIZE, DEFAULT_MAX_MESSAGE_BYTES, DEFAULT_MAX_PUBLISH_BATCH_SIZE},
+    WarningRepository,
+    warning_repository::{
+        sqlite::SqliteWarningRepository, postgres::PostgresWarningRepository, mongo::MongoWarningRepository,
+    },
 };
-use fc_common::{RouterConfig, PoolConfig, QueueConfig, WarningSeverity};
-use fc_queue::sqs::SqsQueueConsumer;
+use fc_common::{RouterConfig, PoolConfig, QueueConfig, RateLimitScope, StallConfig, WarningSeverity, DEFAULT_POOL_CODE};
+use fc_common::http::ProxyConfig;
+use fc_secrets::{SecretService, SecretsConfig};
+use fc_queue::sqs::{AdaptiveVisibility, SqsQueueConsumer};
 use anyhow::Result;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::postgres::PgPoolOptions;
 use tracing::{info, warn, error};
 use tokio::{signal, net::TcpListener};
 use tower_http::cors::{CorsLayer, Any};
@@ -43,7 +52,8 @@ async fn main() -> Result<()> {
     // Load .env file if present (for local development)
     let _ = dotenvy::dotenv();
 
-    fc_common::logging::init_logging("fc-router");
+    let _logging = fc_common::logging::init_logging("fc-router");
+    fc_router::router_metrics::install_prometheus_recorder();
 
     info!("Starting FlowCatalyst Message Router (Production)");
 
@@ -68,10 +78,19 @@ async fn main() -> Result<()> {
         aws_sdk_sqs::Client::new(&config)
     };
 
+    // Cap the number of distinct `client` metric label values
+    if let Some(max_clients) = std::env::var("FLOWCATALYST_METRICS_MAX_CLIENT_LABELS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+    {
+        fc_router::router_metrics::client_labels().set_max_clients(max_clients);
+        info!(max_clients = max_clients, "Client metrics label limit configured");
+    }
+
     // 2. Initialize Warning and Health Services
-    let warning_service = Arc::new(WarningService::new(WarningServiceConfig::default()));
+    let warning_service = Arc::new(WarningService::new(load_warning_config()));
     let health_service = Arc::new(HealthService::new(
-        HealthServiceConfig::default(),
+        load_health_config(),
         warning_service.clone(),
     ));
 
@@ -89,11 +108,108 @@ async fn main() -> Result<()> {
         info!("Notification service disabled - no channels configured");
     }
 
-    // 3. Initialize Mediator (production mode: HTTP/2, 15 minute timeout)
-    let mediator = Arc::new(HttpMediator::production());
+    // 2c. Post severe warnings to a webhook (e.g. Slack)
+    if let Ok(url) = std::env::var("FLOWCATALYST_WARNING_WEBHOOK_URL") {
+        warning_service.add_sink(Arc::new(HttpWarningSink::new(url)));
+    }
+
+    // 2d. Persist warnings to a database (optional, in-memory only by default)
+    if let Ok(db_type) = std::env::var("FLOWCATALYST_WARNINGS_DB_TYPE") {
+        let repository = create_warning_repository(&db_type).await?;
+        warning_service.set_repository(repository);
+
+        let refresh_secs = std::env::var("FLOWCATALYST_WARNINGS_REFRESH_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+        let warning_service = warning_service.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(refresh_secs));
+            loop {
+                interval.tick().await;
+                if let Err(e) = warning_service.refresh_persisted().await {
+                    warn!(error = %e, "Failed to refresh persisted warnings");
+                }
+            }
+        });
+    }
+
+    // 3. Initialize Mediator (production mode: HTTP/2, 15 minute timeout, proxy from HTTPS_PROXY/NO_PROXY)
+    // Circuit breakers are tracked per endpoint host and reported by the monitoring API
+    let mediator_config = HttpMediatorConfig {
+        proxy: ProxyConfig::from_env(),
+        max_concurrent_per_endpoint: std::env::var("FLOWCATALYST_MAX_CONCURRENT_PER_ENDPOINT")
+            .ok()
+            .and_then(|v| v.parse().ok()),
+        auth_scheme: std::env::var("FLOWCATALYST_AUTH_SCHEME")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+        payload_format: std::env::var("FLOWCATALYST_PAYLOAD_FORMAT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+        cloudevents_source: std::env::var("FLOWCATALYST_CLOUDEVENTS_SOURCE")
+            .unwrap_or_else(|_| HttpMediatorConfig::production().cloudevents_source),
+        ..HttpMediatorConfig::production()
+    };
+    let circuit_breaker_registry = Arc::new(CircuitBreakerRegistry::new(mediator_config.circuit_breaker_config()));
+    let mut mediator = HttpMediator::with_config(mediator_config)
+        .with_circuit_breaker_registry(circuit_breaker_registry.clone());
+
+    // Auth tokens given as `encrypted:` references are resolved from the local secret store
+    if let Ok(encryption_key) = std::env::var("FLOWCATALYST_SECRETS_ENCRYPTION_KEY") {
+        let secrets_config = SecretsConfig {
+            encryption_key: Some(encryption_key),
+            data_dir: std::env::var("FLOWCATALYST_SECRETS_DATA_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| SecretsConfig::default().data_dir),
+            ..SecretsConfig::default()
+        };
+        let secret_service = SecretService::new(&secrets_config).await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize secret service: {}", e))?;
+        info!("Auth token secret references enabled");
+        mediator = mediator.with_secret_service(Arc::new(secret_service));
+    }
+    let mediator = Arc::new(mediator);
+
+    let body_format = std::env::var("FLOWCATALYST_QUEUE_BODY_FORMAT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
 
     // 4. Create QueueManager
-    let queue_manager = Arc::new(QueueManager::new(mediator.clone()));
+    let mut queue_manager = QueueManager::new(mediator.clone());
+    queue_manager.set_health_service(health_service.clone());
+    if let Ok(dead_letter_queue_url) = std::env::var("FLOWCATALYST_DEAD_LETTER_QUEUE_URL") {
+        info!(queue_url = %dead_letter_queue_url, "Dead-letter queue configured");
+        queue_manager.set_dead_letter_sink(Arc::new(SqsDeadLetterSink::new(sqs_client.clone(), dead_letter_queue_url)));
+    }
+    // In-flight messages older than the stuck threshold raise a warning and are flagged `stuck`;
+    // those older than the max age are NACKed and dropped from tracking
+    let mut stall_config = StallConfig::default();
+    if let Some(threshold) = std::env::var("FLOWCATALYST_STUCK_THRESHOLD_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        stall_config.stall_threshold_seconds = threshold;
+        stall_config.stuck_threshold_seconds = threshold;
+    }
+    if let Some(max_age) = std::env::var("FLOWCATALYST_MAX_IN_FLIGHT_AGE_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        stall_config.force_nack_stalled = true;
+        stall_config.force_nack_after_seconds = max_age;
+    }
+    queue_manager.update_stall_config(stall_config);
+    if let Some(drain_timeout) = std::env::var("FLOWCATALYST_SHUTDOWN_DRAIN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        queue_manager.set_shutdown_drain_timeout(Duration::from_secs(drain_timeout));
+    }
+    let queue_manager = Arc::new(queue_manager);
 
     // 5. Initialize Standby Processor (Active/Passive HA)
     let standby_config = load_standby_config();
@@ -180,6 +296,12 @@ async fn main() -> Result<()> {
     };
 
     // 8. Create SQS consumers from config
+    // Optional backlog-aware visibility: estimated processing seconds per outstanding message
+    let adaptive_visibility = std::env::var("FLOWCATALYST_SQS_VISIBILITY_SECONDS_PER_MESSAGE")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .map(AdaptiveVisibility::new);
+
     let mut first_queue_url: Option<String> = None;
     for queue_config in &router_config.queues {
         info!(
@@ -190,12 +312,18 @@ async fn main() -> Result<()> {
             "Creating SQS consumer from config"
         );
 
-        let consumer = Arc::new(SqsQueueConsumer::from_queue_url(
+        // Registered under the config name, which keys per-queue settings like
+        // `max_delivery_attempts` and poll parallelism
+        let mut consumer = SqsQueueConsumer::new(
             sqs_client.clone(),
             queue_config.uri.clone(),
+            queue_config.name.clone(),
             queue_config.visibility_timeout as i32,
-        ).await);
-        queue_manager.add_consumer(consumer).await;
+        );
+        if let Some(adaptive_visibility) = adaptive_visibility {
+            consumer = consumer.with_adaptive_visibility(adaptive_visibility);
+        }
+        queue_manager.add_consumer(Arc::new(consumer)).await;
 
         // Track first queue URL for publisher
         if first_queue_url.is_none() {
@@ -226,17 +354,44 @@ async fn main() -> Result<()> {
 
     // Create a simple publisher that publishes to the first queue
     let publisher_queue_url = first_queue_url.expect("At least one queue must be configured");
-    let publisher = Arc::new(SqsPublisher::new(sqs_client, publisher_queue_url));
+    let publisher = Arc::new(SqsPublisher::new(sqs_client, publisher_queue_url).with_body_format(body_format));
 
-    // Create circuit breaker registry for endpoint tracking
-    let circuit_breaker_registry = Arc::new(CircuitBreakerRegistry::default());
+    let max_publish_batch_size = std::env::var("FLOWCATALYST_MAX_PUBLISH_BATCH_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_PUBLISH_BATCH_SIZE);
+
+    let reject_publish_when_saturated = std::env::var("FLOWCATALYST_PUBLISH_ADMISSION_CONTROL")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+
+    let publish_pressure_threshold = load_publish_pressure_threshold()?;
+
+    let max_in_flight_page_size = std::env::var("FLOWCATALYST_MAX_IN_FLIGHT_PAGE_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_IN_FLIGHT_PAGE_SIZE);
+
+    let max_message_bytes = std::env::var("FLOWCATALYST_MAX_MESSAGE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_MESSAGE_BYTES);
 
-    let app = create_router(
+    let app = create_router_with_options(
         publisher,
         queue_manager.clone(),
         warning_service.clone(),
         health_service.clone(),
         circuit_breaker_registry,
+        RouterOptions {
+            max_publish_batch_size,
+            reject_publish_when_saturated,
+            publish_pressure_threshold,
+            max_in_flight_page_size,
+            max_message_bytes,
+            body_format,
+            ..RouterOptions::default()
+        },
     )
     .layer(TraceLayer::new_for_http())
     .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any));
@@ -309,6 +464,14 @@ async fn main() -> Result<()> {
     lifecycle.shutdown().await;
     queue_manager.shutdown().await;
 
+    // Flush batched warning notifications so they are not lost on exit
+    if let Some(ns) = notification_scheduler {
+        if let Some(handle) = ns.scheduler_handle {
+            handle.abort();
+        }
+        ns.service.flush().await;
+    }
+
     server_task.abort();
 
     // Wait for manager handle with timeout, then abort if still running
@@ -361,6 +524,66 @@ fn load_standby_config() -> StandbyRouterConfig {
     }
 }
 
+/// Load warning service configuration from environment variables
+/// Pressure above which publishes get 503; a fraction between 0 and 1
+fn load_publish_pressure_threshold() -> Result<Option<f64>> {
+    let Ok(value) = std::env::var("FLOWCATALYST_PUBLISH_PRESSURE_THRESHOLD") else {
+        return Ok(None);
+    };
+    match value.trim().parse::<f64>() {
+        Ok(threshold) if (0.0..=1.0).contains(&threshold) => Ok(Some(threshold)),
+        _ => Err(anyhow::anyhow!(
+            "FLOWCATALYST_PUBLISH_PRESSURE_THRESHOLD must be a number between 0 and 1, got '{}'",
+            value
+        )),
+    }
+}
+
+fn load_warning_config() -> WarningServiceConfig {
+    let defaults = WarningServiceConfig::default();
+
+    let acknowledged_ttl_seconds = std::env::var("FLOWCATALYST_WARNING_ACKNOWLEDGED_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults.acknowledged_ttl_seconds);
+
+    let sink_min_severity = std::env::var("FLOWCATALYST_WARNING_WEBHOOK_MIN_SEVERITY")
+        .map(|s| match s.to_uppercase().as_str() {
+            "INFO" => WarningSeverity::Info,
+            "WARN" | "WARNING" => WarningSeverity::Warn,
+            "ERROR" => WarningSeverity::Error,
+            _ => WarningSeverity::Critical,
+        })
+        .unwrap_or(defaults.sink_min_severity);
+
+    WarningServiceConfig {
+        acknowledged_ttl_seconds,
+        sink_min_severity,
+        ..defaults
+    }
+}
+
+/// Load health service configuration from environment variables
+fn load_health_config() -> HealthServiceConfig {
+    let defaults = HealthServiceConfig::default();
+
+    let consumer_stall_threshold_secs = std::env::var("FLOWCATALYST_CONSUMER_STALL_THRESHOLD_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults.consumer_stall_threshold_secs);
+
+    let consumer_startup_grace_secs = std::env::var("FLOWCATALYST_CONSUMER_STARTUP_GRACE_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults.consumer_startup_grace_secs);
+
+    HealthServiceConfig {
+        consumer_stall_threshold_secs,
+        consumer_startup_grace_secs,
+        ..defaults
+    }
+}
+
 /// Load notification configuration from environment variables
 fn load_notification_config() -> NotificationConfig {
     let teams_enabled = std::env::var("NOTIFICATION_TEAMS_ENABLED")
@@ -392,6 +615,42 @@ fn load_notification_config() -> NotificationConfig {
     }
 }
 
+/// Create the warning repository selected by `FLOWCATALYST_WARNINGS_DB_TYPE`
+async fn create_warning_repository(db_type: &str) -> Result<Arc<dyn WarningRepository>> {
+    let url = std::env::var("FLOWCATALYST_WARNINGS_DB_URL")
+        .map_err(|_| anyhow::anyhow!("FLOWCATALYST_WARNINGS_DB_URL is required when FLOWCATALYST_WARNINGS_DB_TYPE is set"))?;
+
+    let repository: Arc<dyn WarningRepository> = match db_type {
+        "sqlite" => {
+            let pool = SqlitePoolOptions::new()
+                .max_connections(2)
+                .connect(&url)
+                .await?;
+            Arc::new(SqliteWarningRepository::new(pool))
+        }
+        "postgres" => {
+            let pool = PgPoolOptions::new()
+                .max_connections(2)
+                .connect(&url)
+                .await?;
+            Arc::new(PostgresWarningRepository::new(pool))
+        }
+        "mongo" => {
+            let db_name = std::env::var("FLOWCATALYST_WARNINGS_MONGO_DB")
+                .unwrap_or_else(|_| "flowcatalyst".to_string());
+            let client = mongodb::Client::with_uri_str(&url).await?;
+            Arc::new(MongoWarningRepository::new(client, &db_name))
+        }
+        other => {
+            return Err(anyhow::anyhow!("Unknown warnings database type: {}. Use sqlite, postgres, or mongo", other));
+        }
+    };
+
+    repository.init_schema().await?;
+    info!(db_type = %db_type, "Persisting warnings to database");
+    Ok(repository)
+}
+
 /// Load config sync configuration from environment variables
 fn load_config_sync_config(config_url: &str) -> ConfigSyncConfig {
     let interval_secs = std::env::var("FLOWCATALYST_CONFIG_INTERVAL")
@@ -420,19 +679,28 @@ fn create_dev_config() -> RouterConfig {
     RouterConfig {
         processing_pools: vec![
             PoolConfig {
-                code: "DEFAULT".to_string(),
+                code: DEFAULT_POOL_CODE.to_string(),
                 concurrency: 10,
                 rate_limit_per_minute: None,
+                rate_limit_scope: RateLimitScope::Pool,
+                rate_limit_burst: None,
+                ack_on_4xx: true,
             },
             PoolConfig {
                 code: "HIGH".to_string(),
                 concurrency: 20,
                 rate_limit_per_minute: None,
+                rate_limit_scope: RateLimitScope::Pool,
+                rate_limit_burst: None,
+                ack_on_4xx: true,
             },
             PoolConfig {
                 code: "LOW".to_string(),
                 concurrency: 5,
                 rate_limit_per_minute: Some(60),
+                rate_limit_scope: RateLimitScope::Pool,
+                rate_limit_burst: None,
+                ack_on_4xx: true,
             },
         ],
         queues: vec![
@@ -441,18 +709,24 @@ fn create_dev_config() -> RouterConfig {
                 uri: format!("{}/000000000000/fc-high-priority.fifo", sqs_host),
                 connections: 2,
                 visibility_timeout: 120,
+                max_delivery_attempts: None,
+                poll_batch_size: None,
             },
             QueueConfig {
                 name: "fc-default.fifo".to_string(),
                 uri: format!("{}/000000000000/fc-default.fifo", sqs_host),
                 connections: 2,
                 visibility_timeout: 120,
+                max_delivery_attempts: None,
+                poll_batch_size: None,
             },
             QueueConfig {
                 name: "fc-low-priority.fifo".to_string(),
                 uri: format!("{}/000000000000/fc-low-priority.fifo", sqs_host),
                 connections: 1,
                 visibility_timeout: 120,
+                max_delivery_attempts: None,
+                poll_batch_size: None,
             },
         ],
     }
@@ -509,17 +783,29 @@ async fn shutdown_signal() {
 
 // Simple SQS publisher implementation
 use async_trait::async_trait;
-use fc_queue::{QueuePublisher, QueueError};
+use fc_queue::{BatchPublishResult, BodyFormat, QueuePublisher, QueueError};
+use fc_queue::codec::encode_body;
+use fc_queue::sqs::{record_send_batch_output, SqsQueueType, SqsSendAttributes, SEND_BATCH_MAX_ENTRIES};
+use aws_sdk_sqs::types::SendMessageBatchRequestEntry;
 use fc_common::Message;
+use fc_router::{DeadLetter, DeadLetterSink};
 
 struct SqsPublisher {
     client: aws_sdk_sqs::Client,
     queue_url: String,
+    queue_type: SqsQueueType,
+    body_format: BodyFormat,
 }
 
 impl SqsPublisher {
     fn new(client: aws_sdk_sqs::Client, queue_url: String) -> Self {
-        Self { client, queue_url }
+        let queue_type = SqsQueueType::from_queue_url(&queue_url);
+        Self { client, queue_url, queue_type, body_format: BodyFormat::default() }
+    }
+
+    fn with_body_format(mut self, body_format: BodyFormat) -> Self {
+        self.body_format = body_format;
+        self
     }
 }
 
@@ -531,34 +817,110 @@ impl QueuePublisher for SqsPublisher {
 
     async fn publish(&self, message: Message) -> fc_queue::Result<String> {
         let message_id = message.id.clone();
-        let body = serde_json::to_string(&message)?;
+        let body = encode_body(&message, self.body_format)?;
 
-        let mut request = self.client.send_message()
-            .queue_url(&self.queue_url)
-            .message_body(body);
-
-        // FIFO queues require message_group_id and message_deduplication_id
-        if self.queue_url.ends_with(".fifo") {
-            let group_id = message.message_group_id.clone()
-                .unwrap_or_else(|| "default".to_string());
-            request = request
-                .message_group_id(group_id)
-                .message_deduplication_id(&message_id);
-        }
+        // FIFO queues require message_group_id and message_deduplication_id,
+        // standard queues reject them
+        let attrs = SqsSendAttributes::for_message(self.queue_type, &message);
 
-        request.send()
+        self.client.send_message()
+            .queue_url(&self.queue_url)
+            .message_body(body)
+            .set_message_group_id(attrs.message_group_id)
+            .set_message_deduplication_id(attrs.message_deduplication_id)
+            .send()
             .await
             .map_err(|e| QueueError::Sqs(e.to_string()))?;
 
         Ok(message_id)
     }
 
-    async fn publish_batch(&self, messages: Vec<Message>) -> fc_queue::Result<Vec<String>> {
-        let mut ids = Vec::with_capacity(messages.len());
-        for message in messages {
-            let id = self.publish(message).await?;
-            ids.push(id);
+    /// Sends in `SendMessageBatch` chunks of 10. Entries SQS rejects, and every
+    /// entry of a chunk whose request fails, are reported as failures.
+    async fn publish_batch(&self, messages: Vec<Message>) -> fc_queue::Result<BatchPublishResult> {
+        // Encode up front so a bad message fails the batch before anything is sent
+        let bodies = messages.iter()
+            .map(|message| encode_body(message, self.body_format))
+            .collect::<fc_queue::Result<Vec<_>>>()?;
+
+        let mut result = BatchPublishResult::default();
+        for (chunk_index, (chunk, chunk_bodies)) in messages.chunks(SEND_BATCH_MAX_ENTRIES)
+            .zip(bodies.chunks(SEND_BATCH_MAX_ENTRIES))
+            .enumerate()
+        {
+            let offset = chunk_index * SEND_BATCH_MAX_ENTRIES;
+            let mut entries = Vec::with_capacity(chunk.len());
+            for (index, (message, body)) in chunk.iter().zip(chunk_bodies).enumerate() {
+                let attrs = SqsSendAttributes::for_message(self.queue_type, message);
+                let entry = SendMessageBatchRequestEntry::builder()
+                    .id(index.to_string())
+                    .message_body(body)
+                    .set_message_group_id(attrs.message_group_id)
+                    .set_message_deduplication_id(attrs.message_deduplication_id)
+                    .build()
+                    .map_err(|e| QueueError::Sqs(e.to_string()))?;
+                entries.push(entry);
+            }
+
+            let output = match self.client.send_message_batch()
+                .queue_url(&self.queue_url)
+                .set_entries(Some(entries))
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(e) => {
+                    error!(error = %e, count = chunk.len(), "Failed to send message batch");
+                    for index in offset..offset + chunk.len() {
+                        result.fail(index, e.to_string());
+                    }
+                    continue;
+                }
+            };
+
+            for failed in output.failed() {
+                warn!(
+                    entry_id = %failed.id(),
+                    code = %failed.code(),
+                    message = ?failed.message(),
+                    "SQS rejected batch entry"
+                );
+            }
+            record_send_batch_output(&mut result, offset, chunk, &output);
         }
-        Ok(ids)
+        Ok(result)
+    }
+}
+
+/// Dead-letter sink sending failed messages, with their failure reason, to an SQS queue
+struct SqsDeadLetterSink {
+    client: aws_sdk_sqs::Client,
+    queue_url: String,
+    queue_type: SqsQueueType,
+}
+
+impl SqsDeadLetterSink {
+    fn new(client: aws_sdk_sqs::Client, queue_url: String) -> Self {
+        let queue_type = SqsQueueType::from_queue_url(&queue_url);
+        Self { client, queue_url, queue_type }
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for SqsDeadLetterSink {
+    async fn send(&self, dead_letter: DeadLetter) -> fc_router::Result<()> {
+        let attrs = SqsSendAttributes::for_message(self.queue_type, &dead_letter.message);
+        let body = serde_json::to_string(&dead_letter)?;
+
+        self.client.send_message()
+            .queue_url(&self.queue_url)
+            .message_body(body)
+            .set_message_group_id(attrs.message_group_id)
+            .set_message_deduplication_id(attrs.message_deduplication_id)
+            .send()
+            .await
+            .map_err(|e| fc_router::RouterError::Queue(e.to_string()))?;
+
+        Ok(())
     }
 }
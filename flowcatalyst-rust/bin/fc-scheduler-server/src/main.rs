@@ -30,7 +30,7 @@ struct HealthResponse {
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    fc_common::logging::init_logging("fc-scheduler-server");
+    let _logging = fc_common::logging::init_logging("fc-scheduler-server");
 
     info!("Starting FlowCatalyst Dispatch Scheduler");
 
@@ -44,7 +44,7 @@ fn env_or_parse<T: std::str::FromStr>(key: &str, default: T) -> T {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    fc_common::logging::init_logging("fc-stream-processor");
+    let _logging = fc_common::logging::init_logging("fc-stream-processor");
 
     info!("Starting FlowCatalyst Stream Processor");
 
@@ -199,6 +199,11 @@ async fn process_event(
             }
         }
 
+        // Check the subscription's payload filter
+        if !sub.matches_payload(&event.data) {
+            continue;
+        }
+
         // Serialize event data as payload
         let payload = serde_json::to_string(&event.data).unwrap_or_default();
 
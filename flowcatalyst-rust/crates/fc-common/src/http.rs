@@ -0,0 +1,250 @@
+//! Shared HTTP Client
+//!
+//! Builds a configured `reqwest::Client` for outbound calls (mediation, OIDC).
+//! Build one client at startup and inject clones where needed: clones share
+//! the same connection pool, so callers don't each open their own.
+//!
+//! # Proxy
+//!
+//! Egress can be routed through an HTTP or SOCKS5 proxy, either set explicitly
+//! on [`ProxyConfig`] or read from the environment with [`ProxyConfig::from_env`]:
+//!
+//! - `HTTPS_PROXY` / `https_proxy`: proxy URL (falls back to `HTTP_PROXY` / `http_proxy`)
+//! - `NO_PROXY` / `no_proxy`: comma-separated hosts, `.domain` suffixes or CIDRs to reach directly
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use fc_common::http::HttpClientConfig;
+//!
+//! let client = HttpClientConfig::default().build_client()?;
+//! let oidc_service = OidcService::new().with_http_client(client.clone());
+//! ```
+
+use std::time::Duration;
+
+/// Outbound proxy settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// Proxy URL: `http://`, `https://`, `socks5://` or `socks5h://`
+    pub url: Option<String>,
+    /// Comma-separated hosts, `.domain` suffixes or CIDRs that bypass the proxy
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Read proxy settings from `HTTPS_PROXY`/`HTTP_PROXY` and `NO_PROXY`
+    pub fn from_env() -> Self {
+        let url = env_any(&["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]);
+        let no_proxy = env_any(&["NO_PROXY", "no_proxy"]);
+        Self { url, no_proxy }
+    }
+
+    fn build(&self) -> reqwest::Result<Option<reqwest::Proxy>> {
+        let Some(ref url) = self.url else {
+            return Ok(None);
+        };
+        let no_proxy = self.no_proxy.as_deref().and_then(reqwest::NoProxy::from_string);
+        Ok(Some(reqwest::Proxy::all(url)?.no_proxy(no_proxy)))
+    }
+}
+
+fn env_any(keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Configuration for the shared outbound HTTP client
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// Total request timeout
+    pub timeout: Duration,
+    /// TCP/TLS connect timeout
+    pub connect_timeout: Duration,
+    /// How long idle pooled connections are kept
+    pub pool_idle_timeout: Duration,
+    /// Max idle pooled connections per host
+    pub pool_max_idle_per_host: usize,
+    /// Force HTTP/1.1 (otherwise HTTP/2 is negotiated via ALPN for HTTPS)
+    pub http1_only: bool,
+    /// Outbound proxy. When unset, reqwest's default system proxy detection applies.
+    pub proxy: ProxyConfig,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            pool_idle_timeout: Duration::from_secs(90),
+            pool_max_idle_per_host: 10,
+            http1_only: false,
+            proxy: ProxyConfig::default(),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_proxy_url(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy.url = Some(proxy_url.into());
+        self
+    }
+
+    pub fn with_no_proxy(mut self, no_proxy: impl Into<String>) -> Self {
+        self.proxy.no_proxy = Some(no_proxy.into());
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Build a client from this configuration
+    pub fn build_client(&self) -> reqwest::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .pool_max_idle_per_host(self.pool_max_idle_per_host);
+
+        if self.http1_only {
+            builder = builder.http1_only();
+        }
+
+        if let Some(proxy) = self.proxy.build()? {
+            builder = builder.proxy(proxy);
+        }
+
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// What a stub server has seen: peer address per connection and request lines
+    #[derive(Clone, Default)]
+    struct Seen {
+        peers: Arc<Mutex<HashSet<SocketAddr>>>,
+        request_lines: Arc<Mutex<Vec<String>>>,
+    }
+
+    /// Minimal HTTP/1.1 server that answers every request on a connection with
+    /// `body` after `delay`. Also serves as a forward-proxy stub, since proxied
+    /// plain-HTTP requests arrive as ordinary requests with an absolute URI.
+    async fn start_server(delay: Duration, body: &'static str) -> (String, Seen) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let seen = Seen::default();
+        let seen_clone = seen.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, peer) = listener.accept().await.unwrap();
+                seen_clone.peers.lock().unwrap().insert(peer);
+                let seen = seen_clone.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    while let Ok(n) = socket.read(&mut buf).await {
+                        if n == 0 {
+                            break;
+                        }
+                        let request = String::from_utf8_lossy(&buf[..n]);
+                        if let Some(line) = request.lines().next() {
+                            seen.request_lines.lock().unwrap().push(line.to_string());
+                        }
+                        tokio::time::sleep(delay).await;
+                        let response = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}", body.len(), body);
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        (format!("http://{}", addr), seen)
+    }
+
+    #[tokio::test]
+    async fn test_cloned_client_shares_connection_pool() {
+        let (url, seen) = start_server(Duration::ZERO, "ok").await;
+        let client = HttpClientConfig::default().build_client().unwrap();
+
+        // Two "components" holding clones of the same injected client
+        let mediation = client.clone();
+        let oidc = client;
+
+        mediation.get(&url).send().await.unwrap().text().await.unwrap();
+        oidc.get(&url).send().await.unwrap().text().await.unwrap();
+
+        assert_eq!(seen.peers.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_configured_timeout_honored() {
+        let (url, _seen) = start_server(Duration::from_secs(2), "ok").await;
+        let client = HttpClientConfig::default()
+            .with_timeout(Duration::from_millis(100))
+            .build_client()
+            .unwrap();
+
+        let err = client.get(&url).send().await.unwrap_err();
+        assert!(err.is_timeout());
+    }
+
+    #[test]
+    fn test_invalid_proxy_url_rejected() {
+        let config = HttpClientConfig::default().with_proxy_url("not a url");
+        assert!(config.build_client().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requests_route_through_proxy() {
+        let (proxy_url, proxy_seen) = start_server(Duration::ZERO, "proxied").await;
+        let client = HttpClientConfig::default()
+            .with_proxy_url(proxy_url)
+            .build_client()
+            .unwrap();
+
+        let body = client.get("http://upstream.invalid/webhook").send().await.unwrap()
+            .text().await.unwrap();
+
+        assert_eq!(body, "proxied");
+        assert_eq!(
+            *proxy_seen.request_lines.lock().unwrap(),
+            vec!["GET http://upstream.invalid/webhook HTTP/1.1".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_proxy_hosts_bypass_proxy() {
+        let (proxy_url, proxy_seen) = start_server(Duration::ZERO, "proxied").await;
+        let (direct_url, direct_seen) = start_server(Duration::ZERO, "direct").await;
+        let client = HttpClientConfig::default()
+            .with_proxy_url(proxy_url)
+            .with_no_proxy("localhost,127.0.0.1")
+            .build_client()
+            .unwrap();
+
+        let body = client.get(format!("{}/webhook", direct_url)).send().await.unwrap()
+            .text().await.unwrap();
+
+        assert_eq!(body, "direct");
+        assert_eq!(direct_seen.request_lines.lock().unwrap().len(), 1);
+        assert!(proxy_seen.request_lines.lock().unwrap().is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 use std::time::Instant;
 use utoipa::ToSchema;
 
+pub mod http;
 pub mod logging;
 
 // ============================================================================
@@ -12,7 +13,7 @@ pub mod logging;
 /// The core message structure that flows through the system.
 ///
 /// This struct is compatible with Java's MessagePointer using camelCase field names.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Message {
     pub id: String,
@@ -26,11 +27,47 @@ pub struct Message {
     pub mediation_target: String,
     #[serde(default)]
     pub message_group_id: Option<String>,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+    /// Event payload, used to resolve `{placeholder}`s in `mediation_target` (Rust extension, not in Java)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// Base64-encoded body sent verbatim instead of the JSON envelope (Rust extension, not in Java)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_body_base64: Option<String>,
+    /// Content type for `raw_body_base64`; defaults to `application/octet-stream`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Owning client (tenant), used as a cardinality-capped metrics label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Per-message HTTP request timeout, overriding the mediator's configured timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u32>,
+    /// Per-message delivery attempts, overriding the mediator's configured `max_retries`.
+    /// Like the config value this counts total attempts, so `0` and `1` both mean a
+    /// single attempt. A transient failure (`ErrorProcess`/`ErrorConnection`) on the
+    /// last attempt is still NACKed back to the queue for redelivery; `0` only turns
+    /// off the mediator's in-process retries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    /// Correlation ID for tracing the message from publish to delivery; added to
+    /// log spans and forwarded as `X-Correlation-Id` (Rust extension, not in Java)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// W3C `traceparent` of the publishing span, so routing and mediation spans
+    /// join the publisher's trace. Set only when OpenTelemetry export is active
+    /// (Rust extension, not in Java)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 pub enum MediationType {
+    #[default]
     HTTP,
+    /// Internal gRPC service call
+    GRPC,
+    /// Fan-out to an SQS queue
+    SQS,
 }
 
 /// A message that has been received from a queue with tracking metadata
@@ -57,7 +94,13 @@ pub struct BatchMessage {
 #[derive(Debug, Clone)]
 pub enum AckNack {
     Ack,
+    /// Return the message for redelivery without it counting as a failed delivery
     Nack { delay_seconds: Option<u32> },
+    /// The target failed to process the message (`ErrorProcess`). NACKed like `Nack`,
+    /// but counted toward the queue's `max_delivery_attempts`.
+    Failed { delay_seconds: Option<u32> },
+    /// Hand the message back unattempted (back-pressure); not a failure
+    Defer { delay_seconds: Option<u32> },
     ExtendVisibility { seconds: u32 },
 }
 
@@ -113,19 +156,83 @@ impl InFlightMessage {
 // Configuration Types
 // ============================================================================
 
+/// Pool for messages published or received without a pool code (matches Java: DEFAULT_POOL_CODE).
+/// Shared by the publish API, the router and the default configs so they agree.
+pub const DEFAULT_POOL_CODE: &str = "DEFAULT-POOL";
+
+/// Pool code the publish API and outbox used to fill in before [`DEFAULT_POOL_CODE`].
+/// Messages still queued with it go to the default pool unless a pool with this code exists.
+pub const LEGACY_DEFAULT_POOL_CODE: &str = "DEFAULT";
+
+/// Messages requested per poll when a queue doesn't set `poll_batch_size`.
+/// Also the most a single SQS receive call returns.
+pub const DEFAULT_POLL_BATCH_SIZE: u32 = 10;
+
+/// Concurrency of a pool created without a config (matches Java: DEFAULT_POOL_CONCURRENCY).
+pub const DEFAULT_POOL_CONCURRENCY: u32 = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PoolConfig {
     pub code: String,
     pub concurrency: u32,
     pub rate_limit_per_minute: Option<u32>,
+    #[serde(default)]
+    pub rate_limit_scope: RateLimitScope,
+    /// Permits that can be used at once before `rate_limit_per_minute` pacing
+    /// applies. Defaults to the per-minute rate, i.e. a full minute's budget.
+    #[serde(default)]
+    pub rate_limit_burst: Option<u32>,
+    /// ACK messages whose endpoint answers with a 4xx (other than 429) instead
+    /// of retrying them. Disable for endpoints that return 4xx transiently,
+    /// e.g. during deploys, to NACK those with a backoff.
+    #[serde(default = "default_ack_on_4xx")]
+    pub ack_on_4xx: bool,
+}
+
+fn default_ack_on_4xx() -> bool {
+    true
+}
+
+impl Default for PoolConfig {
+    /// The default pool: [`DEFAULT_POOL_CODE`] at [`DEFAULT_POOL_CONCURRENCY`], unlimited
+    fn default() -> Self {
+        Self {
+            code: DEFAULT_POOL_CODE.to_string(),
+            concurrency: DEFAULT_POOL_CONCURRENCY,
+            rate_limit_per_minute: None,
+            rate_limit_scope: RateLimitScope::Pool,
+            rate_limit_burst: None,
+            ack_on_4xx: default_ack_on_4xx(),
+        }
+    }
+}
+
+/// What `rate_limit_per_minute` applies to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RateLimitScope {
+    /// One limit shared by every message in the pool
+    #[default]
+    Pool,
+    /// Each message group gets its own limit, so a hot group can't
+    /// consume the whole pool's budget
+    Group,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueConfig {
     pub name: String,
     pub uri: String,
+    /// Poll tasks run in parallel against the queue (at least one)
     pub connections: u32,
     pub visibility_timeout: u32,
+    /// Failed deliveries after which a message is moved to the dead-letter queue.
+    /// `None` retries forever.
+    #[serde(default)]
+    pub max_delivery_attempts: Option<u32>,
+    /// Messages requested by each poll. `None` uses [`DEFAULT_POLL_BATCH_SIZE`].
+    #[serde(default)]
+    pub poll_batch_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -174,6 +281,9 @@ pub struct StallConfig {
     pub force_nack_after_seconds: u64,
     /// Delay in seconds when NACKing stalled messages
     pub nack_delay_seconds: u32,
+    /// Age in seconds after which in-flight messages are flagged `stuck` in monitoring
+    /// Applies even when stall detection is disabled
+    pub stuck_threshold_seconds: u64,
 }
 
 impl Default for StallConfig {
@@ -184,6 +294,7 @@ impl Default for StallConfig {
             force_nack_stalled: false,
             force_nack_after_seconds: 600, // 10 minutes
             nack_delay_seconds: 30,
+            stuck_threshold_seconds: 300, // 5 minutes
         }
     }
 }
@@ -214,6 +325,9 @@ pub enum MediationResult {
     ErrorProcess,
     /// Connection error - NACK for retry
     ErrorConnection,
+    /// Not attempted because of back-pressure (e.g. a saturated endpoint) - defer
+    /// without counting a failure
+    Deferred,
 }
 
 /// Outcome of mediation including result and optional delay
@@ -261,6 +375,15 @@ impl MediationOutcome {
             error_message: Some(message),
         }
     }
+
+    pub fn deferred(delay_seconds: u32, message: String) -> Self {
+        Self {
+            result: MediationResult::Deferred,
+            delay_seconds: Some(delay_seconds),
+            status_code: None,
+            error_message: Some(message),
+        }
+    }
 }
 
 // ============================================================================
@@ -430,6 +553,9 @@ pub struct OutboxItem {
     pub updated_at: Option<DateTime<Utc>>,
     /// Error message from last failure (optional)
     pub error_message: Option<String>,
+    /// Earliest time a retried item may be published again (None = immediately)
+    #[serde(default)]
+    pub next_attempt_at: Option<DateTime<Utc>>,
     // Extended fields for FlowCatalyst routing (not in Java base schema)
     /// Pool code for routing (optional)
     pub pool_code: Option<String>,
@@ -555,7 +681,19 @@ pub struct PoolStats {
     pub queue_capacity: u32,
     pub message_group_count: u32,
     pub rate_limit_per_minute: Option<u32>,
+    #[serde(default)]
+    pub rate_limit_scope: RateLimitScope,
+    #[serde(default)]
+    pub rate_limit_burst: Option<u32>,
+    #[serde(default = "default_ack_on_4xx")]
+    pub ack_on_4xx: bool,
     pub is_rate_limited: bool,
+    /// Pool has been removed and is finishing in-flight work before cleanup
+    #[serde(default)]
+    pub is_draining: bool,
+    /// Message group workers restarted after panicking or dying
+    #[serde(default)]
+    pub group_thread_restarts: u64,
     /// Enhanced metrics (optional, available when metrics collection is enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<EnhancedPoolMetrics>,
@@ -565,11 +703,13 @@ pub struct PoolStats {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EnhancedPoolMetrics {
-    /// Total messages processed successfully (all time)
+    /// Total messages processed successfully since the pool was created.
+    /// Kept across concurrency/rate-limit updates; reset if the pool is
+    /// removed and re-created.
     pub total_success: u64,
-    /// Total messages failed (all time)
+    /// Total messages failed since the pool was created (same lifetime as `total_success`)
     pub total_failure: u64,
-    /// Total messages rate limited (all time)
+    /// Total messages rate limited since the pool was created (same lifetime as `total_success`)
     pub total_rate_limited: u64,
     /// Success rate (0.0 - 1.0)
     pub success_rate: f64,
@@ -652,13 +792,23 @@ impl Default for WindowedMetrics {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ConsumerHealth {
     pub queue_identifier: String,
     pub is_healthy: bool,
     pub last_poll_time_ms: Option<i64>,
     pub time_since_last_poll_ms: Option<i64>,
     pub is_running: bool,
+    /// Running but not polled yet, within the startup grace period
+    #[serde(default)]
+    pub is_starting: bool,
+    /// Most recent poll error. Kept after later successful polls; compare
+    /// `last_error_at` with the last poll to tell whether it is current.
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// When `last_error` happened
+    #[serde(default)]
+    pub last_error_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,7 +1,7 @@
 //! Structured Logging Configuration
 //!
 //! Provides configurable logging with:
-//! - JSON output for production (LOG_FORMAT=json)
+//! - JSON output for production (FC_LOG_FORMAT=json)
 //! - Human-readable output for development (default)
 //! - Context fields via spans (request_id, correlation_id, etc.)
 //!
@@ -11,7 +11,8 @@
 //! use fc_common::logging::init_logging;
 //!
 //! fn main() {
-//!     init_logging("my-service");
+//!     // Keep the guard until exit so buffered spans are flushed
+//!     let _logging = init_logging("my-service");
 //!
 //!     // Use tracing macros with structured fields
 //!     tracing::info!(user_id = %id, "User logged in");
@@ -20,9 +21,26 @@
 //!
 //! # Environment Variables
 //!
-//! - `LOG_FORMAT`: Set to "json" for JSON output, anything else for text (default: text)
+//! - `FC_LOG_FORMAT`: `json` for JSON output, `text` for human-readable output (default: text).
+//!   `LOG_FORMAT` is still read when `FC_LOG_FORMAT` is unset.
 //! - `RUST_LOG`: Standard log level filter (default: info)
 //!   Examples: `RUST_LOG=debug`, `RUST_LOG=fc_router=trace,tower_http=info`
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: OTLP/gRPC collector endpoint. Only read when
+//!   built with the `otel` feature; spans are exported when it is set.
+//!
+//! # OpenTelemetry
+//!
+//! With the `otel` feature, an extra `tracing-opentelemetry` layer turns
+//! tracing spans into OpenTelemetry spans and exports them in batches, tagged
+//! with `service.name` set to the service name passed to [`init_logging`].
+//! Spans passing the `RUST_LOG` filter are exported, so parent/child structure
+//! follows `Instrument`/`in_scope` as it does for log output. Spans still
+//! buffered are flushed when the [`LoggingGuard`] returned by [`init_logging`]
+//! is dropped, so hold it for the life of the process.
+//!
+//! Spans don't cross the queue on their own. Publishers store [`traceparent`]
+//! on the message and consumers pass it to [`set_remote_parent`], so the
+//! consumer's spans join the publisher's trace.
 //!
 //! # Adding Context to Requests
 //!
@@ -47,37 +65,105 @@
 //! }
 //! ```
 
+use tracing::{Span, Subscriber};
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
-    layer::SubscriberExt,
+    layer::{Layered, SubscriberExt},
+    registry::LookupSpan,
     util::SubscriberInitExt,
-    EnvFilter,
+    EnvFilter, Layer, Registry,
 };
 
+/// Log output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per event, with event and span fields as top-level keys
+    Json,
+    /// Human-readable text
+    Text,
+}
+
+impl LogFormat {
+    /// Parse a format name; anything other than "json" is text
+    pub fn parse(value: &str) -> Self {
+        if value.trim().eq_ignore_ascii_case("json") {
+            Self::Json
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Read the format from `FC_LOG_FORMAT`, falling back to `LOG_FORMAT`
+    pub fn from_env() -> Self {
+        std::env::var("FC_LOG_FORMAT")
+            .or_else(|_| std::env::var("LOG_FORMAT"))
+            .map(|v| Self::parse(&v))
+            .unwrap_or(Self::Text)
+    }
+}
+
 /// Initialize logging with the given service name.
 ///
-/// Reads LOG_FORMAT env var to determine output format:
+/// Reads FC_LOG_FORMAT (or LOG_FORMAT) env var to determine output format:
 /// - "json" -> JSON output (for production/log aggregation)
 /// - anything else -> human-readable text (for development)
 ///
 /// Reads RUST_LOG env var for log level filtering (defaults to INFO).
-pub fn init_logging(_service_name: &str) {
-    let log_format = std::env::var("LOG_FORMAT").unwrap_or_default();
-
+///
+/// With the `otel` feature, also exports spans to `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// when that variable is set. If the exporter can't be built, logging still
+/// starts and the failure is logged as an error.
+///
+/// Returns a guard that flushes buffered spans when dropped.
+pub fn init_logging(service_name: &str) -> LoggingGuard {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info"));
 
-    if log_format.eq_ignore_ascii_case("json") {
-        init_json_logging(env_filter);
-    } else {
-        init_text_logging(env_filter);
+    let (otel, otel_error) = match otel_layer::<Layered<EnvFilter, Registry>>(service_name) {
+        Ok(layer) => (layer, None),
+        Err(e) => (None, Some(e)),
+    };
+
+    match LogFormat::from_env() {
+        LogFormat::Json => init_json_logging(env_filter, otel),
+        LogFormat::Text => init_text_logging(env_filter, otel),
+    }
+
+    if let Some(e) = otel_error {
+        tracing::error!(error = %e, "Failed to start OTLP span export, spans will not be exported");
+    }
+
+    LoggingGuard { _private: () }
+}
+
+/// Flushes logging on drop. Returned by [`init_logging`]; keep it alive in
+/// `main` so spans buffered for export aren't lost on shutdown, including
+/// when `main` returns early with an error.
+#[must_use = "logging is flushed when the guard is dropped"]
+pub struct LoggingGuard {
+    _private: (),
+}
+
+impl Drop for LoggingGuard {
+    fn drop(&mut self) {
+        shutdown_logging();
     }
 }
 
+/// Flush and shut down span export. A no-op unless OpenTelemetry export is active.
+pub fn shutdown_logging() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
 /// Initialize JSON logging for production.
-fn init_json_logging(env_filter: EnvFilter) {
+fn init_json_logging<L>(env_filter: EnvFilter, otel: Option<L>)
+where
+    L: Layer<Layered<EnvFilter, Registry>> + Send + Sync + 'static,
+{
     tracing_subscriber::registry()
         .with(env_filter)
+        .with(otel)
         .with(
             fmt::layer()
                 .json()
@@ -94,9 +180,13 @@ fn init_json_logging(env_filter: EnvFilter) {
 }
 
 /// Initialize human-readable text logging for development.
-fn init_text_logging(env_filter: EnvFilter) {
+fn init_text_logging<L>(env_filter: EnvFilter, otel: Option<L>)
+where
+    L: Layer<Layered<EnvFilter, Registry>> + Send + Sync + 'static,
+{
     tracing_subscriber::registry()
         .with(env_filter)
+        .with(otel)
         .with(
             fmt::layer()
                 .with_target(true)
@@ -108,9 +198,108 @@ fn init_text_logging(env_filter: EnvFilter) {
         .init();
 }
 
+/// Error building the OTLP span exporter
+type OtelError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Build the OTLP export layer if `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+///
+/// Runs before the subscriber exists, so errors are returned for
+/// [`init_logging`] to log once it has installed one.
+#[cfg(feature = "otel")]
+fn otel_layer<S>(service_name: &str) -> Result<Option<impl Layer<S>>, OtelError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace::TracerProvider, Resource};
+
+    let Some(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint.clone())
+        .build()
+        .map_err(|e| format!("failed to create OTLP span exporter for {}: {}", endpoint, e))?;
+
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            service_name.to_string(),
+        )]))
+        .build();
+    let tracer = provider.tracer(service_name.to_string());
+    opentelemetry::global::set_tracer_provider(provider);
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+#[cfg(not(feature = "otel"))]
+fn otel_layer<S>(_service_name: &str) -> Result<Option<impl Layer<S>>, OtelError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    Ok(None::<tracing_subscriber::layer::Identity>)
+}
+
+/// W3C `traceparent` for `span`, to carry its trace across the queue in
+/// [`Message::traceparent`](crate::Message::traceparent).
+///
+/// `None` unless OpenTelemetry export is active and `span` is being exported.
+pub fn traceparent(span: &Span) -> Option<String> {
+    #[cfg(feature = "otel")]
+    {
+        use std::collections::HashMap;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let mut carrier: HashMap<String, String> = HashMap::new();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut carrier)
+        });
+        carrier.remove("traceparent")
+    }
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = span;
+        None
+    }
+}
+
+/// Make `span` a child of the remote span identified by a W3C `traceparent`,
+/// so consumer-side spans join the publisher's trace. Invalid values are
+/// ignored, leaving `span` where it is.
+pub fn set_remote_parent(span: &Span, traceparent: &str) {
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TraceContextExt;
+        use std::collections::HashMap;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let carrier = HashMap::from([("traceparent".to_string(), traceparent.to_string())]);
+        let context = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&carrier)
+        });
+        if context.span().span_context().is_valid() {
+            span.set_parent(context);
+        }
+    }
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = (span, traceparent);
+    }
+}
+
 /// Initialize logging with defaults (uses "flowcatalyst" as service name).
-pub fn init_default_logging() {
-    init_logging("flowcatalyst");
+pub fn init_default_logging() -> LoggingGuard {
+    init_logging("flowcatalyst")
 }
 
 #[cfg(test)]
@@ -124,4 +313,12 @@ mod tests {
             .unwrap_or_else(|_| EnvFilter::new("info"));
         drop(filter);
     }
+
+    #[test]
+    fn test_log_format_parsing() {
+        assert_eq!(LogFormat::parse("json"), LogFormat::Json);
+        assert_eq!(LogFormat::parse(" JSON "), LogFormat::Json);
+        assert_eq!(LogFormat::parse("text"), LogFormat::Text);
+        assert_eq!(LogFormat::parse(""), LogFormat::Text);
+    }
 }
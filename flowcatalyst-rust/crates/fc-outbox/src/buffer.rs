@@ -86,9 +86,9 @@ impl GlobalBuffer {
         let mut buffer = self.buffer.lock().await;
         if buffer.len() >= self.config.max_size {
             warn!(
-                "Global buffer full (capacity: {}), message {} rejected - will be recovered from PROCESSING state",
-                self.config.max_size,
-                message.id
+                message_id = %message.id,
+                capacity = self.config.max_size,
+                "Global buffer full, message rejected - will be recovered from PROCESSING state"
             );
             return Err(BufferFullError {
                 message_id: message.id,
@@ -143,11 +143,9 @@ mod tests {
         Message {
             id: id.to_string(),
             pool_code: "default".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://localhost".to_string(),
-            message_group_id: None,
+            ..Default::default()
         }
     }
 
@@ -11,8 +11,7 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, AtomicBool, Ordering};
 use std::time::Duration;
-use tokio::sync::RwLock;
-use fc_common::{Message, MediationType, OutboxStatus};
+use fc_common::{Message, MediationType, OutboxStatus, DEFAULT_POOL_CODE};
 use tracing::{info, debug, warn, error};
 
 use crate::repository::OutboxRepository;
@@ -70,18 +69,73 @@ impl Default for EnhancedProcessorConfig {
     }
 }
 
-/// Processor metrics
-#[derive(Debug, Clone, Default)]
+/// Processor counters, updated as items are processed and shared with the
+/// metrics endpoint
+#[derive(Debug, Default)]
 pub struct ProcessorMetrics {
-    pub items_polled: u64,
-    pub items_processed: u64,
-    pub items_succeeded: u64,
-    pub items_failed: u64,
-    pub items_recovered: u64,
-    pub current_in_flight: u64,
-    pub buffer_size: usize,
-    pub active_groups: usize,
-    pub blocked_groups: usize,
+    items_fetched: AtomicU64,
+    items_published: AtomicU64,
+    items_failed: AtomicU64,
+    items_retried: AtomicU64,
+    items_recovered: AtomicU64,
+    backlog_size: AtomicU64,
+}
+
+impl ProcessorMetrics {
+    pub fn record_fetched(&self, count: u64) {
+        self.items_fetched.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_published(&self, count: u64) {
+        self.items_published.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_failed(&self, count: u64) {
+        self.items_failed.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_retried(&self, count: u64) {
+        self.items_retried.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_recovered(&self, count: u64) {
+        self.items_recovered.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Record the pending item count sampled at the latest poll
+    pub fn set_backlog_size(&self, size: u64) {
+        self.backlog_size.store(size, Ordering::Relaxed);
+    }
+
+    /// Items fetched from the outbox
+    pub fn items_fetched(&self) -> u64 {
+        self.items_fetched.load(Ordering::Relaxed)
+    }
+
+    /// Items published (or dispatched) successfully
+    pub fn items_published(&self) -> u64 {
+        self.items_published.load(Ordering::Relaxed)
+    }
+
+    /// Items marked failed
+    pub fn items_failed(&self) -> u64 {
+        self.items_failed.load(Ordering::Relaxed)
+    }
+
+    /// Failed publishes scheduled for a retry
+    pub fn items_retried(&self) -> u64 {
+        self.items_retried.load(Ordering::Relaxed)
+    }
+
+    /// Stuck items reset to pending
+    pub fn items_recovered(&self) -> u64 {
+        self.items_recovered.load(Ordering::Relaxed)
+    }
+
+    /// Pending items as of the latest poll
+    pub fn backlog_size(&self) -> u64 {
+        self.backlog_size.load(Ordering::Relaxed)
+    }
 }
 
 /// Enhanced outbox processor with Java-like architecture
@@ -93,7 +147,7 @@ pub struct EnhancedOutboxProcessor {
     in_flight: Arc<AtomicU64>,
     is_primary: Arc<AtomicBool>,
     running: Arc<AtomicBool>,
-    metrics: Arc<RwLock<ProcessorMetrics>>,
+    metrics: Arc<ProcessorMetrics>,
 }
 
 impl EnhancedOutboxProcessor {
@@ -101,6 +155,14 @@ impl EnhancedOutboxProcessor {
         config: EnhancedProcessorConfig,
         repository: Arc<dyn OutboxRepository>,
     ) -> anyhow::Result<Self> {
+        if config.poll_batch_size > repository.max_batch_size() {
+            warn!(
+                poll_batch_size = %config.poll_batch_size,
+                max_batch_size = %repository.max_batch_size(),
+                "Poll batch size exceeds the outbox backend's limit; polls will fetch at most the limit"
+            );
+        }
+
         // Create HTTP dispatcher
         let http_dispatcher = Arc::new(HttpDispatcher::new(config.http_config.clone())?);
 
@@ -135,7 +197,7 @@ impl EnhancedOutboxProcessor {
             in_flight: Arc::new(AtomicU64::new(0)),
             is_primary,
             running: Arc::new(AtomicBool::new(false)),
-            metrics: Arc::new(RwLock::new(ProcessorMetrics::default())),
+            metrics: Arc::new(ProcessorMetrics::default()),
         })
     }
 
@@ -164,17 +226,14 @@ impl EnhancedOutboxProcessor {
         self.in_flight.load(Ordering::SeqCst)
     }
 
-    /// Get current metrics
-    pub async fn metrics(&self) -> ProcessorMetrics {
-        let mut metrics = self.metrics.read().await.clone();
-        metrics.current_in_flight = self.in_flight_count();
-        metrics.buffer_size = self.buffer.len().await;
-
-        let stats = self.distributor.stats().await;
-        metrics.active_groups = stats.active_groups;
-        metrics.blocked_groups = stats.blocked_groups;
+    /// Processor counters, shared so they can be read while the processor runs
+    pub fn metrics(&self) -> Arc<ProcessorMetrics> {
+        Arc::clone(&self.metrics)
+    }
 
-        metrics
+    /// Get current buffer size
+    pub async fn buffer_len(&self) -> usize {
+        self.buffer.len().await
     }
 
     /// Get distributor stats
@@ -227,8 +286,7 @@ impl EnhancedOutboxProcessor {
                                 info!("Recovered {} stuck items", count);
                                 // Decrement in-flight for recovered items
                                 in_flight.fetch_sub(count, Ordering::SeqCst);
-                                let mut m = metrics.write().await;
-                                m.items_recovered += count;
+                                metrics.record_recovered(count);
                             }
                         }
                         Err(e) => {
@@ -262,23 +320,19 @@ impl EnhancedOutboxProcessor {
                             Ok(()) => {
                                 // Update status to completed on success
                                 if let Err(e) = repository.update_status(&msg_id, OutboxStatus::COMPLETED, None).await {
-                                    error!("Failed to update status for {}: {}", msg_id, e);
+                                    error!(message_id = %msg_id, error = %e, "Failed to update outbox item status");
                                 }
                                 in_flight.fetch_sub(1, Ordering::SeqCst);
-                                let mut m = metrics.write().await;
-                                m.items_processed += 1;
-                                m.items_succeeded += 1;
+                                metrics.record_published(1);
                             }
                             Err(e) => {
-                                warn!("Failed to distribute message {}: {}", msg_id, e);
+                                warn!(message_id = %msg_id, error = %e, "Failed to distribute message");
                                 // Update status to failed
                                 if let Err(e2) = repository.update_status(&msg_id, OutboxStatus::FAILED, Some(e)).await {
-                                    error!("Failed to update status for {}: {}", msg_id, e2);
+                                    error!(message_id = %msg_id, error = %e2, "Failed to update outbox item status");
                                 }
                                 in_flight.fetch_sub(1, Ordering::SeqCst);
-                                let mut m = metrics.write().await;
-                                m.items_processed += 1;
-                                m.items_failed += 1;
+                                metrics.record_failed(1);
                             }
                         }
                     }
@@ -400,8 +454,7 @@ impl EnhancedOutboxProcessor {
                             if count > 0 {
                                 info!("Recovered {} stuck items", count);
                                 in_flight.fetch_sub(count, Ordering::SeqCst);
-                                let mut m = metrics.write().await;
-                                m.items_recovered += count;
+                                metrics.record_recovered(count);
                             }
                         }
                         Err(e) => {
@@ -434,22 +487,18 @@ impl EnhancedOutboxProcessor {
                         match distributor.distribute(message).await {
                             Ok(()) => {
                                 if let Err(e) = repository.update_status(&msg_id, OutboxStatus::COMPLETED, None).await {
-                                    error!("Failed to update status for {}: {}", msg_id, e);
+                                    error!(message_id = %msg_id, error = %e, "Failed to update outbox item status");
                                 }
                                 in_flight.fetch_sub(1, Ordering::SeqCst);
-                                let mut m = metrics.write().await;
-                                m.items_processed += 1;
-                                m.items_succeeded += 1;
+                                metrics.record_published(1);
                             }
                             Err(e) => {
-                                warn!("Failed to distribute message {}: {}", msg_id, e);
+                                warn!(message_id = %msg_id, error = %e, "Failed to distribute message");
                                 if let Err(e2) = repository.update_status(&msg_id, OutboxStatus::FAILED, Some(e)).await {
-                                    error!("Failed to update status for {}: {}", msg_id, e2);
+                                    error!(message_id = %msg_id, error = %e2, "Failed to update outbox item status");
                                 }
                                 in_flight.fetch_sub(1, Ordering::SeqCst);
-                                let mut m = metrics.write().await;
-                                m.items_processed += 1;
-                                m.items_failed += 1;
+                                metrics.record_failed(1);
                             }
                         }
                     }
@@ -484,6 +533,12 @@ impl EnhancedOutboxProcessor {
 
     /// Poll for pending items and add to buffer (with backpressure)
     async fn poll_and_buffer(&self) -> anyhow::Result<()> {
+        // Sample the backlog once per poll, including polls skipped for backpressure
+        match self.repository.count_pending().await {
+            Ok(backlog) => self.metrics.set_backlog_size(backlog),
+            Err(e) => warn!("Failed to count pending outbox items: {}", e),
+        }
+
         // Check backpressure
         let current_in_flight = self.in_flight.load(Ordering::SeqCst);
         let available_slots = self.config.max_in_flight.saturating_sub(current_in_flight);
@@ -511,23 +566,27 @@ impl EnhancedOutboxProcessor {
         // Increment in-flight
         self.in_flight.fetch_add(items.len() as u64, Ordering::SeqCst);
 
-        // Update metrics
-        {
-            let mut m = self.metrics.write().await;
-            m.items_polled += items.len() as u64;
-        }
+        self.metrics.record_fetched(items.len() as u64);
 
         // Add to buffer
         let mut rejected_count = 0;
         for item in items {
             let message = Message {
                 id: item.id.clone(),
-                pool_code: item.pool_code.clone().unwrap_or_else(|| "DEFAULT".to_string()),
+                pool_code: item.pool_code.clone().unwrap_or_else(|| DEFAULT_POOL_CODE.to_string()),
                 auth_token: None,
                 signing_secret: None,
                 mediation_type: MediationType::HTTP,
                 mediation_target: item.mediation_target.clone().unwrap_or_default(),
                 message_group_id: item.message_group.clone(),
+                payload: None,
+                raw_body_base64: None,
+                content_type: None,
+                client_id: None,
+                timeout_seconds: None,
+                max_retries: None,
+                correlation_id: None,
+                traceparent: None,
             };
 
             if let Err(_) = self.buffer.push(message).await {
@@ -563,7 +622,13 @@ mod tests {
     #[test]
     fn test_processor_metrics_default() {
         let metrics = ProcessorMetrics::default();
-        assert_eq!(metrics.items_polled, 0);
-        assert_eq!(metrics.current_in_flight, 0);
+        assert_eq!(metrics.items_fetched(), 0);
+        assert_eq!(metrics.backlog_size(), 0);
+
+        metrics.record_fetched(3);
+        metrics.set_backlog_size(7);
+        metrics.set_backlog_size(5);
+        assert_eq!(metrics.items_fetched(), 3);
+        assert_eq!(metrics.backlog_size(), 5);
     }
 }
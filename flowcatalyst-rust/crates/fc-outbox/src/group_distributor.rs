@@ -80,7 +80,7 @@ impl GroupDistributor {
                 stats.messages_without_group += 1;
                 drop(stats);
 
-                debug!("Message {} has no group, dispatching directly", message.id);
+                debug!(message_id = %message.id, "Message has no group, dispatching directly");
                 return self.dispatch_direct(message).await;
             }
         };
@@ -149,7 +149,7 @@ impl GroupDistributor {
         let mut stats = self.stats.write().await;
         stats.active_groups = groups.len();
 
-        info!("Created message group processor for {}", group_id);
+        info!(group_id = %group_id, "Created message group processor");
 
         Ok(processor)
     }
@@ -186,7 +186,7 @@ impl GroupDistributor {
                     if let Some(tx) = entry.shutdown_tx.take() {
                         let _ = tx.send(());
                     }
-                    info!("Cleaned up idle message group processor: {}", group_id);
+                    info!(group_id = %group_id, "Cleaned up idle message group processor");
                 } else {
                     // Put it back - still has messages
                     groups.insert(group_id, entry);
@@ -307,7 +307,7 @@ impl GroupDistributor {
             if let Some(tx) = entry.shutdown_tx.take() {
                 let _ = tx.send(());
             }
-            info!("Shutdown message group processor: {}", group_id);
+            info!(group_id = %group_id, "Shutdown message group processor");
         }
     }
 }
@@ -353,11 +353,10 @@ mod tests {
         Message {
             id: id.to_string(),
             pool_code: "default".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://localhost".to_string(),
             message_group_id: group.map(String::from),
+            ..Default::default()
         }
     }
 
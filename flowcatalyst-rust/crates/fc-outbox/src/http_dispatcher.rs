@@ -10,7 +10,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 use async_trait::async_trait;
-use fc_common::{Message, OutboxItem, OutboxItemType, OutboxStatus};
+use fc_common::{Message, OutboxItem, OutboxItemType, OutboxStatus, DEFAULT_POOL_CODE};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, warn};
 
@@ -181,7 +181,7 @@ impl HttpDispatcher {
         let batch_request = BatchRequest {
             items: items.iter().map(|item| BatchItem {
                 id: item.id.clone(),
-                pool_code: item.pool_code.clone().unwrap_or_else(|| "DEFAULT".to_string()),
+                pool_code: item.pool_code.clone().unwrap_or_else(|| DEFAULT_POOL_CODE.to_string()),
                 auth_token: None, // OutboxItem doesn't have auth_token
                 mediation_type: "HTTP".to_string(),
                 mediation_target: item.mediation_target.clone().unwrap_or_default(),
@@ -401,11 +401,10 @@ mod tests {
         Message {
             id: id.to_string(),
             pool_code: "DEFAULT".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://target.example.com/webhook".to_string(),
             message_group_id: Some("group-1".to_string()),
+            ..Default::default()
         }
     }
 
@@ -16,9 +16,10 @@ pub mod mysql;
 pub mod mongo;
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tokio::time::{sleep, Duration};
-use fc_common::{OutboxStatus, OutboxItemType, Message, MediationType};
+use fc_common::{OutboxItem, OutboxStatus, OutboxItemType, Message, MediationType, DEFAULT_POOL_CODE};
+use fc_queue::BatchPublishResult;
 use anyhow::Result;
 use tracing::{info, error, debug, warn};
 use async_trait::async_trait;
@@ -37,7 +38,7 @@ pub use http_dispatcher::{
     ItemStatus, OutboxDispatchResult,
 };
 pub use enhanced_processor::{EnhancedOutboxProcessor, EnhancedProcessorConfig, ProcessorMetrics};
-pub use repository::{OutboxRepository, OutboxTableConfig, OutboxRepositoryExt};
+pub use repository::{OutboxRepository, OutboxTableConfig, OutboxRepositoryExt, RequeueSummary};
 
 /// Configuration for leader election in outbox processor
 #[derive(Debug, Clone)]
@@ -66,18 +67,105 @@ impl Default for LeaderElectionConfig {
     }
 }
 
+/// Retry schedule for outbox items whose publish fails
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries before an item is marked failed
+    pub max_retries: i32,
+    /// Backoff before the first retry, doubled for each later one
+    pub initial_backoff: Duration,
+    /// Upper bound on the backoff
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying an item that has been retried `retry_count` times
+    ///
+    /// Exponential backoff with equal jitter: between half and all of the
+    /// backoff, so items that failed together don't all retry together.
+    pub fn backoff(&self, retry_count: i32) -> Duration {
+        let exponent = retry_count.clamp(0, 30) as u32;
+        let backoff = self.initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        let half = backoff / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+}
+
 pub struct OutboxProcessor {
     repository: Arc<dyn OutboxRepository>,
     queue_publisher: Arc<dyn QueuePublisher>,
     poll_interval: Duration,
     batch_size: u32,
+    retry_policy: RetryPolicy,
     leader_election_config: LeaderElectionConfig,
     is_primary: Arc<AtomicBool>,
+    publish_stats: Arc<PublishStats>,
+    metrics: Arc<ProcessorMetrics>,
 }
 
+/// What the queue did with a successfully published message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishOutcome {
+    /// Enqueued (also used when the queue can't report deduplication)
+    Accepted,
+    /// Suppressed by the queue's deduplication as a repeat of an earlier publish
+    Deduplicated,
+}
+
+/// Most messages sent to the queue in one request (the SQS `SendMessageBatch` limit)
+pub const PUBLISH_BATCH_SIZE: usize = 10;
+
 #[async_trait]
 pub trait QueuePublisher: Send + Sync {
-    async fn publish(&self, message: Message) -> Result<()>;
+    async fn publish(&self, message: Message) -> Result<PublishOutcome>;
+
+    /// Publish up to [`PUBLISH_BATCH_SIZE`] messages in one request. Returns one
+    /// outcome per message, in order; an error means nothing could be attempted.
+    ///
+    /// Defaults to publishing the messages one at a time.
+    async fn publish_batch(&self, messages: Vec<Message>) -> Result<Vec<Result<PublishOutcome>>> {
+        let mut outcomes = Vec::with_capacity(messages.len());
+        for message in messages {
+            outcomes.push(self.publish(message).await);
+        }
+        Ok(outcomes)
+    }
+}
+
+/// Publish outcome counters, used to check that queue deduplication is working
+#[derive(Debug, Default)]
+pub struct PublishStats {
+    accepted: AtomicU64,
+    deduplicated: AtomicU64,
+}
+
+impl PublishStats {
+    pub fn record(&self, outcome: PublishOutcome) {
+        match outcome {
+            PublishOutcome::Accepted => self.accepted.fetch_add(1, Ordering::Relaxed),
+            PublishOutcome::Deduplicated => self.deduplicated.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub fn accepted(&self) -> u64 {
+        self.accepted.load(Ordering::Relaxed)
+    }
+
+    pub fn deduplicated(&self) -> u64 {
+        self.deduplicated.load(Ordering::Relaxed)
+    }
 }
 
 impl OutboxProcessor {
@@ -92,8 +180,11 @@ impl OutboxProcessor {
             queue_publisher,
             poll_interval,
             batch_size,
+            retry_policy: RetryPolicy::default(),
             leader_election_config: LeaderElectionConfig::default(),
             is_primary: Arc::new(AtomicBool::new(true)), // Default to primary (single-instance mode)
+            publish_stats: Arc::new(PublishStats::default()),
+            metrics: Arc::new(ProcessorMetrics::default()),
         }
     }
 
@@ -111,11 +202,20 @@ impl OutboxProcessor {
             queue_publisher,
             poll_interval,
             batch_size,
+            retry_policy: RetryPolicy::default(),
             leader_election_config,
             is_primary,
+            publish_stats: Arc::new(PublishStats::default()),
+            metrics: Arc::new(ProcessorMetrics::default()),
         }
     }
 
+    /// Set how failed publishes are retried
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Check if this processor is the current leader
     pub fn is_primary(&self) -> bool {
         self.is_primary.load(Ordering::SeqCst)
@@ -136,6 +236,16 @@ impl OutboxProcessor {
         self.is_primary.clone()
     }
 
+    /// Accepted vs deduplicated publish counts
+    pub fn publish_stats(&self) -> Arc<PublishStats> {
+        self.publish_stats.clone()
+    }
+
+    /// Fetched, published, failed and retried counts and the outbox backlog
+    pub fn metrics(&self) -> Arc<ProcessorMetrics> {
+        self.metrics.clone()
+    }
+
     pub async fn start(&self) {
         info!(
             poll_interval_ms = %self.poll_interval.as_millis(),
@@ -145,6 +255,15 @@ impl OutboxProcessor {
             "Starting Outbox Processor"
         );
 
+        // Each poll fetches half the batch size per item type
+        if self.batch_size / 2 > self.repository.max_batch_size() {
+            warn!(
+                batch_size = %self.batch_size,
+                max_batch_size = %self.repository.max_batch_size(),
+                "Batch size exceeds the outbox backend's limit; polls will fetch at most the limit per item type"
+            );
+        }
+
         loop {
             // Only process if we're the primary (leader)
             if !self.is_primary() {
@@ -161,6 +280,12 @@ impl OutboxProcessor {
     }
 
     async fn process_batch(&self) -> Result<()> {
+        // Sample the backlog once per poll rather than per metrics request
+        match self.repository.count_pending().await {
+            Ok(backlog) => self.metrics.set_backlog_size(backlog),
+            Err(e) => warn!("Failed to count pending outbox items: {}", e),
+        }
+
         // Process both EVENT and DISPATCH_JOB items
         for item_type in [OutboxItemType::EVENT, OutboxItemType::DISPATCH_JOB] {
             self.process_items_of_type(item_type).await?;
@@ -174,44 +299,473 @@ impl OutboxProcessor {
             return Ok(());
         }
 
+        self.metrics.record_fetched(items.len() as u64);
         let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
         self.repository.mark_in_progress(item_type, ids).await?;
 
-        for item in items {
-            debug!("Processing outbox item [{}] type={}", item.id, item_type);
-
-            // Map OutboxItem to Message
-            let message = Message {
-                id: item.id.clone(),
-                pool_code: item.pool_code.clone().unwrap_or_else(|| "DEFAULT".to_string()),
-                auth_token: None,
-                signing_secret: None,
-                mediation_type: MediationType::HTTP,
-                mediation_target: item.mediation_target.clone().unwrap_or_else(|| "http://localhost:8080".to_string()),
-                message_group_id: item.message_group.clone(),
-            };
+        let mut outcome = BatchPublishResult::default();
+        for (chunk_index, chunk) in items.chunks(PUBLISH_BATCH_SIZE).enumerate() {
+            let offset = chunk_index * PUBLISH_BATCH_SIZE;
+            let messages: Vec<Message> = chunk.iter().map(|item| {
+                debug!(item_id = %item.id, item_type = %item_type, "Processing outbox item");
+                Self::to_message(item)
+            }).collect();
 
-            match self.queue_publisher.publish(message).await {
-                Ok(_) => {
-                    self.repository.mark_with_status(
-                        item_type,
-                        vec![item.id.clone()],
-                        OutboxStatus::SUCCESS,
-                        None,
-                    ).await?;
-                }
+            let results = match self.queue_publisher.publish_batch(messages).await {
+                Ok(results) => results,
                 Err(e) => {
-                    error!("Failed to publish outbox item [{}]: {}", item.id, e);
-                    self.repository.mark_with_status(
-                        item_type,
-                        vec![item.id.clone()],
-                        OutboxStatus::INTERNAL_ERROR,
-                        Some(e.to_string()),
-                    ).await?;
+                    error!("Failed to publish batch of {} outbox items: {}", chunk.len(), e);
+                    for index in offset..offset + chunk.len() {
+                        outcome.fail(index, e.to_string());
+                    }
+                    continue;
+                }
+            };
+
+            let mut results = results.into_iter();
+            for (index, item) in chunk.iter().enumerate() {
+                match results.next() {
+                    Some(Ok(publish_outcome)) => {
+                        if publish_outcome == PublishOutcome::Deduplicated {
+                            debug!(item_id = %item.id, "Outbox item was deduplicated by the queue");
+                        }
+                        self.publish_stats.record(publish_outcome);
+                        outcome.successes.push(item.id.clone());
+                    }
+                    Some(Err(e)) => {
+                        error!(item_id = %item.id, error = %e, "Failed to publish outbox item");
+                        outcome.fail(offset + index, e.to_string());
+                    }
+                    None => {
+                        error!(item_id = %item.id, "No publish result for outbox item");
+                        outcome.fail(offset + index, "No result reported by the queue publisher");
+                    }
                 }
             }
         }
 
+        self.record_batch_outcome(item_type, &items, outcome).await
+    }
+
+    /// Map an outbox item to the message published for it
+    fn to_message(item: &OutboxItem) -> Message {
+        Message {
+            id: item.id.clone(),
+            pool_code: item.pool_code.clone().unwrap_or_else(|| DEFAULT_POOL_CODE.to_string()),
+            auth_token: None,
+            signing_secret: None,
+            mediation_type: MediationType::HTTP,
+            mediation_target: item.mediation_target.clone().unwrap_or_else(|| "http://localhost:8080".to_string()),
+            message_group_id: item.message_group.clone(),
+            payload: None,
+            raw_body_base64: None,
+            content_type: None,
+            client_id: None,
+            timeout_seconds: None,
+            max_retries: None,
+            correlation_id: None,
+            traceparent: None,
+        }
+    }
+
+    /// Mark published items SUCCESS together. Each failed item is scheduled for
+    /// a retry with its own error, or marked INTERNAL_ERROR once out of retries.
+    async fn record_batch_outcome(
+        &self,
+        item_type: OutboxItemType,
+        items: &[OutboxItem],
+        outcome: BatchPublishResult,
+    ) -> Result<()> {
+        if !outcome.successes.is_empty() {
+            let published = outcome.successes.len() as u64;
+            self.repository.mark_with_status(item_type, outcome.successes, OutboxStatus::SUCCESS, None).await?;
+            self.metrics.record_published(published);
+        }
+
+        for failure in outcome.failures {
+            let Some(item) = items.get(failure.index) else { continue };
+
+            if item.retry_count < self.retry_policy.max_retries {
+                let delay = self.retry_policy.backoff(item.retry_count);
+                debug!(
+                    item_id = %item.id,
+                    delay_ms = delay.as_millis() as u64,
+                    retry = item.retry_count + 1,
+                    max_retries = self.retry_policy.max_retries,
+                    "Retrying outbox item"
+                );
+                self.repository.schedule_retry(
+                    item_type,
+                    &item.id,
+                    chrono::Utc::now() + delay,
+                    Some(failure.error),
+                ).await?;
+                self.metrics.record_retried(1);
+            } else {
+                warn!(item_id = %item.id, retries = item.retry_count, error = %failure.error, "Outbox item failed after max retries");
+                self.repository.mark_with_status(
+                    item_type,
+                    vec![item.id.clone()],
+                    OutboxStatus::FAILED,
+                    Some(failure.error),
+                ).await?;
+                self.metrics.record_failed(1);
+            }
+        }
+
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use chrono::Utc;
+    use fc_common::OutboxItem;
+
+    /// Item ID, next attempt time and error passed to `schedule_retry`
+    type ScheduledRetry = (String, chrono::DateTime<Utc>, Option<String>);
+
+    /// Hands out each queued batch of pending items once and records final statuses
+    #[derive(Default)]
+    struct InMemoryRepository {
+        pending: Mutex<Vec<OutboxItem>>,
+        statuses: Mutex<Vec<(String, OutboxStatus, Option<String>)>>,
+        retries: Mutex<Vec<ScheduledRetry>>,
+        table_config: OutboxTableConfig,
+    }
+
+    #[async_trait]
+    impl OutboxRepository for InMemoryRepository {
+        async fn fetch_pending_by_type(&self, item_type: OutboxItemType, _limit: u32) -> Result<Vec<OutboxItem>> {
+            let mut pending = self.pending.lock().unwrap();
+            let (matching, rest) = pending.drain(..).partition(|i| i.item_type == item_type);
+            *pending = rest;
+            Ok(matching)
+        }
+
+        async fn mark_in_progress(&self, _item_type: OutboxItemType, _ids: Vec<String>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn mark_with_status(
+            &self,
+            _item_type: OutboxItemType,
+            ids: Vec<String>,
+            status: OutboxStatus,
+            error_message: Option<String>,
+        ) -> Result<()> {
+            self.statuses.lock().unwrap().extend(ids.into_iter().map(|id| (id, status, error_message.clone())));
+            Ok(())
+        }
+
+        async fn increment_retry_count(&self, _item_type: OutboxItemType, _ids: Vec<String>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn schedule_retry(
+            &self,
+            _item_type: OutboxItemType,
+            id: &str,
+            next_attempt_at: chrono::DateTime<Utc>,
+            error_message: Option<String>,
+        ) -> Result<()> {
+            self.retries.lock().unwrap().push((id.to_string(), next_attempt_at, error_message));
+            Ok(())
+        }
+
+        async fn fetch_recoverable_items(&self, _item_type: OutboxItemType, _timeout: Duration, _limit: u32) -> Result<Vec<OutboxItem>> {
+            Ok(vec![])
+        }
+
+        async fn reset_recoverable_items(&self, _item_type: OutboxItemType, _ids: Vec<String>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn fetch_stuck_items(&self, _item_type: OutboxItemType, _timeout: Duration, _limit: u32) -> Result<Vec<OutboxItem>> {
+            Ok(vec![])
+        }
+
+        async fn reset_stuck_items(&self, _item_type: OutboxItemType, _ids: Vec<String>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn count_pending_by_type(&self, item_type: OutboxItemType) -> Result<u64> {
+            Ok(self.pending.lock().unwrap().iter().filter(|i| i.item_type == item_type).count() as u64)
+        }
+
+        async fn count_completed_since(&self, _item_type: OutboxItemType, _since: chrono::DateTime<Utc>) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn requeue_completed_since(&self, _item_type: OutboxItemType, _since: chrono::DateTime<Utc>) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn init_schema(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn table_config(&self) -> &OutboxTableConfig {
+            &self.table_config
+        }
+    }
+
+    /// Queue that deduplicates on message ID, like an SQS FIFO queue
+    #[derive(Default)]
+    struct DeduplicatingQueue {
+        seen: Mutex<HashSet<String>>,
+    }
+
+    #[async_trait]
+    impl QueuePublisher for DeduplicatingQueue {
+        async fn publish(&self, message: Message) -> Result<PublishOutcome> {
+            if self.seen.lock().unwrap().insert(message.id) {
+                Ok(PublishOutcome::Accepted)
+            } else {
+                Ok(PublishOutcome::Deduplicated)
+            }
+        }
+    }
+
+    fn item(id: &str) -> OutboxItem {
+        OutboxItem {
+            id: id.to_string(),
+            item_type: OutboxItemType::EVENT,
+            message_group: None,
+            payload: serde_json::json!({}),
+            status: OutboxStatus::PENDING,
+            retry_count: 0,
+            created_at: Utc::now(),
+            updated_at: None,
+            error_message: None,
+            next_attempt_at: None,
+            pool_code: None,
+            mediation_target: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_publish_counted() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let processor = OutboxProcessor::new(
+            repository.clone(),
+            Arc::new(DeduplicatingQueue::default()),
+            Duration::from_millis(10),
+            100,
+        );
+
+        repository.pending.lock().unwrap().extend([item("evt-1"), item("evt-2")]);
+        processor.process_batch().await.unwrap();
+
+        // evt-1 is picked up again, e.g. after being recovered as stuck
+        repository.pending.lock().unwrap().push(item("evt-1"));
+        processor.process_batch().await.unwrap();
+
+        let stats = processor.publish_stats();
+        assert_eq!(stats.accepted(), 2);
+        assert_eq!(stats.deduplicated(), 1);
+
+        // The message is on the queue either way
+        assert!(repository.statuses.lock().unwrap().iter().all(|(_, s, _)| *s == OutboxStatus::SUCCESS));
+    }
+
+    /// Queue that rejects messages whose ID starts with `bad`
+    struct PartiallyFailingQueue;
+
+    #[async_trait]
+    impl QueuePublisher for PartiallyFailingQueue {
+        async fn publish(&self, message: Message) -> Result<PublishOutcome> {
+            if message.id.starts_with("bad") {
+                anyhow::bail!("InvalidParameterValue: message {} rejected", message.id);
+            }
+            Ok(PublishOutcome::Accepted)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partial_batch_failure_marks_each_item() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let processor = OutboxProcessor::new(
+            repository.clone(),
+            Arc::new(PartiallyFailingQueue),
+            Duration::from_millis(10),
+            100,
+        ).with_retry_policy(RetryPolicy { max_retries: 0, ..Default::default() });
+
+        repository.pending.lock().unwrap().extend([item("evt-1"), item("bad-2"), item("evt-3"), item("bad-4")]);
+        processor.process_batch().await.unwrap();
+
+        let mut statuses = repository.statuses.lock().unwrap().clone();
+        statuses.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(statuses, vec![
+            ("bad-2".to_string(), OutboxStatus::INTERNAL_ERROR, Some("InvalidParameterValue: message bad-2 rejected".to_string())),
+            ("bad-4".to_string(), OutboxStatus::INTERNAL_ERROR, Some("InvalidParameterValue: message bad-4 rejected".to_string())),
+            ("evt-1".to_string(), OutboxStatus::SUCCESS, None),
+            ("evt-3".to_string(), OutboxStatus::SUCCESS, None),
+        ]);
+        assert_eq!(processor.publish_stats().accepted(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_publish_scheduled_for_retry_until_exhausted() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let processor = OutboxProcessor::new(
+            repository.clone(),
+            Arc::new(PartiallyFailingQueue),
+            Duration::from_millis(10),
+            100,
+        ).with_retry_policy(RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(60),
+        });
+
+        let mut retried = item("bad-1");
+        retried.retry_count = 2;
+        let mut exhausted = item("bad-2");
+        exhausted.retry_count = 3;
+        repository.pending.lock().unwrap().extend([retried, exhausted]);
+
+        let before = Utc::now();
+        processor.process_batch().await.unwrap();
+
+        // Third retry: 40s backoff, jittered to 20-40s
+        let retries = repository.retries.lock().unwrap().clone();
+        assert_eq!(retries.len(), 1);
+        let (id, next_attempt_at, error) = &retries[0];
+        assert_eq!(id, "bad-1");
+        assert!(*next_attempt_at >= before + chrono::Duration::seconds(20));
+        assert!(*next_attempt_at <= Utc::now() + chrono::Duration::seconds(40));
+        assert_eq!(error.as_deref(), Some("InvalidParameterValue: message bad-1 rejected"));
+
+        assert_eq!(repository.statuses.lock().unwrap().clone(), vec![
+            ("bad-2".to_string(), OutboxStatus::FAILED, Some("InvalidParameterValue: message bad-2 rejected".to_string())),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_outcomes_and_sample_backlog() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let processor = OutboxProcessor::new(
+            repository.clone(),
+            Arc::new(PartiallyFailingQueue),
+            Duration::from_millis(10),
+            100,
+        );
+
+        let mut exhausted = item("bad-3");
+        exhausted.retry_count = RetryPolicy::default().max_retries;
+        repository.pending.lock().unwrap().extend([item("evt-1"), item("bad-2"), exhausted]);
+        processor.process_batch().await.unwrap();
+
+        let metrics = processor.metrics();
+        assert_eq!(metrics.items_fetched(), 3);
+        assert_eq!(metrics.items_published(), 1);
+        assert_eq!(metrics.items_retried(), 1);
+        assert_eq!(metrics.items_failed(), 1);
+        // Sampled before the poll fetched the items
+        assert_eq!(metrics.backlog_size(), 3);
+
+        processor.process_batch().await.unwrap();
+        assert_eq!(metrics.backlog_size(), 0);
+        assert_eq!(metrics.items_fetched(), 3);
+    }
+
+    /// Batch publisher recording batch sizes. Rejects `bad` messages per entry,
+    /// and fails a whole batch containing a `down` message.
+    #[derive(Default)]
+    struct BatchingQueue {
+        batch_sizes: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl QueuePublisher for BatchingQueue {
+        async fn publish(&self, _message: Message) -> Result<PublishOutcome> {
+            panic!("messages should be published in batches");
+        }
+
+        async fn publish_batch(&self, messages: Vec<Message>) -> Result<Vec<Result<PublishOutcome>>> {
+            self.batch_sizes.lock().unwrap().push(messages.len());
+            if messages.iter().any(|m| m.id.starts_with("down")) {
+                anyhow::bail!("connection reset");
+            }
+            Ok(messages.into_iter().map(|m| {
+                if m.id.starts_with("bad") {
+                    Err(anyhow::anyhow!("InvalidParameterValue: message {} rejected", m.id))
+                } else {
+                    Ok(PublishOutcome::Accepted)
+                }
+            }).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_items_published_in_batches_of_ten() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let queue = Arc::new(BatchingQueue::default());
+        let processor = OutboxProcessor::new(repository.clone(), queue.clone(), Duration::from_millis(10), 100);
+
+        repository.pending.lock().unwrap().extend((0..25).map(|i| item(&format!("evt-{}", i))));
+        processor.process_batch().await.unwrap();
+
+        assert_eq!(queue.batch_sizes.lock().unwrap().clone(), vec![10, 10, 5]);
+        let statuses = repository.statuses.lock().unwrap().clone();
+        assert_eq!(statuses.len(), 25);
+        assert!(statuses.iter().all(|(_, s, _)| *s == OutboxStatus::SUCCESS));
+        assert_eq!(processor.publish_stats().accepted(), 25);
+    }
+
+    #[tokio::test]
+    async fn test_batch_failures_keep_successful_items_completed() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let processor = OutboxProcessor::new(
+            repository.clone(),
+            Arc::new(BatchingQueue::default()),
+            Duration::from_millis(10),
+            100,
+        );
+
+        // First batch: one entry rejected. Second batch: the whole request fails.
+        let mut items: Vec<OutboxItem> = (0..10)
+            .map(|i| item(&if i == 3 { "bad-3".to_string() } else { format!("evt-{}", i) }))
+            .collect();
+        items.extend([item("evt-10"), item("down-11")]);
+        repository.pending.lock().unwrap().extend(items);
+        processor.process_batch().await.unwrap();
+
+        let statuses = repository.statuses.lock().unwrap().clone();
+        assert_eq!(statuses.len(), 9);
+        assert!(statuses.iter().all(|(id, s, _)| *s == OutboxStatus::SUCCESS && id.starts_with("evt-")));
+
+        let mut retries: Vec<(String, Option<String>)> = repository.retries.lock().unwrap()
+            .iter()
+            .map(|(id, _, error)| (id.clone(), error.clone()))
+            .collect();
+        retries.sort();
+        assert_eq!(retries, vec![
+            ("bad-3".to_string(), Some("InvalidParameterValue: message bad-3 rejected".to_string())),
+            ("down-11".to_string(), Some("connection reset".to_string())),
+            ("evt-10".to_string(), Some("connection reset".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_retry_backoff_grows_with_jitter_and_caps() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        };
+
+        for (retry_count, backoff) in [(0, 1), (1, 2), (3, 8), (5, 30), (20, 30), (i32::MAX, 30)] {
+            let backoff = Duration::from_secs(backoff);
+            for _ in 0..20 {
+                let delay = policy.backoff(retry_count);
+                assert!(delay >= backoff / 2 && delay <= backoff, "retry {}: {:?}", retry_count, delay);
+            }
+        }
+    }
+}
+
@@ -163,17 +163,18 @@ impl MessageGroupProcessor {
 
         if queue.len() >= self.config.max_queue_depth {
             warn!(
-                "Queue depth exceeded for group {}, current: {}",
-                self.group_id, queue.len()
+                group_id = %self.group_id,
+                queue_depth = queue.len(),
+                "Queue depth exceeded for message group"
             );
             return Err("Queue depth exceeded".to_string());
         }
 
         queue.push_back(TrackedMessage::new(message));
         debug!(
-            "Message enqueued for group {}, queue depth: {}",
-            self.group_id,
-            queue.len()
+            group_id = %self.group_id,
+            queue_depth = queue.len(),
+            "Message enqueued for message group"
         );
 
         Ok(())
@@ -196,7 +197,7 @@ impl MessageGroupProcessor {
         let mut state = self.state.lock().await;
         if *state == ProcessorState::Running {
             *state = ProcessorState::Paused;
-            info!("Message group processor {} paused", self.group_id);
+            info!(group_id = %self.group_id, "Message group processor paused");
         }
     }
 
@@ -205,7 +206,7 @@ impl MessageGroupProcessor {
         let mut state = self.state.lock().await;
         if *state == ProcessorState::Paused {
             *state = ProcessorState::Running;
-            info!("Message group processor {} resumed", self.group_id);
+            info!(group_id = %self.group_id, "Message group processor resumed");
         }
     }
 
@@ -214,7 +215,7 @@ impl MessageGroupProcessor {
         let mut state = self.state.lock().await;
         if matches!(*state, ProcessorState::Blocked { .. }) {
             *state = ProcessorState::Running;
-            info!("Message group processor {} unblocked", self.group_id);
+            info!(group_id = %self.group_id, "Message group processor unblocked");
         }
     }
 
@@ -233,8 +234,9 @@ impl MessageGroupProcessor {
 
         if let Some(ref msg) = skipped {
             info!(
-                "Skipped blocking message {} in group {}",
-                msg.message.id, self.group_id
+                message_id = %msg.message.id,
+                group_id = %self.group_id,
+                "Skipped blocking message"
             );
         }
 
@@ -269,8 +271,9 @@ impl MessageGroupProcessor {
         }
 
         debug!(
-            "Processing batch of {} messages in group {}",
-            batch.len(), self.group_id
+            group_id = %self.group_id,
+            batch_size = batch.len(),
+            "Processing message group batch"
         );
 
         // Extract messages for dispatch
@@ -289,8 +292,9 @@ impl MessageGroupProcessor {
             match &item_result.result {
                 DispatchResult::Success => {
                     debug!(
-                        "Message {} dispatched successfully in group {}",
-                        tracked.message.id, self.group_id
+                        message_id = %tracked.message.id,
+                        group_id = %self.group_id,
+                        "Message dispatched successfully"
                     );
                 }
                 DispatchResult::Failure { error, retryable } => {
@@ -301,8 +305,9 @@ impl MessageGroupProcessor {
                         // Re-queue for retry
                         failed_to_requeue.push(tracked);
                         debug!(
-                            "Message {} will be re-queued for retry in group {}",
-                            item_result.message_id, self.group_id
+                            message_id = %item_result.message_id,
+                            group_id = %self.group_id,
+                            "Message will be re-queued for retry"
                         );
                     } else if self.config.block_on_error {
                         // Block on first non-retryable failure
@@ -314,8 +319,10 @@ impl MessageGroupProcessor {
                         failed_to_requeue.push(tracked);
                     } else {
                         error!(
-                            "Message {} failed permanently in group {}: {}",
-                            item_result.message_id, self.group_id, error
+                            message_id = %item_result.message_id,
+                            group_id = %self.group_id,
+                            error = %error,
+                            "Message failed permanently"
                         );
                     }
                 }
@@ -346,8 +353,9 @@ impl MessageGroupProcessor {
                 error: block_error.clone(),
             };
             error!(
-                "Message group processor {} blocked on message {}",
-                self.group_id, block_message_id
+                group_id = %self.group_id,
+                message_id = %block_message_id,
+                "Message group processor blocked"
             );
         }
 
@@ -362,7 +370,7 @@ impl MessageGroupProcessor {
 
     /// Run the processor loop
     pub async fn run(&self) {
-        info!("Starting message group processor for {}", self.group_id);
+        info!(group_id = %self.group_id, "Starting message group processor");
 
         let mut shutdown_rx = {
             let mut rx = self.shutdown_rx.lock().await;
@@ -391,7 +399,7 @@ impl MessageGroupProcessor {
             }
         }
 
-        info!("Message group processor {} stopped", self.group_id);
+        info!(group_id = %self.group_id, "Message group processor stopped");
     }
 }
 
@@ -441,11 +449,10 @@ mod tests {
         Message {
             id: id.to_string(),
             pool_code: "default".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://localhost".to_string(),
             message_group_id: Some("group-1".to_string()),
+            ..Default::default()
         }
     }
 
@@ -15,6 +15,12 @@ use futures::stream::TryStreamExt;
 use std::time::Duration;
 use tracing::{info, debug};
 
+/// Largest fetch for MongoDB
+///
+/// Status updates filter on `{ id: { $in: [...] } }`, which has to fit in a
+/// single 16MB BSON document alongside the update.
+pub const MONGO_MAX_BATCH_SIZE: u32 = 50_000;
+
 /// MongoDB implementation of OutboxRepository
 pub struct MongoOutboxRepository {
     database: Database,
@@ -48,6 +54,19 @@ impl MongoOutboxRepository {
         self.database.collection(name)
     }
 
+    /// Oldest-first find options returning up to `limit` documents
+    ///
+    /// The cursor batch size matches the limit, so a fetch is a single round
+    /// trip rather than the server's default first batch of 101 plus getMores.
+    fn find_options(limit: u32) -> FindOptions {
+        let limit = limit.min(MONGO_MAX_BATCH_SIZE);
+        FindOptions::builder()
+            .sort(doc! { "created_at": 1 })
+            .limit(limit as i64)
+            .batch_size(limit)
+            .build()
+    }
+
     /// Parse a document into an OutboxItem
     fn parse_doc(&self, doc: &Document, item_type: OutboxItemType) -> Result<OutboxItem> {
         let created_at_ts = doc.get_i64("created_at")?;
@@ -75,6 +94,8 @@ impl MongoOutboxRepository {
             error_message: doc.get_str("error_message").ok().map(String::from),
             created_at,
             updated_at,
+            next_attempt_at: doc.get_i64("next_attempt_at").ok()
+                .and_then(DateTime::from_timestamp_millis),
         })
     }
 }
@@ -82,12 +103,21 @@ impl MongoOutboxRepository {
 #[async_trait]
 impl OutboxRepository for MongoOutboxRepository {
     async fn fetch_pending_by_type(&self, item_type: OutboxItemType, limit: u32) -> Result<Vec<OutboxItem>> {
+        // A MongoDB limit of 0 means no limit
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
         let collection = self.collection_for_type(item_type);
-        let filter = doc! { "status": OutboxStatus::PENDING.code() };
-        let find_options = FindOptions::builder()
-            .sort(doc! { "created_at": 1 })
-            .limit(limit as i64)
-            .build();
+        // `null` also matches items without the field
+        let filter = doc! {
+            "status": OutboxStatus::PENDING.code(),
+            "$or": [
+                { "next_attempt_at": null },
+                { "next_attempt_at": { "$lte": Utc::now().timestamp_millis() } }
+            ]
+        };
+        let find_options = Self::find_options(limit);
 
         let mut cursor = collection.find(filter).with_options(find_options).await?;
         let mut items = Vec::new();
@@ -197,12 +227,53 @@ impl OutboxRepository for MongoOutboxRepository {
         Ok(())
     }
 
+    async fn schedule_retry(
+        &self,
+        item_type: OutboxItemType,
+        id: &str,
+        next_attempt_at: DateTime<Utc>,
+        error_message: Option<String>,
+    ) -> Result<()> {
+        let collection = self.collection_for_type(item_type);
+        let now = Utc::now().timestamp_millis();
+
+        let mut set_doc = doc! {
+            "status": OutboxStatus::PENDING.code(),
+            "next_attempt_at": next_attempt_at.timestamp_millis(),
+            "updated_at": now
+        };
+
+        if let Some(err) = &error_message {
+            set_doc.insert("error_message", err);
+        }
+
+        let update = doc! {
+            "$inc": { "retry_count": 1 },
+            "$set": set_doc
+        };
+
+        collection.update_one(doc! { "id": id }, update).await?;
+
+        debug!(
+            collection = %self.table_config.table_for_type(item_type),
+            id = %id,
+            next_attempt_at = %next_attempt_at,
+            "Scheduled retry"
+        );
+
+        Ok(())
+    }
+
     async fn fetch_recoverable_items(
         &self,
         item_type: OutboxItemType,
         timeout: Duration,
         limit: u32,
     ) -> Result<Vec<OutboxItem>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
         let collection = self.collection_for_type(item_type);
         let timeout_ms = timeout.as_millis() as i64;
         let cutoff = Utc::now().timestamp_millis() - timeout_ms;
@@ -221,10 +292,7 @@ impl OutboxRepository for MongoOutboxRepository {
             "updated_at": { "$lt": cutoff }
         };
 
-        let find_options = FindOptions::builder()
-            .sort(doc! { "created_at": 1 })
-            .limit(limit as i64)
-            .build();
+        let find_options = Self::find_options(limit);
 
         let mut cursor = collection.find(filter).with_options(find_options).await?;
         let mut items = Vec::new();
@@ -269,6 +337,10 @@ impl OutboxRepository for MongoOutboxRepository {
         timeout: Duration,
         limit: u32,
     ) -> Result<Vec<OutboxItem>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
         let collection = self.collection_for_type(item_type);
         let timeout_ms = timeout.as_millis() as i64;
         let cutoff = Utc::now().timestamp_millis() - timeout_ms;
@@ -278,10 +350,7 @@ impl OutboxRepository for MongoOutboxRepository {
             "updated_at": { "$lt": cutoff }
         };
 
-        let find_options = FindOptions::builder()
-            .sort(doc! { "created_at": 1 })
-            .limit(limit as i64)
-            .build();
+        let find_options = Self::find_options(limit);
 
         let mut cursor = collection.find(filter).with_options(find_options).await?;
         let mut items = Vec::new();
@@ -297,6 +366,49 @@ impl OutboxRepository for MongoOutboxRepository {
         self.reset_recoverable_items(item_type, ids).await
     }
 
+    async fn count_pending_by_type(&self, item_type: OutboxItemType) -> Result<u64> {
+        let collection = self.collection_for_type(item_type);
+        Ok(collection.count_documents(doc! { "status": OutboxStatus::PENDING.code() }).await?)
+    }
+
+    async fn count_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let collection = self.collection_for_type(item_type);
+        let filter = doc! {
+            "status": OutboxStatus::COMPLETED.code(),
+            "updated_at": { "$gte": since.timestamp_millis() }
+        };
+
+        Ok(collection.count_documents(filter).await?)
+    }
+
+    async fn requeue_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let collection = self.collection_for_type(item_type);
+        let now = Utc::now().timestamp_millis();
+
+        let filter = doc! {
+            "status": OutboxStatus::COMPLETED.code(),
+            "updated_at": { "$gte": since.timestamp_millis() }
+        };
+        let update = doc! {
+            "$set": {
+                "status": OutboxStatus::PENDING.code(),
+                "retry_count": 0,
+                "updated_at": now
+            },
+            "$unset": { "error_message": "" }
+        };
+
+        let result = collection.update_many(filter, update).await?;
+
+        info!(
+            collection = %self.table_config.table_for_type(item_type),
+            count = result.modified_count,
+            "Requeued completed items"
+        );
+
+        Ok(result.modified_count)
+    }
+
     async fn init_schema(&self) -> Result<()> {
         // Create indexes for events collection
         let events_collection = self.collection_for_type(OutboxItemType::EVENT);
@@ -324,7 +436,74 @@ impl OutboxRepository for MongoOutboxRepository {
         Ok(())
     }
 
+    fn max_batch_size(&self) -> u32 {
+        MONGO_MAX_BATCH_SIZE
+    }
+
     fn table_config(&self) -> &OutboxTableConfig {
         &self.table_config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_options_match_batch_size_to_limit() {
+        let options = MongoOutboxRepository::find_options(250);
+        assert_eq!(options.limit, Some(250));
+        assert_eq!(options.batch_size, Some(250));
+    }
+
+    #[test]
+    fn test_find_options_capped_at_backend_limit() {
+        let options = MongoOutboxRepository::find_options(MONGO_MAX_BATCH_SIZE + 1);
+        assert_eq!(options.limit, Some(MONGO_MAX_BATCH_SIZE as i64));
+        assert_eq!(options.batch_size, Some(MONGO_MAX_BATCH_SIZE));
+    }
+
+    /// Runs against a live server when `FC_TEST_MONGO_URL` is set, and is skipped otherwise
+    #[tokio::test]
+    async fn test_requeue_completed_since_only_requeues_later_items() {
+        use crate::repository::OutboxRepositoryExt;
+
+        let Ok(url) = std::env::var("FC_TEST_MONGO_URL") else {
+            return;
+        };
+        let client = Client::with_uri_str(&url).await.unwrap();
+        let repository = MongoOutboxRepository::new(client, "fc_test_outbox_requeue");
+        repository.database().drop().await.unwrap();
+
+        let outage = Utc::now() - chrono::Duration::hours(1);
+        let items = [
+            (OutboxItemType::EVENT, "before", outage - chrono::Duration::minutes(5)),
+            (OutboxItemType::EVENT, "after", outage + chrono::Duration::minutes(5)),
+            (OutboxItemType::DISPATCH_JOB, "job-after", outage + chrono::Duration::minutes(10)),
+        ];
+        for (item_type, id, completed_at) in items {
+            repository.collection_for_type(item_type).insert_one(doc! {
+                "id": id,
+                "payload": "{}",
+                "status": OutboxStatus::COMPLETED.code(),
+                "retry_count": 2,
+                "created_at": completed_at.timestamp_millis(),
+                "updated_at": completed_at.timestamp_millis(),
+            }).await.unwrap();
+        }
+
+        let summary = repository.requeue_completed(outage, true).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        assert!(repository.fetch_pending(10).await.unwrap().is_empty());
+
+        let summary = repository.requeue_completed(outage, false).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        let pending = repository.fetch_pending(10).await.unwrap();
+        let mut ids: Vec<&str> = pending.iter().map(|i| i.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["after", "job-after"]);
+        assert!(pending.iter().all(|i| i.retry_count == 0));
+
+        repository.database().drop().await.unwrap();
+    }
+}
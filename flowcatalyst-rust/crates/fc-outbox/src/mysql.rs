@@ -12,6 +12,12 @@ use chrono::{DateTime, Utc};
 use std::time::Duration;
 use tracing::{info, debug};
 
+/// Largest fetch for MySQL
+///
+/// Status updates bind one parameter per ID plus up to three more, and MySQL
+/// allows 65,535 placeholders per prepared statement.
+pub const MYSQL_MAX_BATCH_SIZE: u32 = 65_532;
+
 /// MySQL implementation of OutboxRepository
 pub struct MySqlOutboxRepository {
     pool: MySqlPool,
@@ -43,6 +49,25 @@ impl MySqlOutboxRepository {
         placeholders.join(", ")
     }
 
+    /// Add the `next_attempt_at` column to a table created without it
+    async fn add_next_attempt_column(&self, table: &str) -> Result<()> {
+        let exists: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM information_schema.COLUMNS \
+             WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND COLUMN_NAME = 'next_attempt_at'",
+        )
+            .bind(table)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if exists == 0 {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN next_attempt_at BIGINT", table))
+                .execute(&self.pool)
+                .await?;
+            info!(table = %table, "Added next_attempt_at column");
+        }
+        Ok(())
+    }
+
     /// Parse a row into an OutboxItem
     fn parse_row(&self, row: &sqlx::mysql::MySqlRow, item_type: OutboxItemType) -> Result<OutboxItem> {
         let created_at_ts: i64 = row.get("created_at");
@@ -52,6 +77,9 @@ impl MySqlOutboxRepository {
         let updated_at_ts: Option<i64> = row.try_get("updated_at").ok();
         let updated_at = updated_at_ts.and_then(DateTime::from_timestamp_millis);
 
+        let next_attempt_at_ts: Option<i64> = row.try_get("next_attempt_at").ok().flatten();
+        let next_attempt_at = next_attempt_at_ts.and_then(DateTime::from_timestamp_millis);
+
         let status_code: i32 = row.get("status");
         let status = OutboxStatus::from_code(status_code);
 
@@ -67,6 +95,7 @@ impl MySqlOutboxRepository {
             error_message: row.try_get("error_message").ok().flatten(),
             created_at,
             updated_at,
+            next_attempt_at,
         })
     }
 }
@@ -74,15 +103,18 @@ impl MySqlOutboxRepository {
 #[async_trait]
 impl OutboxRepository for MySqlOutboxRepository {
     async fn fetch_pending_by_type(&self, item_type: OutboxItemType, limit: u32) -> Result<Vec<OutboxItem>> {
+        let limit = limit.min(MYSQL_MAX_BATCH_SIZE);
         let table = self.table_config.table_for_type(item_type);
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
-             FROM {} WHERE status = ? ORDER BY created_at ASC LIMIT ?",
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
+             FROM {} WHERE status = ? AND (next_attempt_at IS NULL OR next_attempt_at <= ?) \
+             ORDER BY created_at ASC LIMIT ?",
             table
         );
 
         let rows = sqlx::query(&query)
             .bind(OutboxStatus::PENDING.code())
+            .bind(Utc::now().timestamp_millis())
             .bind(limit as i64)
             .fetch_all(&self.pool)
             .await?;
@@ -181,18 +213,45 @@ impl OutboxRepository for MySqlOutboxRepository {
         Ok(())
     }
 
+    async fn schedule_retry(
+        &self,
+        item_type: OutboxItemType,
+        id: &str,
+        next_attempt_at: DateTime<Utc>,
+        error_message: Option<String>,
+    ) -> Result<()> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!(
+            "UPDATE {} SET status = ?, retry_count = retry_count + 1, next_attempt_at = ?, error_message = ?, updated_at = ? WHERE id = ?",
+            table
+        );
+
+        sqlx::query(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .bind(next_attempt_at.timestamp_millis())
+            .bind(&error_message)
+            .bind(Utc::now().timestamp_millis())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        debug!(table = %table, id = %id, next_attempt_at = %next_attempt_at, "Scheduled retry");
+        Ok(())
+    }
+
     async fn fetch_recoverable_items(
         &self,
         item_type: OutboxItemType,
         timeout: Duration,
         limit: u32,
     ) -> Result<Vec<OutboxItem>> {
+        let limit = limit.min(MYSQL_MAX_BATCH_SIZE);
         let table = self.table_config.table_for_type(item_type);
         let timeout_ms = timeout.as_millis() as i64;
         let cutoff = Utc::now().timestamp_millis() - timeout_ms;
 
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
              FROM {} WHERE status IN (?, ?, ?, ?, ?, ?) AND updated_at < ? ORDER BY created_at ASC LIMIT ?",
             table
         );
@@ -248,12 +307,13 @@ impl OutboxRepository for MySqlOutboxRepository {
         timeout: Duration,
         limit: u32,
     ) -> Result<Vec<OutboxItem>> {
+        let limit = limit.min(MYSQL_MAX_BATCH_SIZE);
         let table = self.table_config.table_for_type(item_type);
         let timeout_ms = timeout.as_millis() as i64;
         let cutoff = Utc::now().timestamp_millis() - timeout_ms;
 
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
              FROM {} WHERE status = ? AND updated_at < ? ORDER BY created_at ASC LIMIT ?",
             table
         );
@@ -276,6 +336,53 @@ impl OutboxRepository for MySqlOutboxRepository {
         self.reset_recoverable_items(item_type, ids).await
     }
 
+    async fn count_pending_by_type(&self, item_type: OutboxItemType) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!("SELECT COUNT(*) FROM {} WHERE status = ?", table);
+
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn count_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!("SELECT COUNT(*) FROM {} WHERE status = ? AND updated_at >= ?", table);
+
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(since.timestamp_millis())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn requeue_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let now = Utc::now().timestamp_millis();
+
+        let query = format!(
+            "UPDATE {} SET status = ?, retry_count = 0, error_message = NULL, updated_at = ? \
+             WHERE status = ? AND updated_at >= ?",
+            table
+        );
+
+        let result = sqlx::query(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .bind(now)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(since.timestamp_millis())
+            .execute(&self.pool)
+            .await?;
+
+        info!(table = %table, count = result.rows_affected(), "Requeued completed items");
+        Ok(result.rows_affected())
+    }
+
     async fn init_schema(&self) -> Result<()> {
         // Create events table
         let events_schema = format!(
@@ -291,6 +398,7 @@ impl OutboxRepository for MySqlOutboxRepository {
                 error_message TEXT,
                 created_at BIGINT NOT NULL,
                 updated_at BIGINT,
+                next_attempt_at BIGINT,
                 INDEX idx_{}_status (status),
                 INDEX idx_{}_created_at (created_at)
             ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
@@ -318,6 +426,7 @@ impl OutboxRepository for MySqlOutboxRepository {
                 error_message TEXT,
                 created_at BIGINT NOT NULL,
                 updated_at BIGINT,
+                next_attempt_at BIGINT,
                 INDEX idx_{}_status (status),
                 INDEX idx_{}_created_at (created_at)
             ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
@@ -331,6 +440,11 @@ impl OutboxRepository for MySqlOutboxRepository {
             .execute(&self.pool)
             .await?;
 
+        // Tables created before retry scheduling lack next_attempt_at
+        for table in [&self.table_config.events_table, &self.table_config.dispatch_jobs_table] {
+            self.add_next_attempt_column(table).await?;
+        }
+
         info!(
             events_table = %self.table_config.events_table,
             dispatch_jobs_table = %self.table_config.dispatch_jobs_table,
@@ -340,7 +454,150 @@ impl OutboxRepository for MySqlOutboxRepository {
         Ok(())
     }
 
+    fn max_batch_size(&self) -> u32 {
+        MYSQL_MAX_BATCH_SIZE
+    }
+
     fn table_config(&self) -> &OutboxTableConfig {
         &self.table_config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs against a live server when `FC_TEST_MYSQL_URL` is set, and is skipped otherwise
+    #[tokio::test]
+    async fn test_fetch_pending_capped_below_placeholder_limit() {
+        let Ok(url) = std::env::var("FC_TEST_MYSQL_URL") else {
+            return;
+        };
+        let pool = MySqlPool::connect(&url).await.unwrap();
+        let repository = MySqlOutboxRepository::with_config(pool, OutboxTableConfig {
+            events_table: "fc_test_cap_events".to_string(),
+            dispatch_jobs_table: "fc_test_cap_dispatch_jobs".to_string(),
+        });
+        for table in ["fc_test_cap_events", "fc_test_cap_dispatch_jobs"] {
+            sqlx::query(&format!("DROP TABLE IF EXISTS {}", table)).execute(&repository.pool).await.unwrap();
+        }
+        repository.init_schema().await.unwrap();
+
+        // More rows than a single fetch may return, inserted 1,000 at a time
+        let total = MYSQL_MAX_BATCH_SIZE as usize + 10;
+        for start in (0..total).step_by(1000) {
+            let end = (start + 1000).min(total);
+            let query = format!(
+                "INSERT INTO fc_test_cap_events (id, payload, status, created_at) VALUES {}",
+                vec!["(?, '{}', ?, ?)"; end - start].join(", ")
+            );
+            let mut q = sqlx::query(&query);
+            for i in start..end {
+                q = q.bind(format!("evt-{}", i)).bind(OutboxStatus::PENDING.code()).bind(i as i64);
+            }
+            q.execute(&repository.pool).await.unwrap();
+        }
+
+        // The requested limit is reduced to the backend's cap before it is split between types
+        let items = repository.fetch_pending(u32::MAX).await.unwrap();
+        assert_eq!(items.len(), MYSQL_MAX_BATCH_SIZE.div_ceil(2) as usize);
+
+        let items = repository.fetch_pending_by_type(OutboxItemType::EVENT, u32::MAX).await.unwrap();
+        assert_eq!(items.len(), MYSQL_MAX_BATCH_SIZE as usize);
+
+        // The capped batch can be written back in one statement
+        let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+        repository.mark_in_progress(OutboxItemType::EVENT, ids).await.unwrap();
+        assert_eq!(repository.fetch_pending(u32::MAX).await.unwrap().len(), 10);
+    }
+
+    /// Runs against a live server when `FC_TEST_MYSQL_URL` is set, and is skipped otherwise
+    #[tokio::test]
+    async fn test_requeue_completed_since_only_requeues_later_items() {
+        use crate::repository::OutboxRepositoryExt;
+
+        let Ok(url) = std::env::var("FC_TEST_MYSQL_URL") else {
+            return;
+        };
+        let pool = MySqlPool::connect(&url).await.unwrap();
+        let repository = MySqlOutboxRepository::with_config(pool, OutboxTableConfig {
+            events_table: "fc_test_requeue_events".to_string(),
+            dispatch_jobs_table: "fc_test_requeue_dispatch_jobs".to_string(),
+        });
+        for table in ["fc_test_requeue_events", "fc_test_requeue_dispatch_jobs"] {
+            sqlx::query(&format!("DROP TABLE IF EXISTS {}", table)).execute(&repository.pool).await.unwrap();
+        }
+        repository.init_schema().await.unwrap();
+
+        let outage = Utc::now() - chrono::Duration::hours(1);
+        let items = [
+            ("fc_test_requeue_events", "before", outage - chrono::Duration::minutes(5)),
+            ("fc_test_requeue_events", "after", outage + chrono::Duration::minutes(5)),
+            ("fc_test_requeue_dispatch_jobs", "job-after", outage + chrono::Duration::minutes(10)),
+        ];
+        for (table, id, completed_at) in items {
+            sqlx::query(&format!(
+                "INSERT INTO {} (id, payload, status, retry_count, created_at, updated_at) VALUES (?, '{{}}', ?, 2, ?, ?)",
+                table
+            ))
+                .bind(id)
+                .bind(OutboxStatus::COMPLETED.code())
+                .bind(completed_at.timestamp_millis())
+                .bind(completed_at.timestamp_millis())
+                .execute(&repository.pool)
+                .await
+                .unwrap();
+        }
+
+        let summary = repository.requeue_completed(outage, true).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        assert!(repository.fetch_pending(10).await.unwrap().is_empty());
+
+        let summary = repository.requeue_completed(outage, false).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        let pending = repository.fetch_pending(10).await.unwrap();
+        let mut ids: Vec<&str> = pending.iter().map(|i| i.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["after", "job-after"]);
+        assert!(pending.iter().all(|i| i.retry_count == 0));
+    }
+
+    /// Runs against a live server when `FC_TEST_MYSQL_URL` is set, and is skipped otherwise
+    #[tokio::test]
+    async fn test_pending_item_lifecycle() {
+        let Ok(url) = std::env::var("FC_TEST_MYSQL_URL") else {
+            return;
+        };
+        let pool = MySqlPool::connect(&url).await.unwrap();
+        let repository = MySqlOutboxRepository::with_config(pool, OutboxTableConfig {
+            events_table: "fc_test_outbox_events".to_string(),
+            dispatch_jobs_table: "fc_test_outbox_dispatch_jobs".to_string(),
+        });
+        for table in ["fc_test_outbox_events", "fc_test_outbox_dispatch_jobs"] {
+            sqlx::query(&format!("DROP TABLE IF EXISTS {}", table)).execute(&repository.pool).await.unwrap();
+        }
+        repository.init_schema().await.unwrap();
+        // Idempotent on an existing schema
+        repository.init_schema().await.unwrap();
+
+        for id in ["evt-1", "evt-2"] {
+            sqlx::query("INSERT INTO fc_test_outbox_events (id, payload, status, created_at) VALUES (?, '{}', ?, ?)")
+                .bind(id)
+                .bind(OutboxStatus::PENDING.code())
+                .bind(Utc::now().timestamp_millis())
+                .execute(&repository.pool)
+                .await
+                .unwrap();
+        }
+
+        let items = repository.fetch_pending(10).await.unwrap();
+        assert_eq!(items.len(), 2);
+        let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+        repository.mark_in_progress(OutboxItemType::EVENT, ids).await.unwrap();
+        assert!(repository.fetch_pending(10).await.unwrap().is_empty());
+
+        repository.mark_with_status(OutboxItemType::EVENT, vec!["evt-1".to_string()], OutboxStatus::SUCCESS, None).await.unwrap();
+        let completed = repository.count_completed_since(OutboxItemType::EVENT, Utc::now() - chrono::Duration::minutes(1)).await.unwrap();
+        assert_eq!(completed, 1);
+    }
+}
@@ -37,6 +37,14 @@ impl PostgresOutboxRepository {
         &self.pool
     }
 
+    /// Add the `next_attempt_at` column to a table created without it
+    async fn add_next_attempt_column(&self, table: &str) -> Result<()> {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN IF NOT EXISTS next_attempt_at BIGINT", table))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     /// Parse a row into an OutboxItem
     fn parse_row(&self, row: &sqlx::postgres::PgRow, item_type: OutboxItemType) -> Result<OutboxItem> {
         let created_at_ts: i64 = row.get("created_at");
@@ -46,6 +54,9 @@ impl PostgresOutboxRepository {
         let updated_at_ts: Option<i64> = row.try_get("updated_at").ok();
         let updated_at = updated_at_ts.and_then(DateTime::from_timestamp_millis);
 
+        let next_attempt_at_ts: Option<i64> = row.try_get("next_attempt_at").ok().flatten();
+        let next_attempt_at = next_attempt_at_ts.and_then(DateTime::from_timestamp_millis);
+
         let status_code: i32 = row.get("status");
         let status = OutboxStatus::from_code(status_code);
 
@@ -61,6 +72,7 @@ impl PostgresOutboxRepository {
             error_message: row.try_get("error_message").ok().flatten(),
             created_at,
             updated_at,
+            next_attempt_at,
         })
     }
 }
@@ -74,13 +86,15 @@ impl OutboxRepository for PostgresOutboxRepository {
     async fn fetch_pending_by_type(&self, item_type: OutboxItemType, limit: u32) -> Result<Vec<OutboxItem>> {
         let table = self.table_config.table_for_type(item_type);
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
-             FROM {} WHERE status = $1 ORDER BY created_at ASC LIMIT $2",
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
+             FROM {} WHERE status = $1 AND (next_attempt_at IS NULL OR next_attempt_at <= $2) \
+             ORDER BY created_at ASC LIMIT $3",
             table
         );
 
         let rows = sqlx::query(&query)
             .bind(OutboxStatus::PENDING.code())
+            .bind(Utc::now().timestamp_millis())
             .bind(limit as i64)
             .fetch_all(&self.pool)
             .await?;
@@ -195,6 +209,32 @@ impl OutboxRepository for PostgresOutboxRepository {
         Ok(())
     }
 
+    async fn schedule_retry(
+        &self,
+        item_type: OutboxItemType,
+        id: &str,
+        next_attempt_at: DateTime<Utc>,
+        error_message: Option<String>,
+    ) -> Result<()> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!(
+            "UPDATE {} SET status = $1, retry_count = retry_count + 1, next_attempt_at = $2, error_message = $3, updated_at = $4 WHERE id = $5",
+            table
+        );
+
+        sqlx::query(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .bind(next_attempt_at.timestamp_millis())
+            .bind(&error_message)
+            .bind(Utc::now().timestamp_millis())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        debug!(table = %table, id = %id, next_attempt_at = %next_attempt_at, "Scheduled retry");
+        Ok(())
+    }
+
     async fn fetch_recoverable_items(
         &self,
         item_type: OutboxItemType,
@@ -207,7 +247,7 @@ impl OutboxRepository for PostgresOutboxRepository {
 
         // Recoverable items: IN_PROGRESS or error states that have been stuck
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
              FROM {} WHERE (status = $1 OR status = $2 OR status = $3 OR status = $4 OR status = $5 OR status = $6) \
              AND updated_at < $7 ORDER BY created_at ASC LIMIT $8",
             table
@@ -274,7 +314,7 @@ impl OutboxRepository for PostgresOutboxRepository {
 
         // Stuck items: only IN_PROGRESS that have been stuck
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
              FROM {} WHERE status = $1 AND updated_at < $2 ORDER BY created_at ASC LIMIT $3",
             table
         );
@@ -299,6 +339,53 @@ impl OutboxRepository for PostgresOutboxRepository {
         self.reset_recoverable_items(item_type, ids).await
     }
 
+    async fn count_pending_by_type(&self, item_type: OutboxItemType) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!("SELECT COUNT(*) FROM {} WHERE status = $1", table);
+
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn count_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!("SELECT COUNT(*) FROM {} WHERE status = $1 AND updated_at >= $2", table);
+
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(since.timestamp_millis())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn requeue_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let now = Utc::now().timestamp_millis();
+
+        let query = format!(
+            "UPDATE {} SET status = $1, retry_count = 0, error_message = NULL, updated_at = $2 \
+             WHERE status = $3 AND updated_at >= $4",
+            table
+        );
+
+        let result = sqlx::query(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .bind(now)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(since.timestamp_millis())
+            .execute(&self.pool)
+            .await?;
+
+        info!(table = %table, count = result.rows_affected(), "Requeued completed items");
+        Ok(result.rows_affected())
+    }
+
     // ========================================================================
     // Schema Management
     // ========================================================================
@@ -317,7 +404,8 @@ impl OutboxRepository for PostgresOutboxRepository {
                 retry_count INTEGER NOT NULL DEFAULT 0,
                 error_message TEXT,
                 created_at BIGINT NOT NULL,
-                updated_at BIGINT
+                updated_at BIGINT,
+                next_attempt_at BIGINT
             );
             CREATE INDEX IF NOT EXISTS idx_{}_status ON {}(status);
             CREATE INDEX IF NOT EXISTS idx_{}_created_at ON {}(created_at);
@@ -346,7 +434,8 @@ impl OutboxRepository for PostgresOutboxRepository {
                 retry_count INTEGER NOT NULL DEFAULT 0,
                 error_message TEXT,
                 created_at BIGINT NOT NULL,
-                updated_at BIGINT
+                updated_at BIGINT,
+                next_attempt_at BIGINT
             );
             CREATE INDEX IF NOT EXISTS idx_{}_status ON {}(status);
             CREATE INDEX IF NOT EXISTS idx_{}_created_at ON {}(created_at);
@@ -362,6 +451,11 @@ impl OutboxRepository for PostgresOutboxRepository {
             .execute(&self.pool)
             .await?;
 
+        // Tables created before retry scheduling lack next_attempt_at
+        for table in [&self.table_config.events_table, &self.table_config.dispatch_jobs_table] {
+            self.add_next_attempt_column(table).await?;
+        }
+
         info!(
             events_table = %self.table_config.events_table,
             dispatch_jobs_table = %self.table_config.dispatch_jobs_table,
@@ -393,4 +487,101 @@ mod tests {
         assert_eq!(config.table_for_type(OutboxItemType::EVENT), "outbox_events");
         assert_eq!(config.table_for_type(OutboxItemType::DISPATCH_JOB), "outbox_dispatch_jobs");
     }
+
+    /// Runs against a live server when `FC_TEST_POSTGRES_URL` is set, and is skipped otherwise
+    #[tokio::test]
+    async fn test_fetch_pending_beyond_parameter_limit() {
+        let Ok(url) = std::env::var("FC_TEST_POSTGRES_URL") else {
+            return;
+        };
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repository = PostgresOutboxRepository::with_config(pool, OutboxTableConfig {
+            events_table: "fc_test_cap_events".to_string(),
+            dispatch_jobs_table: "fc_test_cap_dispatch_jobs".to_string(),
+        });
+        for table in ["fc_test_cap_events", "fc_test_cap_dispatch_jobs"] {
+            sqlx::query(&format!("DROP TABLE IF EXISTS {}", table)).execute(&repository.pool).await.unwrap();
+        }
+        repository.init_schema().await.unwrap();
+
+        // More rows than Postgres allows bind parameters in one statement
+        let total: i64 = 70_000;
+        for (table, prefix) in [("fc_test_cap_events", "evt"), ("fc_test_cap_dispatch_jobs", "job")] {
+            sqlx::query(&format!(
+                "INSERT INTO {} (id, payload, status, created_at) \
+                 SELECT $1 || '-' || n, '{{}}', $2, n FROM generate_series(1, $3) AS n",
+                table
+            ))
+                .bind(prefix)
+                .bind(OutboxStatus::PENDING.code())
+                .bind(total)
+                .execute(&repository.pool)
+                .await
+                .unwrap();
+        }
+
+        // A smaller limit is honoured and split between types
+        let items = repository.fetch_pending(7).await.unwrap();
+        assert_eq!(items.len(), 7);
+        assert_eq!(items.iter().filter(|i| i.item_type == OutboxItemType::EVENT).count(), 4);
+
+        // IDs are bound as a single array (`id = ANY($n)`), so the whole table
+        // can be fetched and written back in one statement
+        let items = repository.fetch_pending_by_type(OutboxItemType::EVENT, u32::MAX).await.unwrap();
+        assert_eq!(items.len(), total as usize);
+        let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+        repository.mark_in_progress(OutboxItemType::EVENT, ids).await.unwrap();
+        assert!(repository.fetch_pending_by_type(OutboxItemType::EVENT, u32::MAX).await.unwrap().is_empty());
+    }
+
+    /// Runs against a live server when `FC_TEST_POSTGRES_URL` is set, and is skipped otherwise
+    #[tokio::test]
+    async fn test_requeue_completed_since_only_requeues_later_items() {
+        use crate::repository::OutboxRepositoryExt;
+
+        let Ok(url) = std::env::var("FC_TEST_POSTGRES_URL") else {
+            return;
+        };
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repository = PostgresOutboxRepository::with_config(pool, OutboxTableConfig {
+            events_table: "fc_test_requeue_events".to_string(),
+            dispatch_jobs_table: "fc_test_requeue_dispatch_jobs".to_string(),
+        });
+        for table in ["fc_test_requeue_events", "fc_test_requeue_dispatch_jobs"] {
+            sqlx::query(&format!("DROP TABLE IF EXISTS {}", table)).execute(&repository.pool).await.unwrap();
+        }
+        repository.init_schema().await.unwrap();
+
+        let outage = Utc::now() - chrono::Duration::hours(1);
+        let items = [
+            ("fc_test_requeue_events", "before", outage - chrono::Duration::minutes(5)),
+            ("fc_test_requeue_events", "after", outage + chrono::Duration::minutes(5)),
+            ("fc_test_requeue_dispatch_jobs", "job-after", outage + chrono::Duration::minutes(10)),
+        ];
+        for (table, id, completed_at) in items {
+            sqlx::query(&format!(
+                "INSERT INTO {} (id, payload, status, retry_count, created_at, updated_at) VALUES ($1, '{{}}', $2, 2, $3, $4)",
+                table
+            ))
+                .bind(id)
+                .bind(OutboxStatus::COMPLETED.code())
+                .bind(completed_at.timestamp_millis())
+                .bind(completed_at.timestamp_millis())
+                .execute(&repository.pool)
+                .await
+                .unwrap();
+        }
+
+        let summary = repository.requeue_completed(outage, true).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        assert!(repository.fetch_pending(10).await.unwrap().is_empty());
+
+        let summary = repository.requeue_completed(outage, false).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        let pending = repository.fetch_pending(10).await.unwrap();
+        let mut ids: Vec<&str> = pending.iter().map(|i| i.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["after", "job-after"]);
+        assert!(pending.iter().all(|i| i.retry_count == 0));
+    }
 }
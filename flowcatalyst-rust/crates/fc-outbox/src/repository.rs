@@ -6,6 +6,8 @@
 use async_trait::async_trait;
 use fc_common::{OutboxItem, OutboxItemType, OutboxStatus};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::time::Duration;
 
 /// Configuration for outbox repository tables
@@ -43,7 +45,7 @@ pub trait OutboxRepository: Send + Sync {
     // Core Operations (Java-compatible)
     // ========================================================================
 
-    /// Fetch pending items of the specified type
+    /// Fetch pending items of the specified type whose next attempt is due
     ///
     /// Java equivalent: `fetchPending(OutboxItemType type, int limit)`
     async fn fetch_pending_by_type(&self, item_type: OutboxItemType, limit: u32) -> Result<Vec<OutboxItem>>;
@@ -69,6 +71,16 @@ pub trait OutboxRepository: Send + Sync {
     /// Java equivalent: `incrementRetryCount(OutboxItemType type, List<String> ids)`
     async fn increment_retry_count(&self, item_type: OutboxItemType, ids: Vec<String>) -> Result<()>;
 
+    /// Return a failed item to PENDING, incrementing its retry count and
+    /// recording the error. Pending fetches skip it until `next_attempt_at`.
+    async fn schedule_retry(
+        &self,
+        item_type: OutboxItemType,
+        id: &str,
+        next_attempt_at: DateTime<Utc>,
+        error_message: Option<String>,
+    ) -> Result<()>;
+
     /// Fetch items that are recoverable (stuck in IN_PROGRESS or error states)
     ///
     /// Java equivalent: `fetchRecoverableItems(OutboxItemType type, int timeoutSeconds, int limit)`
@@ -99,18 +111,52 @@ pub trait OutboxRepository: Send + Sync {
     /// Java equivalent: `resetStuckItems(OutboxItemType type, List<String> ids)`
     async fn reset_stuck_items(&self, item_type: OutboxItemType, ids: Vec<String>) -> Result<()>;
 
+    /// Count PENDING items, including those waiting for a scheduled retry
+    async fn count_pending_by_type(&self, item_type: OutboxItemType) -> Result<u64>;
+
+    // ========================================================================
+    // Reprocessing
+    // ========================================================================
+
+    /// Count items completed (SUCCESS) at or after `since`
+    async fn count_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64>;
+
+    /// Reset items completed at or after `since` to PENDING with a zero retry
+    /// count, so they are published again. Returns how many were requeued.
+    async fn requeue_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64>;
+
+    /// Largest number of items a single fetch returns
+    ///
+    /// Fetched IDs are written back in one statement (e.g. `mark_in_progress`),
+    /// so backends that bind one parameter per ID cap fetches to stay within
+    /// their limits. Larger requested limits are reduced to this.
+    fn max_batch_size(&self) -> u32 {
+        u32::MAX
+    }
+
     // ========================================================================
     // Convenience Methods (backward compatibility)
     // ========================================================================
 
     /// Fetch all pending items (both types) - convenience method
+    ///
+    /// The limit is split between the two types, with events taking the odd
+    /// item, so a limit of 1 still fetches something.
     async fn fetch_pending(&self, limit: u32) -> Result<Vec<OutboxItem>> {
-        let mut items = self.fetch_pending_by_type(OutboxItemType::EVENT, limit / 2).await?;
+        let limit = limit.min(self.max_batch_size());
+        let mut items = self.fetch_pending_by_type(OutboxItemType::EVENT, limit.div_ceil(2)).await?;
         let dispatch_items = self.fetch_pending_by_type(OutboxItemType::DISPATCH_JOB, limit / 2).await?;
         items.extend(dispatch_items);
         Ok(items)
     }
 
+    /// Count pending items of both types (the outbox backlog)
+    async fn count_pending(&self) -> Result<u64> {
+        let events = self.count_pending_by_type(OutboxItemType::EVENT).await?;
+        let dispatch_jobs = self.count_pending_by_type(OutboxItemType::DISPATCH_JOB).await?;
+        Ok(events + dispatch_jobs)
+    }
+
     /// Mark items as processing (legacy method)
     async fn mark_processing(&self, ids: Vec<String>) -> Result<()> {
         // Assume EVENT type for legacy callers
@@ -157,6 +203,16 @@ pub trait OutboxRepository: Send + Sync {
     fn table_config(&self) -> &OutboxTableConfig;
 }
 
+/// Items requeued by [`OutboxRepositoryExt::requeue_completed`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequeueSummary {
+    /// Only counted; nothing was requeued
+    pub dry_run: bool,
+    pub events: u64,
+    pub dispatch_jobs: u64,
+}
+
 /// Extension trait for batch operations
 #[async_trait]
 pub trait OutboxRepositoryExt: OutboxRepository {
@@ -230,6 +286,26 @@ pub trait OutboxRepositoryExt: OutboxRepository {
 
         Ok(retried)
     }
+
+    /// Requeue items of both types completed at or after `since`, e.g. after a
+    /// downstream outage lost them. A dry run only counts the matching items.
+    async fn requeue_completed(&self, since: DateTime<Utc>, dry_run: bool) -> Result<RequeueSummary> {
+        let mut summary = RequeueSummary { dry_run, ..Default::default() };
+
+        for item_type in [OutboxItemType::EVENT, OutboxItemType::DISPATCH_JOB] {
+            let count = if dry_run {
+                self.count_completed_since(item_type, since).await?
+            } else {
+                self.requeue_completed_since(item_type, since).await?
+            };
+            match item_type {
+                OutboxItemType::EVENT => summary.events = count,
+                OutboxItemType::DISPATCH_JOB => summary.dispatch_jobs = count,
+            }
+        }
+
+        Ok(summary)
+    }
 }
 
 // Blanket implementation
@@ -12,6 +12,12 @@ use chrono::{DateTime, Utc};
 use std::time::Duration;
 use tracing::{info, debug};
 
+/// Largest fetch for SQLite
+///
+/// Status updates bind one parameter per ID plus up to three more, and SQLite
+/// builds before 3.32 allow 999 bound parameters per statement.
+pub const SQLITE_MAX_BATCH_SIZE: u32 = 996;
+
 /// SQLite implementation of OutboxRepository
 pub struct SqliteOutboxRepository {
     pool: SqlitePool,
@@ -43,6 +49,24 @@ impl SqliteOutboxRepository {
         placeholders.join(", ")
     }
 
+    /// Add the `next_attempt_at` column to a table created without it
+    async fn add_next_attempt_column(&self, table: &str) -> Result<()> {
+        let exists: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = 'next_attempt_at'",
+        )
+            .bind(table)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if exists == 0 {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN next_attempt_at INTEGER", table))
+                .execute(&self.pool)
+                .await?;
+            info!(table = %table, "Added next_attempt_at column");
+        }
+        Ok(())
+    }
+
     /// Parse a row into an OutboxItem
     fn parse_row(&self, row: &sqlx::sqlite::SqliteRow, item_type: OutboxItemType) -> Result<OutboxItem> {
         let created_at_ts: i64 = row.get("created_at");
@@ -52,6 +76,9 @@ impl SqliteOutboxRepository {
         let updated_at_ts: Option<i64> = row.try_get("updated_at").ok();
         let updated_at = updated_at_ts.and_then(DateTime::from_timestamp_millis);
 
+        let next_attempt_at_ts: Option<i64> = row.try_get("next_attempt_at").ok().flatten();
+        let next_attempt_at = next_attempt_at_ts.and_then(DateTime::from_timestamp_millis);
+
         let status_code: i32 = row.get("status");
         let status = OutboxStatus::from_code(status_code);
 
@@ -67,6 +94,7 @@ impl SqliteOutboxRepository {
             error_message: row.try_get("error_message").ok().flatten(),
             created_at,
             updated_at,
+            next_attempt_at,
         })
     }
 }
@@ -74,15 +102,18 @@ impl SqliteOutboxRepository {
 #[async_trait]
 impl OutboxRepository for SqliteOutboxRepository {
     async fn fetch_pending_by_type(&self, item_type: OutboxItemType, limit: u32) -> Result<Vec<OutboxItem>> {
+        let limit = limit.min(SQLITE_MAX_BATCH_SIZE);
         let table = self.table_config.table_for_type(item_type);
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
-             FROM {} WHERE status = ? ORDER BY created_at ASC LIMIT ?",
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
+             FROM {} WHERE status = ? AND (next_attempt_at IS NULL OR next_attempt_at <= ?) \
+             ORDER BY created_at ASC LIMIT ?",
             table
         );
 
         let rows = sqlx::query(&query)
             .bind(OutboxStatus::PENDING.code())
+            .bind(Utc::now().timestamp_millis())
             .bind(limit)
             .fetch_all(&self.pool)
             .await?;
@@ -181,18 +212,45 @@ impl OutboxRepository for SqliteOutboxRepository {
         Ok(())
     }
 
+    async fn schedule_retry(
+        &self,
+        item_type: OutboxItemType,
+        id: &str,
+        next_attempt_at: DateTime<Utc>,
+        error_message: Option<String>,
+    ) -> Result<()> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!(
+            "UPDATE {} SET status = ?, retry_count = retry_count + 1, next_attempt_at = ?, error_message = ?, updated_at = ? WHERE id = ?",
+            table
+        );
+
+        sqlx::query(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .bind(next_attempt_at.timestamp_millis())
+            .bind(&error_message)
+            .bind(Utc::now().timestamp_millis())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        debug!(table = %table, id = %id, next_attempt_at = %next_attempt_at, "Scheduled retry");
+        Ok(())
+    }
+
     async fn fetch_recoverable_items(
         &self,
         item_type: OutboxItemType,
         timeout: Duration,
         limit: u32,
     ) -> Result<Vec<OutboxItem>> {
+        let limit = limit.min(SQLITE_MAX_BATCH_SIZE);
         let table = self.table_config.table_for_type(item_type);
         let timeout_ms = timeout.as_millis() as i64;
         let cutoff = Utc::now().timestamp_millis() - timeout_ms;
 
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
              FROM {} WHERE status IN (?, ?, ?, ?, ?, ?) AND updated_at < ? ORDER BY created_at ASC LIMIT ?",
             table
         );
@@ -248,12 +306,13 @@ impl OutboxRepository for SqliteOutboxRepository {
         timeout: Duration,
         limit: u32,
     ) -> Result<Vec<OutboxItem>> {
+        let limit = limit.min(SQLITE_MAX_BATCH_SIZE);
         let table = self.table_config.table_for_type(item_type);
         let timeout_ms = timeout.as_millis() as i64;
         let cutoff = Utc::now().timestamp_millis() - timeout_ms;
 
         let query = format!(
-            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at \
+            "SELECT id, pool_code, mediation_target, message_group, payload, status, retry_count, error_message, created_at, updated_at, next_attempt_at \
              FROM {} WHERE status = ? AND updated_at < ? ORDER BY created_at ASC LIMIT ?",
             table
         );
@@ -276,6 +335,53 @@ impl OutboxRepository for SqliteOutboxRepository {
         self.reset_recoverable_items(item_type, ids).await
     }
 
+    async fn count_pending_by_type(&self, item_type: OutboxItemType) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!("SELECT COUNT(*) FROM {} WHERE status = ?", table);
+
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn count_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let query = format!("SELECT COUNT(*) FROM {} WHERE status = ? AND updated_at >= ?", table);
+
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(since.timestamp_millis())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn requeue_completed_since(&self, item_type: OutboxItemType, since: DateTime<Utc>) -> Result<u64> {
+        let table = self.table_config.table_for_type(item_type);
+        let now = Utc::now().timestamp_millis();
+
+        let query = format!(
+            "UPDATE {} SET status = ?, retry_count = 0, error_message = NULL, updated_at = ? \
+             WHERE status = ? AND updated_at >= ?",
+            table
+        );
+
+        let result = sqlx::query(&query)
+            .bind(OutboxStatus::PENDING.code())
+            .bind(now)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(since.timestamp_millis())
+            .execute(&self.pool)
+            .await?;
+
+        info!(table = %table, count = result.rows_affected(), "Requeued completed items");
+        Ok(result.rows_affected())
+    }
+
     async fn init_schema(&self) -> Result<()> {
         // Create events table
         let events_schema = format!(
@@ -290,7 +396,8 @@ impl OutboxRepository for SqliteOutboxRepository {
                 retry_count INTEGER NOT NULL DEFAULT 0,
                 error_message TEXT,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER
+                updated_at INTEGER,
+                next_attempt_at INTEGER
             );
             CREATE INDEX IF NOT EXISTS idx_{}_status ON {}(status);
             CREATE INDEX IF NOT EXISTS idx_{}_created_at ON {}(created_at);
@@ -319,7 +426,8 @@ impl OutboxRepository for SqliteOutboxRepository {
                 retry_count INTEGER NOT NULL DEFAULT 0,
                 error_message TEXT,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER
+                updated_at INTEGER,
+                next_attempt_at INTEGER
             );
             CREATE INDEX IF NOT EXISTS idx_{}_status ON {}(status);
             CREATE INDEX IF NOT EXISTS idx_{}_created_at ON {}(created_at);
@@ -335,6 +443,11 @@ impl OutboxRepository for SqliteOutboxRepository {
             .execute(&self.pool)
             .await?;
 
+        // Tables created before retry scheduling lack next_attempt_at
+        for table in [&self.table_config.events_table, &self.table_config.dispatch_jobs_table] {
+            self.add_next_attempt_column(table).await?;
+        }
+
         info!(
             events_table = %self.table_config.events_table,
             dispatch_jobs_table = %self.table_config.dispatch_jobs_table,
@@ -344,7 +457,182 @@ impl OutboxRepository for SqliteOutboxRepository {
         Ok(())
     }
 
+    fn max_batch_size(&self) -> u32 {
+        SQLITE_MAX_BATCH_SIZE
+    }
+
     fn table_config(&self) -> &OutboxTableConfig {
         &self.table_config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn repository() -> SqliteOutboxRepository {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let repository = SqliteOutboxRepository::new(pool);
+        repository.init_schema().await.unwrap();
+        repository
+    }
+
+    async fn insert_pending(repository: &SqliteOutboxRepository, item_type: OutboxItemType, count: usize) {
+        let query = format!(
+            "INSERT INTO {} (id, payload, status, created_at) VALUES (?, '{{}}', ?, ?)",
+            repository.table_config.table_for_type(item_type)
+        );
+        for i in 0..count {
+            sqlx::query(&query)
+                .bind(format!("{}-{}", item_type, i))
+                .bind(OutboxStatus::PENDING.code())
+                .bind(i as i64)
+                .execute(&repository.pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pending_capped_at_backend_limit() {
+        let repository = repository().await;
+        insert_pending(&repository, OutboxItemType::EVENT, SQLITE_MAX_BATCH_SIZE as usize + 10).await;
+
+        let items = repository.fetch_pending_by_type(OutboxItemType::EVENT, 5000).await.unwrap();
+        assert_eq!(items.len(), SQLITE_MAX_BATCH_SIZE as usize);
+
+        // The capped batch can be written back in one statement
+        let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+        repository.mark_in_progress(OutboxItemType::EVENT, ids).await.unwrap();
+        let remaining = repository.fetch_pending_by_type(OutboxItemType::EVENT, 5000).await.unwrap();
+        assert_eq!(remaining.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pending_honors_smaller_limit() {
+        let repository = repository().await;
+        insert_pending(&repository, OutboxItemType::EVENT, 5).await;
+        insert_pending(&repository, OutboxItemType::DISPATCH_JOB, 5).await;
+
+        assert_eq!(repository.fetch_pending_by_type(OutboxItemType::EVENT, 3).await.unwrap().len(), 3);
+        assert_eq!(repository.fetch_pending(4).await.unwrap().len(), 4);
+        // An odd limit goes to events rather than being rounded away
+        let items = repository.fetch_pending(1).await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].item_type, OutboxItemType::EVENT);
+    }
+
+    #[tokio::test]
+    async fn test_count_pending_covers_both_types() {
+        let repository = repository().await;
+        insert_pending(&repository, OutboxItemType::EVENT, 3).await;
+        insert_pending(&repository, OutboxItemType::DISPATCH_JOB, 2).await;
+        assert_eq!(repository.count_pending().await.unwrap(), 5);
+
+        // Items being processed are no longer part of the backlog
+        repository.mark_in_progress(OutboxItemType::EVENT, vec!["EVENT-0".to_string()]).await.unwrap();
+        assert_eq!(repository.count_pending_by_type(OutboxItemType::EVENT).await.unwrap(), 2);
+        assert_eq!(repository.count_pending().await.unwrap(), 4);
+    }
+
+    async fn insert_completed(repository: &SqliteOutboxRepository, item_type: OutboxItemType, id: &str, completed_at: DateTime<Utc>) {
+        let query = format!(
+            "INSERT INTO {} (id, payload, status, retry_count, created_at, updated_at) VALUES (?, '{{}}', ?, 2, ?, ?)",
+            repository.table_config.table_for_type(item_type)
+        );
+        sqlx::query(&query)
+            .bind(id)
+            .bind(OutboxStatus::COMPLETED.code())
+            .bind(completed_at.timestamp_millis())
+            .bind(completed_at.timestamp_millis())
+            .execute(&repository.pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_requeue_completed_since_only_requeues_later_items() {
+        use crate::repository::OutboxRepositoryExt;
+
+        let repository = repository().await;
+        let outage = Utc::now() - chrono::Duration::hours(1);
+        insert_completed(&repository, OutboxItemType::EVENT, "before", outage - chrono::Duration::minutes(5)).await;
+        insert_completed(&repository, OutboxItemType::EVENT, "after", outage + chrono::Duration::minutes(5)).await;
+        insert_completed(&repository, OutboxItemType::DISPATCH_JOB, "job-after", outage + chrono::Duration::minutes(10)).await;
+        insert_pending(&repository, OutboxItemType::EVENT, 1).await;
+
+        // A dry run only counts
+        let summary = repository.requeue_completed(outage, true).await.unwrap();
+        assert!(summary.dry_run);
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+        assert_eq!(repository.fetch_pending_by_type(OutboxItemType::EVENT, 10).await.unwrap().len(), 1);
+
+        let summary = repository.requeue_completed(outage, false).await.unwrap();
+        assert_eq!((summary.events, summary.dispatch_jobs), (1, 1));
+
+        let events = repository.fetch_pending_by_type(OutboxItemType::EVENT, 10).await.unwrap();
+        let requeued = events.iter().find(|i| i.id == "after").unwrap();
+        assert_eq!(requeued.retry_count, 0);
+        assert!(events.iter().all(|i| i.id != "before"));
+        let jobs = repository.fetch_pending_by_type(OutboxItemType::DISPATCH_JOB, 10).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+
+        // Requeued items are no longer completed, so a repeat finds nothing
+        assert_eq!(repository.count_completed_since(OutboxItemType::EVENT, outage).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retried_item_not_fetched_until_due() {
+        let repository = repository().await;
+        insert_pending(&repository, OutboxItemType::EVENT, 2).await;
+
+        let later = Utc::now() + chrono::Duration::minutes(5);
+        repository.schedule_retry(OutboxItemType::EVENT, "EVENT-0", later, Some("Queue unavailable".to_string())).await.unwrap();
+
+        let items = repository.fetch_pending_by_type(OutboxItemType::EVENT, 10).await.unwrap();
+        assert_eq!(items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), ["EVENT-1"]);
+
+        let due = Utc::now() - chrono::Duration::seconds(1);
+        repository.schedule_retry(OutboxItemType::EVENT, "EVENT-0", due, Some("Queue unavailable".to_string())).await.unwrap();
+
+        let items = repository.fetch_pending_by_type(OutboxItemType::EVENT, 10).await.unwrap();
+        let retried = items.iter().find(|i| i.id == "EVENT-0").unwrap();
+        assert_eq!(retried.retry_count, 2);
+        assert_eq!(retried.status, OutboxStatus::PENDING);
+        assert_eq!(retried.error_message.as_deref(), Some("Queue unavailable"));
+        assert_eq!(retried.next_attempt_at.map(|t| t.timestamp_millis()), Some(due.timestamp_millis()));
+    }
+
+    #[tokio::test]
+    async fn test_init_schema_adds_next_attempt_column_to_existing_table() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TABLE outbox_events (id TEXT PRIMARY KEY, pool_code TEXT, mediation_target TEXT, message_group TEXT, \
+             payload TEXT NOT NULL, status INTEGER NOT NULL DEFAULT 0, retry_count INTEGER NOT NULL DEFAULT 0, \
+             error_message TEXT, created_at INTEGER NOT NULL, updated_at INTEGER)",
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let repository = SqliteOutboxRepository::new(pool);
+        repository.init_schema().await.unwrap();
+        // Running again leaves the column in place
+        repository.init_schema().await.unwrap();
+
+        insert_pending(&repository, OutboxItemType::EVENT, 1).await;
+        let items = repository.fetch_pending_by_type(OutboxItemType::EVENT, 10).await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert!(items[0].next_attempt_at.is_none());
+    }
+}
+
@@ -13,7 +13,7 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 
 use crate::AuditLog;
-use crate::AuditLogRepository;
+use crate::{AuditLogFilter, AuditLogRepository};
 use crate::shared::error::PlatformError;
 use crate::shared::middleware::Authenticated;
 
@@ -119,7 +119,7 @@ pub struct AuditLogsQuery {
     #[serde(default)]
     pub page: i32,
 
-    /// Page size (default 50)
+    /// Page size (default 50, max 500)
     #[serde(default = "default_page_size")]
     pub page_size: i32,
 
@@ -132,24 +132,65 @@ pub struct AuditLogsQuery {
     /// Filter by operation (Java calls this "operation", maps to action internally)
     pub operation: Option<String>,
 
+    /// Filter by action (alias for `operation`)
+    pub action: Option<String>,
+
     /// Filter by principal ID
     pub principal_id: Option<String>,
+
+    /// Filter by the acting principal ID (alias for `principalId`)
+    pub actor_principal_id: Option<String>,
+
+    /// Only entries performed at or after this time (RFC 3339)
+    pub from: Option<String>,
+
+    /// Only entries performed before this time (RFC 3339)
+    pub to: Option<String>,
 }
 
 fn default_page_size() -> i32 { 50 }
 
+/// Maximum page size for audit log listing
+const MAX_PAGE_SIZE: i32 = 500;
+
+impl AuditLogsQuery {
+    /// Validate the query and build the repository filter
+    fn to_filter(&self) -> Result<AuditLogFilter, PlatformError> {
+        let from = self.from.as_deref().map(|s| parse_time_param("from", s)).transpose()?;
+        let to = self.to.as_deref().map(|s| parse_time_param("to", s)).transpose()?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Err(PlatformError::validation("from must be before to"));
+            }
+        }
+
+        Ok(AuditLogFilter {
+            entity_type: self.entity_type.clone(),
+            entity_id: self.entity_id.clone(),
+            operation: self.action.clone().or_else(|| self.operation.clone()),
+            principal_id: self.actor_principal_id.clone().or_else(|| self.principal_id.clone()),
+            from,
+            to,
+        })
+    }
+}
+
 /// Audit logs service state
 #[derive(Clone)]
 pub struct AuditLogsState {
     pub audit_log_repo: Arc<AuditLogRepository>,
 }
 
-
-#[allow(dead_code)]
 fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.with_timezone(&Utc))
 }
 
+fn parse_time_param(name: &str, value: &str) -> Result<DateTime<Utc>, PlatformError> {
+    parse_datetime(value).ok_or_else(|| PlatformError::validation(format!(
+        "{} must be an RFC 3339 timestamp", name
+    )))
+}
+
 /// Get distinct entity types
 #[utoipa::path(
     get,
@@ -223,6 +264,9 @@ pub async fn get_audit_log(
 }
 
 /// List audit logs with filters (matches Java AuditLogAdminResource)
+///
+/// Results are sorted by `performedAt` descending; `total` is the count of all
+/// entries matching the filter.
 #[utoipa::path(
     get,
     path = "",
@@ -230,7 +274,8 @@ pub async fn get_audit_log(
     operation_id = "getApiAdminPlatformAuditLogs",
     params(AuditLogsQuery),
     responses(
-        (status = 200, description = "List of audit logs", body = AuditLogListResponse)
+        (status = 200, description = "List of audit logs", body = AuditLogListResponse),
+        (status = 400, description = "Invalid filter or pagination")
     ),
     security(("bearer_auth" = []))
 )]
@@ -243,25 +288,22 @@ pub async fn list_audit_logs(
 
     let page = query.page;
     let page_size = query.page_size;
-    let skip = (page * page_size) as u64;
+    if page < 0 {
+        return Err(PlatformError::validation("page must not be negative"));
+    }
+    if !(1..=MAX_PAGE_SIZE).contains(&page_size) {
+        return Err(PlatformError::validation(format!(
+            "pageSize must be between 1 and {}", MAX_PAGE_SIZE
+        )));
+    }
+    let skip = page as u64 * page_size as u64;
     let limit = page_size as i64;
 
-    let logs = state.audit_log_repo.search(
-        query.entity_type.as_deref(),
-        query.entity_id.as_deref(),
-        query.operation.as_deref(),
-        query.principal_id.as_deref(),
-        skip,
-        limit,
-    ).await?;
+    let filter = query.to_filter()?;
+    let logs = state.audit_log_repo.search(&filter, skip, limit).await?;
 
     // Get total count for pagination
-    let total = state.audit_log_repo.count_with_filters(
-        query.entity_type.as_deref(),
-        query.entity_id.as_deref(),
-        query.operation.as_deref(),
-        query.principal_id.as_deref(),
-    ).await?;
+    let total = state.audit_log_repo.count_with_filters(&filter).await?;
 
     let audit_logs: Vec<AuditLogResponse> = logs.into_iter()
         .map(|l| l.into())
@@ -383,3 +425,41 @@ pub fn audit_logs_router(state: AuditLogsState) -> OpenApiRouter {
         .routes(routes!(get_principal_audit_logs))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_aliases_map_to_filter() {
+        let query: AuditLogsQuery = serde_json::from_value(serde_json::json!({
+            "actorPrincipalId": "actor-1",
+            "action": "CreateClientCommand",
+            "entityType": "Client",
+            "from": "2024-01-01T00:00:00Z",
+            "to": "2024-02-01T00:00:00+02:00",
+        })).unwrap();
+
+        let filter = query.to_filter().unwrap();
+        assert_eq!(filter.principal_id.as_deref(), Some("actor-1"));
+        assert_eq!(filter.operation.as_deref(), Some("CreateClientCommand"));
+        assert_eq!(filter.entity_type.as_deref(), Some("Client"));
+        assert_eq!(filter.to.unwrap().to_rfc3339(), "2024-01-31T22:00:00+00:00");
+    }
+
+    #[test]
+    fn test_invalid_time_range_rejected() {
+        let query = AuditLogsQuery {
+            from: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(query.to_filter().is_err());
+
+        let query = AuditLogsQuery {
+            from: Some("2024-02-01T00:00:00Z".to_string()),
+            to: Some("2024-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert!(query.to_filter().is_err());
+    }
+}
@@ -52,6 +52,40 @@ pub enum AuditAction {
     Other,
 }
 
+/// Why a login attempt failed
+///
+/// Recorded in the audit trail only. Login endpoints return the same error for
+/// every reason so responses can't be used to enumerate accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LoginFailureReason {
+    /// No principal with the submitted email
+    UnknownPrincipal,
+    /// Password did not match (or the principal has no password)
+    InvalidPassword,
+    /// Principal is deactivated
+    Inactive,
+    /// Principal is locked out after repeated failed logins
+    Locked,
+    /// External IDP returned an error or the code exchange failed
+    IdpError,
+    /// ID token from the external IDP failed validation
+    InvalidIdToken,
+}
+
+impl LoginFailureReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnknownPrincipal => "UNKNOWN_PRINCIPAL",
+            Self::InvalidPassword => "INVALID_PASSWORD",
+            Self::Inactive => "INACTIVE",
+            Self::Locked => "LOCKED",
+            Self::IdpError => "IDP_ERROR",
+            Self::InvalidIdToken => "INVALID_ID_TOKEN",
+        }
+    }
+}
+
 /// Audit log entry (matches Java AuditLog schema)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,7 +8,7 @@ pub mod api;
 pub mod service;
 
 // Re-export main types
-pub use entity::{AuditLog, AuditAction};
-pub use repository::AuditLogRepository;
+pub use entity::{AuditLog, AuditAction, LoginFailureReason};
+pub use repository::{AuditLogRepository, AuditLogFilter};
 pub use api::{audit_logs_router};
 pub use service::AuditService;
@@ -1,10 +1,57 @@
 //! Audit Log Repository
 
-use mongodb::{Collection, Database, bson::doc, options::FindOptions};
+use mongodb::{Collection, Database, bson::{doc, Document}, options::FindOptions};
 use futures::TryStreamExt;
+use chrono::{DateTime, Utc};
 use crate::AuditLog;
 use crate::shared::error::Result;
 
+/// Filter for audit log searches (all fields optional, combined with AND)
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub operation: Option<String>,
+    pub principal_id: Option<String>,
+    /// Inclusive lower bound on `performedAt`
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `performedAt`
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl AuditLogFilter {
+    /// Build the MongoDB filter document
+    pub fn to_document(&self) -> Document {
+        let mut filter = doc! {};
+
+        if let Some(ref et) = self.entity_type {
+            filter.insert("entityType", et);
+        }
+        if let Some(ref eid) = self.entity_id {
+            filter.insert("entityId", eid);
+        }
+        if let Some(ref op) = self.operation {
+            filter.insert("operation", op);
+        }
+        if let Some(ref pid) = self.principal_id {
+            filter.insert("principalId", pid);
+        }
+
+        let mut performed_at = doc! {};
+        if let Some(from) = self.from {
+            performed_at.insert("$gte", mongodb::bson::DateTime::from_chrono(from));
+        }
+        if let Some(to) = self.to {
+            performed_at.insert("$lt", mongodb::bson::DateTime::from_chrono(to));
+        }
+        if !performed_at.is_empty() {
+            filter.insert("performedAt", performed_at);
+        }
+
+        filter
+    }
+}
+
 pub struct AuditLogRepository {
     collection: Collection<AuditLog>,
 }
@@ -78,38 +125,20 @@ impl AuditLogRepository {
         Ok(cursor.try_collect().await?)
     }
 
-    /// Search audit logs with filters (matches Java schema)
+    /// Search audit logs with filters, newest first
     pub async fn search(
         &self,
-        entity_type: Option<&str>,
-        entity_id: Option<&str>,
-        operation: Option<&str>,
-        principal_id: Option<&str>,
+        filter: &AuditLogFilter,
         skip: u64,
         limit: i64,
     ) -> Result<Vec<AuditLog>> {
-        let mut filter = doc! {};
-
-        if let Some(et) = entity_type {
-            filter.insert("entityType", et);
-        }
-        if let Some(eid) = entity_id {
-            filter.insert("entityId", eid);
-        }
-        if let Some(op) = operation {
-            filter.insert("operation", op);
-        }
-        if let Some(pid) = principal_id {
-            filter.insert("principalId", pid);
-        }
-
         let options = FindOptions::builder()
             .sort(doc! { "performedAt": -1 })
             .skip(skip)
             .limit(limit)
             .build();
 
-        let cursor = self.collection.find(filter).with_options(options).await?;
+        let cursor = self.collection.find(filter.to_document()).with_options(options).await?;
         Ok(cursor.try_collect().await?)
     }
 
@@ -118,29 +147,8 @@ impl AuditLogRepository {
     }
 
     /// Count audit logs with filters (for pagination)
-    pub async fn count_with_filters(
-        &self,
-        entity_type: Option<&str>,
-        entity_id: Option<&str>,
-        operation: Option<&str>,
-        principal_id: Option<&str>,
-    ) -> Result<i64> {
-        let mut filter = doc! {};
-
-        if let Some(et) = entity_type {
-            filter.insert("entityType", et);
-        }
-        if let Some(eid) = entity_id {
-            filter.insert("entityId", eid);
-        }
-        if let Some(op) = operation {
-            filter.insert("operation", op);
-        }
-        if let Some(pid) = principal_id {
-            filter.insert("principalId", pid);
-        }
-
-        Ok(self.collection.count_documents(filter).await? as i64)
+    pub async fn count_with_filters(&self, filter: &AuditLogFilter) -> Result<i64> {
+        Ok(self.collection.count_documents(filter.to_document()).await? as i64)
     }
 
     /// Find distinct entity types
@@ -159,3 +167,48 @@ impl AuditLogRepository {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_empty_filter_matches_all() {
+        assert!(AuditLogFilter::default().to_document().is_empty());
+    }
+
+    #[test]
+    fn test_filter_document() {
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        let filter = AuditLogFilter {
+            entity_type: Some("Principal".to_string()),
+            operation: Some("UpdateUserCommand".to_string()),
+            principal_id: Some("0HZXEQ5Y8JY5Z".to_string()),
+            from: Some(from),
+            to: Some(to),
+            ..Default::default()
+        };
+
+        let doc = filter.to_document();
+        assert_eq!(doc.get_str("entityType").unwrap(), "Principal");
+        assert_eq!(doc.get_str("operation").unwrap(), "UpdateUserCommand");
+        assert_eq!(doc.get_str("principalId").unwrap(), "0HZXEQ5Y8JY5Z");
+        assert!(doc.get("entityId").is_none());
+
+        let range = doc.get_document("performedAt").unwrap();
+        assert_eq!(range.get_datetime("$gte").unwrap().to_chrono(), from);
+        assert_eq!(range.get_datetime("$lt").unwrap().to_chrono(), to);
+    }
+
+    #[test]
+    fn test_open_ended_time_range() {
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let filter = AuditLogFilter { from: Some(from), ..Default::default() };
+
+        let range = filter.to_document().get_document("performedAt").unwrap().clone();
+        assert!(range.contains_key("$gte"));
+        assert!(!range.contains_key("$lt"));
+    }
+}
@@ -8,6 +8,7 @@ use tracing::{info, error};
 
 use crate::AuditLog;
 use crate::AuditLogRepository;
+use crate::LoginFailureReason;
 use crate::AuthContext;
 use crate::shared::error::Result;
 
@@ -115,15 +116,74 @@ impl AuditService {
         self.insert(log).await
     }
 
-    /// Log a login attempt
-    pub async fn log_login(
+    /// Log a successful login
+    pub async fn log_login_success(
         &self,
-        _email: &str,
-        success: bool,
-        _ip_address: Option<&str>,
+        principal_id: &str,
+        method: &str,
+        ip_address: Option<&str>,
+    ) -> Result<()> {
+        let details = serde_json::json!({
+            "method": method,
+            "ipAddress": ip_address,
+        });
+        let log = AuditLog::new(
+            "Session",
+            None,
+            "LoginCommand",
+            Some(details.to_string()),
+            Some(principal_id.to_string()),
+        );
+        self.insert(log).await
+    }
+
+    /// Log a failed login attempt
+    ///
+    /// `identifier` is the submitted email, or just the email domain when the
+    /// address is not known. Credentials are never recorded.
+    pub async fn log_login_failure(
+        &self,
+        identifier: &str,
+        method: &str,
+        reason: LoginFailureReason,
+        ip_address: Option<&str>,
     ) -> Result<()> {
-        let operation = if success { "LoginCommand" } else { "FailedLoginCommand" };
-        let log = AuditLog::new("Session", None, operation, None, None);
+        let identifier = identifier.trim().to_lowercase();
+        let (email, domain) = match identifier.rsplit_once('@') {
+            Some((_, domain)) => (Some(identifier.as_str()), domain),
+            None => (None, identifier.as_str()),
+        };
+        let details = serde_json::json!({
+            "email": email,
+            "emailDomain": domain,
+            "method": method,
+            "reason": reason.as_str(),
+            "ipAddress": ip_address,
+        });
+        let log = AuditLog::new("Session", None, "FailedLoginCommand", Some(details.to_string()), None);
+        self.insert(log).await
+    }
+
+    /// Log an access token issued by the OAuth token endpoint
+    pub async fn log_token_issued(
+        &self,
+        principal_id: &str,
+        grant_type: &str,
+        client_id: Option<&str>,
+        ip_address: Option<&str>,
+    ) -> Result<()> {
+        let details = serde_json::json!({
+            "grantType": grant_type,
+            "clientId": client_id,
+            "ipAddress": ip_address,
+        });
+        let log = AuditLog::new(
+            "Session",
+            None,
+            "IssueTokenCommand",
+            Some(details.to_string()),
+            Some(principal_id.to_string()),
+        );
         self.insert(log).await
     }
 
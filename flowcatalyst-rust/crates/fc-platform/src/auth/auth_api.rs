@@ -18,12 +18,12 @@ use utoipa::{ToSchema, IntoParams};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::{PrincipalRepository, RefreshTokenRepository};
+use crate::{Principal, PrincipalStore, RefreshTokenRepository};
 use crate::RefreshToken;
-use crate::AuthService;
+use crate::{AuditService, AuthService, LoginFailureReason};
 use crate::PasswordService;
 use crate::shared::error::PlatformError;
-use crate::shared::middleware::Authenticated;
+use crate::shared::middleware::{Authenticated, ClientIp};
 
 /// Login request
 #[derive(Debug, Deserialize, ToSchema)]
@@ -132,7 +132,7 @@ pub struct CurrentUserResponse {
 #[derive(Clone)]
 pub struct AuthState {
     pub auth_service: Arc<AuthService>,
-    pub principal_repo: Arc<PrincipalRepository>,
+    pub principal_repo: Arc<dyn PrincipalStore>,
     pub password_service: Arc<PasswordService>,
     pub refresh_token_repo: Arc<RefreshTokenRepository>,
     /// Session cookie name (default: "fc_session")
@@ -143,13 +143,15 @@ pub struct AuthState {
     pub session_cookie_same_site: String,
     /// Session token expiry in seconds
     pub session_token_expiry_secs: i64,
+    /// Records login successes and failures
+    pub audit_service: Option<Arc<AuditService>>,
 }
 
 impl AuthState {
     /// Create with default cookie settings
     pub fn new(
         auth_service: Arc<AuthService>,
-        principal_repo: Arc<PrincipalRepository>,
+        principal_repo: Arc<dyn PrincipalStore>,
         password_service: Arc<PasswordService>,
         refresh_token_repo: Arc<RefreshTokenRepository>,
     ) -> Self {
@@ -162,9 +164,16 @@ impl AuthState {
             session_cookie_secure: false,
             session_cookie_same_site: "Lax".to_string(),
             session_token_expiry_secs: 28800, // 8 hours
+            audit_service: None,
         }
     }
 
+    /// Record login attempts in the audit log
+    pub fn with_audit_service(mut self, audit_service: Arc<AuditService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
     /// Configure session cookie settings
     pub fn with_session_cookie_settings(
         mut self,
@@ -181,6 +190,81 @@ impl AuthState {
     }
 }
 
+/// Check email/password credentials, returning the failure reason on mismatch
+async fn authenticate_password(
+    state: &AuthState,
+    email: &str,
+    password: &str,
+) -> Result<std::result::Result<Principal, LoginFailureReason>, PlatformError> {
+    let principal = state.principal_repo.find_by_email(email).await?;
+    let (mut principal, upgraded_hash) = match check_password(&state.password_service, principal, password) {
+        Ok(checked) => checked,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    // Persist a re-hash if the stored hash predates the current Argon2 parameters.
+    // Only the hash is written, and only if no one changed it since it was read.
+    let stored_hash = principal.user_identity.as_mut().and_then(|id| id.password_hash.as_mut());
+    if let (Some(upgraded), Some(stored_hash)) = (upgraded_hash, stored_hash) {
+        if let Err(e) = state.principal_repo.replace_password_hash(&principal.id, stored_hash, &upgraded).await {
+            tracing::warn!(principal_id = %principal.id, error = %e, "Failed to persist upgraded password hash");
+        }
+        *stored_hash = upgraded;
+    }
+
+    Ok(Ok(principal))
+}
+
+/// Check a password against the principal found for the login email
+///
+/// Every path runs exactly one Argon2 verification, against a dummy hash when
+/// there is no usable stored one, so failures can't be told apart by timing.
+/// On success, returns any re-hash the caller should persist.
+fn check_password(
+    password_service: &PasswordService,
+    principal: Option<Principal>,
+    password: &str,
+) -> std::result::Result<(Principal, Option<String>), LoginFailureReason> {
+    let Some(principal) = principal else {
+        password_service.verify_dummy(password);
+        return Err(LoginFailureReason::UnknownPrincipal);
+    };
+
+    // Locked accounts are rejected without checking the password
+    if password_service.is_locked(&principal.id) {
+        password_service.verify_dummy(password);
+        return Err(LoginFailureReason::Locked);
+    }
+
+    let stored_hash = principal.user_identity.as_ref().and_then(|id| id.password_hash.as_deref());
+    let verification = match stored_hash {
+        Some(hash) => password_service.verify_and_upgrade(password, hash).ok(),
+        None => {
+            password_service.verify_dummy(password);
+            None
+        }
+    };
+
+    let Some(verification) = verification.filter(|v| v.valid) else {
+        password_service.record_failure(&principal.id);
+        return Err(LoginFailureReason::InvalidPassword);
+    };
+    password_service.record_success(&principal.id);
+
+    if !principal.active {
+        return Err(LoginFailureReason::Inactive);
+    }
+
+    Ok((principal, verification.upgraded_hash))
+}
+
+/// The single error returned for every failed login, so it can't be used to probe for accounts
+fn login_failed() -> PlatformError {
+    PlatformError::Unauthorized {
+        message: "Invalid credentials".to_string(),
+    }
+}
+
 /// Login with email and password
 ///
 /// Authenticates a user with email and password credentials.
@@ -199,39 +283,26 @@ impl AuthState {
 pub async fn login(
     State(state): State<AuthState>,
     jar: CookieJar,
+    ClientIp(client_ip): ClientIp,
     Json(req): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, PlatformError> {
-    // Find principal by email
-    let principal = state
-        .principal_repo
-        .find_by_email(&req.email)
-        .await?
-        .ok_or_else(|| PlatformError::Unauthorized {
-            message: "Invalid credentials".to_string(),
-        })?;
-
-    // Verify password using Argon2id
-    let password_valid = principal.user_identity
-        .as_ref()
-        .and_then(|id| id.password_hash.as_ref())
-        .map(|hash| {
-            state.password_service
-                .verify_password(&req.password, hash)
-                .unwrap_or(false)
-        })
-        .unwrap_or(false);
-
-    if !password_valid {
-        return Err(PlatformError::Unauthorized {
-            message: "Invalid credentials".to_string(),
-        });
-    }
+    // The actual failure reason only goes to the audit trail
+    let principal = match authenticate_password(&state, &req.email, &req.password).await? {
+        Ok(principal) => principal,
+        Err(reason) => {
+            if let Some(ref audit) = state.audit_service {
+                if let Err(e) = audit.log_login_failure(&req.email, "PASSWORD", reason, client_ip.as_deref()).await {
+                    tracing::warn!(error = %e, "Failed to record login failure audit entry");
+                }
+            }
+            return Err(login_failed());
+        }
+    };
 
-    // Check if user is active
-    if !principal.active {
-        return Err(PlatformError::Unauthorized {
-            message: "Account is not active".to_string(),
-        });
+    if let Some(ref audit) = state.audit_service {
+        if let Err(e) = audit.log_login_success(&principal.id, "PASSWORD", client_ip.as_deref()).await {
+            tracing::warn!(principal_id = %principal.id, error = %e, "Failed to record login audit entry");
+        }
     }
 
     // Generate session token
@@ -509,6 +580,100 @@ mod tests {
         );
     }
 
+    /// Principals held in memory, looked up by email
+    struct StaticPrincipals(Vec<Principal>);
+
+    #[async_trait::async_trait]
+    impl PrincipalStore for StaticPrincipals {
+        async fn find_by_id(&self, id: &str) -> crate::shared::error::Result<Option<Principal>> {
+            Ok(self.0.iter().find(|p| p.id == id).cloned())
+        }
+
+        async fn find_by_email(&self, email: &str) -> crate::shared::error::Result<Option<Principal>> {
+            Ok(self.0.iter().find(|p| p.email() == Some(email)).cloned())
+        }
+
+        async fn find_by_service_account(&self, _service_account_id: &str) -> crate::shared::error::Result<Option<Principal>> {
+            Ok(None)
+        }
+
+        async fn insert(&self, _principal: &Principal) -> crate::shared::error::Result<()> {
+            unimplemented!("not used by login")
+        }
+
+        async fn replace_password_hash(&self, _id: &str, _current_hash: &str, _new_hash: &str) -> crate::shared::error::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_failures_are_indistinguishable() {
+        use crate::auth::auth_service::AuthConfig;
+        use crate::auth::password_service::{Argon2Config, PasswordPolicy};
+        use crate::{LockoutConfig, UserScope};
+        use mongodb::{options::ClientOptions, Client as MongoClient};
+
+        let service = PasswordService::new(Argon2Config::testing(), PasswordPolicy::lenient())
+            .with_lockout_config(LockoutConfig { max_failures: 1, ..LockoutConfig::default() });
+        let hash = service.hash_password("correct-password").unwrap();
+        let with_password = |email: &str| {
+            let mut principal = Principal::new_user(email, UserScope::Anchor);
+            principal.user_identity.as_mut().unwrap().password_hash = Some(hash.clone());
+            principal
+        };
+
+        let mut inactive = with_password("inactive@example.com");
+        inactive.active = false;
+        let locked = with_password("locked@example.com");
+        service.record_failure(&locked.id);
+        let principals = vec![
+            with_password("user@example.com"),
+            Principal::new_user("oidc@example.com", UserScope::Anchor),
+            locked,
+            inactive,
+        ];
+
+        // The refresh token repository points at a database login never reaches
+        let db = MongoClient::with_options(ClientOptions::parse("mongodb://localhost:27017").await.unwrap())
+            .unwrap()
+            .database("unused");
+        let state = AuthState::new(
+            Arc::new(AuthService::new(AuthConfig::default())),
+            Arc::new(StaticPrincipals(principals)),
+            Arc::new(service),
+            Arc::new(RefreshTokenRepository::new(&db)),
+        );
+        let attempt = |email: &str, password: &str| {
+            let req = LoginRequest {
+                email: email.to_string(),
+                password: password.to_string(),
+                remember_me: false,
+            };
+            let state = state.clone();
+            async move {
+                let response = match login(State(state), CookieJar::new(), ClientIp(None), Json(req)).await {
+                    Ok(response) => response.into_response(),
+                    Err(e) => e.into_response(),
+                };
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, body)
+            }
+        };
+
+        assert_eq!(attempt("user@example.com", "correct-password").await.0, StatusCode::OK);
+
+        let responses = [
+            attempt("unknown@example.com", "correct-password").await,
+            attempt("user@example.com", "wrong-password").await,
+            attempt("oidc@example.com", "any-password").await,
+            attempt("locked@example.com", "correct-password").await,
+            attempt("inactive@example.com", "correct-password").await,
+        ];
+        assert_eq!(responses[0].0, StatusCode::UNAUTHORIZED);
+        assert!(responses.iter().all(|r| *r == responses[0]), "{:?}", responses);
+    }
+
     #[test]
     fn test_domain_extraction() {
         let email = "user@example.com";
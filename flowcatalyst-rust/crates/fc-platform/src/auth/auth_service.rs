@@ -4,14 +4,53 @@
 //! Supports both RS256 (RSA) for production and HS256 (HMAC) for development.
 
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation, Algorithm};
+use jsonwebtoken::{decode, decode_header, encode, DecodingKey, EncodingKey, Header, Validation, Algorithm};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
 use tracing::{info, warn};
-use crate::{Principal, UserScope};
+use crate::{Principal, PrincipalRepository, UserScope};
 use crate::shared::error::{PlatformError, Result};
 
+/// How long [`AuthService::validate`] trusts a principal's status and token version
+/// before re-reading them, which bounds how late a revocation takes effect
+pub const DEFAULT_PRINCIPAL_STATE_TTL: StdDuration = StdDuration::from_secs(5);
+
+/// Cached principal states kept before expired entries are dropped
+const PRINCIPAL_STATE_CACHE_CAPACITY: usize = 10_000;
+
+/// What token validation needs to know about a principal to honour revocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrincipalTokenState {
+    pub active: bool,
+    pub token_version: i64,
+}
+
+impl From<&Principal> for PrincipalTokenState {
+    fn from(principal: &Principal) -> Self {
+        Self { active: principal.active, token_version: principal.token_version }
+    }
+}
+
+/// Source of principal token state for [`AuthService::validate`]
+#[async_trait]
+pub trait PrincipalStateLookup: Send + Sync {
+    /// Current state of a principal, or None if it no longer exists
+    async fn token_state(&self, principal_id: &str) -> Result<Option<PrincipalTokenState>>;
+}
+
+#[async_trait]
+impl PrincipalStateLookup for PrincipalRepository {
+    async fn token_state(&self, principal_id: &str) -> Result<Option<PrincipalTokenState>> {
+        Ok(self.find_by_id(principal_id).await?.as_ref().map(PrincipalTokenState::from))
+    }
+}
+
 /// JWT Claims for access tokens
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessTokenClaims {
@@ -57,6 +96,10 @@ pub struct AccessTokenClaims {
     /// Roles assigned to this principal
     #[serde(default)]
     pub roles: Vec<String>,
+
+    /// Principal token version at issue time (see [`Principal::token_version`])
+    #[serde(default)]
+    pub ver: i64,
 }
 
 /// Configuration for the auth service
@@ -69,6 +112,10 @@ pub struct AuthConfig {
     /// RSA public key PEM content (for RS256)
     pub rsa_public_key: Option<String>,
 
+    /// Additional RSA public keys (PEM) accepted for verification and
+    /// published in JWKS, e.g. the previous key during a rotation
+    pub additional_rsa_public_keys: Vec<String>,
+
     /// JWT secret key for HS256 (fallback for development)
     pub secret_key: String,
 
@@ -93,6 +140,7 @@ impl Default for AuthConfig {
         Self {
             rsa_private_key: None,
             rsa_public_key: None,
+            additional_rsa_public_keys: Vec::new(),
             secret_key: String::new(),
             issuer: "flowcatalyst".to_string(),
             audience: "flowcatalyst".to_string(),
@@ -119,6 +167,25 @@ impl AuthConfig {
         (private_key, public_key)
     }
 
+    /// Load additional verification public keys from a comma-separated list of PEM file paths
+    ///
+    /// Fails if any listed file can't be read, rather than silently dropping a
+    /// key that tokens in circulation may still be signed with.
+    pub fn load_additional_public_keys(paths: &str) -> Result<Vec<String>> {
+        paths
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let content = fs::read_to_string(p).map_err(|e| PlatformError::Configuration {
+                    message: format!("Could not read JWT public key {}: {}", p, e),
+                })?;
+                info!("Loaded additional JWT public key from file: {}", p);
+                Ok(content)
+            })
+            .collect()
+    }
+
     /// Load key from file path, or from env var if path is empty/missing
     fn load_key_from_path_or_env(path: &str, env_var: &str) -> Option<String> {
         // Try file path first
@@ -231,15 +298,75 @@ pub struct RsaPublicKeyComponents {
     pub e: String,
 }
 
-/// Authentication service for token management
-pub struct AuthService {
-    config: AuthConfig,
+/// RSA public key accepted for token verification
+#[derive(Clone)]
+pub struct VerificationKey {
+    /// Key ID carried in the `kid` header of tokens signed with this key
+    pub kid: String,
+    /// Public key components for JWKS
+    pub components: RsaPublicKeyComponents,
+    decoding_key: DecodingKey,
+}
+
+impl VerificationKey {
+    /// Build a verification key from an RSA public key PEM
+    pub fn from_public_pem(public_key_pem: &str) -> Result<Self> {
+        let decoding_key = DecodingKey::from_rsa_pem(public_key_pem.as_bytes())
+            .map_err(|e| PlatformError::Internal {
+                message: format!("Invalid RSA public key: {}", e)
+            })?;
+
+        Ok(Self {
+            kid: AuthService::generate_key_id(public_key_pem),
+            components: AuthService::extract_rsa_components(public_key_pem)?,
+            decoding_key,
+        })
+    }
+}
+
+/// Signing key plus the keys accepted for verification
+struct KeyRing {
     encoding_key: EncodingKey,
     decoding_key: DecodingKey,
     algorithm: Algorithm,
     key_id: Option<String>,
     /// RSA public key components for JWKS (only set when using RS256)
     rsa_components: Option<RsaPublicKeyComponents>,
+    /// All RSA keys accepted for verification, signing key first
+    verification_keys: Vec<VerificationKey>,
+}
+
+impl KeyRing {
+    /// Select the verification key named by the token's `kid` header.
+    /// Tokens without a `kid` (issued before key IDs were added) use the signing key.
+    fn decoding_key_for(&self, token: &str) -> Result<&DecodingKey> {
+        let header = decode_header(token)
+            .map_err(|e| PlatformError::InvalidToken { message: format!("{}", e) })?;
+
+        match header.kid {
+            Some(kid) if self.algorithm == Algorithm::RS256 => self.verification_keys
+                .iter()
+                .find(|k| k.kid == kid)
+                .map(|k| &k.decoding_key)
+                .ok_or_else(|| PlatformError::InvalidToken {
+                    message: format!("Unknown signing key: {}", kid),
+                }),
+            _ => Ok(&self.decoding_key),
+        }
+    }
+}
+
+/// Authentication service for token management
+///
+/// Keys live behind a lock so [`AuthService::rotate_key`] can promote a new
+/// signing key at runtime while tokens signed with retired keys still verify.
+pub struct AuthService {
+    config: AuthConfig,
+    keys: RwLock<KeyRing>,
+    principal_lookup: Option<Arc<dyn PrincipalStateLookup>>,
+    principal_state_ttl: StdDuration,
+    /// Principal states by ID, with when they were read
+    principal_states: Mutex<HashMap<String, (Instant, Option<PrincipalTokenState>)>>,
 }
 
 impl AuthService {
@@ -250,26 +377,37 @@ impl AuthService {
                 message: format!("Invalid RSA private key: {}", e)
             })?;
 
-        let decoding_key = DecodingKey::from_rsa_pem(public_key_pem.as_bytes())
-            .map_err(|e| PlatformError::Internal {
-                message: format!("Invalid RSA public key: {}", e)
-            })?;
-
-        // Generate key ID from public key hash (like Java)
-        let key_id = Self::generate_key_id(public_key_pem);
+        // Key ID is derived from the public key hash (like Java)
+        let primary = VerificationKey::from_public_pem(public_key_pem)?;
+        let key_id = primary.kid.clone();
 
-        // Extract RSA components for JWKS
-        let rsa_components = Self::extract_rsa_components(public_key_pem)?;
+        let mut verification_keys = vec![primary.clone()];
+        for pem in &config.additional_rsa_public_keys {
+            let key = VerificationKey::from_public_pem(pem)?;
+            if verification_keys.iter().all(|k| k.kid != key.kid) {
+                verification_keys.push(key);
+            }
+        }
 
-        info!("AuthService initialized with RS256 (key_id: {})", key_id);
+        info!(
+            "AuthService initialized with RS256 (key_id: {}, verification keys: {})",
+            key_id,
+            verification_keys.len()
+        );
 
         Ok(Self {
             config,
-            encoding_key,
-            decoding_key,
-            algorithm: Algorithm::RS256,
-            key_id: Some(key_id),
-            rsa_components: Some(rsa_components),
+            keys: RwLock::new(KeyRing {
+                encoding_key,
+                decoding_key: primary.decoding_key,
+                algorithm: Algorithm::RS256,
+                key_id: Some(key_id),
+                rsa_components: Some(primary.components),
+                verification_keys,
+            }),
+            principal_lookup: None,
+            principal_state_ttl: DEFAULT_PRINCIPAL_STATE_TTL,
+            principal_states: Mutex::new(HashMap::new()),
         })
     }
 
@@ -303,11 +441,17 @@ impl AuthService {
 
         Self {
             config,
-            encoding_key,
-            decoding_key,
-            algorithm: Algorithm::HS256,
-            key_id: None,
-            rsa_components: None,
+            keys: RwLock::new(KeyRing {
+                encoding_key,
+                decoding_key,
+                algorithm: Algorithm::HS256,
+                key_id: None,
+                rsa_components: None,
+                verification_keys: Vec::new(),
+            }),
+            principal_lookup: None,
+            principal_state_ttl: DEFAULT_PRINCIPAL_STATE_TTL,
+            principal_states: Mutex::new(HashMap::new()),
         }
     }
 
@@ -327,6 +471,23 @@ impl AuthService {
         Self::new_with_secret(config)
     }
 
+    /// Check token versions against the stored principal in [`AuthService::validate`]
+    pub fn with_principal_repo(self, principal_repo: Arc<PrincipalRepository>) -> Self {
+        self.with_principal_lookup(principal_repo)
+    }
+
+    /// Check token versions against principal state from `lookup` in [`AuthService::validate`]
+    pub fn with_principal_lookup(mut self, lookup: Arc<dyn PrincipalStateLookup>) -> Self {
+        self.principal_lookup = Some(lookup);
+        self
+    }
+
+    /// How long principal state is cached between lookups (default [`DEFAULT_PRINCIPAL_STATE_TTL`])
+    pub fn with_principal_state_ttl(mut self, ttl: StdDuration) -> Self {
+        self.principal_state_ttl = ttl;
+        self
+    }
+
     /// Generate key ID from public key (8 char SHA-256 hash, like Java)
     fn generate_key_id(public_key_pem: &str) -> String {
         use sha2::{Sha256, Digest};
@@ -336,19 +497,93 @@ impl AuthService {
         base64::Engine::encode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, &hash[..6])
     }
 
-    /// Get the key ID (for JWKS)
-    pub fn key_id(&self) -> Option<&str> {
-        self.key_id.as_deref()
+    /// Get the key ID of the current signing key (for JWKS)
+    pub fn key_id(&self) -> Option<String> {
+        self.keys.read().key_id.clone()
     }
 
-    /// Get the RSA public key components (for JWKS)
-    pub fn rsa_components(&self) -> Option<&RsaPublicKeyComponents> {
-        self.rsa_components.as_ref()
+    /// Get the RSA public key components of the current signing key (for JWKS)
+    pub fn rsa_components(&self) -> Option<RsaPublicKeyComponents> {
+        self.keys.read().rsa_components.clone()
+    }
+
+    /// All RSA keys accepted for verification, signing key first (for JWKS)
+    pub fn verification_keys(&self) -> Vec<VerificationKey> {
+        self.keys.read().verification_keys.clone()
     }
 
     /// Get the algorithm being used
     pub fn algorithm(&self) -> Algorithm {
-        self.algorithm
+        self.keys.read().algorithm
+    }
+
+    /// Promote a new RSA keypair to signing key.
+    ///
+    /// The previous signing key stays in the verification set, so tokens it
+    /// signed keep validating until they expire. The pair is checked by
+    /// signing and verifying a probe token before anything is swapped, so a
+    /// mismatched pair is rejected. Returns the new key ID.
+    pub fn rotate_key(&self, new_private_key_pem: &str, new_public_key_pem: &str) -> Result<String> {
+        let encoding_key = EncodingKey::from_rsa_pem(new_private_key_pem.as_bytes())
+            .map_err(|e| PlatformError::Internal {
+                message: format!("Invalid RSA private key: {}", e)
+            })?;
+        let new_key = VerificationKey::from_public_pem(new_public_key_pem)?;
+        let new_kid = new_key.kid.clone();
+        Self::verify_key_pair(&encoding_key, &new_key.decoding_key)?;
+
+        let mut keys = self.keys.write();
+        if keys.algorithm != Algorithm::RS256 {
+            return Err(PlatformError::Internal {
+                message: "Key rotation requires RS256 signing keys".to_string(),
+            });
+        }
+
+        let previous_kid = keys.key_id.replace(new_kid.clone());
+        keys.encoding_key = encoding_key;
+        keys.decoding_key = new_key.decoding_key.clone();
+        keys.rsa_components = Some(new_key.components.clone());
+        keys.verification_keys.retain(|k| k.kid != new_kid);
+        keys.verification_keys.insert(0, new_key);
+
+        info!(
+            "Rotated JWT signing key (key_id: {}, retired: {}, verification keys: {})",
+            new_kid,
+            previous_kid.as_deref().unwrap_or("none"),
+            keys.verification_keys.len()
+        );
+
+        Ok(new_kid)
+    }
+
+    /// Sign a short-lived probe token and verify it, proving the keys belong together
+    fn verify_key_pair(encoding_key: &EncodingKey, decoding_key: &DecodingKey) -> Result<()> {
+        let claims = serde_json::json!({
+            "sub": "key-rotation-probe",
+            "exp": (Utc::now() + Duration::seconds(60)).timestamp(),
+        });
+        let token = encode(&Header::new(Algorithm::RS256), &claims, encoding_key)
+            .map_err(|e| PlatformError::Internal {
+                message: format!("Failed to sign with the new RSA private key: {}", e)
+            })?;
+        decode::<serde_json::Value>(&token, decoding_key, &Validation::new(Algorithm::RS256))
+            .map_err(|e| PlatformError::Internal {
+                message: format!("New RSA public key does not match the private key: {}", e)
+            })?;
+        Ok(())
+    }
+
+    /// Stop accepting tokens signed with a retired key.
+    ///
+    /// The current signing key cannot be removed. Returns whether a key was removed.
+    pub fn remove_verification_key(&self, kid: &str) -> bool {
+        let mut keys = self.keys.write();
+        if keys.key_id.as_deref() == Some(kid) {
+            return false;
+        }
+        let before = keys.verification_keys.len();
+        keys.verification_keys.retain(|k| k.kid != kid);
+        before != keys.verification_keys.len()
     }
 
     /// Generate an access token for a principal
@@ -377,20 +612,24 @@ impl AuthService {
             name: principal.name.clone(),
             clients,
             roles: principal.roles.iter().map(|r| r.role.clone()).collect(),
+            ver: principal.token_version,
         };
 
-        let header = Header::new(self.algorithm);
-        encode(&header, &claims, &self.encoding_key)
+        let keys = self.keys.read();
+        let mut header = Header::new(keys.algorithm);
+        header.kid = keys.key_id.clone();
+        encode(&header, &claims, &keys.encoding_key)
             .map_err(|e| PlatformError::Internal { message: format!("Failed to encode JWT: {}", e) })
     }
 
     /// Validate an access token and extract claims
     pub fn validate_token(&self, token: &str) -> Result<AccessTokenClaims> {
-        let mut validation = Validation::new(self.algorithm);
+        let keys = self.keys.read();
+        let mut validation = Validation::new(keys.algorithm);
         validation.set_issuer(&[&self.config.issuer]);
         validation.set_audience(&[&self.config.audience]);
 
-        decode::<AccessTokenClaims>(token, &self.decoding_key, &validation)
+        decode::<AccessTokenClaims>(token, keys.decoding_key_for(token)?, &validation)
             .map(|data| data.claims)
             .map_err(|e| match e.kind() {
                 jsonwebtoken::errors::ErrorKind::ExpiredSignature => PlatformError::TokenExpired,
@@ -398,6 +637,63 @@ impl AuthService {
             })
     }
 
+    /// Validate an access token and check it against the stored principal
+    ///
+    /// Rejects tokens for principals that were deleted or deactivated, or whose
+    /// token version was bumped by a role/status change after issue. Principal
+    /// state is cached briefly, so a revocation can take up to the cache TTL to
+    /// apply. Failed lookups are returned as-is rather than as token errors.
+    /// Without a principal lookup this is equivalent to [`AuthService::validate_token`].
+    pub async fn validate(&self, token: &str) -> Result<AccessTokenClaims> {
+        let claims = self.validate_token(token)?;
+
+        let Some(ref lookup) = self.principal_lookup else {
+            return Ok(claims);
+        };
+
+        match self.principal_state(lookup.as_ref(), &claims.sub).await? {
+            Some(principal) => Self::check_token_version(&claims, &principal)?,
+            None => {
+                return Err(PlatformError::InvalidToken {
+                    message: "Principal no longer exists".to_string(),
+                });
+            }
+        }
+        Ok(claims)
+    }
+
+    async fn principal_state(&self, lookup: &dyn PrincipalStateLookup, principal_id: &str) -> Result<Option<PrincipalTokenState>> {
+        if let Some((read_at, state)) = self.principal_states.lock().get(principal_id) {
+            if read_at.elapsed() < self.principal_state_ttl {
+                return Ok(*state);
+            }
+        }
+
+        let state = lookup.token_state(principal_id).await?;
+
+        let mut cache = self.principal_states.lock();
+        if cache.len() >= PRINCIPAL_STATE_CACHE_CAPACITY {
+            let ttl = self.principal_state_ttl;
+            cache.retain(|_, (read_at, _)| read_at.elapsed() < ttl);
+        }
+        cache.insert(principal_id.to_string(), (Instant::now(), state));
+        Ok(state)
+    }
+
+    fn check_token_version(claims: &AccessTokenClaims, principal: &PrincipalTokenState) -> Result<()> {
+        if !principal.active {
+            return Err(PlatformError::InvalidToken {
+                message: "Principal is deactivated".to_string(),
+            });
+        }
+        if claims.ver != principal.token_version {
+            return Err(PlatformError::InvalidToken {
+                message: "Token is stale, permissions have changed".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// Check if claims grant access to a specific client
     pub fn has_client_access(&self, claims: &AccessTokenClaims, client_id: &str) -> bool {
         claims.clients.contains(&"*".to_string()) || claims.clients.contains(&client_id.to_string())
@@ -463,6 +759,182 @@ mod tests {
         assert!(!claims.clients.contains(&"*".to_string()));
     }
 
+    fn rsa_config() -> (AuthConfig, String) {
+        let (private_key, public_key) = AuthConfig::generate_rsa_keys(None).unwrap();
+        let config = AuthConfig {
+            rsa_private_key: Some(private_key),
+            rsa_public_key: Some(public_key.clone()),
+            ..AuthConfig::default()
+        };
+        (config, public_key)
+    }
+
+    #[test]
+    fn test_rsa_token_carries_kid() {
+        let (config, _) = rsa_config();
+        let service = AuthService::new(config);
+
+        let principal = Principal::new_user("test@example.com", UserScope::Anchor);
+        let token = service.generate_access_token(&principal).unwrap();
+
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.kid, service.key_id());
+        assert_eq!(service.verification_keys().len(), 1);
+        assert!(service.validate_token(&token).is_ok());
+    }
+
+    #[test]
+    fn test_additional_key_verifies_tokens() {
+        let (old_config, old_public) = rsa_config();
+        let old_service = AuthService::new(old_config);
+        let principal = Principal::new_user("test@example.com", UserScope::Anchor);
+        let old_token = old_service.generate_access_token(&principal).unwrap();
+
+        // New signing key, with the old public key kept for verification
+        let (mut new_config, _) = rsa_config();
+        new_config.additional_rsa_public_keys = vec![old_public];
+        let new_service = AuthService::new(new_config);
+
+        assert_eq!(new_service.verification_keys().len(), 2);
+        assert_eq!(Some(new_service.verification_keys()[0].kid.clone()), new_service.key_id());
+        assert_eq!(new_service.validate_token(&old_token).unwrap().sub, principal.id);
+
+        // Without the old key, its tokens are rejected
+        let (other_config, _) = rsa_config();
+        assert!(AuthService::new(other_config).validate_token(&old_token).is_err());
+    }
+
+    #[test]
+    fn test_unreadable_additional_key_is_an_error() {
+        assert!(AuthConfig::load_additional_public_keys(" , ").unwrap().is_empty());
+        assert!(AuthConfig::load_additional_public_keys("/nonexistent/fc-jwt-old.pem").is_err());
+    }
+
+    #[test]
+    fn test_rotate_key_keeps_old_tokens_valid() {
+        let (config, _) = rsa_config();
+        let service = AuthService::new(config);
+        let principal = Principal::new_user("test@example.com", UserScope::Anchor);
+
+        let old_kid = service.key_id().unwrap();
+        let old_token = service.generate_access_token(&principal).unwrap();
+
+        let (new_private, new_public) = AuthConfig::generate_rsa_keys(None).unwrap();
+        let new_kid = service.rotate_key(&new_private, &new_public).unwrap();
+        assert_ne!(new_kid, old_kid);
+        assert_eq!(service.key_id(), Some(new_kid.clone()));
+
+        // New tokens are signed with the new key; old ones still verify
+        let new_token = service.generate_access_token(&principal).unwrap();
+        assert_eq!(decode_header(&new_token).unwrap().kid, Some(new_kid.clone()));
+        assert!(service.validate_token(&new_token).is_ok());
+        assert!(service.validate_token(&old_token).is_ok());
+
+        let kids: Vec<_> = service.verification_keys().into_iter().map(|k| k.kid).collect();
+        assert_eq!(kids, vec![new_kid.clone(), old_kid.clone()]);
+
+        // Retiring the old key rejects its tokens; the signing key can't be removed
+        assert!(!service.remove_verification_key(&new_kid));
+        assert!(service.remove_verification_key(&old_kid));
+        assert!(service.validate_token(&old_token).is_err());
+        assert!(service.validate_token(&new_token).is_ok());
+    }
+
+    #[test]
+    fn test_rotate_key_rejects_mismatched_pair() {
+        let (config, _) = rsa_config();
+        let service = AuthService::new(config);
+        let old_kid = service.key_id();
+
+        let (new_private, _) = AuthConfig::generate_rsa_keys(None).unwrap();
+        let (_, other_public) = AuthConfig::generate_rsa_keys(None).unwrap();
+        assert!(service.rotate_key(&new_private, &other_public).is_err());
+
+        // Nothing was swapped
+        assert_eq!(service.key_id(), old_kid);
+        assert_eq!(service.verification_keys().len(), 1);
+    }
+
+    #[test]
+    fn test_rotate_key_rejected_for_hmac() {
+        let service = AuthService::new(AuthConfig::default());
+        let (new_private, new_public) = AuthConfig::generate_rsa_keys(None).unwrap();
+        assert!(service.rotate_key(&new_private, &new_public).is_err());
+        assert_eq!(service.algorithm(), Algorithm::HS256);
+    }
+
+    #[test]
+    fn test_token_version_change_invalidates_token() {
+        let service = AuthService::new(AuthConfig::default());
+        let mut principal = Principal::new_user("test@example.com", UserScope::Anchor);
+        let token = service.generate_access_token(&principal).unwrap();
+
+        let claims = service.validate_token(&token).unwrap();
+        assert_eq!(claims.ver, principal.token_version);
+        assert!(AuthService::check_token_version(&claims, &PrincipalTokenState::from(&principal)).is_ok());
+
+        principal.assign_role("platform:admin");
+        principal.bump_token_version();
+        assert!(AuthService::check_token_version(&claims, &PrincipalTokenState::from(&principal)).is_err());
+
+        let reissued = service.validate_token(&service.generate_access_token(&principal).unwrap()).unwrap();
+        assert!(AuthService::check_token_version(&reissued, &PrincipalTokenState::from(&principal)).is_ok());
+
+        principal.deactivate();
+        let reissued = service.validate_token(&service.generate_access_token(&principal).unwrap()).unwrap();
+        assert!(AuthService::check_token_version(&reissued, &PrincipalTokenState::from(&principal)).is_err());
+    }
+
+    #[test]
+    fn test_unchanged_active_state_keeps_tokens_valid() {
+        let service = AuthService::new(AuthConfig::default());
+        let mut principal = Principal::new_user("test@example.com", UserScope::Anchor);
+        let claims = service.validate_token(&service.generate_access_token(&principal).unwrap()).unwrap();
+
+        // e.g. a PATCH with "active": true on an active principal
+        principal.activate();
+        assert!(AuthService::check_token_version(&claims, &PrincipalTokenState::from(&principal)).is_ok());
+
+        principal.deactivate();
+        let version = principal.token_version;
+        principal.deactivate();
+        assert_eq!(principal.token_version, version);
+    }
+
+    struct CountingLookup {
+        state: Mutex<Option<PrincipalTokenState>>,
+        lookups: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PrincipalStateLookup for CountingLookup {
+        async fn token_state(&self, _principal_id: &str) -> Result<Option<PrincipalTokenState>> {
+            self.lookups.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(*self.state.lock())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_caches_principal_state_until_ttl() {
+        let principal = Principal::new_user("test@example.com", UserScope::Anchor);
+        let lookup = Arc::new(CountingLookup {
+            state: Mutex::new(Some(PrincipalTokenState::from(&principal))),
+            lookups: Default::default(),
+        });
+        let service = AuthService::new(AuthConfig::default()).with_principal_lookup(lookup.clone());
+        let token = service.generate_access_token(&principal).unwrap();
+
+        assert!(service.validate(&token).await.is_ok());
+        *lookup.state.lock() = None;
+        assert!(service.validate(&token).await.is_ok(), "state is served from cache within the TTL");
+        assert_eq!(lookup.lookups.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let service = AuthService::new(AuthConfig::default())
+            .with_principal_lookup(lookup.clone())
+            .with_principal_state_ttl(StdDuration::ZERO);
+        assert!(matches!(service.validate(&token).await, Err(PlatformError::InvalidToken { .. })));
+    }
+
     #[test]
     fn test_extract_bearer_token() {
         assert_eq!(extract_bearer_token("Bearer abc123"), Some("abc123"));
@@ -1,13 +1,34 @@
 //! Authorization Code Repository
 //!
 //! MongoDB repository for OAuth2 authorization codes.
+//!
+//! The token endpoint talks to codes through [`AuthorizationCodeStore`] so
+//! the authorization-code grant works across instances; an in-memory store
+//! is provided for tests.
+
+use std::collections::HashMap;
 
+use async_trait::async_trait;
 use chrono::Utc;
 use mongodb::{bson::doc, Collection, Database};
+use tokio::sync::Mutex;
 
 use crate::AuthorizationCode;
 use crate::shared::error::Result;
 
+/// Storage for issued authorization codes.
+#[async_trait]
+pub trait AuthorizationCodeStore: Send + Sync {
+    /// Store a newly issued code.
+    async fn insert(&self, code: &AuthorizationCode) -> Result<()>;
+
+    /// Atomically remove and return a valid (not used, not expired) code.
+    ///
+    /// Returns `None` if the code is unknown, expired, or was already
+    /// exchanged, so a code can only ever be redeemed once.
+    async fn consume(&self, code: &str) -> Result<Option<AuthorizationCode>>;
+}
+
 /// Repository for authorization codes.
 pub struct AuthorizationCodeRepository {
     collection: Collection<AuthorizationCode>,
@@ -49,6 +70,19 @@ impl AuthorizationCodeRepository {
         Ok(result)
     }
 
+    /// Delete and return a valid authorization code in a single operation.
+    pub async fn consume_valid_code(&self, code: &str) -> Result<Option<AuthorizationCode>> {
+        let now = Utc::now();
+        let result = self.collection
+            .find_one_and_delete(doc! {
+                "_id": code,
+                "used": false,
+                "expires_at": { "$gt": now }
+            })
+            .await?;
+        Ok(result)
+    }
+
     /// Mark an authorization code as used.
     pub async fn mark_as_used(&self, code: &str) -> Result<bool> {
         let result = self.collection
@@ -111,3 +145,74 @@ impl AuthorizationCodeRepository {
         Ok(count)
     }
 }
+
+#[async_trait]
+impl AuthorizationCodeStore for AuthorizationCodeRepository {
+    async fn insert(&self, code: &AuthorizationCode) -> Result<()> {
+        AuthorizationCodeRepository::insert(self, code).await
+    }
+
+    async fn consume(&self, code: &str) -> Result<Option<AuthorizationCode>> {
+        self.consume_valid_code(code).await
+    }
+}
+
+/// In-memory authorization code store for tests and single-instance setups.
+#[derive(Default)]
+pub struct InMemoryAuthorizationCodeStore {
+    codes: Mutex<HashMap<String, AuthorizationCode>>,
+}
+
+impl InMemoryAuthorizationCodeStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuthorizationCodeStore for InMemoryAuthorizationCodeStore {
+    async fn insert(&self, code: &AuthorizationCode) -> Result<()> {
+        self.codes.lock().await.insert(code.code.clone(), code.clone());
+        Ok(())
+    }
+
+    async fn consume(&self, code: &str) -> Result<Option<AuthorizationCode>> {
+        let removed = self.codes.lock().await.remove(code);
+        Ok(removed.filter(|c| c.is_valid()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_code(code: &str) -> AuthorizationCode {
+        AuthorizationCode::new(
+            code.to_string(),
+            "client-123".to_string(),
+            "principal-456".to_string(),
+            "https://example.com/callback".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_code_is_single_use() {
+        let store = InMemoryAuthorizationCodeStore::new();
+        store.insert(&new_code("abc")).await.unwrap();
+
+        let consumed = store.consume("abc").await.unwrap();
+        assert_eq!(consumed.map(|c| c.principal_id), Some("principal-456".to_string()));
+        assert!(store.consume("abc").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_rejects_expired_and_unknown_codes() {
+        let store = InMemoryAuthorizationCodeStore::new();
+        let mut expired = new_code("old");
+        expired.expires_at = Utc::now() - chrono::Duration::seconds(1);
+        store.insert(&expired).await.unwrap();
+
+        assert!(store.consume("old").await.unwrap().is_none());
+        assert!(store.consume("missing").await.unwrap().is_none());
+    }
+}
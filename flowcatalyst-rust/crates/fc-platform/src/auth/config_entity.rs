@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use bson::serde_helpers::chrono_datetime_as_bson_datetime;
 
+use crate::UserScope;
+
 /// Auth provider type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -220,6 +222,15 @@ impl ClientAuthConfig {
     }
 
     /// Get all accessible client IDs for this config
+    /// Scope given to users created through this config
+    pub fn user_scope(&self) -> UserScope {
+        match self.config_type {
+            AuthConfigType::Anchor => UserScope::Anchor,
+            AuthConfigType::Partner => UserScope::Partner,
+            AuthConfigType::Client => UserScope::Client,
+        }
+    }
+
     pub fn accessible_clients(&self) -> Vec<String> {
         let mut clients = Vec::new();
         if let Some(ref primary) = self.primary_client_id {
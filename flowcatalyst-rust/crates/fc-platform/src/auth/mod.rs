@@ -29,6 +29,9 @@ pub mod oidc_sync_service;
 pub mod authorization_code;
 pub mod authorization_code_repository;
 
+// Pending authorizations
+pub mod pending_auth_repository;
+
 // Refresh tokens
 pub mod refresh_token;
 pub mod refresh_token_repository;
@@ -39,8 +42,8 @@ pub use config_repository::ClientAuthConfigRepository;
 pub use config_api::{anchor_domains_router, client_auth_configs_router, idp_role_mappings_router, AuthConfigState};
 pub use auth_api::auth_router;
 pub use auth_service::AuthService;
-pub use oauth_api::{oauth_router, OAuthState};
+pub use oauth_api::{oauth_router, OAuthState, ClientSecretVerification, ClientAuthConfigResolver, OidcPrincipalResolver};
 pub use oauth_clients_api::oauth_clients_router;
 pub use oidc_login_api::oidc_login_router;
 pub use oidc_service::OidcService;
-pub use password_service::PasswordService;
+pub use password_service::{PasswordService, PasswordVerification, LockoutConfig, DEFAULT_LOCKOUT_PRUNE_INTERVAL};
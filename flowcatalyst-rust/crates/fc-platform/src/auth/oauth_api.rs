@@ -16,17 +16,22 @@ use utoipa::{ToSchema, IntoParams};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
-use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use chrono::Utc;
+use bson::serde_helpers::chrono_datetime_as_bson_datetime;
 use tracing::{info, warn, error};
 
-use crate::{Principal, AuthorizationCode, RefreshToken};
-use crate::{OAuthClientRepository, PrincipalRepository, AuthorizationCodeRepository, RefreshTokenRepository};
-use crate::AuthService;
-use crate::OidcService;
+use fc_secrets::{Provider as SecretsProvider, SecretsError};
+
+use crate::{Principal, AuthorizationCode, RefreshToken, OAuthClient};
+use crate::{ClientAuthConfigRepository, OAuthClientStore, PrincipalStore, RefreshTokenRepository};
+use crate::auth::authorization_code_repository::AuthorizationCodeStore;
+use crate::auth::pending_auth_repository::PendingAuthStore;
+use crate::{AuditService, AuthService};
+use crate::{OidcService, OidcSyncService};
+use crate::auth::oidc_service::IdTokenClaims;
 use crate::shared::error::PlatformError;
+use crate::shared::middleware::ClientIp;
 
 /// Authorization request parameters
 #[derive(Debug, Deserialize, IntoParams)]
@@ -87,37 +92,161 @@ pub struct ErrorResponse {
 /// OAuth2 state
 #[derive(Clone)]
 pub struct OAuthState {
-    pub oauth_client_repo: Arc<OAuthClientRepository>,
-    pub principal_repo: Arc<PrincipalRepository>,
+    /// OAuth clients (MongoDB outside tests)
+    pub oauth_client_repo: Arc<dyn OAuthClientStore>,
+    /// Principals tokens are issued for (MongoDB outside tests)
+    pub principal_repo: Arc<dyn PrincipalStore>,
     pub auth_service: Arc<AuthService>,
     pub oidc_service: Arc<OidcService>,
+    /// Resolves principals from validated IDP identities
+    pub principal_resolver: Arc<dyn OidcPrincipalResolver>,
     /// Authorization code storage (MongoDB for distributed deployment)
-    pub auth_code_repo: Arc<AuthorizationCodeRepository>,
+    pub auth_code_store: Arc<dyn AuthorizationCodeStore>,
+    /// Pending authorization states, for CSRF protection (MongoDB for distributed deployment)
+    pub pending_auth_store: Arc<dyn PendingAuthStore>,
     /// Refresh token storage for token rotation
     pub refresh_token_repo: Arc<RefreshTokenRepository>,
-    /// Pending authorization states (for CSRF protection)
-    pub pending_states: Arc<RwLock<HashMap<String, PendingAuth>>>,
+    /// Holds client secret hashes referenced by `OAuthClient::client_secret_ref`
+    pub secrets_provider: Option<Arc<dyn SecretsProvider>>,
+    /// How the client_credentials grant checks client secrets
+    pub client_secret_verification: ClientSecretVerification,
+    /// Records issued tokens
+    pub audit_service: Option<Arc<AuditService>>,
+}
+
+/// Client secret verification mode for the client_credentials grant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientSecretVerification {
+    /// Secret must match the hash stored under the client's `client_secret_ref`
+    #[default]
+    Strict,
+    /// Clients without a stored secret accept any non-empty secret (development only)
+    Lenient,
+}
+
+impl std::str::FromStr for ClientSecretVerification {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "lenient" => Ok(Self::Lenient),
+            other => Err(format!("Unknown client secret verification mode: {}", other)),
+        }
+    }
+}
+
+/// Resolves the platform principal for an identity asserted by an external IDP
+#[async_trait::async_trait]
+pub trait OidcPrincipalResolver: Send + Sync {
+    /// Find or create the principal for validated ID token claims.
+    /// Identities no auth configuration admits are rejected with `Forbidden`.
+    async fn resolve(&self, claims: &IdTokenClaims) -> Result<Principal, PlatformError>;
+}
+
+/// Resolves principals the way the federated login flow does: the email domain's
+/// [`ClientAuthConfig`](crate::ClientAuthConfig) must trust the token issuer, and
+/// decides the scope and home client of new users. Users are linked by issuer and subject.
+pub struct ClientAuthConfigResolver {
+    client_auth_config_repo: Arc<ClientAuthConfigRepository>,
+    oidc_sync_service: Arc<OidcSyncService>,
+}
+
+impl ClientAuthConfigResolver {
+    pub fn new(
+        client_auth_config_repo: Arc<ClientAuthConfigRepository>,
+        oidc_sync_service: Arc<OidcSyncService>,
+    ) -> Self {
+        Self { client_auth_config_repo, oidc_sync_service }
+    }
+}
+
+#[async_trait::async_trait]
+impl OidcPrincipalResolver for ClientAuthConfigResolver {
+    async fn resolve(&self, claims: &IdTokenClaims) -> Result<Principal, PlatformError> {
+        let email = claims.email.as_deref()
+            .ok_or_else(|| PlatformError::forbidden("Identity token has no email claim"))?;
+        let domain = email.rsplit_once('@').map(|(_, d)| d.to_lowercase())
+            .ok_or_else(|| PlatformError::forbidden("Identity token email is invalid"))?;
+
+        let config = self.client_auth_config_repo.find_by_email_domain(&domain).await?
+            .ok_or_else(|| PlatformError::forbidden(format!("No authentication configuration for {}", domain)))?;
+        if !config.is_valid_issuer(&claims.iss) {
+            warn!(domain = %domain, issuer = %claims.iss, "Token issuer is not trusted for email domain");
+            return Err(PlatformError::forbidden("Identity provider is not trusted for this email domain"));
+        }
+
+        self.oidc_sync_service.sync_linked_oidc_login(
+            email,
+            claims.name.as_deref().unwrap_or(email),
+            &claims.sub,
+            &claims.iss,
+            config.primary_client_id.as_deref(),
+            config.user_scope(),
+            &claims.roles.clone().unwrap_or_default(),
+        ).await
+    }
 }
 
-/// Pending authorization (between authorize and callback)
-#[derive(Debug, Clone)]
+/// Pending authorization (between authorize and callback), keyed by a
+/// server-generated state
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingAuth {
+    /// Server-generated state - also used as MongoDB _id
+    #[serde(rename = "_id")]
+    pub state: String,
     pub client_id: String,
+    /// The client's own `state`, returned to it unchanged
+    pub client_state: Option<String>,
     pub redirect_uri: String,
     pub scope: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
     pub nonce: Option<String>,
+    /// External OIDC provider the user was sent to, if any
+    pub provider: Option<String>,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
     pub created_at: chrono::DateTime<Utc>,
+    /// When the authorization lapses if the user never completes it
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+/// How long a user has to complete an authorization: 10 minutes
+const PENDING_AUTH_EXPIRY_SECONDS: i64 = 600;
+
+impl PendingAuth {
+    pub fn new(state: impl Into<String>, client_id: impl Into<String>, redirect_uri: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            state: state.into(),
+            client_id: client_id.into(),
+            client_state: None,
+            redirect_uri: redirect_uri.into(),
+            scope: None,
+            code_challenge: None,
+            code_challenge_method: None,
+            nonce: None,
+            provider: None,
+            created_at: now,
+            expires_at: now + chrono::Duration::seconds(PENDING_AUTH_EXPIRY_SECONDS),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
 }
 
 impl OAuthState {
     pub fn new(
-        oauth_client_repo: Arc<OAuthClientRepository>,
-        principal_repo: Arc<PrincipalRepository>,
+        oauth_client_repo: Arc<dyn OAuthClientStore>,
+        principal_repo: Arc<dyn PrincipalStore>,
         auth_service: Arc<AuthService>,
         oidc_service: Arc<OidcService>,
-        auth_code_repo: Arc<AuthorizationCodeRepository>,
+        principal_resolver: Arc<dyn OidcPrincipalResolver>,
+        auth_code_store: Arc<dyn AuthorizationCodeStore>,
+        pending_auth_store: Arc<dyn PendingAuthStore>,
         refresh_token_repo: Arc<RefreshTokenRepository>,
     ) -> Self {
         Self {
@@ -125,11 +254,32 @@ impl OAuthState {
             principal_repo,
             auth_service,
             oidc_service,
-            auth_code_repo,
+            principal_resolver,
+            auth_code_store,
+            pending_auth_store,
             refresh_token_repo,
-            pending_states: Arc::new(RwLock::new(HashMap::new())),
+            secrets_provider: None,
+            client_secret_verification: ClientSecretVerification::Strict,
+            audit_service: None,
         }
     }
+
+    /// Record issued tokens in the audit log
+    pub fn with_audit_service(mut self, audit_service: Arc<AuditService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
+    /// Verify client secrets against hashes held by this provider
+    pub fn with_secrets_provider(mut self, provider: Arc<dyn SecretsProvider>) -> Self {
+        self.secrets_provider = Some(provider);
+        self
+    }
+
+    pub fn with_client_secret_verification(mut self, mode: ClientSecretVerification) -> Self {
+        self.client_secret_verification = mode;
+        self
+    }
 }
 
 /// OIDC callback query parameters
@@ -200,23 +350,24 @@ pub async fn authorize(
         }
     }
 
-    // Generate state for CSRF protection if not provided
-    let state_param = req.state.clone().unwrap_or_else(|| generate_random_string(32));
+    // Pending authorizations are keyed by our own state, so a client-chosen
+    // value can't collide with or replace another user's authorization
+    let state_param = generate_random_string(32);
 
     // Store pending authorization
     let pending = PendingAuth {
-        client_id: req.client_id.clone(),
-        redirect_uri: req.redirect_uri.clone(),
+        client_state: req.state.clone(),
         scope: req.scope.clone(),
         code_challenge: req.code_challenge.clone(),
         code_challenge_method: req.code_challenge_method.clone(),
         nonce: req.nonce.clone(),
-        created_at: Utc::now(),
+        provider: req.provider.clone(),
+        ..PendingAuth::new(state_param.clone(), req.client_id.clone(), req.redirect_uri.clone())
     };
 
-    {
-        let mut pending_states = state.pending_states.write().await;
-        pending_states.insert(state_param.clone(), pending);
+    if let Err(e) = state.pending_auth_store.insert(&pending).await {
+        error!(error = %e, "Failed to store pending authorization");
+        return error_redirect(&req.redirect_uri, "server_error", "Internal error", req.state.as_deref());
     }
 
     // If external provider specified, redirect to OIDC provider
@@ -258,12 +409,14 @@ pub async fn authorize(
 )]
 pub async fn token(
     State(state): State<OAuthState>,
+    ClientIp(client_ip): ClientIp,
     Form(req): Form<TokenRequest>,
 ) -> Response {
+    let client_ip = client_ip.as_deref();
     match req.grant_type.as_str() {
-        "authorization_code" => handle_authorization_code_grant(state, req).await,
-        "refresh_token" => handle_refresh_token_grant(state, req).await,
-        "client_credentials" => handle_client_credentials_grant(state, req).await,
+        "authorization_code" => handle_authorization_code_grant(state, req, client_ip).await,
+        "refresh_token" => handle_refresh_token_grant(state, req, client_ip).await,
+        "client_credentials" => handle_client_credentials_grant(state, req, client_ip).await,
         _ => (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -274,7 +427,7 @@ pub async fn token(
     }
 }
 
-async fn handle_authorization_code_grant(state: OAuthState, req: TokenRequest) -> Response {
+async fn handle_authorization_code_grant(state: OAuthState, req: TokenRequest, client_ip: Option<&str>) -> Response {
     let code = match req.code {
         Some(c) => c,
         None => {
@@ -288,8 +441,8 @@ async fn handle_authorization_code_grant(state: OAuthState, req: TokenRequest) -
         }
     };
 
-    // Find valid authorization code (not used, not expired)
-    let auth_code = match state.auth_code_repo.find_valid_code(&code).await {
+    // Consume the code atomically so concurrent exchanges can't both succeed
+    let auth_code = match state.auth_code_store.consume(&code).await {
         Ok(Some(c)) => c,
         Ok(None) => {
             return (
@@ -312,18 +465,6 @@ async fn handle_authorization_code_grant(state: OAuthState, req: TokenRequest) -
         }
     };
 
-    // Mark code as used (single-use enforcement)
-    if let Err(e) = state.auth_code_repo.mark_as_used(&code).await {
-        error!(error = %e, "Failed to mark authorization code as used");
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "server_error".to_string(),
-                error_description: None,
-            }),
-        ).into_response();
-    }
-
     // Validate redirect_uri
     if req.redirect_uri.as_deref() != Some(&auth_code.redirect_uri) {
         return (
@@ -421,6 +562,7 @@ async fn handle_authorization_code_grant(state: OAuthState, req: TokenRequest) -
     };
 
     info!(principal_id = %principal.id, client_id = %auth_code.client_id, "Token issued via authorization code grant");
+    audit_token_issued(&state, &principal.id, "authorization_code", Some(auth_code.client_id.as_str()), client_ip).await;
 
     (
         StatusCode::OK,
@@ -438,7 +580,7 @@ async fn handle_authorization_code_grant(state: OAuthState, req: TokenRequest) -
     ).into_response()
 }
 
-async fn handle_refresh_token_grant(state: OAuthState, req: TokenRequest) -> Response {
+async fn handle_refresh_token_grant(state: OAuthState, req: TokenRequest, client_ip: Option<&str>) -> Response {
     // Validate refresh_token parameter
     let refresh_token_str = match req.refresh_token {
         Some(t) => t,
@@ -558,6 +700,7 @@ async fn handle_refresh_token_grant(state: OAuthState, req: TokenRequest) -> Res
     }
 
     info!(principal_id = %principal.id, "Token refreshed via refresh_token grant");
+    audit_token_issued(&state, &principal.id, "refresh_token", req.client_id.as_deref(), client_ip).await;
 
     (
         StatusCode::OK,
@@ -575,7 +718,7 @@ async fn handle_refresh_token_grant(state: OAuthState, req: TokenRequest) -> Res
     ).into_response()
 }
 
-async fn handle_client_credentials_grant(state: OAuthState, req: TokenRequest) -> Response {
+async fn handle_client_credentials_grant(state: OAuthState, req: TokenRequest, client_ip: Option<&str>) -> Response {
     let client_id = match req.client_id {
         Some(id) => id,
         None => {
@@ -637,18 +780,59 @@ async fn handle_client_credentials_grant(state: OAuthState, req: TokenRequest) -
         ).into_response();
     }
 
-    if client_secret.is_empty() {
-        return (
-            StatusCode::UNAUTHORIZED,
-            Json(ErrorResponse {
-                error: "invalid_client".to_string(),
-                error_description: Some("Invalid client credentials".to_string()),
-            }),
-        ).into_response();
+    match verify_client_secret(
+        state.secrets_provider.as_ref(),
+        state.client_secret_verification,
+        &client,
+        &client_secret,
+    ).await {
+        Ok(true) => {}
+        Ok(false) => {
+            warn!(client_id = %client_id, "Client secret verification failed");
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    error: "invalid_client".to_string(),
+                    error_description: Some("Invalid client credentials".to_string()),
+                }),
+            ).into_response();
+        }
+        Err(e) => {
+            error!(error = %e, client_id = %client_id, "Failed to fetch client secret");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "server_error".to_string(),
+                    error_description: None,
+                }),
+            ).into_response();
+        }
     }
 
-    // Find or create service account principal for this client
-    let principal = Principal::new_service(&client_id, &client.client_name);
+    // Tokens are validated against the stored principal, so issue for the persisted one
+    let principal = match find_or_create_service_principal(&state, &client).await {
+        Ok(p) if p.active => p,
+        Ok(_) => {
+            warn!(client_id = %client_id, "Service account principal is deactivated");
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    error: "invalid_client".to_string(),
+                    error_description: Some("Invalid client credentials".to_string()),
+                }),
+            ).into_response();
+        }
+        Err(e) => {
+            error!(error = %e, client_id = %client_id, "Failed to resolve service account principal");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "server_error".to_string(),
+                    error_description: None,
+                }),
+            ).into_response();
+        }
+    };
 
     let access_token = match state.auth_service.generate_access_token(&principal) {
         Ok(t) => t,
@@ -665,6 +849,7 @@ async fn handle_client_credentials_grant(state: OAuthState, req: TokenRequest) -
     };
 
     info!(client_id = %client_id, "Token issued via client credentials grant");
+    audit_token_issued(&state, &principal.id, "client_credentials", Some(client_id.as_str()), client_ip).await;
 
     (
         StatusCode::OK,
@@ -682,6 +867,41 @@ async fn handle_client_credentials_grant(state: OAuthState, req: TokenRequest) -
     ).into_response()
 }
 
+/// The client's linked service account principal, else the one keyed by its
+/// client ID, creating that on first use
+async fn find_or_create_service_principal(
+    state: &OAuthState,
+    client: &OAuthClient,
+) -> Result<Principal, PlatformError> {
+    if let Some(ref principal_id) = client.service_account_principal_id {
+        if let Some(principal) = state.principal_repo.find_by_id(principal_id).await? {
+            return Ok(principal);
+        }
+    }
+    if let Some(principal) = state.principal_repo.find_by_service_account(&client.client_id).await? {
+        return Ok(principal);
+    }
+
+    let principal = Principal::new_service(&client.client_id, &client.client_name);
+    state.principal_repo.insert(&principal).await?;
+    info!(client_id = %client.client_id, principal_id = %principal.id, "Created service account principal");
+    Ok(principal)
+}
+
+async fn audit_token_issued(
+    state: &OAuthState,
+    principal_id: &str,
+    grant_type: &str,
+    client_id: Option<&str>,
+    client_ip: Option<&str>,
+) {
+    if let Some(ref audit) = state.audit_service {
+        if let Err(e) = audit.log_token_issued(principal_id, grant_type, client_id, client_ip).await {
+            warn!(principal_id = %principal_id, error = %e, "Failed to record token issued audit entry");
+        }
+    }
+}
+
 /// OIDC callback endpoint
 #[utoipa::path(
     get,
@@ -697,7 +917,7 @@ pub async fn oidc_callback(
     State(state): State<OAuthState>,
     Query(params): Query<OidcCallbackParams>,
 ) -> Response {
-    let _oidc_code = match &params.code {
+    let oidc_code = match &params.code {
         Some(c) => c,
         None => {
             let error = params.error.clone().unwrap_or_else(|| "unknown".to_string());
@@ -727,14 +947,19 @@ pub async fn oidc_callback(
     };
 
     // Retrieve and validate pending authorization
-    let pending = {
-        let mut pending_states = state.pending_states.write().await;
-        pending_states.remove(state_param)
-    };
-
-    let pending = match pending {
-        Some(p) => p,
-        None => {
+    let pending = match state.pending_auth_store.consume(state_param).await {
+        Ok(Some(p)) => p,
+        Err(e) => {
+            error!(error = %e, "Failed to load pending authorization");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "server_error".to_string(),
+                    error_description: Some("Internal error".to_string()),
+                }),
+            ).into_response();
+        }
+        Ok(None) => {
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ErrorResponse {
@@ -745,33 +970,88 @@ pub async fn oidc_callback(
         }
     };
 
+    let client_state = pending.client_state.as_deref();
+
+    // The provider comes from our own record of the authorization, never the callback
+    let provider_id = match pending.provider.clone() {
+        Some(p) => p,
+        None => {
+            return error_redirect(&pending.redirect_uri, "invalid_request", "Authorization was not started with an external provider", client_state);
+        }
+    };
+
+    // Exchange the IDP code for tokens
+    let tokens = match state.oidc_service.exchange_code(&provider_id, oidc_code).await {
+        Ok(t) => t,
+        Err(e) => {
+            error!(error = %e, provider = %provider_id, "OIDC token exchange failed");
+            return error_redirect(&pending.redirect_uri, "server_error", "Failed to exchange authorization code", client_state);
+        }
+    };
+
+    let id_token = match tokens.id_token {
+        Some(t) => t,
+        None => {
+            error!(provider = %provider_id, "OIDC token response did not include an ID token");
+            return error_redirect(&pending.redirect_uri, "server_error", "Identity provider did not return an ID token", client_state);
+        }
+    };
+
+    // Validate the ID token (signature, issuer, audience, nonce)
+    let claims = match state.oidc_service.validate_id_token(&provider_id, &id_token, pending.nonce.as_deref()).await {
+        Ok(c) => c,
+        Err(e) => {
+            warn!(error = %e, provider = %provider_id, "ID token validation failed");
+            return error_redirect(&pending.redirect_uri, "access_denied", "Failed to validate identity token", client_state);
+        }
+    };
+
+    // Sync user and roles, linked by issuer and subject
+    let principal = match state.principal_resolver.resolve(&claims).await {
+        Ok(p) => p,
+        Err(PlatformError::Forbidden { message }) => {
+            warn!(provider = %provider_id, subject = %claims.sub, reason = %message, "OIDC identity rejected");
+            return error_redirect(&pending.redirect_uri, "access_denied", &message, client_state);
+        }
+        Err(e) => {
+            error!(error = %e, "User sync failed");
+            return error_redirect(&pending.redirect_uri, "server_error", "Failed to resolve user", client_state);
+        }
+    };
+
+    if !principal.active {
+        return error_redirect(&pending.redirect_uri, "access_denied", "Account is not active", client_state);
+    }
+
     let auth_code_str = generate_random_string(64);
 
     // Build authorization code using domain model
     let mut auth_code = AuthorizationCode::new(
         auth_code_str.clone(),
         pending.client_id.clone(),
-        "placeholder".to_string(),
+        principal.id.clone(),
         pending.redirect_uri.clone(),
     )
     .with_scope(pending.scope.clone())
     .with_nonce(pending.nonce.clone())
-    .with_state(params.state.clone());
+    .with_state(pending.client_state.clone());
 
-    if let (Some(challenge), Some(method)) = (pending.code_challenge, pending.code_challenge_method) {
+    if let (Some(challenge), Some(method)) = (pending.code_challenge.clone(), pending.code_challenge_method.clone()) {
         auth_code = auth_code.with_pkce(challenge, method);
     }
 
     // Store in MongoDB
-    if let Err(e) = state.auth_code_repo.insert(&auth_code).await {
+    if let Err(e) = state.auth_code_store.insert(&auth_code).await {
         error!(error = %e, "Failed to store authorization code");
-        return error_redirect(&pending.redirect_uri, "server_error", "Failed to create authorization code", params.state.as_deref());
+        return error_redirect(&pending.redirect_uri, "server_error", "Failed to create authorization code", client_state);
     }
 
+    info!(principal_id = %principal.id, provider = %provider_id, "OIDC authorization completed");
+
     // Redirect back to client
     let mut redirect_url = pending.redirect_uri.clone();
     redirect_url.push_str(&format!("?code={}", urlencoding::encode(&auth_code_str)));
-    if let Some(s) = &params.state {
+    if let Some(s) = client_state {
         redirect_url.push_str(&format!("&state={}", urlencoding::encode(s)));
     }
 
@@ -784,10 +1064,7 @@ pub async fn issue_code(
     principal_id: &str,
     pending_state: &str,
 ) -> Result<String, PlatformError> {
-    let pending = {
-        let mut pending_states = state.pending_states.write().await;
-        pending_states.remove(pending_state)
-    };
+    let pending = state.pending_auth_store.consume(pending_state).await?;
 
     let pending = pending.ok_or_else(|| PlatformError::InvalidToken {
         message: "Invalid or expired state".to_string(),
@@ -803,14 +1080,15 @@ pub async fn issue_code(
         pending.redirect_uri,
     )
     .with_scope(pending.scope)
-    .with_nonce(pending.nonce);
+    .with_nonce(pending.nonce)
+    .with_state(pending.client_state);
 
     if let (Some(challenge), Some(method)) = (pending.code_challenge, pending.code_challenge_method) {
         auth_code = auth_code.with_pkce(challenge, method);
     }
 
     // Store in MongoDB
-    state.auth_code_repo.insert(&auth_code).await.map_err(|e| {
+    state.auth_code_store.insert(&auth_code).await.map_err(|e| {
         error!(error = %e, "Failed to store authorization code");
         PlatformError::Internal {
             message: "Failed to create authorization code".to_string(),
@@ -820,6 +1098,38 @@ pub async fn issue_code(
     Ok(auth_code_str)
 }
 
+/// Verify a presented client secret against the hash stored in the secrets provider.
+///
+/// Clients without a secret reference (or with no provider configured) are
+/// rejected in strict mode and accepted in lenient mode.
+async fn verify_client_secret(
+    provider: Option<&Arc<dyn SecretsProvider>>,
+    mode: ClientSecretVerification,
+    client: &OAuthClient,
+    client_secret: &str,
+) -> std::result::Result<bool, SecretsError> {
+    if client_secret.is_empty() {
+        return Ok(false);
+    }
+
+    let (Some(secret_ref), Some(provider)) = (client.client_secret_ref.as_deref(), provider) else {
+        if mode == ClientSecretVerification::Lenient {
+            warn!(client_id = %client.client_id, "No stored client secret; accepting in lenient mode");
+            return Ok(true);
+        }
+        return Ok(false);
+    };
+
+    match provider.get(secret_ref).await {
+        Ok(stored_hash) => Ok(OAuthClient::secret_matches(client_secret, &stored_hash)),
+        Err(SecretsError::NotFound(_)) => {
+            warn!(client_id = %client.client_id, "Client secret reference not found in secrets provider");
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
 fn error_redirect(redirect_uri: &str, error: &str, description: &str, state: Option<&str>) -> Response {
     let mut url = redirect_uri.to_string();
     url.push_str(&format!(
@@ -850,3 +1160,360 @@ pub fn oauth_router(state: OAuthState) -> Router {
         .route("/callback", get(oidc_callback))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use mongodb::{options::ClientOptions, Client as MongoClient};
+    use rsa::{pkcs8::DecodePrivateKey, traits::PublicKeyParts, RsaPrivateKey};
+    use tower::ServiceExt;
+
+    use crate::auth::authorization_code_repository::InMemoryAuthorizationCodeStore;
+    use crate::auth::pending_auth_repository::InMemoryPendingAuthStore;
+    use std::collections::HashMap;
+    use crate::auth::oidc_service::OidcProviderConfig;
+    use crate::auth::auth_service::AuthConfig;
+    use crate::OAuthClient;
+
+    const UNUSED_MONGO_URL: &str = "mongodb://localhost:27017";
+    const IDP_CLIENT_ID: &str = "fc-platform";
+    const IDP_EMAIL: &str = "oidc.user@example.com";
+    const NONCE: &str = "nonce-123";
+    const REDIRECT_URI: &str = "http://localhost:3000/callback";
+
+    /// Minimal IDP serving discovery, JWKS and a token endpoint that signs ID tokens
+    #[derive(Clone)]
+    struct MockIdp {
+        issuer: String,
+        signing_key: Arc<EncodingKey>,
+        jwks: serde_json::Value,
+    }
+
+    async fn idp_discovery(State(idp): State<MockIdp>) -> Json<serde_json::Value> {
+        Json(serde_json::json!({
+            "issuer": idp.issuer,
+            "authorization_endpoint": format!("{}/authorize", idp.issuer),
+            "token_endpoint": format!("{}/token", idp.issuer),
+            "jwks_uri": format!("{}/jwks", idp.issuer),
+            "response_types_supported": ["code"],
+            "subject_types_supported": ["public"],
+            "id_token_signing_alg_values_supported": ["RS256"],
+        }))
+    }
+
+    async fn idp_jwks(State(idp): State<MockIdp>) -> Json<serde_json::Value> {
+        Json(idp.jwks.clone())
+    }
+
+    async fn idp_token(State(idp): State<MockIdp>) -> Json<serde_json::Value> {
+        let now = Utc::now().timestamp();
+        let claims = serde_json::json!({
+            "iss": idp.issuer,
+            "sub": "idp-user-1",
+            "aud": IDP_CLIENT_ID,
+            "exp": now + 300,
+            "iat": now,
+            "nonce": NONCE,
+            "email": IDP_EMAIL,
+            "name": "OIDC User",
+        });
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("mock-key".to_string());
+        let id_token = encode(&header, &claims, &idp.signing_key).unwrap();
+        Json(serde_json::json!({ "access_token": "idp-access", "token_type": "Bearer", "id_token": id_token }))
+    }
+
+    async fn start_mock_idp() -> String {
+        let (private_pem, _) = AuthConfig::generate_rsa_keys(None).unwrap();
+        let private_key = RsaPrivateKey::from_pkcs8_pem(&private_pem).unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        let idp = MockIdp {
+            issuer: issuer.clone(),
+            signing_key: Arc::new(EncodingKey::from_rsa_pem(private_pem.as_bytes()).unwrap()),
+            jwks: serde_json::json!({ "keys": [{
+                "kty": "RSA",
+                "kid": "mock-key",
+                "alg": "RS256",
+                "use": "sig",
+                "n": URL_SAFE_NO_PAD.encode(private_key.n().to_bytes_be()),
+                "e": URL_SAFE_NO_PAD.encode(private_key.e().to_bytes_be()),
+            }]}),
+        };
+
+        let app = Router::new()
+            .route("/.well-known/openid-configuration", get(idp_discovery))
+            .route("/jwks", get(idp_jwks))
+            .route("/token", post(idp_token))
+            .with_state(idp);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        issuer
+    }
+
+    /// Secrets provider backed by a fixed map
+    struct StaticSecrets(HashMap<String, String>);
+
+    #[async_trait::async_trait]
+    impl SecretsProvider for StaticSecrets {
+        async fn get(&self, key: &str) -> std::result::Result<String, SecretsError> {
+            self.0.get(key).cloned().ok_or_else(|| SecretsError::NotFound(key.to_string()))
+        }
+
+        async fn set(&self, _key: &str, _value: &str) -> std::result::Result<(), SecretsError> {
+            Err(SecretsError::ProviderError("Static secrets are read-only".to_string()))
+        }
+
+        async fn delete(&self, _key: &str) -> std::result::Result<(), SecretsError> {
+            Err(SecretsError::ProviderError("Static secrets are read-only".to_string()))
+        }
+
+        fn name(&self) -> &str {
+            "static"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_secret_verification() {
+        let provider: Arc<dyn SecretsProvider> = Arc::new(StaticSecrets(HashMap::from([
+            ("svc-secret".to_string(), OAuthClient::hash_secret("s3cret")),
+        ])));
+        let client = OAuthClient::confidential("svc", "Service").with_secret_ref("svc-secret");
+        let strict = ClientSecretVerification::Strict;
+
+        assert!(verify_client_secret(Some(&provider), strict, &client, "s3cret").await.unwrap());
+        assert!(!verify_client_secret(Some(&provider), strict, &client, "wrong").await.unwrap());
+        assert!(!verify_client_secret(Some(&provider), strict, &client, "").await.unwrap());
+
+        // Unknown reference and missing provider are rejected in strict mode
+        let dangling = OAuthClient::confidential("svc", "Service").with_secret_ref("missing");
+        assert!(!verify_client_secret(Some(&provider), strict, &dangling, "s3cret").await.unwrap());
+        assert!(!verify_client_secret(None, strict, &client, "s3cret").await.unwrap());
+
+        // Lenient mode only relaxes clients without a stored secret
+        let lenient = ClientSecretVerification::Lenient;
+        let unset = OAuthClient::confidential("dev", "Dev");
+        assert!(verify_client_secret(Some(&provider), lenient, &unset, "anything").await.unwrap());
+        assert!(!verify_client_secret(Some(&provider), lenient, &client, "wrong").await.unwrap());
+    }
+
+    fn location(response: &Response) -> String {
+        response.headers().get(header::LOCATION).unwrap().to_str().unwrap().to_string()
+    }
+
+    fn query_param(url: &str, name: &str) -> Option<String> {
+        let query = url.split_once('?')?.1;
+        query.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| urlencoding::decode(v).unwrap().into_owned())
+    }
+
+    /// Resolver standing in for the auth config and user stores: records the
+    /// (issuer, subject) pairs it resolves, and links them to `principal` or
+    /// rejects them when there is none
+    struct StaticResolver {
+        principal: Option<Principal>,
+        resolved: std::sync::Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl OidcPrincipalResolver for StaticResolver {
+        async fn resolve(&self, claims: &IdTokenClaims) -> Result<Principal, PlatformError> {
+            self.resolved.lock().unwrap().push((claims.iss.clone(), claims.sub.clone()));
+            self.principal.clone().ok_or_else(|| PlatformError::forbidden("Identity is not linked"))
+        }
+    }
+
+    /// OAuth clients held in memory
+    struct StaticClients(Vec<OAuthClient>);
+
+    #[async_trait::async_trait]
+    impl OAuthClientStore for StaticClients {
+        async fn find_by_client_id(&self, client_id: &str) -> crate::shared::error::Result<Option<OAuthClient>> {
+            Ok(self.0.iter().find(|c| c.client_id == client_id).cloned())
+        }
+    }
+
+    /// Principals held in memory
+    struct StaticPrincipals(Vec<Principal>);
+
+    #[async_trait::async_trait]
+    impl PrincipalStore for StaticPrincipals {
+        async fn find_by_id(&self, id: &str) -> crate::shared::error::Result<Option<Principal>> {
+            Ok(self.0.iter().find(|p| p.id == id).cloned())
+        }
+
+        async fn find_by_email(&self, _email: &str) -> crate::shared::error::Result<Option<Principal>> {
+            unimplemented!("not used by the authorization code flow")
+        }
+
+        async fn find_by_service_account(&self, _service_account_id: &str) -> crate::shared::error::Result<Option<Principal>> {
+            Ok(None)
+        }
+
+        async fn insert(&self, _principal: &Principal) -> crate::shared::error::Result<()> {
+            unimplemented!("not used by the authorization code flow")
+        }
+
+        async fn replace_password_hash(&self, _id: &str, _current_hash: &str, _new_hash: &str) -> crate::shared::error::Result<bool> {
+            unimplemented!("not used by the authorization code flow")
+        }
+    }
+
+    /// OAuth state for authorization code flow tests, with the `spa` client and the
+    /// resolver's principal in memory. The refresh token repository points at a
+    /// database that is never reached: that flow doesn't issue refresh tokens.
+    async fn callback_state(resolver: Arc<StaticResolver>, code_store: Arc<InMemoryAuthorizationCodeStore>) -> (OAuthState, String) {
+        let issuer = start_mock_idp().await;
+        let oidc_service = Arc::new(OidcService::new());
+        oidc_service.register_provider(OidcProviderConfig {
+            provider_id: "mock".to_string(),
+            client_id: IDP_CLIENT_ID.to_string(),
+            client_secret: None,
+            issuer_url: issuer.clone(),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+            redirect_uri: "http://localhost:8080/oauth/callback".to_string(),
+        }).await.unwrap();
+
+        let db = MongoClient::with_options(ClientOptions::parse(UNUSED_MONGO_URL).await.unwrap())
+            .unwrap()
+            .database("unused");
+        let state = OAuthState::new(
+            Arc::new(StaticClients(vec![OAuthClient::new("spa", "SPA").with_redirect_uri(REDIRECT_URI)])),
+            Arc::new(StaticPrincipals(resolver.principal.clone().into_iter().collect())),
+            Arc::new(AuthService::new(AuthConfig::default())),
+            oidc_service,
+            resolver,
+            code_store,
+            Arc::new(InMemoryPendingAuthStore::new()),
+            Arc::new(RefreshTokenRepository::new(&db)),
+        );
+        (state, issuer)
+    }
+
+    async fn insert_pending(state: &OAuthState, server_state: &str) {
+        state.pending_auth_store.insert(&PendingAuth {
+            client_state: Some("client-state".to_string()),
+            code_challenge: Some("challenge".to_string()),
+            code_challenge_method: Some("S256".to_string()),
+            nonce: Some(NONCE.to_string()),
+            provider: Some("mock".to_string()),
+            ..PendingAuth::new(server_state, "spa", REDIRECT_URI)
+        }).await.unwrap();
+    }
+
+    async fn callback(app: Router, query: &str) -> Response {
+        app.oneshot(Request::get(format!("/callback?{}", query)).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_oidc_callback_issues_code_for_linked_principal() {
+        let principal = Principal::new_user(IDP_EMAIL, crate::UserScope::Client);
+        let resolver = Arc::new(StaticResolver {
+            principal: Some(principal.clone()),
+            resolved: Default::default(),
+        });
+        let code_store = Arc::new(InMemoryAuthorizationCodeStore::new());
+        let (state, issuer) = callback_state(resolver.clone(), code_store.clone()).await;
+        insert_pending(&state, "server-state").await;
+        let app = oauth_router(state);
+
+        // The client's own state is not a key into pending authorizations
+        let response = callback(app.clone(), "code=idp-code&state=client-state").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = callback(app.clone(), "code=idp-code&state=server-state").await;
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        let redirect = location(&response);
+        assert!(redirect.starts_with(REDIRECT_URI), "unexpected redirect {}", redirect);
+        assert_eq!(query_param(&redirect, "state").as_deref(), Some("client-state"));
+
+        // Resolved by issuer and subject, and the code is bound to that principal
+        assert_eq!(*resolver.resolved.lock().unwrap(), vec![(issuer, "idp-user-1".to_string())]);
+        let code = query_param(&redirect, "code").expect("authorization code in redirect");
+        let stored = code_store.consume(&code).await.unwrap().expect("stored authorization code");
+        assert_eq!(stored.principal_id, principal.id);
+        assert_eq!(stored.code_challenge.as_deref(), Some("challenge"));
+
+        // The pending authorization is single-use
+        let response = callback(app, "code=idp-code&state=server-state").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_oidc_callback_rejects_unlinked_identity() {
+        let resolver = Arc::new(StaticResolver { principal: None, resolved: Default::default() });
+        let (state, _) = callback_state(resolver, Arc::new(InMemoryAuthorizationCodeStore::new())).await;
+        insert_pending(&state, "server-state").await;
+
+        let response = callback(oauth_router(state), "code=idp-code&state=server-state").await;
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        let redirect = location(&response);
+        assert_eq!(query_param(&redirect, "error").as_deref(), Some("access_denied"));
+        assert_eq!(query_param(&redirect, "state").as_deref(), Some("client-state"));
+        assert!(query_param(&redirect, "code").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_authorize_callback_token_flow() {
+        let principal = Principal::new_user(IDP_EMAIL, crate::UserScope::Client);
+        let resolver = Arc::new(StaticResolver {
+            principal: Some(principal.clone()),
+            resolved: Default::default(),
+        });
+        let (state, issuer) = callback_state(resolver, Arc::new(InMemoryAuthorizationCodeStore::new())).await;
+        let auth_service = state.auth_service.clone();
+        let app = oauth_router(state);
+
+        // The client starts the flow and is sent to the IDP
+        let verifier = "pkce-verifier-0123456789";
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        let authorize_query = format!(
+            "response_type=code&client_id=spa&redirect_uri={}&state=client-state&nonce={}&code_challenge={}&code_challenge_method=S256&provider=mock",
+            urlencoding::encode(REDIRECT_URI), NONCE, challenge,
+        );
+        let response = app.clone()
+            .oneshot(Request::get(format!("/authorize?{}", authorize_query)).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        let idp_redirect = location(&response);
+        assert!(idp_redirect.starts_with(&format!("{}/authorize", issuer)), "unexpected redirect {}", idp_redirect);
+        let server_state = query_param(&idp_redirect, "state").expect("state sent to the IDP");
+        assert_ne!(server_state, "client-state");
+
+        // The IDP sends the user back with its code
+        let response = callback(app.clone(), &format!("code=idp-code&state={}", server_state)).await;
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        let client_redirect = location(&response);
+        assert_eq!(query_param(&client_redirect, "state").as_deref(), Some("client-state"));
+        let code = query_param(&client_redirect, "code").expect("authorization code in redirect");
+
+        // The client redeems the code for a token issued to the resolved principal
+        let form = format!(
+            "grant_type=authorization_code&code={}&redirect_uri={}&client_id=spa&code_verifier={}",
+            code, urlencoding::encode(REDIRECT_URI), verifier,
+        );
+        let token_request = || Request::post("/token")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form.clone()))
+            .unwrap();
+        let response = app.clone().oneshot(token_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let claims = auth_service.validate_token(body["access_token"].as_str().unwrap()).unwrap();
+        assert_eq!(claims.sub, principal.id);
+
+        // The code is single-use
+        let response = app.oneshot(token_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
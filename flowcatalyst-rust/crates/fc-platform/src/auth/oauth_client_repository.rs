@@ -1,10 +1,17 @@
 //! OAuth Client Repository
 
+use async_trait::async_trait;
 use mongodb::{Collection, Database, bson::doc};
 use futures::TryStreamExt;
 use crate::OAuthClient;
 use crate::shared::error::Result;
 
+/// OAuth client lookups made by the OAuth endpoints
+#[async_trait]
+pub trait OAuthClientStore: Send + Sync {
+    async fn find_by_client_id(&self, client_id: &str) -> Result<Option<OAuthClient>>;
+}
+
 pub struct OAuthClientRepository {
     collection: Collection<OAuthClient>,
 }
@@ -67,3 +74,10 @@ impl OAuthClientRepository {
         Ok(result.deleted_count > 0)
     }
 }
+
+#[async_trait]
+impl OAuthClientStore for OAuthClientRepository {
+    async fn find_by_client_id(&self, client_id: &str) -> Result<Option<OAuthClient>> {
+        OAuthClientRepository::find_by_client_id(self, client_id).await
+    }
+}
@@ -10,11 +10,12 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use utoipa::{ToSchema, IntoParams};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use fc_secrets::{Provider as SecretsProvider, SecretsError};
 
 use crate::auth::oauth_entity::{OAuthClient, OAuthClientType, GrantType};
 use crate::OAuthClientRepository;
 use crate::shared::error::PlatformError;
-use crate::shared::api_common::{PaginationParams, CreatedResponse, SuccessResponse};
+use crate::shared::api_common::{PaginationParams, SuccessResponse};
 use crate::shared::middleware::Authenticated;
 
 /// Create OAuth client request
@@ -108,6 +109,25 @@ impl From<OAuthClient> for OAuthClientResponse {
     }
 }
 
+/// Created OAuth client response
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOAuthClientResponse {
+    pub id: String,
+    /// Initial client secret for confidential clients (shown only once)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+}
+
+/// Rotated client secret response
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateClientSecretResponse {
+    pub client_id: String,
+    /// New client secret (shown only once)
+    pub client_secret: String,
+}
+
 /// Query parameters for OAuth clients list
 #[derive(Debug, Default, Deserialize, IntoParams)]
 #[serde(rename_all = "camelCase")]
@@ -124,6 +144,9 @@ pub struct OAuthClientsQuery {
 #[derive(Clone)]
 pub struct OAuthClientsState {
     pub oauth_client_repo: Arc<OAuthClientRepository>,
+    /// Holds client secret hashes; required to rotate secrets. Without one,
+    /// confidential clients are created without an initial secret.
+    pub secrets_provider: Option<Arc<dyn SecretsProvider>>,
 }
 
 fn parse_client_type(s: &str) -> OAuthClientType {
@@ -144,6 +167,10 @@ fn parse_grant_type(s: &str) -> Option<GrantType> {
 }
 
 /// Create a new OAuth client
+///
+/// Confidential clients get an initial secret, whose hash is stored in the
+/// secrets provider; the raw secret is returned only in this response. If the
+/// provider is read-only the client is created without a secret.
 #[utoipa::path(
     post,
     path = "",
@@ -151,7 +178,7 @@ fn parse_grant_type(s: &str) -> Option<GrantType> {
     operation_id = "postApiAdminPlatformOauthClients",
     request_body = CreateOAuthClientRequest,
     responses(
-        (status = 201, description = "OAuth client created", body = CreatedResponse),
+        (status = 201, description = "OAuth client created", body = CreateOAuthClientResponse),
         (status = 400, description = "Validation error"),
         (status = 409, description = "Duplicate client_id")
     ),
@@ -161,7 +188,7 @@ pub async fn create_oauth_client(
     State(state): State<OAuthClientsState>,
     auth: Authenticated,
     Json(req): Json<CreateOAuthClientRequest>,
-) -> Result<Json<CreatedResponse>, PlatformError> {
+) -> Result<Json<CreateOAuthClientResponse>, PlatformError> {
     crate::checks::require_anchor(&auth.0)?;
 
     // Check for duplicate client_id
@@ -197,7 +224,35 @@ pub async fn create_oauth_client(
     let id = client.id.clone();
     state.oauth_client_repo.insert(&client).await?;
 
-    Ok(Json(CreatedResponse::new(id)))
+    // Store the secret only once the client exists, so a failed insert leaves nothing behind
+    let mut client_secret = None;
+    if client.client_type == OAuthClientType::Confidential {
+        if let Some(provider) = state.secrets_provider.as_ref() {
+            let secret_ref = default_secret_ref(&client);
+            match store_new_secret(provider.as_ref(), &secret_ref).await {
+                Ok(secret) => {
+                    client.client_secret_ref = Some(secret_ref.clone());
+                    if let Err(e) = state.oauth_client_repo.update(&client).await {
+                        if let Err(delete_err) = provider.delete(&secret_ref).await {
+                            tracing::warn!(secret_ref = %secret_ref, error = %delete_err, "Failed to delete unused client secret");
+                        }
+                        return Err(e.into());
+                    }
+                    client_secret = Some(secret);
+                }
+                // A read-only provider (such as env) can't hold new secrets
+                Err(e) => tracing::warn!(
+                    client_id = %client.client_id,
+                    provider = provider.name(),
+                    error = %e,
+                    "Created confidential OAuth client without a secret; set one with POST /api/admin/oauth-clients/{}/rotate-secret",
+                    id
+                ),
+            }
+        }
+    }
+
+    Ok(Json(CreateOAuthClientResponse { id, client_secret }))
 }
 
 /// Get OAuth client by ID
@@ -346,10 +401,131 @@ pub async fn delete_oauth_client(
     Ok(Json(SuccessResponse::ok()))
 }
 
+/// Secrets provider key for a client without a `client_secret_ref`
+fn default_secret_ref(client: &OAuthClient) -> String {
+    format!("oauth-clients/{}/secret", client.id)
+}
+
+/// Generate a new secret and store its hash under `secret_ref`, returning the raw secret
+async fn store_new_secret(
+    provider: &dyn SecretsProvider,
+    secret_ref: &str,
+) -> std::result::Result<String, SecretsError> {
+    let secret = OAuthClient::generate_secret();
+    provider.set(secret_ref, &OAuthClient::hash_secret(&secret)).await?;
+    Ok(secret)
+}
+
+/// Rotate a confidential client's secret
+///
+/// Stores the new secret's hash under the client's `client_secret_ref`,
+/// assigning one if the client has none. The previous secret stops working
+/// immediately.
+#[utoipa::path(
+    post,
+    path = "/{id}/rotate-secret",
+    tag = "oauth-clients",
+    operation_id = "postApiAdminPlatformOauthClientsByIdRotateSecret",
+    params(
+        ("id" = String, Path, description = "OAuth client ID")
+    ),
+    responses(
+        (status = 200, description = "Secret rotated", body = RotateClientSecretResponse),
+        (status = 400, description = "Client is not confidential"),
+        (status = 404, description = "OAuth client not found"),
+        (status = 500, description = "Secrets provider missing or read-only")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn rotate_oauth_client_secret(
+    State(state): State<OAuthClientsState>,
+    auth: Authenticated,
+    Path(id): Path<String>,
+) -> Result<Json<RotateClientSecretResponse>, PlatformError> {
+    crate::checks::require_anchor(&auth.0)?;
+
+    let provider = state.secrets_provider.as_ref()
+        .ok_or_else(|| PlatformError::Configuration {
+            message: "No secrets provider configured for OAuth client secrets".to_string(),
+        })?;
+
+    let mut client = state.oauth_client_repo.find_by_id(&id).await?
+        .ok_or_else(|| PlatformError::not_found("OAuthClient", &id))?;
+    if client.client_type != OAuthClientType::Confidential {
+        return Err(PlatformError::validation("Only confidential clients have a client secret"));
+    }
+
+    let secret_ref = client.client_secret_ref.clone()
+        .unwrap_or_else(|| default_secret_ref(&client));
+    let client_secret = store_new_secret(provider.as_ref(), &secret_ref).await
+        .map_err(|e| PlatformError::Configuration {
+            message: format!("Failed to store client secret in {} secrets provider: {}", provider.name(), e),
+        })?;
+
+    if client.client_secret_ref.is_none() {
+        client.client_secret_ref = Some(secret_ref);
+        client.updated_at = chrono::Utc::now();
+        state.oauth_client_repo.update(&client).await?;
+    }
+
+    Ok(Json(RotateClientSecretResponse {
+        client_id: client.client_id,
+        client_secret,
+    }))
+}
+
 /// Create OAuth clients router
 pub fn oauth_clients_router(state: OAuthClientsState) -> OpenApiRouter {
     OpenApiRouter::new()
         .routes(routes!(create_oauth_client, list_oauth_clients))
         .routes(routes!(get_oauth_client, update_oauth_client, delete_oauth_client))
+        .routes(routes!(rotate_oauth_client_secret))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Writable secrets provider backed by a map
+    #[derive(Default)]
+    struct MemorySecrets(Mutex<HashMap<String, String>>);
+
+    #[async_trait::async_trait]
+    impl SecretsProvider for MemorySecrets {
+        async fn get(&self, key: &str) -> std::result::Result<String, SecretsError> {
+            self.0.lock().unwrap().get(key).cloned().ok_or_else(|| SecretsError::NotFound(key.to_string()))
+        }
+
+        async fn set(&self, key: &str, value: &str) -> std::result::Result<(), SecretsError> {
+            self.0.lock().unwrap().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn delete(&self, key: &str) -> std::result::Result<(), SecretsError> {
+            self.0.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "memory"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotated_secret_replaces_previous() {
+        let provider = MemorySecrets::default();
+
+        let first = store_new_secret(&provider, "svc-secret").await.unwrap();
+        let second = store_new_secret(&provider, "svc-secret").await.unwrap();
+        assert_ne!(first, second);
+
+        // Only the hash is stored, and only the latest secret matches it
+        let stored = provider.get("svc-secret").await.unwrap();
+        assert_ne!(stored, second);
+        assert!(OAuthClient::secret_matches(&second, &stored));
+        assert!(!OAuthClient::secret_matches(&first, &stored));
+    }
+}
@@ -165,6 +165,38 @@ impl OAuthClient {
         self.grant_types.contains(&grant)
     }
 
+    /// Generate a new random client secret
+    pub fn generate_secret() -> String {
+        use rand::Rng;
+        use base64::Engine;
+
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill(&mut bytes);
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Hash a client secret for storage.
+    ///
+    /// The secrets provider holds this hash under `client_secret_ref`,
+    /// never the raw secret.
+    pub fn hash_secret(raw_secret: &str) -> String {
+        use sha2::{Sha256, Digest};
+        use base64::Engine;
+
+        let hash = Sha256::digest(raw_secret.as_bytes());
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(hash)
+    }
+
+    /// Check a presented secret against a stored hash in constant time.
+    pub fn secret_matches(raw_secret: &str, stored_hash: &str) -> bool {
+        use subtle::ConstantTimeEq;
+
+        Self::hash_secret(raw_secret)
+            .as_bytes()
+            .ct_eq(stored_hash.trim().as_bytes())
+            .into()
+    }
+
     pub fn is_redirect_uri_allowed(&self, uri: &str) -> bool {
         self.redirect_uris.iter().any(|allowed| {
             // Exact match or pattern match (for localhost with varying ports)
@@ -23,15 +23,43 @@ use sha2::{Sha256, Digest};
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use std::sync::Arc;
 use chrono::Utc;
+use tokio::sync::Semaphore;
 use tracing::{info, warn, error, debug};
 use rand::Rng;
 
 use crate::auth::config_entity::{ClientAuthConfig, AuthProvider, AuthConfigType};
-use crate::UserScope;
 use crate::{
     ClientAuthConfigRepository, OidcLoginStateRepository, AnchorDomainRepository,
 };
-use crate::{AuthService, OidcSyncService};
+use crate::{AuditService, AuthService, LoginFailureReason, OidcSyncService};
+use crate::shared::middleware::ClientIp;
+
+/// Default cap on concurrent token exchanges with external IDPs
+pub const DEFAULT_MAX_CONCURRENT_TOKEN_EXCHANGES: usize = 32;
+
+/// HTTP client for IDP token exchanges, shared across login requests.
+/// Bounds concurrent exchanges so a burst of logins cannot open an
+/// unbounded number of outbound connections to the IDP.
+#[derive(Clone)]
+pub struct TokenExchangeClient {
+    http_client: reqwest::Client,
+    permits: Arc<Semaphore>,
+}
+
+impl TokenExchangeClient {
+    pub fn new(http_client: reqwest::Client, max_concurrent: usize) -> Self {
+        Self {
+            http_client,
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+}
+
+impl Default for TokenExchangeClient {
+    fn default() -> Self {
+        Self::new(reqwest::Client::new(), DEFAULT_MAX_CONCURRENT_TOKEN_EXCHANGES)
+    }
+}
 
 /// OIDC Login API State
 #[derive(Clone)]
@@ -48,6 +76,10 @@ pub struct OidcLoginApiState {
     pub session_cookie_secure: bool,
     pub session_cookie_same_site: String,
     pub session_token_expiry_secs: i64,
+    /// Shared, concurrency-bounded client for IDP token exchanges
+    pub token_exchange_client: TokenExchangeClient,
+    /// Records login successes and failures
+    pub audit_service: Option<Arc<AuditService>>,
 }
 
 impl OidcLoginApiState {
@@ -69,9 +101,28 @@ impl OidcLoginApiState {
             session_cookie_secure: true,
             session_cookie_same_site: "Lax".to_string(),
             session_token_expiry_secs: 86400, // 24 hours
+            token_exchange_client: TokenExchangeClient::default(),
+            audit_service: None,
         }
     }
 
+    /// Record login attempts in the audit log
+    pub fn with_audit_service(mut self, audit_service: Arc<AuditService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
+    pub fn with_max_concurrent_token_exchanges(mut self, max_concurrent: usize) -> Self {
+        self.token_exchange_client.permits = Arc::new(Semaphore::new(max_concurrent.max(1)));
+        self
+    }
+
+    /// Use a shared HTTP client (see `fc_common::http`) for token exchanges
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.token_exchange_client.http_client = http_client;
+        self
+    }
+
     pub fn with_external_base_url(mut self, url: impl Into<String>) -> Self {
         self.external_base_url = Some(url.into());
         self
@@ -391,6 +442,7 @@ pub async fn oidc_callback(
     Host(host): Host,
     uri: Uri,
     Query(params): Query<OidcCallbackParams>,
+    ClientIp(client_ip): ClientIp,
     jar: CookieJar,
 ) -> Response {
     // Handle IDP errors
@@ -449,10 +501,14 @@ pub async fn oidc_callback(
 
     // Exchange code for tokens
     let callback_url = get_callback_url(&state, &host, &uri);
-    let tokens = match exchange_code_for_tokens(&config, code, &login_state.code_verifier, &callback_url).await {
+    let tokens = match state.token_exchange_client
+        .exchange_code_for_tokens(&config, code, &login_state.code_verifier, &callback_url)
+        .await
+    {
         Ok(t) => t,
         Err(e) => {
             error!(error = %e, "Token exchange failed");
+            audit_oidc_failure(&state, &login_state.email_domain, LoginFailureReason::IdpError, client_ip.as_deref()).await;
             return error_redirect("Failed to exchange authorization code");
         }
     };
@@ -462,17 +518,11 @@ pub async fn oidc_callback(
         Ok(c) => c,
         Err(e) => {
             error!(error = %e, "ID token validation failed");
+            audit_oidc_failure(&state, &login_state.email_domain, LoginFailureReason::InvalidIdToken, client_ip.as_deref()).await;
             return error_redirect("Failed to validate identity token");
         }
     };
 
-    // Determine user scope based on config type
-    let user_scope = match config.config_type {
-        AuthConfigType::Anchor => UserScope::Anchor,
-        AuthConfigType::Partner => UserScope::Partner,
-        AuthConfigType::Client => UserScope::Client,
-    };
-
     // Sync user and roles
     let principal = match state.oidc_sync_service.sync_oidc_login(
         &claims.email,
@@ -480,7 +530,7 @@ pub async fn oidc_callback(
         &claims.subject,
         config.oidc_issuer_url.as_deref().unwrap_or("unknown"),
         config.primary_client_id.as_deref(),
-        user_scope,
+        config.user_scope(),
         &claims.roles.unwrap_or_default(),
     ).await {
         Ok(p) => p,
@@ -525,6 +575,12 @@ pub async fn oidc_callback(
         "OIDC login successful"
     );
 
+    if let Some(ref audit) = state.audit_service {
+        if let Err(e) = audit.log_login_success(&principal.id, "OIDC", client_ip.as_deref()).await {
+            warn!(principal_id = %principal.id, error = %e, "Failed to record login audit entry");
+        }
+    }
+
     // Redirect with cookie
     (
         jar,
@@ -537,6 +593,19 @@ pub async fn oidc_callback(
 
 // ==================== Helper Functions ====================
 
+async fn audit_oidc_failure(
+    state: &OidcLoginApiState,
+    email_domain: &str,
+    reason: LoginFailureReason,
+    client_ip: Option<&str>,
+) {
+    if let Some(ref audit) = state.audit_service {
+        if let Err(e) = audit.log_login_failure(email_domain, "OIDC", reason, client_ip).await {
+            warn!(error = %e, "Failed to record login failure audit entry");
+        }
+    }
+}
+
 fn generate_random_string(length: usize) -> String {
     let bytes: Vec<u8> = (0..length).map(|_| rand::thread_rng().gen()).collect();
     URL_SAFE_NO_PAD.encode(&bytes)
@@ -614,58 +683,63 @@ struct TokenExchangeResponse {
     refresh_token: Option<String>,
 }
 
-async fn exchange_code_for_tokens(
-    config: &ClientAuthConfig,
-    code: &str,
-    code_verifier: &str,
-    callback_url: &str,
-) -> Result<TokenExchangeResponse, String> {
-    let issuer = config.oidc_issuer_url.as_deref().ok_or("Missing issuer URL")?;
-    let token_endpoint = get_token_endpoint(issuer);
-    let client_id = config.oidc_client_id.as_deref().ok_or("Missing client ID")?;
-
-    let mut params = vec![
-        ("grant_type", "authorization_code"),
-        ("code", code),
-        ("redirect_uri", callback_url),
-        ("client_id", client_id),
-        ("code_verifier", code_verifier),
-    ];
-
-    // Add client secret if present
-    let client_secret = config.oidc_client_secret_ref.clone();
-    if let Some(ref secret) = client_secret {
-        params.push(("client_secret", secret));
-    }
-
-    let client = reqwest::Client::new();
-    let response = client
-        .post(&token_endpoint)
-        .form(&params)
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+impl TokenExchangeClient {
+    async fn exchange_code_for_tokens(
+        &self,
+        config: &ClientAuthConfig,
+        code: &str,
+        code_verifier: &str,
+        callback_url: &str,
+    ) -> Result<TokenExchangeResponse, String> {
+        let issuer = config.oidc_issuer_url.as_deref().ok_or("Missing issuer URL")?;
+        let token_endpoint = get_token_endpoint(issuer);
+        let client_id = config.oidc_client_id.as_deref().ok_or("Missing client ID")?;
+
+        let mut params = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", callback_url),
+            ("client_id", client_id),
+            ("code_verifier", code_verifier),
+        ];
+
+        // Add client secret if present
+        let client_secret = config.oidc_client_secret_ref.clone();
+        if let Some(ref secret) = client_secret {
+            params.push(("client_secret", secret));
+        }
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response.text().await.unwrap_or_default();
-        return Err(format!("Token endpoint returned {}: {}", status, body));
-    }
+        let _permit = self.permits.acquire().await
+            .map_err(|_| "Token exchange limiter closed".to_string())?;
+
+        let response = self.http_client
+            .post(&token_endpoint)
+            .form(&params)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Token endpoint returned {}: {}", status, body));
+        }
 
-    let json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse token response: {}", e))?;
+        let json: serde_json::Value = response.json().await
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
 
-    let id_token = json["id_token"]
-        .as_str()
-        .ok_or("No ID token in response")?
-        .to_string();
+        let id_token = json["id_token"]
+            .as_str()
+            .ok_or("No ID token in response")?
+            .to_string();
 
-    Ok(TokenExchangeResponse {
-        access_token: json["access_token"].as_str().unwrap_or("").to_string(),
-        id_token,
-        refresh_token: json["refresh_token"].as_str().map(String::from),
-    })
+        Ok(TokenExchangeResponse {
+            access_token: json["access_token"].as_str().unwrap_or("").to_string(),
+            id_token,
+            refresh_token: json["refresh_token"].as_str().map(String::from),
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -820,3 +894,82 @@ pub fn oidc_login_router(state: OidcLoginApiState) -> Router {
         .route("/oidc/callback", get(oidc_callback))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use axum::extract::ConnectInfo;
+
+    /// Records peer addresses and peak concurrency seen by the token endpoint
+    #[derive(Clone, Default)]
+    struct TokenEndpointStats {
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+        peers: Arc<std::sync::Mutex<HashSet<SocketAddr>>>,
+    }
+
+    async fn token_handler(
+        State(stats): State<TokenEndpointStats>,
+        ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    ) -> Json<serde_json::Value> {
+        stats.peers.lock().unwrap().insert(peer);
+        let current = stats.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        stats.max_in_flight.fetch_max(current, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        stats.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Json(serde_json::json!({ "access_token": "access", "id_token": "id" }))
+    }
+
+    async fn start_token_endpoint() -> (ClientAuthConfig, TokenEndpointStats) {
+        let stats = TokenEndpointStats::default();
+        let app = Router::new()
+            .route("/token", post(token_handler))
+            .with_state(stats.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .unwrap();
+        });
+
+        let config = ClientAuthConfig::new_partner("example.com")
+            .with_oidc(format!("http://{}", addr), "test-client");
+        (config, stats)
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_reuses_client() {
+        let (config, stats) = start_token_endpoint().await;
+        let client = TokenExchangeClient::new(reqwest::Client::new(), 4);
+
+        for _ in 0..3 {
+            let tokens = client
+                .exchange_code_for_tokens(&config, "code", "verifier", "http://localhost/callback")
+                .await
+                .unwrap();
+            assert_eq!(tokens.id_token, "id");
+        }
+
+        // Sequential exchanges share one pooled connection
+        assert_eq!(stats.peers.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_concurrency_bounded() {
+        let (config, stats) = start_token_endpoint().await;
+        let client = TokenExchangeClient::new(reqwest::Client::new(), 2);
+
+        let exchanges = (0..6).map(|_| {
+            client.exchange_code_for_tokens(&config, "code", "verifier", "http://localhost/callback")
+        });
+        for result in futures::future::join_all(exchanges).await {
+            assert!(result.is_ok());
+        }
+
+        assert_eq!(stats.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+}
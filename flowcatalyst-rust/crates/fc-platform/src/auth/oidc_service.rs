@@ -164,6 +164,12 @@ impl OidcService {
         }
     }
 
+    /// Use a shared HTTP client (see `fc_common::http`) for discovery and JWKS fetches
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
     /// Register an OIDC provider
     pub async fn register_provider(&self, config: OidcProviderConfig) -> Result<()> {
         let discovery_url = format!("{}/.well-known/openid-configuration", config.issuer_url);
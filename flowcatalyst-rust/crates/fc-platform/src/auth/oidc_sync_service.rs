@@ -21,7 +21,7 @@ use tracing::{debug, info, warn};
 use crate::{Principal, UserScope, ExternalIdentity};
 use crate::auth::config_entity::IdpRoleMapping;
 use crate::{PrincipalRepository, IdpRoleMappingRepository};
-use crate::shared::error::Result;
+use crate::shared::error::{PlatformError, Result};
 
 /// Assignment source for IDP-synced roles
 pub const IDP_SYNC_SOURCE: &str = "IDP_SYNC";
@@ -190,6 +190,9 @@ impl OidcSyncService {
             }
         }
 
+        let mut roles_before: Vec<String> = principal.roles.iter().map(|r| r.role.clone()).collect();
+        roles_before.sort();
+
         // Remove all existing IDP-sourced roles
         let removed_count = principal.remove_roles_by_source(IDP_SYNC_SOURCE);
         if removed_count > 0 {
@@ -216,6 +219,13 @@ impl OidcSyncService {
             }
         }
 
+        // Invalidate existing sessions only if the effective role set changed
+        let mut roles_after: Vec<String> = principal.roles.iter().map(|r| r.role.clone()).collect();
+        roles_after.sort();
+        if roles_before != roles_after {
+            principal.bump_token_version();
+        }
+
         // Save updated principal
         self.principal_repo.update(principal).await?;
 
@@ -266,6 +276,73 @@ impl OidcSyncService {
         Ok(principal)
     }
 
+    /// OIDC sync for identities that must only ever resolve to their own principal.
+    ///
+    /// Unlike [`Self::sync_oidc_login`], existing users are matched on the
+    /// (`issuer`, `subject`) pair alone. An unlinked user that merely shares the
+    /// email is never taken over: the login is rejected instead.
+    ///
+    /// # Returns
+    /// Synchronized principal, or `Forbidden` if the email belongs to another user
+    pub async fn sync_linked_oidc_login(
+        &self,
+        email: &str,
+        name: &str,
+        subject: &str,
+        issuer: &str,
+        client_id: Option<&str>,
+        scope: UserScope,
+        idp_role_names: &[String],
+    ) -> Result<Principal> {
+        let mut principal = match self.principal_repo.find_by_external_identity(issuer, subject).await? {
+            Some(mut existing) => {
+                existing.name = name.to_string();
+                existing.updated_at = Utc::now();
+                existing
+            }
+            None => {
+                if self.principal_repo.find_by_email(email).await?.is_some() {
+                    warn!(
+                        email = %email,
+                        issuer = %issuer,
+                        "OIDC identity not linked to the existing user with this email - rejecting login"
+                    );
+                    return Err(PlatformError::forbidden(
+                        "An account with this email exists and is not linked to this identity",
+                    ));
+                }
+
+                let mut new_principal = Principal::new_user(email, scope);
+                new_principal.name = name.to_string();
+                if let Some(ref mut identity) = new_principal.user_identity {
+                    identity.external_id = Some(subject.to_string());
+                    identity.provider = Some(issuer.to_string());
+                }
+                new_principal.external_identity = Some(ExternalIdentity {
+                    provider_id: issuer.to_string(),
+                    external_id: subject.to_string(),
+                });
+                if let Some(cid) = client_id {
+                    new_principal.client_id = Some(cid.to_string());
+                }
+                self.principal_repo.insert(&new_principal).await?;
+                new_principal
+            }
+        };
+
+        principal.update_last_login();
+        self.principal_repo.update(&principal).await?;
+        self.sync_idp_roles(&mut principal, idp_role_names).await?;
+
+        info!(
+            principal_id = %principal.id,
+            issuer = %issuer,
+            "Linked OIDC user synchronized"
+        );
+
+        Ok(principal)
+    }
+
     /// Find IDP role mapping by IDP role name
     async fn find_idp_role_mapping(&self, idp_role_name: &str) -> Result<Option<IdpRoleMapping>> {
         // For now, search across all IDP types
@@ -9,7 +9,11 @@ use argon2::{
     },
     Argon2, Algorithm, Params, Version,
 };
-use tracing::{debug, warn};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 use crate::shared::error::{PlatformError, Result};
 
@@ -142,10 +146,77 @@ impl Argon2Config {
     }
 }
 
+/// Result of [`PasswordService::verify_and_upgrade`]
+#[derive(Debug, Clone)]
+pub struct PasswordVerification {
+    /// Whether the password matched
+    pub valid: bool,
+    /// Replacement hash using the current parameters, if the stored one was weaker
+    pub upgraded_hash: Option<String>,
+}
+
+/// How often [`PasswordService::start_lockout_pruner`] drops stale lockout entries
+pub const DEFAULT_LOCKOUT_PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Account lockout configuration for repeated failed password logins
+#[derive(Debug, Clone)]
+pub struct LockoutConfig {
+    /// Consecutive failures that trigger a lockout (default: 5)
+    pub max_failures: u32,
+    /// Duration of the first lockout; doubles on each repeat (default: 15 min)
+    pub lockout_duration: Duration,
+    /// Upper bound for escalated lockouts (default: 24 h)
+    pub max_lockout_duration: Duration,
+    /// Failures older than this no longer count (default: 15 min)
+    pub failure_window: Duration,
+}
+
+impl Default for LockoutConfig {
+    fn default() -> Self {
+        Self {
+            max_failures: 5,
+            lockout_duration: Duration::from_secs(15 * 60),
+            max_lockout_duration: Duration::from_secs(24 * 60 * 60),
+            failure_window: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+/// Failed-login state for one principal
+#[derive(Debug, Clone)]
+struct LockoutEntry {
+    failures: u32,
+    last_failure: Instant,
+    /// Number of lockouts so far; drives the exponential backoff
+    lockouts: u32,
+    locked_until: Option<Instant>,
+}
+
+impl LockoutEntry {
+    fn is_locked(&self, now: Instant) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+
+    /// Entries with no active lock and no recent activity can be forgotten
+    fn is_stale(&self, now: Instant, config: &LockoutConfig) -> bool {
+        !self.is_locked(now)
+            && now.duration_since(self.last_failure) > config.max_lockout_duration.max(config.failure_window)
+    }
+}
+
 /// Password authentication service
+///
+/// Also tracks consecutive failed logins per principal in memory. Lock state is
+/// per instance and is lost on restart: behind a load balancer each instance
+/// counts failures separately, and an admin unlock only clears the instance
+/// that handles it. Stale entries are dropped by [`Self::start_lockout_pruner`].
 pub struct PasswordService {
     argon2: Argon2<'static>,
     policy: PasswordPolicy,
+    lockout_config: LockoutConfig,
+    lockouts: Mutex<HashMap<String, LockoutEntry>>,
+    /// Hash of a random password, verified against when there is no real hash
+    dummy_hash: std::sync::OnceLock<String>,
 }
 
 impl PasswordService {
@@ -153,7 +224,131 @@ impl PasswordService {
         let params = config.to_params();
         let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
 
-        Self { argon2, policy }
+        Self {
+            argon2,
+            policy,
+            lockout_config: LockoutConfig::default(),
+            lockouts: Mutex::new(HashMap::new()),
+            dummy_hash: std::sync::OnceLock::new(),
+        }
+    }
+
+    pub fn with_lockout_config(mut self, config: LockoutConfig) -> Self {
+        self.lockout_config = config;
+        self
+    }
+
+    /// Record a failed password attempt
+    ///
+    /// Returns the lockout duration if this failure locked the account.
+    pub fn record_failure(&self, principal_id: &str) -> Option<Duration> {
+        self.record_failure_at(principal_id, Instant::now())
+    }
+
+    fn record_failure_at(&self, principal_id: &str, now: Instant) -> Option<Duration> {
+        let config = &self.lockout_config;
+        let mut lockouts = self.lockouts.lock();
+
+        let entry = lockouts.entry(principal_id.to_string()).or_insert(LockoutEntry {
+            failures: 0,
+            last_failure: now,
+            lockouts: 0,
+            locked_until: None,
+        });
+
+        if entry.is_stale(now, config) {
+            entry.lockouts = 0;
+        }
+        if now.duration_since(entry.last_failure) > config.failure_window {
+            entry.failures = 0;
+        }
+        entry.last_failure = now;
+
+        // Attempts while locked don't extend the lock
+        if entry.is_locked(now) {
+            return None;
+        }
+
+        entry.failures += 1;
+        if entry.failures < config.max_failures.max(1) {
+            return None;
+        }
+
+        let duration = config.lockout_duration
+            .saturating_mul(2u32.saturating_pow(entry.lockouts))
+            .min(config.max_lockout_duration);
+        entry.failures = 0;
+        entry.lockouts += 1;
+        entry.locked_until = Some(now + duration);
+
+        warn!(
+            principal_id = %principal_id,
+            lockout_secs = duration.as_secs(),
+            lockouts = entry.lockouts,
+            "Account locked after repeated failed logins"
+        );
+        Some(duration)
+    }
+
+    /// Drop lockout entries that no longer affect logins
+    ///
+    /// Returns the number of entries removed.
+    pub fn prune_lockouts(&self) -> usize {
+        self.prune_lockouts_at(Instant::now())
+    }
+
+    fn prune_lockouts_at(&self, now: Instant) -> usize {
+        let mut lockouts = self.lockouts.lock();
+        let before = lockouts.len();
+        lockouts.retain(|_, entry| !entry.is_stale(now, &self.lockout_config));
+        before - lockouts.len()
+    }
+
+    /// Prune stale lockout entries every `interval` until the service is dropped
+    pub fn start_lockout_pruner(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let service: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(service) = service.upgrade() else {
+                    break;
+                };
+                let pruned = service.prune_lockouts();
+                if pruned > 0 {
+                    debug!(pruned, "Pruned stale lockout entries");
+                }
+            }
+        })
+    }
+
+    /// Record a successful login, resetting failure and lockout history
+    pub fn record_success(&self, principal_id: &str) {
+        self.lockouts.lock().remove(principal_id);
+    }
+
+    /// Whether the principal is currently locked out
+    pub fn is_locked(&self, principal_id: &str) -> bool {
+        self.is_locked_at(principal_id, Instant::now())
+    }
+
+    fn is_locked_at(&self, principal_id: &str, now: Instant) -> bool {
+        self.lockouts.lock()
+            .get(principal_id)
+            .is_some_and(|entry| entry.is_locked(now))
+    }
+
+    /// Clear any lock and failure history (admin action)
+    ///
+    /// Returns true if the principal was locked.
+    pub fn clear_lock(&self, principal_id: &str) -> bool {
+        let removed = self.lockouts.lock().remove(principal_id);
+        let was_locked = removed.is_some_and(|entry| entry.is_locked(Instant::now()));
+        if was_locked {
+            info!(principal_id = %principal_id, "Account lock cleared");
+        }
+        was_locked
     }
 
     /// Hash a password using Argon2id
@@ -165,6 +360,10 @@ impl PasswordService {
             });
         }
 
+        self.hash_with_current_params(password)
+    }
+
+    fn hash_with_current_params(&self, password: &str) -> Result<String> {
         let salt = SaltString::generate(&mut OsRng);
 
         let hash = self
@@ -179,6 +378,9 @@ impl PasswordService {
     }
 
     /// Verify a password against a stored hash
+    ///
+    /// The hash's own parameters are used, so hashes created under an older
+    /// `Argon2Config` still verify.
     pub fn verify_password(&self, password: &str, hash: &str) -> Result<bool> {
         let parsed_hash = PasswordHash::new(hash).map_err(|e| PlatformError::Internal {
             message: format!("Invalid password hash format: {}", e),
@@ -199,22 +401,64 @@ impl PasswordService {
         }
     }
 
-    /// Check if a password hash needs to be upgraded (e.g., if config changed)
-    pub fn needs_rehash(&self, hash: &str) -> bool {
-        if let Ok(parsed) = PasswordHash::new(hash) {
-            // Check if algorithm is Argon2id
-            if parsed.algorithm != argon2::Algorithm::Argon2id.ident() {
-                return true;
-            }
+    /// Spend the same time as [`PasswordService::verify_password`] against a
+    /// hash that never matches
+    ///
+    /// Used when there is no stored hash to check (unknown, locked or
+    /// password-less principals) so those logins can't be told apart by timing.
+    pub fn verify_dummy(&self, password: &str) {
+        let hash = self.dummy_hash.get_or_init(|| {
+            let random = SaltString::generate(&mut OsRng);
+            self.hash_with_current_params(random.as_str())
+                .expect("hashing with the configured Argon2 parameters succeeds")
+        });
+        let _ = self.verify_password(password, hash);
+    }
 
-            // Check params (would need to parse and compare)
-            // For simplicity, we'll just return false here
-            // In production, you'd compare the params in the hash
+    /// Verify a password and, if it matches a hash made with weaker parameters
+    /// than the current `Argon2Config`, re-hash it with the current ones
+    ///
+    /// The caller is responsible for persisting `upgraded_hash`.
+    pub fn verify_and_upgrade(&self, password: &str, hash: &str) -> Result<PasswordVerification> {
+        if !self.verify_password(password, hash)? {
+            return Ok(PasswordVerification { valid: false, upgraded_hash: None });
+        }
 
-            false
+        let upgraded_hash = if self.needs_rehash(hash) {
+            // Skip the policy check: the user already has this password
+            let upgraded = self.hash_with_current_params(password)?;
+            info!("Upgrading password hash to current Argon2 parameters");
+            Some(upgraded)
         } else {
-            true // Invalid hash format needs rehash
+            None
+        };
+
+        Ok(PasswordVerification { valid: true, upgraded_hash })
+    }
+
+    /// Check if a password hash is weaker than the current configuration
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return true; // Invalid hash format needs rehash
+        };
+
+        if parsed.algorithm != argon2::Algorithm::Argon2id.ident() {
+            return true;
+        }
+        if parsed.version != Some(Version::V0x13 as u32) {
+            return true;
         }
+
+        let Ok(params) = Params::try_from(&parsed) else {
+            return true;
+        };
+        let current = self.argon2.params();
+
+        params.m_cost() < current.m_cost()
+            || params.t_cost() < current.t_cost()
+            || params.p_cost() < current.p_cost()
+            || params.output_len().unwrap_or(Params::DEFAULT_OUTPUT_LEN)
+                < current.output_len().unwrap_or(Params::DEFAULT_OUTPUT_LEN)
     }
 
     /// Validate password against policy without hashing
@@ -272,6 +516,136 @@ impl PasswordResetToken {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_weaker_hash_upgraded_on_verify() {
+        let old_service = PasswordService::new(Argon2Config::testing(), PasswordPolicy::lenient());
+        let old_hash = old_service.hash_password("CorrectHorse9!").unwrap();
+
+        let stronger = Argon2Config { memory_cost: 8192, time_cost: 2, ..Argon2Config::testing() };
+        let service = PasswordService::new(stronger, PasswordPolicy::default());
+        assert!(service.needs_rehash(&old_hash));
+
+        // Old hash still verifies during the transition
+        assert!(service.verify_password("CorrectHorse9!", &old_hash).unwrap());
+
+        let wrong = service.verify_and_upgrade("wrong-password", &old_hash).unwrap();
+        assert!(!wrong.valid);
+        assert!(wrong.upgraded_hash.is_none());
+
+        let result = service.verify_and_upgrade("CorrectHorse9!", &old_hash).unwrap();
+        assert!(result.valid);
+        let upgraded = result.upgraded_hash.expect("hash should be upgraded");
+        assert!(upgraded.contains("m=8192,t=2,p=1"));
+        assert!(!service.needs_rehash(&upgraded));
+        assert!(service.verify_password("CorrectHorse9!", &upgraded).unwrap());
+
+        // Already current: no upgrade
+        let again = service.verify_and_upgrade("CorrectHorse9!", &upgraded).unwrap();
+        assert!(again.valid);
+        assert!(again.upgraded_hash.is_none());
+    }
+
+    #[test]
+    fn test_stronger_hash_not_downgraded() {
+        let strong = Argon2Config { memory_cost: 8192, ..Argon2Config::testing() };
+        let hash = PasswordService::new(strong, PasswordPolicy::lenient())
+            .hash_password("password123")
+            .unwrap();
+        let service = PasswordService::new(Argon2Config::testing(), PasswordPolicy::lenient());
+        assert!(!service.needs_rehash(&hash));
+    }
+
+    fn lockout_service() -> PasswordService {
+        PasswordService::new(Argon2Config::testing(), PasswordPolicy::lenient())
+            .with_lockout_config(LockoutConfig {
+                max_failures: 3,
+                lockout_duration: Duration::from_secs(60),
+                max_lockout_duration: Duration::from_secs(600),
+                failure_window: Duration::from_secs(300),
+            })
+    }
+
+    #[test]
+    fn test_lockout_after_max_failures() {
+        let service = lockout_service();
+        let now = Instant::now();
+
+        assert_eq!(service.record_failure_at("p1", now), None);
+        assert_eq!(service.record_failure_at("p1", now), None);
+        assert!(!service.is_locked_at("p1", now));
+
+        assert_eq!(service.record_failure_at("p1", now), Some(Duration::from_secs(60)));
+        assert!(service.is_locked_at("p1", now));
+        assert!(!service.is_locked_at("p2", now));
+
+        // Lock expires
+        assert!(!service.is_locked_at("p1", now + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn test_lockout_escalates_exponentially() {
+        let service = lockout_service();
+        let mut now = Instant::now();
+        let mut durations = Vec::new();
+
+        for _ in 0..5 {
+            let lock = (0..3).filter_map(|_| service.record_failure_at("p1", now)).last();
+            let lock = lock.unwrap();
+            durations.push(lock.as_secs());
+            now += lock + Duration::from_secs(1);
+        }
+
+        assert_eq!(durations, vec![60, 120, 240, 480, 600]);
+    }
+
+    #[test]
+    fn test_failures_outside_window_reset() {
+        let service = lockout_service();
+        let now = Instant::now();
+
+        service.record_failure_at("p1", now);
+        service.record_failure_at("p1", now);
+        assert_eq!(service.record_failure_at("p1", now + Duration::from_secs(301)), None);
+        assert!(!service.is_locked_at("p1", now + Duration::from_secs(301)));
+    }
+
+    #[test]
+    fn test_success_and_clear_reset_lockout() {
+        let service = lockout_service();
+        let now = Instant::now();
+
+        service.record_failure_at("p1", now);
+        service.record_failure_at("p1", now);
+        service.record_success("p1");
+        assert_eq!(service.record_failure_at("p1", now), None);
+
+        for _ in 0..3 {
+            service.record_failure("p2");
+        }
+        assert!(service.is_locked("p2"));
+        assert!(service.clear_lock("p2"));
+        assert!(!service.is_locked("p2"));
+        assert!(!service.clear_lock("p2"));
+    }
+
+    #[test]
+    fn test_prune_drops_only_stale_entries() {
+        let service = lockout_service();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            service.record_failure_at("locked", now);
+        }
+        service.record_failure_at("recent", now + Duration::from_secs(500));
+
+        // Nothing is stale until the max lockout duration has passed
+        assert_eq!(service.prune_lockouts_at(now + Duration::from_secs(60)), 0);
+
+        assert_eq!(service.prune_lockouts_at(now + Duration::from_secs(601)), 1);
+        assert_eq!(service.lockouts.lock().len(), 1);
+        assert!(service.lockouts.lock().contains_key("recent"));
+    }
+
     #[test]
     fn test_password_policy_default() {
         let policy = PasswordPolicy::default();
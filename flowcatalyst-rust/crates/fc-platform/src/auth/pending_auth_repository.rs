@@ -0,0 +1,139 @@
+//! Pending Authorization Repository
+//!
+//! MongoDB repository for OAuth2 authorizations in progress, between
+//! `/oauth/authorize` and the login or OIDC callback that completes them.
+//!
+//! Handlers talk to pending authorizations through [`PendingAuthStore`] so
+//! the callback can land on a different instance than the one that started
+//! the flow; an in-memory store is provided for tests.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use mongodb::{bson::doc, Collection, Database};
+use tokio::sync::Mutex;
+
+use crate::auth::oauth_api::PendingAuth;
+use crate::shared::error::Result;
+
+/// Storage for pending authorizations, keyed by server-generated state.
+#[async_trait]
+pub trait PendingAuthStore: Send + Sync {
+    /// Store a newly started authorization.
+    async fn insert(&self, pending: &PendingAuth) -> Result<()>;
+
+    /// Atomically remove and return an unexpired pending authorization.
+    ///
+    /// Returns `None` if the state is unknown, expired, or was already
+    /// used, so each authorization can only be completed once.
+    async fn consume(&self, state: &str) -> Result<Option<PendingAuth>>;
+}
+
+/// Repository for pending authorizations.
+pub struct PendingAuthRepository {
+    collection: Collection<PendingAuth>,
+}
+
+impl PendingAuthRepository {
+    const COLLECTION_NAME: &'static str = "oauth_pending_authorizations";
+
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(Self::COLLECTION_NAME),
+        }
+    }
+
+    /// Insert a new pending authorization.
+    pub async fn insert(&self, pending: &PendingAuth) -> Result<()> {
+        self.collection.insert_one(pending).await?;
+        Ok(())
+    }
+
+    /// Delete and return an unexpired pending authorization in a single operation.
+    pub async fn consume_valid_state(&self, state: &str) -> Result<Option<PendingAuth>> {
+        let now = Utc::now();
+        let result = self.collection
+            .find_one_and_delete(doc! {
+                "_id": state,
+                "expires_at": { "$gt": now }
+            })
+            .await?;
+        Ok(result)
+    }
+
+    /// Delete all expired pending authorizations (abandoned logins).
+    pub async fn delete_expired(&self) -> Result<u64> {
+        let now = Utc::now();
+        let result = self.collection
+            .delete_many(doc! { "expires_at": { "$lt": now } })
+            .await?;
+        Ok(result.deleted_count)
+    }
+}
+
+#[async_trait]
+impl PendingAuthStore for PendingAuthRepository {
+    async fn insert(&self, pending: &PendingAuth) -> Result<()> {
+        PendingAuthRepository::insert(self, pending).await
+    }
+
+    async fn consume(&self, state: &str) -> Result<Option<PendingAuth>> {
+        self.consume_valid_state(state).await
+    }
+}
+
+/// In-memory pending authorization store for tests and single-instance setups.
+#[derive(Default)]
+pub struct InMemoryPendingAuthStore {
+    pending: Mutex<HashMap<String, PendingAuth>>,
+}
+
+impl InMemoryPendingAuthStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PendingAuthStore for InMemoryPendingAuthStore {
+    async fn insert(&self, pending: &PendingAuth) -> Result<()> {
+        self.pending.lock().await.insert(pending.state.clone(), pending.clone());
+        Ok(())
+    }
+
+    async fn consume(&self, state: &str) -> Result<Option<PendingAuth>> {
+        let removed = self.pending.lock().await.remove(state);
+        Ok(removed.filter(|p| !p.is_expired()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(state: &str) -> PendingAuth {
+        PendingAuth::new(state, "client-123", "https://example.com/callback")
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_state_is_single_use() {
+        let store = InMemoryPendingAuthStore::new();
+        store.insert(&pending("abc")).await.unwrap();
+
+        let consumed = store.consume("abc").await.unwrap();
+        assert_eq!(consumed.map(|p| p.client_id), Some("client-123".to_string()));
+        assert!(store.consume("abc").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_rejects_expired_and_unknown_states() {
+        let store = InMemoryPendingAuthStore::new();
+        let mut expired = pending("old");
+        expired.expires_at = Utc::now() - chrono::Duration::seconds(1);
+        store.insert(&expired).await.unwrap();
+
+        assert!(store.consume("old").await.unwrap().is_none());
+        assert!(store.consume("missing").await.unwrap().is_none());
+    }
+}
@@ -16,6 +16,7 @@ use super::repository::ClientRepository;
 use crate::shared::error::PlatformError;
 use crate::shared::api_common::{PaginationParams, CreatedResponse, SuccessResponse};
 use crate::shared::middleware::Authenticated;
+use crate::shared::patch::Patch;
 
 /// Create client request
 #[derive(Debug, Deserialize, ToSchema)]
@@ -37,10 +38,14 @@ pub struct CreateClientRequest {
 #[serde(rename_all = "camelCase")]
 pub struct UpdateClientRequest {
     /// Human-readable name
-    pub name: Option<String>,
-
-    /// Description
-    pub description: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub name: Patch<String>,
+
+    /// Description (`null` clears it)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub description: Patch<String>,
 }
 
 /// Status change request (for suspend/deactivate)
@@ -309,12 +314,8 @@ pub async fn update_client(
     let mut client = state.client_repo.find_by_id(&id).await?
         .ok_or_else(|| PlatformError::not_found("Client", &id))?;
 
-    if let Some(name) = req.name {
-        client.name = name;
-    }
-    if let Some(desc) = req.description {
-        client.description = Some(desc);
-    }
+    req.name.apply_required(&mut client.name, "name")?;
+    req.description.apply_to(&mut client.description);
     client.updated_at = chrono::Utc::now();
 
     state.client_repo.update(&client).await?;
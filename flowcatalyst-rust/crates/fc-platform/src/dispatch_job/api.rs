@@ -15,10 +15,12 @@ use crate::{
     DispatchJob, DispatchJobRead, DispatchStatus, DispatchKind, DispatchMode,
     DispatchAttempt, RetryStrategy, DispatchMetadata,
 };
-use crate::DispatchJobRepository;
+use crate::{DispatchJobRepository, SubscriptionRepository};
+use crate::dispatch_job::repository::{DispatchJobCancelFilter, CANCELLABLE_STATUSES};
 use crate::shared::error::PlatformError;
 use crate::shared::api_common::PaginationParams;
 use crate::shared::middleware::Authenticated;
+use crate::shared::projections_service::DispatchJobProjectionWriter;
 
 /// Dispatch job response DTO (matches Java DispatchJobReadResponse)
 #[derive(Debug, Serialize, ToSchema)]
@@ -208,6 +210,21 @@ pub struct DispatchJobsQuery {
 #[derive(Clone)]
 pub struct DispatchJobsState {
     pub dispatch_job_repo: Arc<DispatchJobRepository>,
+    pub subscription_repo: Arc<SubscriptionRepository>,
+    pub projection_writer: Arc<DispatchJobProjectionWriter>,
+}
+
+fn parse_status(status: &str) -> Result<DispatchStatus, PlatformError> {
+    match status.to_uppercase().as_str() {
+        "PENDING" => Ok(DispatchStatus::Pending),
+        "QUEUED" => Ok(DispatchStatus::Queued),
+        "IN_PROGRESS" => Ok(DispatchStatus::InProgress),
+        "COMPLETED" => Ok(DispatchStatus::Completed),
+        "FAILED" => Ok(DispatchStatus::Failed),
+        "EXPIRED" => Ok(DispatchStatus::Expired),
+        "CANCELLED" => Ok(DispatchStatus::Cancelled),
+        _ => Err(PlatformError::validation(format!("Invalid status: {}", status))),
+    }
 }
 
 // ============================================================================
@@ -313,6 +330,129 @@ pub struct BatchCreateDispatchJobsResponse {
     pub count: usize,
 }
 
+// ============================================================================
+// Bulk Cancel Request & Response
+// ============================================================================
+
+/// How long a bulk-cancel confirmation token stays valid
+const CANCEL_CONFIRMATION_TTL_SECS: i64 = 300;
+
+/// Request to cancel dispatch jobs in bulk
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelDispatchJobsRequest {
+    /// Subscription whose jobs should be cancelled; omitting it (anchor only)
+    /// matches jobs of every subscription
+    pub subscription_id: Option<String>,
+
+    /// Only match jobs in this status. Only PENDING jobs can be cancelled, so any
+    /// other status is rejected; omitting it still reports queued and in-progress
+    /// jobs in `notCancellable`
+    pub status: Option<String>,
+
+    /// Only cancel jobs created before this time (RFC 3339)
+    pub created_before: Option<String>,
+
+    /// Token from a previous unconfirmed call with the same filter
+    pub confirmation_token: Option<String>,
+}
+
+/// Bulk cancel response
+///
+/// Without a confirmation token nothing is cancelled; the response reports
+/// how many jobs match and carries the token needed to confirm.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelDispatchJobsResponse {
+    /// Pending jobs matching the filter
+    pub matched: u64,
+    /// Jobs transitioned to CANCELLED
+    pub cancelled: u64,
+    /// Matching jobs already queued or in progress, which are left to finish
+    pub not_cancellable: u64,
+    /// Token to repeat the request with to perform the cancellation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_token: Option<String>,
+    /// When the confirmation token expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_expires_at: Option<String>,
+}
+
+impl CancelDispatchJobsRequest {
+    fn to_filter(&self) -> Result<DispatchJobCancelFilter, PlatformError> {
+        let subscription_id = match self.subscription_id.as_deref().map(str::trim) {
+            Some("") => return Err(PlatformError::validation("subscriptionId must not be empty")),
+            other => other.map(str::to_string),
+        };
+
+        let status = match self.status.as_deref() {
+            Some(s) => {
+                let status = parse_status(s)?;
+                if !CANCELLABLE_STATUSES.contains(&status) {
+                    return Err(PlatformError::validation(format!(
+                        "Cannot cancel jobs in status {}: only PENDING jobs can be cancelled", s
+                    )));
+                }
+                Some(status)
+            }
+            None => None,
+        };
+
+        let created_before = match self.created_before.as_deref() {
+            Some(s) => Some(
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map_err(|_| PlatformError::validation(format!("Invalid createdBefore: {}", s)))?
+                    .with_timezone(&chrono::Utc),
+            ),
+            None => None,
+        };
+
+        Ok(DispatchJobCancelFilter {
+            subscription_id,
+            status,
+            created_before,
+        })
+    }
+}
+
+/// Confirmation token binding the caller and filter to an expiry time.
+/// This guards against accidental mass cancellation; it is not a credential.
+fn cancel_confirmation_token(principal_id: &str, filter: &DispatchJobCancelFilter, expires_at: i64) -> String {
+    use sha2::{Sha256, Digest};
+    use base64::Engine;
+
+    let mut hasher = Sha256::new();
+    hasher.update(principal_id.as_bytes());
+    hasher.update(b"|");
+    hasher.update(filter.to_document().to_string().as_bytes());
+    hasher.update(b"|");
+    hasher.update(filter.in_flight_document().to_string().as_bytes());
+    hasher.update(b"|");
+    hasher.update(expires_at.to_string().as_bytes());
+    let hash = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(hasher.finalize());
+    format!("{}.{}", expires_at, hash)
+}
+
+fn verify_cancel_confirmation(
+    token: &str,
+    principal_id: &str,
+    filter: &DispatchJobCancelFilter,
+    now: i64,
+) -> Result<(), PlatformError> {
+    let expires_at = token
+        .split_once('.')
+        .and_then(|(exp, _)| exp.parse::<i64>().ok())
+        .ok_or_else(|| PlatformError::validation("Invalid confirmation token"))?;
+
+    if cancel_confirmation_token(principal_id, filter, expires_at) != token {
+        return Err(PlatformError::validation("Confirmation token does not match this request"));
+    }
+    if expires_at < now {
+        return Err(PlatformError::validation("Confirmation token has expired"));
+    }
+    Ok(())
+}
+
 /// Dispatch attempt response DTO
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -410,15 +550,7 @@ pub async fn list_dispatch_jobs(
         }
         state.dispatch_job_repo.find_by_client(client_id, query.pagination.size() as i64).await?
     } else if let Some(ref status_str) = query.status {
-        let status = match status_str.to_uppercase().as_str() {
-            "PENDING" => DispatchStatus::Pending,
-            "QUEUED" => DispatchStatus::Queued,
-            "IN_PROGRESS" => DispatchStatus::InProgress,
-            "COMPLETED" => DispatchStatus::Completed,
-            "FAILED" => DispatchStatus::Failed,
-            "EXPIRED" => DispatchStatus::Expired,
-            _ => return Err(PlatformError::validation(format!("Invalid status: {}", status_str))),
-        };
+        let status = parse_status(status_str)?;
         state.dispatch_job_repo.find_by_status(status, query.pagination.size() as i64).await?
     } else {
         // Return empty for now - need proper listing
@@ -751,13 +883,188 @@ pub async fn get_dispatch_job_attempts(
     Ok(Json(attempts))
 }
 
+/// Cancel dispatch jobs in bulk
+///
+/// Call once without `confirmationToken` to see how many jobs match and
+/// obtain a token, then repeat the same request with the token to cancel
+/// every matching PENDING job. QUEUED and IN_PROGRESS jobs are already on
+/// the queue and are only reported in `notCancellable`. Omitting
+/// `subscriptionId` matches every subscription and requires anchor access.
+#[utoipa::path(
+    post,
+    path = "/cancel",
+    tag = "dispatch-jobs",
+    operation_id = "postApiBffDispatchJobsCancel",
+    request_body = CancelDispatchJobsRequest,
+    responses(
+        (status = 200, description = "Match count and confirmation token, or cancellation result", body = CancelDispatchJobsResponse),
+        (status = 400, description = "Invalid filter or confirmation token"),
+        (status = 403, description = "No access to subscription, or no subscription given by a non-anchor"),
+        (status = 404, description = "Subscription not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn cancel_dispatch_jobs(
+    State(state): State<DispatchJobsState>,
+    auth: Authenticated,
+    Json(req): Json<CancelDispatchJobsRequest>,
+) -> Result<Json<CancelDispatchJobsResponse>, PlatformError> {
+    crate::shared::authorization_service::checks::can_cancel_dispatch_jobs(&auth.0)?;
+
+    let filter = req.to_filter()?;
+
+    // Check access via the owning subscription; anchors may also clean up
+    // jobs whose subscription has since been deleted
+    match filter.subscription_id.as_deref() {
+        Some(subscription_id) => match state.subscription_repo.find_by_id(subscription_id).await? {
+            Some(subscription) => match subscription.client_id {
+                Some(ref cid) if !auth.0.can_access_client(cid) => {
+                    return Err(PlatformError::forbidden("No access to this subscription"));
+                }
+                None if !auth.0.is_anchor() => {
+                    return Err(PlatformError::forbidden("No access to this subscription"));
+                }
+                _ => {}
+            },
+            None if !auth.0.is_anchor() => {
+                return Err(PlatformError::not_found("Subscription", subscription_id));
+            }
+            None => {}
+        },
+        None if !auth.0.is_anchor() => {
+            return Err(PlatformError::forbidden("Cancelling jobs across subscriptions requires anchor access"));
+        }
+        None => {}
+    }
+
+    let now = chrono::Utc::now();
+
+    let Some(token) = req.confirmation_token.as_deref() else {
+        let matched = state.dispatch_job_repo.count_cancellable(&filter).await?;
+        let not_cancellable = state.dispatch_job_repo.count_not_cancellable(&filter).await?;
+        let expires_at = now + chrono::Duration::seconds(CANCEL_CONFIRMATION_TTL_SECS);
+        return Ok(Json(CancelDispatchJobsResponse {
+            matched,
+            cancelled: 0,
+            not_cancellable,
+            confirmation_token: Some(cancel_confirmation_token(&auth.0.principal_id, &filter, expires_at.timestamp())),
+            confirmation_expires_at: Some(expires_at.to_rfc3339()),
+        }));
+    };
+
+    verify_cancel_confirmation(token, &auth.0.principal_id, &filter, now.timestamp())?;
+
+    let jobs = state.dispatch_job_repo.cancel_matching(&filter).await?;
+    let cancelled = jobs.len() as u64;
+    state.projection_writer.project_batch(jobs.iter().collect()).await?;
+    let not_cancellable = state.dispatch_job_repo.count_not_cancellable(&filter).await?;
+
+    tracing::info!(
+        subscription_id = filter.subscription_id.as_deref().unwrap_or("*"),
+        principal_id = %auth.0.principal_id,
+        cancelled,
+        "Bulk cancelled dispatch jobs"
+    );
+
+    Ok(Json(CancelDispatchJobsResponse {
+        matched: cancelled,
+        cancelled,
+        not_cancellable,
+        confirmation_token: None,
+        confirmation_expires_at: None,
+    }))
+}
+
 /// Create dispatch jobs router
 pub fn dispatch_jobs_router(state: DispatchJobsState) -> OpenApiRouter {
     OpenApiRouter::new()
         .routes(routes!(list_dispatch_jobs, create_dispatch_job))
         .routes(routes!(batch_create_dispatch_jobs))
+        .routes(routes!(cancel_dispatch_jobs))
         .routes(routes!(get_dispatch_job))
         .routes(routes!(get_dispatch_job_attempts))
         .routes(routes!(get_jobs_for_event))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter() -> DispatchJobCancelFilter {
+        DispatchJobCancelFilter {
+            subscription_id: Some("sub-1".to_string()),
+            status: Some(DispatchStatus::Pending),
+            created_before: None,
+        }
+    }
+
+    #[test]
+    fn test_cancel_confirmation_round_trip() {
+        let token = cancel_confirmation_token("principal-1", &filter(), 1_000);
+        assert!(verify_cancel_confirmation(&token, "principal-1", &filter(), 900).is_ok());
+    }
+
+    #[test]
+    fn test_cancel_confirmation_rejects_mismatch_and_expiry() {
+        let token = cancel_confirmation_token("principal-1", &filter(), 1_000);
+
+        let widened = DispatchJobCancelFilter { status: None, ..filter() };
+        assert!(verify_cancel_confirmation(&token, "principal-1", &widened, 900).is_err());
+        let all_subscriptions = DispatchJobCancelFilter { subscription_id: None, ..filter() };
+        assert!(verify_cancel_confirmation(&token, "principal-1", &all_subscriptions, 900).is_err());
+        assert!(verify_cancel_confirmation(&token, "principal-2", &filter(), 900).is_err());
+        assert!(verify_cancel_confirmation(&token, "principal-1", &filter(), 1_001).is_err());
+        assert!(verify_cancel_confirmation("garbage", "principal-1", &filter(), 900).is_err());
+
+        // Extending the expiry invalidates the hash
+        let forged = token.replacen("1000", "9999", 1);
+        assert!(verify_cancel_confirmation(&forged, "principal-1", &filter(), 900).is_err());
+    }
+
+    #[test]
+    fn test_cancel_request_rejects_non_cancellable_status() {
+        let req = CancelDispatchJobsRequest {
+            subscription_id: Some("sub-1".to_string()),
+            status: Some("COMPLETED".to_string()),
+            created_before: None,
+            confirmation_token: None,
+        };
+        assert!(req.to_filter().is_err());
+
+        // Already on the queue, so a filter for them could never cancel anything
+        for status in ["QUEUED", "in_progress"] {
+            let queued = CancelDispatchJobsRequest {
+                subscription_id: Some("sub-1".to_string()),
+                status: Some(status.to_string()),
+                created_before: None,
+                confirmation_token: None,
+            };
+            let err = queued.to_filter().unwrap_err();
+            assert!(err.to_string().contains("only PENDING jobs can be cancelled"), "{}", err);
+        }
+
+        let req = CancelDispatchJobsRequest {
+            status: Some("pending".to_string()),
+            created_before: Some("2026-01-01T00:00:00Z".to_string()),
+            ..req
+        };
+        let filter = req.to_filter().unwrap();
+        assert_eq!(filter.status, Some(DispatchStatus::Pending));
+        assert!(filter.created_before.is_some());
+    }
+
+    #[test]
+    fn test_cancel_request_subscription_is_optional() {
+        let req = CancelDispatchJobsRequest {
+            subscription_id: None,
+            status: None,
+            created_before: None,
+            confirmation_token: None,
+        };
+        assert_eq!(req.to_filter().unwrap().subscription_id, None);
+
+        let req = CancelDispatchJobsRequest { subscription_id: Some("  ".to_string()), ..req };
+        assert!(req.to_filter().is_err());
+    }
+}
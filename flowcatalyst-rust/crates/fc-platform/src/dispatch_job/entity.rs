@@ -39,6 +39,8 @@ pub enum DispatchStatus {
     Failed,
     /// Job expired (TTL exceeded)
     Expired,
+    /// Job cancelled before completion
+    Cancelled,
 }
 
 impl Default for DispatchStatus {
@@ -49,7 +51,7 @@ impl Default for DispatchStatus {
 
 impl DispatchStatus {
     pub fn is_terminal(&self) -> bool {
-        matches!(self, Self::Completed | Self::Failed | Self::Expired)
+        matches!(self, Self::Completed | Self::Failed | Self::Expired | Self::Cancelled)
     }
 
     pub fn is_successful(&self) -> bool {
@@ -8,5 +8,5 @@ pub mod api;
 
 // Re-export main types
 pub use entity::{DispatchJob, DispatchStatus};
-pub use repository::DispatchJobRepository;
+pub use repository::{DispatchJobRepository, DispatchJobCancelFilter};
 pub use api::{dispatch_jobs_router};
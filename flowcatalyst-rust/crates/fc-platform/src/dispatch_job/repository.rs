@@ -1,11 +1,72 @@
 //! DispatchJob Repository
 
-use mongodb::{Collection, Database, bson::doc};
+use mongodb::{Collection, Database, bson::{doc, Bson, Document}};
 use futures::TryStreamExt;
 use chrono::{DateTime, Utc};
 use crate::{DispatchJob, DispatchJobRead, DispatchStatus};
 use crate::shared::error::Result;
 
+/// Statuses a job can be cancelled from. Queued and in-progress jobs are
+/// already on the queue, so cancelling them here would not stop delivery.
+pub const CANCELLABLE_STATUSES: &[DispatchStatus] = &[DispatchStatus::Pending];
+
+/// Non-terminal statuses past the point where a job can be cancelled
+pub const IN_FLIGHT_STATUSES: &[DispatchStatus] = &[
+    DispatchStatus::Queued,
+    DispatchStatus::InProgress,
+];
+
+/// Maximum number of IDs per `$in` clause when cancelling in bulk
+const CANCEL_BATCH_SIZE: usize = 1000;
+
+/// Selects dispatch jobs to cancel in bulk
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchJobCancelFilter {
+    /// Only jobs of this subscription; `None` matches every subscription
+    pub subscription_id: Option<String>,
+    /// Only jobs in this status; `None` means every non-terminal status
+    pub status: Option<DispatchStatus>,
+    /// Only jobs created strictly before this time
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl DispatchJobCancelFilter {
+    /// Query for the matching jobs that can be cancelled
+    pub fn to_document(&self) -> Document {
+        self.query(CANCELLABLE_STATUSES)
+    }
+
+    /// Query for the matching jobs that are already queued or in progress
+    pub fn in_flight_document(&self) -> Document {
+        self.query(IN_FLIGHT_STATUSES)
+    }
+
+    /// Matching jobs in any of `statuses`. Terminal statuses are never matched.
+    fn query(&self, statuses: &[DispatchStatus]) -> Document {
+        let statuses: Vec<Bson> = statuses
+            .iter()
+            .filter(|s| self.status.is_none_or(|wanted| wanted == **s))
+            .map(|s| Bson::String(status_str(*s)))
+            .collect();
+
+        let mut filter = doc! { "status": { "$in": statuses } };
+        if let Some(ref subscription_id) = self.subscription_id {
+            filter.insert("subscriptionId", subscription_id);
+        }
+        if let Some(before) = self.created_before {
+            filter.insert("createdAt", doc! { "$lt": mongodb::bson::DateTime::from_chrono(before) });
+        }
+        filter
+    }
+}
+
+fn status_str(status: DispatchStatus) -> String {
+    serde_json::to_string(&status)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
 pub struct DispatchJobRepository {
     collection: Collection<DispatchJob>,
     read_collection: Collection<DispatchJobRead>,
@@ -121,6 +182,55 @@ impl DispatchJobRepository {
         Ok(result.modified_count > 0)
     }
 
+    /// Count jobs that a bulk cancellation with this filter would affect
+    pub async fn count_cancellable(&self, filter: &DispatchJobCancelFilter) -> Result<u64> {
+        Ok(self.collection.count_documents(filter.to_document()).await?)
+    }
+
+    /// Count matching jobs that are already queued or in progress and can't be cancelled
+    pub async fn count_not_cancellable(&self, filter: &DispatchJobCancelFilter) -> Result<u64> {
+        Ok(self.collection.count_documents(filter.in_flight_document()).await?)
+    }
+
+    /// Transition all pending jobs matching the filter to CANCELLED.
+    /// Returns the jobs as stored after cancellation; jobs that were queued
+    /// concurrently are left untouched and not returned.
+    pub async fn cancel_matching(&self, filter: &DispatchJobCancelFilter) -> Result<Vec<DispatchJob>> {
+        use mongodb::options::FindOptions;
+
+        let options = FindOptions::builder().projection(doc! { "_id": 1 }).build();
+        let ids: Vec<String> = self.collection
+            .clone_with_type::<Document>()
+            .find(filter.to_document())
+            .with_options(options)
+            .await?
+            .try_collect::<Vec<Document>>()
+            .await?
+            .into_iter()
+            .filter_map(|d| d.get_str("_id").ok().map(str::to_string))
+            .collect();
+
+        let cancellable: Vec<String> = CANCELLABLE_STATUSES.iter().map(|s| status_str(*s)).collect();
+        let now = mongodb::bson::DateTime::from_chrono(Utc::now());
+        let mut cancelled = Vec::new();
+
+        for chunk in ids.chunks(CANCEL_BATCH_SIZE) {
+            self.collection
+                .update_many(
+                    doc! { "_id": { "$in": chunk.to_vec() }, "status": { "$in": cancellable.clone() } },
+                    doc! { "$set": { "status": status_str(DispatchStatus::Cancelled), "updatedAt": now, "completedAt": now } },
+                )
+                .await?;
+
+            let cursor = self.collection
+                .find(doc! { "_id": { "$in": chunk.to_vec() }, "status": status_str(DispatchStatus::Cancelled), "updatedAt": now })
+                .await?;
+            cancelled.extend(cursor.try_collect::<Vec<_>>().await?);
+        }
+
+        Ok(cancelled)
+    }
+
     // Read projection methods
     pub async fn find_read_by_id(&self, id: &str) -> Result<Option<DispatchJobRead>> {
         Ok(self.read_collection.find_one(doc! { "_id": id }).await?)
@@ -171,3 +281,60 @@ impl DispatchJobRepository {
         Ok(cursor.try_collect().await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_filter_only_cancels_pending_jobs() {
+        let filter = DispatchJobCancelFilter {
+            subscription_id: Some("sub-1".to_string()),
+            status: None,
+            created_before: None,
+        };
+
+        let doc = filter.to_document();
+        assert_eq!(doc.get_str("subscriptionId").unwrap(), "sub-1");
+        let statuses = doc.get_document("status").unwrap().get_array("$in").unwrap();
+        assert_eq!(statuses, &vec![Bson::String("PENDING".to_string())]);
+        assert!(!doc.contains_key("createdAt"));
+
+        // Queued and in-progress jobs are reported, not cancelled
+        let doc = filter.in_flight_document();
+        let statuses = doc.get_document("status").unwrap().get_array("$in").unwrap();
+        assert_eq!(statuses, &vec![Bson::String("QUEUED".to_string()), Bson::String("IN_PROGRESS".to_string())]);
+    }
+
+    #[test]
+    fn test_cancel_filter_without_subscription_matches_all() {
+        let filter = DispatchJobCancelFilter {
+            subscription_id: None,
+            status: None,
+            created_before: Some(Utc::now()),
+        };
+
+        let doc = filter.to_document();
+        assert!(!doc.contains_key("subscriptionId"));
+        assert!(doc.contains_key("createdAt"));
+    }
+
+    #[test]
+    fn test_cancel_filter_ignores_terminal_statuses() {
+        let filter = DispatchJobCancelFilter {
+            subscription_id: Some("sub-1".to_string()),
+            status: Some(DispatchStatus::Pending),
+            created_before: Some(Utc::now()),
+        };
+
+        let doc = filter.to_document();
+        let statuses = doc.get_document("status").unwrap().get_array("$in").unwrap();
+        assert_eq!(statuses, &vec![Bson::String("PENDING".to_string())]);
+        assert!(doc.get_document("createdAt").unwrap().contains_key("$lt"));
+        assert!(filter.in_flight_document().get_document("status").unwrap().get_array("$in").unwrap().is_empty());
+
+        let terminal = DispatchJobCancelFilter { status: Some(DispatchStatus::Completed), ..filter };
+        let doc = terminal.to_document();
+        assert!(doc.get_document("status").unwrap().get_array("$in").unwrap().is_empty());
+    }
+}
@@ -3,7 +3,9 @@
 //! REST endpoints for event management.
 
 use axum::{
+    body::Bytes,
     extract::{State, Path, Query},
+    http::HeaderMap,
     Json,
 };
 use utoipa_axum::{router::OpenApiRouter, routes};
@@ -16,6 +18,7 @@ use crate::EventRepository;
 use crate::shared::error::PlatformError;
 use crate::shared::api_common::PaginationParams;
 use crate::shared::middleware::Authenticated;
+use super::cloudevents;
 
 /// Context data for event filtering/searching
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
@@ -57,9 +60,17 @@ pub struct CreateEventRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subject: Option<String>,
 
+    /// Event timestamp (RFC 3339, defaults to now)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+
     /// Event payload data
     pub data: serde_json::Value,
 
+    /// Content type of the payload (defaults to application/json)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_content_type: Option<String>,
+
     /// Message group for FIFO ordering
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message_group: Option<String>,
@@ -214,6 +225,11 @@ pub struct EventsState {
 /// Creates a new event in the event store. If a deduplicationId is provided and
 /// an event with that ID already exists, the existing event is returned (idempotent operation).
 /// Dispatch jobs are automatically created for matching subscriptions.
+///
+/// Also accepts CloudEvents 1.0 over HTTP, in binary content mode (`ce-*` headers)
+/// or structured content mode (`application/cloudevents+json`). The CloudEvent
+/// `source` and `id` together form the deduplication ID. Other requests must be
+/// `application/json`.
 #[utoipa::path(
     post,
     path = "",
@@ -224,18 +240,22 @@ pub struct EventsState {
         (status = 201, description = "Event created", body = CreateEventResponse),
         (status = 200, description = "Event already exists (idempotent)", body = CreateEventResponse),
         (status = 400, description = "Validation error"),
-        (status = 403, description = "No access to client")
+        (status = 403, description = "No access to client"),
+        (status = 415, description = "Unsupported content type")
     ),
     security(("bearer_auth" = []))
 )]
 pub async fn create_event(
     State(state): State<EventsState>,
     auth: Authenticated,
-    Json(req): Json<CreateEventRequest>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Result<(axum::http::StatusCode, Json<CreateEventResponse>), PlatformError> {
     // Verify permission
     crate::shared::authorization_service::checks::can_write_events(&auth.0)?;
 
+    let req = cloudevents::parse_create_event_request(&headers, &body)?;
+
     // Check for duplicate deduplication ID
     if let Some(ref dedup_id) = req.deduplication_id {
         if let Some(existing) = state.event_repo.find_by_deduplication_id(dedup_id).await? {
@@ -270,6 +290,12 @@ pub async fn create_event(
     // Create event
     let mut event = Event::new(&req.event_type, &req.source, req.data);
 
+    if let Some(time) = req.time {
+        event.time = parse_event_time(&time)?;
+    }
+    if let Some(content_type) = req.data_content_type {
+        event.data_content_type = content_type;
+    }
     if let Some(subject) = req.subject {
         event = event.with_subject(subject);
     }
@@ -475,6 +501,12 @@ pub async fn batch_create_events(
         // Create event
         let mut event = Event::new(&event_req.event_type, &event_req.source, event_req.data);
 
+        if let Some(time) = event_req.time {
+            event.time = parse_event_time(&time)?;
+        }
+        if let Some(content_type) = event_req.data_content_type {
+            event.data_content_type = content_type;
+        }
         if let Some(subject) = event_req.subject {
             event = event.with_subject(subject);
         }
@@ -520,6 +552,13 @@ pub async fn batch_create_events(
     }))
 }
 
+/// Parse an RFC 3339 event timestamp
+fn parse_event_time(time: &str) -> Result<chrono::DateTime<chrono::Utc>, PlatformError> {
+    chrono::DateTime::parse_from_rfc3339(time)
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .map_err(|_| PlatformError::validation(format!("Invalid event time (expected RFC 3339): {}", time)))
+}
+
 /// Create events router
 pub fn events_router(state: EventsState) -> OpenApiRouter {
     OpenApiRouter::new()
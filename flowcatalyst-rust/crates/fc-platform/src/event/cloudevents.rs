@@ -0,0 +1,302 @@
+//! CloudEvents HTTP Binding
+//!
+//! Parses CloudEvents 1.0 requests into the internal create-event request.
+//! Supports binary content mode (`ce-*` headers with the data as the raw body)
+//! and structured content mode (`application/cloudevents+json`). Any other
+//! request is treated as the platform's own JSON format.
+//!
+//! Besides the core attributes, the `correlationid`, `causationid` and
+//! `messagegroup` extension attributes are mapped onto the event. CloudEvents
+//! ids are only unique per `source`, so the deduplication ID is built from both
+//! and re-sending an event with the same `source` and `id` is idempotent.
+
+use axum::http::{header, HeaderMap};
+use serde::Deserialize;
+
+use crate::event::api::CreateEventRequest;
+use crate::event::entity::CLOUDEVENTS_SPEC_VERSION;
+use crate::shared::error::PlatformError;
+
+/// Content type of a structured-mode CloudEvent
+pub const STRUCTURED_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Header carrying the spec version in binary mode
+const SPEC_VERSION_HEADER: &str = "ce-specversion";
+
+/// Structured-mode CloudEvent (JSON event format)
+#[derive(Debug, Deserialize)]
+struct StructuredCloudEvent {
+    specversion: String,
+    id: String,
+    source: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    subject: Option<String>,
+    time: Option<String>,
+    datacontenttype: Option<String>,
+    #[serde(default)]
+    data: serde_json::Value,
+    data_base64: Option<String>,
+    correlationid: Option<String>,
+    causationid: Option<String>,
+    messagegroup: Option<String>,
+}
+
+/// Parse an event creation request in whichever content mode it uses
+pub fn parse_create_event_request(headers: &HeaderMap, body: &[u8]) -> Result<CreateEventRequest, PlatformError> {
+    if headers.contains_key(SPEC_VERSION_HEADER) {
+        return parse_binary(headers, body);
+    }
+
+    if content_type(headers).is_some_and(|ct| ct.eq_ignore_ascii_case(STRUCTURED_CONTENT_TYPE)) {
+        return parse_structured(body);
+    }
+
+    if !content_type(headers).is_some_and(is_json_media_type) {
+        return Err(PlatformError::UnsupportedMediaType {
+            message: "Expected request with `Content-Type: application/json`".to_string(),
+        });
+    }
+
+    serde_json::from_slice(body)
+        .map_err(|e| PlatformError::validation(format!("Invalid event request body: {}", e)))
+}
+
+/// Deduplication ID for a CloudEvent
+///
+/// The source length prefix keeps the key unambiguous whatever characters the
+/// source and id contain.
+fn deduplication_id(source: &str, id: &str) -> String {
+    format!("ce:{}:{}:{}", source.len(), source, id)
+}
+
+/// Media type of the request without parameters such as `charset`
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or_default().trim())
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    let media_type = media_type.to_ascii_lowercase();
+    media_type == "application/json" || media_type == "text/json" || media_type.ends_with("+json")
+}
+
+fn check_spec_version(version: &str) -> Result<(), PlatformError> {
+    if version != CLOUDEVENTS_SPEC_VERSION {
+        return Err(PlatformError::validation(format!(
+            "Unsupported CloudEvents specversion: {} (expected {})",
+            version, CLOUDEVENTS_SPEC_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn parse_binary(headers: &HeaderMap, body: &[u8]) -> Result<CreateEventRequest, PlatformError> {
+    let attr = |name: &str| -> Result<Option<String>, PlatformError> {
+        headers
+            .get(format!("ce-{}", name))
+            .map(|v| {
+                v.to_str()
+                    .map(str::to_string)
+                    .map_err(|_| PlatformError::validation(format!("Header ce-{} is not valid text", name)))
+            })
+            .transpose()
+    };
+    let required = |name: &str| -> Result<String, PlatformError> {
+        attr(name)?
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| PlatformError::validation(format!("Missing required header ce-{}", name)))
+    };
+
+    check_spec_version(&required("specversion")?)?;
+
+    let data_content_type = content_type(headers).map(str::to_string);
+    let data = match data_content_type.as_deref() {
+        _ if body.is_empty() => serde_json::Value::Null,
+        None => parse_json_data(body)?,
+        Some(ct) if is_json_media_type(ct) => parse_json_data(body)?,
+        Some(_) => serde_json::Value::String(
+            String::from_utf8(body.to_vec())
+                .map_err(|_| PlatformError::validation("Non-JSON event data must be valid UTF-8"))?,
+        ),
+    };
+
+    let source = required("source")?;
+    Ok(CreateEventRequest {
+        event_type: required("type")?,
+        deduplication_id: Some(deduplication_id(&source, &required("id")?)),
+        source,
+        subject: attr("subject")?,
+        time: attr("time")?,
+        data,
+        data_content_type,
+        message_group: attr("messagegroup")?,
+        correlation_id: attr("correlationid")?,
+        causation_id: attr("causationid")?,
+        client_id: None,
+        context_data: vec![],
+    })
+}
+
+fn parse_json_data(body: &[u8]) -> Result<serde_json::Value, PlatformError> {
+    serde_json::from_slice(body)
+        .map_err(|e| PlatformError::validation(format!("Invalid JSON event data: {}", e)))
+}
+
+fn parse_structured(body: &[u8]) -> Result<CreateEventRequest, PlatformError> {
+    let event: StructuredCloudEvent = serde_json::from_slice(body)
+        .map_err(|e| PlatformError::validation(format!("Invalid CloudEvent: {}", e)))?;
+
+    check_spec_version(&event.specversion)?;
+    if event.data_base64.is_some() {
+        return Err(PlatformError::validation("CloudEvents data_base64 is not supported"));
+    }
+    if event.id.is_empty() || event.source.is_empty() || event.event_type.is_empty() {
+        return Err(PlatformError::validation("CloudEvent id, source and type must not be empty"));
+    }
+
+    Ok(CreateEventRequest {
+        event_type: event.event_type,
+        deduplication_id: Some(deduplication_id(&event.source, &event.id)),
+        source: event.source,
+        subject: event.subject,
+        time: event.time,
+        data: event.data,
+        data_content_type: event.datacontenttype,
+        message_group: event.messagegroup,
+        correlation_id: event.correlationid,
+        causation_id: event.causationid,
+        client_id: None,
+        context_data: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn test_binary_mode() {
+        let h = headers(&[
+            ("ce-specversion", "1.0"),
+            ("ce-id", "evt-123"),
+            ("ce-type", "orders:fulfillment:shipment:shipped"),
+            ("ce-source", "/orders/service"),
+            ("ce-subject", "order/42"),
+            ("ce-time", "2026-01-02T03:04:05Z"),
+            ("ce-correlationid", "corr-1"),
+            ("content-type", "application/json; charset=utf-8"),
+        ]);
+        let req = parse_create_event_request(&h, br#"{"orderId": 42}"#).unwrap();
+
+        assert_eq!(req.event_type, "orders:fulfillment:shipment:shipped");
+        assert_eq!(req.source, "/orders/service");
+        assert_eq!(req.subject.as_deref(), Some("order/42"));
+        assert_eq!(req.time.as_deref(), Some("2026-01-02T03:04:05Z"));
+        assert_eq!(req.deduplication_id.as_deref(), Some("ce:15:/orders/service:evt-123"));
+        assert_eq!(req.correlation_id.as_deref(), Some("corr-1"));
+        assert_eq!(req.data_content_type.as_deref(), Some("application/json"));
+        assert_eq!(req.data, json!({ "orderId": 42 }));
+    }
+
+    #[test]
+    fn test_binary_mode_text_data_and_missing_attributes() {
+        let h = headers(&[
+            ("ce-specversion", "1.0"),
+            ("ce-id", "evt-1"),
+            ("ce-type", "orders:fulfillment:shipment:shipped"),
+            ("ce-source", "/orders"),
+            ("content-type", "text/plain"),
+        ]);
+        let req = parse_create_event_request(&h, b"shipped").unwrap();
+        assert_eq!(req.data, json!("shipped"));
+
+        let missing_type = headers(&[("ce-specversion", "1.0"), ("ce-id", "evt-1"), ("ce-source", "/orders")]);
+        assert!(parse_create_event_request(&missing_type, b"{}").is_err());
+
+        let wrong_version = headers(&[
+            ("ce-specversion", "0.3"),
+            ("ce-id", "evt-1"),
+            ("ce-type", "a:b:c:d"),
+            ("ce-source", "/orders"),
+        ]);
+        assert!(parse_create_event_request(&wrong_version, b"{}").is_err());
+    }
+
+    #[test]
+    fn test_structured_mode() {
+        let h = headers(&[("content-type", "application/cloudevents+json; charset=utf-8")]);
+        let body = json!({
+            "specversion": "1.0",
+            "id": "evt-456",
+            "type": "orders:fulfillment:shipment:shipped",
+            "source": "/orders/service",
+            "subject": "order/42",
+            "datacontenttype": "application/json",
+            "messagegroup": "order-42",
+            "data": { "orderId": 42 }
+        });
+        let req = parse_create_event_request(&h, body.to_string().as_bytes()).unwrap();
+
+        assert_eq!(req.event_type, "orders:fulfillment:shipment:shipped");
+        assert_eq!(req.source, "/orders/service");
+        assert_eq!(req.subject.as_deref(), Some("order/42"));
+        assert_eq!(req.deduplication_id.as_deref(), Some("ce:15:/orders/service:evt-456"));
+        assert_eq!(req.message_group.as_deref(), Some("order-42"));
+        assert_eq!(req.data, json!({ "orderId": 42 }));
+    }
+
+    #[test]
+    fn test_structured_mode_rejects_invalid_events() {
+        let h = headers(&[("content-type", "application/cloudevents+json")]);
+
+        let missing_id = json!({ "specversion": "1.0", "type": "a:b:c:d", "source": "/s" });
+        assert!(parse_create_event_request(&h, missing_id.to_string().as_bytes()).is_err());
+
+        let base64 = json!({ "specversion": "1.0", "id": "1", "type": "a:b:c:d", "source": "/s", "data_base64": "AA==" });
+        assert!(parse_create_event_request(&h, base64.to_string().as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_platform_json_format() {
+        let h = headers(&[("content-type", "application/json")]);
+        let body = json!({
+            "eventType": "orders:fulfillment:shipment:shipped",
+            "source": "/orders",
+            "data": { "orderId": 42 }
+        });
+        let req = parse_create_event_request(&h, body.to_string().as_bytes()).unwrap();
+        assert_eq!(req.event_type, "orders:fulfillment:shipment:shipped");
+        assert_eq!(req.deduplication_id, None);
+
+        let missing = parse_create_event_request(&HeaderMap::new(), body.to_string().as_bytes());
+        assert!(matches!(missing, Err(PlatformError::UnsupportedMediaType { .. })));
+        let text = parse_create_event_request(&headers(&[("content-type", "text/plain")]), body.to_string().as_bytes());
+        assert!(matches!(text, Err(PlatformError::UnsupportedMediaType { .. })));
+    }
+
+    #[test]
+    fn test_deduplication_id_is_scoped_to_source() {
+        let event = |source: &'static str| headers(&[
+            ("ce-specversion", "1.0"),
+            ("ce-id", "evt-1"),
+            ("ce-type", "a:b:c:d"),
+            ("ce-source", source),
+        ]);
+        let first = parse_create_event_request(&event("/orders"), b"").unwrap();
+        let second = parse_create_event_request(&event("/billing"), b"").unwrap();
+        assert_ne!(first.deduplication_id, second.deduplication_id);
+    }
+}
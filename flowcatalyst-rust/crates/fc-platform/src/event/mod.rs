@@ -5,6 +5,7 @@
 pub mod entity;
 pub mod repository;
 pub mod api;
+pub mod cloudevents;
 
 // Re-export main types
 pub use entity::Event;
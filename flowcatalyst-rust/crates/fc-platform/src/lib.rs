@@ -66,12 +66,12 @@ pub use event_type::entity::{EventType, EventTypeStatus, SpecVersion};
 pub use subscription::entity::{Subscription, SubscriptionStatus, EventTypeBinding};
 pub use dispatch_pool::entity::{DispatchPool, DispatchPoolStatus};
 pub use dispatch_job::entity::{DispatchJob, DispatchJobRead, DispatchStatus, DispatchMode, DispatchKind, DispatchAttempt, RetryStrategy, DispatchMetadata, ErrorType};
-pub use audit::entity::{AuditLog, AuditAction};
+pub use audit::entity::{AuditLog, AuditAction, LoginFailureReason};
 pub use auth::config_entity::ClientAuthConfig;
 
 // Re-export repositories
 pub use client::repository::ClientRepository;
-pub use principal::repository::PrincipalRepository;
+pub use principal::repository::{PrincipalRepository, PrincipalStore};
 pub use role::repository::RoleRepository;
 pub use application::repository::ApplicationRepository;
 pub use application::client_config_repository::ApplicationClientConfigRepository;
@@ -80,13 +80,13 @@ pub use event::repository::EventRepository;
 pub use event_type::repository::EventTypeRepository;
 pub use subscription::repository::SubscriptionRepository;
 pub use dispatch_pool::repository::DispatchPoolRepository;
-pub use dispatch_job::repository::DispatchJobRepository;
-pub use audit::repository::AuditLogRepository;
+pub use dispatch_job::repository::{DispatchJobRepository, DispatchJobCancelFilter};
+pub use audit::repository::{AuditLogRepository, AuditLogFilter};
 
 // Re-export services
 pub use audit::service::AuditService;
-pub use auth::password_service::PasswordService;
-pub use auth::auth_service::{AuthService, AccessTokenClaims};
+pub use auth::password_service::{PasswordService, PasswordVerification, LockoutConfig, DEFAULT_LOCKOUT_PRUNE_INTERVAL};
+pub use auth::auth_service::{AuthService, AccessTokenClaims, PrincipalStateLookup, PrincipalTokenState};
 pub use auth::oidc_service::OidcService;
 pub use auth::oidc_sync_service::OidcSyncService;
 pub use shared::authorization_service::{AuthorizationService, AuthContext, checks};
@@ -94,8 +94,9 @@ pub use shared::authorization_service::{AuthorizationService, AuthContext, check
 // Re-export auth repositories
 pub use auth::config_repository::{ClientAuthConfigRepository, AnchorDomainRepository, IdpRoleMappingRepository, ClientAccessGrantRepository};
 pub use auth::refresh_token_repository::RefreshTokenRepository;
-pub use auth::oauth_client_repository::OAuthClientRepository;
-pub use auth::authorization_code_repository::AuthorizationCodeRepository;
+pub use auth::oauth_client_repository::{OAuthClientRepository, OAuthClientStore};
+pub use auth::authorization_code_repository::{AuthorizationCodeRepository, AuthorizationCodeStore, InMemoryAuthorizationCodeStore};
+pub use auth::pending_auth_repository::{InMemoryPendingAuthStore, PendingAuthRepository, PendingAuthStore};
 pub use auth::oidc_login_state_repository::OidcLoginStateRepository;
 
 // Re-export auth entities
@@ -114,7 +115,7 @@ pub use auth::oidc_login_state::OidcLoginState;
 /// Backward-compatible repository re-exports
 pub mod repository {
     pub use crate::client::repository::ClientRepository;
-    pub use crate::principal::repository::PrincipalRepository;
+    pub use crate::principal::repository::{PrincipalRepository, PrincipalStore};
     pub use crate::role::repository::RoleRepository;
     pub use crate::application::repository::ApplicationRepository;
     pub use crate::application::client_config_repository::ApplicationClientConfigRepository;
@@ -123,32 +124,33 @@ pub mod repository {
     pub use crate::event_type::repository::EventTypeRepository;
     pub use crate::subscription::repository::SubscriptionRepository;
     pub use crate::dispatch_pool::repository::DispatchPoolRepository;
-    pub use crate::dispatch_job::repository::DispatchJobRepository;
-    pub use crate::audit::repository::AuditLogRepository;
+    pub use crate::dispatch_job::repository::{DispatchJobRepository, DispatchJobCancelFilter};
+    pub use crate::audit::repository::{AuditLogRepository, AuditLogFilter};
     pub use crate::auth::config_repository::{ClientAuthConfigRepository, AnchorDomainRepository, IdpRoleMappingRepository, ClientAccessGrantRepository};
     pub use crate::auth::refresh_token_repository::RefreshTokenRepository;
-    pub use crate::auth::oauth_client_repository::OAuthClientRepository;
-    pub use crate::auth::authorization_code_repository::AuthorizationCodeRepository;
+    pub use crate::auth::oauth_client_repository::{OAuthClientRepository, OAuthClientStore};
+    pub use crate::auth::authorization_code_repository::{AuthorizationCodeRepository, AuthorizationCodeStore, InMemoryAuthorizationCodeStore};
+    pub use crate::auth::pending_auth_repository::{InMemoryPendingAuthStore, PendingAuthRepository, PendingAuthStore};
     pub use crate::auth::oidc_login_state_repository::OidcLoginStateRepository;
 }
 
 /// Backward-compatible service re-exports
 pub mod service {
     pub use crate::audit::service::AuditService;
-    pub use crate::auth::password_service::PasswordService;
+    pub use crate::auth::password_service::{PasswordService, PasswordVerification, LockoutConfig, DEFAULT_LOCKOUT_PRUNE_INTERVAL};
     pub use crate::auth::auth_service::{AuthService, AuthConfig, AccessTokenClaims};
     pub use crate::auth::oidc_service::OidcService;
     pub use crate::auth::oidc_sync_service::OidcSyncService;
     pub use crate::shared::authorization_service::{AuthorizationService, AuthContext, checks};
     pub use crate::shared::role_sync_service::RoleSyncService;
     pub use crate::shared::projections_service::{EventProjectionWriter, DispatchJobProjectionWriter};
-    pub use crate::shared::dispatch_service::{DispatchScheduler, DispatchConfig, EventDispatcher};
+    pub use crate::shared::dispatch_service::{DispatchScheduler, DispatchConfig, EventDispatcher, BlockOnErrorChecker, BlockedMessageGroup};
 }
 
 /// Backward-compatible API re-exports
 pub mod api {
     // Middleware
-    pub use crate::shared::middleware::{Authenticated, AppState, AuthLayer, OptionalAuth};
+    pub use crate::shared::middleware::{Authenticated, AppState, AuthLayer, ClientIp, OptionalAuth};
     pub use crate::shared::api_common::{PaginationParams, PaginatedResponse, SuccessResponse, CreatedResponse, ApiError};
 
     // API state and router exports from each aggregate
@@ -164,7 +166,7 @@ pub mod api {
     pub use crate::service_account::api::{service_accounts_router, ServiceAccountsState};
     pub use crate::audit::api::{audit_logs_router, AuditLogsState};
     pub use crate::auth::oauth_clients_api::{oauth_clients_router, OAuthClientsState};
-    pub use crate::auth::oauth_api::{oauth_router, OAuthState};
+    pub use crate::auth::oauth_api::{oauth_router, OAuthState, ClientSecretVerification, ClientAuthConfigResolver, OidcPrincipalResolver};
     pub use crate::auth::{anchor_domains_router, client_auth_configs_router, idp_role_mappings_router, AuthConfigState};
     pub use crate::auth::auth_api::{auth_router, AuthState};
     pub use crate::auth::oidc_login_api::{oidc_login_router, OidcLoginApiState};
@@ -174,8 +176,9 @@ pub mod api {
     pub use crate::shared::monitoring_api::{monitoring_router, MonitoringState, LeaderState, CircuitBreakerRegistry, InFlightTracker};
     pub use crate::shared::debug_api::{debug_events_router, debug_dispatch_jobs_router, DebugState};
     pub use crate::shared::health_api::health_router;
-    pub use crate::shared::well_known_api::well_known_router;
+    pub use crate::shared::well_known_api::{well_known_router, WellKnownState};
     pub use crate::shared::platform_config_api::platform_config_router;
+    pub use crate::shared::tsid_api::tsid_router;
 
     // Re-export middleware module for direct access
     pub mod middleware {
@@ -196,7 +199,7 @@ pub mod domain {
     pub use crate::subscription::entity::{Subscription, SubscriptionStatus, EventTypeBinding, ConfigEntry};
     pub use crate::dispatch_pool::entity::{DispatchPool, DispatchPoolStatus};
     pub use crate::dispatch_job::entity::{DispatchJob, DispatchJobRead, DispatchStatus, DispatchMode, DispatchKind, DispatchAttempt, RetryStrategy, DispatchMetadata, ErrorType};
-    pub use crate::audit::entity::{AuditLog, AuditAction};
+    pub use crate::audit::entity::{AuditLog, AuditAction, LoginFailureReason};
     pub use crate::auth::config_entity::{ClientAuthConfig, AnchorDomain, ClientAccessGrant, IdpRoleMapping, AuthProvider};
     pub use crate::auth::oauth_entity::OAuthClient;
     pub use crate::auth::oidc_login_state::OidcLoginState;
@@ -17,6 +17,7 @@ use crate::principal::repository::PrincipalRepository;
 use crate::shared::error::PlatformError;
 use crate::shared::api_common::{PaginationParams, CreatedResponse, SuccessResponse};
 use crate::shared::middleware::Authenticated;
+use crate::shared::patch::Patch;
 use crate::{AuditService, PasswordService};
 
 /// Create user request (matches Java CreateUserRequest)
@@ -43,16 +44,24 @@ pub struct CreateUserRequest {
 #[serde(rename_all = "camelCase")]
 pub struct UpdatePrincipalRequest {
     /// Display name
-    pub name: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub name: Patch<String>,
 
-    /// First name (for users)
-    pub first_name: Option<String>,
+    /// First name (for users, `null` clears it)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub first_name: Patch<String>,
 
-    /// Last name (for users)
-    pub last_name: Option<String>,
+    /// Last name (for users, `null` clears it)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub last_name: Patch<String>,
 
     /// Active status
-    pub active: Option<bool>,
+    #[serde(default)]
+    #[schema(value_type = Option<bool>)]
+    pub active: Patch<bool>,
 }
 
 /// Assign role request
@@ -490,26 +499,19 @@ pub async fn update_principal(
     }
 
     // Update fields
-    if let Some(name) = req.name {
-        principal.name = name;
-    }
-    if let Some(active) = req.active {
-        if active {
-            principal.activate();
-        } else {
-            principal.deactivate();
-        }
+    req.name.apply_required(&mut principal.name, "name")?;
+    match req.active {
+        Patch::Value(true) => principal.activate(),
+        Patch::Value(false) => principal.deactivate(),
+        Patch::Null => return Err(PlatformError::validation("active cannot be null")),
+        Patch::Missing => {}
     }
 
     // Update user identity if applicable
     if principal.is_user() {
         if let Some(ref mut identity) = principal.user_identity {
-            if let Some(first) = req.first_name {
-                identity.first_name = Some(first);
-            }
-            if let Some(last) = req.last_name {
-                identity.last_name = Some(last);
-            }
+            req.first_name.apply_to(&mut identity.first_name);
+            req.last_name.apply_to(&mut identity.last_name);
         }
     }
 
@@ -600,11 +602,19 @@ pub async fn assign_role(
     let role = req.role.clone();
     let client_id = req.client_id.clone();
 
+    // Re-assigning a held role changes nothing, so existing tokens stay valid
+    let already_assigned = principal.roles.iter()
+        .any(|r| r.role == role && r.client_id == client_id);
+    if already_assigned {
+        return Ok(Json(principal.into()));
+    }
+
     if let Some(cid) = req.client_id {
         principal.assign_role_for_client(req.role, cid);
     } else {
         principal.assign_role(req.role);
     }
+    principal.bump_token_version();
 
     state.principal_repo.update(&principal).await?;
 
@@ -657,7 +667,11 @@ pub async fn batch_assign_roles(
     for role in req.roles {
         principal.assign_role(role);
     }
-    principal.updated_at = chrono::Utc::now();
+    if added.is_empty() && removed.is_empty() {
+        principal.updated_at = chrono::Utc::now();
+    } else {
+        principal.bump_token_version();
+    }
 
     state.principal_repo.update(&principal).await?;
 
@@ -705,8 +719,12 @@ pub async fn remove_role(
     let mut principal = state.principal_repo.find_by_id(&id).await?
         .ok_or_else(|| PlatformError::not_found("Principal", &id))?;
 
+    let role_count = principal.roles.len();
     principal.roles.retain(|r| r.role != role);
-    principal.updated_at = chrono::Utc::now();
+    if principal.roles.len() == role_count {
+        return Ok(Json(principal.into()));
+    }
+    principal.bump_token_version();
 
     state.principal_repo.update(&principal).await?;
 
@@ -1045,6 +1063,55 @@ pub async fn reset_password(
     }))
 }
 
+/// Unlock a principal
+///
+/// Clears a lockout caused by repeated failed password logins.
+#[utoipa::path(
+    post,
+    path = "/{id}/unlock",
+    tag = "principals",
+    operation_id = "postApiAdminPlatformPrincipalsByIdUnlock",
+    params(
+        ("id" = String, Path, description = "Principal ID")
+    ),
+    responses(
+        (status = 200, description = "Lock cleared", body = StatusChangeResponse),
+        (status = 404, description = "Principal not found"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unlock_principal(
+    State(state): State<PrincipalsState>,
+    auth: Authenticated,
+    Path(id): Path<String>,
+) -> Result<Json<StatusChangeResponse>, PlatformError> {
+    crate::checks::require_anchor(&auth.0)?;
+
+    let password_service = state.password_service.as_ref()
+        .ok_or_else(|| PlatformError::internal("Password service not configured"))?;
+
+    state.principal_repo.find_by_id(&id).await?
+        .ok_or_else(|| PlatformError::not_found("Principal", &id))?;
+
+    let was_locked = password_service.clear_lock(&id);
+
+    tracing::info!(principal_id = %id, admin_id = %auth.0.principal_id, was_locked, "Principal unlocked");
+
+    // Audit log
+    if let Some(ref audit) = state.audit_service {
+        let _ = audit.log_update(&auth.0, "Principal", &id, "Account lock cleared by admin".to_string()).await;
+    }
+
+    Ok(Json(StatusChangeResponse {
+        message: if was_locked {
+            "Principal unlocked".to_string()
+        } else {
+            "Principal was not locked".to_string()
+        },
+    }))
+}
+
 /// Check email domain configuration
 #[utoipa::path(
     get,
@@ -1131,6 +1198,7 @@ pub fn principals_router(state: PrincipalsState) -> OpenApiRouter {
         .routes(routes!(activate_principal))
         .routes(routes!(deactivate_principal))
         .routes(routes!(reset_password))
+        .routes(routes!(unlock_principal))
         .routes(routes!(get_roles, assign_role, batch_assign_roles))
         .routes(routes!(remove_role))
         .routes(routes!(get_client_access, grant_client_access))
@@ -194,6 +194,11 @@ pub struct Principal {
     /// External identity for OIDC-authenticated users
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_identity: Option<ExternalIdentity>,
+
+    /// Incremented on role or status changes; tokens issued with an older
+    /// version are rejected so privileges are re-evaluated on next login
+    #[serde(default)]
+    pub token_version: i64,
 }
 
 /// External identity reference for OIDC-authenticated users
@@ -233,6 +238,7 @@ impl Principal {
             updated_at: now,
             created_by: None,
             external_identity: None,
+            token_version: 0,
         }
     }
 
@@ -255,6 +261,7 @@ impl Principal {
             updated_at: now,
             created_by: None,
             external_identity: None,
+            token_version: 0,
         }
     }
 
@@ -328,13 +335,25 @@ impl Principal {
         )
     }
 
+    /// Deactivate, invalidating issued tokens; a no-op if already inactive
     pub fn deactivate(&mut self) {
-        self.active = false;
-        self.updated_at = Utc::now();
+        if self.active {
+            self.active = false;
+            self.bump_token_version();
+        }
     }
 
+    /// Activate, invalidating issued tokens; a no-op if already active
     pub fn activate(&mut self) {
-        self.active = true;
+        if !self.active {
+            self.active = true;
+            self.bump_token_version();
+        }
+    }
+
+    /// Invalidate all previously issued access tokens for this principal
+    pub fn bump_token_version(&mut self) {
+        self.token_version += 1;
         self.updated_at = Utc::now();
     }
 
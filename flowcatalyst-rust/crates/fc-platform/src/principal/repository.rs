@@ -1,10 +1,27 @@
 //! Principal Repository
 
+use async_trait::async_trait;
 use mongodb::{Collection, Database, bson::doc};
 use futures::TryStreamExt;
+use chrono::Utc;
 use crate::principal::entity::{Principal, UserScope};
 use crate::shared::error::Result;
 
+/// Principal reads and writes made by the login and OAuth endpoints
+#[async_trait]
+pub trait PrincipalStore: Send + Sync {
+    async fn find_by_id(&self, id: &str) -> Result<Option<Principal>>;
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Principal>>;
+
+    async fn find_by_service_account(&self, service_account_id: &str) -> Result<Option<Principal>>;
+
+    async fn insert(&self, principal: &Principal) -> Result<()>;
+
+    /// Replace a user's password hash, only if it is still `current_hash`
+    async fn replace_password_hash(&self, id: &str, current_hash: &str, new_hash: &str) -> Result<bool>;
+}
+
 pub struct PrincipalRepository {
     collection: Collection<Principal>,
 }
@@ -32,6 +49,15 @@ impl PrincipalRepository {
         }).await?)
     }
 
+    /// Find the user linked to an external IDP identity (issuer/provider and subject)
+    pub async fn find_by_external_identity(&self, provider_id: &str, external_id: &str) -> Result<Option<Principal>> {
+        Ok(self.collection.find_one(doc! {
+            "type": "USER",
+            "externalIdentity.providerId": provider_id,
+            "externalIdentity.externalId": external_id
+        }).await?)
+    }
+
     pub async fn find_by_service_account(&self, service_account_id: &str) -> Result<Option<Principal>> {
         Ok(self.collection.find_one(doc! {
             "type": "SERVICE",
@@ -112,6 +138,18 @@ impl PrincipalRepository {
         Ok(())
     }
 
+    /// Replace a user's password hash, only if it is still `current_hash`.
+    /// Touches no other field, so concurrent profile or role updates are kept.
+    pub async fn replace_password_hash(&self, id: &str, current_hash: &str, new_hash: &str) -> Result<bool> {
+        let result = self.collection
+            .update_one(
+                doc! { "_id": id, "userIdentity.passwordHash": current_hash },
+                doc! { "$set": { "userIdentity.passwordHash": new_hash, "updatedAt": Utc::now() } },
+            )
+            .await?;
+        Ok(result.modified_count > 0)
+    }
+
     pub async fn delete(&self, id: &str) -> Result<bool> {
         let result = self.collection.delete_one(doc! { "_id": id }).await?;
         Ok(result.deleted_count > 0)
@@ -128,3 +166,26 @@ impl PrincipalRepository {
         Ok(count as i64)
     }
 }
+
+#[async_trait]
+impl PrincipalStore for PrincipalRepository {
+    async fn find_by_id(&self, id: &str) -> Result<Option<Principal>> {
+        PrincipalRepository::find_by_id(self, id).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Principal>> {
+        PrincipalRepository::find_by_email(self, email).await
+    }
+
+    async fn find_by_service_account(&self, service_account_id: &str) -> Result<Option<Principal>> {
+        PrincipalRepository::find_by_service_account(self, service_account_id).await
+    }
+
+    async fn insert(&self, principal: &Principal) -> Result<()> {
+        PrincipalRepository::insert(self, principal).await
+    }
+
+    async fn replace_password_hash(&self, id: &str, current_hash: &str, new_hash: &str) -> Result<bool> {
+        PrincipalRepository::replace_password_hash(self, id, current_hash, new_hash).await
+    }
+}
@@ -304,6 +304,7 @@ pub mod permissions {
         pub const DISPATCH_JOB_VIEW_RAW: &str = "platform:messaging:dispatch-job:view-raw";
         pub const DISPATCH_JOB_CREATE: &str = "platform:messaging:dispatch-job:create";
         pub const DISPATCH_JOB_RETRY: &str = "platform:messaging:dispatch-job:retry";
+        pub const DISPATCH_JOB_CANCEL: &str = "platform:messaging:dispatch-job:cancel";
 
         pub const DISPATCH_POOL_VIEW: &str = "platform:messaging:dispatch-pool:view";
         pub const DISPATCH_POOL_CREATE: &str = "platform:messaging:dispatch-pool:create";
@@ -315,7 +316,7 @@ pub mod permissions {
             EVENT_VIEW, EVENT_VIEW_RAW, EVENT_CREATE,
             EVENT_TYPE_VIEW, EVENT_TYPE_CREATE, EVENT_TYPE_UPDATE, EVENT_TYPE_DELETE,
             SUBSCRIPTION_VIEW, SUBSCRIPTION_CREATE, SUBSCRIPTION_UPDATE, SUBSCRIPTION_DELETE,
-            DISPATCH_JOB_VIEW, DISPATCH_JOB_VIEW_RAW, DISPATCH_JOB_CREATE, DISPATCH_JOB_RETRY,
+            DISPATCH_JOB_VIEW, DISPATCH_JOB_VIEW_RAW, DISPATCH_JOB_CREATE, DISPATCH_JOB_RETRY, DISPATCH_JOB_CANCEL,
             DISPATCH_POOL_VIEW, DISPATCH_POOL_CREATE, DISPATCH_POOL_UPDATE, DISPATCH_POOL_DELETE,
         ];
     }
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use chrono::Utc;
 
 use crate::service_account::RoleAssignment;
-use crate::ServiceAccountRepository;
+use crate::{PrincipalRepository, ServiceAccountRepository};
 use crate::usecase::{
     ExecutionContext, UnitOfWork, UseCaseError, UseCaseResult,
 };
@@ -24,18 +24,24 @@ pub struct AssignRolesCommand {
 }
 
 /// Use case for assigning roles to a service account.
+///
+/// Changing the roles bumps the token version of the account's principal,
+/// so access tokens issued with the old roles stop being accepted.
 pub struct AssignRolesUseCase<U: UnitOfWork> {
     service_account_repo: Arc<ServiceAccountRepository>,
+    principal_repo: Arc<PrincipalRepository>,
     unit_of_work: Arc<U>,
 }
 
 impl<U: UnitOfWork> AssignRolesUseCase<U> {
     pub fn new(
         service_account_repo: Arc<ServiceAccountRepository>,
+        principal_repo: Arc<PrincipalRepository>,
         unit_of_work: Arc<U>,
     ) -> Self {
         Self {
             service_account_repo,
+            principal_repo,
             unit_of_work,
         }
     }
@@ -70,6 +76,15 @@ impl<U: UnitOfWork> AssignRolesUseCase<U> {
         let roles_added: Vec<String> = new_roles.difference(&current_roles).cloned().collect();
         let roles_removed: Vec<String> = current_roles.difference(&new_roles).cloned().collect();
 
+        // Revoke tokens before saving, so a failure never leaves old roles usable
+        if !roles_added.is_empty() || !roles_removed.is_empty() {
+            if let Err(e) = self.revoke_tokens(&service_account.id).await {
+                return UseCaseResult::failure(UseCaseError::commit(
+                    format!("Failed to revoke service account tokens: {}", e),
+                ));
+            }
+        }
+
         // Replace roles
         service_account.roles = command.roles.iter()
             .map(|r| RoleAssignment::new(r))
@@ -87,6 +102,15 @@ impl<U: UnitOfWork> AssignRolesUseCase<U> {
         // Atomic commit
         self.unit_of_work.commit(&service_account, event, &command).await
     }
+
+    /// Bump the token version of the service account's principal, if it has one
+    async fn revoke_tokens(&self, service_account_id: &str) -> crate::shared::error::Result<()> {
+        if let Some(mut principal) = self.principal_repo.find_by_service_account(service_account_id).await? {
+            principal.bump_token_version();
+            self.principal_repo.update(&principal).await?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -338,6 +338,15 @@ pub mod checks {
         }
     }
 
+    /// Check cancel access to dispatch jobs
+    pub fn can_cancel_dispatch_jobs(context: &AuthContext) -> Result<()> {
+        if context.has_permission(permissions::messaging::DISPATCH_JOB_CANCEL) {
+            Ok(())
+        } else {
+            Err(PlatformError::forbidden("Cannot cancel dispatch jobs"))
+        }
+    }
+
     /// Check admin access (any admin permission)
     pub fn is_admin(context: &AuthContext) -> Result<()> {
         if context.is_anchor() || context.has_permission(permissions::ADMIN_ALL) {
@@ -13,6 +13,7 @@ use tracing::{info, warn, error, debug};
 use crate::{DispatchJob, DispatchStatus, ErrorType};
 use crate::DispatchJobRepository;
 use crate::shared::error::Result;
+use crate::shared::monitoring_api::LeaderState;
 
 /// Configuration for the dispatch scheduler
 #[derive(Debug, Clone)]
@@ -43,6 +44,13 @@ pub struct DispatchConfig {
 
     /// Interval for checking stale queued jobs
     pub queued_stale_check_interval: Duration,
+
+    /// Cooldown before a blocked message group is probed with a single retry
+    pub block_resume_cooldown: Duration,
+
+    /// Upper bound for the cooldown after repeated failed probes. Keep this
+    /// below one hour: older failures no longer count as blocking.
+    pub block_resume_max_cooldown: Duration,
 }
 
 impl Default for DispatchConfig {
@@ -57,10 +65,23 @@ impl Default for DispatchConfig {
             enabled: true,
             block_check_interval: Duration::from_secs(60), // 1 minute
             queued_stale_check_interval: Duration::from_secs(120), // 2 minutes
+            block_resume_cooldown: Duration::from_secs(300), // 5 minutes
+            block_resume_max_cooldown: Duration::from_secs(1800), // 30 minutes
         }
     }
 }
 
+impl DispatchConfig {
+    /// Cooldown before the next probe of a blocked group after `failures`
+    /// failed probes: doubles each time, capped at `block_resume_max_cooldown`
+    pub fn block_resume_cooldown_after(&self, failures: u32) -> Duration {
+        self.block_resume_cooldown
+            .checked_mul(2u32.saturating_pow(failures))
+            .unwrap_or(self.block_resume_max_cooldown)
+            .min(self.block_resume_max_cooldown)
+    }
+}
+
 /// Dispatch job processor callback type
 pub type JobProcessor = Arc<dyn Fn(DispatchJob) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>> + Send + Sync>;
 
@@ -165,7 +186,7 @@ impl DispatchScheduler {
                         for job in jobs {
                             if let Some(ref proc) = processor {
                                 if let Err(e) = proc(job.clone()).await {
-                                    error!("Failed to process job {}: {:?}", job.id, e);
+                                    error!(job_id = %job.id, error = ?e, "Failed to process job");
                                 }
                             } else {
                                 // No processor - just mark as queued
@@ -259,13 +280,13 @@ impl DispatchScheduler {
                 None,
             );
             repo.update(&job).await?;
-            warn!("Job {} failed after {} attempts", job.id, job.attempt_count);
+            warn!(job_id = %job.id, attempts = job.attempt_count, "Job failed after max attempts");
         } else {
             // Retry the job - reset to queued status
             job.status = DispatchStatus::Queued;
             job.updated_at = Utc::now();
             repo.update(&job).await?;
-            info!("Requeued stale job {} (attempt {})", job.id, job.attempt_count);
+            info!(job_id = %job.id, attempt = job.attempt_count, "Requeued stale job");
         }
         Ok(())
     }
@@ -311,6 +332,11 @@ impl EventDispatcher {
                 continue;
             }
 
+            // Skip if the event data doesn't satisfy the subscription's filter
+            if !subscription.matches_payload(&data) {
+                continue;
+            }
+
             // Create dispatch job using for_event constructor
             let mut job = DispatchJob::for_event(
                 event_id,
@@ -363,8 +389,10 @@ impl EventDispatcher {
             self.job_repo.insert(&job).await?;
 
             debug!(
-                "Created dispatch job {} for event {} to subscription {}",
-                job.id, event_id, subscription.id
+                job_id = %job.id,
+                event_id = %event_id,
+                subscription_id = %subscription.id,
+                "Created dispatch job"
             );
         }
 
@@ -386,13 +414,55 @@ pub struct BlockedMessageGroup {
     pub error_message: String,
     pub blocked_since: chrono::DateTime<Utc>,
     pub pending_jobs_count: u32,
+    /// Failed auto-resume probes since the group was blocked
+    pub probe_failures: u32,
+    /// Job currently being retried as a probe, if any
+    pub probe_job_id: Option<String>,
+    /// When the next probe is due (`None` while a probe is in flight)
+    pub next_probe_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl BlockedMessageGroup {
+    /// Seconds until the next probe, zero if overdue, `None` while probing
+    pub fn cooldown_remaining_secs(&self, now: chrono::DateTime<Utc>) -> Option<i64> {
+        self.next_probe_at.map(|at| (at - now).num_seconds().max(0))
+    }
+}
+
+/// Auto-resume state for a blocked message group
+#[derive(Debug, Clone)]
+struct GroupCooldown {
+    failures: u32,
+    next_probe_at: chrono::DateTime<Utc>,
+    probe_job_id: Option<String>,
+    /// The probe job's own `max_retries`, if it was lowered for the probe
+    probe_max_retries: Option<u32>,
 }
 
-/// Block on error checker - monitors message groups that are blocked due to errors
+/// Limit a job to one more attempt, returning its previous `max_retries` if
+/// that had to be lowered. A job that exhausted its retries fails again after
+/// one attempt anyway, so usually nothing changes.
+fn limit_to_one_attempt(job: &mut DispatchJob) -> Option<u32> {
+    let limit = job.attempt_count + 1;
+    (job.max_retries > limit).then(|| std::mem::replace(&mut job.max_retries, limit))
+}
+
+/// Block on error checker - monitors message groups that are blocked due to errors.
+///
+/// After `block_resume_cooldown` the blocking job of a group is retried once
+/// as a probe. If the probe succeeds the group's other failed jobs are
+/// retried and the block lifts; if it fails the cooldown doubles, up to
+/// `block_resume_max_cooldown`.
+///
+/// With a leader state set, only the leader probes; an instance that loses
+/// leadership forgets its probes so the new leader starts from the database.
+#[derive(Clone)]
 pub struct BlockOnErrorChecker {
     job_repo: Arc<DispatchJobRepository>,
     config: DispatchConfig,
     running: Arc<Mutex<bool>>,
+    leader: Option<LeaderState>,
+    cooldowns: Arc<Mutex<std::collections::HashMap<String, GroupCooldown>>>,
 }
 
 impl BlockOnErrorChecker {
@@ -401,18 +471,31 @@ impl BlockOnErrorChecker {
             job_repo,
             config,
             running: Arc::new(Mutex::new(false)),
+            leader: None,
+            cooldowns: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        }
+    }
+
+    /// Only probe while this instance is the leader
+    pub fn with_leader_state(mut self, leader: LeaderState) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+
+    async fn is_leader(&self) -> bool {
+        match &self.leader {
+            Some(leader) => *leader.is_leader.read().await,
+            None => true,
         }
     }
 
     /// Start the checker loop
     pub async fn start(&self) -> JoinHandle<()> {
-        let running = self.running.clone();
-        let job_repo = self.job_repo.clone();
+        let checker = self.clone();
         let interval = self.config.block_check_interval;
-        let batch_size = self.config.poll_batch_size;
 
         {
-            let mut r = running.lock().await;
+            let mut r = self.running.lock().await;
             *r = true;
         }
 
@@ -420,55 +503,19 @@ impl BlockOnErrorChecker {
             info!("Block on error checker started");
             loop {
                 {
-                    let is_running = running.lock().await;
+                    let is_running = checker.running.lock().await;
                     if !*is_running {
                         break;
                     }
                 }
 
-                // Find jobs that have failed - some may be blocking message groups
-                match job_repo.find_by_status(DispatchStatus::Failed, batch_size).await {
-                    Ok(failed_jobs) if !failed_jobs.is_empty() => {
-                        // Filter for jobs that have message groups and recent failures
-                        let recent_cutoff = Utc::now() - chrono::Duration::hours(1);
-                        let blocking_jobs: Vec<_> = failed_jobs
-                            .iter()
-                            .filter(|j| j.message_group.is_some() && j.updated_at > recent_cutoff)
-                            .collect();
-
-                        if !blocking_jobs.is_empty() {
-                            warn!("Found {} potentially blocking failed jobs", blocking_jobs.len());
-
-                            // Group by message_group
-                            let mut groups: std::collections::HashMap<String, Vec<&&DispatchJob>> =
-                                std::collections::HashMap::new();
-
-                            for job in &blocking_jobs {
-                                if let Some(ref group_id) = job.message_group {
-                                    groups.entry(group_id.clone()).or_default().push(job);
-                                }
-                            }
-
-                            for (group_id, jobs) in groups {
-                                let oldest = jobs.iter().min_by_key(|j| j.updated_at);
-                                if let Some(oldest_job) = oldest {
-                                    info!(
-                                        "Message group {} may be blocked by job {} since {}: {}",
-                                        group_id,
-                                        oldest_job.id,
-                                        oldest_job.updated_at,
-                                        oldest_job.last_error.as_deref().unwrap_or("unknown error")
-                                    );
-                                }
-                            }
-                        }
-                    }
-                    Ok(_) => {
-                        debug!("No failed jobs found");
-                    }
-                    Err(e) => {
-                        error!("Error checking failed jobs: {:?}", e);
-                    }
+                let result = if checker.is_leader().await {
+                    checker.check_once().await
+                } else {
+                    checker.release_probes().await
+                };
+                if let Err(e) = result {
+                    error!("Error checking blocked message groups: {:?}", e);
                 }
 
                 tokio::time::sleep(interval).await;
@@ -483,8 +530,149 @@ impl BlockOnErrorChecker {
         *running = false;
     }
 
-    /// Get currently blocked message groups (failed jobs with message groups)
-    pub async fn get_blocked_groups(&self) -> Result<Vec<BlockedMessageGroup>> {
+    /// Run one pass: settle in-flight probes, then probe groups whose cooldown elapsed
+    pub async fn check_once(&self) -> Result<()> {
+        // Work on a copy so the lock isn't held across database calls. It is
+        // written back on error too, so probes already started stay tracked.
+        let mut cooldowns = self.cooldowns.lock().await.clone();
+        let result = self.check_with(&mut cooldowns, Utc::now()).await;
+        *self.cooldowns.lock().await = cooldowns;
+        result
+    }
+
+    /// Forget all probes after losing leadership, restoring any lowered
+    /// `max_retries` since the new leader doesn't know about them
+    async fn release_probes(&self) -> Result<()> {
+        let cooldowns = std::mem::take(&mut *self.cooldowns.lock().await);
+        for (group_id, cooldown) in cooldowns {
+            if let (Some(probe_id), Some(max_retries)) = (cooldown.probe_job_id, cooldown.probe_max_retries) {
+                self.restore_max_retries(&probe_id, max_retries).await?;
+                debug!(job_id = %probe_id, message_group = %group_id, "Released probe after losing leadership");
+            }
+        }
+        Ok(())
+    }
+
+    /// Put back a probe job's own `max_retries` once the probe has settled
+    async fn restore_max_retries(&self, job_id: &str, max_retries: u32) -> Result<()> {
+        if let Some(mut job) = self.job_repo.find_by_id(job_id).await? {
+            job.max_retries = max_retries;
+            self.job_repo.update(&job).await?;
+        }
+        Ok(())
+    }
+
+    async fn check_with(
+        &self,
+        cooldowns: &mut std::collections::HashMap<String, GroupCooldown>,
+        now: chrono::DateTime<Utc>,
+    ) -> Result<()> {
+        let blocked = self.find_blocked_groups().await?;
+
+        // Settle probes first - a probed job is no longer FAILED, so its
+        // group may not appear in the blocked list while the probe runs
+        let probing: Vec<(String, String, Option<u32>)> = cooldowns
+            .iter()
+            .filter_map(|(group, c)| c.probe_job_id.clone().map(|id| (group.clone(), id, c.probe_max_retries)))
+            .collect();
+
+        for (group_id, probe_id, probe_max_retries) in probing {
+            let status = self.job_repo.find_by_id(&probe_id).await?.map(|j| j.status);
+            let settled = !matches!(
+                status,
+                Some(DispatchStatus::Pending | DispatchStatus::Queued | DispatchStatus::InProgress)
+            );
+            if let (true, Some(max_retries)) = (settled, probe_max_retries) {
+                self.restore_max_retries(&probe_id, max_retries).await?;
+            }
+
+            match status {
+                Some(DispatchStatus::Completed) => {
+                    cooldowns.remove(&group_id);
+                    let remaining = blocked
+                        .get(&group_id)
+                        .map(|(_, jobs)| jobs.clone())
+                        .unwrap_or_default();
+                    for job_id in &remaining {
+                        self.retry_failed_job(job_id).await?;
+                    }
+                    info!(
+                        job_id = %probe_id,
+                        message_group = %group_id,
+                        retried_jobs = remaining.len(),
+                        "Probe job succeeded, unblocked message group"
+                    );
+                }
+                Some(DispatchStatus::Failed) => {
+                    if let Some(cooldown) = cooldowns.get_mut(&group_id) {
+                        cooldown.failures += 1;
+                        cooldown.probe_job_id = None;
+                        cooldown.probe_max_retries = None;
+                        let delay = self.config.block_resume_cooldown_after(cooldown.failures);
+                        cooldown.next_probe_at = now + chrono::Duration::from_std(delay)
+                            .unwrap_or_else(|_| chrono::Duration::seconds(300));
+                        warn!(
+                            job_id = %probe_id,
+                            message_group = %group_id,
+                            cooldown = ?delay,
+                            "Probe job failed, message group stays blocked"
+                        );
+                    }
+                }
+                Some(DispatchStatus::Pending | DispatchStatus::Queued | DispatchStatus::InProgress) => {
+                    debug!(job_id = %probe_id, message_group = %group_id, "Probe job still in flight");
+                }
+                // Cancelled, expired or deleted - the block was resolved elsewhere
+                _ => {
+                    cooldowns.remove(&group_id);
+                }
+            }
+        }
+
+        // Forget groups that were unblocked manually
+        cooldowns.retain(|group, c| c.probe_job_id.is_some() || blocked.contains_key(group));
+
+        for (group_id, (group, _)) in &blocked {
+            let cooldown = cooldowns.entry(group_id.clone()).or_insert_with(|| GroupCooldown {
+                failures: 0,
+                next_probe_at: group.blocked_since + chrono::Duration::from_std(self.config.block_resume_cooldown)
+                    .unwrap_or_else(|_| chrono::Duration::seconds(300)),
+                probe_job_id: None,
+                probe_max_retries: None,
+            });
+
+            if cooldown.probe_job_id.is_some() || now < cooldown.next_probe_at {
+                continue;
+            }
+
+            if let Some(mut job) = self.job_repo.find_by_id(&group.blocked_job_id).await? {
+                if job.status != DispatchStatus::Failed {
+                    continue;
+                }
+                // Allow exactly one more attempt
+                let previous_max_retries = limit_to_one_attempt(&mut job);
+                job.status = DispatchStatus::Pending;
+                job.next_retry_at = None;
+                job.updated_at = now;
+                self.job_repo.update(&job).await?;
+                cooldown.probe_job_id = Some(job.id.clone());
+                cooldown.probe_max_retries = previous_max_retries;
+                info!(
+                    message_group = %group_id,
+                    job_id = %job.id,
+                    previous_failures = cooldown.failures,
+                    "Probing blocked message group"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Blocked groups keyed by ID, with the IDs of all their failed jobs
+    async fn find_blocked_groups(
+        &self,
+    ) -> Result<std::collections::HashMap<String, (BlockedMessageGroup, Vec<String>)>> {
         let failed_jobs = self.job_repo
             .find_by_status(DispatchStatus::Failed, self.config.poll_batch_size)
             .await?;
@@ -505,20 +693,71 @@ impl BlockOnErrorChecker {
             }
         }
 
-        let result: Vec<BlockedMessageGroup> = groups
+        Ok(groups
             .into_iter()
             .filter_map(|(group_id, jobs)| {
                 let oldest = jobs.iter().min_by_key(|j| j.updated_at)?;
-                Some(BlockedMessageGroup {
-                    message_group_id: group_id,
+                let group = BlockedMessageGroup {
+                    message_group_id: group_id.clone(),
                     blocked_job_id: oldest.id.clone(),
                     error_message: oldest.last_error.clone().unwrap_or_default(),
                     blocked_since: oldest.updated_at,
                     pending_jobs_count: jobs.len() as u32,
-                })
+                    probe_failures: 0,
+                    probe_job_id: None,
+                    next_probe_at: None,
+                };
+                let job_ids = jobs.iter().map(|j| j.id.clone()).collect();
+                Some((group_id, (group, job_ids)))
+            })
+            .collect())
+    }
+
+    /// Get currently blocked message groups (failed jobs with message groups),
+    /// including groups whose probe is in flight
+    pub async fn get_blocked_groups(&self) -> Result<Vec<BlockedMessageGroup>> {
+        let blocked = self.find_blocked_groups().await?;
+        let cooldowns = self.cooldowns.lock().await.clone();
+        let default_next_probe = |since: chrono::DateTime<Utc>| {
+            since + chrono::Duration::from_std(self.config.block_resume_cooldown)
+                .unwrap_or_else(|_| chrono::Duration::seconds(300))
+        };
+
+        let mut result: Vec<BlockedMessageGroup> = blocked
+            .into_values()
+            .map(|(mut group, _)| {
+                match cooldowns.get(&group.message_group_id) {
+                    Some(c) => {
+                        group.probe_failures = c.failures;
+                        group.probe_job_id = c.probe_job_id.clone();
+                        group.next_probe_at = c.probe_job_id.is_none().then_some(c.next_probe_at);
+                    }
+                    None => group.next_probe_at = Some(default_next_probe(group.blocked_since)),
+                }
+                group
             })
             .collect();
 
+        // Groups whose only failed job is currently being probed
+        for (group_id, c) in cooldowns.iter() {
+            let Some(ref probe_id) = c.probe_job_id else { continue };
+            if result.iter().any(|g| &g.message_group_id == group_id) {
+                continue;
+            }
+            if let Some(job) = self.job_repo.find_by_id(probe_id).await? {
+                result.push(BlockedMessageGroup {
+                    message_group_id: group_id.clone(),
+                    blocked_job_id: job.id.clone(),
+                    error_message: job.last_error.clone().unwrap_or_default(),
+                    blocked_since: job.updated_at,
+                    pending_jobs_count: 1,
+                    probe_failures: c.failures,
+                    probe_job_id: Some(job.id),
+                    next_probe_at: None,
+                });
+            }
+        }
+
         Ok(result)
     }
 
@@ -612,17 +851,17 @@ impl StaleQueuedJobPoller {
                                         ErrorType::Unknown,
                                         None,
                                     );
-                                    warn!("Failed stale queued job {}", job.id);
+                                    warn!(job_id = %job.id, "Failed stale queued job");
                                 } else {
                                     // Reset to pending for re-processing
                                     job.status = DispatchStatus::Pending;
                                     job.attempt_count += 1;
                                     job.updated_at = Utc::now();
-                                    info!("Requeued stale job {} (attempt {})", job.id, job.attempt_count);
+                                    info!(job_id = %job.id, attempt = job.attempt_count, "Requeued stale job");
                                 }
 
                                 if let Err(e) = job_repo.update(&job).await {
-                                    error!("Failed to update stale job {}: {:?}", job.id, e);
+                                    error!(job_id = %job.id, error = ?e, "Failed to update stale job");
                                 }
                             }
                         }
@@ -673,4 +912,52 @@ mod tests {
         assert_eq!(config.max_retries, 3);
         assert_eq!(config.poll_batch_size, 100);
     }
+
+    #[test]
+    fn test_block_resume_cooldown_backoff() {
+        let config = DispatchConfig::default();
+        assert_eq!(config.block_resume_cooldown_after(0), Duration::from_secs(300));
+        assert_eq!(config.block_resume_cooldown_after(1), Duration::from_secs(600));
+        assert_eq!(config.block_resume_cooldown_after(2), Duration::from_secs(1200));
+        assert_eq!(config.block_resume_cooldown_after(3), Duration::from_secs(1800));
+        assert_eq!(config.block_resume_cooldown_after(40), Duration::from_secs(1800));
+    }
+
+    #[test]
+    fn test_probe_only_lowers_max_retries_when_needed() {
+        let mut job = DispatchJob::for_task("task", "test", "http://localhost/hook", "{}");
+
+        // Retries exhausted: one more failure fails the job again unchanged
+        job.max_retries = 3;
+        job.attempt_count = 3;
+        assert_eq!(limit_to_one_attempt(&mut job), None);
+        assert_eq!(job.max_retries, 3);
+
+        // Failed early (e.g. went stale): lowered for the probe, old value returned
+        job.max_retries = 5;
+        job.attempt_count = 2;
+        assert_eq!(limit_to_one_attempt(&mut job), Some(5));
+        assert_eq!(job.max_retries, 3);
+    }
+
+    #[test]
+    fn test_blocked_group_cooldown_remaining() {
+        let now = Utc::now();
+        let mut group = BlockedMessageGroup {
+            message_group_id: "group-1".to_string(),
+            blocked_job_id: "job-1".to_string(),
+            error_message: "HTTP 503".to_string(),
+            blocked_since: now,
+            pending_jobs_count: 1,
+            probe_failures: 0,
+            probe_job_id: None,
+            next_probe_at: Some(now + chrono::Duration::seconds(90)),
+        };
+        assert_eq!(group.cooldown_remaining_secs(now), Some(90));
+        assert_eq!(group.cooldown_remaining_secs(now + chrono::Duration::seconds(120)), Some(0));
+
+        group.probe_job_id = Some("job-1".to_string());
+        group.next_probe_at = None;
+        assert_eq!(group.cooldown_remaining_secs(now), None);
+    }
 }
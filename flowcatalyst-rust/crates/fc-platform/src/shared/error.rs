@@ -20,6 +20,9 @@ pub enum PlatformError {
     #[error("Validation error: {message}")]
     Validation { message: String },
 
+    #[error("Unsupported media type: {message}")]
+    UnsupportedMediaType { message: String },
+
     #[error("Authorization error: {message}")]
     Unauthorized { message: String },
 
@@ -138,6 +141,7 @@ impl IntoResponse for PlatformError {
             PlatformError::NotFound { .. } => (StatusCode::NOT_FOUND, "NOT_FOUND"),
             PlatformError::Duplicate { .. } => (StatusCode::CONFLICT, "DUPLICATE"),
             PlatformError::Validation { .. } => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
+            PlatformError::UnsupportedMediaType { .. } => (StatusCode::UNSUPPORTED_MEDIA_TYPE, "UNSUPPORTED_MEDIA_TYPE"),
             PlatformError::Unauthorized { .. } => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED"),
             PlatformError::Forbidden { .. } => (StatusCode::FORBIDDEN, "FORBIDDEN"),
             PlatformError::InvalidCredentials => (StatusCode::UNAUTHORIZED, "INVALID_CREDENTIALS"),
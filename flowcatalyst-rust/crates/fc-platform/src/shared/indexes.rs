@@ -301,6 +301,19 @@ async fn create_oauth_indexes(db: &Database) -> Result<(), mongodb::error::Error
             .build(),
     ).await?;
 
+    // Pending authorizations (_id is the state); TTL index drops abandoned logins
+    let pending_auths = db.collection::<mongodb::bson::Document>("oauth_pending_authorizations");
+
+    pending_auths.create_index(
+        IndexModel::builder()
+            .keys(doc! { "expires_at": 1 })
+            .options(IndexOptions::builder()
+                .expire_after(std::time::Duration::from_secs(0))
+                .background(true)
+                .build())
+            .build(),
+    ).await?;
+
     // Refresh tokens
     let refresh_tokens = db.collection::<mongodb::bson::Document>("refresh_tokens");
 
@@ -335,7 +348,7 @@ async fn create_oauth_indexes(db: &Database) -> Result<(), mongodb::error::Error
             .build(),
     ).await?;
 
-    info!("Created indexes on oauth_clients, authorization_codes, refresh_tokens");
+    info!("Created indexes on oauth_clients, authorization_codes, oauth_pending_authorizations, refresh_tokens");
     Ok(())
 }
 
@@ -473,26 +486,37 @@ async fn create_subscription_indexes(db: &Database) -> Result<(), mongodb::error
 async fn create_audit_log_indexes(db: &Database) -> Result<(), mongodb::error::Error> {
     let audit_logs = db.collection::<mongodb::bson::Document>("audit_logs");
 
+    // Audit logs are stored with camelCase fields; every filtered listing sorts
+    // by performedAt descending, so each index ends with it.
+
     // Entity lookup
     audit_logs.create_index(
         IndexModel::builder()
-            .keys(doc! { "entity_type": 1, "entity_id": 1 })
+            .keys(doc! { "entityType": 1, "entityId": 1, "performedAt": -1 })
             .options(IndexOptions::builder().background(true).build())
             .build(),
     ).await?;
 
-    // Principal lookup
+    // Principal (actor) lookup
     audit_logs.create_index(
         IndexModel::builder()
-            .keys(doc! { "principal_id": 1 })
+            .keys(doc! { "principalId": 1, "performedAt": -1 })
+            .options(IndexOptions::builder().background(true).build())
+            .build(),
+    ).await?;
+
+    // Operation lookup
+    audit_logs.create_index(
+        IndexModel::builder()
+            .keys(doc! { "operation": 1, "performedAt": -1 })
             .options(IndexOptions::builder().background(true).build())
             .build(),
     ).await?;
 
-    // Time-ordered listing
+    // Time-ordered listing and time-range queries
     audit_logs.create_index(
         IndexModel::builder()
-            .keys(doc! { "created_at": -1 })
+            .keys(doc! { "performedAt": -1 })
             .options(IndexOptions::builder().background(true).build())
             .build(),
     ).await?;
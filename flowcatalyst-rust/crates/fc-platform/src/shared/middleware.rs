@@ -10,8 +10,9 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use std::net::IpAddr;
 use std::sync::Arc;
-use crate::{AuthService, AuthorizationService, AuthContext};
+use crate::{AuthService, AuthorizationService, AuthContext, PlatformError};
 use crate::shared::api_common::ApiError;
 
 /// Default session cookie name
@@ -42,10 +43,26 @@ pub struct AuthError {
     pub message: String,
 }
 
+impl AuthError {
+    /// Token problems are 401s; anything else (e.g. the principal store being
+    /// unreachable) is a 503 so clients retry instead of discarding the token
+    fn from_validation(error: PlatformError) -> Self {
+        let status = match &error {
+            PlatformError::InvalidToken { .. }
+            | PlatformError::TokenExpired
+            | PlatformError::Unauthorized { .. }
+            | PlatformError::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        Self { status, message: error.to_string() }
+    }
+}
+
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
+        let error = if self.status.is_server_error() { "SERVICE_UNAVAILABLE" } else { "UNAUTHORIZED" };
         let body = ApiError {
-            error: "UNAUTHORIZED".to_string(),
+            error: error.to_string(),
             message: self.message,
             details: None,
         };
@@ -95,18 +112,12 @@ where
             })?;
 
         // Validate token
-        let claims = app_state.auth_service.validate_token(&token)
-            .map_err(|e: crate::PlatformError| AuthError {
-                status: StatusCode::UNAUTHORIZED,
-                message: e.to_string(),
-            })?;
+        let claims = app_state.auth_service.validate(&token).await
+            .map_err(AuthError::from_validation)?;
 
         // Build auth context with resolved permissions
         let context = app_state.authz_service.build_context(&claims).await
-            .map_err(|e: crate::PlatformError| AuthError {
-                status: StatusCode::UNAUTHORIZED,
-                message: e.to_string(),
-            })?;
+            .map_err(AuthError::from_validation)?;
 
         Ok(Authenticated(context))
     }
@@ -150,7 +161,7 @@ where
         };
 
         // Try to validate token
-        let Ok(claims) = app_state.auth_service.validate_token(&token) else {
+        let Ok(claims) = app_state.auth_service.validate(&token).await else {
             return Ok(OptionalAuth(None));
         };
 
@@ -163,6 +174,105 @@ where
     }
 }
 
+/// Proxies whose forwarding headers [`ClientIp`] believes
+///
+/// Add to the router as an `Extension`. Entries are IPs or CIDR ranges.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<(IpAddr, u8)>);
+
+impl TrustedProxies {
+    /// Parse a comma-separated list such as `10.0.0.0/8, 127.0.0.1`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut ranges = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (addr, prefix) = entry.split_once('/').unwrap_or((entry, ""));
+            let addr: IpAddr = addr.parse()
+                .map_err(|_| format!("Invalid trusted proxy address '{}'", entry))?;
+            let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+            let prefix = if prefix.is_empty() {
+                max_prefix
+            } else {
+                prefix.parse::<u8>().ok()
+                    .filter(|p| *p <= max_prefix)
+                    .ok_or_else(|| format!("Invalid trusted proxy prefix length in '{}'", entry))?
+            };
+            ranges.push((addr, prefix));
+        }
+        Ok(Self(ranges))
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.0.iter().any(|(net, prefix)| match (net, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - *prefix as u32).unwrap_or(0);
+                u32::from(*net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - *prefix as u32).unwrap_or(0);
+                u128::from(*net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        })
+    }
+}
+
+/// Client IP address extractor
+///
+/// Uses the socket peer address, which needs the server started with connect
+/// info. Only when the peer is one of the [`TrustedProxies`] are forwarding
+/// headers used: the right-most `X-Forwarded-For` hop that isn't itself a
+/// trusted proxy, then `X-Real-IP`. Never rejects.
+pub struct ClientIp(pub Option<String>);
+
+impl ClientIp {
+    fn from_parts(parts: &Parts) -> Option<String> {
+        let peer = parts.extensions
+            .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()?
+            .0.ip().to_canonical();
+
+        let Some(trusted) = parts.extensions.get::<TrustedProxies>().filter(|t| t.contains(peer)) else {
+            return Some(peer.to_string());
+        };
+
+        // Hops are appended by each proxy, so anything left of the first
+        // untrusted hop (reading from the right) is client-controlled
+        let forwarded: Vec<&str> = parts.headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .collect();
+        for hop in forwarded.iter().rev() {
+            match hop.parse::<IpAddr>() {
+                Ok(ip) if trusted.contains(ip) => continue,
+                Ok(ip) => return Some(ip.to_canonical().to_string()),
+                Err(_) => break,
+            }
+        }
+
+        parts.headers
+            .get("x-real-ip")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<IpAddr>().ok())
+            .map(|ip| ip.to_canonical().to_string())
+            .or_else(|| Some(peer.to_string()))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(ClientIp(Self::from_parts(parts)))
+    }
+}
+
 /// Middleware layer that injects AppState into request extensions
 /// This enables the Authenticated extractor to work
 use tower::Layer;
@@ -221,3 +331,163 @@ where
         Box::pin(async move { future.await })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use crate::auth::auth_service::AuthConfig;
+    use crate::{Principal, PrincipalStateLookup, PrincipalTokenState, RoleRepository, UserScope};
+
+    fn parts(headers: &[(&str, &str)]) -> Parts {
+        let mut builder = Request::builder();
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    fn from_peer(mut parts: Parts, peer: &str, trusted: Option<&str>) -> Option<String> {
+        let peer: std::net::SocketAddr = peer.parse().unwrap();
+        parts.extensions.insert(axum::extract::ConnectInfo(peer));
+        if let Some(trusted) = trusted {
+            parts.extensions.insert(TrustedProxies::parse(trusted).unwrap());
+        }
+        ClientIp::from_parts(&parts)
+    }
+
+    #[test]
+    fn test_client_ip_ignores_headers_from_untrusted_peer() {
+        let headers = [("x-forwarded-for", "203.0.113.7"), ("x-real-ip", "203.0.113.8")];
+        assert_eq!(from_peer(parts(&headers), "198.51.100.4:51234", None).as_deref(), Some("198.51.100.4"));
+        assert_eq!(
+            from_peer(parts(&headers), "198.51.100.4:51234", Some("10.0.0.0/8")).as_deref(),
+            Some("198.51.100.4")
+        );
+    }
+
+    #[test]
+    fn test_client_ip_uses_rightmost_untrusted_hop_from_trusted_peer() {
+        let trusted = Some("10.0.0.0/8, 192.0.2.1");
+        let headers = [("x-forwarded-for", "1.2.3.4, 203.0.113.7, 192.0.2.1")];
+        assert_eq!(from_peer(parts(&headers), "10.0.0.2:443", trusted).as_deref(), Some("203.0.113.7"));
+
+        let headers = [("x-real-ip", "203.0.113.8")];
+        assert_eq!(from_peer(parts(&headers), "[::ffff:10.1.2.3]:443", trusted).as_deref(), Some("203.0.113.8"));
+
+        assert_eq!(from_peer(parts(&[]), "10.0.0.2:443", trusted).as_deref(), Some("10.0.0.2"));
+    }
+
+    #[test]
+    fn test_client_ip_requires_connect_info() {
+        let mut parts = parts(&[("x-forwarded-for", "203.0.113.7")]);
+        parts.extensions.insert(TrustedProxies::parse("0.0.0.0/0").unwrap());
+        assert_eq!(ClientIp::from_parts(&parts), None);
+    }
+
+    #[test]
+    fn test_trusted_proxies_parse() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8, fd00::/8, 127.0.0.1").unwrap();
+        assert!(proxies.contains("10.255.0.1".parse().unwrap()));
+        assert!(proxies.contains("fd12::1".parse().unwrap()));
+        assert!(proxies.contains("127.0.0.1".parse().unwrap()));
+        assert!(!proxies.contains("127.0.0.2".parse().unwrap()));
+        assert!(!proxies.contains("11.0.0.1".parse().unwrap()));
+
+        assert!(TrustedProxies::parse("").unwrap().0.is_empty());
+        assert!(TrustedProxies::parse("10.0.0.0/33").is_err());
+        assert!(TrustedProxies::parse("proxy.internal").is_err());
+    }
+
+    /// Principal state served from a fixed slot, or an error when `fail` is set
+    struct StaticLookup {
+        state: parking_lot::Mutex<Option<PrincipalTokenState>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl PrincipalStateLookup for StaticLookup {
+        async fn token_state(&self, _principal_id: &str) -> crate::Result<Option<PrincipalTokenState>> {
+            if self.fail {
+                return Err(PlatformError::internal("principal store unavailable"));
+            }
+            Ok(*self.state.lock())
+        }
+    }
+
+    /// Authenticate `principal`'s current token against `lookup`, with caching disabled
+    async fn authenticate(lookup: Arc<StaticLookup>, token: &str) -> Result<Authenticated, AuthError> {
+        use mongodb::{options::ClientOptions, Client};
+
+        // Role-less principals never query roles, so the client is never connected
+        let db = Client::with_options(ClientOptions::parse("mongodb://localhost:27017").await.unwrap())
+            .unwrap()
+            .database("unused");
+        let auth_service = AuthService::new(AuthConfig::default())
+            .with_principal_lookup(lookup)
+            .with_principal_state_ttl(std::time::Duration::ZERO);
+        let state = AppState {
+            auth_service: Arc::new(auth_service),
+            authz_service: Arc::new(AuthorizationService::new(Arc::new(RoleRepository::new(&db)))),
+        };
+
+        let mut parts = parts(&[("authorization", &format!("Bearer {}", token))]);
+        parts.extensions.insert(state);
+        Authenticated::from_request_parts(&mut parts, &()).await
+    }
+
+    fn issue(principal: &Principal) -> (String, Arc<StaticLookup>) {
+        let token = AuthService::new(AuthConfig::default()).generate_access_token(principal).unwrap();
+        let lookup = Arc::new(StaticLookup {
+            state: parking_lot::Mutex::new(Some(PrincipalTokenState::from(principal))),
+            fail: false,
+        });
+        (token, lookup)
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_accepts_current_token() {
+        let principal = Principal::new_user("user@example.com", UserScope::Anchor);
+        let (token, lookup) = issue(&principal);
+
+        let auth = authenticate(lookup, &token).await.ok().unwrap();
+        assert_eq!(auth.principal_id, principal.id);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_rejects_revoked_tokens() {
+        let mut principal = Principal::new_user("user@example.com", UserScope::Anchor);
+        let (token, lookup) = issue(&principal);
+
+        principal.bump_token_version();
+        *lookup.state.lock() = Some(PrincipalTokenState::from(&principal));
+        let err = authenticate(lookup.clone(), &token).await.err().unwrap();
+        assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+
+        principal.deactivate();
+        let (token, lookup) = issue(&principal);
+        let err = authenticate(lookup, &token).await.err().unwrap();
+        assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_rejects_missing_principal() {
+        let principal = Principal::new_service("svc-client", "Service");
+        let (token, lookup) = issue(&principal);
+
+        *lookup.state.lock() = None;
+        let err = authenticate(lookup, &token).await.err().unwrap();
+        assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_lookup_failure_is_unavailable() {
+        let principal = Principal::new_user("user@example.com", UserScope::Anchor);
+        let token = AuthService::new(AuthConfig::default()).generate_access_token(&principal).unwrap();
+        let lookup = Arc::new(StaticLookup { state: parking_lot::Mutex::new(None), fail: true });
+
+        let err = authenticate(lookup, &token).await.err().unwrap();
+        assert_eq!(err.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(err.into_response().status().is_server_error());
+    }
+}
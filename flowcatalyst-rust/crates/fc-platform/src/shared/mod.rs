@@ -4,6 +4,7 @@
 
 pub mod error;
 pub mod tsid;
+pub mod patch;
 pub mod middleware;
 pub mod api_common;
 pub mod indexes;
@@ -17,6 +18,7 @@ pub mod monitoring_api;
 pub mod filter_options_api;
 pub mod client_selection_api;
 pub mod application_roles_sdk_api;
+pub mod tsid_api;
 
 // Services
 pub mod authorization_service;
@@ -27,6 +29,7 @@ pub mod role_sync_service;
 // Re-export commonly used items
 pub use error::{PlatformError, Result};
 pub use tsid::TsidGenerator;
+pub use patch::Patch;
 pub use middleware::{Authenticated, AppState};
 pub use api_common::{PaginationParams, PaginatedResponse};
 pub use health_api::health_router;
@@ -36,5 +39,6 @@ pub use monitoring_api::monitoring_router;
 pub use filter_options_api::filter_options_router;
 pub use client_selection_api::client_selection_router;
 pub use application_roles_sdk_api::application_roles_sdk_router;
+pub use tsid_api::tsid_router;
 pub use authorization_service::AuthorizationService;
-pub use dispatch_service::{DispatchScheduler, DispatchConfig};
+pub use dispatch_service::{DispatchScheduler, DispatchConfig, BlockOnErrorChecker, BlockedMessageGroup};
@@ -21,6 +21,7 @@ use crate::{
     PrincipalRepository, ApplicationRepository,
 };
 use crate::DispatchStatus;
+use crate::shared::dispatch_service::BlockOnErrorChecker;
 
 /// Standby status response
 #[derive(Debug, Serialize, ToSchema)]
@@ -132,6 +133,32 @@ pub struct InFlightMessagesResponse {
     pub by_message_group: HashMap<String, usize>,
 }
 
+/// Blocked message group info
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedGroupInfo {
+    pub message_group: String,
+    /// Oldest failed job blocking the group
+    pub blocked_job_id: String,
+    pub error_message: String,
+    pub blocked_since: String,
+    pub failed_jobs_count: u32,
+    /// Failed auto-resume probes so far
+    pub probe_failures: u32,
+    /// Job being retried as a probe right now, if any
+    pub probe_job_id: Option<String>,
+    /// Seconds until the next probe (absent while probing)
+    pub cooldown_remaining_secs: Option<i64>,
+}
+
+/// Blocked message groups response
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedGroupsResponse {
+    pub groups: Vec<BlockedGroupInfo>,
+    pub total_blocked: usize,
+}
+
 /// Leader election state (shared across handlers)
 #[derive(Clone)]
 pub struct LeaderState {
@@ -265,6 +292,7 @@ pub struct MonitoringState {
     pub circuit_breakers: CircuitBreakerRegistry,
     pub in_flight: InFlightTracker,
     pub dispatch_job_repo: Arc<DispatchJobRepository>,
+    pub block_checker: Arc<BlockOnErrorChecker>,
     pub start_time: std::time::Instant,
 }
 
@@ -379,6 +407,43 @@ pub async fn get_circuit_breakers(
     }))
 }
 
+/// Get blocked message groups
+#[utoipa::path(
+    get,
+    path = "/blocked-groups",
+    tag = "monitoring",
+    operation_id = "getApiAdminMonitoringBlockedGroups",
+    responses(
+        (status = 200, description = "Message groups blocked by failed jobs", body = BlockedGroupsResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_blocked_groups(
+    State(state): State<MonitoringState>,
+    auth: Authenticated,
+) -> Result<Json<BlockedGroupsResponse>, PlatformError> {
+    crate::checks::require_anchor(&auth.0)?;
+
+    let now = chrono::Utc::now();
+    let mut groups: Vec<BlockedGroupInfo> = state.block_checker.get_blocked_groups().await?
+        .into_iter()
+        .map(|g| BlockedGroupInfo {
+            cooldown_remaining_secs: g.cooldown_remaining_secs(now),
+            message_group: g.message_group_id,
+            blocked_job_id: g.blocked_job_id,
+            error_message: g.error_message,
+            blocked_since: g.blocked_since.to_rfc3339(),
+            failed_jobs_count: g.pending_jobs_count,
+            probe_failures: g.probe_failures,
+            probe_job_id: g.probe_job_id,
+        })
+        .collect();
+    groups.sort_by(|a, b| a.blocked_since.cmp(&b.blocked_since));
+
+    let total_blocked = groups.len();
+    Ok(Json(BlockedGroupsResponse { groups, total_blocked }))
+}
+
 /// Get in-flight messages
 #[utoipa::path(
     get,
@@ -582,6 +647,7 @@ pub fn monitoring_router(state: MonitoringState) -> OpenApiRouter {
         .routes(routes!(get_dashboard))
         .routes(routes!(get_circuit_breakers))
         .routes(routes!(get_in_flight_messages))
+        .routes(routes!(get_blocked_groups))
         .routes(routes!(get_pool_stats))
         .with_state(state)
 }
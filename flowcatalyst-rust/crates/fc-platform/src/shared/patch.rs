@@ -0,0 +1,128 @@
+//! JSON Merge Patch
+//!
+//! Field wrapper implementing RFC 7386 semantics for update DTOs:
+//! an absent field leaves the value unchanged, `null` clears it, and
+//! any other value replaces it.
+
+use serde::{Deserialize, Deserializer};
+
+use super::error::PlatformError;
+
+/// A single field in a JSON Merge Patch document.
+///
+/// Fields of this type must be annotated with `#[serde(default)]` so that
+/// an absent key deserializes to [`Patch::Missing`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    /// Field was not present in the request - leave unchanged
+    #[default]
+    Missing,
+    /// Field was explicitly `null` - clear the value
+    Null,
+    /// Field carries a new value
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Patch::Missing)
+    }
+
+    /// Apply to a nullable field.
+    pub fn apply_to(self, target: &mut Option<T>) {
+        match self {
+            Patch::Missing => {}
+            Patch::Null => *target = None,
+            Patch::Value(v) => *target = Some(v),
+        }
+    }
+
+    /// Apply to a required field. Sending `null` for it is a validation error.
+    pub fn apply_required(self, target: &mut T, field: &str) -> Result<(), PlatformError> {
+        match self {
+            Patch::Missing => Ok(()),
+            Patch::Null => Err(PlatformError::validation(format!("{} cannot be null", field))),
+            Patch::Value(v) => {
+                *target = v;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Only reached when the key is present; absent keys use Default.
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(v) => Patch::Value(v),
+            None => Patch::Null,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::api::UpdateClientRequest;
+    use crate::client::entity::Client;
+
+    /// Deserialize a client update body and apply it like `update_client` does
+    fn apply(json: &str, client: &mut Client) -> Result<(), PlatformError> {
+        let req: UpdateClientRequest = serde_json::from_str(json).unwrap();
+        req.name.apply_required(&mut client.name, "name")?;
+        req.description.apply_to(&mut client.description);
+        Ok(())
+    }
+
+    fn client() -> Client {
+        let mut client = Client::new("Acme", "acme");
+        client.description = Some("Widgets".to_string());
+        client
+    }
+
+    #[test]
+    fn test_fields_deserialize_to_missing_null_and_value() {
+        let req: UpdateClientRequest = serde_json::from_str(r#"{"description": null}"#).unwrap();
+        assert_eq!(req.name, Patch::Missing);
+        assert_eq!(req.description, Patch::Null);
+
+        let req: UpdateClientRequest = serde_json::from_str(r#"{"name": "Acme Corp"}"#).unwrap();
+        assert_eq!(req.name, Patch::Value("Acme Corp".to_string()));
+        assert_eq!(req.description, Patch::Missing);
+    }
+
+    #[test]
+    fn test_set_value() {
+        let mut client = client();
+        apply(r#"{"name": "Acme Corp", "description": "Gadgets"}"#, &mut client).unwrap();
+
+        assert_eq!(client.name, "Acme Corp");
+        assert_eq!(client.description.as_deref(), Some("Gadgets"));
+    }
+
+    #[test]
+    fn test_clear_field() {
+        let mut client = client();
+        apply(r#"{"description": null}"#, &mut client).unwrap();
+
+        assert_eq!(client.name, "Acme");
+        assert_eq!(client.description, None);
+    }
+
+    #[test]
+    fn test_leave_unchanged() {
+        let mut client = client();
+        apply(r#"{}"#, &mut client).unwrap();
+
+        assert_eq!(client.name, "Acme");
+        assert_eq!(client.description.as_deref(), Some("Widgets"));
+    }
+
+    #[test]
+    fn test_null_on_required_field_rejected() {
+        let mut client = client();
+
+        assert!(apply(r#"{"name": null}"#, &mut client).is_err());
+        assert_eq!(client.name, "Acme");
+    }
+}
@@ -91,7 +91,7 @@ impl EventProjectionWriter {
         let mut count = 0;
         for event in events {
             if let Err(e) = self.project(event).await {
-                error!("Failed to project event {}: {:?}", event.id, e);
+                error!(event_id = %event.id, error = ?e, "Failed to project event");
             } else {
                 count += 1;
             }
@@ -148,7 +148,7 @@ impl DispatchJobProjectionWriter {
         let mut count = 0;
         for job in jobs {
             if let Err(e) = self.project(job).await {
-                error!("Failed to project job {}: {:?}", job.id, e);
+                error!(job_id = %job.id, error = ?e, "Failed to project job");
             } else {
                 count += 1;
             }
@@ -6,6 +6,8 @@
 use std::sync::atomic::{AtomicU16, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use chrono::{DateTime, Utc};
+
 /// Crockford Base32 alphabet (excludes I, L, O, U)
 const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
 
@@ -49,6 +51,23 @@ impl TsidGenerator {
     pub fn from_long(value: i64) -> String {
         encode_crockford(value as u64)
     }
+
+    /// Check whether a string is a well-formed TSID
+    ///
+    /// Accepts 13 Crockford Base32 characters (case-insensitive) whose
+    /// decoded value fits in 64 bits.
+    pub fn is_valid(s: &str) -> bool {
+        decode_crockford(s).is_some()
+    }
+
+    /// Extract the creation timestamp embedded in a TSID
+    ///
+    /// Returns `None` if the string is not a valid TSID.
+    pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+        let value = decode_crockford(s)?;
+        let millis = (value >> 22) as i64;
+        DateTime::from_timestamp_millis(millis)
+    }
 }
 
 /// Encode a 64-bit value to Crockford Base32 (13 characters)
@@ -70,7 +89,7 @@ fn decode_crockford(s: &str) -> Option<u64> {
     }
 
     let mut result: u64 = 0;
-    for c in s.chars() {
+    for (i, c) in s.chars().enumerate() {
         let c = c.to_ascii_uppercase();
         let val = match c {
             '0'..='9' => c as u64 - '0' as u64,
@@ -81,6 +100,10 @@ fn decode_crockford(s: &str) -> Option<u64> {
             'V'..='Z' => c as u64 - 'V' as u64 + 27,
             _ => return None,
         };
+        // 13 chars carry 65 bits; the leading char may only use the low 4
+        if i == 0 && val > 0xF {
+            return None;
+        }
         result = (result << 5) | val;
     }
 
@@ -132,4 +155,37 @@ mod tests {
         let id2 = TsidGenerator::generate();
         assert!(id1 < id2, "TSIDs should be lexicographically sortable");
     }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(TsidGenerator::is_valid(&TsidGenerator::generate()));
+        assert!(TsidGenerator::is_valid("0HZXEQ5Y8JY5Z"));
+        assert!(TsidGenerator::is_valid("0hzxeq5y8jy5z"));
+        assert!(!TsidGenerator::is_valid(""));
+        assert!(!TsidGenerator::is_valid("0HZXEQ5Y8JY5"));
+        assert!(!TsidGenerator::is_valid("0HZXEQ5Y8JY5ZZ"));
+        assert!(!TsidGenerator::is_valid("0HZXEQ5Y8JY5U"));
+        assert!(!TsidGenerator::is_valid("0HZXEQ5Y8JY5-"));
+        // Leading char above F would overflow 64 bits
+        assert!(!TsidGenerator::is_valid("GZZZZZZZZZZZZ"));
+    }
+
+    #[test]
+    fn test_parse_timestamp_round_trip() {
+        let before = Utc::now().timestamp_millis();
+        let id = TsidGenerator::generate();
+        let after = Utc::now().timestamp_millis();
+
+        let ts = TsidGenerator::parse_timestamp(&id).unwrap().timestamp_millis();
+        assert!(ts >= before && ts <= after, "timestamp {} not in [{}, {}]", ts, before, after);
+    }
+
+    #[test]
+    fn test_parse_timestamp_from_known_value() {
+        let millis: i64 = 1_700_000_000_123;
+        let id = TsidGenerator::from_long((millis << 22) | 0x3FFFFF);
+        let ts = TsidGenerator::parse_timestamp(&id).unwrap();
+        assert_eq!(ts.timestamp_millis(), millis);
+        assert!(TsidGenerator::parse_timestamp("not-a-tsid!!").is_none());
+    }
 }
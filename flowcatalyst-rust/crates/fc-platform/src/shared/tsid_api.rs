@@ -0,0 +1,71 @@
+//! TSID Admin API
+//!
+//! Mints TSIDs compatible with platform entity IDs, for seeding test data
+//! from external tools.
+
+use axum::{extract::Query, Json};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa::{ToSchema, IntoParams};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::error::PlatformError;
+use crate::shared::middleware::Authenticated;
+use crate::shared::tsid::TsidGenerator;
+
+/// Maximum number of IDs returned by a single request
+pub const MAX_TSID_BATCH: usize = 1000;
+
+/// Query parameters for TSID generation
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct NewTsidQuery {
+    /// Number of IDs to generate (default 1, max 1000)
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize { 1 }
+
+/// Generated TSIDs response
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTsidResponse {
+    /// Freshly generated IDs, in generation order
+    pub ids: Vec<String>,
+}
+
+/// Generate new TSIDs
+#[utoipa::path(
+    get,
+    path = "/new",
+    tag = "tsid",
+    operation_id = "getApiAdminTsidNew",
+    params(NewTsidQuery),
+    responses(
+        (status = 200, description = "Generated TSIDs", body = NewTsidResponse),
+        (status = 400, description = "Count out of range")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn new_tsids(
+    auth: Authenticated,
+    Query(query): Query<NewTsidQuery>,
+) -> Result<Json<NewTsidResponse>, PlatformError> {
+    crate::checks::require_anchor(&auth.0)?;
+
+    if query.count == 0 || query.count > MAX_TSID_BATCH {
+        return Err(PlatformError::validation(format!(
+            "count must be between 1 and {}", MAX_TSID_BATCH
+        )));
+    }
+
+    let ids = (0..query.count).map(|_| TsidGenerator::generate()).collect();
+    Ok(Json(NewTsidResponse { ids }))
+}
+
+/// Create the TSID admin router
+pub fn tsid_router() -> OpenApiRouter {
+    OpenApiRouter::new()
+        .routes(routes!(new_tsids))
+}
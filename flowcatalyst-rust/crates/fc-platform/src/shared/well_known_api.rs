@@ -170,8 +170,10 @@ pub async fn get_openid_configuration(
 
 /// Get JSON Web Key Set (JWKS)
 ///
-/// Returns the public keys used to verify JWT signatures.
-/// Clients should cache this response and refresh periodically.
+/// Returns the public keys used to verify JWT signatures: the current
+/// signing key first, followed by any keys still accepted during rotation.
+/// Clients should cache this response and refresh periodically, selecting
+/// keys by the token's `kid` header.
 #[utoipa::path(
     get,
     path = "/jwks.json",
@@ -183,24 +185,19 @@ pub async fn get_openid_configuration(
 pub async fn get_jwks(
     State(state): State<WellKnownState>,
 ) -> Json<JwksResponse> {
-    // Build JWKS from RSA components if available
-    let keys = match (state.auth_service.key_id(), state.auth_service.rsa_components()) {
-        (Some(kid), Some(components)) => {
-            // RS256 mode - return the public key with full components
-            vec![JwkKey {
-                kty: "RSA".to_string(),
-                key_use: "sig".to_string(),
-                kid: Some(kid.to_string()),
-                alg: "RS256".to_string(),
-                n: Some(components.n.clone()),
-                e: Some(components.e.clone()),
-            }]
-        }
-        _ => {
-            // HS256 mode - no public keys to expose
-            vec![]
-        }
-    };
+    // RS256 mode publishes every verification key; HS256 mode has none to expose
+    let keys = state.auth_service
+        .verification_keys()
+        .into_iter()
+        .map(|key| JwkKey {
+            kty: "RSA".to_string(),
+            key_use: "sig".to_string(),
+            kid: Some(key.kid),
+            alg: "RS256".to_string(),
+            n: Some(key.components.n),
+            e: Some(key.components.e),
+        })
+        .collect();
 
     Json(JwksResponse { keys })
 }
@@ -240,6 +237,30 @@ mod tests {
         assert!(json.contains("jwks_uri"));
     }
 
+    #[tokio::test]
+    async fn test_jwks_lists_signing_and_additional_keys() {
+        use crate::auth::auth_service::AuthConfig;
+
+        let (private_key, public_key) = AuthConfig::generate_rsa_keys(None).unwrap();
+        let (_, previous_public_key) = AuthConfig::generate_rsa_keys(None).unwrap();
+        let auth_service = Arc::new(AuthService::new(AuthConfig {
+            rsa_private_key: Some(private_key),
+            rsa_public_key: Some(public_key),
+            additional_rsa_public_keys: vec![previous_public_key],
+            ..AuthConfig::default()
+        }));
+        let state = WellKnownState {
+            auth_service: auth_service.clone(),
+            external_base_url: "https://example.com".to_string(),
+        };
+
+        let Json(jwks) = get_jwks(State(state)).await;
+        assert_eq!(jwks.keys.len(), 2);
+        assert_eq!(jwks.keys[0].kid, auth_service.key_id());
+        assert_ne!(jwks.keys[0].kid, jwks.keys[1].kid);
+        assert!(jwks.keys.iter().all(|k| k.alg == "RS256" && k.n.is_some() && k.e.is_some()));
+    }
+
     #[test]
     fn test_jwks_serialization() {
         let jwks = JwksResponse {
@@ -16,6 +16,8 @@ use crate::SubscriptionRepository;
 use crate::shared::error::PlatformError;
 use crate::shared::api_common::{PaginationParams, CreatedResponse, SuccessResponse};
 use crate::shared::middleware::Authenticated;
+use crate::shared::patch::Patch;
+use crate::subscription::operations::create::validate_filter_expression;
 
 /// Event type binding request
 #[derive(Debug, Deserialize, ToSchema)]
@@ -77,6 +79,10 @@ pub struct CreateSubscriptionRequest {
     /// Send raw event data only
     #[serde(default)]
     pub data_only: bool,
+
+    /// Predicate over event data, e.g. `$.order.total > 100`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_expression: Option<String>,
 }
 
 /// Update subscription request
@@ -84,19 +90,34 @@ pub struct CreateSubscriptionRequest {
 #[serde(rename_all = "camelCase")]
 pub struct UpdateSubscriptionRequest {
     /// Human-readable name
-    pub name: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub name: Patch<String>,
 
-    /// Description
-    pub description: Option<String>,
+    /// Description (`null` clears it)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub description: Patch<String>,
 
     /// Target URL
-    pub target: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub target: Patch<String>,
 
     /// Timeout in seconds
-    pub timeout_seconds: Option<u32>,
+    #[serde(default)]
+    #[schema(value_type = Option<u32>)]
+    pub timeout_seconds: Patch<u32>,
 
     /// Maximum retry attempts
-    pub max_retries: Option<u32>,
+    #[serde(default)]
+    #[schema(value_type = Option<u32>)]
+    pub max_retries: Patch<u32>,
+
+    /// Payload filter expression (`null` clears it)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub filter_expression: Patch<String>,
 }
 
 /// Event type binding response
@@ -159,6 +180,7 @@ pub struct SubscriptionResponse {
     pub max_retries: u32,
     pub service_account_id: Option<String>,
     pub data_only: bool,
+    pub filter_expression: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -188,6 +210,7 @@ impl From<Subscription> for SubscriptionResponse {
             max_retries: s.max_retries,
             service_account_id: s.service_account_id,
             data_only: s.data_only,
+            filter_expression: s.filter_expression,
             created_at: s.created_at.to_rfc3339(),
             updated_at: s.updated_at.to_rfc3339(),
         }
@@ -286,6 +309,7 @@ pub async fn create_subscription(
     }
 
     subscription = subscription.with_data_only(req.data_only);
+    subscription.filter_expression = validate_filter_expression(req.filter_expression.as_deref())?;
 
     if let Some(timeout) = req.timeout_seconds {
         subscription.timeout_seconds = timeout;
@@ -424,20 +448,17 @@ pub async fn update_subscription(
     }
 
     // Update fields
-    if let Some(name) = req.name {
-        subscription.name = name;
-    }
-    if let Some(desc) = req.description {
-        subscription.description = Some(desc);
-    }
-    if let Some(target) = req.target {
-        subscription.target = target;
-    }
-    if let Some(timeout) = req.timeout_seconds {
-        subscription.timeout_seconds = timeout;
-    }
-    if let Some(retries) = req.max_retries {
-        subscription.max_retries = retries;
+    req.name.apply_required(&mut subscription.name, "name")?;
+    req.description.apply_to(&mut subscription.description);
+    req.target.apply_required(&mut subscription.target, "target")?;
+    req.timeout_seconds.apply_required(&mut subscription.timeout_seconds, "timeoutSeconds")?;
+    req.max_retries.apply_required(&mut subscription.max_retries, "maxRetries")?;
+    match req.filter_expression {
+        Patch::Missing => {}
+        Patch::Null => subscription.filter_expression = None,
+        Patch::Value(expression) => {
+            subscription.filter_expression = validate_filter_expression(Some(&expression))?;
+        }
     }
 
     subscription.updated_at = chrono::Utc::now();
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use bson::serde_helpers::chrono_datetime_as_bson_datetime;
 use crate::dispatch_job::entity::DispatchMode;
+use crate::subscription::filter::FilterExpression;
 
 /// Subscription status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -145,6 +146,11 @@ pub struct Subscription {
     #[serde(default)]
     pub data_only: bool,
 
+    /// Optional predicate over event data; events that don't match are not dispatched
+    /// (see [`crate::subscription::filter`] for the syntax)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_expression: Option<String>,
+
     // === Status ===
 
     #[serde(default)]
@@ -194,6 +200,7 @@ impl Subscription {
             timeout_seconds: default_timeout(),
             max_retries: default_max_retries(),
             data_only: false,
+            filter_expression: None,
             status: SubscriptionStatus::Active,
             created_at: now,
             updated_at: now,
@@ -241,6 +248,11 @@ impl Subscription {
         self
     }
 
+    pub fn with_filter_expression(mut self, expression: impl Into<String>) -> Self {
+        self.filter_expression = Some(expression.into());
+        self
+    }
+
     /// Check if this subscription matches an event type code
     pub fn matches_event_type(&self, event_type_code: &str) -> bool {
         self.event_types.iter().any(|binding| binding.matches(event_type_code))
@@ -258,6 +270,22 @@ impl Subscription {
         }
     }
 
+    /// Check if event data satisfies this subscription's filter expression.
+    /// Subscriptions without a filter match everything; a stored expression
+    /// that no longer parses matches nothing.
+    pub fn matches_payload(&self, data: &serde_json::Value) -> bool {
+        let Some(source) = &self.filter_expression else {
+            return true;
+        };
+        match FilterExpression::cached(source) {
+            Ok(expression) => expression.matches(data),
+            Err(e) => {
+                tracing::warn!(subscription_id = %self.id, error = %e, "Ignoring event for subscription with invalid filter expression");
+                false
+            }
+        }
+    }
+
     pub fn pause(&mut self) {
         self.status = SubscriptionStatus::Paused;
         self.updated_at = Utc::now();
@@ -311,4 +339,24 @@ mod tests {
         assert!(!client_sub.matches_client(Some("client2")));
         assert!(!client_sub.matches_client(None));
     }
+
+    #[test]
+    fn test_subscription_payload_filter() {
+        let data = serde_json::json!({ "order": { "total": 250 } });
+
+        let unfiltered = Subscription::new("test", "Test", "http://example.com");
+        assert!(unfiltered.matches_payload(&data));
+
+        let matching = Subscription::new("test", "Test", "http://example.com")
+            .with_filter_expression("$.order.total > 100");
+        assert!(matching.matches_payload(&data));
+
+        let non_matching = Subscription::new("test", "Test", "http://example.com")
+            .with_filter_expression("$.order.total > 1000");
+        assert!(!non_matching.matches_payload(&data));
+
+        let malformed = Subscription::new("test", "Test", "http://example.com")
+            .with_filter_expression("$.order.total >");
+        assert!(!malformed.matches_payload(&data));
+    }
 }
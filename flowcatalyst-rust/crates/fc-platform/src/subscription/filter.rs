@@ -0,0 +1,439 @@
+//! Subscription Payload Filters
+//!
+//! Small predicate language evaluated against event data before a dispatch
+//! job is created for a subscription.
+//!
+//! Grammar:
+//! - A clause is either a bare path (`$.order.express`), which matches when
+//!   the value exists and is not `null` or `false`, or a comparison
+//!   `$.path <op> <literal>` with `==`, `!=`, `>`, `>=`, `<`, `<=`.
+//! - Paths start at `$` and use `.field`, `["field"]` and `[index]` segments.
+//! - Literals are JSON scalars: numbers, double-quoted strings, `true`,
+//!   `false` and `null`.
+//! - Clauses combine with `&&` and `||` (`&&` binds tighter) and may be
+//!   grouped with parentheses.
+//!
+//! Example: `$.order.total >= 100 && ($.order.country == "NZ" || $.order.express)`
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, OnceLock};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Maximum accepted length of a filter expression
+pub const MAX_FILTER_EXPRESSION_LEN: usize = 1024;
+
+/// Parsed expressions kept by [`FilterExpression::cached`]; the least
+/// recently used one is evicted beyond this
+const PARSE_CACHE_CAPACITY: usize = 1024;
+
+type ParseCache = Mutex<LruCache<String, Arc<FilterExpression>>>;
+
+/// Error raised when a filter expression cannot be parsed
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid filter expression at position {position}: {message}")]
+pub struct FilterParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl FilterParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self { position, message: message.into() }
+    }
+}
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// Path segment
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+/// Parsed expression tree
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Or(Vec<Expr>),
+    And(Vec<Expr>),
+    Exists(Vec<Segment>),
+    Compare(Vec<Segment>, CompareOp, Value),
+}
+
+/// Parsed subscription filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpression {
+    source: String,
+    root: Expr,
+}
+
+impl FilterExpression {
+    /// Parse a filter expression
+    pub fn parse(source: &str) -> Result<Self, FilterParseError> {
+        if source.trim().is_empty() {
+            return Err(FilterParseError::new(0, "expression is empty"));
+        }
+        if source.len() > MAX_FILTER_EXPRESSION_LEN {
+            return Err(FilterParseError::new(
+                MAX_FILTER_EXPRESSION_LEN,
+                format!("expression exceeds {} characters", MAX_FILTER_EXPRESSION_LEN),
+            ));
+        }
+
+        let mut parser = Parser { input: source, pos: 0 };
+        let root = parser.parse_or()?;
+        parser.skip_ws();
+        if parser.pos < source.len() {
+            return Err(FilterParseError::new(parser.pos, "unexpected trailing input"));
+        }
+
+        Ok(Self { source: source.to_string(), root })
+    }
+
+    /// Parse a filter expression, reusing the result of an earlier call with
+    /// the same text. Filters are checked for every event, so each distinct
+    /// expression is parsed once rather than once per event. Parse errors
+    /// are not cached.
+    pub fn cached(source: &str) -> Result<Arc<Self>, FilterParseError> {
+        static CACHE: OnceLock<ParseCache> = OnceLock::new();
+        let cache = CACHE.get_or_init(|| {
+            let capacity = NonZeroUsize::new(PARSE_CACHE_CAPACITY).expect("parse cache capacity is non-zero");
+            Mutex::new(LruCache::new(capacity))
+        });
+
+        if let Some(parsed) = cache.lock().get(source) {
+            return Ok(parsed.clone());
+        }
+
+        let parsed = Arc::new(Self::parse(source)?);
+        cache.lock().put(source.to_string(), parsed.clone());
+        Ok(parsed)
+    }
+
+    /// Original expression text
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate the expression against event data
+    pub fn matches(&self, data: &Value) -> bool {
+        eval(&self.root, data)
+    }
+}
+
+fn eval(expr: &Expr, data: &Value) -> bool {
+    match expr {
+        Expr::Or(items) => items.iter().any(|e| eval(e, data)),
+        Expr::And(items) => items.iter().all(|e| eval(e, data)),
+        Expr::Exists(path) => {
+            !matches!(resolve(path, data), None | Some(Value::Null) | Some(Value::Bool(false)))
+        }
+        Expr::Compare(path, op, expected) => match resolve(path, data) {
+            Some(actual) => compare(actual, *op, expected),
+            // A missing value is only "not equal" to anything
+            None => *op == CompareOp::Ne,
+        },
+    }
+}
+
+fn resolve<'a>(path: &[Segment], data: &'a Value) -> Option<&'a Value> {
+    path.iter().try_fold(data, |current, segment| match segment {
+        Segment::Field(name) => current.get(name.as_str()),
+        Segment::Index(index) => current.get(*index),
+    })
+}
+
+fn compare(actual: &Value, op: CompareOp, expected: &Value) -> bool {
+    let ordering = match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => None,
+        },
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+
+    match op {
+        CompareOp::Eq => ordering.map_or(actual == expected, |o| o.is_eq()),
+        CompareOp::Ne => ordering.map_or(actual != expected, |o| o.is_ne()),
+        CompareOp::Gt => ordering.is_some_and(|o| o.is_gt()),
+        CompareOp::Ge => ordering.is_some_and(|o| o.is_ge()),
+        CompareOp::Lt => ordering.is_some_and(|o| o.is_lt()),
+        CompareOp::Le => ordering.is_some_and(|o| o.is_le()),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterParseError> {
+        let mut items = vec![self.parse_and()?];
+        while self.eat("||") {
+            items.push(self.parse_and()?);
+        }
+        Ok(if items.len() == 1 { items.remove(0) } else { Expr::Or(items) })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterParseError> {
+        let mut items = vec![self.parse_clause()?];
+        while self.eat("&&") {
+            items.push(self.parse_clause()?);
+        }
+        Ok(if items.len() == 1 { items.remove(0) } else { Expr::And(items) })
+    }
+
+    fn parse_clause(&mut self) -> Result<Expr, FilterParseError> {
+        if self.eat("(") {
+            let inner = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(FilterParseError::new(self.pos, "expected ')'"));
+            }
+            return Ok(inner);
+        }
+
+        let path = self.parse_path()?;
+        match self.parse_op() {
+            Some(op) => {
+                let literal = self.parse_literal()?;
+                Ok(Expr::Compare(path, op, literal))
+            }
+            None => Ok(Expr::Exists(path)),
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<Vec<Segment>, FilterParseError> {
+        self.skip_ws();
+        if !self.rest().starts_with('$') {
+            return Err(FilterParseError::new(self.pos, "expected path starting with '$'"));
+        }
+        self.pos += 1;
+
+        let mut segments = Vec::new();
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    let len = self
+                        .rest()
+                        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                        .unwrap_or(self.rest().len());
+                    if len == 0 {
+                        return Err(FilterParseError::new(start, "expected field name after '.'"));
+                    }
+                    segments.push(Segment::Field(self.input[start..start + len].to_string()));
+                    self.pos += len;
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    if self.peek() == Some('"') {
+                        match self.parse_literal()? {
+                            Value::String(name) => segments.push(Segment::Field(name)),
+                            _ => unreachable!("quoted literal is always a string"),
+                        }
+                    } else {
+                        let len = self
+                            .rest()
+                            .find(|c: char| !c.is_ascii_digit())
+                            .unwrap_or(self.rest().len());
+                        let index = self.input[start..start + len]
+                            .parse::<usize>()
+                            .map_err(|_| FilterParseError::new(start, "expected array index or quoted field name"))?;
+                        segments.push(Segment::Index(index));
+                        self.pos += len;
+                    }
+                    if self.peek() != Some(']') {
+                        return Err(FilterParseError::new(self.pos, "expected ']'"));
+                    }
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(segments)
+    }
+
+    fn parse_op(&mut self) -> Option<CompareOp> {
+        // Two-character operators first so ">=" is not read as ">"
+        const OPS: [(&str, CompareOp); 6] = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            (">=", CompareOp::Ge),
+            ("<=", CompareOp::Le),
+            (">", CompareOp::Gt),
+            ("<", CompareOp::Lt),
+        ];
+        OPS.iter().find(|(token, _)| self.eat(token)).map(|(_, op)| *op)
+    }
+
+    fn parse_literal(&mut self) -> Result<Value, FilterParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        let rest = self.rest();
+
+        let len = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut escaped = false;
+            let end = quoted.char_indices().find_map(|(i, c)| {
+                match (escaped, c) {
+                    (true, _) => escaped = false,
+                    (false, '\\') => escaped = true,
+                    (false, '"') => return Some(i + 2),
+                    _ => {}
+                }
+                None
+            });
+            end.ok_or_else(|| FilterParseError::new(start, "unterminated string literal"))?
+        } else {
+            rest.find(|c: char| c.is_whitespace() || matches!(c, ')' | '&' | '|'))
+                .unwrap_or(rest.len())
+        };
+
+        if len == 0 {
+            return Err(FilterParseError::new(start, "expected literal value"));
+        }
+
+        let value: Value = serde_json::from_str(&rest[..len])
+            .map_err(|_| FilterParseError::new(start, format!("invalid literal '{}'", &rest[..len])))?;
+        if value.is_array() || value.is_object() {
+            return Err(FilterParseError::new(start, "literal must be a string, number, boolean or null"));
+        }
+
+        self.pos += len;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn matches(expr: &str, data: &Value) -> bool {
+        FilterExpression::parse(expr).unwrap().matches(data)
+    }
+
+    #[test]
+    fn test_cached_parses_each_expression_once() {
+        let first = FilterExpression::cached("$.order.total > 100").unwrap();
+        let second = FilterExpression::cached("$.order.total > 100").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(second.matches(&json!({"order": {"total": 150}})));
+
+        let invalid = FilterExpression::cached("$.order.total >").unwrap_err();
+        assert_eq!(FilterExpression::cached("$.order.total >").unwrap_err(), invalid);
+    }
+
+    #[test]
+    fn test_cached_evicts_least_recently_used() {
+        let kept = FilterExpression::cached("$.lru.kept").unwrap();
+        for i in 0..PARSE_CACHE_CAPACITY {
+            // Touch the first expression so it stays most recently used
+            assert!(Arc::ptr_eq(&kept, &FilterExpression::cached("$.lru.kept").unwrap()));
+            FilterExpression::cached(&format!("$.lru.filler[{}]", i)).unwrap();
+        }
+        assert!(Arc::ptr_eq(&kept, &FilterExpression::cached("$.lru.kept").unwrap()));
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let data = json!({
+            "order": { "total": 150, "country": "NZ", "express": false, "items": [{ "sku": "A-1" }] }
+        });
+
+        assert!(matches("$.order.total >= 100", &data));
+        assert!(matches("$.order.total == 150.0", &data));
+        assert!(!matches("$.order.total < 100", &data));
+        assert!(matches("$.order.country == \"NZ\"", &data));
+        assert!(matches("$.order.country != \"AU\"", &data));
+        assert!(matches("$.order.items[0].sku == \"A-1\"", &data));
+        assert!(matches("$[\"order\"][\"country\"] == \"NZ\"", &data));
+        assert!(!matches("$.order.total > \"100\"", &data));
+    }
+
+    #[test]
+    fn test_existence_and_missing_paths() {
+        let data = json!({ "order": { "express": false, "note": null, "ref": "x" } });
+
+        assert!(matches("$.order.ref", &data));
+        assert!(!matches("$.order.express", &data));
+        assert!(!matches("$.order.note", &data));
+        assert!(!matches("$.order.missing", &data));
+        assert!(!matches("$.order.missing == 1", &data));
+        assert!(matches("$.order.missing != 1", &data));
+    }
+
+    #[test]
+    fn test_boolean_combinators() {
+        let data = json!({ "total": 50, "country": "NZ", "express": true });
+
+        assert!(matches("$.total > 100 || $.country == \"NZ\"", &data));
+        assert!(!matches("$.total > 100 && $.country == \"NZ\"", &data));
+        // && binds tighter than ||
+        assert!(matches("$.total > 100 && $.country == \"AU\" || $.express", &data));
+        assert!(!matches("$.total > 100 && ($.country == \"AU\" || $.express)", &data));
+    }
+
+    #[test]
+    fn test_malformed_expressions() {
+        for expr in [
+            "",
+            "   ",
+            "order.total > 1",
+            "$.order.total >",
+            "$.order.total > abc",
+            "$.order.total == \"unterminated",
+            "$.order. == 1",
+            "$.items[x] == 1",
+            "$.total > 1 &&",
+            "($.total > 1",
+            "$.total > 1 )",
+            "$.total == [1]",
+        ] {
+            assert!(FilterExpression::parse(expr).is_err(), "expected parse error for {:?}", expr);
+        }
+
+        let too_long = format!("$.a == \"{}\"", "x".repeat(MAX_FILTER_EXPRESSION_LEN));
+        assert!(FilterExpression::parse(&too_long).is_err());
+    }
+}
@@ -3,11 +3,13 @@
 //! Event subscription management.
 
 pub mod entity;
+pub mod filter;
 pub mod repository;
 pub mod api;
 pub mod operations;
 
 // Re-export main types
 pub use entity::{Subscription, SubscriptionStatus};
+pub use filter::{FilterExpression, FilterParseError};
 pub use repository::SubscriptionRepository;
 pub use api::{SubscriptionsState, subscriptions_router};
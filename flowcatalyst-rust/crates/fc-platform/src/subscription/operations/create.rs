@@ -10,6 +10,8 @@ use crate::usecase::{
     ExecutionContext, UnitOfWork, UseCaseError, UseCaseResult,
     unit_of_work::HasId,
 };
+use crate::subscription::filter::FilterExpression;
+use crate::details;
 use super::events::SubscriptionCreated;
 
 /// Subscription code pattern: lowercase alphanumeric with hyphens
@@ -77,6 +79,26 @@ pub struct CreateSubscriptionCommand {
     /// Send raw event data only (no envelope)
     #[serde(default)]
     pub data_only: bool,
+
+    /// Predicate over event data; non-matching events are not dispatched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_expression: Option<String>,
+}
+
+/// Validate an optional filter expression, returning the trimmed expression
+/// (blank expressions are treated as no filter)
+pub(crate) fn validate_filter_expression(expression: Option<&str>) -> Result<Option<String>, UseCaseError> {
+    let Some(expression) = expression.map(str::trim).filter(|e| !e.is_empty()) else {
+        return Ok(None);
+    };
+
+    FilterExpression::parse(expression)
+        .map(|_| Some(expression.to_string()))
+        .map_err(|e| UseCaseError::validation_with_details(
+            "INVALID_FILTER_EXPRESSION",
+            e.to_string(),
+            details! { "position" => e.position },
+        ))
 }
 
 impl HasId for Subscription {
@@ -151,6 +173,12 @@ impl<U: UnitOfWork> CreateSubscriptionUseCase<U> {
             ));
         }
 
+        // Validation: filter expression must parse
+        let filter_expression = match validate_filter_expression(command.filter_expression.as_deref()) {
+            Ok(expression) => expression,
+            Err(e) => return UseCaseResult::failure(e),
+        };
+
         // Business rule: code must be unique within client scope
         let existing = self.subscription_repo
             .find_by_code_and_client(&code, command.client_id.as_deref())
@@ -184,6 +212,7 @@ impl<U: UnitOfWork> CreateSubscriptionUseCase<U> {
         subscription.dispatch_pool_id = command.dispatch_pool_id.clone();
         subscription.service_account_id = command.service_account_id.clone();
         subscription.data_only = command.data_only;
+        subscription.filter_expression = filter_expression;
         subscription.created_by = Some(ctx.principal_id.clone());
 
         if let Some(mode) = command.mode {
@@ -241,6 +270,7 @@ mod tests {
             max_retries: Some(5),
             timeout_seconds: Some(60),
             data_only: false,
+            filter_expression: Some("$.total > 100".to_string()),
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
@@ -248,6 +278,19 @@ mod tests {
         assert!(json.contains("Order Webhook"));
     }
 
+    #[test]
+    fn test_validate_filter_expression() {
+        assert_eq!(validate_filter_expression(None).unwrap(), None);
+        assert_eq!(validate_filter_expression(Some("  ")).unwrap(), None);
+        assert_eq!(
+            validate_filter_expression(Some(" $.total > 100 ")).unwrap(),
+            Some("$.total > 100".to_string())
+        );
+
+        let err = validate_filter_expression(Some("$.total >")).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FILTER_EXPRESSION");
+    }
+
     #[test]
     fn test_subscription_has_id() {
         let subscription = Subscription::new("test", "Test", "http://example.com");
@@ -20,7 +20,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 use fc_common::{Message, QueuedMessage};
-use crate::{QueueConsumer, QueueError, Result};
+use crate::{BodyFormat, QueueConsumer, QueueError, Result};
 
 /// Configuration for ActiveMQ consumer
 #[derive(Debug, Clone)]
@@ -37,6 +37,8 @@ pub struct ActiveMqConfig {
     pub auto_create_queue: bool,
     /// Queue durability
     pub durable: bool,
+    /// Encoding for published message bodies (consumers accept either format)
+    pub body_format: BodyFormat,
 }
 
 impl Default for ActiveMqConfig {
@@ -48,6 +50,7 @@ impl Default for ActiveMqConfig {
             prefetch_count: 10,
             auto_create_queue: true,
             durable: true,
+            body_format: BodyFormat::default(),
         }
     }
 }
@@ -222,7 +225,7 @@ impl QueueConsumer for ActiveMqConsumer {
             match result {
                 Ok(Some(Ok(delivery))) => {
                     // Parse the message body
-                    match serde_json::from_slice::<Message>(&delivery.data) {
+                    match crate::codec::decode_body_bytes(&delivery.data) {
                         Ok(message) => {
                             let receipt_handle = self.generate_receipt_handle(delivery.delivery_tag);
                             let broker_message_id = delivery
@@ -468,7 +471,7 @@ impl ActiveMqPublisher {
             .as_ref()
             .ok_or_else(|| QueueError::Database("Not connected".to_string()))?;
 
-        let body = serde_json::to_vec(message)?;
+        let body = crate::codec::encode_body(message, self.config.body_format)?.into_bytes();
         let message_id = message.id.clone();
 
         channel
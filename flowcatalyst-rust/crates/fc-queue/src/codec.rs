@@ -0,0 +1,195 @@
+//! Queue message body encoding
+//!
+//! Bodies are JSON by default. MessagePack is an opt-in alternative for
+//! publishers with large structured payloads: it is cheaper to parse and,
+//! for payloads with many small fields, smaller even after base64.
+//!
+//! MessagePack bodies are marked with a `msgpack:` prefix followed by the
+//! base64-encoded bytes, so they stay valid text for brokers such as SQS. A
+//! JSON body always starts with `{`, so consumers detect the format from the
+//! body itself and accept both, whichever format the publisher was set to.
+
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use fc_common::Message;
+use tracing::error;
+
+use crate::{QueueError, Result};
+
+/// Marks a body as base64-encoded MessagePack
+pub const MSGPACK_BODY_PREFIX: &str = "msgpack:";
+
+/// Encoding used when publishing message bodies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl FromStr for BodyFormat {
+    type Err = QueueError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "msgpack" | "messagepack" => Ok(Self::MessagePack),
+            other => Err(QueueError::Config(format!(
+                "Unknown body format '{}' (expected json or msgpack)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Encode a message body in the given format
+///
+/// Fails with [`QueueError::Serialization`] if the message can't be encoded.
+pub fn encode_body(message: &Message, format: BodyFormat) -> Result<String> {
+    let encoded = match format {
+        BodyFormat::Json => serde_json::to_string(message).map_err(QueueError::from),
+        BodyFormat::MessagePack => {
+            // Named (map) encoding: `Message` skips unset optional fields, which
+            // the positional array encoding can't represent
+            rmp_serde::to_vec_named(message)
+                .map(|bytes| format!("{}{}", MSGPACK_BODY_PREFIX, BASE64.encode(bytes)))
+                .map_err(|e| QueueError::Serialization(format!("MessagePack encoding failed: {}", e)))
+        }
+    };
+
+    encoded.inspect_err(|e| error!(message_id = %message.id, error = %e, "Failed to encode message body"))
+}
+
+/// Decode a message body, detecting its format from the `msgpack:` marker
+pub fn decode_body(body: &str) -> Result<Message> {
+    let Some(encoded) = body.strip_prefix(MSGPACK_BODY_PREFIX) else {
+        return Ok(serde_json::from_str(body)?);
+    };
+
+    let bytes = BASE64.decode(encoded.trim())
+        .map_err(|e| QueueError::Serialization(format!("Invalid base64 in MessagePack body: {}", e)))?;
+    rmp_serde::from_slice(&bytes)
+        .map_err(|e| QueueError::Serialization(format!("MessagePack decoding failed: {}", e)))
+}
+
+/// Decode a message body delivered as bytes (e.g. AMQP)
+pub fn decode_body_bytes(body: &[u8]) -> Result<Message> {
+    match body.strip_prefix(MSGPACK_BODY_PREFIX.as_bytes()) {
+        Some(_) => {
+            let body = std::str::from_utf8(body)
+                .map_err(|e| QueueError::Serialization(format!("Invalid MessagePack body: {}", e)))?;
+            decode_body(body)
+        }
+        None => Ok(serde_json::from_slice(body)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fc_common::MediationType;
+
+    fn test_message() -> Message {
+        Message {
+            id: "msg-1".to_string(),
+            pool_code: "POOL-A".to_string(),
+            auth_token: Some("token".to_string()),
+            signing_secret: None,
+            mediation_type: MediationType::HTTP,
+            mediation_target: "http://localhost:8080/{orderId}".to_string(),
+            message_group_id: Some("group-1".to_string()),
+            payload: Some(serde_json::json!({
+                "orderId": 42,
+                "lines": [{"sku": "A-1", "qty": 2.5}],
+                "note": null
+            })),
+            raw_body_base64: None,
+            content_type: None,
+            client_id: Some("client-1".to_string()),
+            timeout_seconds: Some(30),
+            max_retries: None,
+            correlation_id: None,
+            traceparent: Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string()),
+        }
+    }
+
+    fn assert_same(a: &Message, b: &Message) {
+        assert_eq!(serde_json::to_value(a).unwrap(), serde_json::to_value(b).unwrap());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let message = test_message();
+        let body = encode_body(&message, BodyFormat::Json).unwrap();
+
+        assert!(body.starts_with('{'));
+        assert_same(&decode_body(&body).unwrap(), &message);
+        assert_same(&decode_body_bytes(body.as_bytes()).unwrap(), &message);
+    }
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let message = test_message();
+        let body = encode_body(&message, BodyFormat::MessagePack).unwrap();
+
+        assert!(body.starts_with(MSGPACK_BODY_PREFIX));
+        assert_same(&decode_body(&body).unwrap(), &message);
+        assert_same(&decode_body_bytes(body.as_bytes()).unwrap(), &message);
+    }
+
+    #[test]
+    fn test_consumer_detects_each_format() {
+        let message = test_message();
+        let bodies = [
+            encode_body(&message, BodyFormat::Json).unwrap(),
+            encode_body(&message, BodyFormat::MessagePack).unwrap(),
+        ];
+
+        for body in &bodies {
+            assert_same(&decode_body(body).unwrap(), &message);
+        }
+    }
+
+    #[test]
+    fn test_msgpack_body_is_smaller_on_the_wire() {
+        let mut message = test_message();
+        message.payload = Some(serde_json::json!({
+            "items": (0..200).map(|i| serde_json::json!({"index": i, "enabled": true})).collect::<Vec<_>>()
+        }));
+
+        // Compare what is actually published, including the prefix and base64 overhead
+        let json = encode_body(&message, BodyFormat::Json).unwrap();
+        let msgpack = encode_body(&message, BodyFormat::MessagePack).unwrap();
+        assert!(msgpack.len() < json.len(), "msgpack {} bytes, json {} bytes", msgpack.len(), json.len());
+    }
+
+    #[test]
+    fn test_invalid_msgpack_body_rejected() {
+        assert!(decode_body("msgpack:not base64!").is_err());
+        assert!(decode_body(&format!("{}{}", MSGPACK_BODY_PREFIX, BASE64.encode(b"\xc1"))).is_err());
+    }
+
+    #[test]
+    fn test_codec_failures_are_serialization_errors() {
+        let failures = [
+            decode_body("{not json").unwrap_err(),
+            decode_body("msgpack:not base64!").unwrap_err(),
+            decode_body(&format!("{}{}", MSGPACK_BODY_PREFIX, BASE64.encode(b"\xc1"))).unwrap_err(),
+            decode_body_bytes(b"msgpack:\xff").unwrap_err(),
+        ];
+
+        for failure in failures {
+            assert!(matches!(failure, QueueError::Serialization(_)), "{:?}", failure);
+        }
+    }
+
+    #[test]
+    fn test_body_format_from_str() {
+        assert_eq!("json".parse::<BodyFormat>().unwrap(), BodyFormat::Json);
+        assert_eq!("MsgPack".parse::<BodyFormat>().unwrap(), BodyFormat::MessagePack);
+        assert_eq!("messagepack".parse::<BodyFormat>().unwrap(), BodyFormat::MessagePack);
+        assert!("xml".parse::<BodyFormat>().is_err());
+    }
+}
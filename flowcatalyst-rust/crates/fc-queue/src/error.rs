@@ -5,8 +5,10 @@ pub enum QueueError {
     #[error("Database error: {0}")]
     Database(String),
 
+    /// A message body couldn't be encoded or decoded. Caused by the message
+    /// itself, so retrying the same message won't help.
     #[error("Serialization error: {0}")]
-    Serialization(#[from] serde_json::Error),
+    Serialization(String),
 
     #[error("Message not found: {0}")]
     NotFound(String),
@@ -20,10 +22,29 @@ pub enum QueueError {
     #[error("AWS SQS error: {0}")]
     Sqs(String),
 
+    #[error("Kafka error: {0}")]
+    Kafka(String),
+
+    #[error("NATS error: {0}")]
+    Nats(String),
+
     #[error("Configuration error: {0}")]
     Config(String),
 }
 
+impl From<serde_json::Error> for QueueError {
+    fn from(e: serde_json::Error) -> Self {
+        QueueError::Serialization(e.to_string())
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl From<rdkafka::error::KafkaError> for QueueError {
+    fn from(e: rdkafka::error::KafkaError) -> Self {
+        QueueError::Kafka(e.to_string())
+    }
+}
+
 #[cfg(feature = "sqlite")]
 impl From<sqlx::Error> for QueueError {
     fn from(e: sqlx::Error) -> Self {
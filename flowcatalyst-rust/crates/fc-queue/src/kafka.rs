@@ -0,0 +1,761 @@
+//! Kafka Queue Consumer and Publisher
+//!
+//! Maps Kafka's offset-based consumption onto the poll/ack/nack model:
+//! - Messages are keyed by `message_group_id`, so a group always lands on the
+//!   same partition and is consumed in publish order
+//! - ACK commits the partition offset, but only up to the lowest message still
+//!   outstanding on that partition, so an ACK for a later message (another
+//!   group on the same partition) never commits past an unprocessed one
+//! - NACK holds the message in memory and redelivers it once the delay has
+//!   passed. The partition keeps flowing for other groups; later messages of
+//!   the NACKed message's group are held behind it so the group stays in order.
+//!   The commit stays behind a held message until it is ACKed.
+//! - Visibility extension is a no-op: a polled message stays with this
+//!   consumer until it is settled or the partition is reassigned
+//!
+//! Per-partition state is dropped when a partition is revoked in a rebalance.
+//! Delivery is at-least-once: messages held or in flight when a partition is
+//! reassigned are delivered again to the new owner.
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use rdkafka::client::ClientContext;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer};
+use rdkafka::message::Message as KafkaMessage;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{Offset, TopicPartitionList};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info};
+
+use fc_common::{Message, QueuedMessage};
+use crate::{BatchPublishResult, BodyFormat, QueueConsumer, QueueError, QueueMetrics, QueuePublisher, Result};
+
+/// Timeout for broker calls made on behalf of a single operation (watermarks, enqueue)
+const BROKER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Configuration for Kafka consumers and publishers
+#[derive(Debug, Clone)]
+pub struct KafkaConfig {
+    /// Comma-separated broker list (e.g., "localhost:9092")
+    pub bootstrap_servers: String,
+    /// Topic to consume from or publish to
+    pub topic: String,
+    /// Consumer group ID; partitions are shared between consumers in the group
+    pub group_id: String,
+    /// How long `poll` waits for each message before returning what it has
+    pub poll_timeout_ms: u64,
+    /// Encoding for published message bodies (consumers accept either format)
+    pub body_format: BodyFormat,
+    /// Extra librdkafka properties (e.g., `security.protocol`, `sasl.mechanism`)
+    pub properties: HashMap<String, String>,
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            bootstrap_servers: "localhost:9092".to_string(),
+            topic: "flowcatalyst".to_string(),
+            group_id: "flowcatalyst-router".to_string(),
+            poll_timeout_ms: 100,
+            body_format: BodyFormat::default(),
+            properties: HashMap::new(),
+        }
+    }
+}
+
+impl KafkaConfig {
+    fn client_config(&self) -> ClientConfig {
+        let mut client_config = ClientConfig::new();
+        client_config.set("bootstrap.servers", &self.bootstrap_servers);
+        for (key, value) in &self.properties {
+            client_config.set(key, value);
+        }
+        client_config
+    }
+}
+
+/// Kafka record key for a message: its `message_group_id`, so each group is
+/// kept on one partition. Ungrouped messages have no key and are spread
+/// across partitions.
+pub fn message_key(message: &Message) -> Option<&str> {
+    message.message_group_id.as_deref().filter(|group| !group.is_empty())
+}
+
+fn receipt_handle(topic: &str, partition: i32, offset: i64) -> String {
+    format!("{}:{}:{}", topic, partition, offset)
+}
+
+/// Parse a `topic:partition:offset` receipt handle into partition and offset
+fn parse_receipt_handle(receipt_handle: &str) -> Result<(i32, i64)> {
+    let mut parts = receipt_handle.rsplitn(3, ':');
+    let offset = parts.next().and_then(|o| o.parse().ok());
+    let partition = parts.next().and_then(|p| p.parse().ok());
+
+    match (partition, offset, parts.next()) {
+        (Some(partition), Some(offset), Some(_topic)) => Ok((partition, offset)),
+        _ => Err(QueueError::NotFound(receipt_handle.to_string())),
+    }
+}
+
+/// Messages polled from one partition and not yet settled
+#[derive(Debug, Default)]
+struct PartitionState {
+    /// Unsettled messages by offset, including held ones
+    outstanding: BTreeMap<i64, Message>,
+    /// NACKed messages waiting for local redelivery, with when each is due
+    held: BTreeMap<i64, Instant>,
+    /// One past the highest offset polled
+    next: i64,
+    /// Last offset committed to the broker
+    committed: i64,
+}
+
+impl PartitionState {
+    /// Track a polled message, returning it for delivery. A message whose
+    /// group has an earlier message held is held behind it instead, so the
+    /// group is redelivered in order.
+    fn polled(&mut self, offset: i64, message: Message) -> Option<Message> {
+        self.next = self.next.max(offset + 1);
+
+        let held_until = message_key(&message).and_then(|group| self.held_until(group, offset));
+        match held_until {
+            Some(due) => {
+                self.held.insert(offset, due);
+                self.outstanding.insert(offset, message);
+                None
+            }
+            None => {
+                self.outstanding.insert(offset, message.clone());
+                Some(message)
+            }
+        }
+    }
+
+    /// When the group's last held message before `offset` is due, if any is held
+    fn held_until(&self, group: &str, offset: i64) -> Option<Instant> {
+        self.held
+            .range(..offset)
+            .filter(|(held, _)| self.outstanding.get(held).and_then(message_key) == Some(group))
+            .map(|(_, due)| *due)
+            .max()
+    }
+
+    /// Hold an outstanding message until `due`, or longer if an earlier message
+    /// of its group is held longer. Later held messages of the same group are
+    /// held at least as long, so the group can't be reordered.
+    fn hold(&mut self, offset: i64, due: Instant) -> bool {
+        if !self.outstanding.contains_key(&offset) || self.held.contains_key(&offset) {
+            return false;
+        }
+        let due = self.outstanding.get(&offset)
+            .and_then(message_key)
+            .and_then(|group| self.held_until(group, offset))
+            .map_or(due, |earlier| earlier.max(due));
+        self.held.insert(offset, due);
+
+        let Self { outstanding, held, .. } = self;
+        if let Some(group) = outstanding.get(&offset).and_then(message_key) {
+            for (later, later_due) in held.range_mut(offset + 1..) {
+                if outstanding.get(later).and_then(message_key) == Some(group) {
+                    *later_due = (*later_due).max(due);
+                }
+            }
+        }
+        true
+    }
+
+    /// Release up to `max` held messages that are due, lowest offset first
+    fn release(&mut self, now: Instant, max: usize) -> Vec<(i64, Message)> {
+        let due: Vec<i64> = self.held
+            .iter()
+            .filter(|(_, due)| **due <= now)
+            .map(|(offset, _)| *offset)
+            .take(max)
+            .collect();
+
+        due.into_iter()
+            .filter_map(|offset| {
+                self.held.remove(&offset);
+                self.outstanding.get(&offset).map(|message| (offset, message.clone()))
+            })
+            .collect()
+    }
+
+    /// Settle an offset, returning the offset to commit if it moved forward
+    fn ack(&mut self, offset: i64) -> Option<i64> {
+        self.outstanding.remove(&offset)?;
+        self.held.remove(&offset);
+        self.advance()
+    }
+
+    /// Commit past a polled offset that can't be delivered
+    fn skip(&mut self, offset: i64) -> Option<i64> {
+        self.next = self.next.max(offset + 1);
+        self.advance()
+    }
+
+    /// The commit stops at the lowest offset still outstanding
+    fn advance(&mut self) -> Option<i64> {
+        let commit = self.outstanding.keys().next().copied().unwrap_or(self.next);
+        if commit > self.committed {
+            self.committed = commit;
+            Some(commit)
+        } else {
+            None
+        }
+    }
+}
+
+type Partitions = Arc<Mutex<HashMap<i32, PartitionState>>>;
+
+/// Drops the tracked state of partitions revoked from this consumer. Their
+/// unsettled messages are redelivered to the new owner from the last commit,
+/// and late ACKs for them must not commit on a partition we no longer own.
+struct RebalanceContext {
+    topic: String,
+    partitions: Partitions,
+}
+
+impl ClientContext for RebalanceContext {}
+
+impl ConsumerContext for RebalanceContext {
+    fn pre_rebalance(&self, _consumer: &BaseConsumer<Self>, rebalance: &Rebalance<'_>) {
+        let Rebalance::Revoke(revoked) = rebalance else { return };
+
+        let mut partitions = self.partitions.lock().unwrap();
+        for element in revoked.elements_for_topic(&self.topic) {
+            if let Some(state) = partitions.remove(&element.partition()) {
+                debug!(
+                    topic = %self.topic,
+                    partition = element.partition(),
+                    outstanding = state.outstanding.len(),
+                    "Kafka partition revoked, dropping unsettled messages"
+                );
+            }
+        }
+    }
+}
+
+/// Kafka queue consumer
+pub struct KafkaQueueConsumer {
+    config: KafkaConfig,
+    consumer: Arc<StreamConsumer<RebalanceContext>>,
+    /// Unsettled messages by partition, shared with the rebalance callback
+    partitions: Partitions,
+    running: AtomicBool,
+    total_polled: AtomicU64,
+    total_acked: AtomicU64,
+    total_nacked: AtomicU64,
+    total_deferred: AtomicU64,
+}
+
+impl KafkaQueueConsumer {
+    /// Create a consumer and subscribe to the configured topic.
+    /// Offsets are committed only on ACK; a new group starts from the earliest offset.
+    pub fn new(config: KafkaConfig) -> Result<Self> {
+        let partitions = Partitions::default();
+        let context = RebalanceContext {
+            topic: config.topic.clone(),
+            partitions: partitions.clone(),
+        };
+
+        let consumer: StreamConsumer<RebalanceContext> = config
+            .client_config()
+            .set("group.id", &config.group_id)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .create_with_context(context)?;
+
+        consumer.subscribe(&[config.topic.as_str()])?;
+
+        info!(
+            servers = %config.bootstrap_servers,
+            topic = %config.topic,
+            group_id = %config.group_id,
+            "Subscribed to Kafka topic"
+        );
+
+        Ok(Self {
+            config,
+            consumer: Arc::new(consumer),
+            partitions,
+            running: AtomicBool::new(true),
+            total_polled: AtomicU64::new(0),
+            total_acked: AtomicU64::new(0),
+            total_nacked: AtomicU64::new(0),
+            total_deferred: AtomicU64::new(0),
+        })
+    }
+
+    /// Create with default configuration
+    pub fn with_servers(bootstrap_servers: &str, topic: &str) -> Result<Self> {
+        Self::new(KafkaConfig {
+            bootstrap_servers: bootstrap_servers.to_string(),
+            topic: topic.to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn queued(&self, partition: i32, offset: i64, message: Message) -> QueuedMessage {
+        let handle = receipt_handle(&self.config.topic, partition, offset);
+        QueuedMessage {
+            message,
+            receipt_handle: handle.clone(),
+            broker_message_id: Some(handle),
+            queue_identifier: self.config.topic.clone(),
+        }
+    }
+
+    fn commit(&self, partition: i32, commit: Option<i64>) -> Result<()> {
+        if let Some(commit) = commit {
+            let mut tpl = TopicPartitionList::new();
+            tpl.add_partition_offset(&self.config.topic, partition, Offset::Offset(commit))?;
+            self.consumer.commit(&tpl, CommitMode::Async)?;
+        }
+        Ok(())
+    }
+
+    /// Settle an offset and commit the partition if its committed offset moved
+    fn settle(&self, partition: i32, offset: i64) -> Result<bool> {
+        let commit = {
+            let mut partitions = self.partitions.lock().unwrap();
+            let Some(state) = partitions.get_mut(&partition) else { return Ok(false) };
+            if !state.outstanding.contains_key(&offset) {
+                return Ok(false);
+            }
+            state.ack(offset)
+        };
+
+        self.commit(partition, commit)?;
+        Ok(true)
+    }
+
+    /// Hold an outstanding message for local redelivery once the delay passes.
+    /// The partition keeps flowing for other groups; its commit stays behind
+    /// the held message until it is settled.
+    fn hold(&self, receipt_handle: &str, delay_seconds: Option<u32>) -> Result<bool> {
+        let (partition, offset) = parse_receipt_handle(receipt_handle)?;
+        let due = Instant::now() + Duration::from_secs(delay_seconds.unwrap_or(0) as u64);
+
+        let held = self.partitions.lock().unwrap()
+            .get_mut(&partition)
+            .is_some_and(|state| state.hold(offset, due));
+        if !held {
+            debug!(
+                receipt_handle = %receipt_handle,
+                topic = %self.config.topic,
+                "Message not outstanding (already settled or held, or partition reassigned), nothing to do"
+            );
+        }
+        Ok(held)
+    }
+
+    /// Take up to `max` held messages that are due for redelivery
+    fn release_due(&self, max: usize) -> Vec<QueuedMessage> {
+        let now = Instant::now();
+        let mut released = Vec::new();
+
+        let mut partitions = self.partitions.lock().unwrap();
+        for (partition, state) in partitions.iter_mut() {
+            if released.len() >= max {
+                break;
+            }
+            for (offset, message) in state.release(now, max - released.len()) {
+                released.push(self.queued(*partition, offset, message));
+            }
+        }
+        released
+    }
+}
+
+#[async_trait]
+impl QueueConsumer for KafkaQueueConsumer {
+    fn identifier(&self) -> &str {
+        &self.config.topic
+    }
+
+    async fn poll(&self, max_messages: u32) -> Result<Vec<QueuedMessage>> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err(QueueError::Stopped);
+        }
+
+        // Held messages that are due go first, then new records fill the batch
+        let mut messages = self.release_due(max_messages as usize);
+        let timeout = Duration::from_millis(self.config.poll_timeout_ms);
+
+        for _ in messages.len()..max_messages as usize {
+            let record = match tokio::time::timeout(timeout, self.consumer.recv()).await {
+                Ok(Ok(record)) => record,
+                Ok(Err(e)) if messages.is_empty() => return Err(e.into()),
+                Ok(Err(e)) => {
+                    error!(topic = %self.config.topic, error = %e, "Error receiving Kafka message");
+                    break;
+                }
+                Err(_) => break, // Timeout - no more messages available
+            };
+
+            let (partition, offset) = (record.partition(), record.offset());
+            let decoded = record.payload()
+                .ok_or_else(|| QueueError::Serialization("Kafka message has no payload".to_string()))
+                .and_then(crate::codec::decode_body_bytes);
+
+            match decoded {
+                Ok(message) => {
+                    let delivered = self.partitions.lock().unwrap()
+                        .entry(partition)
+                        .or_default()
+                        .polled(offset, message);
+                    if let Some(message) = delivered {
+                        messages.push(self.queued(partition, offset, message));
+                    }
+                }
+                Err(e) => {
+                    error!(
+                        topic = %self.config.topic,
+                        partition = partition,
+                        offset = offset,
+                        error = %e,
+                        "Failed to parse Kafka message"
+                    );
+                    // Commit past the malformed message to prevent infinite retries
+                    let commit = self.partitions.lock().unwrap()
+                        .entry(partition)
+                        .or_default()
+                        .skip(offset);
+                    self.commit(partition, commit)?;
+                }
+            }
+        }
+
+        if !messages.is_empty() {
+            self.total_polled.fetch_add(messages.len() as u64, Ordering::Relaxed);
+            debug!(
+                topic = %self.config.topic,
+                count = messages.len(),
+                "Polled messages from Kafka"
+            );
+        }
+
+        Ok(messages)
+    }
+
+    async fn ack(&self, receipt_handle: &str) -> Result<()> {
+        let (partition, offset) = parse_receipt_handle(receipt_handle)?;
+
+        if self.settle(partition, offset)? {
+            self.total_acked.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                receipt_handle = %receipt_handle,
+                topic = %self.config.topic,
+                "Message acknowledged in Kafka"
+            );
+        } else {
+            debug!(
+                receipt_handle = %receipt_handle,
+                topic = %self.config.topic,
+                "Message not outstanding (partition reassigned), will be redelivered to the new owner"
+            );
+        }
+        Ok(())
+    }
+
+    async fn nack(&self, receipt_handle: &str, delay_seconds: Option<u32>) -> Result<()> {
+        if self.hold(receipt_handle, delay_seconds)? {
+            self.total_nacked.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                receipt_handle = %receipt_handle,
+                topic = %self.config.topic,
+                delay_seconds = ?delay_seconds,
+                "Message NACKed in Kafka (held for redelivery)"
+            );
+        }
+        Ok(())
+    }
+
+    async fn defer(&self, receipt_handle: &str, delay_seconds: Option<u32>) -> Result<()> {
+        // Same hold as nack, but tracked separately as not a failure
+        if self.hold(receipt_handle, delay_seconds)? {
+            self.total_deferred.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                receipt_handle = %receipt_handle,
+                topic = %self.config.topic,
+                delay_seconds = ?delay_seconds,
+                "Message deferred in Kafka (not counted as failure)"
+            );
+        }
+        Ok(())
+    }
+
+    async fn extend_visibility(&self, receipt_handle: &str, _seconds: u32) -> Result<()> {
+        // Kafka has no visibility timeout: uncommitted messages stay with this
+        // consumer until the partition is reassigned
+        debug!(
+            receipt_handle = %receipt_handle,
+            topic = %self.config.topic,
+            "Visibility extension not applicable for Kafka (offset uncommitted until ACK)"
+        );
+        Ok(())
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    async fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.consumer.unsubscribe();
+        info!(topic = %self.config.topic, "Kafka queue consumer stopped");
+    }
+
+    async fn get_metrics(&self) -> Result<Option<QueueMetrics>> {
+        let consumer = self.consumer.clone();
+        let topic = self.config.topic.clone();
+
+        // Lag of each assigned partition: high watermark minus our position.
+        // Partitions not fetched from yet have no position and are skipped.
+        let pending_messages = tokio::task::spawn_blocking(move || -> Result<u64> {
+            let position = consumer.position()?;
+            let mut pending = 0;
+            for element in position.elements_for_topic(&topic) {
+                if let Offset::Offset(position) = element.offset() {
+                    let (_, high) = consumer.fetch_watermarks(&topic, element.partition(), BROKER_TIMEOUT)?;
+                    pending += (high - position).max(0) as u64;
+                }
+            }
+            Ok(pending)
+        })
+        .await
+        .map_err(|e| QueueError::Kafka(e.to_string()))??;
+
+        let in_flight_messages = self.partitions.lock().unwrap()
+            .values()
+            .map(|state| state.outstanding.len() as u64)
+            .sum();
+
+        Ok(Some(QueueMetrics {
+            pending_messages,
+            in_flight_messages,
+            queue_identifier: self.config.topic.clone(),
+            total_polled: self.total_polled.load(Ordering::Relaxed),
+            total_acked: self.total_acked.load(Ordering::Relaxed),
+            total_nacked: self.total_nacked.load(Ordering::Relaxed),
+            total_deferred: self.total_deferred.load(Ordering::Relaxed),
+        }))
+    }
+}
+
+/// Kafka queue publisher
+pub struct KafkaPublisher {
+    config: KafkaConfig,
+    producer: FutureProducer,
+}
+
+impl KafkaPublisher {
+    /// Create a publisher for the configured topic.
+    /// Idempotence is enabled so producer retries can't reorder a partition.
+    pub fn new(config: KafkaConfig) -> Result<Self> {
+        let producer: FutureProducer = config
+            .client_config()
+            .set("enable.idempotence", "true")
+            .create()?;
+
+        Ok(Self { config, producer })
+    }
+
+    /// Create with broker list and topic
+    pub fn with_servers(bootstrap_servers: &str, topic: &str) -> Result<Self> {
+        Self::new(KafkaConfig {
+            bootstrap_servers: bootstrap_servers.to_string(),
+            topic: topic.to_string(),
+            ..Default::default()
+        })
+    }
+
+    async fn send(&self, message: &Message, body: &str) -> Result<()> {
+        let mut record = FutureRecord::<str, str>::to(&self.config.topic).payload(body);
+        if let Some(key) = message_key(message) {
+            record = record.key(key);
+        }
+
+        let (partition, offset) = self.producer
+            .send(record, BROKER_TIMEOUT)
+            .await
+            .map_err(|(e, _)| QueueError::from(e))?;
+
+        debug!(
+            message_id = %message.id,
+            topic = %self.config.topic,
+            partition = partition,
+            offset = offset,
+            "Message published to Kafka"
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl QueuePublisher for KafkaPublisher {
+    fn identifier(&self) -> &str {
+        &self.config.topic
+    }
+
+    async fn publish(&self, message: Message) -> Result<String> {
+        let body = crate::codec::encode_body(&message, self.config.body_format)?;
+        self.send(&message, &body).await?;
+        Ok(message.id)
+    }
+
+    async fn publish_batch(&self, messages: Vec<Message>) -> Result<BatchPublishResult> {
+        // Sent concurrently; the producer keeps each partition in send order
+        let sends = messages.iter().map(|message| async move {
+            let body = crate::codec::encode_body(message, self.config.body_format)?;
+            self.send(message, &body).await
+        });
+
+        let mut result = BatchPublishResult::default();
+        for (index, (message, outcome)) in messages.iter().zip(join_all(sends).await).enumerate() {
+            match outcome {
+                Ok(()) => result.successes.push(message.id.clone()),
+                Err(e) => result.fail(index, e.to_string()),
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fc_common::MediationType;
+
+    fn message(group: Option<&str>) -> Message {
+        Message {
+            id: "msg-1".to_string(),
+            pool_code: "DEFAULT".to_string(),
+            mediation_type: MediationType::HTTP,
+            mediation_target: "http://localhost:8080/test".to_string(),
+            message_group_id: group.map(|g| g.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = KafkaConfig::default();
+        assert_eq!(config.bootstrap_servers, "localhost:9092");
+        assert_eq!(config.poll_timeout_ms, 100);
+        assert!(config.properties.is_empty());
+    }
+
+    #[test]
+    fn test_message_key_from_group() {
+        assert_eq!(message_key(&message(Some("order-42"))), Some("order-42"));
+        assert_eq!(message_key(&message(Some(""))), None);
+        assert_eq!(message_key(&message(None)), None);
+    }
+
+    #[test]
+    fn test_receipt_handle_round_trip() {
+        let handle = receipt_handle("orders.v1", 3, 1042);
+        assert_eq!(parse_receipt_handle(&handle).unwrap(), (3, 1042));
+
+        assert!(parse_receipt_handle("orders:3").is_err());
+        assert!(parse_receipt_handle("orders:x:1").is_err());
+    }
+
+    fn polled(state: &mut PartitionState, offset: i64, group: Option<&str>) -> bool {
+        state.polled(offset, message(group)).is_some()
+    }
+
+    #[test]
+    fn test_in_order_acks_commit_each_offset() {
+        let mut state = PartitionState::default();
+        polled(&mut state, 0, None);
+        polled(&mut state, 1, None);
+
+        assert_eq!(state.ack(0), Some(1));
+        assert_eq!(state.ack(1), Some(2));
+        assert_eq!(state.ack(1), None);
+    }
+
+    #[test]
+    fn test_out_of_order_ack_waits_for_outstanding_offset() {
+        let mut state = PartitionState::default();
+        for offset in 0..3 {
+            polled(&mut state, offset, None);
+        }
+
+        // Offset 0 is still being processed, nothing can be committed
+        assert_eq!(state.ack(2), None);
+        assert_eq!(state.ack(1), None);
+        assert_eq!(state.ack(0), Some(3));
+    }
+
+    #[test]
+    fn test_held_message_does_not_block_other_groups() {
+        let now = Instant::now();
+        let mut state = PartitionState::default();
+        assert!(polled(&mut state, 0, Some("a")));
+        assert!(polled(&mut state, 1, Some("b")));
+
+        assert!(state.hold(0, now + Duration::from_secs(30)));
+        assert!(!state.hold(0, now), "already held");
+
+        // Group b keeps flowing, but the commit stays behind the held message
+        assert!(polled(&mut state, 2, Some("b")));
+        assert!(polled(&mut state, 3, None));
+        assert_eq!(state.ack(1), None);
+        assert_eq!(state.ack(2), None);
+
+        assert!(state.release(now, 10).is_empty());
+        let released = state.release(now + Duration::from_secs(30), 10);
+        assert_eq!(released.iter().map(|(offset, _)| *offset).collect::<Vec<_>>(), vec![0]);
+
+        assert_eq!(state.ack(0), Some(3));
+    }
+
+    #[test]
+    fn test_group_is_held_behind_its_nacked_message() {
+        let now = Instant::now();
+        let mut state = PartitionState::default();
+        assert!(polled(&mut state, 0, Some("a")));
+        assert!(polled(&mut state, 1, Some("a")));
+
+        // The router defers the rest of the batch's group after a NACK
+        assert!(state.hold(0, now + Duration::from_secs(10)));
+        assert!(state.hold(1, now));
+
+        // Later messages of the group are held too and can't overtake it
+        assert!(!polled(&mut state, 2, Some("a")));
+        assert!(state.release(now, 10).is_empty());
+
+        let released = state.release(now + Duration::from_secs(10), 2);
+        assert_eq!(released.iter().map(|(offset, _)| *offset).collect::<Vec<_>>(), vec![0, 1]);
+        let released = state.release(now + Duration::from_secs(10), 2);
+        assert_eq!(released.iter().map(|(offset, _)| *offset).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_skip_commits_past_undeliverable_offset() {
+        let mut state = PartitionState::default();
+        assert_eq!(state.skip(0), Some(1));
+
+        polled(&mut state, 1, None);
+        assert_eq!(state.skip(2), None);
+        assert_eq!(state.ack(1), Some(3));
+    }
+
+    #[test]
+    fn test_settled_message_cannot_be_held() {
+        let mut state = PartitionState::default();
+        polled(&mut state, 0, None);
+        state.ack(0);
+
+        assert!(!state.hold(0, Instant::now()));
+        assert!(!state.hold(7, Instant::now()));
+    }
+}
@@ -1,6 +1,10 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use fc_common::{Message, QueuedMessage};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
+pub mod codec;
 pub mod error;
 
 #[cfg(feature = "sqlite")]
@@ -12,6 +16,13 @@ pub mod sqs;
 #[cfg(feature = "activemq")]
 pub mod activemq;
 
+#[cfg(feature = "kafka")]
+pub mod kafka;
+
+#[cfg(feature = "nats")]
+pub mod nats;
+
+pub use codec::BodyFormat;
 pub use error::QueueError;
 
 pub type Result<T> = std::result::Result<T, QueueError>;
@@ -72,6 +83,35 @@ pub trait QueueConsumer: Send + Sync {
     async fn get_metrics(&self) -> Result<Option<QueueMetrics>> {
         Ok(None) // Default implementation returns None
     }
+
+    /// This consumer as a [`Peekable`], if its queue can be inspected
+    /// Returns None by default; brokers such as SQS can't list messages without receiving them
+    fn as_peekable(&self) -> Option<&dyn Peekable> {
+        None
+    }
+}
+
+/// Metadata of a queued message, as seen by [`Peekable::peek`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PeekedMessage {
+    /// Message ID
+    pub id: String,
+    /// Message group, if any
+    pub message_group_id: Option<String>,
+    /// When the message was published to the queue
+    pub enqueued_at: DateTime<Utc>,
+    /// When the message is (or was) next visible to consumers; in the future while in flight or delayed
+    pub visible_at: DateTime<Utc>,
+    /// Times the message has been delivered
+    pub receive_count: u32,
+}
+
+/// Queues whose messages can be listed without consuming them
+#[async_trait]
+pub trait Peekable: Send + Sync {
+    /// Up to `limit` messages in delivery order, including in-flight ones.
+    /// Does not change visibility or receive counts.
+    async fn peek(&self, limit: u32) -> Result<Vec<PeekedMessage>>;
 }
 
 /// Trait for publishing messages to a queue
@@ -83,8 +123,37 @@ pub trait QueuePublisher: Send + Sync {
     /// Publish a single message
     async fn publish(&self, message: Message) -> Result<String>;
 
-    /// Publish a batch of messages
-    async fn publish_batch(&self, messages: Vec<Message>) -> Result<Vec<String>>;
+    /// Publish a batch of messages. Messages the queue rejects are reported in
+    /// the result's `failures`; an error means nothing could be attempted.
+    async fn publish_batch(&self, messages: Vec<Message>) -> Result<BatchPublishResult>;
+}
+
+/// Outcome of a batch publish, message by message
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchPublishResult {
+    /// IDs of the messages the queue accepted
+    pub successes: Vec<String>,
+    /// Messages that were not published
+    pub failures: Vec<BatchPublishFailure>,
+}
+
+/// A message that failed to publish, by its position in the batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchPublishFailure {
+    pub index: usize,
+    pub error: String,
+}
+
+impl BatchPublishResult {
+    /// Record a failed message
+    pub fn fail(&mut self, index: usize, error: impl Into<String>) {
+        self.failures.push(BatchPublishFailure { index, error: error.into() });
+    }
+
+    /// The failure for the message at `index`, if it failed
+    pub fn failure(&self, index: usize) -> Option<&BatchPublishFailure> {
+        self.failures.iter().find(|f| f.index == index)
+    }
 }
 
 /// Combined consumer and publisher for embedded/dev mode
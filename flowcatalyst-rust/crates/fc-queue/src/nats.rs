@@ -0,0 +1,595 @@
+//! NATS JetStream Queue Consumer and Publisher
+//!
+//! Maps a JetStream work-queue stream and a durable pull consumer onto the
+//! poll/ack/nack model:
+//! - The stream is read in publish order, so messages of a group are delivered
+//!   in the order they were published
+//! - ACK maps to `ack`, which removes the message from the work-queue stream
+//! - NACK and defer map to `nak`, with the delay passed through to the server.
+//!   The message's group is blocked until it comes back: later messages of
+//!   the group delivered meanwhile are NAKed again so they can't overtake it.
+//! - Visibility extension maps to `in_progress`, which restarts the ack wait
+//!
+//! Group blocking is per consumer instance. A block is dropped once the NAKed
+//! message is ACKed, or after its delay plus the ack wait if it is redelivered
+//! elsewhere. Messages not settled within the ack wait are forgotten, since
+//! the server redelivers them anyway.
+//!
+//! Published messages carry their ID as `Nats-Msg-Id`, so a re-publish within
+//! the stream's duplicate window is dropped by the server.
+
+use async_nats::jetstream::{self, consumer::pull, consumer::AckPolicy, consumer::PullConsumer, AckKind};
+use async_nats::HeaderMap;
+use async_trait::async_trait;
+use futures::future::join_all;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info};
+
+use fc_common::{Message, QueuedMessage};
+use crate::{BatchPublishResult, BodyFormat, QueueConsumer, QueueError, QueueMetrics, QueuePublisher, Result};
+
+/// Configuration for NATS JetStream consumers and publishers
+#[derive(Debug, Clone)]
+pub struct NatsConfig {
+    /// NATS server URL (e.g., "nats://localhost:4222")
+    pub url: String,
+    /// JetStream stream name; created as a work-queue stream if missing
+    pub stream: String,
+    /// Subject the stream captures and publishers send to
+    pub subject: String,
+    /// Durable pull consumer name
+    pub consumer_name: String,
+    /// Seconds before an unacknowledged message is redelivered
+    pub ack_wait_seconds: u64,
+    /// Maximum messages delivered and not yet acknowledged
+    pub max_ack_pending: i64,
+    /// How long `poll` waits for messages before returning what it has
+    pub poll_timeout_ms: u64,
+    /// Encoding for published message bodies (consumers accept either format)
+    pub body_format: BodyFormat,
+}
+
+impl Default for NatsConfig {
+    fn default() -> Self {
+        Self {
+            url: "nats://localhost:4222".to_string(),
+            stream: "FLOWCATALYST".to_string(),
+            subject: "flowcatalyst".to_string(),
+            consumer_name: "flowcatalyst-router".to_string(),
+            ack_wait_seconds: 120,
+            max_ack_pending: 1000,
+            poll_timeout_ms: 1000,
+            body_format: BodyFormat::default(),
+        }
+    }
+}
+
+fn nats_error(e: impl Display) -> QueueError {
+    QueueError::Nats(e.to_string())
+}
+
+/// Connect and get or create the configured stream
+async fn connect(config: &NatsConfig) -> Result<(jetstream::Context, jetstream::stream::Stream)> {
+    let client = async_nats::connect(&config.url).await.map_err(nats_error)?;
+    let context = jetstream::new(client);
+
+    let stream = context
+        .get_or_create_stream(jetstream::stream::Config {
+            name: config.stream.clone(),
+            subjects: vec![config.subject.clone()],
+            retention: jetstream::stream::RetentionPolicy::WorkQueue,
+            ..Default::default()
+        })
+        .await
+        .map_err(nats_error)?;
+
+    Ok((context, stream))
+}
+
+fn receipt_handle(stream: &str, sequence: u64) -> String {
+    format!("{}:{}", stream, sequence)
+}
+
+/// Shortest NAK delay for a message held back behind its blocked group, so a
+/// held-back message isn't redelivered in a tight loop
+const MIN_HOLD_BACK: Duration = Duration::from_secs(1);
+
+/// A delivered message kept until it is settled
+struct InFlight {
+    message: jetstream::Message,
+    sequence: u64,
+    group: Option<String>,
+    /// Last delivery or ack wait restart; the server redelivers after `ack_wait`
+    touched: Instant,
+}
+
+/// A group blocked behind a NAKed message
+#[derive(Debug, Clone, Copy)]
+struct GroupBlock {
+    /// Stream sequence of the NAKed message at the head of the group
+    sequence: u64,
+    /// When the NAKed message is due for redelivery
+    due: Instant,
+    /// When the block is dropped if the message never comes back to this consumer
+    expires: Instant,
+}
+
+/// Groups blocked after a NAK, keyed by message group ID
+#[derive(Debug, Default)]
+struct BlockedGroups(HashMap<String, GroupBlock>);
+
+impl BlockedGroups {
+    /// Block a group behind a NAKed message. If the group is already blocked,
+    /// the earliest message stays at the head and the block lasts the longest.
+    fn block(&mut self, group: &str, sequence: u64, due: Instant, expires: Instant) {
+        let block = self.0.entry(group.to_string()).or_insert(GroupBlock { sequence, due, expires });
+        block.sequence = block.sequence.min(sequence);
+        block.due = block.due.max(due);
+        block.expires = block.expires.max(expires);
+    }
+
+    /// How long to hold back a delivered message, or None if it can be
+    /// delivered. The head of a blocked group and anything before it can.
+    fn hold_back(&mut self, group: &str, sequence: u64, now: Instant) -> Option<Duration> {
+        let block = *self.0.get(group)?;
+        if block.expires <= now {
+            self.0.remove(group);
+            return None;
+        }
+        if sequence <= block.sequence {
+            return None;
+        }
+        Some(block.due.saturating_duration_since(now).max(MIN_HOLD_BACK))
+    }
+
+    /// Unblock a group once the message at its head is ACKed
+    fn acked(&mut self, group: &str, sequence: u64) {
+        if self.0.get(group).is_some_and(|block| block.sequence == sequence) {
+            self.0.remove(group);
+        }
+    }
+}
+
+/// NATS JetStream queue consumer
+pub struct NatsQueueConsumer {
+    config: NatsConfig,
+    consumer: PullConsumer,
+    /// Delivered messages by receipt handle, kept until settled or the ack wait passes
+    in_flight: Mutex<HashMap<String, InFlight>>,
+    blocked_groups: Mutex<BlockedGroups>,
+    running: AtomicBool,
+    total_polled: AtomicU64,
+    total_acked: AtomicU64,
+    total_nacked: AtomicU64,
+    total_deferred: AtomicU64,
+}
+
+impl NatsQueueConsumer {
+    /// Connect and bind to the durable pull consumer, creating the stream and
+    /// consumer if they don't exist
+    pub async fn new(config: NatsConfig) -> Result<Self> {
+        let (_, stream) = connect(&config).await?;
+
+        let consumer: PullConsumer = stream
+            .get_or_create_consumer(
+                &config.consumer_name,
+                pull::Config {
+                    durable_name: Some(config.consumer_name.clone()),
+                    ack_policy: AckPolicy::Explicit,
+                    ack_wait: Duration::from_secs(config.ack_wait_seconds),
+                    max_ack_pending: config.max_ack_pending,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(nats_error)?;
+
+        info!(
+            url = %config.url,
+            stream = %config.stream,
+            consumer = %config.consumer_name,
+            "Bound to NATS JetStream consumer"
+        );
+
+        Ok(Self {
+            config,
+            consumer,
+            in_flight: Mutex::new(HashMap::new()),
+            blocked_groups: Mutex::new(BlockedGroups::default()),
+            running: AtomicBool::new(true),
+            total_polled: AtomicU64::new(0),
+            total_acked: AtomicU64::new(0),
+            total_nacked: AtomicU64::new(0),
+            total_deferred: AtomicU64::new(0),
+        })
+    }
+
+    /// Create with default configuration
+    pub async fn with_url(url: &str, stream: &str, subject: &str) -> Result<Self> {
+        Self::new(NatsConfig {
+            url: url.to_string(),
+            stream: stream.to_string(),
+            subject: subject.to_string(),
+            ..Default::default()
+        })
+        .await
+    }
+
+    fn ack_wait(&self) -> Duration {
+        Duration::from_secs(self.config.ack_wait_seconds)
+    }
+
+    /// Forget messages whose ack wait has passed; the server has already
+    /// made them available for redelivery
+    fn prune_in_flight(&self, now: Instant) {
+        let ack_wait = self.ack_wait();
+        self.in_flight.lock().unwrap().retain(|_, in_flight| now.duration_since(in_flight.touched) < ack_wait);
+    }
+
+    /// Settle an in-flight message. Returns false if it was already settled.
+    /// A NAK blocks the message's group until it is ACKed.
+    async fn settle(&self, receipt_handle: &str, kind: AckKind) -> Result<bool> {
+        let in_flight = self.in_flight.lock().unwrap().remove(receipt_handle);
+        let Some(in_flight) = in_flight else {
+            debug!(
+                receipt_handle = %receipt_handle,
+                stream = %self.config.stream,
+                "Message not in flight (already settled or ack wait passed), nothing to do"
+            );
+            return Ok(false);
+        };
+
+        // Block before the NAK reaches the server, so later messages of the
+        // group delivered from here on are held back
+        if let (AckKind::Nak(delay), Some(group)) = (&kind, &in_flight.group) {
+            let due = Instant::now() + delay.unwrap_or_default();
+            self.blocked_groups.lock().unwrap().block(group, in_flight.sequence, due, due + self.ack_wait());
+        }
+
+        in_flight.message.ack_with(kind).await.map_err(nats_error)?;
+
+        if let (AckKind::Ack, Some(group)) = (&kind, &in_flight.group) {
+            self.blocked_groups.lock().unwrap().acked(group, in_flight.sequence);
+        }
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl QueueConsumer for NatsQueueConsumer {
+    fn identifier(&self) -> &str {
+        &self.config.stream
+    }
+
+    async fn poll(&self, max_messages: u32) -> Result<Vec<QueuedMessage>> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err(QueueError::Stopped);
+        }
+
+        let mut batch = self.consumer
+            .fetch()
+            .max_messages(max_messages as usize)
+            .expires(Duration::from_millis(self.config.poll_timeout_ms))
+            .messages()
+            .await
+            .map_err(nats_error)?;
+
+        let mut messages = Vec::with_capacity(max_messages as usize);
+        self.prune_in_flight(Instant::now());
+
+        while let Some(delivered) = batch.next().await {
+            let delivered = match delivered {
+                Ok(delivered) => delivered,
+                Err(e) if messages.is_empty() => return Err(nats_error(e)),
+                Err(e) => {
+                    error!(stream = %self.config.stream, error = %e, "Error receiving NATS message");
+                    break;
+                }
+            };
+
+            // Errors past this point affect only this message; the rest of
+            // the batch is already in flight and must still be returned
+            let sequence = match delivered.info() {
+                Ok(info) => info.stream_sequence,
+                Err(e) => {
+                    error!(stream = %self.config.stream, error = %e, "NATS message has no JetStream metadata, skipping");
+                    continue;
+                }
+            };
+
+            match crate::codec::decode_body_bytes(&delivered.payload) {
+                Ok(message) => {
+                    let group = message.message_group_id.clone().filter(|group| !group.is_empty());
+                    let hold_back = group.as_deref().and_then(|group| {
+                        self.blocked_groups.lock().unwrap().hold_back(group, sequence, Instant::now())
+                    });
+                    if let Some(delay) = hold_back {
+                        debug!(
+                            stream = %self.config.stream,
+                            sequence = sequence,
+                            group = ?group,
+                            "Message group blocked behind a NAKed message, holding back"
+                        );
+                        if let Err(e) = delivered.ack_with(AckKind::Nak(Some(delay))).await {
+                            error!(stream = %self.config.stream, sequence = sequence, error = %e, "Failed to hold back NATS message");
+                        }
+                        continue;
+                    }
+
+                    let handle = receipt_handle(&self.config.stream, sequence);
+                    self.in_flight.lock().unwrap().insert(handle.clone(), InFlight {
+                        message: delivered,
+                        sequence,
+                        group,
+                        touched: Instant::now(),
+                    });
+                    messages.push(QueuedMessage {
+                        message,
+                        receipt_handle: handle.clone(),
+                        broker_message_id: Some(handle),
+                        queue_identifier: self.config.stream.clone(),
+                    });
+                }
+                Err(e) => {
+                    error!(
+                        stream = %self.config.stream,
+                        sequence = sequence,
+                        error = %e,
+                        "Failed to parse NATS message"
+                    );
+                    // Terminate the malformed message to prevent infinite redelivery
+                    if let Err(e) = delivered.ack_with(AckKind::Term).await {
+                        error!(stream = %self.config.stream, sequence = sequence, error = %e, "Failed to terminate NATS message");
+                    }
+                }
+            }
+        }
+
+        if !messages.is_empty() {
+            self.total_polled.fetch_add(messages.len() as u64, Ordering::Relaxed);
+            debug!(
+                stream = %self.config.stream,
+                count = messages.len(),
+                "Polled messages from NATS JetStream"
+            );
+        }
+
+        Ok(messages)
+    }
+
+    async fn ack(&self, receipt_handle: &str) -> Result<()> {
+        if self.settle(receipt_handle, AckKind::Ack).await? {
+            self.total_acked.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                receipt_handle = %receipt_handle,
+                stream = %self.config.stream,
+                "Message acknowledged in NATS JetStream"
+            );
+        }
+        Ok(())
+    }
+
+    async fn nack(&self, receipt_handle: &str, delay_seconds: Option<u32>) -> Result<()> {
+        let delay = delay_seconds.map(|d| Duration::from_secs(d as u64));
+        if self.settle(receipt_handle, AckKind::Nak(delay)).await? {
+            self.total_nacked.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                receipt_handle = %receipt_handle,
+                stream = %self.config.stream,
+                delay_seconds = ?delay_seconds,
+                "Message NACKed in NATS JetStream"
+            );
+        }
+        Ok(())
+    }
+
+    async fn defer(&self, receipt_handle: &str, delay_seconds: Option<u32>) -> Result<()> {
+        // Same nak as nack, but tracked separately as not a failure
+        let delay = delay_seconds.map(|d| Duration::from_secs(d as u64));
+        if self.settle(receipt_handle, AckKind::Nak(delay)).await? {
+            self.total_deferred.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                receipt_handle = %receipt_handle,
+                stream = %self.config.stream,
+                delay_seconds = ?delay_seconds,
+                "Message deferred in NATS JetStream (not counted as failure)"
+            );
+        }
+        Ok(())
+    }
+
+    async fn extend_visibility(&self, receipt_handle: &str, _seconds: u32) -> Result<()> {
+        // `in_progress` restarts the consumer's ack wait; the extension length
+        // is fixed by `ack_wait_seconds`, not chosen per call
+        let message = self.in_flight.lock().unwrap().get_mut(receipt_handle).map(|in_flight| {
+            in_flight.touched = Instant::now();
+            in_flight.message.clone()
+        });
+        let Some(message) = message else {
+            return Err(QueueError::NotFound(receipt_handle.to_string()));
+        };
+
+        message.ack_with(AckKind::Progress).await.map_err(nats_error)?;
+        debug!(
+            receipt_handle = %receipt_handle,
+            stream = %self.config.stream,
+            "Ack wait restarted in NATS JetStream"
+        );
+        Ok(())
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    async fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.in_flight.lock().unwrap().clear();
+        info!(stream = %self.config.stream, "NATS JetStream consumer stopped");
+    }
+
+    async fn get_metrics(&self) -> Result<Option<QueueMetrics>> {
+        // `info` refreshes the consumer's cached info, so query through a clone
+        let mut consumer = self.consumer.clone();
+        let info = consumer.info().await.map_err(nats_error)?;
+
+        Ok(Some(QueueMetrics {
+            pending_messages: info.num_pending,
+            in_flight_messages: info.num_ack_pending as u64,
+            queue_identifier: self.config.stream.clone(),
+            total_polled: self.total_polled.load(Ordering::Relaxed),
+            total_acked: self.total_acked.load(Ordering::Relaxed),
+            total_nacked: self.total_nacked.load(Ordering::Relaxed),
+            total_deferred: self.total_deferred.load(Ordering::Relaxed),
+        }))
+    }
+}
+
+/// NATS JetStream queue publisher
+pub struct NatsPublisher {
+    config: NatsConfig,
+    context: jetstream::Context,
+}
+
+impl NatsPublisher {
+    /// Connect and create the stream if it doesn't exist
+    pub async fn new(config: NatsConfig) -> Result<Self> {
+        let (context, _) = connect(&config).await?;
+        Ok(Self { config, context })
+    }
+
+    /// Create with server URL, stream and subject
+    pub async fn with_url(url: &str, stream: &str, subject: &str) -> Result<Self> {
+        Self::new(NatsConfig {
+            url: url.to_string(),
+            stream: stream.to_string(),
+            subject: subject.to_string(),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Send a message, returning a future for the server's publish ack
+    async fn send(&self, message: &Message) -> Result<jetstream::context::PublishAckFuture> {
+        let body = crate::codec::encode_body(message, self.config.body_format)?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(async_nats::header::NATS_MESSAGE_ID, message.id.as_str());
+
+        self.context
+            .publish_with_headers(self.config.subject.clone(), headers, body.into_bytes().into())
+            .await
+            .map_err(nats_error)
+    }
+}
+
+#[async_trait]
+impl QueuePublisher for NatsPublisher {
+    fn identifier(&self) -> &str {
+        &self.config.stream
+    }
+
+    async fn publish(&self, message: Message) -> Result<String> {
+        let ack = self.send(&message).await?.await.map_err(nats_error)?;
+
+        debug!(
+            message_id = %message.id,
+            stream = %ack.stream,
+            sequence = ack.sequence,
+            duplicate = ack.duplicate,
+            "Message published to NATS JetStream"
+        );
+        Ok(message.id)
+    }
+
+    async fn publish_batch(&self, messages: Vec<Message>) -> Result<BatchPublishResult> {
+        // Sent one after another to keep stream order, acks awaited together
+        let mut acks = Vec::with_capacity(messages.len());
+        for message in &messages {
+            acks.push(self.send(message).await);
+        }
+
+        let acks = join_all(acks.into_iter().map(|sent| async move {
+            sent?.await.map_err(nats_error)
+        }))
+        .await;
+
+        let mut result = BatchPublishResult::default();
+        for (index, (message, outcome)) in messages.iter().zip(acks).enumerate() {
+            match outcome {
+                Ok(_) => result.successes.push(message.id.clone()),
+                Err(e) => result.fail(index, e.to_string()),
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = NatsConfig::default();
+        assert_eq!(config.url, "nats://localhost:4222");
+        assert_eq!(config.ack_wait_seconds, 120);
+        assert_eq!(config.max_ack_pending, 1000);
+    }
+
+    #[test]
+    fn test_receipt_handle_format() {
+        assert_eq!(receipt_handle("FLOWCATALYST", 42), "FLOWCATALYST:42");
+    }
+
+    #[test]
+    fn test_blocked_group_holds_back_later_messages() {
+        let now = Instant::now();
+        let mut blocked = BlockedGroups::default();
+        blocked.block("order-1", 10, now + Duration::from_secs(30), now + Duration::from_secs(150));
+
+        // The NAKed message and anything before it can be delivered
+        assert_eq!(blocked.hold_back("order-1", 10, now), None);
+        assert_eq!(blocked.hold_back("order-1", 9, now), None);
+        assert_eq!(blocked.hold_back("order-1", 11, now), Some(Duration::from_secs(30)));
+
+        // Other groups are unaffected
+        assert_eq!(blocked.hold_back("order-2", 11, now), None);
+
+        // Past the redelivery time, held-back messages still wait a little
+        let later = now + Duration::from_secs(60);
+        assert_eq!(blocked.hold_back("order-1", 11, later), Some(MIN_HOLD_BACK));
+    }
+
+    #[test]
+    fn test_blocked_group_keeps_earliest_head_until_acked() {
+        let now = Instant::now();
+        let mut blocked = BlockedGroups::default();
+        blocked.block("order-1", 10, now, now + Duration::from_secs(120));
+        blocked.block("order-1", 8, now, now + Duration::from_secs(120));
+
+        assert!(blocked.hold_back("order-1", 10, now).is_some());
+
+        // Acking a message that isn't the head leaves the block in place
+        blocked.acked("order-1", 10);
+        assert!(blocked.hold_back("order-1", 9, now).is_some());
+
+        blocked.acked("order-1", 8);
+        assert_eq!(blocked.hold_back("order-1", 9, now), None);
+    }
+
+    #[test]
+    fn test_block_expires_if_message_never_returns() {
+        let now = Instant::now();
+        let mut blocked = BlockedGroups::default();
+        blocked.block("order-1", 10, now, now + Duration::from_secs(120));
+
+        assert_eq!(blocked.hold_back("order-1", 11, now + Duration::from_secs(120)), None);
+        assert!(blocked.0.is_empty());
+    }
+}
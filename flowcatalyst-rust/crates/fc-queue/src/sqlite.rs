@@ -1,23 +1,89 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
 use sqlx::{Pool, Sqlite, Row};
+use std::future::Future;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
 use tracing::{debug, warn, info};
 
 use fc_common::{Message, QueuedMessage};
-use crate::{QueueConsumer, QueuePublisher, EmbeddedQueue, QueueMetrics, Result, QueueError};
+use crate::{BatchPublishResult, BodyFormat, QueueConsumer, QueuePublisher, EmbeddedQueue, Peekable, PeekedMessage, QueueMetrics, Result, QueueError};
+
+/// How long a connection waits on another connection's write lock before
+/// SQLite reports the database as busy
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attempts at a write that keeps failing with a busy or locked database
+const BUSY_RETRY_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry of a busy write, doubled on each further attempt
+const BUSY_RETRY_BASE_DELAY: Duration = Duration::from_millis(25);
+
+/// Open a connection pool for the embedded queue. Uses WAL mode so polls don't
+/// block publishers, and a busy timeout so concurrent writers wait for the lock
+/// instead of failing straight away.
+pub async fn connect_pool(url: &str, max_connections: u32) -> Result<Pool<Sqlite>> {
+    let options = SqliteConnectOptions::from_str(url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(BUSY_TIMEOUT);
+
+    Ok(SqlitePoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(options)
+        .await?)
+}
+
+/// Whether the error is SQLITE_BUSY or SQLITE_LOCKED (including their extended
+/// codes), i.e. another connection held the lock for longer than the busy timeout
+fn is_busy_error(error: &sqlx::Error) -> bool {
+    let sqlx::Error::Database(db_error) = error else {
+        return false;
+    };
+    let primary_code = db_error.code()
+        .and_then(|code| code.parse::<i32>().ok())
+        .map(|code| code & 0xff);
+    matches!(primary_code, Some(5 | 6)) || db_error.message().contains("database is locked")
+}
+
+/// Run a write, retrying with exponential backoff while the database is busy
+/// or locked. Other errors, and the last busy error, are returned as is.
+async fn retry_on_busy<T, F, Fut>(queue: &str, operation: &str, mut write: F) -> std::result::Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
+{
+    let mut delay = BUSY_RETRY_BASE_DELAY;
+    let mut attempt = 1;
+    loop {
+        match write().await {
+            Err(e) if attempt < BUSY_RETRY_ATTEMPTS && is_busy_error(&e) => {
+                debug!(queue = %queue, operation, attempt, error = %e, "SQLite database busy, retrying");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
 
 /// SQLite-based queue that mimics SQS FIFO semantics for local development
+///
+/// Messages sharing a `message_group_id` are delivered strictly one at a time,
+/// in publish order: the next message of a group only becomes visible once the
+/// previous one is ACKed. While the group's head is in flight or delayed by a
+/// NACK, the rest of the group waits behind it. Messages without a group are
+/// independent of each other.
 pub struct SqliteQueue {
     pool: Pool<Sqlite>,
     queue_name: String,
     visibility_timeout_seconds: u32,
     running: AtomicBool,
-    // Mutex for message group ordering - ensures only one message per group is in-flight
-    #[allow(dead_code)]
-    group_locks: Arc<Mutex<std::collections::HashMap<String, bool>>>,
+    /// Encoding for published message payloads
+    body_format: BodyFormat,
 }
 
 impl SqliteQueue {
@@ -27,10 +93,16 @@ impl SqliteQueue {
             queue_name,
             visibility_timeout_seconds,
             running: AtomicBool::new(true),
-            group_locks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            body_format: BodyFormat::default(),
         }
     }
 
+    /// Publish payloads in `format` (polling accepts either format)
+    pub fn with_body_format(mut self, format: BodyFormat) -> Self {
+        self.body_format = format;
+        self
+    }
+
     /// Create the queue schema
     async fn create_schema(&self) -> Result<()> {
         sqlx::query(
@@ -94,19 +166,23 @@ impl QueueConsumer for SqliteQueue {
         let now = Utc::now().timestamp();
         let new_visible_at = now + self.visibility_timeout_seconds as i64;
 
-        // Fetch visible messages, respecting message group ordering
-        // For FIFO: only take the first message from each message group
+        // For FIFO: rank every message of a group, not just the visible ones, so
+        // a group whose head is in flight or NACK-delayed yields nothing. Rowid
+        // breaks ties between messages published within the same second.
         let rows = sqlx::query(
             r#"
-            WITH eligible AS (
-                SELECT id, message_group_id, payload,
-                       ROW_NUMBER() OVER (PARTITION BY COALESCE(message_group_id, id) ORDER BY created_at) as rn
+            WITH heads AS (
+                SELECT id, message_group_id, payload, visible_at, created_at, rowid AS seq,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY COALESCE(message_group_id, id) ORDER BY created_at, rowid
+                       ) as rn
                 FROM queue_messages
-                WHERE queue_name = ? AND visible_at <= ?
+                WHERE queue_name = ?
             )
             SELECT id, message_group_id, payload
-            FROM eligible
-            WHERE rn = 1
+            FROM heads
+            WHERE rn = 1 AND visible_at <= ?
+            ORDER BY created_at, seq
             LIMIT ?
             "#,
         )
@@ -126,19 +202,22 @@ impl QueueConsumer for SqliteQueue {
             // Generate receipt handle and update visibility
             let receipt_handle = self.generate_receipt_handle();
 
-            let updated = sqlx::query(
-                r#"
-                UPDATE queue_messages
-                SET receipt_handle = ?, visible_at = ?, receive_count = receive_count + 1
-                WHERE id = ? AND queue_name = ? AND visible_at <= ?
-                "#,
-            )
-            .bind(&receipt_handle)
-            .bind(new_visible_at)
-            .bind(&id)
-            .bind(&self.queue_name)
-            .bind(now)
-            .execute(&self.pool)
+            let (pool, queue_name) = (&self.pool, self.queue_name.as_str());
+            let updated = retry_on_busy(queue_name, "poll", || {
+                sqlx::query(
+                    r#"
+                    UPDATE queue_messages
+                    SET receipt_handle = ?, visible_at = ?, receive_count = receive_count + 1
+                    WHERE id = ? AND queue_name = ? AND visible_at <= ?
+                    "#,
+                )
+                .bind(&receipt_handle)
+                .bind(new_visible_at)
+                .bind(&id)
+                .bind(queue_name)
+                .bind(now)
+                .execute(pool)
+            })
             .await?;
 
             if updated.rows_affected() == 0 {
@@ -147,7 +226,7 @@ impl QueueConsumer for SqliteQueue {
             }
 
             // Parse the message
-            let message: Message = serde_json::from_str(&payload)?;
+            let message: Message = crate::codec::decode_body(&payload)?;
 
             messages.push(QueuedMessage {
                 message,
@@ -169,12 +248,13 @@ impl QueueConsumer for SqliteQueue {
     }
 
     async fn ack(&self, receipt_handle: &str) -> Result<()> {
-        let result = sqlx::query(
-            "DELETE FROM queue_messages WHERE receipt_handle = ? AND queue_name = ?",
-        )
-        .bind(receipt_handle)
-        .bind(&self.queue_name)
-        .execute(&self.pool)
+        let (pool, queue_name) = (&self.pool, self.queue_name.as_str());
+        let result = retry_on_busy(queue_name, "ack", || {
+            sqlx::query("DELETE FROM queue_messages WHERE receipt_handle = ? AND queue_name = ?")
+                .bind(receipt_handle)
+                .bind(queue_name)
+                .execute(pool)
+        })
         .await?;
 
         if result.rows_affected() == 0 {
@@ -198,17 +278,20 @@ impl QueueConsumer for SqliteQueue {
         let delay = delay_seconds.unwrap_or(0) as i64;
         let new_visible_at = Utc::now().timestamp() + delay;
 
-        let result = sqlx::query(
-            r#"
-            UPDATE queue_messages
-            SET visible_at = ?, receipt_handle = NULL
-            WHERE receipt_handle = ? AND queue_name = ?
-            "#,
-        )
-        .bind(new_visible_at)
-        .bind(receipt_handle)
-        .bind(&self.queue_name)
-        .execute(&self.pool)
+        let (pool, queue_name) = (&self.pool, self.queue_name.as_str());
+        let result = retry_on_busy(queue_name, "nack", || {
+            sqlx::query(
+                r#"
+                UPDATE queue_messages
+                SET visible_at = ?, receipt_handle = NULL
+                WHERE receipt_handle = ? AND queue_name = ?
+                "#,
+            )
+            .bind(new_visible_at)
+            .bind(receipt_handle)
+            .bind(queue_name)
+            .execute(pool)
+        })
         .await?;
 
         if result.rows_affected() == 0 {
@@ -232,17 +315,20 @@ impl QueueConsumer for SqliteQueue {
     async fn extend_visibility(&self, receipt_handle: &str, seconds: u32) -> Result<()> {
         let new_visible_at = Utc::now().timestamp() + seconds as i64;
 
-        let result = sqlx::query(
-            r#"
-            UPDATE queue_messages
-            SET visible_at = ?
-            WHERE receipt_handle = ? AND queue_name = ?
-            "#,
-        )
-        .bind(new_visible_at)
-        .bind(receipt_handle)
-        .bind(&self.queue_name)
-        .execute(&self.pool)
+        let (pool, queue_name) = (&self.pool, self.queue_name.as_str());
+        let result = retry_on_busy(queue_name, "extend_visibility", || {
+            sqlx::query(
+                r#"
+                UPDATE queue_messages
+                SET visible_at = ?
+                WHERE receipt_handle = ? AND queue_name = ?
+                "#,
+            )
+            .bind(new_visible_at)
+            .bind(receipt_handle)
+            .bind(queue_name)
+            .execute(pool)
+        })
         .await?;
 
         if result.rows_affected() == 0 {
@@ -309,8 +395,44 @@ impl QueueConsumer for SqliteQueue {
             total_polled: 0,
             total_acked: 0,
             total_nacked: 0,
+            total_deferred: 0,
         }))
     }
+
+    fn as_peekable(&self) -> Option<&dyn Peekable> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl Peekable for SqliteQueue {
+    async fn peek(&self, limit: u32) -> Result<Vec<PeekedMessage>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, message_group_id, created_at, visible_at, receive_count
+            FROM queue_messages
+            WHERE queue_name = ?
+            ORDER BY created_at, rowid
+            LIMIT ?
+            "#,
+        )
+        .bind(&self.queue_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let timestamp = |secs: i64| DateTime::from_timestamp(secs, 0).unwrap_or_default();
+        Ok(rows
+            .into_iter()
+            .map(|row| PeekedMessage {
+                id: row.get("id"),
+                message_group_id: row.get("message_group_id"),
+                enqueued_at: timestamp(row.get("created_at")),
+                visible_at: timestamp(row.get("visible_at")),
+                receive_count: row.get::<i64, _>("receive_count") as u32,
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -321,7 +443,7 @@ impl QueuePublisher for SqliteQueue {
 
     async fn publish(&self, message: Message) -> Result<String> {
         let now = Utc::now();
-        let payload = serde_json::to_string(&message)?;
+        let payload = crate::codec::encode_body(&message, self.body_format)?;
 
         // Check for duplicate (idempotency)
         let existing = sqlx::query(
@@ -341,19 +463,22 @@ impl QueuePublisher for SqliteQueue {
             return Ok(message.id);
         }
 
-        sqlx::query(
-            r#"
-            INSERT INTO queue_messages (id, queue_name, message_group_id, visible_at, payload, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&message.id)
-        .bind(&self.queue_name)
-        .bind(&message.message_group_id)
-        .bind(now.timestamp())
-        .bind(&payload)
-        .bind(now.timestamp())
-        .execute(&self.pool)
+        let (pool, queue_name) = (&self.pool, self.queue_name.as_str());
+        retry_on_busy(queue_name, "publish", || {
+            sqlx::query(
+                r#"
+                INSERT INTO queue_messages (id, queue_name, message_group_id, visible_at, payload, created_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&message.id)
+            .bind(queue_name)
+            .bind(&message.message_group_id)
+            .bind(now.timestamp())
+            .bind(&payload)
+            .bind(now.timestamp())
+            .execute(pool)
+        })
         .await?;
 
         debug!(
@@ -366,13 +491,15 @@ impl QueuePublisher for SqliteQueue {
         Ok(message.id)
     }
 
-    async fn publish_batch(&self, messages: Vec<Message>) -> Result<Vec<String>> {
-        let mut ids = Vec::with_capacity(messages.len());
-        for message in messages {
-            let id = self.publish(message).await?;
-            ids.push(id);
+    async fn publish_batch(&self, messages: Vec<Message>) -> Result<BatchPublishResult> {
+        let mut result = BatchPublishResult::default();
+        for (index, message) in messages.into_iter().enumerate() {
+            match self.publish(message).await {
+                Ok(id) => result.successes.push(id),
+                Err(e) => result.fail(index, e.to_string()),
+            }
         }
-        Ok(ids)
+        Ok(result)
     }
 }
 
@@ -387,6 +514,7 @@ impl EmbeddedQueue for SqliteQueue {
 mod tests {
     use super::*;
     use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Arc;
     use fc_common::MediationType;
 
     async fn create_test_queue() -> SqliteQueue {
@@ -406,11 +534,9 @@ mod tests {
         let message = Message {
             id: "msg-1".to_string(),
             pool_code: "TEST".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://localhost:8080".to_string(),
-            message_group_id: None,
+            ..Default::default()
         };
 
         // Publish
@@ -437,11 +563,9 @@ mod tests {
         let message = Message {
             id: "msg-2".to_string(),
             pool_code: "TEST".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://localhost:8080".to_string(),
-            message_group_id: None,
+            ..Default::default()
         };
 
         queue.publish(message).await.unwrap();
@@ -464,11 +588,10 @@ mod tests {
             let message = Message {
                 id: format!("msg-{}", i),
                 pool_code: "TEST".to_string(),
-                auth_token: None,
-                signing_secret: None,
                 mediation_type: MediationType::HTTP,
                 mediation_target: "http://localhost:8080".to_string(),
                 message_group_id: Some("group-1".to_string()),
+                ..Default::default()
             };
             queue.publish(message).await.unwrap();
         }
@@ -487,6 +610,67 @@ mod tests {
         assert_eq!(messages[0].message.id, "msg-2");
     }
 
+    #[tokio::test]
+    async fn test_message_group_blocked_while_head_in_flight() {
+        let queue = create_test_queue().await;
+
+        for i in 1..=3 {
+            let mut message = stress_message(format!("msg-{}", i));
+            message.message_group_id = Some("group-1".to_string());
+            queue.publish(message).await.unwrap();
+        }
+        queue.publish(stress_message("ungrouped".to_string())).await.unwrap();
+
+        let first = queue.poll(10).await.unwrap();
+        let ids: Vec<&str> = first.iter().map(|m| m.message.id.as_str()).collect();
+        assert_eq!(ids, vec!["msg-1", "ungrouped"]);
+
+        // The group's head is in flight, so the rest of the group stays hidden
+        assert!(queue.poll(10).await.unwrap().is_empty());
+
+        // A delayed NACK keeps the group blocked behind its head
+        queue.nack(&first[0].receipt_handle, Some(60)).await.unwrap();
+        assert!(queue.poll(10).await.unwrap().is_empty());
+
+        // Once redelivered and ACKed, the group advances one message at a time
+        sqlx::query("UPDATE queue_messages SET visible_at = 0 WHERE id = 'msg-1'")
+            .execute(&queue.pool)
+            .await
+            .unwrap();
+        for expected in ["msg-1", "msg-2", "msg-3"] {
+            let messages = queue.poll(10).await.unwrap();
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0].message.id, expected);
+            assert!(queue.poll(10).await.unwrap().is_empty());
+            queue.ack(&messages[0].receipt_handle).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peek_leaves_messages_in_place() {
+        let queue = create_test_queue().await;
+        for i in 1..=3 {
+            let mut message = stress_message(format!("msg-{}", i));
+            message.message_group_id = Some("group-1".to_string());
+            queue.publish(message).await.unwrap();
+        }
+        let polled = queue.poll(10).await.unwrap();
+        assert_eq!(polled.len(), 1);
+
+        let peeked = queue.as_peekable().unwrap().peek(2).await.unwrap();
+        let ids: Vec<&str> = peeked.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["msg-1", "msg-2"]);
+        assert_eq!(peeked[0].receive_count, 1);
+        assert!(peeked[0].visible_at > peeked[0].enqueued_at);
+        assert_eq!(peeked[1].receive_count, 0);
+        assert_eq!(peeked[1].message_group_id.as_deref(), Some("group-1"));
+
+        // Peeking doesn't claim anything
+        queue.ack(&polled[0].receipt_handle).await.unwrap();
+        assert_eq!(queue.poll(10).await.unwrap()[0].message.id, "msg-2");
+        assert_eq!(queue.peek(10).await.unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_deduplication() {
         let queue = create_test_queue().await;
@@ -494,11 +678,9 @@ mod tests {
         let message = Message {
             id: "dup-msg".to_string(),
             pool_code: "TEST".to_string(),
-            auth_token: None,
-            signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: "http://localhost:8080".to_string(),
-            message_group_id: None,
+            ..Default::default()
         };
 
         // Publish same message twice
@@ -509,4 +691,79 @@ mod tests {
         let messages = queue.poll(10).await.unwrap();
         assert_eq!(messages.len(), 1);
     }
+
+    fn stress_message(id: String) -> Message {
+        Message {
+            id,
+            pool_code: "TEST".to_string(),
+            mediation_type: MediationType::HTTP,
+            mediation_target: "http://localhost:8080".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_publishers_and_consumers() {
+        const PUBLISHERS: usize = 8;
+        const MESSAGES_PER_PUBLISHER: usize = 50;
+        const CONSUMERS: usize = 4;
+        const TOTAL: usize = PUBLISHERS * MESSAGES_PER_PUBLISHER;
+
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("queue.db").display());
+        let pool = connect_pool(&url, 8).await.unwrap();
+        let queue = Arc::new(SqliteQueue::new(pool, "stress-queue".to_string(), 30));
+        queue.init_schema().await.unwrap();
+
+        let publishers: Vec<_> = (0..PUBLISHERS)
+            .map(|p| {
+                let queue = queue.clone();
+                tokio::spawn(async move {
+                    for i in 0..MESSAGES_PER_PUBLISHER {
+                        queue.publish(stress_message(format!("msg-{}-{}", p, i))).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        let acked = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let nacked = Arc::new(std::sync::Mutex::new(std::collections::HashSet::new()));
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = queue.clone();
+                let acked = acked.clone();
+                let nacked = nacked.clone();
+                tokio::spawn(async move {
+                    while acked.load(Ordering::SeqCst) < TOTAL {
+                        let messages = queue.poll(10).await.unwrap();
+                        for message in &messages {
+                            // Nack every tenth message once; it is redelivered and acked later
+                            let id = &message.message.id;
+                            if id.ends_with('0') && nacked.lock().unwrap().insert(id.clone()) {
+                                queue.nack(&message.receipt_handle, None).await.unwrap();
+                            } else {
+                                queue.ack(&message.receipt_handle).await.unwrap();
+                                acked.fetch_add(1, Ordering::SeqCst);
+                            }
+                        }
+                        if messages.is_empty() {
+                            tokio::time::sleep(Duration::from_millis(5)).await;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        tokio::time::timeout(Duration::from_secs(60), async {
+            for handle in publishers.into_iter().chain(consumers) {
+                handle.await.unwrap();
+            }
+        })
+        .await
+        .expect("stress test should finish");
+
+        assert_eq!(acked.load(Ordering::SeqCst), TOTAL);
+        let metrics = queue.get_metrics().await.unwrap().unwrap();
+        assert_eq!(metrics.pending_messages + metrics.in_flight_messages, 0);
+    }
 }
@@ -1,10 +1,266 @@
 use async_trait::async_trait;
 use aws_sdk_sqs::{Client, types::Message as SqsMessage, types::QueueAttributeName};
+use aws_sdk_sqs::types::ChangeMessageVisibilityBatchRequestEntry;
+use aws_sdk_sqs::operation::send_message_batch::SendMessageBatchOutput;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use tracing::{debug, info, error};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, error, warn};
 
 use fc_common::{Message, QueuedMessage};
-use crate::{QueueConsumer, QueueMetrics, Result, QueueError};
+use crate::{BatchPublishResult, QueueConsumer, QueueMetrics, Result, QueueError};
+
+/// Message group used when publishing to a FIFO queue without a `message_group_id`
+pub const DEFAULT_MESSAGE_GROUP_ID: &str = "default";
+
+/// SQS queue type, which determines the attributes required on publish
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqsQueueType {
+    /// At-least-once, best-effort ordering. Group and deduplication IDs are rejected.
+    Standard,
+    /// Ordered within a message group. Group ID is required; deduplication ID is
+    /// required unless content-based deduplication is enabled on the queue.
+    Fifo,
+}
+
+impl SqsQueueType {
+    /// Detect the queue type from its URL or name.
+    /// AWS requires FIFO queue names to end in `.fifo`, so the suffix is authoritative.
+    pub fn from_queue_url(queue_url: &str) -> Self {
+        if queue_url.ends_with(".fifo") {
+            Self::Fifo
+        } else {
+            Self::Standard
+        }
+    }
+
+    pub fn is_fifo(&self) -> bool {
+        matches!(self, Self::Fifo)
+    }
+}
+
+/// FIFO attributes to set on an SQS `SendMessage` request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SqsSendAttributes {
+    pub message_group_id: Option<String>,
+    pub message_deduplication_id: Option<String>,
+}
+
+impl SqsSendAttributes {
+    /// Resolve publish attributes for a message on a queue of the given type.
+    ///
+    /// FIFO queues get the message's group ID (falling back to
+    /// [`DEFAULT_MESSAGE_GROUP_ID`] with a warning, since that serializes every
+    /// ungrouped message) and the message ID as deduplication ID. Standard queues
+    /// get neither, as SQS rejects them there.
+    pub fn for_message(queue_type: SqsQueueType, message: &Message) -> Self {
+        match queue_type {
+            SqsQueueType::Fifo => {
+                let message_group_id = match message.message_group_id.as_deref() {
+                    Some(group) if !group.is_empty() => group.to_string(),
+                    _ => {
+                        warn!(
+                            message_id = %message.id,
+                            "Publishing to FIFO queue without message_group_id, using default group"
+                        );
+                        DEFAULT_MESSAGE_GROUP_ID.to_string()
+                    }
+                };
+                Self {
+                    message_group_id: Some(message_group_id),
+                    message_deduplication_id: Some(message.id.clone()),
+                }
+            }
+            SqsQueueType::Standard => {
+                if message.message_group_id.is_some() {
+                    debug!(
+                        message_id = %message.id,
+                        "Ignoring message_group_id on standard SQS queue, ordering is not guaranteed"
+                    );
+                }
+                Self::default()
+            }
+        }
+    }
+}
+
+/// How long SQS FIFO queues remember a deduplication ID
+pub const DEDUPLICATION_WINDOW: Duration = Duration::from_secs(300);
+
+/// Detects sends that an SQS FIFO queue suppressed as duplicates.
+///
+/// SQS doesn't flag a deduplicated send: it reports success and returns the
+/// message ID of the original. Remembering the message ID returned for each
+/// deduplication ID over the deduplication window makes those sends visible.
+///
+/// Only sends recorded by this tracker are remembered, so duplicates are
+/// detected within a single process. A send SQS suppressed because another
+/// process (or a previous run) sent the original still looks like a new message.
+#[derive(Debug, Default)]
+pub struct SqsDeduplicationTracker {
+    sent: Mutex<SentMessages>,
+}
+
+/// Message IDs by deduplication ID, with their send times in order so
+/// expired entries can be dropped from the front
+#[derive(Debug, Default)]
+struct SentMessages {
+    by_deduplication_id: HashMap<String, (String, Instant)>,
+    order: VecDeque<(Instant, String)>,
+}
+
+impl SentMessages {
+    fn expire(&mut self, now: Instant) {
+        while let Some((sent_at, _)) = self.order.front() {
+            if now.duration_since(*sent_at) < DEDUPLICATION_WINDOW {
+                break;
+            }
+            let (sent_at, deduplication_id) = self.order.pop_front().unwrap();
+            // A later send under the same ID has its own entry further back
+            if self.by_deduplication_id.get(&deduplication_id).is_some_and(|(_, at)| *at == sent_at) {
+                self.by_deduplication_id.remove(&deduplication_id);
+            }
+        }
+    }
+
+    fn insert(&mut self, deduplication_id: &str, sqs_message_id: &str, now: Instant) {
+        self.by_deduplication_id.insert(deduplication_id.to_string(), (sqs_message_id.to_string(), now));
+        self.order.push_back((now, deduplication_id.to_string()));
+    }
+}
+
+impl SqsDeduplicationTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a successful send, returning true if SQS deduplicated it
+    pub fn record(&self, deduplication_id: &str, sqs_message_id: &str) -> bool {
+        let now = Instant::now();
+        let mut sent = self.sent.lock().unwrap();
+        sent.expire(now);
+
+        match sent.by_deduplication_id.get(deduplication_id) {
+            // The window runs from the original send, so keep its timestamp
+            Some((original_id, _)) if original_id == sqs_message_id => true,
+            _ => {
+                sent.insert(deduplication_id, sqs_message_id, now);
+                false
+            }
+        }
+    }
+}
+
+/// Maximum entries in one SQS `SendMessageBatch` request
+pub const SEND_BATCH_MAX_ENTRIES: usize = 10;
+
+/// Per-entry results of a `SendMessageBatch` request whose entry IDs are
+/// indices below `len`: the SQS message ID of each entry sent, or its error.
+///
+/// SQS reports partial failures per entry, with an error code and usually a
+/// message; entries it reports neither way count as failed.
+pub fn send_batch_entry_results(
+    len: usize,
+    output: &SendMessageBatchOutput,
+) -> Vec<std::result::Result<String, String>> {
+    let entry_index = |id: &str| id.parse::<usize>().ok().filter(|&index| index < len);
+    let mut results: Vec<Option<std::result::Result<String, String>>> = vec![None; len];
+
+    for entry in output.successful() {
+        if let Some(index) = entry_index(entry.id()) {
+            results[index] = Some(Ok(entry.message_id().to_string()));
+        }
+    }
+
+    for entry in output.failed() {
+        if let Some(index) = entry_index(entry.id()) {
+            let error = match entry.message() {
+                Some(message) => format!("{}: {}", entry.code(), message),
+                None => entry.code().to_string(),
+            };
+            results[index] = Some(Err(error));
+        }
+    }
+
+    results.into_iter()
+        .map(|result| result.unwrap_or_else(|| Err("No result reported by SQS".to_string())))
+        .collect()
+}
+
+/// Record the outcome of a `SendMessageBatch` request in `result`.
+///
+/// Entry IDs are each message's index within `chunk`, and `offset` is the chunk's
+/// position in the whole batch. See [`send_batch_entry_results`].
+pub fn record_send_batch_output(
+    result: &mut BatchPublishResult,
+    offset: usize,
+    chunk: &[Message],
+    output: &SendMessageBatchOutput,
+) {
+    for (index, entry) in send_batch_entry_results(chunk.len(), output).into_iter().enumerate() {
+        match entry {
+            Ok(_) => result.successes.push(chunk[index].id.clone()),
+            Err(error) => result.fail(offset + index, error),
+        }
+    }
+}
+
+/// SQS upper bound for a message's visibility timeout (12 hours)
+pub const MAX_VISIBILITY_TIMEOUT_SECONDS: i32 = 43_200;
+
+/// Backlog-aware visibility timeout for received batches.
+///
+/// Messages received while the router still holds many unsettled messages
+/// wait behind them before processing starts. When the estimated wait
+/// exceeds the base visibility timeout, the new batch's visibility is
+/// extended at receive time so SQS doesn't redeliver it prematurely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveVisibility {
+    /// Estimated processing seconds per outstanding message
+    pub seconds_per_message: u32,
+    /// Cap on the extended timeout
+    pub max_seconds: i32,
+}
+
+impl AdaptiveVisibility {
+    pub fn new(seconds_per_message: u32) -> Self {
+        Self {
+            seconds_per_message,
+            max_seconds: MAX_VISIBILITY_TIMEOUT_SECONDS,
+        }
+    }
+
+    /// Visibility timeout for a batch received while `outstanding` messages
+    /// (including the batch) are unsettled, or `None` when `base_seconds`
+    /// already covers the estimated wait.
+    pub fn timeout_for(&self, base_seconds: i32, outstanding: u64) -> Option<i32> {
+        let needed = outstanding
+            .saturating_mul(self.seconds_per_message as u64)
+            .min(self.max_seconds.min(MAX_VISIBILITY_TIMEOUT_SECONDS) as u64) as i32;
+        (needed > base_seconds).then_some(needed)
+    }
+}
+
+/// Build `ChangeMessageVisibilityBatch` entries, at most 10 per request (the SQS limit)
+fn visibility_batch_entries(receipt_handles: &[&str], timeout_seconds: i32) -> Vec<Vec<ChangeMessageVisibilityBatchRequestEntry>> {
+    receipt_handles
+        .chunks(10)
+        .map(|chunk| {
+            chunk.iter()
+                .enumerate()
+                .map(|(i, handle)| {
+                    ChangeMessageVisibilityBatchRequestEntry::builder()
+                        .id(i.to_string())
+                        .receipt_handle(*handle)
+                        .visibility_timeout(timeout_seconds)
+                        .build()
+                        .expect("id and receipt handle are set")
+                })
+                .collect()
+        })
+        .collect()
+}
 
 /// AWS SQS queue consumer
 pub struct SqsQueueConsumer {
@@ -13,6 +269,7 @@ pub struct SqsQueueConsumer {
     queue_name: String,
     visibility_timeout_seconds: i32,
     wait_time_seconds: i32,
+    adaptive_visibility: Option<AdaptiveVisibility>,
     running: AtomicBool,
     /// Total messages polled from queue
     total_polled: AtomicU64,
@@ -42,6 +299,7 @@ impl SqsQueueConsumer {
             queue_name,
             visibility_timeout_seconds,
             wait_time_seconds: Self::DEFAULT_WAIT_TIME_SECONDS,
+            adaptive_visibility: None,
             running: AtomicBool::new(true),
             total_polled: AtomicU64::new(0),
             total_acked: AtomicU64::new(0),
@@ -68,11 +326,57 @@ impl SqsQueueConsumer {
         self
     }
 
+    /// Extend visibility of received batches in proportion to the local backlog
+    pub fn with_adaptive_visibility(mut self, adaptive_visibility: AdaptiveVisibility) -> Self {
+        self.adaptive_visibility = Some(adaptive_visibility);
+        self
+    }
+
+    /// Messages received but not yet ACKed, NACKed or deferred
+    fn outstanding(&self) -> u64 {
+        let settled = self.total_acked.load(Ordering::Relaxed)
+            + self.total_nacked.load(Ordering::Relaxed)
+            + self.total_deferred.load(Ordering::Relaxed);
+        self.total_polled.load(Ordering::Relaxed).saturating_sub(settled)
+    }
+
+    /// Apply one visibility timeout to a received batch via `ChangeMessageVisibilityBatch`.
+    /// Failures are logged; messages then keep the receive-time timeout.
+    async fn extend_batch_visibility(&self, messages: &[QueuedMessage], timeout_seconds: i32) {
+        let handles: Vec<&str> = messages.iter().map(|m| m.receipt_handle.as_str()).collect();
+
+        for entries in visibility_batch_entries(&handles, timeout_seconds) {
+            match self.client
+                .change_message_visibility_batch()
+                .queue_url(&self.queue_url)
+                .set_entries(Some(entries))
+                .send()
+                .await
+            {
+                Ok(output) if !output.failed().is_empty() => {
+                    warn!(
+                        queue = %self.queue_name,
+                        failed = output.failed().len(),
+                        "Some visibility extensions failed"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(
+                        queue = %self.queue_name,
+                        error = %e,
+                        "Failed to extend visibility for received batch"
+                    );
+                }
+            }
+        }
+    }
+
     fn parse_sqs_message(&self, sqs_msg: &SqsMessage) -> Result<(Message, String, Option<String>)> {
         let body = sqs_msg.body()
             .ok_or_else(|| QueueError::Sqs("Message body is empty".to_string()))?;
 
-        let message: Message = serde_json::from_str(body)?;
+        let message: Message = crate::codec::decode_body(body)?;
 
         let receipt_handle = sqs_msg.receipt_handle()
             .ok_or_else(|| QueueError::Sqs("Missing receipt handle".to_string()))?
@@ -141,6 +445,19 @@ impl QueueConsumer for SqsQueueConsumer {
                 count = messages.len(),
                 "Polled messages from SQS"
             );
+
+            let outstanding = self.outstanding();
+            if let Some(timeout) = self.adaptive_visibility
+                .and_then(|a| a.timeout_for(self.visibility_timeout_seconds, outstanding))
+            {
+                debug!(
+                    queue = %self.queue_name,
+                    outstanding = outstanding,
+                    visibility_timeout = timeout,
+                    "Extending visibility for backlogged batch"
+                );
+                self.extend_batch_visibility(&messages, timeout).await;
+            }
         }
 
         Ok(messages)
@@ -279,3 +596,160 @@ impl QueueConsumer for SqsQueueConsumer {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fc_common::MediationType;
+
+    fn message(group: Option<&str>) -> Message {
+        Message {
+            id: "msg-1".to_string(),
+            pool_code: "DEFAULT".to_string(),
+            mediation_type: MediationType::HTTP,
+            mediation_target: "http://localhost/webhook".to_string(),
+            message_group_id: group.map(|g| g.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_adaptive_visibility_keeps_base_for_small_backlog() {
+        let adaptive = AdaptiveVisibility::new(2);
+        assert_eq!(adaptive.timeout_for(30, 10), None);
+        assert_eq!(adaptive.timeout_for(30, 15), None);
+    }
+
+    #[test]
+    fn test_adaptive_visibility_extends_for_large_backlog() {
+        let adaptive = AdaptiveVisibility::new(2);
+        assert_eq!(adaptive.timeout_for(30, 16), Some(32));
+        assert_eq!(adaptive.timeout_for(30, 500), Some(1000));
+    }
+
+    #[test]
+    fn test_adaptive_visibility_capped() {
+        let adaptive = AdaptiveVisibility { seconds_per_message: 60, max_seconds: 600 };
+        assert_eq!(adaptive.timeout_for(30, 1_000), Some(600));
+
+        let uncapped = AdaptiveVisibility::new(u32::MAX);
+        assert_eq!(uncapped.timeout_for(30, u64::MAX), Some(MAX_VISIBILITY_TIMEOUT_SECONDS));
+    }
+
+    #[test]
+    fn test_visibility_batch_entries_chunked() {
+        let handles: Vec<String> = (0..12).map(|i| format!("handle-{}", i)).collect();
+        let handles: Vec<&str> = handles.iter().map(String::as_str).collect();
+
+        let batches = visibility_batch_entries(&handles, 120);
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), 10);
+        assert_eq!(batches[1].len(), 2);
+        assert_eq!(batches[1][1].receipt_handle(), "handle-11");
+        assert_eq!(batches[1][1].id(), "1");
+        assert!(batches.iter().flatten().all(|e| e.visibility_timeout() == Some(120)));
+    }
+
+    #[test]
+    fn test_queue_type_from_url() {
+        assert_eq!(
+            SqsQueueType::from_queue_url("https://sqs.us-east-1.amazonaws.com/123456789/queue.fifo"),
+            SqsQueueType::Fifo
+        );
+        assert_eq!(
+            SqsQueueType::from_queue_url("https://sqs.us-east-1.amazonaws.com/123456789/queue"),
+            SqsQueueType::Standard
+        );
+    }
+
+    #[test]
+    fn test_fifo_attributes_required() {
+        let attrs = SqsSendAttributes::for_message(SqsQueueType::Fifo, &message(Some("order-42")));
+        assert_eq!(attrs.message_group_id.as_deref(), Some("order-42"));
+        assert_eq!(attrs.message_deduplication_id.as_deref(), Some("msg-1"));
+
+        let attrs = SqsSendAttributes::for_message(SqsQueueType::Fifo, &message(None));
+        assert_eq!(attrs.message_group_id.as_deref(), Some(DEFAULT_MESSAGE_GROUP_ID));
+        assert_eq!(attrs.message_deduplication_id.as_deref(), Some("msg-1"));
+    }
+
+    #[test]
+    fn test_standard_attributes_omitted() {
+        let attrs = SqsSendAttributes::for_message(SqsQueueType::Standard, &message(Some("order-42")));
+        assert_eq!(attrs, SqsSendAttributes::default());
+    }
+
+    #[test]
+    fn test_deduplication_detected_from_repeated_message_id() {
+        let tracker = SqsDeduplicationTracker::new();
+
+        assert!(!tracker.record("msg-1", "sqs-a"));
+        assert!(tracker.record("msg-1", "sqs-a"));
+        assert!(!tracker.record("msg-2", "sqs-b"));
+    }
+
+    #[test]
+    fn test_new_message_id_after_window_not_deduplicated() {
+        let tracker = SqsDeduplicationTracker::new();
+        tracker.sent.lock().unwrap().insert("msg-1", "sqs-a", Instant::now() - DEDUPLICATION_WINDOW);
+
+        // SQS accepted it again under a new ID once the window passed
+        assert!(!tracker.record("msg-1", "sqs-b"));
+        assert!(tracker.record("msg-1", "sqs-b"));
+    }
+
+    #[test]
+    fn test_expired_sends_dropped_without_losing_newer_ones() {
+        let tracker = SqsDeduplicationTracker::new();
+        let expired = Instant::now() - DEDUPLICATION_WINDOW;
+        {
+            let mut sent = tracker.sent.lock().unwrap();
+            sent.insert("msg-1", "sqs-a", expired);
+            sent.insert("msg-2", "sqs-b", expired);
+        }
+        // msg-1 was sent again under a new ID within the window
+        tracker.sent.lock().unwrap().insert("msg-1", "sqs-c", Instant::now());
+
+        assert!(!tracker.record("msg-3", "sqs-d"));
+
+        let sent = tracker.sent.lock().unwrap();
+        assert_eq!(sent.order.len(), 2);
+        assert!(!sent.by_deduplication_id.contains_key("msg-2"));
+        assert_eq!(sent.by_deduplication_id["msg-1"].0, "sqs-c");
+    }
+
+    #[test]
+    fn test_send_batch_partial_failure_mapped_to_batch_indices() {
+        use aws_sdk_sqs::types::{BatchResultErrorEntry, SendMessageBatchResultEntry};
+
+        let chunk: Vec<Message> = ["msg-10", "msg-11", "msg-12"].iter()
+            .map(|id| Message { id: id.to_string(), ..message(None) })
+            .collect();
+        let output = SendMessageBatchOutput::builder()
+            .successful(SendMessageBatchResultEntry::builder()
+                .id("0")
+                .message_id("sqs-a")
+                .md5_of_message_body("md5")
+                .build()
+                .unwrap())
+            .failed(BatchResultErrorEntry::builder()
+                .id("2")
+                .sender_fault(true)
+                .code("InvalidParameterValue")
+                .message("Message too long")
+                .build()
+                .unwrap())
+            .build()
+            .unwrap();
+
+        let mut result = BatchPublishResult::default();
+        record_send_batch_output(&mut result, 10, &chunk, &output);
+
+        assert_eq!(result.successes, vec!["msg-10".to_string()]);
+        assert_eq!(result.failures.len(), 2);
+        assert_eq!(result.failure(12).unwrap().error, "InvalidParameterValue: Message too long");
+        // Entry 1 appears in neither list
+        assert_eq!(result.failure(11).unwrap().error, "No result reported by SQS");
+    }
+}
@@ -16,7 +16,7 @@
 use std::time::Duration;
 
 use fc_common::{Message, MediationType};
-use fc_queue::{QueueConsumer, activemq::{ActiveMqConfig, ActiveMqConsumer, ActiveMqPublisher}};
+use fc_queue::{BodyFormat, QueueConsumer, activemq::{ActiveMqConfig, ActiveMqConsumer, ActiveMqPublisher}};
 use chrono::Utc;
 use reqwest;
 
@@ -31,6 +31,7 @@ fn create_test_config() -> ActiveMqConfig {
         prefetch_count: 10,
         auto_create_queue: true,
         durable: false, // Non-durable for tests
+        body_format: BodyFormat::default(),
     }
 }
 
@@ -38,11 +39,9 @@ fn create_test_message(id: &str) -> Message {
     Message {
         id: id.to_string(),
         pool_code: "DEFAULT".to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: "http://localhost:8080/test".to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
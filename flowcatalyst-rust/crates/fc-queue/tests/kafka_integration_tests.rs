@@ -0,0 +1,117 @@
+//! Kafka Queue Consumer Integration Tests
+//!
+//! These tests require Kafka to be running:
+//! docker-compose -f docker-compose.test.yml up -d kafka
+//!
+//! Tests for:
+//! - Publish and consume round trip
+//! - Offset commit on ACK
+//! - Redelivery after NACK
+
+#![cfg(feature = "kafka")]
+
+use std::time::Duration;
+
+use fc_common::{Message, MediationType};
+use fc_queue::{QueueConsumer, QueuePublisher, kafka::{KafkaConfig, KafkaPublisher, KafkaQueueConsumer}};
+
+const BOOTSTRAP_SERVERS: &str = "localhost:9092";
+
+fn create_test_config(topic: &str) -> KafkaConfig {
+    KafkaConfig {
+        bootstrap_servers: BOOTSTRAP_SERVERS.to_string(),
+        topic: topic.to_string(),
+        group_id: format!("test-group-{}", uuid::Uuid::new_v4()),
+        poll_timeout_ms: 1000,
+        ..Default::default()
+    }
+}
+
+fn create_test_message(id: &str, group_id: Option<&str>) -> Message {
+    Message {
+        id: id.to_string(),
+        pool_code: "DEFAULT".to_string(),
+        mediation_type: MediationType::HTTP,
+        mediation_target: "http://localhost:8080/test".to_string(),
+        message_group_id: group_id.map(|s| s.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Check if Kafka is available
+async fn is_kafka_available() -> bool {
+    tokio::time::timeout(Duration::from_secs(2), tokio::net::TcpStream::connect(BOOTSTRAP_SERVERS))
+        .await
+        .map(|r| r.is_ok())
+        .unwrap_or(false)
+}
+
+/// Poll until `count` messages arrive; the first poll waits for partition assignment
+async fn poll_until(consumer: &KafkaQueueConsumer, count: usize) -> Vec<fc_common::QueuedMessage> {
+    let mut messages = Vec::new();
+    for _ in 0..20 {
+        messages.extend(consumer.poll(10).await.expect("Poll failed"));
+        if messages.len() >= count {
+            break;
+        }
+    }
+    messages
+}
+
+#[tokio::test]
+async fn test_publish_and_consume() {
+    if !is_kafka_available().await {
+        eprintln!("Skipping test - Kafka not available");
+        return;
+    }
+
+    let topic = format!("pub-con-test-{}", uuid::Uuid::new_v4());
+    let publisher = KafkaPublisher::new(create_test_config(&topic)).expect("Failed to create publisher");
+    let msg_id = publisher.publish(create_test_message("kafka-msg-1", Some("group-a"))).await.expect("Publish failed");
+    assert_eq!(msg_id, "kafka-msg-1");
+
+    let consumer = KafkaQueueConsumer::new(create_test_config(&topic)).expect("Failed to create consumer");
+    let messages = poll_until(&consumer, 1).await;
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].message.id, "kafka-msg-1");
+    consumer.ack(&messages[0].receipt_handle).await.expect("Ack failed");
+
+    consumer.stop().await;
+}
+
+#[tokio::test]
+async fn test_nack_redelivers_message() {
+    if !is_kafka_available().await {
+        eprintln!("Skipping test - Kafka not available");
+        return;
+    }
+
+    let topic = format!("nack-test-{}", uuid::Uuid::new_v4());
+    let publisher = KafkaPublisher::new(create_test_config(&topic)).expect("Failed to create publisher");
+    let messages = vec![
+        create_test_message("kafka-msg-1", Some("group-a")),
+        create_test_message("kafka-msg-2", Some("group-a")),
+    ];
+    let result = publisher.publish_batch(messages).await.expect("Publish failed");
+    assert_eq!(result.successes.len(), 2);
+
+    let consumer = KafkaQueueConsumer::new(create_test_config(&topic)).expect("Failed to create consumer");
+    let messages = poll_until(&consumer, 2).await;
+    assert_eq!(messages.len(), 2);
+
+    // NACKing the first message redelivers both, in order
+    consumer.nack(&messages[0].receipt_handle, None).await.expect("Nack failed");
+    let redelivered = poll_until(&consumer, 2).await;
+    let ids: Vec<_> = redelivered.iter().map(|m| m.message.id.as_str()).collect();
+    assert_eq!(ids, vec!["kafka-msg-1", "kafka-msg-2"]);
+
+    for message in &redelivered {
+        consumer.ack(&message.receipt_handle).await.expect("Ack failed");
+    }
+    let metrics = consumer.get_metrics().await.expect("Metrics failed").unwrap();
+    assert_eq!(metrics.in_flight_messages, 0);
+    assert_eq!(metrics.total_nacked, 1);
+
+    consumer.stop().await;
+}
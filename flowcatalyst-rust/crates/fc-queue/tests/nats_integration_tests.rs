@@ -0,0 +1,115 @@
+//! NATS JetStream Queue Consumer Integration Tests
+//!
+//! These tests require NATS with JetStream enabled to be running:
+//! docker-compose -f docker-compose.test.yml up -d nats
+//!
+//! Tests for:
+//! - Publish and consume round trip
+//! - Redelivery after NACK
+//! - Pending and ack-pending metrics
+
+#![cfg(feature = "nats")]
+
+use std::time::Duration;
+
+use fc_common::{Message, MediationType};
+use fc_queue::{QueueConsumer, QueuePublisher, nats::{NatsConfig, NatsPublisher, NatsQueueConsumer}};
+
+const NATS_ADDR: &str = "localhost:4222";
+
+fn create_test_config(name: &str) -> NatsConfig {
+    NatsConfig {
+        url: format!("nats://{}", NATS_ADDR),
+        stream: name.to_string(),
+        subject: format!("test.{}", name),
+        consumer_name: format!("{}-consumer", name),
+        poll_timeout_ms: 500,
+        ..Default::default()
+    }
+}
+
+fn create_test_message(id: &str, group_id: Option<&str>) -> Message {
+    Message {
+        id: id.to_string(),
+        pool_code: "DEFAULT".to_string(),
+        mediation_type: MediationType::HTTP,
+        mediation_target: "http://localhost:8080/test".to_string(),
+        message_group_id: group_id.map(|s| s.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Check if NATS is available
+async fn is_nats_available() -> bool {
+    tokio::time::timeout(Duration::from_secs(2), tokio::net::TcpStream::connect(NATS_ADDR))
+        .await
+        .map(|r| r.is_ok())
+        .unwrap_or(false)
+}
+
+fn unique_name(prefix: &str) -> String {
+    format!("{}-{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+#[tokio::test]
+async fn test_publish_and_consume() {
+    if !is_nats_available().await {
+        eprintln!("Skipping test - NATS not available");
+        return;
+    }
+
+    let config = create_test_config(&unique_name("pubcon"));
+    let publisher = NatsPublisher::new(config.clone()).await.expect("Failed to create publisher");
+    let msg_id = publisher.publish(create_test_message("nats-msg-1", Some("group-a"))).await.expect("Publish failed");
+    assert_eq!(msg_id, "nats-msg-1");
+
+    let consumer = NatsQueueConsumer::new(config).await.expect("Failed to create consumer");
+    let messages = consumer.poll(10).await.expect("Poll failed");
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].message.id, "nats-msg-1");
+    consumer.extend_visibility(&messages[0].receipt_handle, 30).await.expect("Extend failed");
+    consumer.ack(&messages[0].receipt_handle).await.expect("Ack failed");
+
+    consumer.stop().await;
+}
+
+#[tokio::test]
+async fn test_nack_redelivers_message() {
+    if !is_nats_available().await {
+        eprintln!("Skipping test - NATS not available");
+        return;
+    }
+
+    let config = create_test_config(&unique_name("nack"));
+    let publisher = NatsPublisher::new(config.clone()).await.expect("Failed to create publisher");
+    let messages = vec![
+        create_test_message("nats-msg-1", Some("group-a")),
+        create_test_message("nats-msg-2", Some("group-a")),
+    ];
+    let result = publisher.publish_batch(messages).await.expect("Publish failed");
+    assert_eq!(result.successes.len(), 2);
+
+    let consumer = NatsQueueConsumer::new(config).await.expect("Failed to create consumer");
+    let messages = consumer.poll(10).await.expect("Poll failed");
+    let ids: Vec<_> = messages.iter().map(|m| m.message.id.as_str()).collect();
+    assert_eq!(ids, vec!["nats-msg-1", "nats-msg-2"]);
+
+    let metrics = consumer.get_metrics().await.expect("Metrics failed").unwrap();
+    assert_eq!(metrics.pending_messages, 0);
+    assert_eq!(metrics.in_flight_messages, 2);
+
+    consumer.ack(&messages[1].receipt_handle).await.expect("Ack failed");
+    consumer.nack(&messages[0].receipt_handle, None).await.expect("Nack failed");
+
+    let redelivered = consumer.poll(10).await.expect("Poll failed");
+    assert_eq!(redelivered.len(), 1);
+    assert_eq!(redelivered[0].message.id, "nats-msg-1");
+    consumer.ack(&redelivered[0].receipt_handle).await.expect("Ack failed");
+
+    let metrics = consumer.get_metrics().await.expect("Metrics failed").unwrap();
+    assert_eq!(metrics.total_nacked, 1);
+    assert_eq!(metrics.total_acked, 2);
+
+    consumer.stop().await;
+}
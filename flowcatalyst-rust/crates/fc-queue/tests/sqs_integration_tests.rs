@@ -7,7 +7,7 @@
 //! - Message polling
 //! - Message acknowledgment
 //! - Message rejection (NACK)
-//! - Visibility timeout extension
+//! - Visibility timeout extension, including backlog-aware extension at receive time
 //! - Consumer lifecycle
 
 #![cfg(feature = "sqs")]
@@ -17,7 +17,7 @@ use aws_config::{BehaviorVersion, Region};
 use aws_sdk_sqs::Client;
 
 use fc_common::{Message, MediationType};
-use fc_queue::{QueueConsumer, sqs::SqsQueueConsumer};
+use fc_queue::{QueueConsumer, sqs::{AdaptiveVisibility, SqsQueueConsumer}};
 
 const LOCALSTACK_ENDPOINT: &str = "http://localhost:4566";
 const TEST_QUEUE_NAME: &str = "test-queue";
@@ -58,11 +58,9 @@ fn create_test_message(id: &str) -> Message {
     Message {
         id: id.to_string(),
         pool_code: "DEFAULT".to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: "http://localhost:8080/test".to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
@@ -268,6 +266,41 @@ async fn test_visibility_timeout_extension() {
     assert!(messages2.is_empty());
 }
 
+#[tokio::test]
+async fn test_adaptive_visibility_extends_backlogged_batch() {
+    if !is_localstack_available().await {
+        eprintln!("Skipping test - LocalStack not available");
+        return;
+    }
+
+    let client = create_test_client().await;
+    let queue_url = setup_test_queue(&client).await;
+
+    for i in 0..3 {
+        send_test_message(&client, &queue_url, &create_test_message(&format!("msg-backlog-{}", i))).await;
+    }
+
+    // 3 outstanding x 10s exceeds the 2s base timeout
+    let consumer = SqsQueueConsumer::new(
+        client.clone(),
+        queue_url,
+        TEST_QUEUE_NAME.to_string(),
+        2,
+    ).with_adaptive_visibility(AdaptiveVisibility::new(10));
+
+    let mut received = 0;
+    while received < 3 {
+        let messages = consumer.poll(10).await.expect("Poll failed");
+        assert!(!messages.is_empty(), "expected the remaining messages");
+        received += messages.len();
+    }
+
+    // Past the base timeout, the batch is still invisible
+    tokio::time::sleep(Duration::from_secs(4)).await;
+    let messages = consumer.poll(10).await.expect("Poll failed");
+    assert!(messages.is_empty());
+}
+
 #[tokio::test]
 async fn test_consumer_stop() {
     if !is_localstack_available().await {
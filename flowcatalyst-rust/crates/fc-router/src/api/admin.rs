@@ -0,0 +1,586 @@
+//! Operational endpoints: drain mode, consumers, configuration reload and
+//! circuit breaker controls
+
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use fc_common::ConsumerHealth;
+use tracing::{info, warn, error};
+
+use crate::{CircuitBreakerState, ConfigSnapshot};
+use super::AppState;
+use super::model::{ConfigReloadRequest, FieldViolation, ValidationErrorResponse};
+
+/// Drain mode state
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DrainStatusResponse {
+    /// Whether consumers have stopped polling for new messages
+    pub draining: bool,
+    /// Messages still being processed
+    pub in_flight_messages: usize,
+}
+
+/// Get drain mode state
+#[utoipa::path(
+    get,
+    path = "/admin/drain",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Drain mode state", body = DrainStatusResponse)
+    )
+)]
+pub(super) async fn get_drain_status(State(state): State<AppState>) -> Json<DrainStatusResponse> {
+    Json(drain_status(&state))
+}
+
+/// Start draining: consumers stop polling and readiness reports NOT_READY, while
+/// pools finish in-flight messages. The process keeps running until terminated.
+#[utoipa::path(
+    post,
+    path = "/admin/drain",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Draining", body = DrainStatusResponse)
+    )
+)]
+pub(super) async fn start_drain(State(state): State<AppState>) -> Json<DrainStatusResponse> {
+    state.queue_manager.set_draining(true);
+    Json(drain_status(&state))
+}
+
+/// Stop draining and resume polling
+#[utoipa::path(
+    delete,
+    path = "/admin/drain",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Polling resumed", body = DrainStatusResponse)
+    )
+)]
+pub(super) async fn stop_drain(State(state): State<AppState>) -> Json<DrainStatusResponse> {
+    state.queue_manager.set_draining(false);
+    Json(drain_status(&state))
+}
+
+fn drain_status(state: &AppState) -> DrainStatusResponse {
+    DrainStatusResponse {
+        draining: state.queue_manager.is_draining(),
+        in_flight_messages: state.queue_manager.in_flight_count(),
+    }
+}
+
+/// A queue consumer's state, as listed by `GET /monitoring/consumers`
+#[derive(Serialize, ToSchema)]
+pub struct ConsumerStatusResponse {
+    /// Poll health: running state, last poll and last poll error
+    #[serde(flatten)]
+    pub health: ConsumerHealth,
+    /// Whether the consumer reports its broker connection as healthy
+    pub connection_healthy: bool,
+}
+
+/// List queue consumers
+///
+/// One entry per active consumer, ordered by identifier, with its poll health
+/// (running state, time since the last successful poll, last poll error) and
+/// whether its broker connection is healthy.
+#[utoipa::path(
+    get,
+    path = "/monitoring/consumers",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Queue consumers", body = Vec<ConsumerStatusResponse>)
+    )
+)]
+pub(super) async fn consumers_handler(State(state): State<AppState>) -> Json<Vec<ConsumerStatusResponse>> {
+    let mut ids = state.queue_manager.consumer_ids().await;
+    ids.sort();
+
+    let mut consumers = Vec::with_capacity(ids.len());
+    for id in ids {
+        consumers.push(ConsumerStatusResponse {
+            health: state.health_service.get_consumer_health(&id),
+            connection_healthy: state.queue_manager.is_consumer_healthy(&id).await,
+        });
+    }
+    Json(consumers)
+}
+
+/// Restart a queue consumer
+///
+/// Replaces the consumer's poll tasks with fresh ones, which also recovers a
+/// consumer whose poll has hung. Messages already in flight are unaffected.
+#[utoipa::path(
+    post,
+    path = "/monitoring/consumers/{id}/restart",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "Consumer (queue) identifier")
+    ),
+    responses(
+        (status = 200, description = "Consumer restarted"),
+        (status = 404, description = "Consumer not found"),
+        (status = 503, description = "Router is shutting down")
+    )
+)]
+pub(super) async fn restart_consumer_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    if state.queue_manager.get_consumer(&id).await.is_none() {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Consumer not found" }))).into_response();
+    }
+
+    if state.queue_manager.restart_consumer(&id).await {
+        info!(consumer_id = %id, "Consumer restarted via API");
+        (StatusCode::OK, Json(serde_json::json!({ "status": "success" }))).into_response()
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, Json(serde_json::json!({ "error": "Router is shutting down" }))).into_response()
+    }
+}
+
+// ============================================================================
+// Configuration Management
+// ============================================================================
+
+/// Export the live configuration
+///
+/// Returns `processing_pools` and `queues` as currently in effect, so they can
+/// be fetched, modified and sent back to `POST /config/reload`. Consumers with
+/// no queue config are listed in `unconfigured_consumers`; pools and queues
+/// that are still finishing in-flight work before removal are under `draining`.
+#[utoipa::path(
+    get,
+    path = "/config",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Current router configuration")
+    )
+)]
+pub(super) async fn export_config(State(state): State<AppState>) -> Json<ConfigSnapshot> {
+    Json(state.queue_manager.current_config().await)
+}
+
+/// Reload configuration (hot reload)
+#[utoipa::path(
+    post,
+    path = "/config/reload",
+    tag = "monitoring",
+    request_body = ConfigReloadRequest,
+    responses(
+        (status = 200, description = "Configuration reloaded", body = ConfigReloadResponse),
+        (status = 400, description = "Invalid configuration; nothing was applied", body = ValidationErrorResponse),
+        (status = 503, description = "Service unavailable", body = ConfigReloadResponse),
+        (status = 500, description = "Internal error", body = ConfigReloadResponse)
+    )
+)]
+pub(super) async fn reload_config(
+    State(state): State<AppState>,
+    Json(req): Json<ConfigReloadRequest>,
+) -> Response {
+    use fc_common::RouterConfig;
+
+    // Reject the whole reload rather than applying the valid subset
+    let violations = req.validate(state.queue_manager.max_pools());
+    if !violations.is_empty() {
+        warn!(violations = ?violations, "Rejected invalid configuration reload");
+        return (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+            success: false,
+            error: "Invalid configuration".to_string(),
+            violations,
+        })).into_response();
+    }
+
+    let router_config = RouterConfig {
+        processing_pools: req.pool_configs(),
+        queues: vec![],
+    };
+
+    let pools_before = state.queue_manager.pool_codes().len();
+
+    match state.queue_manager.reload_config(router_config).await {
+        Ok(true) => {
+            let pools_after = state.queue_manager.pool_codes().len();
+            let pool_stats = state.queue_manager.get_pool_stats();
+            let pools_created = pools_after.saturating_sub(pools_before);
+            let pools_removed = pools_before.saturating_sub(pools_after);
+
+            info!(
+                pools_before = pools_before,
+                pools_after = pools_after,
+                pools_created = pools_created,
+                pools_removed = pools_removed,
+                "Configuration reloaded via API"
+            );
+
+            (StatusCode::OK, Json(ConfigReloadResponse {
+                success: true,
+                pools_updated: 0,
+                pools_created,
+                pools_removed,
+                total_active_pools: pool_stats.len(),
+                total_draining_pools: 0,
+            })).into_response()
+        }
+        Ok(false) => {
+            warn!("Configuration reload was skipped (shutdown in progress)");
+            (StatusCode::SERVICE_UNAVAILABLE, Json(ConfigReloadResponse {
+                success: false,
+                pools_updated: 0,
+                pools_created: 0,
+                pools_removed: 0,
+                total_active_pools: 0,
+                total_draining_pools: 0,
+            })).into_response()
+        }
+        Err(e @ crate::RouterError::Config(_)) => {
+            warn!(error = %e, "Rejected invalid configuration");
+            (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+                success: false,
+                error: "Invalid configuration".to_string(),
+                violations: vec![FieldViolation::new("processing_pools", e.to_string())],
+            })).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to reload configuration");
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ConfigReloadResponse {
+                success: false,
+                pools_updated: 0,
+                pools_created: 0,
+                pools_removed: 0,
+                total_active_pools: 0,
+                total_draining_pools: 0,
+            })).into_response()
+        }
+    }
+}
+
+/// Response after config reload
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConfigReloadResponse {
+    /// Whether the reload was successful
+    pub success: bool,
+    /// Number of pools updated
+    pub pools_updated: usize,
+    /// Number of new pools created
+    pub pools_created: usize,
+    /// Number of pools removed (draining)
+    pub pools_removed: usize,
+    /// Total active pools after reload
+    pub total_active_pools: usize,
+    /// Total pools currently draining
+    pub total_draining_pools: usize,
+}
+
+// ============================================================================
+// Circuit Breaker Endpoints
+// ============================================================================
+
+/// Circuit breaker state response
+#[derive(Serialize, ToSchema)]
+pub(super) struct CircuitBreakerStateResponse {
+    name: String,
+    state: String,
+}
+
+/// Get circuit breaker state
+#[utoipa::path(
+    get,
+    path = "/monitoring/circuit-breakers/{name}/state",
+    tag = "circuit-breakers",
+    params(
+        ("name" = String, Path, description = "Circuit breaker name (URL-encoded)")
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker state", body = CircuitBreakerStateResponse),
+        (status = 404, description = "Circuit breaker not found")
+    )
+)]
+pub(super) async fn get_circuit_breaker_state(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    // URL decode the name
+    let decoded_name = urlencoding::decode(&name).unwrap_or(std::borrow::Cow::Borrowed(&name));
+
+    match state.circuit_breaker_registry.get_state(&decoded_name) {
+        Some(breaker_state) => {
+            let state_str = match breaker_state {
+                CircuitBreakerState::Closed => "CLOSED",
+                CircuitBreakerState::Open => "OPEN",
+                CircuitBreakerState::HalfOpen => "HALF_OPEN",
+            };
+            (StatusCode::OK, Json(CircuitBreakerStateResponse {
+                name: decoded_name.to_string(),
+                state: state_str.to_string(),
+            })).into_response()
+        }
+        None => {
+            (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Circuit breaker not found" }))).into_response()
+        }
+    }
+}
+
+/// Reset a circuit breaker: close it immediately and clear its counters
+#[utoipa::path(
+    post,
+    path = "/monitoring/circuit-breakers/{name}/reset",
+    tag = "circuit-breakers",
+    params(
+        ("name" = String, Path, description = "Circuit breaker name (URL-encoded)")
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker reset"),
+        (status = 404, description = "Circuit breaker not found")
+    )
+)]
+pub(super) async fn reset_circuit_breaker(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    let decoded_name = urlencoding::decode(&name).unwrap_or(std::borrow::Cow::Borrowed(&name));
+
+    if state.circuit_breaker_registry.reset(&decoded_name) {
+        info!(name = %decoded_name, "Circuit breaker reset");
+        (StatusCode::OK, Json(serde_json::json!({ "status": "success" }))).into_response()
+    } else {
+        (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Circuit breaker not found" }))).into_response()
+    }
+}
+
+/// Trip a circuit breaker open (for testing); it half-opens after the usual reset timeout
+#[utoipa::path(
+    post,
+    path = "/monitoring/circuit-breakers/{name}/open",
+    tag = "circuit-breakers",
+    params(
+        ("name" = String, Path, description = "Circuit breaker name (URL-encoded)")
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker opened"),
+        (status = 404, description = "Circuit breaker not found")
+    )
+)]
+pub(super) async fn open_circuit_breaker(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    let decoded_name = urlencoding::decode(&name).unwrap_or(std::borrow::Cow::Borrowed(&name));
+
+    if state.circuit_breaker_registry.force_open(&decoded_name) {
+        warn!(name = %decoded_name, "Circuit breaker manually opened");
+        (StatusCode::OK, Json(serde_json::json!({ "status": "success" }))).into_response()
+    } else {
+        (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Circuit breaker not found" }))).into_response()
+    }
+}
+
+/// Reset all circuit breakers
+#[utoipa::path(
+    post,
+    path = "/monitoring/circuit-breakers/reset-all",
+    tag = "circuit-breakers",
+    responses(
+        (status = 200, description = "All circuit breakers reset")
+    )
+)]
+pub(super) async fn reset_all_circuit_breakers(State(state): State<AppState>) -> Json<serde_json::Value> {
+    state.circuit_breaker_registry.reset_all();
+    info!("All circuit breakers reset");
+    Json(serde_json::json!({ "status": "success" }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fc_common::{RateLimitScope, WarningSeverity};
+    use crate::api::model::PoolConfigRequest;
+    use crate::api::readiness_probe;
+    use crate::api::tests::create_test_state;
+
+    #[tokio::test]
+    async fn test_reset_closes_open_circuit_breaker() {
+        let state = create_test_state().await;
+        let registry = &state.circuit_breaker_registry;
+        for _ in 0..10 {
+            registry.record_failure("down.example.com");
+        }
+        assert_eq!(registry.get_state("down.example.com"), Some(CircuitBreakerState::Open));
+
+        let response = reset_circuit_breaker(State(state.clone()), Path("down.example.com".to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(registry.get_state("down.example.com"), Some(CircuitBreakerState::Closed));
+        assert!(registry.allow_request("down.example.com"));
+
+        let stats = registry.get_stats("down.example.com").unwrap();
+        assert_eq!(stats.failed_calls, 0);
+        assert_eq!(stats.buffered_calls, 0);
+
+        let response = reset_circuit_breaker(State(state), Path("unknown.example.com".to_string())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_open_trips_closed_circuit_breaker() {
+        let state = create_test_state().await;
+        let registry = &state.circuit_breaker_registry;
+        registry.record_success("api.example.com:8443");
+
+        let response = open_circuit_breaker(State(state.clone()), Path("api.example.com%3A8443".to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(registry.get_state("api.example.com:8443"), Some(CircuitBreakerState::Open));
+        assert!(!registry.allow_request("api.example.com:8443"));
+
+        let response = open_circuit_breaker(State(state), Path("unknown.example.com".to_string())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_export_config_lists_active_and_draining() {
+        let state = create_test_state().await;
+
+        let Json(snapshot) = export_config(State(state.clone())).await;
+        assert_eq!(snapshot.config.processing_pools.len(), 1);
+        assert_eq!(snapshot.config.processing_pools[0].code, "POOL-A");
+        assert_eq!(snapshot.config.processing_pools[0].concurrency, 4);
+        assert_eq!(snapshot.unconfigured_consumers, vec!["stub-queue".to_string()]);
+        assert!(snapshot.draining.processing_pools.is_empty());
+
+        // The export can be applied back as a reload request
+        let body = serde_json::to_value(&snapshot).unwrap();
+        let reload: ConfigReloadRequest = serde_json::from_value(body).unwrap();
+        assert_eq!(reload.processing_pools[0].code, "POOL-A");
+        assert_eq!(reload.processing_pools[0].rate_limit_per_minute, Some(60));
+
+        state.queue_manager.drain_pool("POOL-A").await.unwrap();
+        let Json(snapshot) = export_config(State(state)).await;
+        assert!(snapshot.config.processing_pools.is_empty());
+        assert_eq!(snapshot.draining.processing_pools.len(), 1);
+        assert_eq!(snapshot.draining.processing_pools[0].code, "POOL-A");
+        assert_eq!(snapshot.draining.processing_pools[0].concurrency, 4);
+    }
+
+    #[tokio::test]
+    async fn test_export_config_lists_pool_created_by_update() {
+        let state = create_test_state().await;
+        let req = serde_json::from_value(serde_json::json!({ "concurrency": 3 })).unwrap();
+        let response = crate::api::pools::update_pool_config(State(state.clone()), Path("POOL-B".to_string()), Json(req)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let Json(snapshot) = export_config(State(state)).await;
+        let codes: Vec<&str> = snapshot.config.processing_pools.iter().map(|p| p.code.as_str()).collect();
+        assert_eq!(codes, vec!["POOL-A", "POOL-B"]);
+        assert_eq!(snapshot.config.processing_pools[1].concurrency, 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_consumers() {
+        let state = create_test_state().await;
+        state.health_service.record_consumer_error("stub-queue", "connection reset");
+
+        let Json(consumers) = consumers_handler(State(state.clone())).await;
+        assert_eq!(consumers.len(), 1);
+        assert_eq!(consumers[0].health.queue_identifier, "stub-queue");
+        assert!(consumers[0].health.is_running);
+        assert!(consumers[0].health.last_poll_time_ms.is_some());
+
+        let body = serde_json::to_value(&consumers[0]).unwrap();
+        assert_eq!(body["queue_identifier"], "stub-queue");
+        assert_eq!(body["last_error"], "connection reset");
+        assert_eq!(body["connection_healthy"], true);
+    }
+
+    #[tokio::test]
+    async fn test_restart_consumer() {
+        let state = create_test_state().await;
+        let restart = |id: &str| restart_consumer_handler(State(state.clone()), Path(id.to_string()));
+
+        // Known but not yet polling: nothing to replace
+        assert_eq!(restart("stub-queue").await.status(), StatusCode::OK);
+        assert_eq!(restart("missing").await.status(), StatusCode::NOT_FOUND);
+
+        state.queue_manager.shutdown().await;
+        assert_eq!(restart("stub-queue").await.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    fn pool_request(code: &str, concurrency: u32) -> PoolConfigRequest {
+        PoolConfigRequest {
+            code: code.to_string(),
+            concurrency,
+            rate_limit_per_minute: None,
+            rate_limit_scope: RateLimitScope::Pool,
+            rate_limit_burst: None,
+            ack_on_4xx: true,
+        }
+    }
+
+    #[test]
+    fn test_config_reload_validation_reports_each_pool() {
+        let req = ConfigReloadRequest {
+            processing_pools: vec![
+                pool_request("A", 4),
+                pool_request("B", 0),
+                pool_request("A", 2),
+                PoolConfigRequest { rate_limit_per_minute: Some(0), rate_limit_burst: Some(0), ..pool_request("C", 1) },
+                pool_request(" ", 1),
+            ],
+        };
+
+        let fields: Vec<String> = req.validate(10).into_iter().map(|v| v.field).collect();
+        assert_eq!(fields, vec![
+            "processing_pools[1].concurrency",
+            "processing_pools[2].code",
+            "processing_pools[3].rate_limit_per_minute",
+            "processing_pools[3].rate_limit_burst",
+            "processing_pools[4].code",
+        ]);
+
+        let too_many = ConfigReloadRequest { processing_pools: vec![pool_request("A", 1), pool_request("B", 1)] };
+        let violations = too_many.validate(1);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "processing_pools");
+        assert!(too_many.validate(2).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_reload_applies_nothing() {
+        let state = create_test_state().await;
+
+        let req = ConfigReloadRequest {
+            processing_pools: vec![pool_request("POOL-B", 2), pool_request("POOL-C", 0)],
+        };
+        let response = reload_config(State(state.clone()), Json(req)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["violations"][0]["field"], "processing_pools[1].concurrency");
+
+        // The valid pool wasn't created and the existing one wasn't drained
+        assert_eq!(state.queue_manager.pool_codes(), vec!["POOL-A".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_drain_mode_fails_readiness() {
+        let state = create_test_state().await;
+        state.warning_service.acknowledge_matching(|w| w.severity == WarningSeverity::Critical);
+
+        let Json(status) = start_drain(State(state.clone())).await;
+        assert!(status.draining);
+        assert_eq!(status.in_flight_messages, 0);
+        assert!(get_drain_status(State(state.clone())).await.0.draining);
+
+        let response = readiness_probe(State(state.clone())).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let Json(status) = stop_drain(State(state.clone())).await;
+        assert!(!status.draining);
+        let response = readiness_probe(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
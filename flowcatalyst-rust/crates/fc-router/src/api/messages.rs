@@ -0,0 +1,1076 @@
+//! Message publishing, queue peeking and in-flight message endpoints
+
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::Ordering;
+use utoipa::ToSchema;
+use fc_common::{logging, Message};
+use fc_queue::{BatchPublishResult, BodyFormat, PeekedMessage, QueueError};
+use fc_queue::codec::encode_body;
+use tracing::{info_span, warn, error, Instrument, Span};
+
+use crate::{CancelOutcome, InFlightMessageInfo, CORRELATION_ID_HEADER};
+use super::{AppState, SimpleState};
+use super::model::{
+    is_valid_correlation_id, MAX_CORRELATION_ID_LENGTH,
+    PublishMessageRequest, PublishMessageResponse, BatchPublishItemResult, BatchPublishResponse,
+    MessageLimitsResponse, FieldViolation, ValidationErrorResponse,
+};
+
+/// Default cap on messages per `POST /messages/batch` request
+pub const DEFAULT_MAX_PUBLISH_BATCH_SIZE: usize = 256;
+
+/// Default cap on a published message's encoded body, matching the SQS message size limit
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 256 * 1024;
+
+/// `Retry-After` sent when a publish is rejected because its pool is saturated.
+/// Matches the delay the router uses when deferring messages from a full pool.
+const SATURATED_RETRY_AFTER_SECONDS: u64 = 5;
+
+/// `Retry-After` sent when a publish is rejected because the router as a whole
+/// is under pressure
+const PRESSURE_RETRY_AFTER_SECONDS: u64 = 10;
+
+/// Query params for peeking at a queue
+#[derive(Deserialize, Default, ToSchema)]
+pub struct PeekQuery {
+    /// Maximum number of messages to return (default 20, at most 100)
+    pub limit: Option<u32>,
+}
+
+/// Default number of messages returned by `GET /monitoring/queues/{queue}/peek`
+pub const DEFAULT_PEEK_LIMIT: u32 = 20;
+
+/// Most messages returned by `GET /monitoring/queues/{queue}/peek`
+pub const MAX_PEEK_LIMIT: u32 = 100;
+
+/// Peek at the messages in a queue without consuming them
+///
+/// Lists messages in delivery order, including in-flight ones, without
+/// changing their visibility. Only queues that can be inspected this way
+/// (the embedded SQLite queue) support it.
+#[utoipa::path(
+    get,
+    path = "/monitoring/queues/{queue}/peek",
+    tag = "monitoring",
+    params(
+        ("queue" = String, Path, description = "Queue (consumer) identifier"),
+        ("limit" = Option<u32>, Query, description = "Maximum number of messages to return (default 20, at most 100)")
+    ),
+    responses(
+        (status = 200, description = "Queued messages, oldest first", body = Vec<PeekedMessage>),
+        (status = 400, description = "Invalid limit", body = ValidationErrorResponse),
+        (status = 404, description = "Queue not found"),
+        (status = 501, description = "Queue does not support peeking"),
+        (status = 500, description = "Failed to read the queue")
+    )
+)]
+pub(super) async fn peek_queue_handler(
+    State(state): State<AppState>,
+    Path(queue): Path<String>,
+    Query(query): Query<PeekQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(DEFAULT_PEEK_LIMIT);
+    if !(1..=MAX_PEEK_LIMIT).contains(&limit) {
+        return (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+            success: false,
+            error: "Invalid peek query".to_string(),
+            violations: vec![FieldViolation::new(
+                "limit",
+                format!("must be between 1 and {} (got {})", MAX_PEEK_LIMIT, limit),
+            )],
+        })).into_response();
+    }
+
+    let Some(consumer) = state.queue_manager.get_consumer(&queue).await else {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Queue not found" }))).into_response();
+    };
+    let Some(peekable) = consumer.as_peekable() else {
+        return (StatusCode::NOT_IMPLEMENTED, Json(serde_json::json!({
+            "error": format!("Queue {} does not support peeking", queue)
+        }))).into_response();
+    };
+
+    match peekable.peek(limit).await {
+        Ok(messages) => (StatusCode::OK, Json(messages)).into_response(),
+        Err(e) => {
+            error!(queue = %queue, error = %e, "Failed to peek queue");
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": "Failed to read the queue" }))).into_response()
+        }
+    }
+}
+
+/// Query params for in-flight messages
+#[derive(Deserialize, Default, ToSchema)]
+pub(super) struct InFlightMessagesQuery {
+    /// Page size (default 100, at most the configured maximum)
+    limit: Option<usize>,
+    /// Substring of the message ID (at most 256 characters)
+    #[serde(rename = "messageId")]
+    message_id: Option<String>,
+}
+
+/// Default page size for the in-flight messages endpoint
+pub const DEFAULT_IN_FLIGHT_PAGE_SIZE: usize = 100;
+
+/// Default cap on the in-flight messages page size
+pub const DEFAULT_MAX_IN_FLIGHT_PAGE_SIZE: usize = 1000;
+
+/// Longest `messageId` filter accepted by the in-flight messages endpoint
+const MAX_MESSAGE_ID_FILTER_LEN: usize = 256;
+
+/// In-flight messages, oldest first, with the total in flight
+#[derive(Serialize, ToSchema)]
+pub(super) struct InFlightMessagesPage {
+    messages: Vec<InFlightMessageInfo>,
+    /// All messages in flight, regardless of limit and filter
+    total: usize,
+}
+
+/// In-flight messages endpoint for dashboard
+#[utoipa::path(
+    get,
+    path = "/monitoring/in-flight-messages",
+    tag = "monitoring",
+    params(
+        ("limit" = Option<usize>, Query, description = "Maximum number of messages to return (default 100, capped at the configured maximum)"),
+        ("messageId" = Option<String>, Query, description = "Filter by message ID substring")
+    ),
+    responses(
+        (status = 200, description = "In-flight messages", body = InFlightMessagesPage),
+        (status = 400, description = "Invalid query", body = ValidationErrorResponse)
+    )
+)]
+pub(super) async fn dashboard_in_flight_messages_handler(
+    State(state): State<AppState>,
+    Query(query): Query<InFlightMessagesQuery>,
+) -> Response {
+    if let Some(filter) = query.message_id.as_deref().filter(|f| f.len() > MAX_MESSAGE_ID_FILTER_LEN) {
+        let violations = vec![FieldViolation::new(
+            "messageId",
+            format!("must be at most {} characters (got {})", MAX_MESSAGE_ID_FILTER_LEN, filter.len()),
+        )];
+        warn!(violations = ?violations, "Rejected invalid in-flight messages query");
+        return (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+            success: false,
+            error: "Invalid in-flight messages query".to_string(),
+            violations,
+        })).into_response();
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_IN_FLIGHT_PAGE_SIZE).min(state.max_in_flight_page_size);
+    let messages = state.queue_manager.get_in_flight_messages(limit, query.message_id.as_deref());
+    Json(InFlightMessagesPage {
+        messages,
+        total: state.queue_manager.in_flight_count(),
+    }).into_response()
+}
+
+/// Eject an in-flight message: NACK it for redelivery and stop tracking it
+#[utoipa::path(
+    post,
+    path = "/monitoring/in-flight-messages/{message_id}/cancel",
+    tag = "monitoring",
+    params(
+        ("message_id" = String, Path, description = "Application message ID")
+    ),
+    responses(
+        (status = 200, description = "Message NACKed and removed from the pipeline"),
+        (status = 404, description = "Message not in flight"),
+        (status = 409, description = "Message already completed")
+    )
+)]
+pub(super) async fn cancel_in_flight_message(
+    State(state): State<AppState>,
+    Path(message_id): Path<String>,
+) -> Response {
+    match state.queue_manager.cancel_in_flight(&message_id).await {
+        CancelOutcome::Cancelled => {
+            (StatusCode::OK, Json(serde_json::json!({ "messageId": message_id, "cancelled": true }))).into_response()
+        }
+        CancelOutcome::AlreadyCompleted => {
+            (StatusCode::CONFLICT, Json(serde_json::json!({ "error": "Message already completed" }))).into_response()
+        }
+        CancelOutcome::NotFound => {
+            (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Message not in flight" }))).into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Message Publishing
+// ============================================================================
+
+/// Correlation ID from the `X-Correlation-Id` request header, if any
+fn correlation_id_header(headers: &HeaderMap) -> Result<Option<String>, FieldViolation> {
+    let Some(value) = headers.get(CORRELATION_ID_HEADER) else {
+        return Ok(None);
+    };
+    match value.to_str() {
+        Ok(id) if id.len() <= MAX_CORRELATION_ID_LENGTH && is_valid_correlation_id(id) => Ok(Some(id.to_string())),
+        _ => Err(FieldViolation::new(
+            CORRELATION_ID_HEADER,
+            format!("must be 1 to {} printable ASCII characters", MAX_CORRELATION_ID_LENGTH),
+        )),
+    }
+}
+
+/// Span around handing a message to the queue publisher; its trace context
+/// travels in `Message::traceparent`
+fn publish_span(message: &Message) -> Span {
+    info_span!(
+        "publish",
+        message_id = %message.id,
+        pool_code = %message.pool_code,
+        correlation_id = message.correlation_id.as_deref(),
+    )
+}
+
+/// Publish a message
+///
+/// A correlation ID, from the body's `correlation_id` or the `X-Correlation-Id`
+/// header, is attached to the message's log spans and forwarded to the
+/// mediation target as `X-Correlation-Id`.
+#[utoipa::path(
+    post,
+    path = "/messages",
+    tag = "messages",
+    request_body = PublishMessageRequest,
+    responses(
+        (status = 200, description = "Message published", body = PublishMessageResponse),
+        (status = 400, description = "Invalid message, or the message could not be serialized", body = ValidationErrorResponse),
+        (status = 413, description = "Encoded message exceeds the maximum size (see `GET /messages/limits`)"),
+        (status = 429, description = "Target pool is saturated or rate limited; retry after the `Retry-After` delay"),
+        (status = 503, description = "Router is under pressure; retry after the `Retry-After` delay"),
+        (status = 500, description = "Failed to publish")
+    )
+)]
+pub(super) async fn publish_message(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut req): Json<PublishMessageRequest>,
+) -> Response {
+    let correlation_id = match correlation_id_header(&headers) {
+        Ok(correlation_id) => correlation_id,
+        Err(violation) => return invalid_message_response(vec![violation]),
+    };
+    req.correlation_id = req.correlation_id.or(correlation_id);
+
+    let violations = req.validate();
+    if !violations.is_empty() {
+        return invalid_message_response(violations);
+    }
+
+    let mut message = req.into_message();
+    let message_id = message.id.clone();
+    let span = publish_span(&message);
+    message.traceparent = logging::traceparent(&span);
+
+    if let Some(response) = message_too_large_response(&message, None, state.max_message_bytes, state.body_format) {
+        return response;
+    }
+    if let Some(response) = pressure_response(&state) {
+        return response;
+    }
+    if let Some(response) = pool_admission_response(&state, [message.pool_code.as_str()]) {
+        return response;
+    }
+
+    match state.publisher.publish(message).instrument(span).await {
+        Ok(_) => {
+            state.messages_published.fetch_add(1, Ordering::Relaxed);
+            (StatusCode::OK, Json(PublishMessageResponse {
+                message_id,
+                status: "ACCEPTED".to_string(),
+            })).into_response()
+        }
+        Err(e) => publish_error_response(&message_id, e),
+    }
+}
+
+/// Publish a batch of messages
+///
+/// Each message gets its own ID and status, so a partially failed batch reports
+/// which entries were accepted and can be retried selectively.
+#[utoipa::path(
+    post,
+    path = "/messages/batch",
+    tag = "messages",
+    request_body = Vec<PublishMessageRequest>,
+    responses(
+        (status = 200, description = "Batch processed; see per-message status", body = BatchPublishResponse),
+        (status = 400, description = "Invalid message in batch, or a message could not be serialized; nothing was published", body = ValidationErrorResponse),
+        (status = 413, description = "Batch exceeds the maximum size, or an encoded message does; nothing was published"),
+        (status = 429, description = "A target pool is saturated or rate limited; nothing was published"),
+        (status = 503, description = "Router is under pressure; nothing was published"),
+        (status = 500, description = "Failed to publish batch", body = BatchPublishResponse)
+    )
+)]
+pub(super) async fn publish_message_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut requests): Json<Vec<PublishMessageRequest>>,
+) -> Response {
+    if requests.len() > state.max_publish_batch_size {
+        return (StatusCode::PAYLOAD_TOO_LARGE, Json(serde_json::json!({
+            "error": format!(
+                "Batch of {} messages exceeds the maximum of {}",
+                requests.len(),
+                state.max_publish_batch_size
+            )
+        }))).into_response();
+    }
+
+    // The header applies to every message that doesn't set its own
+    let correlation_id = match correlation_id_header(&headers) {
+        Ok(correlation_id) => correlation_id,
+        Err(violation) => return invalid_message_response(vec![violation]),
+    };
+    if let Some(correlation_id) = correlation_id {
+        for req in requests.iter_mut().filter(|req| req.correlation_id.is_none()) {
+            req.correlation_id = Some(correlation_id.clone());
+        }
+    }
+
+    // Field names are prefixed with the message's index, e.g. `[3].message_id`
+    let violations: Vec<FieldViolation> = requests.iter()
+        .enumerate()
+        .flat_map(|(index, req)| {
+            req.validate().into_iter().map(move |v| FieldViolation {
+                field: format!("[{}].{}", index, v.field),
+                message: v.message,
+            })
+        })
+        .collect();
+    if !violations.is_empty() {
+        return invalid_message_response(violations);
+    }
+
+    let span = info_span!("publish_batch", batch_size = requests.len());
+    let traceparent = logging::traceparent(&span);
+    let messages: Vec<Message> = requests.into_iter()
+        .map(|req| Message { traceparent: traceparent.clone(), ..req.into_message() })
+        .collect();
+    let message_ids: Vec<String> = messages.iter().map(|m| m.id.clone()).collect();
+
+    for (index, message) in messages.iter().enumerate() {
+        if let Some(response) = message_too_large_response(message, Some(index), state.max_message_bytes, state.body_format) {
+            return response;
+        }
+    }
+
+    if let Some(response) = pressure_response(&state) {
+        return response;
+    }
+    if let Some(response) = pool_admission_response(&state, messages.iter().map(|m| m.pool_code.as_str())) {
+        return response;
+    }
+
+    let (status_code, outcome) = match state.publisher.publish_batch(messages).instrument(span).await {
+        Ok(outcome) => (StatusCode::OK, outcome),
+        Err(e) => {
+            error!(error = %e, count = message_ids.len(), "Failed to publish message batch");
+            // Serialization failures come from the messages themselves
+            let status_code = match e {
+                QueueError::Serialization(_) => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            let mut outcome = BatchPublishResult::default();
+            for index in 0..message_ids.len() {
+                outcome.fail(index, e.to_string());
+            }
+            (status_code, outcome)
+        }
+    };
+
+    let results: Vec<BatchPublishItemResult> = message_ids.into_iter()
+        .enumerate()
+        .map(|(index, message_id)| {
+            let error = outcome.failure(index).map(|f| f.error.clone());
+            let status = if error.is_none() { "ACCEPTED" } else { "FAILED" };
+            BatchPublishItemResult { index, message_id, status: status.to_string(), error }
+        })
+        .collect();
+    let accepted = results.iter().filter(|r| r.status == "ACCEPTED").count();
+    state.messages_published.fetch_add(accepted as u64, Ordering::Relaxed);
+
+    (status_code, Json(BatchPublishResponse {
+        accepted,
+        failed: results.len() - accepted,
+        results,
+    })).into_response()
+}
+
+/// 429 with `Retry-After` if admission control is enabled and any target pool is
+/// at capacity or waiting on its rate limit. For a rate-limited pool the delay is
+/// the time until the pool's next permit.
+fn pool_admission_response<'a>(
+    state: &AppState,
+    pool_codes: impl IntoIterator<Item = &'a str>,
+) -> Option<Response> {
+    if !state.reject_publish_when_saturated {
+        return None;
+    }
+
+    let (pool_code, retry_after, error) = pool_codes.into_iter().find_map(|code| {
+        if state.queue_manager.is_pool_saturated(code) {
+            return Some((code, SATURATED_RETRY_AFTER_SECONDS, format!("Pool {} is at capacity", code)));
+        }
+        state.queue_manager.pool_seconds_until_capacity(code)
+            .map(|seconds| (code, seconds, format!("Pool {} is rate limited", code)))
+    })?;
+
+    warn!(pool_code = %pool_code, retry_after, "Rejected publish to saturated or rate-limited pool");
+    Some((
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after.to_string())],
+        Json(serde_json::json!({ "error": error })),
+    ).into_response())
+}
+
+/// 503 with `Retry-After` if a pressure threshold is configured and the share of
+/// buffered capacity in use across all pools is above it
+fn pressure_response(state: &AppState) -> Option<Response> {
+    let threshold = state.publish_pressure_threshold?;
+    let pressure = state.queue_manager.system_pressure();
+    if pressure <= threshold {
+        return None;
+    }
+
+    warn!(pressure, threshold, "Rejected publish while router is under pressure");
+    Some((
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::RETRY_AFTER, PRESSURE_RETRY_AFTER_SECONDS.to_string())],
+        Json(serde_json::json!({
+            "error": format!("Router is under pressure ({:.0}% of capacity in use)", pressure * 100.0)
+        })),
+    ).into_response())
+}
+
+/// 400 for a message that could not be serialized, 500 for other publish failures
+fn publish_error_response(message_id: &str, error: QueueError) -> Response {
+    match error {
+        QueueError::Serialization(e) => {
+            warn!(message_id = %message_id, error = %e, "Rejected message that could not be serialized");
+            (StatusCode::BAD_REQUEST, Json(serde_json::json!({
+                "error": format!("Message could not be serialized: {}", e)
+            }))).into_response()
+        }
+        _ => {
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": "Failed to publish message" }))).into_response()
+        }
+    }
+}
+
+/// Limits enforced by the publish endpoints
+#[utoipa::path(
+    get,
+    path = "/messages/limits",
+    tag = "messages",
+    responses(
+        (status = 200, description = "Publish limits", body = MessageLimitsResponse)
+    )
+)]
+pub(super) async fn message_limits(State(state): State<AppState>) -> Json<MessageLimitsResponse> {
+    Json(MessageLimitsResponse {
+        max_message_bytes: state.max_message_bytes,
+        max_batch_size: state.max_publish_batch_size,
+    })
+}
+
+/// 413 response if the message, encoded as the publisher will send it, is
+/// over the limit. `index` is the message's position when it is part of a batch.
+///
+/// A message that can't be encoded can't be sized either, so it gets the
+/// publish error response rather than passing the check.
+fn message_too_large_response(
+    message: &Message,
+    index: Option<usize>,
+    max_message_bytes: usize,
+    body_format: BodyFormat,
+) -> Option<Response> {
+    let size = match encode_body(message, body_format) {
+        Ok(body) => body.len(),
+        Err(e) => return Some(publish_error_response(&message.id, e)),
+    };
+    if size <= max_message_bytes {
+        return None;
+    }
+
+    let subject = match index {
+        Some(index) => format!("Message [{}]", index),
+        None => "Message".to_string(),
+    };
+    warn!(size, max_message_bytes, index = ?index, "Rejected oversized publish request");
+    Some((StatusCode::PAYLOAD_TOO_LARGE, Json(serde_json::json!({
+        "error": format!("{} of {} encoded bytes exceeds the maximum of {} bytes", subject, size, max_message_bytes),
+        "max_message_bytes": max_message_bytes,
+    }))).into_response())
+}
+
+fn invalid_message_response(violations: Vec<FieldViolation>) -> Response {
+    warn!(violations = ?violations, "Rejected invalid publish request");
+    (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+        success: false,
+        error: "Invalid message".to_string(),
+        violations,
+    })).into_response()
+}
+
+/// Simple publish message (for simple router)
+pub(super) async fn simple_publish_message(
+    State(state): State<SimpleState>,
+    headers: HeaderMap,
+    Json(mut req): Json<PublishMessageRequest>,
+) -> Response {
+    let correlation_id = match correlation_id_header(&headers) {
+        Ok(correlation_id) => correlation_id,
+        Err(violation) => return invalid_message_response(vec![violation]),
+    };
+    req.correlation_id = req.correlation_id.or(correlation_id);
+
+    let violations = req.validate();
+    if !violations.is_empty() {
+        return invalid_message_response(violations);
+    }
+
+    let mut message = req.into_message();
+    let message_id = message.id.clone();
+    let span = publish_span(&message);
+    message.traceparent = logging::traceparent(&span);
+
+    if let Some(response) = message_too_large_response(&message, None, state.max_message_bytes, state.body_format) {
+        return response;
+    }
+
+    match state.publisher.publish(message).instrument(span).await {
+        Ok(_) => {
+            (StatusCode::OK, Json(PublishMessageResponse {
+                message_id,
+                status: "ACCEPTED".to_string(),
+            })).into_response()
+        }
+        Err(e) => publish_error_response(&message_id, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use std::sync::Arc;
+    use fc_common::{MediationType, PoolConfig, DEFAULT_POOL_CODE};
+    use fc_queue::QueuePublisher;
+    use crate::QueueManager;
+    use crate::api::model::MAX_MESSAGE_ID_LENGTH;
+    use crate::api::tests::{create_test_state, json_body, NoopMediator, StubQueue};
+
+    #[test]
+    fn test_publish_request_mediation_type() {
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({
+            "payload": {}
+        })).unwrap();
+        assert_eq!(req.mediation_type, MediationType::HTTP);
+
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({
+            "payload": {},
+            "mediation_type": "GRPC"
+        })).unwrap();
+        assert_eq!(req.mediation_type, MediationType::GRPC);
+    }
+
+    #[test]
+    fn test_publish_request_defaults_to_shared_default_pool() {
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({
+            "payload": {}
+        })).unwrap();
+        assert_eq!(req.into_message().pool_code, DEFAULT_POOL_CODE);
+    }
+
+    /// Publisher that rejects messages for pool REJECT and accepts the rest
+    struct PartialQueue;
+
+    #[async_trait::async_trait]
+    impl QueuePublisher for PartialQueue {
+        fn identifier(&self) -> &str {
+            "partial-queue"
+        }
+
+        async fn publish(&self, message: Message) -> fc_queue::Result<String> {
+            Ok(message.id)
+        }
+
+        async fn publish_batch(&self, messages: Vec<Message>) -> fc_queue::Result<BatchPublishResult> {
+            let mut result = BatchPublishResult::default();
+            for (index, message) in messages.into_iter().enumerate() {
+                if message.pool_code == "REJECT" {
+                    result.fail(index, "InvalidParameterValue: rejected");
+                } else {
+                    result.successes.push(message.id);
+                }
+            }
+            Ok(result)
+        }
+    }
+
+    /// Publisher that fails every publish, with a serialization or queue error
+    struct FailingQueue {
+        serialization: bool,
+    }
+
+    impl FailingQueue {
+        fn error(&self) -> QueueError {
+            if self.serialization {
+                QueueError::Serialization("MessagePack encoding failed".to_string())
+            } else {
+                QueueError::Sqs("connection refused".to_string())
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl QueuePublisher for FailingQueue {
+        fn identifier(&self) -> &str {
+            "failing-queue"
+        }
+
+        async fn publish(&self, _message: Message) -> fc_queue::Result<String> {
+            Err(self.error())
+        }
+
+        async fn publish_batch(&self, _messages: Vec<Message>) -> fc_queue::Result<BatchPublishResult> {
+            Err(self.error())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_serialization_failure_returns_400() {
+        let mut state = create_test_state().await;
+        state.publisher = Arc::new(FailingQueue { serialization: true });
+
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({ "payload": {} })).unwrap();
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(req)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = publish_message_batch(State(state), HeaderMap::new(), Json(batch_request(&["POOL-A"]))).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_publish_queue_failure_returns_500() {
+        let mut state = create_test_state().await;
+        state.publisher = Arc::new(FailingQueue { serialization: false });
+
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({ "payload": {} })).unwrap();
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(req)).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let response = publish_message_batch(State(state), HeaderMap::new(), Json(batch_request(&["POOL-A"]))).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    fn batch_request(pool_codes: &[&str]) -> Vec<PublishMessageRequest> {
+        pool_codes.iter()
+            .map(|code| serde_json::from_value(serde_json::json!({
+                "payload": {},
+                "pool_code": code
+            })).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_reports_per_message_status() {
+        let mut state = create_test_state().await;
+        state.publisher = Arc::new(PartialQueue);
+
+        let requests = batch_request(&["POOL-A", "REJECT", "POOL-A"]);
+        let response = publish_message_batch(State(state.clone()), HeaderMap::new(), Json(requests)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["accepted"], 2);
+        assert_eq!(body["failed"], 1);
+        let statuses: Vec<&str> = body["results"].as_array().unwrap().iter()
+            .map(|r| r["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["ACCEPTED", "FAILED", "ACCEPTED"]);
+        assert_eq!(body["results"][1]["index"], 1);
+        assert_eq!(body["results"][1]["error"], "InvalidParameterValue: rejected");
+        assert!(body["results"][0].get("error").is_none());
+        assert_ne!(body["results"][0]["message_id"], body["results"][2]["message_id"]);
+
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_over_limit_rejected() {
+        let mut state = create_test_state().await;
+        state.max_publish_batch_size = 2;
+
+        let requests = batch_request(&["POOL-A", "POOL-A", "POOL-A"]);
+        let response = publish_message_batch(State(state.clone()), HeaderMap::new(), Json(requests)).await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_message_rejected() {
+        let mut state = create_test_state().await;
+        state.max_message_bytes = 1024;
+
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({
+            "payload": {"data": "x".repeat(2048)}
+        })).unwrap();
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(req)).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let mut requests = batch_request(&["POOL-A", "POOL-A"]);
+        requests[1].payload = serde_json::json!({"data": "x".repeat(2048)});
+        let response = publish_message_batch(State(state.clone()), HeaderMap::new(), Json(requests)).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["error"].as_str().unwrap().starts_with("Message [1] of"));
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+
+        let Json(limits) = message_limits(State(state)).await;
+        assert_eq!(limits.max_message_bytes, 1024);
+        assert_eq!(limits.max_batch_size, DEFAULT_MAX_PUBLISH_BATCH_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_publish_uses_client_message_id() {
+        let state = create_test_state().await;
+        let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({
+            "payload": {},
+            "message_id": "order-42-created"
+        })).unwrap();
+
+        let response = publish_message(State(state), HeaderMap::new(), Json(req)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["message_id"], "order-42-created");
+    }
+
+    #[tokio::test]
+    async fn test_publish_rejects_unsafe_message_ids() {
+        let state = create_test_state().await;
+
+        for id in ["".to_string(), "x".repeat(MAX_MESSAGE_ID_LENGTH + 1), "line\nbreak".to_string()] {
+            let req: PublishMessageRequest = serde_json::from_value(serde_json::json!({
+                "payload": {},
+                "message_id": id
+            })).unwrap();
+
+            let response = publish_message(State(state.clone()), HeaderMap::new(), Json(req)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "id {:?} should be rejected", id);
+
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["violations"][0]["field"], "message_id");
+        }
+
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_publish_rejects_undeliverable_body_and_content_type() {
+        let state = create_test_state().await;
+
+        for (field, body) in [
+            ("raw_body_base64", serde_json::json!({ "payload": {}, "raw_body_base64": "not base64!" })),
+            ("content_type", serde_json::json!({ "payload": {}, "raw_body_base64": "aGVsbG8=", "content_type": "text/plain\r\nX-Injected: 1" })),
+            ("timeout_seconds", serde_json::json!({ "payload": {}, "timeout_seconds": 0 })),
+            ("timeout_seconds", serde_json::json!({ "payload": {}, "timeout_seconds": 901 })),
+            ("max_retries", serde_json::json!({ "payload": {}, "max_retries": 11 })),
+        ] {
+            let req: PublishMessageRequest = serde_json::from_value(body).unwrap();
+
+            let response = publish_message(State(state.clone()), HeaderMap::new(), Json(req)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{} should be rejected", field);
+
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["violations"][0]["field"], field);
+        }
+
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_publish_carries_correlation_id() {
+        use fc_queue::{EmbeddedQueue, QueueConsumer};
+        use fc_queue::sqlite::{connect_pool, SqliteQueue};
+
+        let mut state = create_test_state().await;
+        let pool = connect_pool("sqlite::memory:", 1).await.unwrap();
+        let queue = Arc::new(SqliteQueue::new(pool, "dev-queue".to_string(), 30));
+        queue.init_schema().await.unwrap();
+        state.publisher = queue.clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CORRELATION_ID_HEADER, HeaderValue::from_static("trace-1"));
+
+        // The header fills in for messages without their own correlation ID
+        let mut requests = batch_request(&["POOL-A", "POOL-A"]);
+        requests[1].correlation_id = Some("trace-2".to_string());
+        let response = publish_message_batch(State(state.clone()), headers.clone(), Json(requests)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = publish_message(State(state.clone()), headers, Json(batch_request(&["POOL-A"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let correlation_ids: Vec<_> = queue.poll(10).await.unwrap()
+            .into_iter()
+            .map(|m| m.message.correlation_id)
+            .collect();
+        assert_eq!(correlation_ids, vec![
+            Some("trace-1".to_string()),
+            Some("trace-2".to_string()),
+            Some("trace-1".to_string()),
+        ]);
+
+        let mut invalid = HeaderMap::new();
+        invalid.insert(CORRELATION_ID_HEADER, HeaderValue::from_static(""));
+        let response = publish_message(State(state.clone()), invalid, Json(batch_request(&["POOL-A"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_rejects_unsafe_message_id_by_index() {
+        let state = create_test_state().await;
+        let mut requests = batch_request(&["POOL-A", "POOL-A"]);
+        requests[1].message_id = Some(String::new());
+
+        let response = publish_message_batch(State(state.clone()), HeaderMap::new(), Json(requests)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["violations"][0]["field"], "[1].message_id");
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+    }
+
+    struct HangingMediator;
+
+    #[async_trait::async_trait]
+    impl crate::Mediator for HangingMediator {
+        async fn mediate(&self, _message: &Message) -> fc_common::MediationOutcome {
+            std::future::pending().await
+        }
+    }
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        panic!("condition not met");
+    }
+
+    /// Queue manager whose POOL-A queue is full: one message stuck in mediation
+    /// and the pool's minimum capacity of 50 waiting behind it
+    async fn saturated_queue_manager() -> Arc<QueueManager> {
+        let queue_manager = Arc::new(QueueManager::new(Arc::new(HangingMediator)));
+        queue_manager.apply_config(fc_common::RouterConfig {
+            processing_pools: vec![PoolConfig {
+                code: "POOL-A".to_string(),
+                concurrency: 1,
+                ..Default::default()
+            }],
+            queues: vec![],
+        }).await.unwrap();
+
+        let queued = |count: usize| -> Vec<fc_common::QueuedMessage> {
+            batch_request(&vec!["POOL-A"; count]).into_iter()
+                .map(|req| {
+                    let message = req.into_message();
+                    fc_common::QueuedMessage {
+                        receipt_handle: format!("receipt-{}", message.id),
+                        broker_message_id: None,
+                        queue_identifier: "stub-queue".to_string(),
+                        message,
+                    }
+                })
+                .collect()
+        };
+        queue_manager.route_batch(queued(50), Arc::new(StubQueue)).await.unwrap();
+        // Let the worker take the first message into mediation before topping up
+        wait_until(|| queue_manager.get_pool_stats()[0].active_workers == 1).await;
+        queue_manager.route_batch(queued(1), Arc::new(StubQueue)).await.unwrap();
+
+        assert!(queue_manager.is_pool_saturated("POOL-A"));
+        queue_manager
+    }
+
+    #[tokio::test]
+    async fn test_publish_to_saturated_pool_returns_429() {
+        let mut state = create_test_state().await;
+        state.queue_manager = saturated_queue_manager().await;
+        state.reject_publish_when_saturated = true;
+
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(batch_request(&["POOL-A"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "5");
+
+        let response = publish_message_batch(State(state.clone()), HeaderMap::new(), Json(batch_request(&["OTHER", "POOL-A"]))).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_publish_under_system_pressure_returns_503() {
+        let mut state = create_test_state().await;
+        state.queue_manager = saturated_queue_manager().await;
+        assert_eq!(state.queue_manager.system_pressure(), 1.0);
+
+        state.publish_pressure_threshold = Some(0.9);
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(batch_request(&["OTHER"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "10");
+
+        let response = publish_message_batch(State(state.clone()), HeaderMap::new(), Json(batch_request(&["OTHER"]))).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+
+        state.publish_pressure_threshold = None;
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(batch_request(&["OTHER"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// Queue manager whose POOL-A has used its one permit a minute, with a
+    /// second message waiting on the rate limiter
+    async fn rate_limited_queue_manager() -> Arc<QueueManager> {
+        let queue_manager = Arc::new(QueueManager::new(Arc::new(NoopMediator)));
+        queue_manager.apply_config(fc_common::RouterConfig {
+            processing_pools: vec![PoolConfig {
+                code: "POOL-A".to_string(),
+                concurrency: 1,
+                rate_limit_per_minute: Some(1),
+                ..Default::default()
+            }],
+            queues: vec![],
+        }).await.unwrap();
+
+        let queued = batch_request(&["POOL-A", "POOL-A"]).into_iter()
+            .map(|req| {
+                let message = req.into_message();
+                fc_common::QueuedMessage {
+                    receipt_handle: format!("receipt-{}", message.id),
+                    broker_message_id: None,
+                    queue_identifier: "stub-queue".to_string(),
+                    message,
+                }
+            })
+            .collect();
+        queue_manager.route_batch(queued, Arc::new(StubQueue)).await.unwrap();
+        wait_until(|| queue_manager.pool_seconds_until_capacity("POOL-A").is_some()).await;
+
+        assert!(!queue_manager.is_pool_saturated("POOL-A"));
+        queue_manager
+    }
+
+    #[tokio::test]
+    async fn test_publish_to_rate_limited_pool_returns_retry_after() {
+        let mut state = create_test_state().await;
+        state.queue_manager = rate_limited_queue_manager().await;
+        state.reject_publish_when_saturated = true;
+
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(batch_request(&["POOL-A"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER].to_str().unwrap().parse().unwrap();
+        assert!((55..=60).contains(&retry_after), "retry_after = {}", retry_after);
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_publish_to_pool_with_capacity_accepted() {
+        let mut state = create_test_state().await;
+        state.reject_publish_when_saturated = true;
+
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(batch_request(&["POOL-A"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(state.messages_published.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_saturated_pool_accepted_without_admission_control() {
+        let mut state = create_test_state().await;
+        state.queue_manager = saturated_queue_manager().await;
+
+        let response = publish_message(State(state.clone()), HeaderMap::new(), Json(batch_request(&["POOL-A"]).remove(0))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_messages_page() {
+        let mut state = create_test_state().await;
+        state.queue_manager = saturated_queue_manager().await;
+        state.max_in_flight_page_size = 5;
+
+        // The requested limit is capped, while the total counts every message in flight
+        let query = InFlightMessagesQuery { limit: Some(100_000_000), message_id: None };
+        let response = dashboard_in_flight_messages_handler(State(state.clone()), Query(query)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["total"], 51);
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 5);
+
+        // Smaller limits and filters still apply within the cap
+        let message_id = messages[0]["messageId"].as_str().unwrap().to_string();
+        let query = InFlightMessagesQuery { limit: Some(2), message_id: None };
+        let body: serde_json::Value = json_body(dashboard_in_flight_messages_handler(State(state.clone()), Query(query)).await).await;
+        assert_eq!(body["messages"].as_array().unwrap().len(), 2);
+        let query = InFlightMessagesQuery { limit: None, message_id: Some(message_id.clone()) };
+        let body: serde_json::Value = json_body(dashboard_in_flight_messages_handler(State(state.clone()), Query(query)).await).await;
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(body["messages"][0]["messageId"], message_id.as_str());
+
+        let query = InFlightMessagesQuery { limit: None, message_id: Some("x".repeat(MAX_MESSAGE_ID_FILTER_LEN + 1)) };
+        let response = dashboard_in_flight_messages_handler(State(state), Query(query)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_peek_queue() {
+        use fc_queue::{EmbeddedQueue, QueueConsumer};
+        use fc_queue::sqlite::{connect_pool, SqliteQueue};
+
+        let state = create_test_state().await;
+        let pool = connect_pool("sqlite::memory:", 1).await.unwrap();
+        let queue = Arc::new(SqliteQueue::new(pool, "dev-queue".to_string(), 30));
+        queue.init_schema().await.unwrap();
+        for req in batch_request(&["POOL-A", "POOL-A"]) {
+            queue.publish(req.into_message()).await.unwrap();
+        }
+        state.queue_manager.add_consumer(queue.clone()).await;
+
+        let peek = |queue: &str, limit: Option<u32>| {
+            peek_queue_handler(State(state.clone()), Path(queue.to_string()), Query(PeekQuery { limit }))
+        };
+
+        let body: serde_json::Value = json_body(peek("dev-queue", None).await).await;
+        assert_eq!(body.as_array().unwrap().len(), 2);
+        assert_eq!(body[0]["receive_count"], 0);
+
+        // Nothing was consumed
+        assert_eq!(queue.poll(10).await.unwrap().len(), 2);
+
+        assert_eq!(peek("dev-queue", Some(0)).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(peek("stub-queue", None).await.status(), StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(peek("missing", None).await.status(), StatusCode::NOT_FOUND);
+    }
+}
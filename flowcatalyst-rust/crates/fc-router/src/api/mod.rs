@@ -5,7 +5,7 @@
 //! - Health and monitoring
 //! - Kubernetes probes (liveness/readiness)
 //! - Warning management
-//! - Pool statistics
+//! - Pool statistics, including a live WebSocket feed
 //! - Circuit breaker management
 //! - Standby/traffic status
 //! - Test/seed endpoints (development)
@@ -13,33 +13,47 @@
 use axum::{
     routing::{get, post, put, delete},
     extract::{Path, Query, State},
+    extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade},
     response::{Html, IntoResponse, Response},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     Json, Router,
 };
 use utoipa::{OpenApi, ToSchema};
 use utoipa_swagger_ui::SwaggerUi;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::collections::HashMap;
-use fc_queue::QueuePublisher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use fc_queue::{BodyFormat, PeekedMessage, QueuePublisher};
 use fc_common::{
-    Message, MediationType, HealthStatus, HealthReport, PoolStats, PoolConfig,
-    Warning, WarningSeverity, WarningCategory,
+    Message, MediationType, HealthStatus, HealthReport, PoolStats,
+    ConsumerHealth, RateLimitScope, Warning, WarningSeverity, WarningCategory, DEFAULT_POOL_CODE,
 };
 use crate::{
     QueueManager, WarningService, HealthService, QueueMetrics, InFlightMessageInfo,
-    CircuitBreakerRegistry, CircuitBreakerState,
+    CircuitBreakerRegistry, LatencyPercentiles,
 };
 use fc_stream::StreamHealthService;
 use uuid::Uuid;
 use chrono::Utc;
-use tracing::{debug, info, warn, error};
+use tracing::{debug, warn, error};
 
 pub mod model;
 pub mod auth;
-
-use model::{PublishMessageRequest, PublishMessageResponse, PoolStatusResponse};
+pub mod admin;
+pub mod messages;
+pub mod pools;
+
+use model::{
+    PublishMessageRequest, PublishMessageResponse, BatchPublishItemResult, BatchPublishResponse,
+    MessageLimitsResponse, PoolStatusResponse, FieldViolation, ValidationErrorResponse,
+    PoolConfigUpdateRequest, ConfigReloadRequest, PoolConfigRequest,
+};
+pub use messages::{
+    DEFAULT_IN_FLIGHT_PAGE_SIZE, DEFAULT_MAX_IN_FLIGHT_PAGE_SIZE, DEFAULT_MAX_MESSAGE_BYTES, DEFAULT_MAX_PUBLISH_BATCH_SIZE,
+    DEFAULT_PEEK_LIMIT, MAX_PEEK_LIMIT,
+};
 pub use auth::{AuthConfig, AuthMode, AuthState, OidcValidator, TokenClaims, auth_middleware, create_auth_state, is_public_path};
 
 /// Application state shared across handlers
@@ -55,6 +69,58 @@ pub struct AppState {
     pub instance_id: String,
     /// Stream health service (optional)
     pub stream_health_service: Option<Arc<StreamHealthService>>,
+    /// Messages successfully published through the API
+    pub messages_published: Arc<AtomicU64>,
+    /// Largest batch accepted by `POST /messages/batch`
+    pub max_publish_batch_size: usize,
+    /// Reject publishes with 429 while the target pool is at capacity or rate limited
+    pub reject_publish_when_saturated: bool,
+    /// Reject publishes with 503 while system pressure is above this fraction
+    pub publish_pressure_threshold: Option<f64>,
+    /// Largest page returned by `GET /monitoring/in-flight-messages`
+    pub max_in_flight_page_size: usize,
+    /// Largest encoded message accepted by the publish endpoints, in bytes
+    pub max_message_bytes: usize,
+    /// Body encoding used by the publisher, which the size limit is measured in
+    pub body_format: BodyFormat,
+}
+
+/// Optional settings for [`create_router_with_options`]
+#[derive(Clone)]
+pub struct RouterOptions {
+    /// Standby configuration
+    pub standby_enabled: bool,
+    pub instance_id: String,
+    /// Stream health service
+    pub stream_health_service: Option<Arc<StreamHealthService>>,
+    /// Largest batch accepted by `POST /messages/batch`
+    pub max_publish_batch_size: usize,
+    /// Reject publishes with 429 while the target pool is at capacity or rate limited
+    pub reject_publish_when_saturated: bool,
+    /// Reject publishes with 503 while system pressure is above this fraction
+    pub publish_pressure_threshold: Option<f64>,
+    /// Largest page returned by `GET /monitoring/in-flight-messages`
+    pub max_in_flight_page_size: usize,
+    /// Largest encoded message accepted by the publish endpoints, in bytes
+    pub max_message_bytes: usize,
+    /// Body encoding used by the publisher
+    pub body_format: BodyFormat,
+}
+
+impl Default for RouterOptions {
+    fn default() -> Self {
+        Self {
+            standby_enabled: false,
+            instance_id: "default".to_string(),
+            stream_health_service: None,
+            max_publish_batch_size: DEFAULT_MAX_PUBLISH_BATCH_SIZE,
+            reject_publish_when_saturated: false,
+            publish_pressure_threshold: None,
+            max_in_flight_page_size: DEFAULT_MAX_IN_FLIGHT_PAGE_SIZE,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            body_format: BodyFormat::default(),
+        }
+    }
 }
 
 /// Simple health response for basic health check
@@ -64,6 +130,33 @@ pub struct SimpleHealthResponse {
     pub status: String,
     /// Application version
     pub version: String,
+    /// Why the router is not UP; omitted while it is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causes: Option<HealthCauses>,
+}
+
+/// Components behind a non-UP `/health` status
+#[derive(Serialize, ToSchema)]
+pub struct HealthCauses {
+    /// No consumer is stalled
+    pub queues_healthy: bool,
+    /// Every pool meets the success-rate threshold
+    pub pools_healthy: bool,
+    /// Unacknowledged critical warnings exist
+    pub critical_warnings: bool,
+    /// Human-readable issues from the health report
+    pub issues: Vec<String>,
+}
+
+impl HealthCauses {
+    fn from_report(report: HealthReport) -> Self {
+        Self {
+            queues_healthy: report.consumers_unhealthy == 0,
+            pools_healthy: report.pools_unhealthy == 0,
+            critical_warnings: report.critical_warnings > 0,
+            issues: report.issues,
+        }
+    }
 }
 
 /// Kubernetes probe response
@@ -71,6 +164,9 @@ pub struct SimpleHealthResponse {
 pub struct ProbeResponse {
     /// Probe status: LIVE, READY, NOT_READY
     pub status: String,
+    /// Queues that failed the readiness connectivity check
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unreachable_queues: Vec<String>,
 }
 
 /// Detailed monitoring response
@@ -96,55 +192,39 @@ pub struct WarningsQuery {
     /// Filter by severity: INFO, WARN, ERROR, CRITICAL
     pub severity: Option<String>,
     /// Filter by category: ROUTING, PROCESSING, CONFIGURATION, etc.
+    /// Comma-separated to match any of several categories.
     pub category: Option<String>,
     /// Filter by acknowledged status
     pub acknowledged: Option<bool>,
+    /// Only warnings created at or after this time (RFC 3339)
+    pub since: Option<String>,
+    /// Page size (default 100, at most 1000)
+    pub limit: Option<usize>,
+    /// Number of matching warnings to skip (default 0)
+    pub offset: Option<usize>,
 }
 
-/// Request to update pool configuration
-#[derive(Debug, Deserialize, ToSchema)]
-pub struct PoolConfigUpdateRequest {
-    /// New concurrency limit
-    pub concurrency: Option<u32>,
-    /// New rate limit (messages per minute)
-    pub rate_limit_per_minute: Option<u32>,
-}
+/// Default page size for the warnings endpoint
+pub const DEFAULT_WARNINGS_PAGE_SIZE: usize = 100;
 
-/// Request to reload router configuration
-#[derive(Debug, Deserialize, ToSchema)]
-pub struct ConfigReloadRequest {
-    /// List of pool configurations
-    pub processing_pools: Vec<PoolConfigRequest>,
-}
-
-/// Pool configuration in reload request
-#[derive(Debug, Clone, Deserialize, ToSchema)]
-pub struct PoolConfigRequest {
-    /// Pool code/identifier
-    pub code: String,
-    /// Worker concurrency
-    pub concurrency: u32,
-    /// Optional rate limit (messages per minute)
-    pub rate_limit_per_minute: Option<u32>,
-}
-
-/// Response after config reload
-#[derive(Debug, Serialize, ToSchema)]
-pub struct ConfigReloadResponse {
-    /// Whether the reload was successful
-    pub success: bool,
-    /// Number of pools updated
-    pub pools_updated: usize,
-    /// Number of new pools created
-    pub pools_created: usize,
-    /// Number of pools removed (draining)
-    pub pools_removed: usize,
-    /// Total active pools after reload
-    pub total_active_pools: usize,
-    /// Total pools currently draining
-    pub total_draining_pools: usize,
+/// Largest page size accepted by the warnings endpoint
+pub const MAX_WARNINGS_PAGE_SIZE: usize = 1000;
+
+/// One page of warnings, newest first
+#[derive(Serialize, ToSchema)]
+pub struct WarningsPage {
+    /// Warnings on this page
+    pub warnings: Vec<Warning>,
+    /// Warnings matching the filters, across all pages
+    pub total: usize,
+    /// Page size used
+    pub limit: usize,
+    /// Matching warnings skipped before this page
+    pub offset: usize,
 }
 
+pub use crate::manager::MAX_POOL_CONCURRENCY;
+
 /// Response for queue metrics endpoint
 #[derive(Serialize, ToSchema)]
 pub struct QueueMetricsResponse {
@@ -166,6 +246,61 @@ impl From<QueueMetrics> for QueueMetricsResponse {
     }
 }
 
+/// Warning counts included in the monitoring summary
+#[derive(Serialize, ToSchema)]
+pub struct WarningCountsResponse {
+    /// Total warnings held by the warning service
+    pub total: u32,
+    /// Unacknowledged warnings
+    pub unacknowledged: u32,
+    /// Unacknowledged critical warnings
+    pub critical: u32,
+}
+
+/// Combined pools/queues/consumers/warnings snapshot for dashboards
+#[derive(Serialize, ToSchema)]
+pub struct MonitoringSummaryResponse {
+    /// When the snapshot was taken (RFC 3339)
+    pub timestamp: String,
+    /// Pool statistics for active pools
+    pub pools: Vec<PoolStats>,
+    /// Pools still finishing in-flight work after being removed or drained;
+    /// kept apart so totals over `pools` don't count a pool and its replacement twice
+    pub draining_pools: Vec<PoolStats>,
+    /// Queue metrics
+    pub queues: Vec<QueueMetricsResponse>,
+    /// Consumer health, one entry per active consumer
+    pub consumers: Vec<ConsumerHealth>,
+    /// Warning counts
+    pub warnings: WarningCountsResponse,
+}
+
+/// Snapshot pushed periodically over `GET /monitoring/stream`
+#[derive(Serialize, ToSchema)]
+pub struct MonitoringStreamSnapshot {
+    /// When the snapshot was taken (RFC 3339)
+    pub timestamp: String,
+    /// Pool statistics
+    pub pools: Vec<PoolStats>,
+    /// Queue metrics
+    pub queues: Vec<QueueMetricsResponse>,
+    /// Messages currently in flight across all pools
+    pub in_flight: usize,
+}
+
+/// Query params for the monitoring stream
+#[derive(Deserialize, Default, ToSchema)]
+pub struct MonitoringStreamQuery {
+    /// Push interval in milliseconds (default 2000, clamped to 250..=60000)
+    #[serde(rename = "intervalMs")]
+    pub interval_ms: Option<u64>,
+}
+
+/// Default push interval for the monitoring stream
+pub const DEFAULT_STREAM_INTERVAL_MS: u64 = 2000;
+const MIN_STREAM_INTERVAL_MS: u64 = 250;
+const MAX_STREAM_INTERVAL_MS: u64 = 60_000;
+
 /// OpenAPI documentation
 #[derive(OpenApi)]
 #[openapi(
@@ -180,10 +315,18 @@ impl From<QueueMetrics> for QueueMetricsResponse {
         readiness_probe,
         metrics_handler,
         monitoring_handler,
-        pool_stats_handler,
+        monitoring_summary_handler,
+        monitoring_stream_handler,
+        pools::pool_stats_handler,
+        pools::pool_latency_handler,
         queue_metrics_handler,
-        update_pool_config,
-        reload_config,
+        messages::peek_queue_handler,
+        admin::consumers_handler,
+        admin::restart_consumer_handler,
+        pools::update_pool_config,
+        pools::drain_pool,
+        admin::reload_config,
+        admin::export_config,
         list_warnings,
         acknowledge_warning,
         acknowledge_all_warnings,
@@ -197,11 +340,13 @@ impl From<QueueMetrics> for QueueMetricsResponse {
         dashboard_pool_stats_handler,
         dashboard_warnings_handler,
         dashboard_circuit_breakers_handler,
-        dashboard_in_flight_messages_handler,
+        messages::dashboard_in_flight_messages_handler,
+        messages::cancel_in_flight_message,
         monitoring_acknowledge_warning,
-        get_circuit_breaker_state,
-        reset_circuit_breaker,
-        reset_all_circuit_breakers,
+        admin::get_circuit_breaker_state,
+        admin::reset_circuit_breaker,
+        admin::open_circuit_breaker,
+        admin::reset_all_circuit_breakers,
         get_standby_status,
         get_traffic_status,
         seed_messages,
@@ -216,20 +361,44 @@ impl From<QueueMetrics> for QueueMetricsResponse {
         test_server_error,
         test_stats,
         reset_test_stats,
-        publish_message,
+        messages::publish_message,
+        messages::publish_message_batch,
+        messages::message_limits,
+        admin::get_drain_status,
+        admin::start_drain,
+        admin::stop_drain,
     ),
     components(schemas(
         SimpleHealthResponse,
+        HealthCauses,
         ProbeResponse,
         MonitoringResponse,
         WarningsQuery,
+        WarningsPage,
         PoolConfigUpdateRequest,
+        FieldViolation,
+        ValidationErrorResponse,
         ConfigReloadRequest,
         PoolConfigRequest,
-        ConfigReloadResponse,
+        RateLimitScope,
+        MediationType,
+        admin::ConfigReloadResponse,
+        pools::PoolDrainResponse,
+        LatencyPercentiles,
+        admin::DrainStatusResponse,
         QueueMetricsResponse,
+        PeekedMessage,
+        WarningCountsResponse,
+        MonitoringSummaryResponse,
+        MonitoringStreamSnapshot,
+        MonitoringStreamQuery,
+        ConsumerHealth,
+        admin::ConsumerStatusResponse,
         PublishMessageRequest,
         PublishMessageResponse,
+        BatchPublishItemResult,
+        BatchPublishResponse,
+        MessageLimitsResponse,
         PoolStatusResponse,
         DashboardHealthResponse,
         DashboardHealthDetails,
@@ -237,13 +406,15 @@ impl From<QueueMetrics> for QueueMetricsResponse {
         DashboardPoolStats,
         DashboardWarning,
         DashboardCircuitBreakerStats,
-        InFlightMessagesQuery,
+        messages::InFlightMessagesQuery,
+        messages::InFlightMessagesPage,
+        InFlightMessageInfo,
         StandbyStatusResponse,
         TrafficStatusResponse,
         SeedMessageRequest,
         SeedMessageResponse,
         ClearWarningsQuery,
-        CircuitBreakerStateResponse,
+        admin::CircuitBreakerStateResponse,
     )),
     tags(
         (name = "health", description = "Health check endpoints"),
@@ -252,6 +423,7 @@ impl From<QueueMetrics> for QueueMetricsResponse {
         (name = "messages", description = "Message publishing endpoints"),
         (name = "circuit-breakers", description = "Circuit breaker management"),
         (name = "standby", description = "Standby and traffic management"),
+        (name = "admin", description = "Operational controls for deployments"),
         (name = "test", description = "Test endpoints for development"),
     )
 )]
@@ -271,9 +443,7 @@ pub fn create_router(
         warning_service,
         health_service,
         circuit_breaker_registry,
-        false,
-        "default".to_string(),
-        None,
+        RouterOptions::default(),
     )
 }
 
@@ -284,9 +454,7 @@ pub fn create_router_with_options(
     warning_service: Arc<WarningService>,
     health_service: Arc<HealthService>,
     circuit_breaker_registry: Arc<CircuitBreakerRegistry>,
-    standby_enabled: bool,
-    instance_id: String,
-    stream_health_service: Option<Arc<StreamHealthService>>,
+    options: RouterOptions,
 ) -> Router {
     let state = AppState {
         publisher,
@@ -294,9 +462,16 @@ pub fn create_router_with_options(
         warning_service,
         health_service,
         circuit_breaker_registry,
-        standby_enabled,
-        instance_id,
-        stream_health_service,
+        standby_enabled: options.standby_enabled,
+        instance_id: options.instance_id,
+        stream_health_service: options.stream_health_service,
+        messages_published: Arc::new(AtomicU64::new(0)),
+        max_publish_batch_size: options.max_publish_batch_size,
+        reject_publish_when_saturated: options.reject_publish_when_saturated,
+        publish_pressure_threshold: options.publish_pressure_threshold,
+        max_in_flight_page_size: options.max_in_flight_page_size,
+        max_message_bytes: options.max_message_bytes,
+        body_format: options.body_format,
     };
 
     Router::new()
@@ -317,9 +492,15 @@ pub fn create_router_with_options(
         // Detailed monitoring
         .route("/monitoring", get(monitoring_handler))
         .route("/monitoring/health", get(dashboard_health_handler))
-        .route("/monitoring/pools", get(pool_stats_handler))
-        .route("/monitoring/pools/:pool_code", put(update_pool_config))
+        .route("/monitoring/summary", get(monitoring_summary_handler))
+        .route("/monitoring/stream", get(monitoring_stream_handler))
+        .route("/monitoring/pools", get(pools::pool_stats_handler))
+        .route("/monitoring/pools/:pool_code", put(pools::update_pool_config).delete(pools::drain_pool))
+        .route("/monitoring/pools/:pool_code/latency", get(pools::pool_latency_handler))
         .route("/monitoring/queues", get(queue_metrics_handler))
+        .route("/monitoring/queues/:queue/peek", get(messages::peek_queue_handler))
+        .route("/monitoring/consumers", get(admin::consumers_handler))
+        .route("/monitoring/consumers/:id/restart", post(admin::restart_consumer_handler))
         // Dashboard-compatible endpoints
         .route("/monitoring/queue-stats", get(dashboard_queue_stats_handler))
         .route("/monitoring/pool-stats", get(dashboard_pool_stats_handler))
@@ -328,10 +509,12 @@ pub fn create_router_with_options(
         .route("/monitoring/warnings/unacknowledged", get(get_unacknowledged_warnings))
         .route("/monitoring/warnings/severity/:severity", get(get_warnings_by_severity))
         .route("/monitoring/circuit-breakers", get(dashboard_circuit_breakers_handler))
-        .route("/monitoring/circuit-breakers/:name/state", get(get_circuit_breaker_state))
-        .route("/monitoring/circuit-breakers/:name/reset", post(reset_circuit_breaker))
-        .route("/monitoring/circuit-breakers/reset-all", post(reset_all_circuit_breakers))
-        .route("/monitoring/in-flight-messages", get(dashboard_in_flight_messages_handler))
+        .route("/monitoring/circuit-breakers/:name/state", get(admin::get_circuit_breaker_state))
+        .route("/monitoring/circuit-breakers/:name/reset", post(admin::reset_circuit_breaker))
+        .route("/monitoring/circuit-breakers/:name/open", post(admin::open_circuit_breaker))
+        .route("/monitoring/circuit-breakers/reset-all", post(admin::reset_all_circuit_breakers))
+        .route("/monitoring/in-flight-messages", get(messages::dashboard_in_flight_messages_handler))
+        .route("/monitoring/in-flight-messages/:message_id/cancel", post(messages::cancel_in_flight_message))
         .route("/monitoring/dashboard", get(dashboard_html_handler))
         .route("/monitoring/standby-status", get(get_standby_status))
         .route("/monitoring/traffic-status", get(get_traffic_status))
@@ -340,7 +523,8 @@ pub fn create_router_with_options(
         .route("/monitoring/stream-health/live", get(stream_liveness_handler))
         .route("/monitoring/stream-health/ready", get(stream_readiness_handler))
         // Configuration management
-        .route("/config/reload", post(reload_config))
+        .route("/config", get(admin::export_config))
+        .route("/config/reload", post(admin::reload_config))
         .route("/api/config", get(get_local_config))
         // Warnings management
         .route("/warnings", get(list_warnings).delete(clear_all_warnings))
@@ -362,7 +546,11 @@ pub fn create_router_with_options(
         .route("/api/test/server-error", post(test_server_error))
         .route("/api/test/stats", get(test_stats).post(reset_test_stats))
         // Message publishing
-        .route("/messages", post(publish_message))
+        .route("/messages", post(messages::publish_message))
+        .route("/messages/batch", post(messages::publish_message_batch))
+        .route("/messages/limits", get(messages::message_limits))
+        // Drain mode for rolling deployments
+        .route("/admin/drain", get(admin::get_drain_status).post(admin::start_drain).delete(admin::stop_drain))
         .with_state(state)
 }
 
@@ -370,15 +558,28 @@ pub fn create_router_with_options(
 #[derive(Clone)]
 pub struct SimpleState {
     pub publisher: Arc<dyn QueuePublisher>,
+    /// Largest encoded message accepted, in bytes
+    pub max_message_bytes: usize,
+    /// Body encoding used by the publisher
+    pub body_format: BodyFormat,
 }
 
 /// Create a simple router with just message publishing
 pub fn create_simple_router(publisher: Arc<dyn QueuePublisher>) -> Router {
-    let state = SimpleState { publisher };
+    create_simple_router_with_options(publisher, DEFAULT_MAX_MESSAGE_BYTES, BodyFormat::default())
+}
+
+/// Create a simple router with just message publishing and a message size limit
+pub fn create_simple_router_with_options(
+    publisher: Arc<dyn QueuePublisher>,
+    max_message_bytes: usize,
+    body_format: BodyFormat,
+) -> Router {
+    let state = SimpleState { publisher, max_message_bytes, body_format };
 
     Router::new()
         .route("/health", get(simple_health_handler))
-        .route("/messages", post(simple_publish_message))
+        .route("/messages", post(messages::simple_publish_message))
         .with_state(state)
 }
 
@@ -387,6 +588,9 @@ pub fn create_simple_router(publisher: Arc<dyn QueuePublisher>) -> Router {
 // ============================================================================
 
 /// Health check endpoint
+///
+/// When the status is not UP, `causes` says which components are unhealthy and
+/// lists the health report's issues.
 #[utoipa::path(
     get,
     path = "/health",
@@ -399,15 +603,16 @@ async fn health_handler(State(state): State<AppState>) -> Json<SimpleHealthRespo
     let pool_stats = state.queue_manager.get_pool_stats();
     let report = state.health_service.get_health_report(&pool_stats);
 
-    let status = match report.status {
-        HealthStatus::Healthy => "UP",
-        HealthStatus::Warning => "UP",
-        HealthStatus::Degraded => "DEGRADED",
+    let (status, causes) = match report.status {
+        HealthStatus::Healthy => ("UP", None),
+        HealthStatus::Warning => ("UP", None),
+        HealthStatus::Degraded => ("DEGRADED", Some(HealthCauses::from_report(report))),
     };
 
     Json(SimpleHealthResponse {
         status: status.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        causes,
     })
 }
 
@@ -416,6 +621,7 @@ async fn simple_health_handler() -> Json<SimpleHealthResponse> {
     Json(SimpleHealthResponse {
         status: "UP".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        causes: None,
     })
 }
 
@@ -429,7 +635,7 @@ async fn simple_health_handler() -> Json<SimpleHealthResponse> {
     )
 )]
 async fn liveness_probe() -> Json<ProbeResponse> {
-    Json(ProbeResponse { status: "LIVE".to_string() })
+    Json(ProbeResponse { status: "LIVE".to_string(), unreachable_queues: Vec::new() })
 }
 
 /// Kubernetes readiness probe - returns 200 if ready to accept traffic
@@ -439,20 +645,52 @@ async fn liveness_probe() -> Json<ProbeResponse> {
     tag = "health",
     responses(
         (status = 200, description = "Application is ready", body = ProbeResponse),
-        (status = 503, description = "Application is not ready", body = ProbeResponse)
+        (status = 503, description = "Application is not ready (degraded, draining or a queue is unreachable)", body = ProbeResponse)
     )
 )]
 async fn readiness_probe(State(state): State<AppState>) -> Response {
     let pool_stats = state.queue_manager.get_pool_stats();
     let report = state.health_service.get_health_report(&pool_stats);
 
-    match report.status {
-        HealthStatus::Healthy | HealthStatus::Warning => {
-            (StatusCode::OK, Json(ProbeResponse { status: "READY".to_string() })).into_response()
-        }
-        HealthStatus::Degraded => {
-            (StatusCode::SERVICE_UNAVAILABLE, Json(ProbeResponse { status: "NOT_READY".to_string() })).into_response()
-        }
+    if report.status == HealthStatus::Degraded || state.queue_manager.is_draining() {
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(ProbeResponse {
+            status: "NOT_READY".to_string(),
+            unreachable_queues: Vec::new(),
+        })).into_response();
+    }
+
+    let unreachable_queues = state.queue_manager.check_queue_connectivity().await;
+    if !unreachable_queues.is_empty() {
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(ProbeResponse {
+            status: "NOT_READY".to_string(),
+            unreachable_queues,
+        })).into_response();
+    }
+
+    (StatusCode::OK, Json(ProbeResponse {
+        status: "READY".to_string(),
+        unreachable_queues: Vec::new(),
+    })).into_response()
+}
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Write one metric family (HELP/TYPE header plus labelled samples)
+fn write_metric_family<'a>(
+    out: &mut String,
+    name: &str,
+    metric_type: &str,
+    help: &str,
+    label: &str,
+    samples: impl IntoIterator<Item = (&'a str, u64)>,
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, metric_type);
+    for (label_value, value) in samples {
+        let _ = writeln!(out, "{}{{{}=\"{}\"}} {}", name, label, escape_label(label_value), value);
     }
 }
 
@@ -465,14 +703,50 @@ async fn readiness_probe(State(state): State<AppState>) -> Response {
         (status = 200, description = "Prometheus metrics", content_type = "text/plain")
     )
 )]
-async fn metrics_handler() -> Response {
-    let output = "# HELP fc_requests_total Total number of requests
-# TYPE fc_requests_total counter
-fc_requests_total 0
-# HELP fc_active_pools Number of active processing pools
-# TYPE fc_active_pools gauge
-fc_active_pools 1
-";
+async fn metrics_handler(State(state): State<AppState>) -> Response {
+    let pool_stats: Vec<PoolStats> = state.queue_manager
+        .get_pool_stats()
+        .into_iter()
+        .filter(|s| !s.is_draining)
+        .collect();
+    let queue_metrics = state.queue_manager.get_queue_metrics().await;
+
+    let mut output = String::new();
+
+    let _ = writeln!(output, "# HELP fc_messages_published_total Messages published through the API");
+    let _ = writeln!(output, "# TYPE fc_messages_published_total counter");
+    let _ = writeln!(output, "fc_messages_published_total {}", state.messages_published.load(Ordering::Relaxed));
+
+    let _ = writeln!(output, "# HELP fc_active_pools Number of active processing pools");
+    let _ = writeln!(output, "# TYPE fc_active_pools gauge");
+    let _ = writeln!(output, "fc_active_pools {}", pool_stats.len());
+
+    write_metric_family(
+        &mut output, "fc_pool_active_workers", "gauge", "Workers currently processing messages",
+        "pool_code", pool_stats.iter().map(|p| (p.pool_code.as_str(), p.active_workers as u64)),
+    );
+    write_metric_family(
+        &mut output, "fc_pool_queue_size", "gauge", "Messages waiting in the pool queue",
+        "pool_code", pool_stats.iter().map(|p| (p.pool_code.as_str(), p.queue_size as u64)),
+    );
+    write_metric_family(
+        &mut output, "fc_pool_available_permits", "gauge", "Free concurrency permits",
+        "pool_code", pool_stats.iter().map(|p| (p.pool_code.as_str(), p.concurrency.saturating_sub(p.active_workers) as u64)),
+    );
+    write_metric_family(
+        &mut output, "fc_queue_pending_messages", "gauge", "Messages visible in the queue",
+        "queue_identifier", queue_metrics.iter().map(|q| (q.queue_identifier.as_str(), q.pending_messages)),
+    );
+    write_metric_family(
+        &mut output, "fc_queue_in_flight_messages", "gauge", "Messages received but not yet acknowledged",
+        "queue_identifier", queue_metrics.iter().map(|q| (q.queue_identifier.as_str(), q.in_flight_messages)),
+    );
+
+    // Counters and histograms recorded through the `metrics` facade
+    if let Some(handle) = crate::router_metrics::prometheus_handle() {
+        output.push_str(&handle.render());
+    }
+
     (
         StatusCode::OK,
         [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
@@ -490,10 +764,11 @@ fc_active_pools 1
     path = "/monitoring",
     tag = "monitoring",
     responses(
-        (status = 200, description = "Monitoring data", body = MonitoringResponse)
+        (status = 200, description = "Monitoring data", body = MonitoringResponse),
+        (status = 304, description = "Unchanged since the `ETag` given in `If-None-Match`")
     )
 )]
-async fn monitoring_handler(State(state): State<AppState>) -> Json<MonitoringResponse> {
+async fn monitoring_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
     let pool_stats = state.queue_manager.get_pool_stats();
     let health_report = state.health_service.get_health_report(&pool_stats);
     let active_warnings = state.warning_service.unacknowledged_count() as u32;
@@ -505,7 +780,15 @@ async fn monitoring_handler(State(state): State<AppState>) -> Json<MonitoringRes
         HealthStatus::Degraded => "DEGRADED",
     };
 
-    Json(MonitoringResponse {
+    let etag = json_etag(&(
+        status,
+        &health_report,
+        etag_stable_pool_stats(&pool_stats),
+        active_warnings,
+        critical_warnings,
+    ));
+
+    conditional_json(&headers, etag, || MonitoringResponse {
         status: status.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         health_report,
@@ -515,246 +798,315 @@ async fn monitoring_handler(State(state): State<AppState>) -> Json<MonitoringRes
     })
 }
 
-/// Pool statistics
-#[utoipa::path(
-    get,
-    path = "/monitoring/pools",
-    tag = "monitoring",
-    responses(
-        (status = 200, description = "Pool statistics", body = Vec<PoolStats>)
-    )
-)]
-async fn pool_stats_handler(State(state): State<AppState>) -> Json<Vec<PoolStats>> {
-    Json(state.queue_manager.get_pool_stats())
+/// Feeds serialized JSON straight into a hasher, so computing an ETag
+/// doesn't buffer the response body
+struct HashWriter<'a>(&'a mut sha2::Sha256);
+
+impl std::io::Write for HashWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        sha2::Digest::update(self.0, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
-/// Queue metrics
+/// Strong ETag over the JSON serialization of `value`
+fn json_etag<T: Serialize>(value: &T) -> String {
+    let mut hasher = <sha2::Sha256 as sha2::Digest>::new();
+    // Serializing plain response structs can't fail; an error just yields a weaker hash
+    let _ = serde_json::to_writer(HashWriter(&mut hasher), value);
+    let digest = sha2::Digest::finalize(hasher);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Pool stats with the sliding-window start times cleared. Those move with the
+/// clock, so they would otherwise change the ETag on every request.
+fn etag_stable_pool_stats(stats: &[PoolStats]) -> Vec<PoolStats> {
+    stats
+        .iter()
+        .cloned()
+        .map(|mut s| {
+            if let Some(ref mut metrics) = s.metrics {
+                metrics.last_5_min.window_start = chrono::DateTime::UNIX_EPOCH;
+                metrics.last_30_min.window_start = chrono::DateTime::UNIX_EPOCH;
+            }
+            s
+        })
+        .collect()
+}
+
+/// Whether an `If-None-Match` header already names `etag`
+fn if_none_match_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// `304 Not Modified` when the client already holds `etag`, otherwise the JSON
+/// body from `body`; both carry the `ETag` header. `body` is only built and
+/// serialized when it is actually sent.
+fn conditional_json<T: Serialize>(headers: &HeaderMap, etag: String, body: impl FnOnce() -> T) -> Response {
+    let etag_value = HeaderValue::from_str(&etag).expect("hex ETag is a valid header value");
+    if if_none_match_matches(headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag_value)]).into_response();
+    }
+    ([(header::ETAG, etag_value)], Json(body())).into_response()
+}
+
+/// Combined monitoring summary
+///
+/// Returns pools, queue metrics, consumer health and warning counts in a
+/// single snapshot so dashboards don't have to stitch together separately
+/// fetched (and possibly inconsistent) responses.
 #[utoipa::path(
     get,
-    path = "/monitoring/queues",
+    path = "/monitoring/summary",
     tag = "monitoring",
     responses(
-        (status = 200, description = "Queue metrics", body = Vec<QueueMetricsResponse>)
+        (status = 200, description = "Monitoring summary", body = MonitoringSummaryResponse)
     )
 )]
-async fn queue_metrics_handler(State(state): State<AppState>) -> Json<Vec<QueueMetricsResponse>> {
-    let metrics = state.queue_manager.get_queue_metrics().await;
-    Json(metrics.into_iter().map(QueueMetricsResponse::from).collect())
-}
+async fn monitoring_summary_handler(State(state): State<AppState>) -> Json<MonitoringSummaryResponse> {
+    let timestamp = Utc::now().to_rfc3339();
+    let (draining_pools, pools): (Vec<PoolStats>, Vec<PoolStats>) = state.queue_manager
+        .get_pool_stats()
+        .into_iter()
+        .partition(|s| s.is_draining);
+    let queues = state.queue_manager.get_queue_metrics().await
+        .into_iter()
+        .map(QueueMetricsResponse::from)
+        .collect();
+    let consumers = state.queue_manager.consumer_ids().await
+        .iter()
+        .map(|id| state.health_service.get_consumer_health(id))
+        .collect();
 
-// ============================================================================
-// Configuration Management
-// ============================================================================
+    let warnings = WarningCountsResponse {
+        total: state.warning_service.warning_count() as u32,
+        unacknowledged: state.warning_service.unacknowledged_count() as u32,
+        critical: state.warning_service.critical_count() as u32,
+    };
 
-/// Reload configuration (hot reload)
+    Json(MonitoringSummaryResponse {
+        timestamp,
+        pools,
+        draining_pools,
+        queues,
+        consumers,
+        warnings,
+    })
+}
+
+/// Live pool/queue stats feed
+///
+/// Upgrades to a WebSocket that pushes a `MonitoringStreamSnapshot` as a JSON
+/// text frame every `intervalMs`. Each connection runs its own timer and stops
+/// when the client closes the socket.
 #[utoipa::path(
-    post,
-    path = "/config/reload",
+    get,
+    path = "/monitoring/stream",
     tag = "monitoring",
-    request_body = ConfigReloadRequest,
+    params(
+        ("intervalMs" = Option<u64>, Query, description = "Push interval in milliseconds (default 2000, clamped to 250..=60000)")
+    ),
     responses(
-        (status = 200, description = "Configuration reloaded", body = ConfigReloadResponse),
-        (status = 503, description = "Service unavailable", body = ConfigReloadResponse),
-        (status = 500, description = "Internal error", body = ConfigReloadResponse)
+        (status = 101, description = "Switching to WebSocket; frames are MonitoringStreamSnapshot JSON", body = MonitoringStreamSnapshot)
     )
 )]
-async fn reload_config(
+async fn monitoring_stream_handler(
     State(state): State<AppState>,
-    Json(req): Json<ConfigReloadRequest>,
+    Query(query): Query<MonitoringStreamQuery>,
+    ws: WebSocketUpgrade,
 ) -> Response {
-    use fc_common::RouterConfig;
+    let interval = stream_interval(query.interval_ms);
+    ws.on_upgrade(move |socket| push_monitoring_snapshots(socket, state, interval))
+}
+
+/// Clamp a requested push interval to the supported range
+fn stream_interval(interval_ms: Option<u64>) -> std::time::Duration {
+    let ms = interval_ms
+        .unwrap_or(DEFAULT_STREAM_INTERVAL_MS)
+        .clamp(MIN_STREAM_INTERVAL_MS, MAX_STREAM_INTERVAL_MS);
+    std::time::Duration::from_millis(ms)
+}
 
-    let router_config = RouterConfig {
-        processing_pools: req.processing_pools
+async fn monitoring_snapshot(state: &AppState) -> MonitoringStreamSnapshot {
+    MonitoringStreamSnapshot {
+        timestamp: Utc::now().to_rfc3339(),
+        pools: state.queue_manager.get_pool_stats(),
+        queues: state.queue_manager.get_queue_metrics().await
             .into_iter()
-            .map(|p| PoolConfig {
-                code: p.code,
-                concurrency: p.concurrency,
-                rate_limit_per_minute: p.rate_limit_per_minute,
-            })
+            .map(QueueMetricsResponse::from)
             .collect(),
-        queues: vec![],
-    };
-
-    let pools_before = state.queue_manager.pool_codes().len();
-
-    match state.queue_manager.reload_config(router_config).await {
-        Ok(true) => {
-            let pools_after = state.queue_manager.pool_codes().len();
-            let pool_stats = state.queue_manager.get_pool_stats();
-            let pools_created = pools_after.saturating_sub(pools_before);
-            let pools_removed = pools_before.saturating_sub(pools_after);
-
-            info!(
-                pools_before = pools_before,
-                pools_after = pools_after,
-                pools_created = pools_created,
-                pools_removed = pools_removed,
-                "Configuration reloaded via API"
-            );
+        in_flight: state.queue_manager.in_flight_count(),
+    }
+}
 
-            (StatusCode::OK, Json(ConfigReloadResponse {
-                success: true,
-                pools_updated: 0,
-                pools_created,
-                pools_removed,
-                total_active_pools: pool_stats.len(),
-                total_draining_pools: 0,
-            })).into_response()
-        }
-        Ok(false) => {
-            warn!("Configuration reload was skipped (shutdown in progress)");
-            (StatusCode::SERVICE_UNAVAILABLE, Json(ConfigReloadResponse {
-                success: false,
-                pools_updated: 0,
-                pools_created: 0,
-                pools_removed: 0,
-                total_active_pools: 0,
-                total_draining_pools: 0,
-            })).into_response()
-        }
-        Err(e) => {
-            error!(error = %e, "Failed to reload configuration");
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ConfigReloadResponse {
-                success: false,
-                pools_updated: 0,
-                pools_created: 0,
-                pools_removed: 0,
-                total_active_pools: 0,
-                total_draining_pools: 0,
-            })).into_response()
+/// Push snapshots until the client goes away
+async fn push_monitoring_snapshots(mut socket: WebSocket, state: AppState, interval: std::time::Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let snapshot = monitoring_snapshot(&state).await;
+                let json = match serde_json::to_string(&snapshot) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        error!(error = %e, "Failed to serialize monitoring snapshot");
+                        break;
+                    }
+                };
+                if socket.send(WsMessage::Text(json)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by the WebSocket layer; other frames are ignored
+                Some(Ok(_)) => {}
+            },
         }
     }
+
+    debug!("Monitoring stream closed");
 }
 
-/// Update pool configuration
+/// Queue metrics
 #[utoipa::path(
-    put,
-    path = "/monitoring/pools/{pool_code}",
+    get,
+    path = "/monitoring/queues",
     tag = "monitoring",
-    params(
-        ("pool_code" = String, Path, description = "Pool code to update")
-    ),
-    request_body = PoolConfigUpdateRequest,
     responses(
-        (status = 200, description = "Pool updated"),
-        (status = 500, description = "Internal error")
+        (status = 200, description = "Queue metrics", body = Vec<QueueMetricsResponse>)
     )
 )]
-async fn update_pool_config(
-    State(state): State<AppState>,
-    Path(pool_code): Path<String>,
-    Json(req): Json<PoolConfigUpdateRequest>,
-) -> Response {
-    let existing_stats: Option<PoolStats> = state.queue_manager
-        .get_pool_stats()
-        .into_iter()
-        .find(|s| s.pool_code == pool_code);
-
-    let new_config = match existing_stats {
-        Some(stats) => PoolConfig {
-            code: pool_code.clone(),
-            concurrency: req.concurrency.unwrap_or(stats.concurrency),
-            rate_limit_per_minute: if req.rate_limit_per_minute.is_some() {
-                req.rate_limit_per_minute
-            } else {
-                stats.rate_limit_per_minute
-            },
-        },
-        None => PoolConfig {
-            code: pool_code.clone(),
-            concurrency: req.concurrency.unwrap_or(10),
-            rate_limit_per_minute: req.rate_limit_per_minute,
-        },
-    };
-
-    match state.queue_manager.update_pool_config(&pool_code, new_config.clone()).await {
-        Ok(_) => {
-            info!(pool_code = %pool_code, "Pool configuration updated via API");
-            (StatusCode::OK, Json(serde_json::json!({
-                "success": true,
-                "pool_code": pool_code,
-                "new_config": {
-                    "concurrency": new_config.concurrency,
-                    "rate_limit_per_minute": new_config.rate_limit_per_minute,
-                }
-            }))).into_response()
-        }
-        Err(e) => {
-            error!(pool_code = %pool_code, error = %e, "Failed to update pool configuration");
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
-                "success": false,
-                "error": e.to_string(),
-            }))).into_response()
-        }
-    }
+async fn queue_metrics_handler(State(state): State<AppState>) -> Json<Vec<QueueMetricsResponse>> {
+    let metrics = state.queue_manager.get_queue_metrics().await;
+    Json(metrics.into_iter().map(QueueMetricsResponse::from).collect())
 }
 
 // ============================================================================
 // Warning Endpoints
 // ============================================================================
 
-/// List warnings with optional filters
+/// List warnings with optional filters, one page at a time
 #[utoipa::path(
     get,
     path = "/warnings",
     tag = "warnings",
     params(
         ("severity" = Option<String>, Query, description = "Filter by severity"),
-        ("category" = Option<String>, Query, description = "Filter by category"),
-        ("acknowledged" = Option<bool>, Query, description = "Filter by acknowledged status")
+        ("category" = Option<String>, Query, description = "Filter by category, comma-separated for several"),
+        ("acknowledged" = Option<bool>, Query, description = "Filter by acknowledged status"),
+        ("since" = Option<String>, Query, description = "Only warnings created at or after this RFC 3339 time"),
+        ("limit" = Option<usize>, Query, description = "Page size (default 100, at most 1000)"),
+        ("offset" = Option<usize>, Query, description = "Matching warnings to skip (default 0)")
     ),
     responses(
-        (status = 200, description = "List of warnings", body = Vec<Warning>)
+        (status = 200, description = "Page of warnings", body = WarningsPage),
+        (status = 400, description = "Invalid query parameters", body = ValidationErrorResponse)
     )
 )]
 async fn list_warnings(
     State(state): State<AppState>,
     Query(query): Query<WarningsQuery>,
-) -> Json<Vec<Warning>> {
-    let mut warnings = if let Some(false) = query.acknowledged {
-        state.warning_service.get_unacknowledged_warnings()
-    } else {
-        state.warning_service.get_all_warnings()
+) -> Response {
+    let mut violations = Vec::new();
+
+    let limit = query.limit.unwrap_or(DEFAULT_WARNINGS_PAGE_SIZE);
+    if !(1..=MAX_WARNINGS_PAGE_SIZE).contains(&limit) {
+        violations.push(FieldViolation::new(
+            "limit",
+            format!("must be between 1 and {} (got {})", MAX_WARNINGS_PAGE_SIZE, limit),
+        ));
+    }
+
+    let since = match query.since.as_deref().map(chrono::DateTime::parse_from_rfc3339) {
+        Some(Ok(since)) => Some(since.with_timezone(&Utc)),
+        Some(Err(e)) => {
+            violations.push(FieldViolation::new("since", format!("must be an RFC 3339 timestamp ({})", e)));
+            None
+        }
+        None => None,
     };
 
+    if !violations.is_empty() {
+        warn!(violations = ?violations, "Rejected invalid warnings query");
+        return (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+            success: false,
+            error: "Invalid warnings query".to_string(),
+            violations,
+        })).into_response();
+    }
+
+    // Includes persisted warnings not held in memory
+    let mut warnings = state.warning_service.get_all_warnings();
+    if let Some(false) = query.acknowledged {
+        warnings.retain(|w| !w.acknowledged);
+    }
+
     // Filter by severity if specified
-    if let Some(ref sev_str) = query.severity {
-        let severity = match sev_str.to_uppercase().as_str() {
-            "INFO" => Some(WarningSeverity::Info),
-            "WARN" | "WARNING" => Some(WarningSeverity::Warn),
-            "ERROR" => Some(WarningSeverity::Error),
-            "CRITICAL" => Some(WarningSeverity::Critical),
-            _ => None,
-        };
-        if let Some(sev) = severity {
-            warnings.retain(|w| w.severity == sev);
-        }
+    if let Some(severity) = query.severity.as_deref().and_then(parse_warning_severity) {
+        warnings.retain(|w| w.severity == severity);
     }
 
-    // Filter by category if specified
+    // Filter by category if specified; unknown names are ignored
     if let Some(ref cat_str) = query.category {
-        let category = match cat_str.to_uppercase().as_str() {
-            "ROUTING" => Some(WarningCategory::Routing),
-            "PROCESSING" => Some(WarningCategory::Processing),
-            "CONFIGURATION" => Some(WarningCategory::Configuration),
-            "GROUPTHREADRESTART" => Some(WarningCategory::GroupThreadRestart),
-            "RATELIMITING" => Some(WarningCategory::RateLimiting),
-            "QUEUECONNECTIVITY" => Some(WarningCategory::QueueConnectivity),
-            "POOLCAPACITY" => Some(WarningCategory::PoolCapacity),
-            "CONSUMERHEALTH" => Some(WarningCategory::ConsumerHealth),
-            "RESOURCE" => Some(WarningCategory::Resource),
-            _ => None,
-        };
-        if let Some(cat) = category {
-            warnings.retain(|w| w.category == cat);
+        let categories: Vec<WarningCategory> = cat_str
+            .split(',')
+            .filter_map(|c| parse_warning_category(c.trim()))
+            .collect();
+        if !categories.is_empty() {
+            warnings.retain(|w| categories.contains(&w.category));
         }
     }
 
+    if let Some(since) = since {
+        warnings.retain(|w| w.created_at >= since);
+    }
+
     // Sort by created_at descending (newest first)
     warnings.sort_by(|a, b| b.created_at.cmp(&a.created_at));
 
-    Json(warnings)
+    let total = warnings.len();
+    let offset = query.offset.unwrap_or(0);
+    let warnings = warnings.into_iter().skip(offset).take(limit).collect();
+
+    Json(WarningsPage { warnings, total, limit, offset }).into_response()
+}
+
+fn parse_warning_severity(value: &str) -> Option<WarningSeverity> {
+    match value.to_uppercase().as_str() {
+        "INFO" => Some(WarningSeverity::Info),
+        "WARN" | "WARNING" => Some(WarningSeverity::Warn),
+        "ERROR" => Some(WarningSeverity::Error),
+        "CRITICAL" => Some(WarningSeverity::Critical),
+        _ => None,
+    }
+}
+
+fn parse_warning_category(value: &str) -> Option<WarningCategory> {
+    match value.to_uppercase().as_str() {
+        "ROUTING" => Some(WarningCategory::Routing),
+        "PROCESSING" => Some(WarningCategory::Processing),
+        "CONFIGURATION" => Some(WarningCategory::Configuration),
+        "GROUPTHREADRESTART" => Some(WarningCategory::GroupThreadRestart),
+        "RATELIMITING" => Some(WarningCategory::RateLimiting),
+        "QUEUECONNECTIVITY" => Some(WarningCategory::QueueConnectivity),
+        "POOLCAPACITY" => Some(WarningCategory::PoolCapacity),
+        "CONSUMERHEALTH" => Some(WarningCategory::ConsumerHealth),
+        "RESOURCE" => Some(WarningCategory::Resource),
+        _ => None,
+    }
 }
 
 /// Acknowledge a warning
@@ -884,7 +1236,7 @@ async fn dashboard_health_handler(State(state): State<AppState>) -> Json<Dashboa
             healthy_pools: health_report.pools_healthy,
             active_warnings: health_report.active_warnings,
             critical_warnings: health_report.critical_warnings,
-            circuit_breakers_open: 0,
+            circuit_breakers_open: state.circuit_breaker_registry.open_count() as u32,
             degradation_reason,
         }),
     })
@@ -937,12 +1289,14 @@ struct DashboardQueueStats {
     path = "/monitoring/queue-stats",
     tag = "monitoring",
     responses(
-        (status = 200, description = "Queue stats for dashboard")
+        (status = 200, description = "Queue stats for dashboard"),
+        (status = 304, description = "Unchanged since the `ETag` given in `If-None-Match`")
     )
 )]
-async fn dashboard_queue_stats_handler(State(state): State<AppState>) -> Json<HashMap<String, DashboardQueueStats>> {
+async fn dashboard_queue_stats_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
     let metrics = state.queue_manager.get_queue_metrics().await;
-    let mut result = HashMap::new();
+    // Ordered so the same stats always hash to the same ETag
+    let mut result = BTreeMap::new();
 
     for m in metrics {
         // pending_messages = messages waiting in queue
@@ -982,7 +1336,8 @@ async fn dashboard_queue_stats_handler(State(state): State<AppState>) -> Json<Ha
         result.insert(m.queue_identifier, stats);
     }
 
-    Json(result)
+    let etag = json_etag(&result);
+    conditional_json(&headers, etag, || result)
 }
 
 /// Pool stats for dashboard (matches Java PoolStats)
@@ -990,6 +1345,8 @@ async fn dashboard_queue_stats_handler(State(state): State<AppState>) -> Json<Ha
 struct DashboardPoolStats {
     #[serde(rename = "poolCode")]
     pool_code: String,
+    /// All-time totals are per pool instance: kept across config updates,
+    /// reset when a pool is removed and re-created
     #[serde(rename = "totalProcessed")]
     total_processed: u64,
     #[serde(rename = "totalSucceeded")]
@@ -1034,6 +1391,8 @@ struct DashboardPoolStats {
     success_rate_30min: f64,
     #[serde(rename = "totalRateLimited30min")]
     total_rate_limited_30min: u64,
+    #[serde(rename = "isDraining")]
+    is_draining: bool,
 }
 
 /// Pool stats endpoint for dashboard
@@ -1047,13 +1406,17 @@ struct DashboardPoolStats {
 )]
 async fn dashboard_pool_stats_handler(State(state): State<AppState>) -> Json<HashMap<String, DashboardPoolStats>> {
     let pool_stats = state.queue_manager.get_pool_stats();
+    let pool_metrics = state.queue_manager.get_pool_metrics();
     let mut result = HashMap::new();
 
     for s in pool_stats {
-        // Extract metrics from the enhanced metrics if available
+        let metrics = pool_metrics.get(&s.pool_code);
+        let latency = state.queue_manager.get_latency_percentiles(&s.pool_code);
+
+        // Extract all-time and sliding-window metrics if available
         let (total_success, total_failure, success_rate, avg_processing_time,
              success_5min, failure_5min, rate_5min,
-             success_30min, failure_30min, rate_30min) = if let Some(ref m) = s.metrics {
+             success_30min, failure_30min, rate_30min) = if let Some(m) = metrics {
             (
                 m.total_success,
                 m.total_failure,
@@ -1071,7 +1434,7 @@ async fn dashboard_pool_stats_handler(State(state): State<AppState>) -> Json<Has
         };
 
         // Extract rate limited counts from metrics if available
-        let (rate_limited_total, rate_limited_5min, rate_limited_30min) = if let Some(ref m) = s.metrics {
+        let (rate_limited_total, rate_limited_5min, rate_limited_30min) = if let Some(m) = metrics {
             (
                 m.total_rate_limited,
                 m.last_5_min.rate_limited_count,
@@ -1089,11 +1452,13 @@ async fn dashboard_pool_stats_handler(State(state): State<AppState>) -> Json<Has
             total_rate_limited: rate_limited_total,
             success_rate,
             active_workers: s.active_workers,
-            available_permits: s.concurrency.saturating_sub(s.active_workers),
+            // Draining pools accept no new work
+            available_permits: if s.is_draining { 0 } else { s.concurrency.saturating_sub(s.active_workers) },
             max_concurrency: s.concurrency,
             queue_size: s.queue_size,
             max_queue_capacity: s.queue_capacity,
-            average_processing_time_ms: avg_processing_time,
+            // End-to-end when available, otherwise mediation time alone
+            average_processing_time_ms: latency.map(|l| l.avg_ms).unwrap_or(avg_processing_time),
             // 5 minute window
             total_processed_5min: success_5min + failure_5min,
             total_succeeded_5min: success_5min,
@@ -1106,8 +1471,10 @@ async fn dashboard_pool_stats_handler(State(state): State<AppState>) -> Json<Has
             total_failed_30min: failure_30min,
             success_rate_30min: rate_30min,
             total_rate_limited_30min: rate_limited_30min,
+            is_draining: s.is_draining,
         };
-        result.insert(s.pool_code, stats);
+        // A re-created pool shares its code with the draining one; show the active pool
+        result.entry(s.pool_code).or_insert(stats);
     }
 
     Json(result)
@@ -1190,7 +1557,7 @@ async fn dashboard_circuit_breakers_handler(
         .map(|(name, s)| {
             (name, DashboardCircuitBreakerStats {
                 name: s.name,
-                state: format!("{:?}", s.state).to_uppercase(),
+                state: s.state.as_str().to_string(),
                 successful_calls: s.successful_calls,
                 failed_calls: s.failed_calls,
                 rejected_calls: s.rejected_calls,
@@ -1203,116 +1570,12 @@ async fn dashboard_circuit_breakers_handler(
     Json(result)
 }
 
-/// Query params for in-flight messages
-#[derive(Deserialize, Default, ToSchema)]
-struct InFlightMessagesQuery {
-    limit: Option<usize>,
-    #[serde(rename = "messageId")]
-    message_id: Option<String>,
-}
-
-/// In-flight messages endpoint for dashboard
-#[utoipa::path(
-    get,
-    path = "/monitoring/in-flight-messages",
-    tag = "monitoring",
-    params(
-        ("limit" = Option<usize>, Query, description = "Maximum number of messages to return"),
-        ("messageId" = Option<String>, Query, description = "Filter by message ID")
-    ),
-    responses(
-        (status = 200, description = "In-flight messages", body = Vec<InFlightMessageInfo>)
-    )
-)]
-async fn dashboard_in_flight_messages_handler(
-    State(state): State<AppState>,
-    Query(query): Query<InFlightMessagesQuery>,
-) -> Json<Vec<InFlightMessageInfo>> {
-    let limit = query.limit.unwrap_or(100);
-    let messages = state.queue_manager.get_in_flight_messages(limit, query.message_id.as_deref());
-    Json(messages)
-}
-
 /// Serve dashboard HTML
 async fn dashboard_html_handler() -> impl IntoResponse {
     const DASHBOARD_HTML: &str = include_str!("../../resources/dashboard.html");
     Html(DASHBOARD_HTML)
 }
 
-// ============================================================================
-// Message Publishing
-// ============================================================================
-
-/// Publish a message
-#[utoipa::path(
-    post,
-    path = "/messages",
-    tag = "messages",
-    request_body = PublishMessageRequest,
-    responses(
-        (status = 200, description = "Message published", body = PublishMessageResponse),
-        (status = 500, description = "Failed to publish")
-    )
-)]
-async fn publish_message(
-    State(state): State<AppState>,
-    Json(req): Json<PublishMessageRequest>,
-) -> Response {
-    let message_id = Uuid::new_v4().to_string();
-
-    let message = Message {
-        id: message_id.clone(),
-        pool_code: req.pool_code.unwrap_or_else(|| "DEFAULT".to_string()),
-        auth_token: None,
-        signing_secret: None,
-        mediation_type: MediationType::HTTP,
-        mediation_target: req.mediation_target.unwrap_or_else(|| "http://localhost:8080/echo".to_string()),
-        message_group_id: req.message_group_id,
-    };
-
-    match state.publisher.publish(message).await {
-        Ok(_) => {
-            (StatusCode::OK, Json(PublishMessageResponse {
-                message_id,
-                status: "ACCEPTED".to_string(),
-            })).into_response()
-        }
-        Err(_) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": "Failed to publish message" }))).into_response()
-        }
-    }
-}
-
-/// Simple publish message (for simple router)
-async fn simple_publish_message(
-    State(state): State<SimpleState>,
-    Json(req): Json<PublishMessageRequest>,
-) -> Response {
-    let message_id = Uuid::new_v4().to_string();
-
-    let message = Message {
-        id: message_id.clone(),
-        pool_code: req.pool_code.unwrap_or_else(|| "DEFAULT".to_string()),
-        auth_token: None,
-        signing_secret: None,
-        mediation_type: MediationType::HTTP,
-        mediation_target: req.mediation_target.unwrap_or_else(|| "http://localhost:8080/echo".to_string()),
-        message_group_id: req.message_group_id,
-    };
-
-    match state.publisher.publish(message).await {
-        Ok(_) => {
-            (StatusCode::OK, Json(PublishMessageResponse {
-                message_id,
-                status: "ACCEPTED".to_string(),
-            })).into_response()
-        }
-        Err(_) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": "Failed to publish message" }))).into_response()
-        }
-    }
-}
-
 // ============================================================================
 // Additional Warning Endpoints (Java compatibility)
 // ============================================================================
@@ -1434,97 +1697,6 @@ async fn clear_old_warnings(
     Json(serde_json::json!({ "status": "success", "removed": removed }))
 }
 
-// ============================================================================
-// Circuit Breaker Endpoints
-// ============================================================================
-
-/// Circuit breaker state response
-#[derive(Serialize, ToSchema)]
-struct CircuitBreakerStateResponse {
-    name: String,
-    state: String,
-}
-
-/// Get circuit breaker state
-#[utoipa::path(
-    get,
-    path = "/monitoring/circuit-breakers/{name}/state",
-    tag = "circuit-breakers",
-    params(
-        ("name" = String, Path, description = "Circuit breaker name (URL-encoded)")
-    ),
-    responses(
-        (status = 200, description = "Circuit breaker state", body = CircuitBreakerStateResponse),
-        (status = 404, description = "Circuit breaker not found")
-    )
-)]
-async fn get_circuit_breaker_state(
-    State(state): State<AppState>,
-    Path(name): Path<String>,
-) -> Response {
-    // URL decode the name
-    let decoded_name = urlencoding::decode(&name).unwrap_or(std::borrow::Cow::Borrowed(&name));
-
-    match state.circuit_breaker_registry.get_state(&decoded_name) {
-        Some(breaker_state) => {
-            let state_str = match breaker_state {
-                CircuitBreakerState::Closed => "CLOSED",
-                CircuitBreakerState::Open => "OPEN",
-                CircuitBreakerState::HalfOpen => "HALF_OPEN",
-            };
-            (StatusCode::OK, Json(CircuitBreakerStateResponse {
-                name: decoded_name.to_string(),
-                state: state_str.to_string(),
-            })).into_response()
-        }
-        None => {
-            (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Circuit breaker not found" }))).into_response()
-        }
-    }
-}
-
-/// Reset a circuit breaker
-#[utoipa::path(
-    post,
-    path = "/monitoring/circuit-breakers/{name}/reset",
-    tag = "circuit-breakers",
-    params(
-        ("name" = String, Path, description = "Circuit breaker name (URL-encoded)")
-    ),
-    responses(
-        (status = 200, description = "Circuit breaker reset"),
-        (status = 500, description = "Failed to reset")
-    )
-)]
-async fn reset_circuit_breaker(
-    State(state): State<AppState>,
-    Path(name): Path<String>,
-) -> Response {
-    let decoded_name = urlencoding::decode(&name).unwrap_or(std::borrow::Cow::Borrowed(&name));
-
-    if state.circuit_breaker_registry.reset(&decoded_name) {
-        info!(name = %decoded_name, "Circuit breaker reset");
-        (StatusCode::OK, Json(serde_json::json!({ "status": "success" }))).into_response()
-    } else {
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": "Failed to reset circuit breaker" }))).into_response()
-    }
-}
-
-/// Reset all circuit breakers
-#[utoipa::path(
-    post,
-    path = "/monitoring/circuit-breakers/reset-all",
-    tag = "circuit-breakers",
-    responses(
-        (status = 200, description = "All circuit breakers reset")
-    )
-)]
-async fn reset_all_circuit_breakers(State(state): State<AppState>) -> Json<serde_json::Value> {
-    state.circuit_breaker_registry.reset_all();
-    info!("All circuit breakers reset");
-    Json(serde_json::json!({ "status": "success" }))
-}
-
 // ============================================================================
 // Standby/Traffic Status Endpoints
 // ============================================================================
@@ -1748,7 +1920,11 @@ async fn stream_readiness_handler(State(state): State<AppState>) -> Response {
     )
 )]
 async fn get_local_config(State(state): State<AppState>) -> Json<serde_json::Value> {
-    let pool_stats = state.queue_manager.get_pool_stats();
+    let pool_stats: Vec<PoolStats> = state.queue_manager
+        .get_pool_stats()
+        .into_iter()
+        .filter(|s| !s.is_draining)
+        .collect();
     let dev_mode = std::env::var("FLOWCATALYST_DEV_MODE")
         .map(|v| v == "true" || v == "1")
         .unwrap_or(false);
@@ -1757,7 +1933,7 @@ async fn get_local_config(State(state): State<AppState>) -> Json<serde_json::Val
         // Return default dev pools
         vec![
             serde_json::json!({
-                "code": "DEFAULT",
+                "code": DEFAULT_POOL_CODE,
                 "concurrency": 10,
                 "rateLimitPerMinute": null,
             }),
@@ -1887,12 +2063,20 @@ async fn seed_messages(
 
         let message = Message {
             id: Uuid::new_v4().to_string(),
-            pool_code: "DEFAULT".to_string(),
+            pool_code: DEFAULT_POOL_CODE.to_string(),
             auth_token: None,
             signing_secret: None,
             mediation_type: MediationType::HTTP,
             mediation_target: target.to_string(),
             message_group_id,
+            payload: None,
+            raw_body_base64: None,
+            content_type: None,
+            client_id: None,
+            timeout_seconds: None,
+            max_retries: None,
+            correlation_id: None,
+            traceparent: None,
         };
 
         if state.publisher.publish(message).await.is_ok() {
@@ -2072,6 +2256,10 @@ async fn reset_test_stats() -> Json<serde_json::Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::messages::publish_message;
+    use super::pools::pool_stats_handler;
+    use fc_common::PoolConfig;
+    use fc_queue::BatchPublishResult;
 
     #[test]
     fn test_severity_parsing() {
@@ -2085,14 +2273,491 @@ mod tests {
         ];
 
         for (input, expected) in cases {
-            let result = match input.to_uppercase().as_str() {
-                "INFO" => Some(WarningSeverity::Info),
-                "WARN" | "WARNING" => Some(WarningSeverity::Warn),
-                "ERROR" => Some(WarningSeverity::Error),
-                "CRITICAL" => Some(WarningSeverity::Critical),
-                _ => None,
+            assert_eq!(parse_warning_severity(input), expected);
+        }
+    }
+
+    /// Minimal queue that acts as both publisher and consumer
+    pub(super) struct StubQueue;
+
+    #[async_trait::async_trait]
+    impl QueuePublisher for StubQueue {
+        fn identifier(&self) -> &str {
+            "stub-queue"
+        }
+
+        async fn publish(&self, message: Message) -> fc_queue::Result<String> {
+            Ok(message.id)
+        }
+
+        async fn publish_batch(&self, messages: Vec<Message>) -> fc_queue::Result<BatchPublishResult> {
+            Ok(BatchPublishResult {
+                successes: messages.into_iter().map(|m| m.id).collect(),
+                failures: vec![],
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl fc_queue::QueueConsumer for StubQueue {
+        fn identifier(&self) -> &str {
+            "stub-queue"
+        }
+
+        async fn poll(&self, _max_messages: u32) -> fc_queue::Result<Vec<fc_common::QueuedMessage>> {
+            Ok(vec![])
+        }
+
+        async fn ack(&self, _receipt_handle: &str) -> fc_queue::Result<()> {
+            Ok(())
+        }
+
+        async fn nack(&self, _receipt_handle: &str, _delay_seconds: Option<u32>) -> fc_queue::Result<()> {
+            Ok(())
+        }
+
+        async fn extend_visibility(&self, _receipt_handle: &str, _seconds: u32) -> fc_queue::Result<()> {
+            Ok(())
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+
+        async fn stop(&self) {}
+
+        async fn get_metrics(&self) -> fc_queue::Result<Option<QueueMetrics>> {
+            Ok(Some(QueueMetrics {
+                queue_identifier: "stub-queue".to_string(),
+                pending_messages: 7,
+                in_flight_messages: 2,
+                ..Default::default()
+            }))
+        }
+    }
+
+    /// Consumer whose broker can't be reached
+    struct UnreachableQueue;
+
+    #[async_trait::async_trait]
+    impl fc_queue::QueueConsumer for UnreachableQueue {
+        fn identifier(&self) -> &str {
+            "down-queue"
+        }
+
+        async fn poll(&self, _max_messages: u32) -> fc_queue::Result<Vec<fc_common::QueuedMessage>> {
+            Ok(vec![])
+        }
+
+        async fn ack(&self, _receipt_handle: &str) -> fc_queue::Result<()> {
+            Ok(())
+        }
+
+        async fn nack(&self, _receipt_handle: &str, _delay_seconds: Option<u32>) -> fc_queue::Result<()> {
+            Ok(())
+        }
+
+        async fn extend_visibility(&self, _receipt_handle: &str, _seconds: u32) -> fc_queue::Result<()> {
+            Ok(())
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+
+        async fn stop(&self) {}
+
+        async fn get_metrics(&self) -> fc_queue::Result<Option<QueueMetrics>> {
+            Err(fc_queue::QueueError::Sqs("connection refused".to_string()))
+        }
+    }
+
+    pub(super) struct NoopMediator;
+
+    #[async_trait::async_trait]
+    impl crate::Mediator for NoopMediator {
+        async fn mediate(&self, _message: &Message) -> fc_common::MediationOutcome {
+            fc_common::MediationOutcome::success()
+        }
+    }
+
+    pub(super) async fn create_test_state() -> AppState {
+        let queue_manager = Arc::new(QueueManager::new(Arc::new(NoopMediator)));
+        queue_manager.apply_config(fc_common::RouterConfig {
+            processing_pools: vec![PoolConfig {
+                code: "POOL-A".to_string(),
+                concurrency: 4,
+                rate_limit_per_minute: Some(60),
+                ..Default::default()
+            }],
+            queues: vec![],
+        }).await.unwrap();
+        queue_manager.add_consumer(Arc::new(StubQueue)).await;
+
+        let warning_service = Arc::new(WarningService::default());
+        warning_service.add_warning(
+            WarningCategory::Configuration,
+            WarningSeverity::Critical,
+            "test critical".to_string(),
+            "test".to_string(),
+        );
+        warning_service.add_warning(
+            WarningCategory::Processing,
+            WarningSeverity::Warn,
+            "test warning".to_string(),
+            "test".to_string(),
+        );
+
+        let health_service = Arc::new(HealthService::new(
+            crate::HealthServiceConfig::default(),
+            warning_service.clone(),
+        ));
+        health_service.set_consumer_running("stub-queue", true);
+        health_service.record_consumer_poll("stub-queue");
+
+        AppState {
+            publisher: Arc::new(StubQueue),
+            queue_manager,
+            warning_service,
+            health_service,
+            circuit_breaker_registry: Arc::new(CircuitBreakerRegistry::default()),
+            standby_enabled: false,
+            instance_id: "test".to_string(),
+            stream_health_service: None,
+            messages_published: Arc::new(AtomicU64::new(0)),
+            max_publish_batch_size: DEFAULT_MAX_PUBLISH_BATCH_SIZE,
+            reject_publish_when_saturated: false,
+            publish_pressure_threshold: None,
+            max_in_flight_page_size: DEFAULT_MAX_IN_FLIGHT_PAGE_SIZE,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            body_format: BodyFormat::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_monitoring_summary_matches_individual_endpoints() {
+        let state = create_test_state().await;
+
+        let Json(summary) = monitoring_summary_handler(State(state.clone())).await;
+        let pools: Vec<PoolStats> = json_body(pool_stats_handler(State(state.clone()), HeaderMap::new()).await).await;
+        let Json(queues) = queue_metrics_handler(State(state.clone())).await;
+        let monitoring: serde_json::Value = json_body(monitoring_handler(State(state.clone()), HeaderMap::new()).await).await;
+
+        // Windowed metrics carry a `windowStart` timestamp, so compare without them
+        let without_metrics = |stats: &[PoolStats]| -> Vec<PoolStats> {
+            stats.iter().cloned().map(|s| PoolStats { metrics: None, ..s }).collect()
+        };
+        assert_eq!(summary.pools.len(), pools.len());
+        assert_eq!(
+            serde_json::to_value(without_metrics(&summary.pools)).unwrap(),
+            serde_json::to_value(without_metrics(&pools)).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&summary.queues).unwrap(),
+            serde_json::to_value(&queues).unwrap()
+        );
+
+        assert_eq!(summary.consumers.len(), 1);
+        assert_eq!(summary.consumers[0].queue_identifier, "stub-queue");
+        assert!(summary.consumers[0].is_healthy);
+
+        assert_eq!(summary.warnings.total, 2);
+        assert_eq!(summary.warnings.unacknowledged as u64, monitoring["active_warnings"].as_u64().unwrap());
+        assert_eq!(summary.warnings.critical as u64, monitoring["critical_warnings"].as_u64().unwrap());
+        assert_eq!(summary.warnings.critical, 1);
+
+        // A drained pool and its replacement are listed apart
+        state.queue_manager.drain_pool("POOL-A").await.unwrap();
+        state.queue_manager.update_pool_config("POOL-A", PoolConfig {
+            code: "POOL-A".to_string(),
+            ..Default::default()
+        }).await.unwrap();
+        let Json(summary) = monitoring_summary_handler(State(state)).await;
+        assert_eq!(summary.pools.len(), 1);
+        assert!(!summary.pools[0].is_draining);
+        assert_eq!(summary.draining_pools.len(), 1);
+        assert_eq!(summary.draining_pools[0].pool_code, "POOL-A");
+    }
+
+    #[test]
+    fn test_stream_interval_clamped() {
+        assert_eq!(stream_interval(None).as_millis() as u64, DEFAULT_STREAM_INTERVAL_MS);
+        assert_eq!(stream_interval(Some(5000)).as_millis(), 5000);
+        assert_eq!(stream_interval(Some(0)).as_millis() as u64, MIN_STREAM_INTERVAL_MS);
+        assert_eq!(stream_interval(Some(u64::MAX)).as_millis() as u64, MAX_STREAM_INTERVAL_MS);
+    }
+
+    #[tokio::test]
+    async fn test_monitoring_snapshot_combines_pools_queues_and_in_flight() {
+        let state = create_test_state().await;
+
+        let snapshot = monitoring_snapshot(&state).await;
+        let Json(queues) = queue_metrics_handler(State(state.clone())).await;
+
+        assert_eq!(snapshot.pools.len(), 1);
+        assert_eq!(snapshot.pools[0].pool_code, "POOL-A");
+        assert_eq!(
+            serde_json::to_value(&snapshot.queues).unwrap(),
+            serde_json::to_value(&queues).unwrap()
+        );
+        assert_eq!(snapshot.in_flight, state.queue_manager.in_flight_count());
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_circuit_breakers_reports_registry() {
+        let state = create_test_state().await;
+        let registry = &state.circuit_breaker_registry;
+        registry.record_success("api.example.com");
+        for _ in 0..10 {
+            registry.record_failure("down.example.com:8443");
+        }
+        assert!(!registry.allow_request("down.example.com:8443"));
+
+        let Json(breakers) = dashboard_circuit_breakers_handler(State(state.clone())).await;
+        assert_eq!(breakers.len(), 2);
+
+        let healthy = &breakers["api.example.com"];
+        assert_eq!(healthy.state, "CLOSED");
+        assert_eq!(healthy.successful_calls, 1);
+        assert_eq!(healthy.failure_rate, 0.0);
+
+        let down = &breakers["down.example.com:8443"];
+        assert_eq!(down.state, "OPEN");
+        assert_eq!(down.failed_calls, 10);
+        assert_eq!(down.rejected_calls, 1);
+        assert_eq!(down.failure_rate, 1.0);
+        assert_eq!(down.buffered_calls, 10);
+
+        let Json(health) = dashboard_health_handler(State(state)).await;
+        assert_eq!(health.details.unwrap().circuit_breakers_open, 1);
+    }
+
+    async fn get_warnings(state: &AppState, query: serde_json::Value) -> (StatusCode, serde_json::Value) {
+        let query: WarningsQuery = serde_json::from_value(query).unwrap();
+        let response = list_warnings(State(state.clone()), Query(query)).await;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_warnings_paginates_newest_first() {
+        let state = create_test_state().await;
+        for i in 0..5 {
+            state.warning_service.add_warning(
+                WarningCategory::Routing,
+                WarningSeverity::Info,
+                format!("routing {}", i),
+                "test".to_string(),
+            );
+        }
+
+        let (status, body) = get_warnings(&state, serde_json::json!({"limit": 3, "offset": 2})).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 7);
+        assert_eq!(body["limit"], 3);
+        assert_eq!(body["offset"], 2);
+        let warnings = body["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 3);
+        let created: Vec<chrono::DateTime<Utc>> = warnings.iter()
+            .map(|w| serde_json::from_value(w["created_at"].clone()).unwrap())
+            .collect();
+        assert!(created.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        // Past the end is an empty page, not an error
+        let (_, body) = get_warnings(&state, serde_json::json!({"offset": 50})).await;
+        assert_eq!(body["total"], 7);
+        assert!(body["warnings"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_warnings_filters_multiple_categories() {
+        let state = create_test_state().await;
+        state.warning_service.add_warning(
+            WarningCategory::Routing,
+            WarningSeverity::Info,
+            "routing".to_string(),
+            "test".to_string(),
+        );
+
+        let (_, body) = get_warnings(&state, serde_json::json!({"category": "processing, CONFIGURATION"})).await;
+        assert_eq!(body["total"], 2);
+
+        // Single-value filters behave as before
+        let (_, body) = get_warnings(&state, serde_json::json!({"category": "ROUTING"})).await;
+        assert_eq!(body["total"], 1);
+        let (_, body) = get_warnings(&state, serde_json::json!({"severity": "CRITICAL"})).await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["warnings"][0]["message"], "test critical");
+    }
+
+    #[tokio::test]
+    async fn test_list_warnings_since() {
+        let state = create_test_state().await;
+        let past = (Utc::now() - chrono::Duration::hours(1)).to_rfc3339();
+        let future = (Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+
+        let (_, body) = get_warnings(&state, serde_json::json!({"since": past})).await;
+        assert_eq!(body["total"], 2);
+        let (_, body) = get_warnings(&state, serde_json::json!({"since": future})).await;
+        assert_eq!(body["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_warnings_rejects_invalid_query() {
+        let state = create_test_state().await;
+
+        let (status, body) = get_warnings(&state, serde_json::json!({"since": "yesterday", "limit": 0})).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let fields: Vec<&str> = body["violations"].as_array().unwrap()
+            .iter()
+            .map(|v| v["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, ["limit", "since"]);
+
+        let (status, _) = get_warnings(&state, serde_json::json!({"limit": MAX_WARNINGS_PAGE_SIZE + 1})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    pub(super) async fn json_body<T: serde::de::DeserializeOwned>(response: Response) -> T {
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    pub(super) fn if_none_match(etag: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(etag).unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_monitoring_endpoints_support_conditional_get() {
+        let state = create_test_state().await;
+
+        for path in ["/monitoring", "/monitoring/pools", "/monitoring/queue-stats"] {
+            let get = |headers: HeaderMap| {
+                let state = state.clone();
+                async move {
+                    match path {
+                        "/monitoring" => monitoring_handler(State(state), headers).await,
+                        "/monitoring/pools" => pool_stats_handler(State(state), headers).await,
+                        _ => dashboard_queue_stats_handler(State(state), headers).await,
+                    }
+                }
+            };
+
+            let first = get(HeaderMap::new()).await;
+            assert_eq!(first.status(), StatusCode::OK, "{}", path);
+            let etag = first.headers()[header::ETAG].to_str().unwrap().to_string();
+            assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+            let unchanged = get(if_none_match(&etag)).await;
+            assert_eq!(unchanged.status(), StatusCode::NOT_MODIFIED, "{}", path);
+            assert_eq!(unchanged.headers()[header::ETAG], etag.as_str());
+            let body = axum::body::to_bytes(unchanged.into_body(), usize::MAX).await.unwrap();
+            assert!(body.is_empty());
+
+            let listed = get(if_none_match(&format!("\"other\", W/{}", etag))).await;
+            assert_eq!(listed.status(), StatusCode::NOT_MODIFIED, "{}", path);
+
+            let stale = get(if_none_match("\"stale\"")).await;
+            assert_eq!(stale.status(), StatusCode::OK, "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_causes_when_degraded() {
+        let state = create_test_state().await;
+
+        // The test state carries an unacknowledged critical warning
+        let Json(health) = health_handler(State(state.clone())).await;
+        assert_eq!(health.status, "DEGRADED");
+        let causes = health.causes.expect("causes for a degraded status");
+        assert!(causes.critical_warnings);
+        assert!(causes.queues_healthy);
+        assert!(causes.pools_healthy);
+        assert_eq!(causes.issues, vec!["1 critical warnings".to_string()]);
+
+        state.warning_service.acknowledge_matching(|w| w.severity == WarningSeverity::Critical);
+        let Json(health) = health_handler(State(state)).await;
+        assert_eq!(health.status, "UP");
+        let body = serde_json::to_value(&health).unwrap();
+        assert!(body.get("causes").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reflect_live_state() {
+        let state = create_test_state().await;
+
+        for _ in 0..3 {
+            let req = PublishMessageRequest {
+                payload: serde_json::json!({}),
+                pool_code: Some("POOL-A".to_string()),
+                message_group_id: None,
+                mediation_type: MediationType::HTTP,
+                mediation_target: None,
+                raw_body_base64: None,
+                content_type: None,
+                client_id: None,
+                timeout_seconds: None,
+                max_retries: None,
+                message_id: None,
+                correlation_id: None,
             };
-            assert_eq!(result, expected);
+            let response = publish_message(State(state.clone()), HeaderMap::new(), Json(req)).await;
+            assert_eq!(response.status(), StatusCode::OK);
         }
+
+        let response = metrics_handler(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body.contains("fc_messages_published_total 3\n"));
+        assert!(body.contains("fc_active_pools 1\n"));
+        assert!(body.contains("fc_pool_active_workers{pool_code=\"POOL-A\"} 0\n"));
+        assert!(body.contains("fc_pool_queue_size{pool_code=\"POOL-A\"} 0\n"));
+        assert!(body.contains("fc_pool_available_permits{pool_code=\"POOL-A\"} 4\n"));
+        assert!(body.contains("fc_queue_pending_messages{queue_identifier=\"stub-queue\"} 7\n"));
+        assert!(body.contains("fc_queue_in_flight_messages{queue_identifier=\"stub-queue\"} 2\n"));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_probe_ready_when_queues_reachable() {
+        let state = create_test_state().await;
+        // The seeded critical warning would make readiness fail before the connectivity check
+        state.warning_service.acknowledge_matching(|w| w.severity == WarningSeverity::Critical);
+
+        let response = readiness_probe(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body, serde_json::json!({ "status": "READY" }));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_probe_lists_unreachable_queues() {
+        let state = create_test_state().await;
+        // The seeded critical warning would make readiness fail before the connectivity check
+        state.warning_service.acknowledge_matching(|w| w.severity == WarningSeverity::Critical);
+        state.queue_manager.add_consumer(Arc::new(UnreachableQueue)).await;
+
+        let response = readiness_probe(State(state)).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["status"], "NOT_READY");
+        assert_eq!(body["unreachable_queues"], serde_json::json!(["down-queue"]));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("line\nbreak"), "line\\nbreak");
     }
 }
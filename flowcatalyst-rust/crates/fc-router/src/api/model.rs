@@ -1,18 +1,255 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::{Deserialize, Serialize};
-use fc_common::PoolConfig;
+use fc_common::{Message, MediationType, PoolConfig, RateLimitScope, DEFAULT_POOL_CODE};
 use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub use crate::manager::FieldViolation;
+use crate::manager::{validate_pool_configs, MAX_POOL_CONCURRENCY};
+use crate::mediator::{MAX_MESSAGE_RETRIES, MAX_MESSAGE_TIMEOUT_SECONDS};
+
+/// Longest client-supplied message ID accepted by the publish endpoints
+pub const MAX_MESSAGE_ID_LENGTH: usize = 128;
+
+/// Longest correlation ID accepted by the publish endpoints
+pub const MAX_CORRELATION_ID_LENGTH: usize = 128;
+
+/// Whether a correlation ID can be sent as an HTTP header value
+pub fn is_valid_correlation_id(id: &str) -> bool {
+    !id.is_empty() && id.bytes().all(|b| b.is_ascii_graphic() || b == b' ')
+}
 
 /// Request to publish a message
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct PublishMessageRequest {
-    /// Message payload (JSON)
+    /// Client-supplied message ID (default: generated UUID). Reusing the ID when
+    /// retrying a publish lets the router drop the duplicate instead of delivering twice.
+    pub message_id: Option<String>,
+    /// Message payload (JSON), also used to resolve `{placeholder}`s in the mediation target
     pub payload: serde_json::Value,
     /// Pool code for processing (default: DEFAULT)
     pub pool_code: Option<String>,
     /// Message group ID for FIFO ordering
     pub message_group_id: Option<String>,
-    /// HTTP endpoint to call
+    /// How the message is delivered (default: HTTP)
+    #[serde(default)]
+    pub mediation_type: MediationType,
+    /// Endpoint to deliver to
     pub mediation_target: Option<String>,
+    /// Base64-encoded body delivered verbatim instead of the JSON envelope
+    pub raw_body_base64: Option<String>,
+    /// Content type for `raw_body_base64` (default: application/octet-stream)
+    pub content_type: Option<String>,
+    /// Owning client (tenant) ID, used as a metrics label
+    pub client_id: Option<String>,
+    /// Delivery request timeout in seconds, 1 to 900 (default: the mediator's configured timeout)
+    pub timeout_seconds: Option<u32>,
+    /// Delivery attempts before NACKing back to the queue, at most 10 (default: the mediator's `max_retries`)
+    pub max_retries: Option<u32>,
+    /// Correlation ID for tracing the message through routing and delivery
+    /// (default: the request's `X-Correlation-Id` header)
+    pub correlation_id: Option<String>,
+}
+
+impl PublishMessageRequest {
+    /// Check the client-supplied message ID, correlation ID, raw body, content type
+    /// and delivery overrides, returning a violation for each that is unsafe to use
+    /// or could never be delivered
+    pub fn validate(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(ref id) = self.message_id {
+            if id.is_empty() {
+                violations.push(FieldViolation::new("message_id", "must not be empty"));
+            } else if id.chars().count() > MAX_MESSAGE_ID_LENGTH {
+                violations.push(FieldViolation::new(
+                    "message_id",
+                    format!("must be at most {} characters", MAX_MESSAGE_ID_LENGTH),
+                ));
+            } else if id.chars().any(char::is_control) {
+                violations.push(FieldViolation::new("message_id", "must not contain control characters"));
+            }
+        }
+
+        if let Some(ref correlation_id) = self.correlation_id {
+            if correlation_id.len() > MAX_CORRELATION_ID_LENGTH {
+                violations.push(FieldViolation::new(
+                    "correlation_id",
+                    format!("must be at most {} characters", MAX_CORRELATION_ID_LENGTH),
+                ));
+            } else if !is_valid_correlation_id(correlation_id) {
+                violations.push(FieldViolation::new(
+                    "correlation_id",
+                    "must be non-empty printable ASCII, as it is sent as an HTTP header",
+                ));
+            }
+        }
+
+        if let Some(timeout_seconds) = self.timeout_seconds {
+            if timeout_seconds == 0 || timeout_seconds > MAX_MESSAGE_TIMEOUT_SECONDS {
+                violations.push(FieldViolation::new(
+                    "timeout_seconds",
+                    format!("must be between 1 and {}", MAX_MESSAGE_TIMEOUT_SECONDS),
+                ));
+            }
+        }
+
+        if self.max_retries.is_some_and(|retries| retries > MAX_MESSAGE_RETRIES) {
+            violations.push(FieldViolation::new(
+                "max_retries",
+                format!("must be at most {}", MAX_MESSAGE_RETRIES),
+            ));
+        }
+
+        if let Some(ref raw) = self.raw_body_base64 {
+            if BASE64.decode(raw).is_err() {
+                violations.push(FieldViolation::new("raw_body_base64", "must be valid base64"));
+            }
+        }
+
+        if let Some(ref content_type) = self.content_type {
+            if reqwest::header::HeaderValue::from_str(content_type).is_err() {
+                violations.push(FieldViolation::new(
+                    "content_type",
+                    "must be a valid HTTP header value",
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// Build the queued message, filling in defaults for omitted fields
+    pub fn into_message(self) -> Message {
+        Message {
+            id: self.message_id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            pool_code: self.pool_code.unwrap_or_else(|| DEFAULT_POOL_CODE.to_string()),
+            auth_token: None,
+            signing_secret: None,
+            mediation_type: self.mediation_type,
+            mediation_target: self.mediation_target.unwrap_or_else(|| "http://localhost:8080/echo".to_string()),
+            message_group_id: self.message_group_id,
+            payload: Some(self.payload).filter(|p| !p.is_null()),
+            raw_body_base64: self.raw_body_base64,
+            content_type: self.content_type,
+            client_id: self.client_id,
+            timeout_seconds: self.timeout_seconds,
+            max_retries: self.max_retries,
+            correlation_id: self.correlation_id,
+            traceparent: None,
+        }
+    }
+}
+
+/// Response for a request rejected by validation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub success: bool,
+    pub error: String,
+    pub violations: Vec<FieldViolation>,
+}
+
+/// Request to update pool configuration
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PoolConfigUpdateRequest {
+    /// New concurrency limit
+    pub concurrency: Option<u32>,
+    /// New rate limit (messages per minute)
+    pub rate_limit_per_minute: Option<u32>,
+    /// New rate limit scope: POOL or GROUP
+    pub rate_limit_scope: Option<RateLimitScope>,
+    /// New rate limit burst size (messages that can be sent at once). `null` or `0`
+    /// clears it, so the burst falls back to the per-minute rate
+    #[serde(default, deserialize_with = "deserialize_burst_update")]
+    #[schema(value_type = Option<u32>)]
+    pub rate_limit_burst: Option<Option<u32>>,
+    /// ACK (true) or NACK for retry (false) messages whose endpoint returns a 4xx
+    pub ack_on_4xx: Option<bool>,
+}
+
+/// Reads a present `rate_limit_burst` as `Some`, with `null` and `0` both meaning
+/// "clear"; an absent field stays `None` (unchanged) through `#[serde(default)]`
+fn deserialize_burst_update<'de, D>(deserializer: D) -> Result<Option<Option<u32>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let burst = Option::<u32>::deserialize(deserializer)?;
+    Ok(Some(burst.filter(|&burst| burst > 0)))
+}
+
+impl PoolConfigUpdateRequest {
+    /// Check field bounds, returning one violation per invalid field
+    pub fn validate(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(concurrency) = self.concurrency {
+            if !(1..=MAX_POOL_CONCURRENCY).contains(&concurrency) {
+                violations.push(FieldViolation::new(
+                    "concurrency",
+                    format!("must be between 1 and {} (got {})", MAX_POOL_CONCURRENCY, concurrency),
+                ));
+            }
+        }
+
+        if self.rate_limit_per_minute == Some(0) {
+            violations.push(FieldViolation::new("rate_limit_per_minute", "must be at least 1 when set"));
+        }
+
+        violations
+    }
+}
+
+/// Request to reload router configuration
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConfigReloadRequest {
+    /// List of pool configurations
+    pub processing_pools: Vec<PoolConfigRequest>,
+}
+
+/// Pool configuration in reload request
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PoolConfigRequest {
+    /// Pool code/identifier
+    pub code: String,
+    /// Worker concurrency
+    pub concurrency: u32,
+    /// Optional rate limit (messages per minute)
+    pub rate_limit_per_minute: Option<u32>,
+    /// Rate limit scope: POOL (default) or GROUP
+    #[serde(default)]
+    pub rate_limit_scope: RateLimitScope,
+    /// Optional rate limit burst size (defaults to the per-minute rate)
+    #[serde(default)]
+    pub rate_limit_burst: Option<u32>,
+    /// ACK messages whose endpoint returns a 4xx (default) instead of retrying them
+    #[serde(default = "default_ack_on_4xx")]
+    pub ack_on_4xx: bool,
+}
+
+fn default_ack_on_4xx() -> bool {
+    true
+}
+
+impl ConfigReloadRequest {
+    /// Check the whole pool list with the same rules as [`crate::QueueManager::reload_config`],
+    /// returning one violation per problem so each bad pool can be found
+    pub fn validate(&self, max_pools: usize) -> Vec<FieldViolation> {
+        validate_pool_configs(&self.pool_configs(), max_pools)
+    }
+
+    pub(crate) fn pool_configs(&self) -> Vec<PoolConfig> {
+        self.processing_pools
+            .iter()
+            .map(|p| PoolConfig {
+                code: p.code.clone(),
+                concurrency: p.concurrency,
+                rate_limit_per_minute: p.rate_limit_per_minute,
+                rate_limit_scope: p.rate_limit_scope,
+                rate_limit_burst: p.rate_limit_burst,
+                ack_on_4xx: p.ack_on_4xx,
+            })
+            .collect()
+    }
 }
 
 /// Response after publishing a message
@@ -24,6 +261,40 @@ pub struct PublishMessageResponse {
     pub status: String,
 }
 
+/// Outcome of one message in a batch publish
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchPublishItemResult {
+    /// Position of the message in the request
+    pub index: usize,
+    /// Generated message ID
+    pub message_id: String,
+    /// Status: ACCEPTED or FAILED
+    pub status: String,
+    /// Why the message failed to publish
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response after publishing a batch of messages
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchPublishResponse {
+    /// Number of messages accepted by the queue
+    pub accepted: usize,
+    /// Number of messages that failed to publish
+    pub failed: usize,
+    /// Per-message outcome, in request order
+    pub results: Vec<BatchPublishItemResult>,
+}
+
+/// Limits enforced by the publish endpoints, so clients can check before sending
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageLimitsResponse {
+    /// Largest message accepted, in bytes, measured as the encoded queue message body
+    pub max_message_bytes: usize,
+    /// Most messages accepted per `POST /messages/batch`
+    pub max_batch_size: usize,
+}
+
 /// Pool status response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PoolStatusResponse {
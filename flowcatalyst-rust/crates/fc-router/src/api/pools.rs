@@ -0,0 +1,369 @@
+//! Pool statistics and runtime pool management endpoints
+
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use fc_common::{PoolConfig, PoolStats};
+use tracing::{info, warn, error};
+
+use crate::{DrainOutcome, LatencyPercentiles};
+use super::{AppState, conditional_json, etag_stable_pool_stats, json_etag};
+use super::model::{PoolConfigUpdateRequest, ValidationErrorResponse};
+
+/// Response after draining a pool
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PoolDrainResponse {
+    /// Pool code that was drained
+    pub pool_code: String,
+    /// Messages queued in the pool at drain time
+    pub queue_size: u32,
+    /// Workers processing messages at drain time
+    pub active_workers: u32,
+}
+
+/// Pool statistics
+#[utoipa::path(
+    get,
+    path = "/monitoring/pools",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Pool statistics", body = Vec<PoolStats>),
+        (status = 304, description = "Unchanged since the `ETag` given in `If-None-Match`")
+    )
+)]
+pub(super) async fn pool_stats_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let pool_stats = state.queue_manager.get_pool_stats();
+    let etag = json_etag(&etag_stable_pool_stats(&pool_stats));
+    conditional_json(&headers, etag, || pool_stats)
+}
+
+/// End-to-end latency percentiles for a pool
+#[utoipa::path(
+    get,
+    path = "/monitoring/pools/{pool_code}/latency",
+    tag = "monitoring",
+    params(
+        ("pool_code" = String, Path, description = "Pool code")
+    ),
+    responses(
+        (status = 200, description = "Latency percentiles over the sliding window", body = LatencyPercentiles),
+        (status = 404, description = "Pool not found or no completed messages in the window")
+    )
+)]
+pub(super) async fn pool_latency_handler(
+    State(state): State<AppState>,
+    Path(pool_code): Path<String>,
+) -> Response {
+    match state.queue_manager.get_latency_percentiles(&pool_code) {
+        Some(latency) => (StatusCode::OK, Json(latency)).into_response(),
+        None if state.queue_manager.pool_codes().contains(&pool_code) => {
+            (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "No completed messages in the latency window" }))).into_response()
+        }
+        None => {
+            (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Pool not found" }))).into_response()
+        }
+    }
+}
+
+/// Update pool configuration
+#[utoipa::path(
+    put,
+    path = "/monitoring/pools/{pool_code}",
+    tag = "monitoring",
+    params(
+        ("pool_code" = String, Path, description = "Pool code to update")
+    ),
+    request_body = PoolConfigUpdateRequest,
+    responses(
+        (status = 200, description = "Pool updated"),
+        (status = 400, description = "Invalid pool configuration", body = ValidationErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(super) async fn update_pool_config(
+    State(state): State<AppState>,
+    Path(pool_code): Path<String>,
+    Json(req): Json<PoolConfigUpdateRequest>,
+) -> Response {
+    let violations = req.validate();
+    if !violations.is_empty() {
+        warn!(pool_code = %pool_code, violations = ?violations, "Rejected invalid pool configuration update");
+        return (StatusCode::BAD_REQUEST, Json(ValidationErrorResponse {
+            success: false,
+            error: "Invalid pool configuration".to_string(),
+            violations,
+        })).into_response();
+    }
+
+    let existing_stats: Option<PoolStats> = state.queue_manager
+        .get_pool_stats()
+        .into_iter()
+        .find(|s| s.pool_code == pool_code && !s.is_draining);
+
+    let new_config = match existing_stats {
+        Some(stats) => PoolConfig {
+            code: pool_code.clone(),
+            concurrency: req.concurrency.unwrap_or(stats.concurrency),
+            rate_limit_per_minute: if req.rate_limit_per_minute.is_some() {
+                req.rate_limit_per_minute
+            } else {
+                stats.rate_limit_per_minute
+            },
+            rate_limit_scope: req.rate_limit_scope.unwrap_or(stats.rate_limit_scope),
+            rate_limit_burst: req.rate_limit_burst.unwrap_or(stats.rate_limit_burst),
+            ack_on_4xx: req.ack_on_4xx.unwrap_or(stats.ack_on_4xx),
+        },
+        None => PoolConfig {
+            code: pool_code.clone(),
+            concurrency: req.concurrency.unwrap_or(10),
+            rate_limit_per_minute: req.rate_limit_per_minute,
+            rate_limit_scope: req.rate_limit_scope.unwrap_or_default(),
+            rate_limit_burst: req.rate_limit_burst.flatten(),
+            ack_on_4xx: req.ack_on_4xx.unwrap_or(true),
+        },
+    };
+
+    match state.queue_manager.update_pool_config(&pool_code, new_config.clone()).await {
+        Ok(_) => {
+            info!(pool_code = %pool_code, "Pool configuration updated via API");
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true,
+                "pool_code": pool_code,
+                "new_config": {
+                    "concurrency": new_config.concurrency,
+                    "rate_limit_per_minute": new_config.rate_limit_per_minute,
+                    "rate_limit_scope": new_config.rate_limit_scope,
+                    "rate_limit_burst": new_config.rate_limit_burst,
+                    "ack_on_4xx": new_config.ack_on_4xx,
+                }
+            }))).into_response()
+        }
+        Err(e) => {
+            error!(pool_code = %pool_code, error = %e, "Failed to update pool configuration");
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string(),
+            }))).into_response()
+        }
+    }
+}
+
+/// Drain and remove a pool
+#[utoipa::path(
+    delete,
+    path = "/monitoring/pools/{pool_code}",
+    tag = "monitoring",
+    params(
+        ("pool_code" = String, Path, description = "Pool code to drain")
+    ),
+    responses(
+        (status = 200, description = "Pool draining", body = PoolDrainResponse),
+        (status = 404, description = "Pool not found"),
+        (status = 409, description = "An earlier pool with this code is still draining"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(super) async fn drain_pool(
+    State(state): State<AppState>,
+    Path(pool_code): Path<String>,
+) -> Response {
+    let stats: Option<PoolStats> = state.queue_manager
+        .get_pool_stats()
+        .into_iter()
+        .find(|s| s.pool_code == pool_code && !s.is_draining);
+
+    match state.queue_manager.drain_pool(&pool_code).await {
+        Ok(DrainOutcome::Draining) => {
+            info!(pool_code = %pool_code, "Pool drained via API");
+            let (queue_size, active_workers) = stats
+                .map(|s| (s.queue_size, s.active_workers))
+                .unwrap_or_default();
+            (StatusCode::OK, Json(PoolDrainResponse {
+                pool_code,
+                queue_size,
+                active_workers,
+            })).into_response()
+        }
+        Ok(DrainOutcome::AlreadyDraining) => {
+            (StatusCode::CONFLICT, Json(serde_json::json!({
+                "error": "An earlier pool with this code is still draining",
+            }))).into_response()
+        }
+        Ok(DrainOutcome::NotFound) => {
+            (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Pool not found" }))).into_response()
+        }
+        Err(e) => {
+            error!(pool_code = %pool_code, error = %e, "Failed to drain pool");
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "error": e.to_string(),
+            }))).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header;
+    use crate::api::dashboard_pool_stats_handler;
+    use crate::api::tests::{create_test_state, if_none_match, json_body};
+    use crate::manager::MAX_POOL_CONCURRENCY;
+
+    async fn put_pool_config(state: &AppState, req: serde_json::Value) -> (StatusCode, serde_json::Value) {
+        let req: PoolConfigUpdateRequest = serde_json::from_value(req).unwrap();
+        let response = update_pool_config(State(state.clone()), Path("POOL-A".to_string()), Json(req)).await;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_update_pool_config_rejects_zero_concurrency() {
+        let state = create_test_state().await;
+
+        let (status, body) = put_pool_config(&state, serde_json::json!({"concurrency": 0})).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["violations"][0]["field"], "concurrency");
+        assert_eq!(state.queue_manager.get_pool_stats()[0].concurrency, 4);
+    }
+
+    #[tokio::test]
+    async fn test_update_pool_config_rejects_out_of_range_values() {
+        let state = create_test_state().await;
+
+        let (status, body) = put_pool_config(&state, serde_json::json!({
+            "concurrency": MAX_POOL_CONCURRENCY + 1,
+            "rate_limit_per_minute": 0,
+        })).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let fields: Vec<&str> = body["violations"].as_array().unwrap()
+            .iter()
+            .map(|v| v["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, vec!["concurrency", "rate_limit_per_minute"]);
+
+        let pool = &state.queue_manager.get_pool_stats()[0];
+        assert_eq!(pool.concurrency, 4);
+        assert_eq!(pool.rate_limit_per_minute, Some(60));
+
+        // In-range values still apply
+        let (status, _) = put_pool_config(&state, serde_json::json!({"concurrency": MAX_POOL_CONCURRENCY})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(state.queue_manager.get_pool_stats()[0].concurrency, MAX_POOL_CONCURRENCY);
+    }
+
+    #[tokio::test]
+    async fn test_update_pool_config_clears_rate_limit_burst() {
+        let state = create_test_state().await;
+        let burst = |state: &AppState| state.queue_manager.get_pool_stats()[0].rate_limit_burst;
+
+        let (status, _) = put_pool_config(&state, serde_json::json!({"rate_limit_burst": 5})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(burst(&state), Some(5));
+
+        // Absent leaves it unchanged
+        put_pool_config(&state, serde_json::json!({"concurrency": 4})).await;
+        assert_eq!(burst(&state), Some(5));
+
+        let (status, body) = put_pool_config(&state, serde_json::json!({"rate_limit_burst": null})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["new_config"]["rate_limit_burst"].is_null());
+        assert_eq!(burst(&state), None);
+
+        put_pool_config(&state, serde_json::json!({"rate_limit_burst": 5})).await;
+        put_pool_config(&state, serde_json::json!({"rate_limit_burst": 0})).await;
+        assert_eq!(burst(&state), None);
+    }
+
+    #[tokio::test]
+    async fn test_drain_pool_endpoint() {
+        let state = create_test_state().await;
+
+        let response = drain_pool(State(state.clone()), Path("POOL-A".to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["pool_code"], "POOL-A");
+        assert_eq!(body["queue_size"], 0);
+        assert_eq!(body["active_workers"], 0);
+
+        assert!(state.queue_manager.pool_codes().is_empty());
+
+        let response = drain_pool(State(state.clone()), Path("POOL-A".to_string())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // A recreated pool can't be drained over the one still draining
+        state.queue_manager.update_pool_config("POOL-A", PoolConfig {
+            code: "POOL-A".to_string(),
+            ..Default::default()
+        }).await.unwrap();
+        let response = drain_pool(State(state.clone()), Path("POOL-A".to_string())).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(state.queue_manager.pool_codes(), vec!["POOL-A".to_string()]);
+
+        // Idle pool is fully drained, so the cleanup loop can retire it
+        state.queue_manager.cleanup_draining_pools().await;
+        let response = drain_pool(State(state.clone()), Path("POOL-A".to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_pool_latency_without_samples() {
+        let state = create_test_state().await;
+
+        let response = pool_latency_handler(State(state.clone()), Path("POOL-A".to_string())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "No completed messages in the latency window");
+
+        let response = pool_latency_handler(State(state), Path("UNKNOWN".to_string())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Pool not found");
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats_include_draining_pools() {
+        let state = create_test_state().await;
+        state.queue_manager.drain_pool("POOL-A").await.unwrap();
+
+        let pools: Vec<PoolStats> = json_body(pool_stats_handler(State(state.clone()), HeaderMap::new()).await).await;
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].pool_code, "POOL-A");
+        assert!(pools[0].is_draining);
+
+        let Json(dashboard) = dashboard_pool_stats_handler(State(state.clone())).await;
+        let pool = &dashboard["POOL-A"];
+        assert!(pool.is_draining);
+        assert_eq!(pool.available_permits, 0);
+        assert_eq!(pool.max_concurrency, 4);
+
+        // Fully drained pool is removed by the cleanup loop
+        state.queue_manager.cleanup_draining_pools().await;
+        let pools: Vec<PoolStats> = json_body(pool_stats_handler(State(state), HeaderMap::new()).await).await;
+        assert!(pools.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats_etag_changes_with_state() {
+        let state = create_test_state().await;
+
+        let before = pool_stats_handler(State(state.clone()), HeaderMap::new()).await;
+        let etag = before.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        state.queue_manager.drain_pool("POOL-A").await.unwrap();
+        let after = pool_stats_handler(State(state), if_none_match(&etag)).await;
+        assert_eq!(after.status(), StatusCode::OK);
+        assert_ne!(after.headers()[header::ETAG], etag.as_str());
+    }
+}
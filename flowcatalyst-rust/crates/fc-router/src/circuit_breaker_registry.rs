@@ -3,12 +3,13 @@
 //! Provides centralized tracking of circuit breakers for monitoring purposes.
 //! Compatible with Java's Resilience4j circuit breaker stats format.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 use utoipa::ToSchema;
 
 /// Circuit breaker state (matches Java Resilience4j states)
@@ -29,6 +30,27 @@ impl Default for CircuitBreakerState {
     }
 }
 
+impl CircuitBreakerState {
+    /// State name as reported to the dashboard: CLOSED, OPEN, HALF_OPEN
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "CLOSED",
+            Self::Open => "OPEN",
+            Self::HalfOpen => "HALF_OPEN",
+        }
+    }
+}
+
+/// How the sliding window of recent calls is bounded (matches Resilience4j)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlidingWindowType {
+    /// Keep the last `window_size` calls
+    #[default]
+    Count,
+    /// Keep the calls made in the last `window_size` seconds
+    Time,
+}
+
 /// Statistics for a single circuit breaker (matches Java format)
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CircuitBreakerStats {
@@ -45,13 +67,13 @@ pub struct CircuitBreakerStats {
     /// Number of calls rejected while open
     #[serde(rename = "rejectedCalls")]
     pub rejected_calls: u64,
-    /// Failure rate (0.0 - 1.0)
+    /// Failure rate over the buffered calls (0.0 - 1.0)
     #[serde(rename = "failureRate")]
     pub failure_rate: f64,
     /// Number of buffered calls for rate calculation
     #[serde(rename = "bufferedCalls")]
     pub buffered_calls: u32,
-    /// Size of the buffer
+    /// Size of the sliding window (calls, or seconds for a time-based window)
     #[serde(rename = "bufferSize")]
     pub buffer_size: u32,
 }
@@ -70,14 +92,21 @@ struct EndpointCircuitBreaker {
     failure_threshold: u32,
     success_threshold: u32,
     reset_timeout: Duration,
-    buffer_size: u32,
+    sliding_window_type: SlidingWindowType,
+    window_size: u32,
+    minimum_calls: u32,
 
-    // Recent calls for failure rate calculation
-    recent_results: RwLock<Vec<bool>>,
+    // Recent calls (with when they were recorded) for failure rate calculation
+    recent_results: RwLock<VecDeque<(Instant, bool)>>,
 }
 
 impl EndpointCircuitBreaker {
-    fn new(name: String, failure_threshold: u32, success_threshold: u32, reset_timeout: Duration, buffer_size: u32) -> Self {
+    fn new(name: String, config: &CircuitBreakerConfig) -> Self {
+        let capacity = match config.sliding_window_type {
+            SlidingWindowType::Count => config.window_size as usize,
+            SlidingWindowType::Time => 0,
+        };
+
         Self {
             name,
             state: RwLock::new(CircuitBreakerState::Closed),
@@ -86,30 +115,52 @@ impl EndpointCircuitBreaker {
             rejected_calls: AtomicU64::new(0),
             last_failure_time: RwLock::new(None),
             last_state_change: RwLock::new(Instant::now()),
-            failure_threshold,
-            success_threshold,
-            reset_timeout,
-            buffer_size,
-            recent_results: RwLock::new(Vec::with_capacity(buffer_size as usize)),
+            failure_threshold: config.failure_threshold,
+            success_threshold: config.success_threshold,
+            reset_timeout: config.reset_timeout,
+            sliding_window_type: config.sliding_window_type,
+            window_size: config.window_size,
+            minimum_calls: config.minimum_calls,
+            recent_results: RwLock::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Drop calls that have fallen out of the sliding window
+    fn evict_expired(&self, results: &mut VecDeque<(Instant, bool)>, now: Instant) {
+        match self.sliding_window_type {
+            SlidingWindowType::Count => {
+                while results.len() > self.window_size as usize {
+                    results.pop_front();
+                }
+            }
+            SlidingWindowType::Time => {
+                let window = Duration::from_secs(self.window_size as u64);
+                while results.front().is_some_and(|&(at, _)| now.duration_since(at) >= window) {
+                    results.pop_front();
+                }
+            }
         }
     }
 
+    /// Add a call to the sliding window
+    fn push_result(&self, results: &mut VecDeque<(Instant, bool)>, success: bool) {
+        let now = Instant::now();
+        results.push_back((now, success));
+        self.evict_expired(results, now);
+    }
+
     fn record_success(&self) {
         self.successful_calls.fetch_add(1, Ordering::Relaxed);
 
         let mut results = self.recent_results.write();
-        if results.len() >= self.buffer_size as usize {
-            results.remove(0);
-        }
-        results.push(true);
+        self.push_result(&mut results, true);
 
         let state = *self.state.read();
         if state == CircuitBreakerState::HalfOpen {
             // Count recent successes in half-open state
-            let recent_successes: u32 = results.iter().rev().take(self.success_threshold as usize).filter(|&&s| s).count() as u32;
+            let recent_successes: u32 = results.iter().rev().take(self.success_threshold as usize).filter(|&&(_, s)| s).count() as u32;
             if recent_successes >= self.success_threshold {
-                *self.state.write() = CircuitBreakerState::Closed;
-                *self.last_state_change.write() = Instant::now();
+                self.transition_to(CircuitBreakerState::Closed);
             }
         }
     }
@@ -119,25 +170,20 @@ impl EndpointCircuitBreaker {
         *self.last_failure_time.write() = Some(Instant::now());
 
         let mut results = self.recent_results.write();
-        if results.len() >= self.buffer_size as usize {
-            results.remove(0);
-        }
-        results.push(false);
+        self.push_result(&mut results, false);
 
         let state = *self.state.read();
         match state {
             CircuitBreakerState::Closed => {
-                // Check if we should trip
-                let recent_failures: u32 = results.iter().rev().take(self.buffer_size as usize).filter(|&&s| !s).count() as u32;
-                if recent_failures >= self.failure_threshold {
-                    *self.state.write() = CircuitBreakerState::Open;
-                    *self.last_state_change.write() = Instant::now();
+                // Check if we should trip; never on fewer than `minimum_calls` samples
+                let recent_failures = results.iter().filter(|&&(_, s)| !s).count() as u32;
+                if results.len() as u32 >= self.minimum_calls && recent_failures >= self.failure_threshold {
+                    self.transition_to(CircuitBreakerState::Open);
                 }
             }
             CircuitBreakerState::HalfOpen => {
                 // Any failure in half-open reopens
-                *self.state.write() = CircuitBreakerState::Open;
-                *self.last_state_change.write() = Instant::now();
+                self.transition_to(CircuitBreakerState::Open);
             }
             CircuitBreakerState::Open => {}
         }
@@ -156,8 +202,7 @@ impl EndpointCircuitBreaker {
                 // Check if we should transition to half-open
                 if let Some(last_failure) = *self.last_failure_time.read() {
                     if last_failure.elapsed() >= self.reset_timeout {
-                        *self.state.write() = CircuitBreakerState::HalfOpen;
-                        *self.last_state_change.write() = Instant::now();
+                        self.transition_to(CircuitBreakerState::HalfOpen);
                         return true;
                     }
                 }
@@ -167,50 +212,83 @@ impl EndpointCircuitBreaker {
         }
     }
 
+    fn transition_to(&self, new_state: CircuitBreakerState) {
+        let old_state = std::mem::replace(&mut *self.state.write(), new_state);
+        if old_state == new_state {
+            return;
+        }
+        *self.last_state_change.write() = Instant::now();
+
+        match new_state {
+            CircuitBreakerState::Open => warn!(
+                endpoint = %self.name,
+                from = old_state.as_str(),
+                "Circuit breaker opened"
+            ),
+            _ => info!(
+                endpoint = %self.name,
+                from = old_state.as_str(),
+                to = new_state.as_str(),
+                "Circuit breaker state changed"
+            ),
+        }
+    }
+
     fn get_stats(&self) -> CircuitBreakerStats {
-        let successful = self.successful_calls.load(Ordering::Relaxed);
-        let failed = self.failed_calls.load(Ordering::Relaxed);
-        let total = successful + failed;
+        let mut results = self.recent_results.write();
+        self.evict_expired(&mut results, Instant::now());
 
-        let failure_rate = if total > 0 {
-            failed as f64 / total as f64
-        } else {
+        // Failure rate over the buffered (recent) calls, like Resilience4j's sliding window
+        let buffered_failures = results.iter().filter(|&&(_, success)| !success).count();
+        let failure_rate = if results.is_empty() {
             0.0
+        } else {
+            buffered_failures as f64 / results.len() as f64
         };
 
-        let results = self.recent_results.read();
-
         CircuitBreakerStats {
             name: self.name.clone(),
             state: *self.state.read(),
-            successful_calls: successful,
-            failed_calls: failed,
+            successful_calls: self.successful_calls.load(Ordering::Relaxed),
+            failed_calls: self.failed_calls.load(Ordering::Relaxed),
             rejected_calls: self.rejected_calls.load(Ordering::Relaxed),
             failure_rate,
             buffered_calls: results.len() as u32,
-            buffer_size: self.buffer_size,
+            buffer_size: self.window_size,
         }
     }
 
     fn reset(&self) {
-        *self.state.write() = CircuitBreakerState::Closed;
-        *self.last_state_change.write() = Instant::now();
+        self.transition_to(CircuitBreakerState::Closed);
         *self.last_failure_time.write() = None;
         self.recent_results.write().clear();
+        self.successful_calls.store(0, Ordering::Relaxed);
+        self.failed_calls.store(0, Ordering::Relaxed);
+        self.rejected_calls.store(0, Ordering::Relaxed);
+    }
+
+    fn force_open(&self) {
+        // Half-open probing resumes after the usual reset timeout
+        *self.last_failure_time.write() = Some(Instant::now());
+        self.transition_to(CircuitBreakerState::Open);
     }
 }
 
 /// Configuration for circuit breaker registry
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
-    /// Number of failures before opening
+    /// Number of failures within the sliding window before opening
     pub failure_threshold: u32,
     /// Number of successes in half-open before closing
     pub success_threshold: u32,
     /// Time before transitioning from open to half-open
     pub reset_timeout: Duration,
-    /// Buffer size for failure rate calculation
-    pub buffer_size: u32,
+    /// Whether the sliding window is bounded by call count or by time
+    pub sliding_window_type: SlidingWindowType,
+    /// Sliding window size: a number of calls, or seconds for a time-based window
+    pub window_size: u32,
+    /// Calls the window must hold before the breaker can trip
+    pub minimum_calls: u32,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -219,7 +297,9 @@ impl Default for CircuitBreakerConfig {
             failure_threshold: 10,
             success_threshold: 5,
             reset_timeout: Duration::from_secs(30),
-            buffer_size: 100,
+            sliding_window_type: SlidingWindowType::Count,
+            window_size: 100,
+            minimum_calls: 1,
         }
     }
 }
@@ -250,13 +330,7 @@ impl CircuitBreakerRegistry {
 
         // Create new
         let mut breakers = self.breakers.write();
-        let breaker = Arc::new(EndpointCircuitBreaker::new(
-            endpoint.to_string(),
-            self.config.failure_threshold,
-            self.config.success_threshold,
-            self.config.reset_timeout,
-            self.config.buffer_size,
-        ));
+        let breaker = Arc::new(EndpointCircuitBreaker::new(endpoint.to_string(), &self.config));
         breakers.insert(endpoint.to_string(), Arc::clone(&breaker));
         breaker
     }
@@ -304,7 +378,8 @@ impl CircuitBreakerRegistry {
         breakers.get(endpoint).map(|b| *b.state.read())
     }
 
-    /// Reset a specific circuit breaker
+    /// Close a specific circuit breaker and clear its counters.
+    /// Returns false if the breaker doesn't exist.
     pub fn reset(&self, endpoint: &str) -> bool {
         let breakers = self.breakers.read();
         if let Some(breaker) = breakers.get(endpoint) {
@@ -315,6 +390,18 @@ impl CircuitBreakerRegistry {
         }
     }
 
+    /// Trip a specific circuit breaker open, e.g. to test fallback behaviour.
+    /// Returns false if the breaker doesn't exist.
+    pub fn force_open(&self, endpoint: &str) -> bool {
+        let breakers = self.breakers.read();
+        if let Some(breaker) = breakers.get(endpoint) {
+            breaker.force_open();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Reset all circuit breakers
     pub fn reset_all(&self) {
         let breakers = self.breakers.read();
@@ -349,7 +436,8 @@ mod tests {
             failure_threshold: 3,
             success_threshold: 2,
             reset_timeout: Duration::from_millis(100),
-            buffer_size: 10,
+            window_size: 10,
+            ..Default::default()
         });
 
         let endpoint = "http://test.com/api";
@@ -384,6 +472,26 @@ mod tests {
         // Reset it
         assert!(registry.reset(endpoint));
         assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Closed));
+        assert_eq!(registry.get_stats(endpoint).unwrap().failed_calls, 0);
+        assert!(!registry.reset("http://unknown.com"));
+    }
+
+    #[test]
+    fn test_force_open_rejects_until_reset_timeout() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            reset_timeout: Duration::from_millis(10),
+            ..Default::default()
+        });
+        let endpoint = "api.example.com";
+        registry.record_success(endpoint);
+
+        assert!(registry.force_open(endpoint));
+        assert!(!registry.allow_request(endpoint));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(registry.allow_request(endpoint));
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::HalfOpen));
+        assert!(!registry.force_open("unknown.example.com"));
     }
 
     #[test]
@@ -405,4 +513,127 @@ mod tests {
         assert_eq!(api2_stats.successful_calls, 1);
         assert_eq!(api2_stats.failed_calls, 1);
     }
+
+    #[test]
+    fn test_failure_rate_from_buffered_calls() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            window_size: 4,
+            ..Default::default()
+        });
+        let endpoint = "api.example.com";
+
+        registry.record_failure(endpoint);
+        registry.record_failure(endpoint);
+        registry.record_success(endpoint);
+        registry.record_success(endpoint);
+
+        let stats = registry.get_stats(endpoint).unwrap();
+        assert_eq!(stats.failure_rate, 0.5);
+
+        // Older failures fall out of the buffer; all-time counts keep them
+        registry.record_success(endpoint);
+        registry.record_success(endpoint);
+
+        let stats = registry.get_stats(endpoint).unwrap();
+        assert_eq!(stats.failure_rate, 0.0);
+        assert_eq!(stats.failed_calls, 2);
+        assert_eq!(stats.buffered_calls, 4);
+    }
+
+    #[test]
+    fn test_half_open_transition() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 1,
+            reset_timeout: Duration::from_millis(10),
+            window_size: 10,
+            ..Default::default()
+        });
+        let endpoint = "api.example.com";
+
+        registry.record_failure(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Open));
+        assert!(!registry.allow_request(endpoint));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(registry.allow_request(endpoint));
+        assert_eq!(registry.get_state(endpoint).unwrap().as_str(), "HALF_OPEN");
+
+        registry.record_success(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Closed));
+        assert_eq!(registry.get_stats(endpoint).unwrap().rejected_calls, 1);
+    }
+
+    #[test]
+    fn test_does_not_trip_below_minimum_calls() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            minimum_calls: 5,
+            window_size: 10,
+            ..Default::default()
+        });
+        let endpoint = "api.example.com";
+
+        for _ in 0..4 {
+            registry.record_failure(endpoint);
+        }
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Closed));
+
+        registry.record_failure(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Open));
+    }
+
+    #[test]
+    fn test_count_window_trips_on_failures_within_last_calls() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            sliding_window_type: SlidingWindowType::Count,
+            window_size: 4,
+            ..Default::default()
+        });
+        let endpoint = "api.example.com";
+
+        // Failures spread wider than the window never add up to the threshold
+        for _ in 0..3 {
+            registry.record_failure(endpoint);
+            registry.record_success(endpoint);
+            registry.record_success(endpoint);
+        }
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Closed));
+        assert_eq!(registry.get_stats(endpoint).unwrap().buffered_calls, 4);
+
+        registry.record_failure(endpoint);
+        registry.record_failure(endpoint);
+        registry.record_failure(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Open));
+    }
+
+    #[test]
+    fn test_time_window_forgets_expired_calls() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            sliding_window_type: SlidingWindowType::Time,
+            window_size: 1,
+            ..Default::default()
+        });
+        let endpoint = "api.example.com";
+
+        registry.record_failure(endpoint);
+        registry.record_failure(endpoint);
+        std::thread::sleep(Duration::from_millis(1100));
+
+        // The earlier failures have aged out of the window
+        registry.record_failure(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Closed));
+        assert_eq!(registry.get_stats(endpoint).unwrap().buffered_calls, 1);
+
+        // Within the window, the call count is unbounded
+        for _ in 0..20 {
+            registry.record_success(endpoint);
+        }
+        registry.record_failure(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Closed));
+        registry.record_failure(endpoint);
+        assert_eq!(registry.get_state(endpoint), Some(CircuitBreakerState::Open));
+    }
 }
@@ -9,7 +9,7 @@ use tokio::sync::broadcast;
 use tracing::{info, warn, error, debug};
 use serde::{Deserialize, Serialize};
 
-use fc_common::{RouterConfig, PoolConfig, QueueConfig};
+use fc_common::{RouterConfig, PoolConfig, QueueConfig, RateLimitScope};
 use crate::manager::QueueManager;
 use crate::warning::WarningService;
 
@@ -89,6 +89,10 @@ pub struct PoolConfigResponse {
     pub concurrency: usize,
     #[serde(default)]
     pub rate_limit_per_minute: Option<u32>,
+    #[serde(default)]
+    pub rate_limit_burst: Option<u32>,
+    #[serde(default)]
+    pub ack_on_4xx: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,6 +106,10 @@ pub struct QueueConfigResponse {
     pub connections: Option<u32>,
     #[serde(default)]
     pub visibility_timeout: Option<u32>,
+    #[serde(default)]
+    pub max_delivery_attempts: Option<u32>,
+    #[serde(default, alias = "pollBatchSize")]
+    pub poll_batch_size: Option<u32>,
 }
 
 impl From<MessageRouterConfigResponse> for RouterConfig {
@@ -113,6 +121,9 @@ impl From<MessageRouterConfigResponse> for RouterConfig {
                     code: p.code,
                     concurrency: p.concurrency as u32,
                     rate_limit_per_minute: p.rate_limit_per_minute,
+                    rate_limit_scope: RateLimitScope::Pool,
+                    rate_limit_burst: p.rate_limit_burst,
+                    ack_on_4xx: p.ack_on_4xx.unwrap_or(true),
                 })
                 .collect(),
             queues: response.queues
@@ -122,6 +133,8 @@ impl From<MessageRouterConfigResponse> for RouterConfig {
                     uri: q.queue_uri,
                     connections: q.connections.unwrap_or(1),
                     visibility_timeout: q.visibility_timeout.unwrap_or(120),
+                    max_delivery_attempts: q.max_delivery_attempts,
+                    poll_batch_size: q.poll_batch_size,
                 })
                 .collect(),
         }
@@ -249,6 +262,8 @@ impl ConfigSyncService {
             pool.code.hash(&mut hasher);
             pool.concurrency.hash(&mut hasher);
             pool.rate_limit_per_minute.hash(&mut hasher);
+            pool.rate_limit_burst.hash(&mut hasher);
+            pool.ack_on_4xx.hash(&mut hasher);
         }
 
         // Hash queues
@@ -447,7 +462,7 @@ mod tests {
             processing_pools: vec![PoolConfig {
                 code: "POOL1".to_string(),
                 concurrency: 10,
-                rate_limit_per_minute: None,
+                ..Default::default()
             }],
             queues: vec![],
         };
@@ -456,7 +471,7 @@ mod tests {
             processing_pools: vec![PoolConfig {
                 code: "POOL1".to_string(),
                 concurrency: 20, // Changed
-                rate_limit_per_minute: None,
+                ..Default::default()
             }],
             queues: vec![],
         };
@@ -474,6 +489,7 @@ mod tests {
                 code: "POOL1".to_string(),
                 concurrency: 10,
                 rate_limit_per_minute: Some(100),
+                ..Default::default()
             }],
             queues: vec![],
         };
@@ -0,0 +1,58 @@
+//! Dead-letter sink for messages the router gives up on
+//!
+//! Messages that fail permanently (e.g. `ErrorConfig` outcomes) or exhaust their
+//! queue's `max_delivery_attempts` are ACKed so they aren't retried forever. When a sink is configured, each one is handed to it with
+//! the failure reason first, so the payload can be inspected and replayed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use fc_common::{MediationOutcome, Message};
+use serde::Serialize;
+
+use crate::Result;
+
+/// A permanently failed message and why it failed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub message: Message,
+    pub pool_code: String,
+    /// Failure description from the mediation outcome
+    pub reason: String,
+    /// HTTP status returned by the target, if any
+    pub status_code: Option<u16>,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn from_outcome(message: Message, pool_code: &str, outcome: &MediationOutcome) -> Self {
+        Self {
+            message,
+            pool_code: pool_code.to_string(),
+            reason: outcome.error_message.clone()
+                .unwrap_or_else(|| format!("{:?}", outcome.result)),
+            // Non-HTTP config errors carry status 0
+            status_code: outcome.status_code.filter(|&code| code != 0),
+            failed_at: Utc::now(),
+        }
+    }
+
+    /// A message that failed `attempts` deliveries, its queue's `max_delivery_attempts`
+    pub fn exhausted(message: Message, attempts: u32) -> Self {
+        Self {
+            pool_code: message.pool_code.clone(),
+            message,
+            reason: format!("exhausted {} delivery attempts", attempts),
+            status_code: None,
+            failed_at: Utc::now(),
+        }
+    }
+}
+
+/// Destination for permanently failed messages (another queue, a database table, ...)
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    /// Store a dead letter. An error leaves the message on the source queue: the pool
+    /// NACKs it instead of ACKing, so it is never dropped without reaching the sink.
+    async fn send(&self, dead_letter: DeadLetter) -> Result<()>;
+}
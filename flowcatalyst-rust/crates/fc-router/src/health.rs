@@ -4,15 +4,17 @@
 //! - Overall health status determination
 //! - 30-minute rolling window for success rates
 //! - Pool and consumer health tracking
+//! - Consumer poll stall detection with ConsumerHealth warnings
 //! - Integration with warning service
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use tracing::{debug, warn};
 
-use fc_common::{HealthStatus, HealthReport, PoolStats, ConsumerHealth};
+use fc_common::{HealthStatus, HealthReport, PoolStats, ConsumerHealth, WarningCategory, WarningSeverity};
 use crate::warning::WarningService;
 
 /// Configuration for health service
@@ -28,6 +30,9 @@ pub struct HealthServiceConfig {
     pub warning_age_minutes: i64,
     /// Consumer stall threshold (seconds since last poll)
     pub consumer_stall_threshold_secs: u64,
+    /// How long a consumer that hasn't polled yet is reported as starting,
+    /// rather than stalled, after it is marked running (seconds)
+    pub consumer_startup_grace_secs: u64,
 }
 
 impl Default for HealthServiceConfig {
@@ -38,6 +43,7 @@ impl Default for HealthServiceConfig {
             rolling_window: Duration::from_secs(30 * 60),  // 30 minutes
             warning_age_minutes: 30,
             consumer_stall_threshold_secs: 60,
+            consumer_startup_grace_secs: 30,
         }
     }
 }
@@ -100,6 +106,15 @@ pub struct HealthService {
 
     /// Consumer running state
     consumer_running: RwLock<HashMap<String, bool>>,
+
+    /// When each consumer was last marked running, for the startup grace period
+    consumer_started_at: RwLock<HashMap<String, Instant>>,
+
+    /// Stalled consumers that already have a warning raised
+    consumer_stall_warned: RwLock<HashSet<String>>,
+
+    /// Most recent poll error per consumer, and when it happened
+    consumer_last_error: RwLock<HashMap<String, (String, DateTime<Utc>)>>,
 }
 
 impl HealthService {
@@ -110,6 +125,9 @@ impl HealthService {
             pool_counters: RwLock::new(HashMap::new()),
             consumer_last_poll: RwLock::new(HashMap::new()),
             consumer_running: RwLock::new(HashMap::new()),
+            consumer_started_at: RwLock::new(HashMap::new()),
+            consumer_stall_warned: RwLock::new(HashSet::new()),
+            consumer_last_error: RwLock::new(HashMap::new()),
         }
     }
 
@@ -137,17 +155,57 @@ impl HealthService {
             .insert(consumer_id.to_string(), Instant::now());
     }
 
+    /// Record a failed poll, replacing the consumer's previous error
+    pub fn record_consumer_error(&self, consumer_id: &str, error: &str) {
+        self.consumer_last_error
+            .write()
+            .insert(consumer_id.to_string(), (error.to_string(), Utc::now()));
+    }
+
+    /// ` (last error: ...)` for a consumer that has failed a poll, for stall messages
+    fn last_error_suffix(&self, consumer_id: &str) -> String {
+        self.consumer_last_error
+            .read()
+            .get(consumer_id)
+            .map(|(error, _)| format!(" (last error: {})", error))
+            .unwrap_or_default()
+    }
+
     /// Set consumer running state
     pub fn set_consumer_running(&self, consumer_id: &str, running: bool) {
-        self.consumer_running
+        let was_running = self.consumer_running
             .write()
-            .insert(consumer_id.to_string(), running);
+            .insert(consumer_id.to_string(), running)
+            .unwrap_or(false);
+
+        if running && !was_running {
+            self.consumer_started_at
+                .write()
+                .insert(consumer_id.to_string(), Instant::now());
+        }
     }
 
-    /// Check if a consumer is healthy (polled recently)
-    pub fn is_consumer_healthy(&self, consumer_id: &str) -> bool {
-        let threshold = Duration::from_secs(self.config.consumer_stall_threshold_secs);
+    /// Whether a consumer that hasn't polled yet is still within its startup grace period
+    fn is_consumer_starting(&self, consumer_id: &str, last_poll: Option<&Instant>) -> bool {
+        let grace = Duration::from_secs(self.config.consumer_startup_grace_secs);
+        last_poll.is_none() && self.consumer_started_at
+            .read()
+            .get(consumer_id)
+            .map(|t| t.elapsed() < grace)
+            .unwrap_or(false)
+    }
 
+    /// Whether a running consumer has gone too long without a successful poll.
+    /// A consumer that has never polled is stalled once its startup grace period ends.
+    fn is_consumer_stalled(&self, consumer_id: &str, last_poll: Option<&Instant>) -> bool {
+        match last_poll {
+            Some(t) => t.elapsed() >= Duration::from_secs(self.config.consumer_stall_threshold_secs),
+            None => !self.is_consumer_starting(consumer_id, None),
+        }
+    }
+
+    /// Check if a consumer is healthy (polled recently, or still starting up)
+    pub fn is_consumer_healthy(&self, consumer_id: &str) -> bool {
         let is_running = self.consumer_running
             .read()
             .get(consumer_id)
@@ -158,11 +216,7 @@ impl HealthService {
             return false;
         }
 
-        self.consumer_last_poll
-            .read()
-            .get(consumer_id)
-            .map(|t| t.elapsed() < threshold)
-            .unwrap_or(false)
+        !self.is_consumer_stalled(consumer_id, self.consumer_last_poll.read().get(consumer_id))
     }
 
     /// Get consumer health details
@@ -181,10 +235,13 @@ impl HealthService {
             None => (None, None),
         };
 
-        let is_healthy = is_running
-            && last_poll_time
-                .map(|t| t.elapsed() < Duration::from_secs(self.config.consumer_stall_threshold_secs))
-                .unwrap_or(false);
+        let is_starting = is_running && self.is_consumer_starting(consumer_id, last_poll_time);
+        let is_healthy = is_running && !self.is_consumer_stalled(consumer_id, last_poll_time);
+        let (last_error, last_error_at) = self.consumer_last_error
+            .read()
+            .get(consumer_id)
+            .cloned()
+            .unzip();
 
         ConsumerHealth {
             queue_identifier: consumer_id.to_string(),
@@ -192,28 +249,59 @@ impl HealthService {
             last_poll_time_ms,
             time_since_last_poll_ms,
             is_running,
+            is_starting,
+            last_error,
+            last_error_at,
         }
     }
 
     /// Get stalled consumer IDs
     pub fn get_stalled_consumers(&self) -> Vec<String> {
-        let threshold = Duration::from_secs(self.config.consumer_stall_threshold_secs);
         let last_poll = self.consumer_last_poll.read();
         let running = self.consumer_running.read();
 
         running
             .iter()
-            .filter(|(id, &is_running)| {
-                is_running && last_poll
-                    .get(*id)
-                    .map(|t| t.elapsed() >= threshold)
-                    .unwrap_or(true)
-            })
+            .filter(|(id, &is_running)| is_running && self.is_consumer_stalled(id, last_poll.get(*id)))
             .map(|(id, _)| id.clone())
             .collect()
     }
 
-    /// Calculate overall health status
+    /// Detect stalled consumers, raising a ConsumerHealth warning the first time
+    /// each one stalls. Returns all currently stalled consumer IDs.
+    pub fn check_consumer_stalls(&self) -> Vec<String> {
+        let stalled = self.get_stalled_consumers();
+        let mut warned = self.consumer_stall_warned.write();
+
+        // Recovered consumers get a fresh warning if they stall again
+        warned.retain(|id| stalled.contains(id));
+
+        for consumer_id in &stalled {
+            if warned.insert(consumer_id.clone()) {
+                warn!(
+                    consumer_id = %consumer_id,
+                    threshold_secs = self.config.consumer_stall_threshold_secs,
+                    "Consumer has not polled successfully within stall threshold"
+                );
+                self.warning_service.add_warning(
+                    WarningCategory::ConsumerHealth,
+                    WarningSeverity::Error,
+                    format!(
+                        "Consumer {} has not polled successfully in over {}s{}",
+                        consumer_id,
+                        self.config.consumer_stall_threshold_secs,
+                        self.last_error_suffix(consumer_id)
+                    ),
+                    "HealthService".to_string(),
+                );
+            }
+        }
+
+        stalled
+    }
+
+    /// Calculate overall health status. Draining pools are left out: their
+    /// replacement, if any, shares the pool code and is counted instead.
     pub fn get_health_report(&self, pool_stats: &[PoolStats]) -> HealthReport {
         let mut issues = Vec::new();
 
@@ -221,7 +309,7 @@ impl HealthService {
         let mut pools_healthy = 0u32;
         let mut pools_unhealthy = 0u32;
 
-        for stat in pool_stats {
+        for stat in pool_stats.iter().filter(|stat| !stat.is_draining) {
             if let Some(rate) = self.get_pool_success_rate(&stat.pool_code) {
                 if rate >= self.config.healthy_threshold {
                     pools_healthy += 1;
@@ -247,7 +335,7 @@ impl HealthService {
         let consumers_healthy = consumers_total.saturating_sub(consumers_unhealthy);
 
         for consumer_id in &stalled {
-            issues.push(format!("Consumer {} is stalled", consumer_id));
+            issues.push(format!("Consumer {} is stalled{}", consumer_id, self.last_error_suffix(consumer_id)));
         }
 
         // Check warnings
@@ -306,8 +394,8 @@ impl HealthService {
         // Cleanup warning service
         self.warning_service.cleanup();
 
-        // Log any stalled consumers
-        let stalled = self.get_stalled_consumers();
+        // Warn about any stalled consumers
+        let stalled = self.check_consumer_stalls();
         if !stalled.is_empty() {
             warn!(
                 count = stalled.len(),
@@ -321,6 +409,7 @@ impl HealthService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fc_common::RateLimitScope;
 
     fn create_test_service() -> HealthService {
         let warning_service = Arc::new(WarningService::default());
@@ -367,11 +456,149 @@ mod tests {
             queue_capacity: 100,
             message_group_count: 0,
             rate_limit_per_minute: None,
+            rate_limit_scope: RateLimitScope::Pool,
             is_rate_limited: false,
+            is_draining: false,
+            group_thread_restarts: 0,
             metrics: None,
+            rate_limit_burst: None,
+            ack_on_4xx: true,
         }];
 
         let report = service.get_health_report(&stats);
         assert_eq!(report.status, HealthStatus::Healthy);
+        assert_eq!(report.pools_healthy, 1);
+
+        // A pool draining alongside its replacement is not counted twice
+        let mut stats = stats;
+        stats.push(PoolStats { is_draining: true, ..stats[0].clone() });
+        let report = service.get_health_report(&stats);
+        assert_eq!(report.pools_healthy, 1);
+    }
+
+    #[test]
+    fn test_consumer_stall_raises_warning_once() {
+        let warning_service = Arc::new(WarningService::default());
+        let config = HealthServiceConfig {
+            consumer_startup_grace_secs: 0,
+            ..Default::default()
+        };
+        let service = HealthService::new(config, warning_service.clone());
+
+        // Running but never polled successfully (e.g. every poll errors)
+        service.set_consumer_running("consumer-1", true);
+
+        assert_eq!(service.check_consumer_stalls(), vec!["consumer-1".to_string()]);
+        assert_eq!(service.check_consumer_stalls(), vec!["consumer-1".to_string()]);
+
+        let warnings = warning_service.get_warnings_by_category(WarningCategory::ConsumerHealth);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("consumer-1"));
+
+        assert!(!service.is_consumer_healthy("consumer-1"));
+        assert!(!service.get_consumer_health("consumer-1").is_healthy);
+        let report = service.get_health_report(&[]);
+        assert_eq!(report.consumers_unhealthy, 1);
+        assert_eq!(report.status, HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn test_consumer_stall_recovery() {
+        let warning_service = Arc::new(WarningService::default());
+        let config = HealthServiceConfig {
+            consumer_stall_threshold_secs: 0,
+            ..Default::default()
+        };
+        let service = HealthService::new(config, warning_service.clone());
+
+        service.set_consumer_running("consumer-1", true);
+        service.record_consumer_poll("consumer-1");
+
+        // Zero threshold: any poll is already too old
+        assert_eq!(service.check_consumer_stalls().len(), 1);
+
+        // Stopped consumers are not considered stalled
+        service.set_consumer_running("consumer-1", false);
+        assert!(service.check_consumer_stalls().is_empty());
+
+        // Stalling again after recovery raises a new warning
+        service.set_consumer_running("consumer-1", true);
+        assert_eq!(service.check_consumer_stalls().len(), 1);
+        assert_eq!(
+            warning_service.get_warnings_by_category(WarningCategory::ConsumerHealth).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_consumer_starting_within_grace_then_healthy_after_poll() {
+        let warning_service = Arc::new(WarningService::default());
+        let service = HealthService::new(HealthServiceConfig::default(), warning_service.clone());
+
+        service.set_consumer_running("consumer-1", true);
+
+        let health = service.get_consumer_health("consumer-1");
+        assert!(health.is_starting);
+        assert!(health.is_healthy);
+        assert!(service.check_consumer_stalls().is_empty());
+        assert_eq!(service.get_health_report(&[]).consumers_unhealthy, 0);
+
+        service.record_consumer_poll("consumer-1");
+
+        let health = service.get_consumer_health("consumer-1");
+        assert!(!health.is_starting);
+        assert!(health.is_healthy);
+        assert!(warning_service.get_warnings_by_category(WarningCategory::ConsumerHealth).is_empty());
+    }
+
+    #[test]
+    fn test_consumer_unhealthy_if_never_polled_after_grace() {
+        let warning_service = Arc::new(WarningService::default());
+        let config = HealthServiceConfig {
+            consumer_startup_grace_secs: 1,
+            ..Default::default()
+        };
+        let service = HealthService::new(config, warning_service.clone());
+
+        service.set_consumer_running("consumer-1", true);
+        assert!(service.is_consumer_healthy("consumer-1"));
+
+        std::thread::sleep(Duration::from_millis(1100));
+
+        let health = service.get_consumer_health("consumer-1");
+        assert!(!health.is_starting);
+        assert!(!health.is_healthy);
+        assert_eq!(service.check_consumer_stalls(), vec!["consumer-1".to_string()]);
+        assert_eq!(warning_service.get_warnings_by_category(WarningCategory::ConsumerHealth).len(), 1);
+    }
+
+    #[test]
+    fn test_consumer_last_error_reported() {
+        let warning_service = Arc::new(WarningService::default());
+        let config = HealthServiceConfig {
+            consumer_startup_grace_secs: 0,
+            ..Default::default()
+        };
+        let service = HealthService::new(config, warning_service.clone());
+
+        service.set_consumer_running("consumer-1", true);
+        let health = service.get_consumer_health("consumer-1");
+        assert_eq!(health.last_error, None);
+        assert_eq!(health.last_error_at, None);
+
+        service.record_consumer_error("consumer-1", "AccessDenied");
+        service.record_consumer_error("consumer-1", "connection refused");
+
+        let health = service.get_consumer_health("consumer-1");
+        assert!(!health.is_healthy);
+        assert_eq!(health.last_error.as_deref(), Some("connection refused"));
+        assert!(health.last_error_at.is_some());
+
+        service.check_consumer_stalls();
+        let warnings = warning_service.get_warnings_by_category(WarningCategory::ConsumerHealth);
+        assert!(warnings[0].message.ends_with("(last error: connection refused)"), "{}", warnings[0].message);
+        assert!(service.get_health_report(&[]).issues.contains(
+            &"Consumer consumer-1 is stalled (last error: connection refused)".to_string()
+        ));
     }
 }
@@ -5,10 +5,13 @@
 //! - ProcessPool: Worker pools with concurrency control, rate limiting, and FIFO ordering
 //! - HttpMediator: HTTP-based message delivery with circuit breaker and retry
 //! - WarningService: In-memory warning storage with categories and severity
+//! - WarningSink: Destination notified of severe warnings (e.g. HttpWarningSink webhooks)
+//! - WarningRepository: Optional warning persistence (SQLite, PostgreSQL, MongoDB)
 //! - HealthService: System health monitoring with rolling windows
 //! - Lifecycle: Background tasks for visibility extension, health checks, etc.
 //! - PoolMetricsCollector: Enhanced metrics with sliding windows and percentiles
 //! - CircuitBreakerRegistry: Per-endpoint circuit breaker tracking for monitoring
+//! - DeadLetterSink: Destination for permanently failed messages before they are ACKed
 //! - ConfigSync: Dynamic configuration sync from central service
 //! - Standby: Active/standby high availability with Redis leader election
 //! - API: HTTP API endpoints for monitoring, health, and message publishing
@@ -20,9 +23,11 @@ pub mod mediator;
 pub mod lifecycle;
 pub mod router_metrics;
 pub mod warning;
+pub mod warning_repository;
 pub mod health;
 pub mod metrics;
 pub mod circuit_breaker_registry;
+pub mod dead_letter;
 pub mod config_sync;
 pub mod standby;
 pub mod notification;
@@ -30,14 +35,22 @@ pub mod queue_health_monitor;
 pub mod api;
 
 pub use error::RouterError;
-pub use manager::{QueueManager, InFlightMessageInfo};
+pub use manager::{
+    QueueManager, InFlightMessageInfo, CancelOutcome, DrainOutcome, ConfigSnapshot, FieldViolation, DrainingConfig,
+    validate_pool_configs, DEFAULT_SHUTDOWN_DRAIN_TIMEOUT, MAX_POOL_CONCURRENCY,
+};
 pub use pool::{ProcessPool, PoolConfigUpdate};
-pub use mediator::{Mediator, HttpMediator, CircuitState, HttpMediatorConfig, HttpVersion};
-pub use lifecycle::{LifecycleManager, LifecycleConfig};
-pub use warning::{WarningService, WarningServiceConfig};
+pub use mediator::{
+    Mediator, HttpMediator, CircuitState, HttpMediatorConfig, HttpVersion, AuthScheme, PayloadFormat, CORRELATION_ID_HEADER,
+    MAX_MESSAGE_RETRIES, MAX_MESSAGE_TIMEOUT_SECONDS,
+};
+pub use lifecycle::{LifecycleManager, LifecycleConfig, VisibilityTuning};
+pub use warning::{WarningService, WarningServiceConfig, WarningSink, HttpWarningSink};
+pub use warning_repository::WarningRepository;
 pub use health::{HealthService, HealthServiceConfig};
-pub use metrics::{PoolMetricsCollector, MetricsConfig};
-pub use circuit_breaker_registry::{CircuitBreakerRegistry, CircuitBreakerConfig, CircuitBreakerStats, CircuitBreakerState};
+pub use metrics::{PoolMetricsCollector, MetricsConfig, LatencyPercentiles};
+pub use dead_letter::{DeadLetter, DeadLetterSink};
+pub use circuit_breaker_registry::{CircuitBreakerRegistry, CircuitBreakerConfig, CircuitBreakerStats, CircuitBreakerState, SlidingWindowType};
 pub use config_sync::{ConfigSyncService, ConfigSyncConfig, ConfigSyncResult, spawn_config_sync_task};
 pub use standby::{
     StandbyProcessor, StandbyAwareProcessor, StandbyRouterConfig,
@@ -16,6 +16,7 @@ use tracing::{info, warn, debug, error};
 
 use fc_common::{WarningCategory, WarningSeverity};
 use crate::manager::QueueManager;
+use crate::metrics::LatencyPercentiles;
 use crate::health::HealthService;
 use crate::warning::WarningService;
 use crate::config_sync::{ConfigSyncService, spawn_config_sync_task};
@@ -32,10 +33,16 @@ pub struct LifecycleConfig {
     pub consumer_health_interval: Duration,
     /// Interval for warning service cleanup
     pub warning_cleanup_interval: Duration,
+    /// Interval for removing acknowledged warnings past their TTL
+    pub acknowledged_warning_sweep_interval: Duration,
     /// Interval for health report generation
     pub health_report_interval: Duration,
+    /// Interval for stalled in-flight message checks (warnings and force-NACK per `StallConfig`)
+    pub stall_check_interval: Duration,
     /// Consumer restart delay after detecting a stall
     pub consumer_restart_delay: Duration,
+    /// Bounds for visibility extension derived from observed latency
+    pub visibility_tuning: VisibilityTuning,
 }
 
 impl Default for LifecycleConfig {
@@ -45,12 +52,69 @@ impl Default for LifecycleConfig {
             memory_health_interval: Duration::from_secs(60),
             consumer_health_interval: Duration::from_secs(30),
             warning_cleanup_interval: Duration::from_secs(300),  // 5 minutes
+            acknowledged_warning_sweep_interval: Duration::from_secs(60),
             health_report_interval: Duration::from_secs(60),
+            stall_check_interval: Duration::from_secs(30),
             consumer_restart_delay: Duration::from_secs(5),
+            visibility_tuning: VisibilityTuning::default(),
         }
     }
 }
 
+/// Adaptive visibility extension. A pool's threshold (how long a message runs
+/// before it is extended) follows its p95 latency, and the extension follows
+/// twice its p99, each clamped to a floor and ceiling. Pools without enough
+/// completed messages use the static threshold and extension.
+#[derive(Debug, Clone)]
+pub struct VisibilityTuning {
+    /// Earliest point at which a message is extended
+    pub threshold_floor: Duration,
+    /// Latest point at which a message is extended; keep below the queue's visibility timeout
+    pub threshold_ceiling: Duration,
+    pub extension_floor: Duration,
+    pub extension_ceiling: Duration,
+    /// Completed messages a pool needs before its latency is trusted
+    pub min_samples: u64,
+}
+
+impl Default for VisibilityTuning {
+    fn default() -> Self {
+        Self {
+            threshold_floor: Duration::from_secs(30),
+            threshold_ceiling: Duration::from_secs(VisibilityTuning::STATIC_THRESHOLD_SECS),
+            extension_floor: Duration::from_secs(VisibilityTuning::STATIC_EXTENSION_SECS as u64),
+            extension_ceiling: Duration::from_secs(900),
+            min_samples: 20,
+        }
+    }
+}
+
+impl VisibilityTuning {
+    /// Threshold used when no latency is available (SQS visibility timeout minus a safety buffer)
+    pub const STATIC_THRESHOLD_SECS: u64 = 50;
+    /// Extension used when no latency is available (matches Java)
+    pub const STATIC_EXTENSION_SECS: u32 = 120;
+
+    /// Threshold and extension, in seconds, for a pool with the given latency
+    pub fn resolve(&self, latency: Option<&LatencyPercentiles>) -> (u64, u32) {
+        let Some(latency) = latency.filter(|l| l.sample_count >= self.min_samples) else {
+            return (Self::STATIC_THRESHOLD_SECS, Self::STATIC_EXTENSION_SECS);
+        };
+
+        let threshold = latency.p95_ms.div_ceil(1000)
+            .clamp(self.threshold_floor.as_secs(), self.threshold_ceiling.as_secs().max(self.threshold_floor.as_secs()));
+        let extension = (latency.p99_ms * 2).div_ceil(1000)
+            .clamp(self.extension_floor.as_secs(), self.extension_ceiling.as_secs().max(self.extension_floor.as_secs()));
+
+        (threshold, extension.min(u32::MAX as u64) as u32)
+    }
+
+    /// Whether a resolved value is at least twice, or at most half, the static default
+    pub fn differs_significantly(value: u64, static_value: u64) -> bool {
+        value >= static_value * 2 || value * 2 <= static_value
+    }
+}
+
 /// Manages lifecycle tasks for the message router
 pub struct LifecycleManager {
     shutdown_tx: broadcast::Sender<()>,
@@ -93,6 +157,7 @@ impl LifecycleManager {
             let _warning_service = warning_service.clone();
             let mut shutdown_rx = shutdown_tx.subscribe();
             let interval = config.visibility_extension_interval;
+            let visibility_tuning = config.visibility_tuning.clone();
 
             tokio::spawn(async move {
                 let mut ticker = tokio::time::interval(interval);
@@ -101,7 +166,7 @@ impl LifecycleManager {
                     tokio::select! {
                         _ = ticker.tick() => {
                             debug!("Running visibility extension check");
-                            manager.extend_visibility_for_long_running().await;
+                            manager.extend_visibility_for_long_running(&visibility_tuning).await;
                         }
                         _ = shutdown_rx.recv() => {
                             info!("Visibility extender shutting down");
@@ -112,6 +177,29 @@ impl LifecycleManager {
             });
         }
 
+        // Stalled in-flight message recovery
+        {
+            let manager = manager.clone();
+            let mut shutdown_rx = shutdown_tx.subscribe();
+            let interval = config.stall_check_interval;
+
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {
+                            manager.check_and_handle_stalled_messages().await;
+                        }
+                        _ = shutdown_rx.recv() => {
+                            info!("Stall checker shutting down");
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+
         // Memory health monitor
         {
             let manager = manager.clone();
@@ -161,7 +249,7 @@ impl LifecycleManager {
                 loop {
                     tokio::select! {
                         _ = ticker.tick() => {
-                            let stalled = health_service.get_stalled_consumers();
+                            let stalled = health_service.check_consumer_stalls();
                             for consumer_id in stalled {
                                 let attempts = restart_attempts.entry(consumer_id.clone()).or_insert(0);
 
@@ -247,6 +335,29 @@ impl LifecycleManager {
             });
         }
 
+        // Acknowledged warning expiry
+        {
+            let warning_service = warning_service.clone();
+            let mut shutdown_rx = shutdown_tx.subscribe();
+            let interval = config.acknowledged_warning_sweep_interval;
+
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {
+                            warning_service.expire_acknowledged();
+                        }
+                        _ = shutdown_rx.recv() => {
+                            info!("Acknowledged warning sweep shutting down");
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+
         // Health report logger
         {
             let manager = manager.clone();
@@ -392,4 +503,43 @@ mod tests {
         assert_eq!(config.visibility_extension_interval, Duration::from_secs(55));
         assert_eq!(config.memory_health_interval, Duration::from_secs(60));
     }
+
+    fn latency(p95_ms: u64, p99_ms: u64, sample_count: u64) -> LatencyPercentiles {
+        LatencyPercentiles {
+            avg_ms: p95_ms as f64 / 2.0,
+            p50_ms: p95_ms / 2,
+            p95_ms,
+            p99_ms,
+            max_ms: p99_ms,
+            sample_count,
+            window_duration_secs: 1800,
+        }
+    }
+
+    #[test]
+    fn test_visibility_tuning_falls_back_to_static() {
+        let tuning = VisibilityTuning::default();
+        assert_eq!(tuning.resolve(None), (50, 120));
+        // Too few samples to trust
+        assert_eq!(tuning.resolve(Some(&latency(600_000, 900_000, 5))), (50, 120));
+    }
+
+    #[test]
+    fn test_visibility_tuning_follows_latency_within_bounds() {
+        let tuning = VisibilityTuning::default();
+
+        // Fast pool: both clamp to their floors
+        assert_eq!(tuning.resolve(Some(&latency(200, 500, 100))), (30, 120));
+        // p95 of 40s, p99 of 150s: extend after 40s by 300s
+        assert_eq!(tuning.resolve(Some(&latency(40_000, 150_000, 100))), (40, 300));
+        // Very slow pool: both clamp to their ceilings
+        assert_eq!(tuning.resolve(Some(&latency(600_000, 3_600_000, 100))), (50, 900));
+    }
+
+    #[test]
+    fn test_visibility_tuning_significant_difference() {
+        assert!(VisibilityTuning::differs_significantly(300, 120));
+        assert!(VisibilityTuning::differs_significantly(25, 50));
+        assert!(!VisibilityTuning::differs_significantly(40, 50));
+    }
 }
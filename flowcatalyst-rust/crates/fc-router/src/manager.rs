@@ -8,25 +8,34 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use lru::LruCache;
 use parking_lot::Mutex;
-use tokio::sync::{oneshot, broadcast, RwLock};
-use tracing::{info, warn, error, debug};
+use tokio::sync::{oneshot, broadcast, watch, RwLock};
+use tracing::{info, warn, error, debug, info_span, Instrument};
 
 use fc_common::{
-    QueuedMessage, BatchMessage, AckNack, InFlightMessage,
-    PoolConfig, RouterConfig, PoolStats, StallConfig, StalledMessageInfo,
-    WarningCategory, WarningSeverity,
+    Message, QueuedMessage, BatchMessage, AckNack, InFlightMessage,
+    PoolConfig, RouterConfig, PoolStats, EnhancedPoolMetrics, StallConfig, StalledMessageInfo,
+    WarningCategory, WarningSeverity, DEFAULT_POLL_BATCH_SIZE, DEFAULT_POOL_CODE, LEGACY_DEFAULT_POOL_CODE,
 };
 use fc_queue::{QueueConsumer, QueueMetrics};
 use chrono::Utc;
+use serde::Serialize;
 use utoipa::ToSchema;
 
 use crate::pool::ProcessPool;
-use crate::mediator::Mediator;
+use crate::metrics::LatencyPercentiles;
+use crate::lifecycle::VisibilityTuning;
+use crate::mediator::{message_span, Mediator};
+use crate::dead_letter::{DeadLetter, DeadLetterSink};
 use crate::warning::WarningService;
+use crate::health::HealthService;
 use crate::error::RouterError;
 use crate::Result;
 
@@ -41,6 +50,114 @@ pub trait ConsumerFactory {
     ) -> Result<Arc<dyn QueueConsumer + Send + Sync>>;
 }
 
+/// How long a queue connectivity check result is reused, so readiness probes don't hit the broker on every call
+const CONNECTIVITY_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Time allowed for each consumer to answer a connectivity check
+const CONNECTIVITY_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long `shutdown` waits for in-flight messages by default
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often `shutdown` logs draining progress
+const SHUTDOWN_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a restart waits for the replaced poll tasks to exit before starting new ones
+const POLL_LOOP_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Completed app message IDs remembered, so cancelling one reports a conflict rather than not found
+const RECENTLY_COMPLETED_CAPACITY: usize = 1000;
+
+/// Broker message IDs whose failed deliveries are counted at once; the least
+/// recently failed one is forgotten beyond this, so messages settled elsewhere
+/// (another instance, a stall sweep) can't grow the map without bound
+const DELIVERY_ATTEMPTS_CAPACITY: usize = 100_000;
+
+/// Failed deliveries so far by broker message ID
+type DeliveryAttemptCounts = Arc<Mutex<LruCache<String, u32>>>;
+
+/// Upper bound on pool concurrency accepted in a config reload
+pub const MAX_POOL_CONCURRENCY: u32 = 1000;
+
+/// A single invalid config or request field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FieldViolation {
+    /// Offending field; list entries are named by index, e.g. `processing_pools[2].concurrency`
+    pub field: String,
+    /// What is wrong with the value
+    pub message: String,
+}
+
+impl FieldViolation {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into() }
+    }
+}
+
+/// How a consumer's queue is polled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PollSettings {
+    /// Poll tasks run in parallel
+    connections: u32,
+    /// Messages requested per poll
+    batch_size: u32,
+}
+
+impl Default for PollSettings {
+    fn default() -> Self {
+        Self { connections: 1, batch_size: DEFAULT_POLL_BATCH_SIZE }
+    }
+}
+
+/// A consumer's live poll tasks
+struct PollLoops {
+    /// Never sent on; dropping it stops the tasks
+    _stop: watch::Sender<()>,
+    handles: Vec<tokio::task::JoinHandle<()>>,
+}
+
+/// Live configuration of a [`QueueManager`], as exported by `GET /config`
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigSnapshot {
+    /// Active pools and the queues of running consumers. Serialized at the top
+    /// level, so an edited snapshot can be sent back to `POST /config/reload`.
+    #[serde(flatten)]
+    pub config: RouterConfig,
+    /// Running consumers without a queue config, i.e. registered directly
+    /// rather than created by a config sync
+    pub unconfigured_consumers: Vec<String>,
+    /// Pools and queues being removed once their in-flight work completes
+    pub draining: DrainingConfig,
+}
+
+/// Pools and queues that are draining
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DrainingConfig {
+    pub processing_pools: Vec<PoolConfig>,
+    /// Identifiers of draining queue consumers
+    pub queues: Vec<String>,
+}
+
+/// Outcome of [`QueueManager::cancel_in_flight`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelOutcome {
+    /// NACKed and removed from the pipeline
+    Cancelled,
+    /// The pool already settled the message
+    AlreadyCompleted,
+    NotFound,
+}
+
+/// Outcome of [`QueueManager::drain_pool`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrainOutcome {
+    /// Stopped accepting messages and moved to the draining pools
+    Draining,
+    /// An earlier pool with the same code is still draining; nothing was changed
+    AlreadyDraining,
+    NotFound,
+}
+
 /// Central orchestrator for message routing
 pub struct QueueManager {
     /// In-pipeline message tracking for deduplication
@@ -82,6 +199,9 @@ pub struct QueueManager {
     /// Running state
     running: AtomicBool,
 
+    /// Consumers stop polling while set; pools keep processing in-flight messages
+    draining: AtomicBool,
+
     /// Shutdown signal sender
     shutdown_tx: broadcast::Sender<()>,
 
@@ -103,8 +223,50 @@ pub struct QueueManager {
     /// Stall detection configuration
     stall_config: StallConfig,
 
+    /// How long `shutdown` waits for in-flight messages to finish
+    shutdown_drain_timeout: Duration,
+
     /// Warning service for generating operational warnings
     warning_service: Option<Arc<WarningService>>,
+
+    /// Health service for recording consumer polls (stall detection)
+    health_service: Option<Arc<HealthService>>,
+
+    /// Receives permanently failed messages from every pool before they are ACKed
+    dead_letter_sink: Option<Arc<dyn DeadLetterSink>>,
+
+    /// Last queue connectivity check: when it ran and which queues were unreachable
+    connectivity_cache: Mutex<Option<(Instant, Vec<String>)>>,
+
+    /// Delivery attempt limits by consumer identifier (the queue config's `name`),
+    /// for queues that set `max_delivery_attempts`
+    max_delivery_attempts: Mutex<HashMap<String, u32>>,
+
+    /// Poll parallelism and batch size by queue name, for configured queues
+    poll_settings: Mutex<HashMap<String, PollSettings>>,
+
+    /// Each consumer's running poll tasks; replacing or removing an entry's stop
+    /// sender stops them
+    poll_loops: Mutex<HashMap<String, PollLoops>>,
+
+    /// Failed deliveries so far, keyed by broker message ID (stable across redeliveries).
+    /// Entries are removed when the message is ACKed or dead-lettered, and the least
+    /// recently failed are evicted past `DELIVERY_ATTEMPTS_CAPACITY`.
+    delivery_attempts: DeliveryAttemptCounts,
+
+    /// Last visibility threshold and extension resolved per pool, so changes are logged once
+    visibility_tuning_logged: Mutex<HashMap<String, (u64, u32)>>,
+
+    /// App message IDs already warned about as stalled, so each raises one warning
+    reported_stalled: Mutex<HashSet<String>>,
+
+    /// Settlement flag per pipeline key, shared with the message's ACK/NACK task.
+    /// Whoever sets it first (the task on pool completion, or a cancel/force-NACK)
+    /// settles the message with the broker; the other side leaves it alone.
+    settlements: Arc<DashMap<String, Arc<AtomicBool>>>,
+
+    /// Most recently completed app message IDs, oldest first
+    recently_completed: Arc<Mutex<VecDeque<String>>>,
 }
 
 impl QueueManager {
@@ -135,15 +297,30 @@ impl QueueManager {
             queue_configs: RwLock::new(HashMap::new()),
             consumer_factory: None,
             mediator,
-            default_pool_code: "DEFAULT-POOL".to_string(),  // Java: DEFAULT_POOL_CODE
+            default_pool_code: DEFAULT_POOL_CODE.to_string(),
             running: AtomicBool::new(true),
+            draining: AtomicBool::new(false),
             shutdown_tx,
             batch_counter: std::sync::atomic::AtomicU64::new(0),
             pending_delete_broker_ids: Arc::new(Mutex::new(HashSet::new())),
             max_pools,
             pool_warning_threshold,
             stall_config,
+            shutdown_drain_timeout: DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
             warning_service: None,
+            health_service: None,
+            dead_letter_sink: None,
+            connectivity_cache: Mutex::new(None),
+            max_delivery_attempts: Mutex::new(HashMap::new()),
+            poll_settings: Mutex::new(HashMap::new()),
+            poll_loops: Mutex::new(HashMap::new()),
+            delivery_attempts: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(DELIVERY_ATTEMPTS_CAPACITY).expect("delivery attempts capacity is non-zero"),
+            ))),
+            visibility_tuning_logged: Mutex::new(HashMap::new()),
+            reported_stalled: Mutex::new(HashSet::new()),
+            settlements: Arc::new(DashMap::new()),
+            recently_completed: Arc::new(Mutex::new(VecDeque::with_capacity(RECENTLY_COMPLETED_CAPACITY))),
         }
     }
 
@@ -162,6 +339,23 @@ impl QueueManager {
         self.warning_service.as_ref()
     }
 
+    /// Set the health service used to track consumer polls
+    pub fn set_health_service(&mut self, health_service: Arc<HealthService>) {
+        self.health_service = Some(health_service);
+    }
+
+    /// Set the dead-letter sink for permanently failed messages, and for messages
+    /// that exhaust their queue's `max_delivery_attempts`; without one those are
+    /// retried forever. Pools pick it up when they are created.
+    pub fn set_dead_letter_sink(&mut self, sink: Arc<dyn DeadLetterSink>) {
+        self.dead_letter_sink = Some(sink);
+    }
+
+    /// Set how long `shutdown` waits for in-flight messages before giving up on them
+    pub fn set_shutdown_drain_timeout(&mut self, timeout: Duration) {
+        self.shutdown_drain_timeout = timeout;
+    }
+
     /// Add a queue consumer
     pub async fn add_consumer(&self, consumer: Arc<dyn QueueConsumer + Send + Sync>) {
         let id = consumer.identifier().to_string();
@@ -170,6 +364,10 @@ impl QueueManager {
 
     /// Apply router configuration (initial setup)
     pub async fn apply_config(&self, config: RouterConfig) -> Result<()> {
+        self.check_pool_configs(&config.processing_pools)?;
+        self.update_max_delivery_attempts(&config.queues);
+        self.update_poll_settings(&config.queues);
+
         let mut pool_configs = self.pool_configs.write().await;
         for pool_config in config.processing_pools {
             let code = pool_config.code.clone();
@@ -179,12 +377,26 @@ impl QueueManager {
         Ok(())
     }
 
+    /// Reject a pool list that fails [`validate_pool_configs`], naming every violation
+    fn check_pool_configs(&self, pools: &[PoolConfig]) -> Result<()> {
+        let violations = validate_pool_configs(pools, self.max_pools);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = violations.iter()
+            .map(|v| format!("{}: {}", v.field, v.message))
+            .collect();
+        Err(RouterError::Config(format!("Invalid pool configuration: {}", details.join("; "))))
+    }
+
     /// Hot reload configuration - applies changes without restart
     /// Mirrors Java's updatePoolConfiguration behavior:
-    /// - Removed pools: drain asynchronously
+    /// - Removed pools: drain asynchronously, or stay running until a later reload
+    ///   if an earlier pool with the same code is still draining
     /// - Updated pools: update concurrency/rate limit in-place
     /// - New pools: create and start
-    pub async fn reload_config(&self, config: RouterConfig) -> Result<bool> {
+    /// - Changed poll settings: restart the queue's running poll tasks
+    pub async fn reload_config(self: &Arc<Self>, config: RouterConfig) -> Result<bool> {
         if !self.running.load(Ordering::SeqCst) {
             warn!("Cannot reload config - QueueManager is shutting down");
             return Ok(false);
@@ -192,6 +404,14 @@ impl QueueManager {
 
         info!("Hot reloading configuration...");
 
+        // Reject before touching any pools, so a bad config leaves the current one intact
+        self.check_pool_configs(&config.processing_pools)?;
+        self.update_max_delivery_attempts(&config.queues);
+        let poll_settings_changed = self.update_poll_settings(&config.queues);
+
+        // Retire finished draining pools first, so pools whose removal was deferred can drain now
+        self.cleanup_draining_pools().await;
+
         // Build map of new pool configs
         let new_pool_configs: HashMap<String, PoolConfig> = config.processing_pools
             .iter()
@@ -211,36 +431,36 @@ impl QueueManager {
                 if let Some(old_config) = pool_configs.get(&pool_code) {
                     let concurrency_changed = old_config.concurrency != new_config.concurrency;
                     let rate_limit_changed = old_config.rate_limit_per_minute != new_config.rate_limit_per_minute;
-
-                    if concurrency_changed || rate_limit_changed {
-                        if let Some(pool) = self.pools.get(&pool_code) {
-                            // Update the pool in-place
-                            if concurrency_changed {
-                                info!(
-                                    pool_code = %pool_code,
-                                    old_concurrency = old_config.concurrency,
-                                    new_concurrency = new_config.concurrency,
-                                    "Updating pool concurrency"
-                                );
-                                pool.update_concurrency(new_config.concurrency).await;
-                            }
-
-                            if rate_limit_changed {
-                                info!(
-                                    pool_code = %pool_code,
-                                    old_rate_limit = ?old_config.rate_limit_per_minute,
-                                    new_rate_limit = ?new_config.rate_limit_per_minute,
-                                    "Updating pool rate limit"
-                                );
-                                pool.update_rate_limit(new_config.rate_limit_per_minute);
+                    let rate_limit_scope_changed = old_config.rate_limit_scope != new_config.rate_limit_scope;
+                    let rate_limit_burst_changed = old_config.rate_limit_burst != new_config.rate_limit_burst;
+                    let ack_on_4xx_changed = old_config.ack_on_4xx != new_config.ack_on_4xx;
+
+                    if concurrency_changed || rate_limit_changed || rate_limit_scope_changed
+                        || rate_limit_burst_changed || ack_on_4xx_changed
+                    {
+                        // Clone the Arc so the DashMap Ref guard is dropped before awaiting
+                        let pool = self.pools.get(&pool_code).map(|p| p.clone());
+                        if let Some(pool) = pool {
+                            // Update the pool in-place; a failed concurrency decrease keeps the old
+                            // limit, and the stored config records the one actually in effect
+                            if let Err(e) = apply_pool_settings(&pool, new_config).await {
+                                warn!(pool_code = %pool_code, error = %e, "Pool concurrency left unchanged");
                             }
-
                             pools_updated += 1;
+                            pool_configs.insert(pool_code, applied_pool_config(&pool, new_config));
+                            continue;
                         }
                     }
                 }
                 // Update stored config
                 pool_configs.insert(pool_code, new_config.clone());
+            } else if self.draining_pools.contains_key(&pool_code) {
+                // Draining this pool would replace the earlier one, which would then never
+                // be shut down, so keep it running until a later reload finds that one retired
+                warn!(
+                    pool_code = %pool_code,
+                    "Pool removed from config while an earlier pool with the same code is still draining - deferring removal"
+                );
             } else {
                 // Pool removed from config - drain asynchronously
                 if let Some((code, pool)) = self.pools.remove(&pool_code) {
@@ -311,6 +531,17 @@ impl QueueManager {
         // Step 3: Sync queue consumers (Java: Step 4)
         let (queues_created, queues_removed) = self.sync_queue_consumers(&config).await?;
 
+        // Poll settings only apply to new poll tasks, so replace those already running
+        for queue_id in poll_settings_changed {
+            if !self.poll_loops.lock().contains_key(&queue_id) {
+                continue;
+            }
+            if let Some(consumer) = self.get_consumer(&queue_id).await {
+                info!(queue_id = %queue_id, "Poll settings changed - restarting consumer poll tasks");
+                self.spawn_poll_loops(consumer).await;
+            }
+        }
+
         // Get counts before logging (avoid await in info! macro)
         let total_active_consumers = self.consumers.read().await.len();
 
@@ -338,11 +569,7 @@ impl QueueManager {
         // Build map of new queue configs
         let new_queue_configs: HashMap<String, fc_common::QueueConfig> = config.queues
             .iter()
-            .map(|q| {
-                // Use name as identifier, fall back to uri if name is empty
-                let identifier = if q.name.is_empty() { q.uri.clone() } else { q.name.clone() };
-                (identifier, q.clone())
-            })
+            .map(|q| (queue_identifier(q), q.clone()))
             .collect();
 
         let mut queue_configs = self.queue_configs.write().await;
@@ -412,6 +639,31 @@ impl QueueManager {
         Ok((queues_created, queues_removed))
     }
 
+    /// Record the delivery attempt limit of each configured queue. Limits are looked
+    /// up by consumer identifier, so consumers must be registered under [`queue_identifier`].
+    fn update_max_delivery_attempts(&self, queues: &[fc_common::QueueConfig]) {
+        *self.max_delivery_attempts.lock() = queues.iter()
+            .filter_map(|q| q.max_delivery_attempts.map(|max| (queue_identifier(q), max)))
+            .collect();
+    }
+
+    /// Record how each configured queue is polled. Takes effect for poll loops
+    /// started afterwards. Returns the queues whose settings changed.
+    fn update_poll_settings(&self, queues: &[fc_common::QueueConfig]) -> Vec<String> {
+        let settings: HashMap<String, PollSettings> = queues.iter()
+            .map(|q| (queue_identifier(q), PollSettings {
+                connections: q.connections.max(1),
+                batch_size: q.poll_batch_size.unwrap_or(DEFAULT_POLL_BATCH_SIZE).max(1),
+            }))
+            .collect();
+
+        let previous = std::mem::replace(&mut *self.poll_settings.lock(), settings.clone());
+        settings.into_iter()
+            .filter(|(name, new)| previous.get(name) != Some(new))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
     /// Cleanup draining pools that have finished
     /// Should be called periodically (e.g., every 10 seconds)
     pub async fn cleanup_draining_pools(&self) {
@@ -439,14 +691,16 @@ impl QueueManager {
 
         let pool_config = config.unwrap_or_else(|| PoolConfig {
             code: code.to_string(),
-            concurrency: 20,  // Java: DEFAULT_POOL_CONCURRENCY = 20
-            rate_limit_per_minute: None,
+            ..Default::default()
         });
 
-        let pool = ProcessPool::new(
+        let mut pool = ProcessPool::new(
             pool_config.clone(),
             self.mediator.clone(),
         );
+        if let Some(ref sink) = self.dead_letter_sink {
+            pool = pool.with_dead_letter_sink(sink.clone());
+        }
 
         let pool_arc = Arc::new(pool);
         pool_arc.start().await;
@@ -472,6 +726,20 @@ impl QueueManager {
         }
 
         let batch_id = self.batch_counter.fetch_add(1, Ordering::SeqCst).to_string();
+        // Parent of each message's span, unless the message carries a traceparent, in which
+        // case only log output nests under it; the spawned ACK callbacks inherit it through those spans
+        let route_span = info_span!(
+            "route_batch",
+            consumer = %consumer.identifier(),
+            batch_id = %batch_id,
+            batch_size = messages.len(),
+        );
+
+        // Attempts are only counted when exhausted messages have somewhere to go
+        let max_delivery_attempts = self.dead_letter_sink.as_ref().and_then(|sink| {
+            let max_attempts = *self.max_delivery_attempts.lock().get(consumer.identifier())?;
+            Some((max_attempts, sink.clone()))
+        });
 
         // Phase 0: Check for messages that need immediate deletion (previously processed but ACK failed)
         // First, identify which messages need deletion (while holding lock)
@@ -499,6 +767,9 @@ impl QueueManager {
                 app_message_id = %msg.message.id,
                 "Message was previously processed - deleting from queue now"
             );
+            if let Some(ref broker_id) = msg.broker_message_id {
+                self.delivery_attempts.lock().pop(broker_id);
+            }
             let _ = consumer.ack(&msg.receipt_handle).await;
         }
 
@@ -506,8 +777,10 @@ impl QueueManager {
             return Ok(());
         }
 
-        // Phase 1: Filter duplicates (takes ownership to avoid cloning payloads)
-        let filtered = self.filter_duplicates(messages_to_process);
+        // Phase 1: Filter duplicates (takes ownership to avoid cloning payloads).
+        // Unique messages are registered in the pipeline here, so concurrent pollers
+        // (several per queue with `connections` > 1) can't both admit the same message
+        let filtered = self.filter_duplicates(messages_to_process, &batch_id);
 
         // Handle duplicates - defer them (let SQS retry later, original still processing)
         // This is not an error, just a redelivery due to visibility timeout
@@ -540,6 +813,7 @@ impl QueueManager {
                     error!(pool_code = %pool_code, error = %e, "Failed to get/create pool");
                     // NACK all messages for this pool
                     for msg in pool_messages {
+                        self.release_pipeline_slot(&msg);
                         let _ = consumer.nack(&msg.receipt_handle, Some(5)).await;
                     }
                     continue;
@@ -565,6 +839,7 @@ impl QueueManager {
                 }
                 // Use defer instead of nack - capacity limits are not errors
                 for msg in pool_messages {
+                    self.release_pipeline_slot(&msg);
                     let _ = consumer.defer(&msg.receipt_handle, Some(5)).await;
                 }
                 continue;
@@ -589,35 +864,34 @@ impl QueueManager {
                             group_id = %group_id,
                             "NACKing message - previous message in group failed submission"
                         );
+                        self.release_pipeline_slot(&msg);
                         let _ = consumer.nack(&msg.receipt_handle, Some(5)).await;
                         continue;
                     }
 
                     let (ack_tx, ack_rx) = oneshot::channel();
                     let app_message_id = msg.message.id.clone();
+                    let span = route_span.in_scope(|| message_span(&msg.message));
 
-                    // Use broker_message_id as pipeline key (mirrors Java's sqsMessageId usage)
-                    // Fall back to a composite key if broker_message_id is not available
-                    let pipeline_key = msg.broker_message_id.clone()
-                        .unwrap_or_else(|| format!("fallback:{}:{}", msg.queue_identifier, msg.message.id));
+                    // Registered in the pipeline by filter_duplicates
+                    let pipeline_key = pipeline_key(&msg);
 
                     let receipt_handle = msg.receipt_handle.clone();
                     let receipt_handle_for_callback = receipt_handle.clone();  // For async task
                     let broker_message_id = msg.broker_message_id.clone();
+                    let delivery_attempts = max_delivery_attempts.as_ref()
+                        .zip(msg.broker_message_id.as_ref())
+                        .map(|((max_attempts, sink), broker_id)| DeliveryAttempts {
+                            broker_message_id: broker_id.clone(),
+                            max_attempts: *max_attempts,
+                            message: msg.message.clone(),
+                            attempts: self.delivery_attempts.clone(),
+                            sink: sink.clone(),
+                            warning_service: self.warning_service.clone(),
+                        });
 
-                    // Track in pipeline with receipt handle
-                    let in_flight = InFlightMessage::new(
-                        &msg.message,
-                        msg.broker_message_id.clone(),
-                        msg.queue_identifier.clone(),
-                        Some(batch_id.clone()),
-                        msg.receipt_handle.clone(),
-                    );
-                    self.in_pipeline.insert(pipeline_key.clone(), in_flight);
-
-                    // Track app message ID -> pipeline key for requeue detection
-                    // This mirrors Java's appMessageIdToPipelineKey map
-                    self.app_message_to_pipeline_key.insert(app_message_id.clone(), pipeline_key.clone());
+                    let settled = Arc::new(AtomicBool::new(false));
+                    self.settlements.insert(pipeline_key.clone(), settled.clone());
 
                     // Submit to pool - pool will send ACK/NACK through ack_tx
                     let batch_msg = BatchMessage {
@@ -635,6 +909,10 @@ impl QueueManager {
                     let in_pipeline = self.in_pipeline.clone();
                     let app_message_to_pipeline_key = self.app_message_to_pipeline_key.clone();
                     let pending_delete = self.pending_delete_broker_ids.clone();
+                    let pool_metrics = pool.metrics_collector();
+                    let settlements = self.settlements.clone();
+                    let recently_completed = self.recently_completed.clone();
+                    let attempt_counts = self.delivery_attempts.clone();
 
                     // Spawn task to handle callback from pool
                     // Uses latest receipt handle from in_pipeline in case of SQS redelivery
@@ -648,14 +926,38 @@ impl QueueManager {
                         // Wait for ACK/NACK from pool
                         let ack_result = ack_rx.await;
 
+                        // Cancelled or force-NACKed while in the pool: already settled and
+                        // removed from tracking, which may now hold a redelivery of this message
+                        if settled.swap(true, Ordering::SeqCst) {
+                            debug!(app_message_id = %app_message_id_clone, "Message was cancelled while in flight, ignoring pool result");
+                            return;
+                        }
+                        settlements.remove(&pipeline_key_clone);
+
                         // Cleanup from in-flight tracking IMMEDIATELY after receiving signal
                         // This ensures messages don't appear stuck even if subsequent SQS calls fail/timeout
-                        in_pipeline.remove(&pipeline_key_clone);
+                        let completed = in_pipeline.remove(&pipeline_key_clone);
                         app_message_to_pipeline_key.remove(&app_message_id_clone);
 
+                        // End-to-end latency for messages the pool finished with
+                        if let (Some((_, in_flight)), Ok(AckNack::Ack | AckNack::Nack { .. } | AckNack::Failed { .. })) = (&completed, &ack_result) {
+                            pool_metrics.record_latency(in_flight.started_at.elapsed().as_millis() as u64);
+                        }
+                        {
+                            let mut recent = recently_completed.lock();
+                            if recent.len() >= RECENTLY_COMPLETED_CAPACITY {
+                                recent.pop_front();
+                            }
+                            recent.push_back(app_message_id_clone.clone());
+                        }
+
                         // Now perform SQS operations (fire-and-forget style for cleanup)
                         match ack_result {
                             Ok(AckNack::Ack) => {
+                                // Cleared even without a limit, in case the queue had one before a reload
+                                if let Some(ref broker_id) = current_broker_id {
+                                    attempt_counts.lock().pop(broker_id);
+                                }
                                 if let Err(e) = consumer_clone.ack(&current_handle).await {
                                     // ACK failed - likely receipt handle expired
                                     // Add broker message ID to pending delete so it gets deleted on next poll
@@ -679,6 +981,20 @@ impl QueueManager {
                             Ok(AckNack::Nack { delay_seconds }) => {
                                 let _ = consumer_clone.nack(&current_handle, delay_seconds).await;
                             }
+                            Ok(AckNack::Defer { delay_seconds }) => {
+                                let _ = consumer_clone.defer(&current_handle, delay_seconds).await;
+                            }
+                            Ok(AckNack::Failed { delay_seconds }) => {
+                                let dead_lettered = match delivery_attempts {
+                                    Some(delivery_attempts) => delivery_attempts.dead_letter_if_exhausted().await,
+                                    None => false,
+                                };
+                                if dead_lettered {
+                                    let _ = consumer_clone.ack(&current_handle).await;
+                                } else {
+                                    let _ = consumer_clone.nack(&current_handle, delay_seconds).await;
+                                }
+                            }
                             Ok(AckNack::ExtendVisibility { seconds }) => {
                                 let _ = consumer_clone.extend_visibility(&current_handle, seconds).await;
                             }
@@ -687,19 +1003,19 @@ impl QueueManager {
                                 let _ = consumer_clone.nack(&current_handle, None).await;
                             }
                         }
-                    });
+                    }.instrument(span.clone()));
 
                     // Actually submit to pool
-                    if let Err(e) = pool.submit(batch_msg).await {
-                        error!(
-                            message_id = %app_message_id,
+                    if let Err(e) = pool.submit(batch_msg).instrument(span.clone()).await {
+                        span.in_scope(|| error!(
                             group_id = %group_id,
                             error = %e,
                             "Failed to submit to pool - NACKing this and remaining messages in group"
-                        );
+                        ));
 
                         // Remove from pipeline since we're NACKing
                         self.in_pipeline.remove(&pipeline_key);
+                        self.settlements.remove(&pipeline_key);
                         self.app_message_to_pipeline_key.remove(&app_message_id);
 
                         // NACK this message
@@ -715,14 +1031,19 @@ impl QueueManager {
         Ok(())
     }
 
-    /// Filter duplicates from a batch.
+    /// Filter duplicates from a batch, reserving a pipeline slot for each unique message.
     ///
     /// Mirrors Java's deduplication logic:
     /// 1. Check broker_message_id first (same SQS message = redelivery due to visibility timeout)
     /// 2. Check app_message_id second (same app ID, different broker ID = external requeue)
     ///
+    /// Both checks claim their key through a map entry, so concurrent pollers
+    /// racing on the same message admit it only once. Unique messages are left
+    /// registered in `in_pipeline`; callers that don't submit one must release it
+    /// with `release_pipeline_slot`.
+    ///
     /// Takes ownership of the messages Vec to avoid cloning payloads.
-    fn filter_duplicates(&self, messages: Vec<QueuedMessage>) -> FilteredBatch {
+    fn filter_duplicates(&self, messages: Vec<QueuedMessage>, batch_id: &str) -> FilteredBatch {
         let mut result = FilteredBatch {
             unique: Vec::with_capacity(messages.len()),
             duplicates: Vec::new(),
@@ -730,88 +1051,112 @@ impl QueueManager {
         };
 
         for msg in messages {
-            // Check 1: Same broker message ID (physical redelivery from SQS due to visibility timeout)
+            let pipeline_key = pipeline_key(&msg);
+
+            // Check 1: Same pipeline key (physical redelivery from SQS due to visibility timeout)
             // This MUST be checked FIRST because the same broker ID means it's a visibility timeout redelivery,
             // NOT a requeue by an external process
-            if let Some(ref broker_msg_id) = msg.broker_message_id {
-                if let Some(mut entry) = self.in_pipeline.get_mut(broker_msg_id) {
+            match self.in_pipeline.entry(pipeline_key.clone()) {
+                Entry::Occupied(mut entry) => {
                     // Update receipt handle with the new one from the redelivered message
                     // This ensures when processing completes, ACK uses the valid (latest) receipt handle
+                    let entry = entry.get_mut();
                     if entry.receipt_handle != msg.receipt_handle {
                         debug!(
                             message_id = %msg.message.id,
-                            broker_message_id = %broker_msg_id,
+                            pipeline_key = %pipeline_key,
                             "Updating receipt handle for redelivered message (visibility timeout)"
                         );
                         entry.receipt_handle = msg.receipt_handle.clone();
-                        // Also update broker_message_id in case it was a fallback key
-                        if entry.broker_message_id.is_none() {
-                            entry.broker_message_id = Some(broker_msg_id.clone());
-                        }
                     }
-                    let pipeline_key = broker_msg_id.clone();
                     result.duplicates.push(DuplicateMessage {
                         message: msg,
                         existing_pipeline_key: pipeline_key,
                     });
                     continue;
                 }
+                Entry::Vacant(entry) => {
+                    entry.insert(InFlightMessage::new(
+                        &msg.message,
+                        msg.broker_message_id.clone(),
+                        msg.queue_identifier.clone(),
+                        Some(batch_id.to_string()),
+                        msg.receipt_handle.clone(),
+                    ));
+                }
             }
 
-            // Check 2: Same application message ID but DIFFERENT broker message ID (requeued by external process)
+            // Check 2: Same application message ID but DIFFERENT pipeline key (requeued by external process)
             // This happens when a separate process requeues messages that were stuck in QUEUED status for 20+ min
             // The external process creates a NEW SQS message with the same application message ID
-            if let Some(existing_pipeline_key) = self.app_message_to_pipeline_key.get(&msg.message.id) {
-                let existing_key = existing_pipeline_key.value().clone();
-
-                // Only treat as requeued duplicate if the broker message IDs are DIFFERENT
-                // If they're the same, it would have been caught by the check above
-                if let Some(ref new_broker_id) = msg.broker_message_id {
-                    if *new_broker_id != existing_key {
-                        info!(
-                            app_message_id = %msg.message.id,
-                            existing_broker_id = %existing_key,
-                            new_broker_id = %new_broker_id,
-                            "Requeued message detected - app ID already in pipeline, will ACK to remove duplicate"
-                        );
-                        result.requeued.push(DuplicateMessage {
-                            message: msg,
-                            existing_pipeline_key: existing_key,
-                        });
-                        continue;
-                    }
+            let existing_key = match self.app_message_to_pipeline_key.entry(msg.message.id.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(pipeline_key);
+                    result.unique.push(msg);
+                    continue;
                 }
+                Entry::Occupied(entry) => entry.get().clone(),
+            };
 
-                // Same broker ID or no broker ID - check if still in pipeline
-                if let Some(mut entry) = self.in_pipeline.get_mut(&existing_key) {
-                    // Update receipt handle for redelivery
-                    if entry.receipt_handle != msg.receipt_handle {
-                        debug!(
-                            message_id = %msg.message.id,
-                            "Updating receipt handle for redelivered message"
-                        );
-                        entry.receipt_handle = msg.receipt_handle.clone();
-                    }
-                    result.duplicates.push(DuplicateMessage {
-                        message: msg,
-                        existing_pipeline_key: existing_key,
-                    });
-                    continue;
+            if msg.broker_message_id.is_some() && existing_key != pipeline_key {
+                info!(
+                    app_message_id = %msg.message.id,
+                    existing_pipeline_key = %existing_key,
+                    new_pipeline_key = %pipeline_key,
+                    "Requeued message detected - app ID already in pipeline, will ACK to remove duplicate"
+                );
+                self.in_pipeline.remove(&pipeline_key);
+                result.requeued.push(DuplicateMessage {
+                    message: msg,
+                    existing_pipeline_key: existing_key,
+                });
+                continue;
+            }
+
+            // No broker ID - a redelivery if the original is still in the pipeline
+            if let Some(mut entry) = self.in_pipeline.get_mut(&existing_key) {
+                // Update receipt handle for redelivery
+                if entry.receipt_handle != msg.receipt_handle {
+                    debug!(
+                        message_id = %msg.message.id,
+                        "Updating receipt handle for redelivered message"
+                    );
+                    entry.receipt_handle = msg.receipt_handle.clone();
                 }
+                drop(entry);
+                self.in_pipeline.remove(&pipeline_key);
+                result.duplicates.push(DuplicateMessage {
+                    message: msg,
+                    existing_pipeline_key: existing_key,
+                });
+                continue;
             }
 
+            // Stale mapping left by a message that has since completed
+            self.app_message_to_pipeline_key.insert(msg.message.id.clone(), pipeline_key);
             result.unique.push(msg);
         }
 
         result
     }
 
+    /// Release the pipeline slot `filter_duplicates` reserved for a message that
+    /// won't be submitted to a pool
+    fn release_pipeline_slot(&self, msg: &QueuedMessage) {
+        let pipeline_key = pipeline_key(msg);
+        self.in_pipeline.remove(&pipeline_key);
+        self.app_message_to_pipeline_key.remove_if(&msg.message.id, |_, key| *key == pipeline_key);
+    }
+
     /// Group messages by pool code
     fn group_by_pool(&self, messages: Vec<QueuedMessage>) -> std::collections::HashMap<String, Vec<QueuedMessage>> {
         let mut by_pool: std::collections::HashMap<String, Vec<QueuedMessage>> = std::collections::HashMap::new();
 
         for msg in messages {
-            let pool_code = if msg.message.pool_code.is_empty() {
+            let pool_code = msg.message.pool_code.as_str();
+            let pool_code = if pool_code.is_empty()
+                || (pool_code == LEGACY_DEFAULT_POOL_CODE && !self.pools.contains_key(pool_code))
+            {
                 self.default_pool_code.clone()
             } else {
                 msg.message.pool_code.clone()
@@ -838,58 +1183,157 @@ impl QueueManager {
         by_group
     }
 
-    /// Start the queue manager and all consumers
+    /// Start the queue manager and all consumers. Returns once the manager shuts
+    /// down and the consumers' poll tasks have exited.
     pub async fn start(self: Arc<Self>) -> Result<()> {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
         let consumers = self.consumers.read().await;
         info!(consumers = consumers.len(), "Starting QueueManager");
 
-        let mut handles = Vec::new();
-
         // Clone consumers for spawning tasks
         let consumers_vec: Vec<_> = consumers.values().cloned().collect();
         drop(consumers); // Release the read lock
 
         for consumer in consumers_vec {
-            let manager = self.clone();
-            let mut shutdown_rx = self.shutdown_tx.subscribe();
-
-            let handle = tokio::spawn(async move {
-                loop {
-                    tokio::select! {
-                        _ = shutdown_rx.recv() => {
-                            info!(consumer = %consumer.identifier(), "Consumer shutting down");
-                            break;
-                        }
-                        result = consumer.poll(10) => {
-                            match result {
-                                Ok(messages) if !messages.is_empty() => {
-                                    if let Err(e) = manager.route_batch(messages, consumer.clone()).await {
-                                        error!(error = %e, "Error routing batch");
-                                    }
+            self.spawn_poll_loops(consumer).await;
+        }
+
+        // Restarts replace poll tasks, so wait for shutdown rather than on the tasks,
+        // then join whichever tasks are live by then. The stop senders stay in place
+        // so the tasks see the shutdown rather than a restart.
+        let _ = shutdown_rx.recv().await;
+        let handles: Vec<_> = self.poll_loops.lock()
+            .values_mut()
+            .flat_map(|loops| std::mem::take(&mut loops.handles))
+            .collect();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        Ok(())
+    }
+
+    /// Spawn a consumer's poll tasks, one per configured connection. Poll tasks
+    /// the consumer already has are stopped first: dropping their stop sender
+    /// wakes them, even mid-poll, and the new tasks start once they have exited.
+    /// Any still running after `POLL_LOOP_STOP_TIMEOUT` (e.g. stuck routing a
+    /// batch) keep their handles so shutdown still waits for them.
+    async fn spawn_poll_loops(self: &Arc<Self>, consumer: Arc<dyn QueueConsumer + Send + Sync>) {
+        let settings = self.poll_settings.lock()
+            .get(consumer.identifier())
+            .copied()
+            .unwrap_or_default();
+        info!(
+            consumer = %consumer.identifier(),
+            connections = settings.connections,
+            batch_size = settings.batch_size,
+            "Starting consumer poll tasks"
+        );
+
+        // Stop the replaced tasks before spawning, so the old and new polls
+        // don't overlap. Dropping the replaced sender wakes them.
+        let replaced = self.poll_loops.lock().remove(consumer.identifier());
+        let mut replaced_handles = Vec::new();
+        for mut handle in replaced.map(|loops| loops.handles).unwrap_or_default() {
+            if tokio::time::timeout(POLL_LOOP_STOP_TIMEOUT, &mut handle).await.is_err() {
+                warn!(consumer = %consumer.identifier(), "Replaced poll task still running - starting new poll tasks anyway");
+                replaced_handles.push(handle);
+            }
+        }
+
+        let (stop_tx, stop_rx) = watch::channel(());
+
+        let mut handles: Vec<_> = (0..settings.connections)
+            .map(|_| {
+                let consumer = consumer.clone();
+                let batch_size = settings.batch_size;
+                let manager = self.clone();
+                let mut shutdown_rx = self.shutdown_tx.subscribe();
+                let mut stop_rx = stop_rx.clone();
+
+                tokio::spawn(async move {
+                    let health = manager.health_service.clone();
+                    if let Some(ref h) = health {
+                        h.set_consumer_running(consumer.identifier(), true);
+                    }
+
+                    let mut restarted = false;
+                    loop {
+                        if manager.is_draining() {
+                            // Idle on purpose: keep the heartbeat so the consumer isn't restarted as stalled
+                            if let Some(ref h) = health {
+                                h.record_consumer_poll(consumer.identifier());
+                            }
+                            tokio::select! {
+                                _ = shutdown_rx.recv() => {
+                                    info!(consumer = %consumer.identifier(), "Consumer shutting down");
+                                    break;
                                 }
-                                Ok(_) => {
-                                    // No messages, brief pause
-                                    tokio::time::sleep(Duration::from_millis(100)).await;
+                                _ = stop_rx.changed() => {
+                                    restarted = true;
+                                    break;
                                 }
-                                Err(e) => {
-                                    error!(error = %e, consumer = %consumer.identifier(), "Error polling");
-                                    tokio::time::sleep(Duration::from_secs(1)).await;
+                                _ = tokio::time::sleep(Duration::from_millis(100)) => continue,
+                            }
+                        }
+
+                        tokio::select! {
+                            _ = shutdown_rx.recv() => {
+                                info!(consumer = %consumer.identifier(), "Consumer shutting down");
+                                break;
+                            }
+                            _ = stop_rx.changed() => {
+                                restarted = true;
+                                break;
+                            }
+                            result = consumer.poll(batch_size) => {
+                                // Only successful polls count; a consumer that keeps erroring
+                                // is reported as stalled by the health service
+                                if let (Ok(_), Some(h)) = (&result, &health) {
+                                    h.record_consumer_poll(consumer.identifier());
+                                }
+
+                                match result {
+                                    Ok(messages) if !messages.is_empty() => {
+                                        if let Err(e) = manager.route_batch(messages, consumer.clone()).await {
+                                            error!(error = %e, "Error routing batch");
+                                        }
+                                    }
+                                    Ok(_) => {
+                                        // No messages, brief pause
+                                        tokio::time::sleep(Duration::from_millis(100)).await;
+                                    }
+                                    Err(e) => {
+                                        error!(error = %e, consumer = %consumer.identifier(), "Error polling");
+                                        if let Some(ref h) = health {
+                                            h.record_consumer_error(consumer.identifier(), &e.to_string());
+                                        }
+                                        tokio::time::sleep(Duration::from_secs(1)).await;
+                                    }
                                 }
                             }
                         }
                     }
-                }
-            });
 
-            handles.push(handle);
-        }
+                    // A restarted consumer keeps running on its replacement tasks
+                    if restarted {
+                        debug!(consumer = %consumer.identifier(), "Poll task replaced by restart");
+                    } else if let Some(ref h) = health {
+                        h.set_consumer_running(consumer.identifier(), false);
+                    }
+                })
+            })
+            .collect();
 
-        // Wait for all consumer tasks
-        for handle in handles {
-            let _ = handle.await;
+        handles.extend(replaced_handles);
+        let mut poll_loops = self.poll_loops.lock();
+        // Tasks spawned by a concurrent restart while this one waited are stopped
+        // by dropping their sender here, and still joined on shutdown
+        if let Some(concurrent) = poll_loops.remove(consumer.identifier()) {
+            handles.extend(concurrent.handles);
         }
-
-        Ok(())
+        poll_loops.insert(consumer.identifier().to_string(), PollLoops { _stop: stop_tx, handles });
     }
 
     /// Graceful shutdown
@@ -913,12 +1357,28 @@ impl QueueManager {
             entry.value().drain().await;
         }
 
-        // Wait for pools to drain with timeout
-        let drain_timeout = Duration::from_secs(60);
+        // Wait for pools to drain with timeout, logging progress so a long drain
+        // doesn't look hung
+        let drain_timeout = self.shutdown_drain_timeout;
         let start = Instant::now();
+        let mut last_progress = start;
+        info!(
+            in_flight = self.in_pipeline.len(),
+            timeout_secs = drain_timeout.as_secs(),
+            "Waiting for in-flight messages to drain"
+        );
 
         while !self.all_pools_drained() && start.elapsed() < drain_timeout {
             tokio::time::sleep(Duration::from_millis(500)).await;
+            if last_progress.elapsed() >= SHUTDOWN_PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                info!(
+                    in_flight = self.in_pipeline.len(),
+                    elapsed_secs = start.elapsed().as_secs(),
+                    timeout_secs = drain_timeout.as_secs(),
+                    "Still draining in-flight messages"
+                );
+            }
         }
 
         // Log any remaining in-flight messages (they'll be NACKed when tasks are dropped)
@@ -927,6 +1387,7 @@ impl QueueManager {
             warn!(remaining = remaining, "Remaining in-flight messages will be NACKed");
             self.in_pipeline.clear();
             self.app_message_to_pipeline_key.clear();
+            self.settlements.clear();
         }
 
         // Shutdown pools
@@ -942,29 +1403,88 @@ impl QueueManager {
     }
 
     /// Get statistics for all pools
+    /// Active pools first, followed by pools that are still draining
     pub fn get_pool_stats(&self) -> Vec<PoolStats> {
-        self.pools.iter().map(|entry| entry.value().get_stats()).collect()
+        let draining = self.draining_pools.iter().map(|entry| PoolStats {
+            is_draining: true,
+            ..entry.value().get_stats()
+        });
+        self.pools.iter().map(|entry| entry.value().get_stats()).chain(draining).collect()
+    }
+
+    /// Whether the pool has no room for more messages. Pools that don't exist yet
+    /// are not saturated: they are created with fresh capacity on first use.
+    pub fn is_pool_saturated(&self, pool_code: &str) -> bool {
+        self.pools.get(pool_code)
+            .map(|pool| pool.available_capacity() == 0)
+            .unwrap_or(false)
+    }
+
+    /// Fraction of buffered capacity in use across all active pools, from 0.0
+    /// (idle, or no pools) to 1.0 (every pool full)
+    pub fn system_pressure(&self) -> f64 {
+        let (used, total) = self.pools.iter().fold((0u64, 0u64), |(used, total), entry| {
+            let pool = entry.value();
+            (used + pool.queue_size() as u64, total + pool.queue_capacity() as u64)
+        });
+        if total == 0 {
+            return 0.0;
+        }
+        (used as f64 / total as f64).min(1.0)
+    }
+
+    /// Seconds until a rate-limited pool can take its next message, or `None`
+    /// if the pool doesn't exist or isn't currently held back by its rate limit
+    pub fn pool_seconds_until_capacity(&self, pool_code: &str) -> Option<u64> {
+        self.pools.get(pool_code)?.seconds_until_capacity()
+    }
+
+    /// Get sliding-window metrics for all pools, keyed by pool code.
+    /// Where a draining pool shares its code with an active one, the active pool wins.
+    pub fn get_pool_metrics(&self) -> HashMap<String, EnhancedPoolMetrics> {
+        let mut metrics: HashMap<String, EnhancedPoolMetrics> = self.pools
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().get_enhanced_metrics()))
+            .collect();
+        for entry in self.draining_pools.iter() {
+            metrics.entry(entry.key().clone())
+                .or_insert_with(|| entry.value().get_enhanced_metrics());
+        }
+        metrics
+    }
+
+    /// End-to-end latency percentiles for a pool, over the sliding window of
+    /// messages it completed. `None` if the pool doesn't exist or has no samples.
+    pub fn get_latency_percentiles(&self, pool_code: &str) -> Option<LatencyPercentiles> {
+        let pool = self.pools.get(pool_code)
+            .map(|entry| entry.value().clone())
+            .or_else(|| self.draining_pools.get(pool_code).map(|entry| entry.value().clone()))?;
+        pool.metrics_collector().latency_percentiles()
     }
 
     /// Extend visibility for long-running messages
     /// Called periodically by LifecycleManager to prevent visibility timeout
-    /// for messages that are still being processed.
-    pub async fn extend_visibility_for_long_running(&self) {
-        // Extend visibility when message has been processing for 50+ seconds
-        // This matches SQS visibility timeout minus a safety buffer
-        let threshold_seconds = 50;
-        let extension_seconds = 120; // Extend by 120 seconds (matches Java)
+    /// for messages that are still being processed. Each pool's threshold and
+    /// extension come from its observed latency, see [`VisibilityTuning`].
+    pub async fn extend_visibility_for_long_running(&self, tuning: &VisibilityTuning) {
+        // Resolve each pool once per pass
+        let mut pool_tuning: HashMap<String, (u64, u32)> = HashMap::new();
 
         // Collect messages that need visibility extension
         let mut extensions = Vec::new();
         for entry in self.in_pipeline.iter() {
             let value = entry.value();
+            let (threshold_seconds, extension_seconds) = *pool_tuning
+                .entry(value.pool_code.clone())
+                .or_insert_with(|| self.resolve_visibility_tuning(&value.pool_code, tuning));
+
             if value.elapsed_seconds() >= threshold_seconds {
                 extensions.push((
                     value.queue_identifier.clone(),
                     value.receipt_handle.clone(),
                     value.message_id.clone(),
                     value.elapsed_seconds(),
+                    extension_seconds,
                 ));
             }
         }
@@ -975,7 +1495,7 @@ impl QueueManager {
 
         // Get consumers and extend visibility
         let consumers = self.consumers.read().await;
-        for (queue_id, receipt_handle, message_id, elapsed) in extensions {
+        for (queue_id, receipt_handle, message_id, elapsed, extension_seconds) in extensions {
             if let Some(consumer) = consumers.get(&queue_id) {
                 match consumer.extend_visibility(&receipt_handle, extension_seconds).await {
                     Ok(()) => {
@@ -1000,6 +1520,32 @@ impl QueueManager {
         }
     }
 
+    /// Threshold and extension for a pool, logging when the adaptive values
+    /// move far from the static defaults
+    fn resolve_visibility_tuning(&self, pool_code: &str, tuning: &VisibilityTuning) -> (u64, u32) {
+        let latency = self.get_latency_percentiles(pool_code);
+        let resolved = tuning.resolve(latency.as_ref());
+
+        let (threshold, extension) = resolved;
+        let significant = VisibilityTuning::differs_significantly(threshold, VisibilityTuning::STATIC_THRESHOLD_SECS)
+            || VisibilityTuning::differs_significantly(extension as u64, VisibilityTuning::STATIC_EXTENSION_SECS as u64);
+        let previous = self.visibility_tuning_logged.lock().insert(pool_code.to_string(), resolved);
+        if significant && previous != Some(resolved) {
+            info!(
+                pool_code = %pool_code,
+                threshold_seconds = threshold,
+                extension_seconds = extension,
+                static_threshold_seconds = VisibilityTuning::STATIC_THRESHOLD_SECS,
+                static_extension_seconds = VisibilityTuning::STATIC_EXTENSION_SECS,
+                p95_ms = latency.as_ref().map(|l| l.p95_ms),
+                p99_ms = latency.as_ref().map(|l| l.p99_ms),
+                "Adaptive visibility extension differs significantly from static default"
+            );
+        }
+
+        resolved
+    }
+
     /// Check for potential memory leaks (large in-pipeline maps)
     pub fn check_memory_health(&self) -> bool {
         let in_pipeline_size = self.in_pipeline.len();
@@ -1055,7 +1601,9 @@ impl QueueManager {
     /// It will:
     /// 1. Detect messages that have exceeded the stall threshold
     /// 2. Log warnings for stalled messages
-    /// 3. If force_nack_stalled is enabled, NACK messages exceeding the force_nack_after_seconds threshold
+    /// 3. If force_nack_stalled is enabled, NACK messages exceeding the force_nack_after_seconds
+    ///    threshold, stop tracking them and raise a warning. This recovers entries whose
+    ///    worker or callback was lost, which would otherwise stay in flight forever.
     ///
     /// Returns the number of messages that were force-NACKed.
     pub async fn check_and_handle_stalled_messages(&self) -> usize {
@@ -1064,11 +1612,12 @@ impl QueueManager {
         }
 
         let stalled = self.detect_stalled_messages();
-        if stalled.is_empty() {
-            return 0;
-        }
 
-        // Log warnings for all stalled messages
+        // Forget messages that are no longer stalled, so a redelivery is reported again
+        self.reported_stalled.lock()
+            .retain(|message_id| stalled.iter().any(|msg| &msg.message_id == message_id));
+
+        // Log warnings for all stalled messages, raising one operational warning per message
         for msg in &stalled {
             warn!(
                 message_id = %msg.message_id,
@@ -1078,55 +1627,86 @@ impl QueueManager {
                 elapsed_seconds = msg.elapsed_seconds,
                 "Stalled message detected - processing time exceeds threshold"
             );
+
+            if !self.reported_stalled.lock().insert(msg.message_id.clone()) {
+                continue;
+            }
+            if let Some(ref ws) = self.warning_service {
+                ws.add_warning(
+                    WarningCategory::Processing,
+                    WarningSeverity::Warn,
+                    format!(
+                        "Message {} in pool {} has been in flight for {}s (stuck threshold {}s)",
+                        msg.message_id, msg.pool_code, msg.elapsed_seconds, self.stall_config.stall_threshold_seconds
+                    ),
+                    "QueueManager".to_string(),
+                );
+            }
         }
 
         // If force-NACK is not enabled, just return the count of detected stalls
         if !self.stall_config.force_nack_stalled {
-            info!(
-                stalled_count = stalled.len(),
-                threshold_seconds = self.stall_config.stall_threshold_seconds,
-                "Stalled messages detected (force-NACK disabled)"
-            );
+            if !stalled.is_empty() {
+                info!(
+                    stalled_count = stalled.len(),
+                    threshold_seconds = self.stall_config.stall_threshold_seconds,
+                    "Stalled messages detected (force-NACK disabled)"
+                );
+            }
             return 0;
         }
 
-        // Force-NACK messages that have exceeded the force_nack_after_seconds threshold
+        // Force-NACK messages that have exceeded the force_nack_after_seconds threshold.
+        // Checked independently of stall detection: the maximum age may be below the stall threshold.
+        // Collect by pipeline key: `in_pipeline` is keyed by broker message ID, not app ID.
         let force_threshold = self.stall_config.force_nack_after_seconds;
         let nack_delay = self.stall_config.nack_delay_seconds;
+        let expired: Vec<(String, InFlightMessage)> = self.in_pipeline
+            .iter()
+            .filter(|entry| entry.value().elapsed_seconds() >= force_threshold)
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+
         let consumers = self.consumers.read().await;
         let mut force_nacked = 0;
 
-        for msg in &stalled {
-            if msg.elapsed_seconds >= force_threshold {
-                // Get the in-flight message to get the receipt handle
-                if let Some(in_flight) = self.in_pipeline.get(&msg.message_id) {
-                    let receipt_handle = in_flight.receipt_handle.clone();
-                    let queue_id = in_flight.queue_identifier.clone();
-                    drop(in_flight); // Release the lock before async call
-
-                    if let Some(consumer) = consumers.get(&queue_id) {
-                        warn!(
-                            message_id = %msg.message_id,
-                            elapsed_seconds = msg.elapsed_seconds,
-                            force_threshold_seconds = force_threshold,
-                            "Force-NACKing stalled message"
-                        );
+        for (pipeline_key, in_flight) in &expired {
+            // The pool may have settled it since the scan
+            if !self.claim_settlement(pipeline_key) {
+                continue;
+            }
 
-                        if let Err(e) = consumer.nack(&receipt_handle, Some(nack_delay)).await {
-                            error!(
-                                message_id = %msg.message_id,
-                                error = %e,
-                                "Failed to force-NACK stalled message"
-                            );
-                        } else {
-                            // Remove from pipeline since we've force-NACKed
-                            self.in_pipeline.remove(&msg.message_id);
-                            self.app_message_to_pipeline_key.remove(&msg.message_id);
-                            force_nacked += 1;
-                        }
+            warn!(
+                message_id = %in_flight.message_id,
+                elapsed_seconds = in_flight.elapsed_seconds(),
+                force_threshold_seconds = force_threshold,
+                "Force-NACKing stalled message"
+            );
+
+            match consumers.get(&in_flight.queue_identifier) {
+                Some(consumer) => {
+                    if let Err(e) = consumer.nack(&in_flight.receipt_handle, Some(nack_delay)).await {
+                        error!(
+                            message_id = %in_flight.message_id,
+                            error = %e,
+                            "Failed to force-NACK stalled message, visibility timeout will redeliver it"
+                        );
                     }
                 }
+                None => {
+                    warn!(
+                        message_id = %in_flight.message_id,
+                        queue_identifier = %in_flight.queue_identifier,
+                        "Consumer for stalled message no longer exists, visibility timeout will redeliver it"
+                    );
+                }
             }
+
+            // Stop tracking either way so the entry can't stay stuck; a redelivery
+            // will be routed as a new message
+            self.in_pipeline.remove(pipeline_key);
+            self.app_message_to_pipeline_key.remove(&in_flight.message_id);
+            force_nacked += 1;
         }
 
         if force_nacked > 0 {
@@ -1135,11 +1715,83 @@ impl QueueManager {
                 total_stalled = stalled.len(),
                 "Force-NACKed stalled messages"
             );
+            if let Some(ref ws) = self.warning_service {
+                ws.add_warning(
+                    WarningCategory::Processing,
+                    WarningSeverity::Error,
+                    format!(
+                        "{} in-flight message(s) exceeded the maximum age of {}s and were NACKed for redelivery",
+                        force_nacked, force_threshold
+                    ),
+                    "QueueManager".to_string(),
+                );
+            }
         }
 
         force_nacked
     }
 
+    /// Claim the right to settle a message with the broker ahead of its ACK/NACK task.
+    /// Returns false if the task already settled it.
+    fn claim_settlement(&self, pipeline_key: &str) -> bool {
+        match self.settlements.remove(pipeline_key) {
+            Some((_, settled)) => !settled.swap(true, Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    /// Eject an in-flight message: NACK it with the stall NACK delay and stop
+    /// tracking it. The pool may still be working on it; its result is ignored.
+    pub async fn cancel_in_flight(&self, message_id: &str) -> CancelOutcome {
+        let pipeline_key = self.app_message_to_pipeline_key.get(message_id).map(|entry| entry.value().clone());
+        let Some(pipeline_key) = pipeline_key else {
+            return if self.recently_completed.lock().iter().any(|id| id == message_id) {
+                CancelOutcome::AlreadyCompleted
+            } else {
+                CancelOutcome::NotFound
+            };
+        };
+
+        if !self.claim_settlement(&pipeline_key) {
+            return CancelOutcome::AlreadyCompleted;
+        }
+
+        self.app_message_to_pipeline_key.remove(message_id);
+        let Some((_, in_flight)) = self.in_pipeline.remove(&pipeline_key) else {
+            return CancelOutcome::AlreadyCompleted;
+        };
+
+        let nack_delay = self.stall_config.nack_delay_seconds;
+        let consumer = self.consumers.read().await.get(&in_flight.queue_identifier).cloned();
+        match consumer {
+            Some(consumer) => {
+                if let Err(e) = consumer.nack(&in_flight.receipt_handle, Some(nack_delay)).await {
+                    error!(
+                        message_id = %message_id,
+                        error = %e,
+                        "Failed to NACK cancelled message, visibility timeout will redeliver it"
+                    );
+                }
+            }
+            None => {
+                warn!(
+                    message_id = %message_id,
+                    queue_identifier = %in_flight.queue_identifier,
+                    "Consumer for cancelled message no longer exists, visibility timeout will redeliver it"
+                );
+            }
+        }
+
+        info!(
+            message_id = %message_id,
+            pool_code = %in_flight.pool_code,
+            elapsed_seconds = in_flight.elapsed_seconds(),
+            nack_delay_seconds = nack_delay,
+            "In-flight message cancelled"
+        );
+        CancelOutcome::Cancelled
+    }
+
     /// Get stall detection configuration
     pub fn stall_config(&self) -> &StallConfig {
         &self.stall_config
@@ -1152,70 +1804,78 @@ impl QueueManager {
             stall_threshold_seconds = config.stall_threshold_seconds,
             force_nack_stalled = config.force_nack_stalled,
             force_nack_after_seconds = config.force_nack_after_seconds,
+            stuck_threshold_seconds = config.stuck_threshold_seconds,
             "Updating stall detection configuration"
         );
         self.stall_config = config;
     }
 
     /// Update pool configuration at runtime (hot-reload)
-    /// Note: Concurrency changes take effect on next message batch
-    /// Rate limit changes take effect immediately
+    /// Changes are applied to the existing pool in place, so in-flight work and
+    /// the pool's processing counters are kept. Creates the pool if missing.
     pub async fn update_pool_config(&self, pool_code: &str, config: PoolConfig) -> Result<()> {
-        // Check if pool exists and get current settings
-        // IMPORTANT: Drop the Ref guard before calling insert() to avoid deadlock
-        let pool_exists = if let Some(existing_pool) = self.pools.get(pool_code) {
-            let current_concurrency = existing_pool.concurrency();
-            let new_concurrency = config.concurrency;
+        // Clone the Arc so the DashMap Ref guard is dropped before awaiting
+        let existing_pool = self.pools.get(pool_code).map(|p| p.clone());
 
-            if current_concurrency != new_concurrency {
-                info!(
-                    pool_code = %pool_code,
-                    old_concurrency = current_concurrency,
-                    new_concurrency = new_concurrency,
-                    "Pool concurrency update requested - will take effect after pool restart"
-                );
-            }
-
-            let current_rate_limit = existing_pool.rate_limit_per_minute();
-            let new_rate_limit = config.rate_limit_per_minute;
-
-            if current_rate_limit != new_rate_limit {
-                info!(
-                    pool_code = %pool_code,
-                    old_rate_limit = ?current_rate_limit,
-                    new_rate_limit = ?new_rate_limit,
-                    "Pool rate limit update requested - creating new pool"
-                );
-            }
-            true
-        } else {
-            false
+        let Some(pool) = existing_pool else {
+            // Pool doesn't exist, create it and store its config like a reload would
+            self.get_or_create_pool(pool_code, Some(config.clone())).await?;
+            self.pool_configs.write().await.insert(pool_code.to_string(), config);
+            return Ok(());
         };
-        // Ref guard is now dropped
 
-        if pool_exists {
-            // For now, we recreate the pool with new config
-            // In production, you might want to drain first
-            let new_pool = ProcessPool::new(config.clone(), self.mediator.clone());
-            let pool_arc = Arc::new(new_pool);
-            pool_arc.start().await;
+        let result = apply_pool_settings(&pool, &config).await;
+        if let Some(stored) = self.pool_configs.write().await.get_mut(pool_code) {
+            *stored = applied_pool_config(&pool, &config);
+        }
+        result?;
 
-            // Replace the old pool
-            self.pools.insert(pool_code.to_string(), pool_arc);
+        info!(
+            pool_code = %pool_code,
+            concurrency = config.concurrency,
+            rate_limit = ?config.rate_limit_per_minute,
+            rate_limit_scope = ?config.rate_limit_scope,
+            rate_limit_burst = ?config.rate_limit_burst,
+            ack_on_4xx = config.ack_on_4xx,
+            "Pool configuration updated"
+        );
 
-            info!(
-                pool_code = %pool_code,
-                concurrency = config.concurrency,
-                rate_limit = ?config.rate_limit_per_minute,
-                "Pool configuration updated"
-            );
+        Ok(())
+    }
 
-            Ok(())
-        } else {
-            // Pool doesn't exist, create it
-            self.get_or_create_pool(pool_code, Some(config)).await?;
-            Ok(())
+    /// Drain and retire a single pool at runtime.
+    /// The pool stops accepting messages, finishes in-flight work and is then
+    /// removed by `cleanup_draining_pools`.
+    /// Note: a config sync that still lists the pool will recreate it. That pool
+    /// can't be drained until the earlier one has finished, which would otherwise
+    /// be replaced in the draining pools and never cleaned up.
+    pub async fn drain_pool(&self, pool_code: &str) -> Result<DrainOutcome> {
+        if !self.pools.contains_key(pool_code) {
+            return Ok(DrainOutcome::NotFound);
         }
+        if self.draining_pools.contains_key(pool_code) {
+            return Ok(DrainOutcome::AlreadyDraining);
+        }
+        let Some((code, pool)) = self.pools.remove(pool_code) else {
+            return Ok(DrainOutcome::NotFound);
+        };
+
+        info!(
+            pool_code = %code,
+            queue_size = pool.queue_size(),
+            active_workers = pool.active_workers(),
+            "Pool drain requested - draining asynchronously"
+        );
+        pool.drain().await;
+        self.draining_pools.insert(code.clone(), pool);
+        self.pool_configs.write().await.remove(&code);
+
+        Ok(DrainOutcome::Draining)
+    }
+
+    /// Most pools the manager will run at once
+    pub fn max_pools(&self) -> usize {
+        self.max_pools
     }
 
     /// Get list of all pool codes
@@ -1223,29 +1883,84 @@ impl QueueManager {
         self.pools.iter().map(|entry| entry.key().clone()).collect()
     }
 
+    /// Reconstruct the configuration currently in effect: stored pool configs,
+    /// the queue config of each running consumer, and what is still draining.
+    /// Lists are sorted by pool code / queue identifier.
+    pub async fn current_config(&self) -> ConfigSnapshot {
+        let mut processing_pools: Vec<PoolConfig> = self.pool_configs.read().await.values().cloned().collect();
+        processing_pools.sort_by(|a, b| a.code.cmp(&b.code));
+
+        let queue_configs = self.queue_configs.read().await;
+        let mut consumer_ids: Vec<String> = self.consumers.read().await.keys().cloned().collect();
+        consumer_ids.sort();
+        let (configured, unconfigured): (Vec<String>, Vec<String>) = consumer_ids
+            .into_iter()
+            .partition(|id| queue_configs.contains_key(id));
+        let queues = configured.iter().filter_map(|id| queue_configs.get(id).cloned()).collect();
+
+        // Draining pools no longer have a stored config; their stats carry the same settings
+        let mut draining_pools: Vec<PoolConfig> = self.draining_pools
+            .iter()
+            .map(|entry| {
+                let stats = entry.value().get_stats();
+                PoolConfig {
+                    code: stats.pool_code,
+                    concurrency: stats.concurrency,
+                    rate_limit_per_minute: stats.rate_limit_per_minute,
+                    rate_limit_scope: stats.rate_limit_scope,
+                    rate_limit_burst: stats.rate_limit_burst,
+                    ack_on_4xx: stats.ack_on_4xx,
+                }
+            })
+            .collect();
+        draining_pools.sort_by(|a, b| a.code.cmp(&b.code));
+
+        let mut draining_queues: Vec<String> = self.draining_consumers.read().await.keys().cloned().collect();
+        draining_queues.sort();
+
+        ConfigSnapshot {
+            config: RouterConfig { processing_pools, queues },
+            unconfigured_consumers: unconfigured,
+            draining: DrainingConfig {
+                processing_pools: draining_pools,
+                queues: draining_queues,
+            },
+        }
+    }
+
     /// Get list of all consumer identifiers
     pub async fn consumer_ids(&self) -> Vec<String> {
         self.consumers.read().await.keys().cloned().collect()
     }
 
-    /// Restart a specific consumer by ID
-    /// Returns true if consumer was found and restart was initiated
-    pub async fn restart_consumer(&self, consumer_id: &str) -> bool {
-        let consumers = self.consumers.read().await;
-        if let Some(consumer) = consumers.get(consumer_id) {
-            info!(consumer_id = %consumer_id, "Restarting consumer");
+    /// Get a running consumer by ID
+    pub async fn get_consumer(&self, consumer_id: &str) -> Option<Arc<dyn QueueConsumer + Send + Sync>> {
+        self.consumers.read().await.get(consumer_id).cloned()
+    }
 
-            // Stop the consumer first
-            consumer.stop().await;
+    /// Restart a specific consumer by ID: its poll tasks are replaced with fresh
+    /// ones, which also frees a consumer whose poll has hung.
+    /// Returns true if the consumer was found and restarted; false for unknown
+    /// consumers and while the manager is shutting down.
+    pub async fn restart_consumer(self: &Arc<Self>, consumer_id: &str) -> bool {
+        if !self.running.load(Ordering::SeqCst) {
+            warn!(consumer_id = %consumer_id, "Not restarting consumer, manager is shutting down");
+            return false;
+        }
 
-            // The consumer loop will detect the stop and exit
-            // A new poll loop will need to be started externally
-            // This is a signal that the consumer needs attention
-            true
-        } else {
+        let Some(consumer) = self.get_consumer(consumer_id).await else {
             warn!(consumer_id = %consumer_id, "Consumer not found for restart");
-            false
+            return false;
+        };
+
+        // Not polling yet; start() will spawn its poll tasks
+        if !self.poll_loops.lock().contains_key(consumer_id) {
+            return true;
         }
+
+        info!(consumer_id = %consumer_id, "Restarting consumer");
+        self.spawn_poll_loops(consumer).await;
+        true
     }
 
     /// Check if a consumer is healthy
@@ -1276,9 +1991,52 @@ impl QueueManager {
         metrics
     }
 
+    /// Check that every consumer's queue is reachable, returning the identifiers of
+    /// those that are not.
+    ///
+    /// Each consumer is pinged with a `get_metrics` call bounded by a short timeout; an
+    /// error or timeout marks the queue unreachable. Consumers that don't report metrics
+    /// are assumed reachable. Results are cached for a few seconds.
+    pub async fn check_queue_connectivity(&self) -> Vec<String> {
+        if let Some((checked_at, ref unreachable)) = *self.connectivity_cache.lock() {
+            if checked_at.elapsed() < CONNECTIVITY_CACHE_TTL {
+                return unreachable.clone();
+            }
+        }
+
+        let consumers: Vec<_> = self.consumers.read().await
+            .iter()
+            .map(|(id, consumer)| (id.clone(), consumer.clone()))
+            .collect();
+
+        let checks = consumers.into_iter().map(|(id, consumer)| async move {
+            match tokio::time::timeout(CONNECTIVITY_CHECK_TIMEOUT, consumer.get_metrics()).await {
+                Ok(Ok(_)) => None,
+                Ok(Err(e)) => {
+                    warn!(queue_identifier = %id, error = %e, "Queue connectivity check failed");
+                    Some(id)
+                }
+                Err(_) => {
+                    warn!(queue_identifier = %id, "Queue connectivity check timed out");
+                    Some(id)
+                }
+            }
+        });
+        let mut unreachable: Vec<String> = futures::future::join_all(checks).await
+            .into_iter()
+            .flatten()
+            .collect();
+        unreachable.sort();
+
+        *self.connectivity_cache.lock() = Some((Instant::now(), unreachable.clone()));
+        unreachable
+    }
+
     /// Get in-flight messages (currently being processed)
     /// Returns messages sorted by elapsed time (oldest first)
+    /// Messages past the stuck threshold are flagged `stuck`, whether or not stall detection is enabled
     pub fn get_in_flight_messages(&self, limit: usize, message_id_filter: Option<&str>) -> Vec<InFlightMessageInfo> {
+        let stuck_threshold = self.stall_config.stuck_threshold_seconds;
         let mut messages: Vec<InFlightMessageInfo> = self.in_pipeline
             .iter()
             .filter(|entry| {
@@ -1297,6 +2055,7 @@ impl QueueManager {
                     pool_code: msg.pool_code.clone(),
                     elapsed_time_ms: msg.started_at.elapsed().as_millis() as u64,
                     added_to_in_pipeline_at: chrono::Utc::now() - chrono::Duration::milliseconds(msg.started_at.elapsed().as_millis() as i64),
+                    stuck: msg.elapsed_seconds() >= stuck_threshold,
                 }
             })
             .collect();
@@ -1313,6 +2072,20 @@ impl QueueManager {
     pub fn in_flight_count(&self) -> usize {
         self.in_pipeline.len()
     }
+
+    /// Stop (or resume) polling for new messages without shutting anything down.
+    /// Used during rolling deployments: in-flight messages finish while the pod
+    /// stays alive until it is terminated.
+    pub fn set_draining(&self, draining: bool) {
+        if self.draining.swap(draining, Ordering::SeqCst) != draining {
+            info!(draining, in_flight = self.in_flight_count(), "QueueManager drain mode changed");
+        }
+    }
+
+    /// Whether consumers are paused by drain mode
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
 }
 
 /// Result of filtering duplicates from a message batch
@@ -1332,6 +2105,175 @@ struct DuplicateMessage {
     existing_pipeline_key: String,
 }
 
+/// Failed-delivery tracking for a message from a queue with `max_delivery_attempts`
+struct DeliveryAttempts {
+    broker_message_id: String,
+    max_attempts: u32,
+    message: Message,
+    attempts: DeliveryAttemptCounts,
+    sink: Arc<dyn DeadLetterSink>,
+    warning_service: Option<Arc<WarningService>>,
+}
+
+impl DeliveryAttempts {
+    /// Count a failed delivery. Once the limit is reached the message is sent to the
+    /// dead-letter sink and `true` is returned, meaning the original can be ACKed.
+    /// If the sink fails the message stays on its queue to be retried.
+    async fn dead_letter_if_exhausted(self) -> bool {
+        let attempts = {
+            let mut counts = self.attempts.lock();
+            let count = counts.get_or_insert_mut(self.broker_message_id.clone(), || 0);
+            *count += 1;
+            *count
+        };
+        if attempts < self.max_attempts {
+            return false;
+        }
+
+        let message_id = self.message.id.clone();
+        let pool_code = self.message.pool_code.clone();
+        let dead_letter = DeadLetter::exhausted(self.message, attempts);
+        if let Err(e) = self.sink.send(dead_letter).await {
+            error!(
+                message_id = %message_id,
+                attempts = attempts,
+                error = %e,
+                "Failed to send exhausted message to dead-letter sink - NACKing"
+            );
+            return false;
+        }
+
+        self.attempts.lock().pop(&self.broker_message_id);
+        warn!(
+            message_id = %message_id,
+            broker_message_id = %self.broker_message_id,
+            attempts = attempts,
+            "Message exhausted delivery attempts - moved to dead-letter queue"
+        );
+        if let Some(ref ws) = self.warning_service {
+            ws.add_warning(
+                WarningCategory::Processing,
+                WarningSeverity::Warn,
+                format!(
+                    "Message [{}] in pool [{}] moved to dead-letter queue after {} failed deliveries",
+                    message_id, pool_code, attempts
+                ),
+                "QueueManager".to_string(),
+            );
+        }
+        true
+    }
+}
+
+/// Apply `config` to a running pool in place. Workers, queued and in-flight
+/// messages and counters are kept; a concurrency decrease waits for workers
+/// to go idle (see [`ProcessPool::update_concurrency`]). Shared by config
+/// reloads and single-pool updates so both behave the same.
+///
+/// The other settings are applied even if the concurrency change fails, so
+/// callers should store [`applied_pool_config`] rather than `config`.
+async fn apply_pool_settings(pool: &ProcessPool, config: &PoolConfig) -> Result<()> {
+    let current_concurrency = pool.concurrency();
+    let mut concurrency_result = Ok(());
+    if current_concurrency != config.concurrency {
+        info!(
+            pool_code = %config.code,
+            old_concurrency = current_concurrency,
+            new_concurrency = config.concurrency,
+            "Updating pool concurrency"
+        );
+        if !pool.update_concurrency(config.concurrency).await {
+            concurrency_result = Err(RouterError::Pool(format!(
+                "Failed to change concurrency of pool {} from {} to {}",
+                config.code, current_concurrency, config.concurrency
+            )));
+        }
+    }
+
+    let current_rate_limit = pool.rate_limit_per_minute();
+    if current_rate_limit != config.rate_limit_per_minute {
+        info!(
+            pool_code = %config.code,
+            old_rate_limit = ?current_rate_limit,
+            new_rate_limit = ?config.rate_limit_per_minute,
+            "Updating pool rate limit"
+        );
+        pool.update_rate_limit(config.rate_limit_per_minute);
+    }
+
+    pool.update_rate_limit_scope(config.rate_limit_scope);
+    pool.update_rate_limit_burst(config.rate_limit_burst);
+    pool.update_ack_on_4xx(config.ack_on_4xx);
+
+    concurrency_result
+}
+
+/// `config` as the pool actually runs it after [`apply_pool_settings`]
+fn applied_pool_config(pool: &ProcessPool, config: &PoolConfig) -> PoolConfig {
+    PoolConfig { concurrency: pool.concurrency(), ..config.clone() }
+}
+
+/// Check a pool list, returning one violation per problem: more pools
+/// than `max_pools`, empty or duplicate codes, concurrency outside
+/// 1..=[`MAX_POOL_CONCURRENCY`], and zero rate limits or bursts
+pub fn validate_pool_configs(pools: &[PoolConfig], max_pools: usize) -> Vec<FieldViolation> {
+    let mut violations = Vec::new();
+    let mut violation = |field: String, message: String| violations.push(FieldViolation::new(field, message));
+
+    if pools.len() > max_pools {
+        violation(
+            "processing_pools".to_string(),
+            format!("at most {} pools are allowed (got {})", max_pools, pools.len()),
+        );
+    }
+
+    let mut seen = HashMap::new();
+    for (i, pool) in pools.iter().enumerate() {
+        let field = |name: &str| format!("processing_pools[{}].{}", i, name);
+
+        if pool.code.trim().is_empty() {
+            violation(field("code"), "must not be empty".to_string());
+        } else if let Some(first) = seen.get(pool.code.as_str()) {
+            violation(
+                field("code"),
+                format!("duplicate pool code {} (also at processing_pools[{}])", pool.code, first),
+            );
+        } else {
+            seen.insert(pool.code.as_str(), i);
+        }
+
+        if !(1..=MAX_POOL_CONCURRENCY).contains(&pool.concurrency) {
+            violation(
+                field("concurrency"),
+                format!("must be between 1 and {} (got {})", MAX_POOL_CONCURRENCY, pool.concurrency),
+            );
+        }
+
+        if pool.rate_limit_per_minute == Some(0) {
+            violation(field("rate_limit_per_minute"), "must be at least 1 when set".to_string());
+        }
+
+        if pool.rate_limit_burst == Some(0) {
+            violation(field("rate_limit_burst"), "must be at least 1 when set".to_string());
+        }
+    }
+
+    violations
+}
+
+/// Identifier a queue's consumer and settings are kept under: its name, or its
+/// URI when the name is empty
+fn queue_identifier(queue: &fc_common::QueueConfig) -> String {
+    if queue.name.is_empty() { queue.uri.clone() } else { queue.name.clone() }
+}
+
+/// Pipeline key of a polled message: its broker message ID (mirrors Java's sqsMessageId
+/// usage), falling back to a composite key when the broker doesn't provide one
+fn pipeline_key(msg: &QueuedMessage) -> String {
+    msg.broker_message_id.clone()
+        .unwrap_or_else(|| format!("fallback:{}:{}", msg.queue_identifier, msg.message.id))
+}
+
 /// Information about an in-flight message for API response
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
 pub struct InFlightMessageInfo {
@@ -1347,4 +2289,6 @@ pub struct InFlightMessageInfo {
     pub elapsed_time_ms: u64,
     #[serde(rename = "addedToInPipelineAt")]
     pub added_to_in_pipeline_at: chrono::DateTime<chrono::Utc>,
+    /// In flight longer than the stall threshold (e.g. a hung mediator)
+    pub stuck: bool,
 }
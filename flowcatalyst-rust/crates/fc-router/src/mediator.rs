@@ -3,31 +3,66 @@
 //! Mirrors the Java HttpMediator with:
 //! - HTTP POST to mediation target
 //! - Auth token handling
-//! - HMAC-SHA256 webhook signing (X-FLOWCATALYST-SIGNATURE, X-FLOWCATALYST-TIMESTAMP)
+//! - HMAC-SHA256 webhook signing (X-FLOWCATALYST-SIGNATURE, X-FLOWCATALYST-TIMESTAMP,
+//!   plus replay-resistant X-FC-Signature / X-FC-Timestamp)
 //! - Response code classification
 //! - Retry with exponential backoff
 //! - Circuit breaker pattern
 //! - Custom delay parsing from response
+//! - `{placeholder}` substitution in the target URL from the message payload
+//! - Raw (base64) bodies with a custom content type
+//! - Optional CloudEvents 1.0 structured-mode wrapping of the outbound body
 
 use async_trait::async_trait;
-use chrono::Utc;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{SecondsFormat, Utc};
 use fc_common::{Message, MediationType, MediationResult, MediationOutcome, WarningCategory, WarningSeverity};
+use fc_common::http::{HttpClientConfig, ProxyConfig};
+use fc_secrets::{SecretService, SecretsError};
 use hmac::{Hmac, Mac};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
+use std::borrow::Cow;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::{Duration, Instant};
+use dashmap::DashMap;
 use parking_lot::RwLock;
-use tracing::{info, warn, error, debug};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{info, warn, error, debug, info_span, Instrument, Span};
 
+use crate::circuit_breaker_registry::{CircuitBreakerConfig, CircuitBreakerRegistry};
+use crate::router_metrics;
 use crate::warning::WarningService;
 
 /// FlowCatalyst webhook signature header (matches Java: X-FLOWCATALYST-SIGNATURE)
 pub const SIGNATURE_HEADER: &str = "X-FLOWCATALYST-SIGNATURE";
 /// FlowCatalyst webhook timestamp header (matches Java: X-FLOWCATALYST-TIMESTAMP)
 pub const TIMESTAMP_HEADER: &str = "X-FLOWCATALYST-TIMESTAMP";
+/// Webhook signature header: `sha256=<hex>` over `timestamp + "." + body`
+pub const FC_SIGNATURE_HEADER: &str = "X-FC-Signature";
+/// Unix timestamp (seconds) covered by `X-FC-Signature`
+pub const FC_TIMESTAMP_HEADER: &str = "X-FC-Timestamp";
+/// Carries a message's correlation ID, on publish requests and on delivery
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+/// Content type of CloudEvents structured-mode request bodies
+pub const CLOUDEVENTS_CONTENT_TYPE: &str = "application/cloudevents+json";
+/// CloudEvents `type` of wrapped mediation requests
+pub const CLOUDEVENTS_MESSAGE_TYPE: &str = "flowcatalyst.message";
+
+/// Longest per-message `timeout_seconds` honoured, matching the Java default request timeout
+pub const MAX_MESSAGE_TIMEOUT_SECONDS: u32 = 900;
+/// Most per-message `max_retries` honoured; higher values are capped
+pub const MAX_MESSAGE_RETRIES: u32 = 10;
+
+/// Redelivery delay for messages turned away by a saturated endpoint
+const ENDPOINT_SATURATED_DELAY_SECONDS: u32 = 1;
+
+/// NACK delay when an `auth_token` secret reference can't be resolved (provider outage or
+/// missing secret); retried rather than dropped since the secret may be fixed in place
+const AUTH_TOKEN_RESOLVE_DELAY_SECONDS: u32 = 30;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -37,17 +72,16 @@ type HmacSha256 = Hmac<Sha256>;
 /// - Signature payload = timestamp + body
 /// - HMAC-SHA256 with signing_secret
 /// - Returns hex-encoded signature
-fn sign_webhook(payload: &str, signing_secret: &str) -> (String, String) {
+fn sign_webhook(payload: &[u8], signing_secret: &str) -> (String, String) {
     // Generate ISO8601 timestamp with millisecond precision (matches Java)
     let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
 
     // Create signature payload: timestamp + body (matches Java: signaturePayload = timestamp + payload)
-    let signature_payload = format!("{}{}", timestamp, payload);
-
     // Generate HMAC-SHA256
     let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
         .expect("HMAC can take key of any size");
-    mac.update(signature_payload.as_bytes());
+    mac.update(timestamp.as_bytes());
+    mac.update(payload);
     let result = mac.finalize();
 
     // Return as lowercase hex (matches Java: HexFormat.of().formatHex())
@@ -56,12 +90,171 @@ fn sign_webhook(payload: &str, signing_secret: &str) -> (String, String) {
     (signature, timestamp)
 }
 
+/// Generate the `X-FC-Signature` value for a webhook body.
+///
+/// The signed payload is `timestamp + "." + body`, so receivers can reject
+/// stale timestamps to prevent replay. Returns `sha256=<lowercase hex>`.
+fn sign_fc_webhook(payload: &[u8], signing_secret: &str, timestamp: i64) -> String {
+    let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Wrap an outbound body in a CloudEvents 1.0 structured-mode event.
+///
+/// JSON bodies are embedded as `data`; any other body is carried base64-encoded
+/// in `data_base64`. The message group is passed on as the `messagegroup`
+/// extension attribute.
+fn to_cloudevent(message: &Message, body: &[u8], content_type: &str, source: &str) -> Vec<u8> {
+    let mut event = serde_json::Map::new();
+    event.insert("specversion".into(), "1.0".into());
+    event.insert("id".into(), message.id.clone().into());
+    event.insert("source".into(), source.into());
+    event.insert("type".into(), CLOUDEVENTS_MESSAGE_TYPE.into());
+    event.insert("time".into(), Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true).into());
+    event.insert("datacontenttype".into(), content_type.into());
+    if let Some(ref group) = message.message_group_id {
+        event.insert("messagegroup".into(), group.clone().into());
+    }
+    if let Some(ref correlation_id) = message.correlation_id {
+        event.insert("correlationid".into(), correlation_id.clone().into());
+    }
+
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    let json_data = if media_type == "application/json" || media_type.ends_with("+json") {
+        serde_json::from_slice::<serde_json::Value>(body).ok()
+    } else {
+        None
+    };
+    match json_data {
+        Some(data) => event.insert("data".into(), data),
+        None => event.insert("data_base64".into(), BASE64.encode(body).into()),
+    };
+
+    serde_json::to_vec(&event).expect("Failed to serialize CloudEvent")
+}
+
+/// Resolve `{placeholder}`s in a mediation target from the message payload.
+///
+/// A placeholder is a dotted path (`{order.id}`) or a JSON pointer
+/// (`{/order/id}`) into the payload. Resolved values are URL-encoded;
+/// only strings, numbers and booleans can be substituted. Braces that don't
+/// enclose a path (`{}`, `{"a": 1}`, an unmatched `{`) are kept as they are.
+/// Targets without placeholders are returned unchanged.
+fn render_target<'a>(target: &'a str, payload: Option<&serde_json::Value>) -> Result<Cow<'a, str>, String> {
+    if !target.contains('{') {
+        return Ok(Cow::Borrowed(target));
+    }
+
+    let mut rendered = String::with_capacity(target.len());
+    let mut rest = target;
+
+    while let Some(open) = rest.find('{') {
+        let Some(len) = rest[open..].find('}') else { break };
+        let placeholder = &rest[open + 1..open + len];
+
+        if !is_target_placeholder(placeholder) {
+            rendered.push_str(&rest[..=open]);
+            rest = &rest[open + 1..];
+            continue;
+        }
+
+        rendered.push_str(&rest[..open]);
+        rest = &rest[open + len + 1..];
+
+        let pointer = if placeholder.starts_with('/') {
+            placeholder.to_string()
+        } else {
+            placeholder
+                .split('.')
+                .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+                .collect()
+        };
+
+        let value = payload
+            .and_then(|p| p.pointer(&pointer))
+            .ok_or_else(|| format!("Missing value for target placeholder {{{}}}", placeholder))?;
+
+        let text = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            _ => return Err(format!("Target placeholder {{{}}} must resolve to a scalar value", placeholder)),
+        };
+        rendered.push_str(&urlencoding::encode(&text));
+    }
+
+    rendered.push_str(rest);
+    Ok(Cow::Owned(rendered))
+}
+
+/// Whether the text between a pair of braces is a payload path
+fn is_target_placeholder(text: &str) -> bool {
+    !text.is_empty()
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '~'))
+}
+
+/// Circuit breaker key for a target URL: its host, with the port when one is given.
+/// Unparseable targets are keyed by the full target string.
+fn endpoint_key(target: &str) -> String {
+    match reqwest::Url::parse(target) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => target.to_string(),
+        },
+        Err(_) => target.to_string(),
+    }
+}
+
+/// A held per-endpoint concurrency permit. Releasing the endpoint's last permit
+/// removes its semaphore from the map, so endpoints that go quiet are not tracked forever.
+struct EndpointPermit<'a> {
+    permit: Option<OwnedSemaphorePermit>,
+    endpoint: String,
+    permits: &'a DashMap<String, Arc<Semaphore>>,
+}
+
+impl Drop for EndpointPermit<'_> {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+        // Acquirers clone the semaphore under the entry's shard lock, so a count of one
+        // here means no other delivery holds or is about to take a permit
+        self.permits.remove_if(&self.endpoint, |_, permits| Arc::strong_count(permits) == 1);
+    }
+}
+
 /// Trait for message mediation
 #[async_trait]
 pub trait Mediator: Send + Sync {
     async fn mediate(&self, message: &Message) -> MediationOutcome;
 }
 
+/// Span for work on a single message, carrying its IDs so every log line inside
+/// it can be traced back to the message and its correlation ID.
+///
+/// When the message carries a `traceparent`, the exported span is parented to
+/// the publisher's span instead, so publish, route and mediation share a trace.
+pub fn message_span(message: &Message) -> Span {
+    let span = info_span!(
+        "message",
+        message_id = %message.id,
+        pool_code = %message.pool_code,
+        correlation_id = tracing::field::Empty,
+    );
+    if let Some(ref correlation_id) = message.correlation_id {
+        span.record("correlation_id", correlation_id.as_str());
+    }
+    if let Some(ref traceparent) = message.traceparent {
+        fc_common::logging::set_remote_parent(&span, traceparent);
+    }
+    span
+}
+
 /// Payload sent to mediation target (matches Java format)
 /// Java sends: {"messageId":"<id>"}
 #[derive(Debug, Serialize)]
@@ -213,6 +406,50 @@ pub enum HttpVersion {
     Http2,
 }
 
+/// Body format of outbound mediation requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PayloadFormat {
+    /// The message body as-is: the `{"messageId":"<id>"}` envelope or the raw body
+    #[default]
+    Raw,
+    /// The body wrapped in a CloudEvents 1.0 structured-mode JSON event
+    CloudEvents,
+}
+
+impl std::str::FromStr for PayloadFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "raw" => Ok(PayloadFormat::Raw),
+            "cloudevents" => Ok(PayloadFormat::CloudEvents),
+            other => Err(format!("Unknown payload format: {} (expected raw or cloudevents)", other)),
+        }
+    }
+}
+
+/// How `Message::auth_token` is sent in the `Authorization` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <token>`
+    #[default]
+    Bearer,
+    /// `Authorization: <token>`, for targets expecting their own scheme prefix in the token
+    Raw,
+}
+
+impl std::str::FromStr for AuthScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bearer" => Ok(AuthScheme::Bearer),
+            "raw" => Ok(AuthScheme::Raw),
+            other => Err(format!("Unknown auth scheme: {} (expected bearer or raw)", other)),
+        }
+    }
+}
+
 /// Configuration for HTTP mediator
 #[derive(Debug, Clone)]
 pub struct HttpMediatorConfig {
@@ -228,6 +465,18 @@ pub struct HttpMediatorConfig {
     pub circuit_breaker_timeout: Duration,
     /// Connection timeout
     pub connect_timeout: Duration,
+    /// Outbound proxy for mediation requests
+    pub proxy: ProxyConfig,
+    /// Max in-flight mediations per endpoint host (`None` = unlimited).
+    /// Messages over the limit are NACKed with a short delay rather than
+    /// holding a pool worker while they wait.
+    pub max_concurrent_per_endpoint: Option<u32>,
+    /// How `auth_token` is presented to the target
+    pub auth_scheme: AuthScheme,
+    /// Whether the body is sent as-is or wrapped in a CloudEvent
+    pub payload_format: PayloadFormat,
+    /// CloudEvents `source` attribute when `payload_format` is `CloudEvents`
+    pub cloudevents_source: String,
 }
 
 impl Default for HttpMediatorConfig {
@@ -244,6 +493,11 @@ impl Default for HttpMediatorConfig {
             circuit_breaker_threshold: 10,
             circuit_breaker_timeout: Duration::from_secs(5),
             connect_timeout: Duration::from_secs(30),
+            proxy: ProxyConfig::default(),
+            max_concurrent_per_endpoint: None,
+            auth_scheme: AuthScheme::Bearer,
+            payload_format: PayloadFormat::Raw,
+            cloudevents_source: "flowcatalyst".to_string(),
         }
     }
 }
@@ -263,6 +517,11 @@ impl HttpMediatorConfig {
             circuit_breaker_threshold: 10,
             circuit_breaker_timeout: Duration::from_secs(5),
             connect_timeout: Duration::from_secs(10),
+            proxy: ProxyConfig::default(),
+            max_concurrent_per_endpoint: None,
+            auth_scheme: AuthScheme::Bearer,
+            payload_format: PayloadFormat::Raw,
+            cloudevents_source: "flowcatalyst".to_string(),
         }
     }
 
@@ -270,6 +529,26 @@ impl HttpMediatorConfig {
     pub fn production() -> Self {
         Self::default()
     }
+
+    /// Shared HTTP client settings matching this mediator configuration
+    pub fn http_client_config(&self) -> HttpClientConfig {
+        HttpClientConfig {
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            http1_only: self.http_version == HttpVersion::Http1,
+            proxy: self.proxy.clone(),
+            ..HttpClientConfig::default()
+        }
+    }
+
+    /// Per-endpoint circuit breaker settings matching this mediator configuration
+    pub fn circuit_breaker_config(&self) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: self.circuit_breaker_threshold,
+            reset_timeout: self.circuit_breaker_timeout,
+            ..CircuitBreakerConfig::default()
+        }
+    }
 }
 
 /// HTTP-based message mediator with circuit breaker
@@ -277,7 +556,14 @@ pub struct HttpMediator {
     client: Client,
     config: HttpMediatorConfig,
     circuit_breaker: CircuitBreaker,
+    /// Per-endpoint circuit breakers; replaces the shared breaker when set
+    circuit_breakers: Option<Arc<CircuitBreakerRegistry>>,
+    /// Concurrency permits per endpoint host, when `max_concurrent_per_endpoint` is set.
+    /// An endpoint's entry is removed once its last delivery finishes.
+    endpoint_permits: DashMap<String, Arc<Semaphore>>,
     warning_service: Option<Arc<WarningService>>,
+    /// Resolves `auth_token` values given as secret references (`aws-sm://`, `vault://`, ...)
+    secret_service: Option<Arc<SecretService>>,
 }
 
 impl HttpMediator {
@@ -296,16 +582,10 @@ impl HttpMediator {
     }
 
     pub fn with_config(config: HttpMediatorConfig) -> Self {
-        let mut builder = Client::builder()
-            .timeout(config.timeout)
-            .connect_timeout(config.connect_timeout)
-            .pool_max_idle_per_host(10);
-
         // Configure HTTP version
         match config.http_version {
             HttpVersion::Http1 => {
                 // Force HTTP/1.1 only
-                builder = builder.http1_only();
                 info!("HttpMediator configured for HTTP/1.1");
             }
             HttpVersion::Http2 => {
@@ -316,8 +596,16 @@ impl HttpMediator {
             }
         }
 
-        let client = builder.build().expect("Failed to build HTTP client");
+        let client = config.http_client_config()
+            .build_client()
+            .expect("Failed to build HTTP client");
 
+        Self::with_client(config, client)
+    }
+
+    /// Create mediator using an existing (shared) HTTP client.
+    /// The client's own timeout and HTTP version settings apply instead of `config`'s.
+    pub fn with_client(config: HttpMediatorConfig, client: Client) -> Self {
         let circuit_breaker = CircuitBreaker::new(
             config.circuit_breaker_threshold,
             5,
@@ -330,7 +618,23 @@ impl HttpMediator {
             "HttpMediator initialized"
         );
 
-        Self { client, config, circuit_breaker, warning_service: None }
+        Self {
+            client,
+            config,
+            circuit_breaker,
+            circuit_breakers: None,
+            endpoint_permits: DashMap::new(),
+            warning_service: None,
+            secret_service: None,
+        }
+    }
+
+    /// Track circuit breakers per endpoint host in `registry` instead of sharing one
+    /// breaker across all targets. Outcomes, rejections and state transitions are
+    /// recorded there, so the registry is what the monitoring API reports.
+    pub fn with_circuit_breaker_registry(mut self, registry: Arc<CircuitBreakerRegistry>) -> Self {
+        self.circuit_breakers = Some(registry);
+        self
     }
 
     /// Set the warning service for generating configuration warnings
@@ -339,6 +643,14 @@ impl HttpMediator {
         self
     }
 
+    /// Resolve `auth_token` values that are secret references through `secret_service`,
+    /// so messages can carry e.g. `vault://webhooks/acme#token` instead of the token itself.
+    /// Tokens that are not references are sent as-is.
+    pub fn with_secret_service(mut self, secret_service: Arc<SecretService>) -> Self {
+        self.secret_service = Some(secret_service);
+        self
+    }
+
     /// Set warning service after construction
     pub fn set_warning_service(&mut self, warning_service: Arc<WarningService>) {
         self.warning_service = Some(warning_service);
@@ -361,23 +673,91 @@ impl HttpMediator {
         }
     }
 
-    /// Get circuit breaker state
+    /// Get the shared circuit breaker state (unused once a registry is attached)
     pub fn circuit_state(&self) -> CircuitState {
         self.circuit_breaker.state()
     }
 
-    async fn mediate_once(&self, message: &Message) -> MediationOutcome {
-        if message.mediation_type != MediationType::HTTP {
-            return MediationOutcome::error_config(
-                0,
-                format!("Unsupported mediation type: {:?}", message.mediation_type),
-            );
+    /// Number of endpoints currently tracked by the per-endpoint concurrency limit
+    pub fn tracked_endpoint_count(&self) -> usize {
+        self.endpoint_permits.len()
+    }
+
+    fn allow_request(&self, endpoint: &str) -> bool {
+        match self.circuit_breakers {
+            Some(ref registry) => registry.allow_request(endpoint),
+            None => self.circuit_breaker.allow_request(),
+        }
+    }
+
+    fn record_success(&self, endpoint: &str) {
+        match self.circuit_breakers {
+            Some(ref registry) => registry.record_success(endpoint),
+            None => self.circuit_breaker.record_success(),
+        }
+    }
+
+    fn record_failure(&self, endpoint: &str) {
+        match self.circuit_breakers {
+            Some(ref registry) => registry.record_failure(endpoint),
+            None => self.circuit_breaker.record_failure(),
         }
+    }
+
+    /// Resolve `token` through the secret service when it is a secret reference.
+    /// Errors name at most the secret reference, never a token value.
+    async fn resolve_auth_token<'a>(&self, token: &'a str) -> Result<Cow<'a, str>, SecretsError> {
+        match &self.secret_service {
+            Some(secrets) if secrets.get_provider_type(token).is_some() => {
+                secrets.resolve(token).await.map(Cow::Owned)
+            }
+            _ => Ok(Cow::Borrowed(token)),
+        }
+    }
+
+    /// Take a concurrency permit for the message's endpoint when a per-endpoint limit is set.
+    /// A saturated endpoint hands the message back instead of waiting, so the worker is
+    /// free to serve other targets.
+    fn try_acquire_endpoint_permit(&self, message: &Message, target: &str) -> Result<Option<EndpointPermit<'_>>, MediationOutcome> {
+        let Some(limit) = self.config.max_concurrent_per_endpoint else {
+            return Ok(None);
+        };
+
+        let endpoint = endpoint_key(target);
+        let permits = self.endpoint_permits
+            .entry(endpoint.clone())
+            .or_insert_with(|| Arc::new(Semaphore::new(limit as usize)))
+            .clone();
 
+        let permit = permits.try_acquire_owned().map_err(|_| {
+            debug!(
+                message_id = %message.id,
+                endpoint = %endpoint,
+                limit = limit,
+                "Endpoint concurrency limit reached, deferring message"
+            );
+            MediationOutcome::deferred(
+                ENDPOINT_SATURATED_DELAY_SECONDS,
+                format!("Endpoint concurrency limit reached for {}", endpoint),
+            )
+        });
+
+        Ok(Some(EndpointPermit {
+            permit: Some(permit?),
+            endpoint,
+            permits: &self.endpoint_permits,
+        }))
+    }
+
+    /// Deliver one attempt to `target`, the message's mediation target rendered against its payload
+    async fn mediate_once(&self, message: &Message, target: &str) -> MediationOutcome {
         // Check circuit breaker
-        if !self.circuit_breaker.allow_request() {
+        let endpoint = endpoint_key(target);
+        Span::current().record("endpoint", endpoint.as_str());
+        if !self.allow_request(&endpoint) {
             debug!(
                 message_id = %message.id,
+                endpoint = %endpoint,
                 "Circuit breaker open, rejecting request"
             );
             return MediationOutcome {
@@ -388,50 +768,112 @@ impl HttpMediator {
             };
         }
 
-        // Build payload matching Java format: {"messageId":"<id>"}
-        let payload = MediationPayload {
-            message_id: &message.id,
+        // Raw bodies are sent verbatim; otherwise build payload matching Java format: {"messageId":"<id>"}
+        // Bad bodies and content types are config errors, not failures of the endpoint
+        let (body, content_type) = match &message.raw_body_base64 {
+            Some(raw) => match BASE64.decode(raw) {
+                Ok(bytes) => {
+                    let content_type = message.content_type.as_deref().unwrap_or("application/octet-stream");
+                    if reqwest::header::HeaderValue::from_str(content_type).is_err() {
+                        warn!(message_id = %message.id, content_type = %content_type, "Invalid content type");
+                        return MediationOutcome::error_config(0, "content_type is not a valid header value".to_string());
+                    }
+                    (bytes, content_type)
+                }
+                Err(e) => {
+                    warn!(message_id = %message.id, error = %e, "Invalid base64 raw body");
+                    return MediationOutcome::error_config(0, format!("Invalid raw_body_base64: {}", e));
+                }
+            },
+            None => {
+                let payload = MediationPayload {
+                    message_id: &message.id,
+                };
+                let json = serde_json::to_vec(&payload)
+                    .expect("Failed to serialize payload");
+                (json, "application/json")
+            }
+        };
+
+        // Wrap before signing so signatures cover the body actually sent
+        let (body, content_type) = match self.config.payload_format {
+            PayloadFormat::Raw => (body, content_type),
+            PayloadFormat::CloudEvents => (
+                to_cloudevent(message, &body, content_type, &self.config.cloudevents_source),
+                CLOUDEVENTS_CONTENT_TYPE,
+            ),
         };
 
         debug!(
             message_id = %message.id,
-            target = %message.mediation_target,
+            target = %target,
+            content_type = %content_type,
             has_auth_token = message.auth_token.is_some(),
-            auth_token_preview = message.auth_token.as_ref().map(|t| if t.len() > 20 { format!("{}...", &t[..20]) } else { t.clone() }),
             "Mediating message"
         );
 
-        // Serialize payload for signing
-        let payload_json = serde_json::to_string(&payload)
-            .expect("Failed to serialize payload");
-
         let mut request = self.client
-            .post(&message.mediation_target)
-            .header("Content-Type", "application/json")
+            .post(target)
+            .header("Content-Type", content_type)
             .header("Accept", "application/json");
 
+        if let Some(ref correlation_id) = message.correlation_id {
+            request = request.header(CORRELATION_ID_HEADER, correlation_id);
+        }
+
         // Add webhook signing headers if signing_secret is present
         if let Some(ref signing_secret) = message.signing_secret {
-            let (signature, timestamp) = sign_webhook(&payload_json, signing_secret);
+            let (signature, timestamp) = sign_webhook(&body, signing_secret);
+            let fc_timestamp = Utc::now().timestamp();
             request = request
                 .header(SIGNATURE_HEADER, signature)
-                .header(TIMESTAMP_HEADER, timestamp);
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(FC_SIGNATURE_HEADER, sign_fc_webhook(&body, signing_secret, fc_timestamp))
+                .header(FC_TIMESTAMP_HEADER, fc_timestamp.to_string());
         }
 
         if let Some(token) = &message.auth_token {
-            request = request.bearer_auth(token);
+            let token = match self.resolve_auth_token(token).await {
+                Ok(token) => token,
+                Err(e) => {
+                    warn!(message_id = %message.id, error = %e, "Failed to resolve auth token reference");
+                    return MediationOutcome::error_process(
+                        Some(AUTH_TOKEN_RESOLVE_DELAY_SECONDS),
+                        format!("Failed to resolve auth token: {}", e),
+                    );
+                }
+            };
+            request = match self.config.auth_scheme {
+                AuthScheme::Bearer => request.bearer_auth(token),
+                AuthScheme::Raw => match reqwest::header::HeaderValue::from_str(&token) {
+                    Ok(mut value) => {
+                        value.set_sensitive(true);
+                        request.header(reqwest::header::AUTHORIZATION, value)
+                    }
+                    Err(_) => {
+                        return MediationOutcome::error_config(0, "Auth token is not a valid header value".to_string());
+                    }
+                },
+            };
+        }
+
+        // A zero timeout would fail every attempt, so it falls back to the configured one
+        if let Some(timeout_seconds) = message.timeout_seconds.filter(|&t| t > 0) {
+            let timeout_seconds = timeout_seconds.min(MAX_MESSAGE_TIMEOUT_SECONDS);
+            request = request.timeout(Duration::from_secs(timeout_seconds as u64));
         }
 
         // Add the body after all headers are set
-        request = request.body(payload_json);
+        request = request.body(body);
 
         match request.send().await {
             Ok(response) => {
                 let status = response.status();
                 let status_code = status.as_u16();
+                Span::current().record("status_code", status_code);
 
                 if status.is_success() {
-                    self.circuit_breaker.record_success();
+                    self.record_success(&endpoint);
 
                     // Parse response body for ack and delaySeconds
                     if let Ok(body) = response.text().await {
@@ -462,39 +904,39 @@ impl HttpMediator {
                     MediationOutcome::success()
                 } else if status_code == 400 {
                     // Bad request - configuration error
-                    self.circuit_breaker.record_success(); // Don't count as failure
+                    self.record_success(&endpoint); // Don't count as failure
                     warn!(
                         message_id = %message.id,
                         status_code = status_code,
                         "Bad request - configuration error"
                     );
-                    self.warn_config(&message.id, &message.mediation_target, status_code, "Bad Request");
+                    self.warn_config(&message.id, target, status_code, "Bad Request");
                     MediationOutcome::error_config(status_code, "HTTP 400: Bad request".to_string())
                 } else if status_code == 401 || status_code == 403 {
                     // Auth errors - configuration error
-                    self.circuit_breaker.record_success();
+                    self.record_success(&endpoint);
                     let desc = if status_code == 401 { "Unauthorized" } else { "Forbidden" };
                     warn!(
                         message_id = %message.id,
                         status_code = status_code,
                         "Authentication/authorization error"
                     );
-                    self.warn_config(&message.id, &message.mediation_target, status_code, desc);
+                    self.warn_config(&message.id, target, status_code, desc);
                     MediationOutcome::error_config(status_code, format!("HTTP {}: Auth error", status_code))
                 } else if status_code == 404 {
                     // Not found - configuration error
-                    self.circuit_breaker.record_success();
+                    self.record_success(&endpoint);
                     warn!(
                         message_id = %message.id,
                         status_code = status_code,
                         "Endpoint not found"
                     );
-                    self.warn_config(&message.id, &message.mediation_target, status_code, "Not Found");
+                    self.warn_config(&message.id, target, status_code, "Not Found");
                     MediationOutcome::error_config(status_code, "HTTP 404: Not found".to_string())
                 } else if status_code == 429 {
                     // Too Many Requests - TRANSIENT error, respect Retry-After
                     // Don't count as circuit breaker failure (it's rate limiting, not a real error)
-                    self.circuit_breaker.record_success();
+                    self.record_success(&endpoint);
 
                     // Parse Retry-After header if present, default to 30 seconds
                     let retry_after = response.headers()
@@ -517,17 +959,17 @@ impl HttpMediator {
                     }
                 } else if status_code == 501 {
                     // Not implemented - configuration error (CRITICAL)
-                    self.circuit_breaker.record_success();
+                    self.record_success(&endpoint);
                     warn!(
                         message_id = %message.id,
                         status_code = status_code,
                         "Not implemented"
                     );
-                    self.warn_config(&message.id, &message.mediation_target, status_code, "Not Implemented");
+                    self.warn_config(&message.id, target, status_code, "Not Implemented");
                     MediationOutcome::error_config(status_code, "HTTP 501: Not implemented".to_string())
                 } else if status.is_client_error() {
                     // Other 4xx - treat as config error (but NOT 429 which is handled above)
-                    self.circuit_breaker.record_success();
+                    self.record_success(&endpoint);
                     warn!(
                         message_id = %message.id,
                         status_code = status_code,
@@ -536,7 +978,7 @@ impl HttpMediator {
                     MediationOutcome::error_config(status_code, format!("HTTP {}: Client error", status_code))
                 } else if status.is_server_error() {
                     // 5xx - Transient error, retry
-                    self.circuit_breaker.record_failure();
+                    self.record_failure(&endpoint);
                     warn!(
                         message_id = %message.id,
                         status_code = status_code,
@@ -562,7 +1004,7 @@ impl HttpMediator {
                 }
             }
             Err(e) => {
-                self.circuit_breaker.record_failure();
+                self.record_failure(&endpoint);
 
                 if e.is_timeout() {
                     warn!(
@@ -581,7 +1023,7 @@ impl HttpMediator {
                 } else {
                     error!(
                         message_id = %message.id,
-                        target = %message.mediation_target,
+                        target = %target,
                         error = %e,
                         error_debug = ?e,
                         is_request = e.is_request(),
@@ -596,25 +1038,64 @@ impl HttpMediator {
             }
         }
     }
-}
 
-#[async_trait]
-impl Mediator for HttpMediator {
-    async fn mediate(&self, message: &Message) -> MediationOutcome {
+    /// Deliver with in-process retries, returning the outcome and the number of attempts made
+    async fn mediate_with_retries(&self, message: &Message) -> (MediationOutcome, u32) {
+        if message.mediation_type != MediationType::HTTP {
+            let outcome = MediationOutcome::error_config(
+                0,
+                format!(
+                    "Unsupported mediation type: {:?} (HttpMediator only delivers HTTP messages)",
+                    message.mediation_type
+                ),
+            );
+            return (outcome, 0);
+        }
+
+        // Rendered once, so the permit and every attempt use the same endpoint
+        let target = match render_target(&message.mediation_target, message.payload.as_ref()) {
+            Ok(target) => target,
+            Err(e) => {
+                warn!(
+                    message_id = %message.id,
+                    target = %message.mediation_target,
+                    error = %e,
+                    "Failed to resolve mediation target"
+                );
+                return (MediationOutcome::error_config(0, e), 0);
+            }
+        };
+
+        // Held across retries, so the limit counts messages rather than attempts
+        let _permit = match self.try_acquire_endpoint_permit(message, &target) {
+            Ok(permit) => permit,
+            Err(outcome) => return (outcome, 0),
+        };
+
+        let max_retries = message.max_retries
+            .map(|retries| retries.min(MAX_MESSAGE_RETRIES))
+            .unwrap_or(self.config.max_retries);
         let mut attempts = 0;
 
         loop {
-            let outcome = self.mediate_once(message).await;
+            attempts += 1;
+            let attempt_span = info_span!(
+                "mediation_attempt",
+                otel.kind = "client",
+                attempt = attempts,
+                endpoint = tracing::field::Empty,
+                status_code = tracing::field::Empty,
+            );
+            let outcome = self.mediate_once(message, &target).instrument(attempt_span).await;
 
             // Don't retry on success or config errors
             if outcome.result == MediationResult::Success ||
                outcome.result == MediationResult::ErrorConfig {
-                return outcome;
+                return (outcome, attempts);
             }
 
-            attempts += 1;
-            if attempts >= self.config.max_retries {
-                return outcome;
+            if attempts >= max_retries {
+                return (outcome, attempts);
             }
 
             // Use configured delay or exponential backoff
@@ -634,6 +1115,22 @@ impl Mediator for HttpMediator {
     }
 }
 
+#[async_trait]
+impl Mediator for HttpMediator {
+    async fn mediate(&self, message: &Message) -> MediationOutcome {
+        let span = info_span!(
+            "mediate",
+            mediation_type = ?message.mediation_type,
+            attempts = tracing::field::Empty,
+            result = tracing::field::Empty,
+        );
+        let (outcome, attempts) = self.mediate_with_retries(message).instrument(span.clone()).await;
+        span.record("attempts", attempts);
+        span.record("result", router_metrics::mediation_result_label(outcome.result));
+        outcome
+    }
+}
+
 impl Default for HttpMediator {
     fn default() -> Self {
         Self::new()
@@ -672,4 +1169,90 @@ mod tests {
         cb.record_success();
         assert_eq!(cb.failure_count(), 0);
     }
+
+    #[test]
+    fn test_render_target_without_placeholders() {
+        let rendered = render_target("http://localhost/webhook", None).unwrap();
+        assert!(matches!(rendered, Cow::Borrowed(_)));
+        assert_eq!(rendered, "http://localhost/webhook");
+    }
+
+    #[test]
+    fn test_render_target_substitutes_and_encodes() {
+        let payload = serde_json::json!({
+            "order": {"id": "a/b c", "count": 3},
+            "a/b": true
+        });
+
+        assert_eq!(
+            render_target("http://h/orders/{order.id}?n={order.count}", Some(&payload)).unwrap(),
+            "http://h/orders/a%2Fb%20c?n=3"
+        );
+        assert_eq!(render_target("http://h/{/a~1b}", Some(&payload)).unwrap(), "http://h/true");
+    }
+
+    #[test]
+    fn test_render_target_errors() {
+        let payload = serde_json::json!({"order": {"id": "1"}});
+
+        assert!(render_target("http://h/{missing}", Some(&payload)).is_err());
+        assert!(render_target("http://h/{order}", Some(&payload)).is_err());
+        assert!(render_target("http://h/{order.id}", None).is_err());
+    }
+
+    #[test]
+    fn test_to_cloudevent_embeds_json_and_encodes_other_bodies() {
+        let mut message = Message {
+            id: "msg-1".to_string(),
+            pool_code: "DEFAULT".to_string(),
+            mediation_type: MediationType::HTTP,
+            mediation_target: "http://h/webhook".to_string(),
+            message_group_id: Some("order-42".to_string()),
+            ..Default::default()
+        };
+
+        let event: serde_json::Value = serde_json::from_slice(&to_cloudevent(
+            &message,
+            br#"{"messageId":"msg-1"}"#,
+            "application/json",
+            "flowcatalyst",
+        )).unwrap();
+        assert_eq!(event["specversion"], "1.0");
+        assert_eq!(event["id"], "msg-1");
+        assert_eq!(event["source"], "flowcatalyst");
+        assert_eq!(event["type"], CLOUDEVENTS_MESSAGE_TYPE);
+        assert_eq!(event["datacontenttype"], "application/json");
+        assert_eq!(event["messagegroup"], "order-42");
+        assert_eq!(event["data"], serde_json::json!({"messageId": "msg-1"}));
+        assert!(chrono::DateTime::parse_from_rfc3339(event["time"].as_str().unwrap()).is_ok());
+
+        message.message_group_id = None;
+        let event: serde_json::Value = serde_json::from_slice(&to_cloudevent(
+            &message,
+            &[0x00, 0xFF],
+            "application/octet-stream",
+            "flowcatalyst",
+        )).unwrap();
+        assert_eq!(event["data_base64"], "AP8=");
+        assert!(event.get("data").is_none());
+        assert!(event.get("messagegroup").is_none());
+    }
+
+    #[test]
+    fn test_sign_fc_webhook_known_vector() {
+        let signature = sign_fc_webhook(br#"{"messageId":"msg-1"}"#, "whsec_test", 1_700_000_000);
+        assert_eq!(
+            signature,
+            "sha256=652e3e92068ecc68e2fc13cef2fab9cebe0ce510f6e5bfddbb6e9ce03b0c254a"
+        );
+    }
+
+    #[test]
+    fn test_sign_fc_webhook_covers_timestamp() {
+        let body = br#"{"messageId":"msg-1"}"#;
+        assert_ne!(
+            sign_fc_webhook(body, "whsec_test", 1_700_000_000),
+            sign_fc_webhook(body, "whsec_test", 1_700_000_001)
+        );
+    }
 }
@@ -3,6 +3,7 @@
 //! Provides sliding window metrics for processing pools with:
 //! - Success/failure counters
 //! - Processing time tracking with percentiles
+//! - End-to-end latency percentiles (queue receipt to completion)
 //! - 5-minute and 30-minute time windows
 
 use std::collections::VecDeque;
@@ -10,6 +11,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use parking_lot::RwLock;
 use chrono::Utc;
+use serde::Serialize;
+use utoipa::ToSchema;
 
 use fc_common::{
     EnhancedPoolMetrics, ProcessingTimeMetrics, WindowedMetrics,
@@ -26,6 +29,22 @@ struct MetricSample {
     success: bool,
 }
 
+/// End-to-end latency percentiles for a pool, measured from the moment a
+/// message entered the pipeline until the pool reported its outcome
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyPercentiles {
+    pub avg_ms: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+    /// Completed messages in the window
+    pub sample_count: u64,
+    /// Length of the sliding window the percentiles cover
+    pub window_duration_secs: u64,
+}
+
 /// Configuration for the metrics collector
 #[derive(Debug, Clone)]
 pub struct MetricsConfig {
@@ -64,6 +83,9 @@ pub struct PoolMetricsCollector {
 
     /// Rate-limited event timestamps for windowed counting
     rate_limited_events: RwLock<VecDeque<Instant>>,
+
+    /// End-to-end latencies of completed messages: (completed at, duration ms)
+    latency_samples: RwLock<VecDeque<(Instant, u64)>>,
 }
 
 impl PoolMetricsCollector {
@@ -79,6 +101,7 @@ impl PoolMetricsCollector {
             total_rate_limited: AtomicU64::new(0),
             samples: RwLock::new(VecDeque::with_capacity(10000)),
             rate_limited_events: RwLock::new(VecDeque::with_capacity(1000)),
+            latency_samples: RwLock::new(VecDeque::with_capacity(10000)),
         }
     }
 
@@ -110,6 +133,44 @@ impl PoolMetricsCollector {
         events.push_back(now);
     }
 
+    /// Record the end-to-end latency of a completed message
+    pub fn record_latency(&self, duration_ms: u64) {
+        let now = Instant::now();
+        let mut samples = self.latency_samples.write();
+
+        let cutoff = now - self.config.long_window;
+        while samples.front().map(|(t, _)| *t < cutoff).unwrap_or(false) {
+            samples.pop_front();
+        }
+
+        samples.push_back((now, duration_ms));
+        while samples.len() > self.config.max_samples {
+            samples.pop_front();
+        }
+    }
+
+    /// Latency percentiles over the long window, or `None` if no message
+    /// completed within it
+    pub fn latency_percentiles(&self) -> Option<LatencyPercentiles> {
+        self.prune_expired(Instant::now());
+
+        let durations: Vec<u64> = self.latency_samples.read().iter().map(|(_, d)| *d).collect();
+        if durations.is_empty() {
+            return None;
+        }
+
+        let metrics = Self::calculate_processing_time_metrics(&durations);
+        Some(LatencyPercentiles {
+            avg_ms: metrics.avg_ms,
+            p50_ms: metrics.p50_ms,
+            p95_ms: metrics.p95_ms,
+            p99_ms: metrics.p99_ms,
+            max_ms: metrics.max_ms,
+            sample_count: metrics.sample_count,
+            window_duration_secs: self.config.long_window.as_secs(),
+        })
+    }
+
     /// Get all-time rate limited count
     pub fn total_rate_limited(&self) -> u64 {
         self.total_rate_limited.load(Ordering::Relaxed)
@@ -150,11 +211,36 @@ impl PoolMetricsCollector {
         self.total_failure.load(Ordering::Relaxed)
     }
 
+    /// Drop samples and rate-limited events older than the long window.
+    /// Called on every read so memory stays bounded even when a pool goes idle.
+    fn prune_expired(&self, now: Instant) {
+        let cutoff = now - self.config.long_window;
+
+        let mut samples = self.samples.write();
+        while samples.front().map(|s| s.timestamp < cutoff).unwrap_or(false) {
+            samples.pop_front();
+        }
+        drop(samples);
+
+        let mut events = self.rate_limited_events.write();
+        while events.front().map(|t| *t < cutoff).unwrap_or(false) {
+            events.pop_front();
+        }
+        drop(events);
+
+        let mut latencies = self.latency_samples.write();
+        while latencies.front().map(|(t, _)| *t < cutoff).unwrap_or(false) {
+            latencies.pop_front();
+        }
+    }
+
     /// Get enhanced metrics snapshot
     pub fn get_metrics(&self) -> EnhancedPoolMetrics {
+        let now = Instant::now();
+        self.prune_expired(now);
+
         let samples = self.samples.read();
         let rate_limited_events = self.rate_limited_events.read();
-        let now = Instant::now();
 
         let total_success = self.total_success.load(Ordering::Relaxed);
         let total_failure = self.total_failure.load(Ordering::Relaxed);
@@ -310,6 +396,7 @@ impl PoolMetricsCollector {
         self.total_rate_limited.store(0, Ordering::Relaxed);
         self.samples.write().clear();
         self.rate_limited_events.write().clear();
+        self.latency_samples.write().clear();
     }
 }
 
@@ -408,4 +495,85 @@ mod tests {
         assert_eq!(metrics.last_5_min.success_count + metrics.last_5_min.failure_count, 10);
         assert!(metrics.last_5_min.throughput_per_sec > 0.0);
     }
+
+    #[test]
+    fn test_windowed_success_rates() {
+        let collector = PoolMetricsCollector::with_config(MetricsConfig {
+            short_window: Duration::from_millis(50),
+            long_window: Duration::from_secs(60),
+            ..Default::default()
+        });
+
+        collector.record_failure(100);
+        collector.record_failure(100);
+        std::thread::sleep(Duration::from_millis(80));
+        collector.record_success(100);
+        collector.record_success(100);
+
+        let metrics = collector.get_metrics();
+
+        // Failures have aged out of the short window but not the long one
+        assert_eq!(metrics.last_5_min.success_count, 2);
+        assert_eq!(metrics.last_5_min.failure_count, 0);
+        assert_eq!(metrics.last_5_min.success_rate, 1.0);
+        assert_eq!(metrics.last_30_min.success_count, 2);
+        assert_eq!(metrics.last_30_min.failure_count, 2);
+        assert_eq!(metrics.last_30_min.success_rate, 0.5);
+    }
+
+    #[test]
+    fn test_expired_entries_pruned_on_read() {
+        let collector = PoolMetricsCollector::with_config(MetricsConfig {
+            short_window: Duration::from_millis(20),
+            long_window: Duration::from_millis(50),
+            ..Default::default()
+        });
+
+        collector.record_success(100);
+        collector.record_failure(100);
+        collector.record_rate_limited();
+        std::thread::sleep(Duration::from_millis(80));
+
+        let metrics = collector.get_metrics();
+
+        assert_eq!(metrics.last_30_min.success_count + metrics.last_30_min.failure_count, 0);
+        assert_eq!(metrics.last_30_min.rate_limited_count, 0);
+        assert!(collector.samples.read().is_empty());
+        assert!(collector.rate_limited_events.read().is_empty());
+        // All-time counters are unaffected by pruning
+        assert_eq!(metrics.total_success, 1);
+        assert_eq!(metrics.total_failure, 1);
+        assert_eq!(metrics.total_rate_limited, 1);
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let collector = PoolMetricsCollector::new();
+        assert!(collector.latency_percentiles().is_none());
+
+        for ms in 1..=100 {
+            collector.record_latency(ms);
+        }
+
+        let latency = collector.latency_percentiles().unwrap();
+        assert_eq!(latency.sample_count, 100);
+        assert_eq!(latency.p50_ms, 51);
+        assert_eq!(latency.p95_ms, 95);
+        assert_eq!(latency.p99_ms, 99);
+        assert_eq!(latency.max_ms, 100);
+        assert!((latency.avg_ms - 50.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_latency_samples_expire() {
+        let collector = PoolMetricsCollector::with_config(MetricsConfig {
+            long_window: Duration::from_millis(50),
+            ..Default::default()
+        });
+
+        collector.record_latency(100);
+        std::thread::sleep(Duration::from_millis(80));
+
+        assert!(collector.latency_percentiles().is_none());
+    }
 }
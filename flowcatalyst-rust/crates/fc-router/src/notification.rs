@@ -3,6 +3,7 @@
 //! Provides:
 //! - Microsoft Teams webhook notifications via Adaptive Cards
 //! - Batching support for warning notifications
+//! - Flushing of pending batches on shutdown
 //! - Severity filtering
 
 use std::collections::HashMap;
@@ -29,6 +30,9 @@ pub trait NotificationService: Send + Sync {
 
     /// Check if notifications are enabled
     fn is_enabled(&self) -> bool;
+
+    /// Deliver any buffered notifications (called during shutdown)
+    async fn flush(&self) {}
 }
 
 /// Notification configuration
@@ -531,6 +535,18 @@ impl NotificationService for BatchingNotificationService {
     fn is_enabled(&self) -> bool {
         self.delegates.iter().any(|d| d.is_enabled())
     }
+
+    async fn flush(&self) {
+        let pending = self.pending_count();
+        if pending > 0 {
+            info!(pending = pending, "Flushing pending warning notifications");
+        }
+        self.send_batch().await;
+
+        for delegate in &self.delegates {
+            delegate.flush().await;
+        }
+    }
 }
 
 /// Create notification service based on configuration
@@ -689,4 +705,53 @@ mod tests {
         let service = create_notification_service(&config);
         assert!(service.is_enabled());
     }
+
+    #[derive(Default)]
+    struct RecordingNotificationService {
+        warnings: Mutex<Vec<Warning>>,
+    }
+
+    #[async_trait]
+    impl NotificationService for RecordingNotificationService {
+        async fn notify_warning(&self, warning: &Warning) {
+            self.warnings.lock().push(warning.clone());
+        }
+        async fn notify_critical_error(&self, _message: &str, _source: &str) {}
+        async fn notify_system_event(&self, _event_type: &str, _message: &str) {}
+        fn is_enabled(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_sends_pending_batch() {
+        let recorder = Arc::new(RecordingNotificationService::default());
+        let service = BatchingNotificationService::new(vec![recorder.clone()], WarningSeverity::Warn);
+
+        service.notify_warning(&Warning::new(
+            WarningCategory::Processing,
+            WarningSeverity::Error,
+            "Pool DEFAULT failing".to_string(),
+            "test".to_string(),
+        )).await;
+        assert_eq!(service.pending_count(), 1);
+        assert!(recorder.warnings.lock().is_empty());
+
+        service.flush().await;
+
+        assert_eq!(service.pending_count(), 0);
+        let sent = recorder.warnings.lock();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].message.contains("Pool DEFAULT failing"));
+    }
+
+    #[tokio::test]
+    async fn test_flush_with_empty_batch_sends_nothing() {
+        let recorder = Arc::new(RecordingNotificationService::default());
+        let service = BatchingNotificationService::new(vec![recorder.clone()], WarningSeverity::Warn);
+
+        service.flush().await;
+
+        assert!(recorder.warnings.lock().is_empty());
+    }
 }
@@ -6,26 +6,37 @@
 //! - Rate limiting using governor
 //! - Dynamic worker tasks per message group
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::time::Duration;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use std::num::NonZeroU32;
 use dashmap::{DashMap, DashSet};
+use futures::FutureExt;
 use tokio::sync::{mpsc, Semaphore, oneshot};
-use governor::{Quota, RateLimiter, state::{NotKeyed, InMemoryState}, clock::DefaultClock};
-use tracing::{info, warn, error, debug};
+use governor::{Quota, RateLimiter, state::{NotKeyed, InMemoryState, keyed::DefaultKeyedStateStore}, clock::{Clock, DefaultClock}};
+use tracing::{info, warn, error, debug, Instrument, Span};
 
 use fc_common::{
     Message, BatchMessage, AckNack, PoolConfig, PoolStats,
-    MediationResult, EnhancedPoolMetrics,
+    MediationOutcome, MediationResult, MediationType, EnhancedPoolMetrics, RateLimitScope,
 };
-use crate::mediator::Mediator;
+use crate::dead_letter::{DeadLetter, DeadLetterSink};
+use crate::mediator::{message_span, Mediator};
 use crate::metrics::PoolMetricsCollector;
+use crate::router_metrics;
 use crate::Result;
 
 const DEFAULT_GROUP: &str = "__DEFAULT__";
 const QUEUE_CAPACITY_MULTIPLIER: u32 = 2;   // Java: QUEUE_CAPACITY_MULTIPLIER = 2
 const MIN_QUEUE_CAPACITY: u32 = 50;          // Java: MIN_QUEUE_CAPACITY = 50
+const DEAD_LETTER_RETRY_DELAY_SECONDS: u32 = 30;
+/// NACK delay for 4xx responses in pools with `ack_on_4xx` disabled (same as 5xx)
+const CLIENT_ERROR_RETRY_DELAY_SECONDS: u32 = 30;
+
+type PoolRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+type GroupRateLimiter = RateLimiter<Arc<str>, DefaultKeyedStateStore<Arc<str>>, DefaultClock>;
 
 /// Composite key for batch+group tracking - avoids format!() string allocation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -58,13 +69,19 @@ pub struct PoolTask {
     pub batch_id: Option<Arc<str>>,
     /// Pre-computed batch+group key for FIFO tracking (uses tuple to avoid string formatting)
     pub batch_group_key: Option<BatchGroupKey>,
+    /// Span mediation runs under, so it stays a child of the routing span
+    pub span: Span,
 }
 
 /// Process pool with FIFO ordering and rate limiting
 pub struct ProcessPool {
     config: PoolConfig,
+    /// Default mediator, used for any mediation type without a dedicated one
     mediator: Arc<dyn Mediator>,
 
+    /// Dedicated mediators keyed by the message's `mediation_type`
+    mediators: Arc<HashMap<MediationType, Arc<dyn Mediator>>>,
+
     /// Current concurrency level (may differ from config after updates)
     concurrency: AtomicU32,
 
@@ -88,11 +105,28 @@ pub struct ProcessPool {
     batch_group_message_count: Arc<DashMap<BatchGroupKey, AtomicU32>>,
 
     /// Rate limiter (optional, behind Arc<RwLock> for sharing with workers and in-place updates)
-    rate_limiter: Arc<parking_lot::RwLock<Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>>>,
+    /// Only set when the rate limit scope is `Pool`
+    rate_limiter: Arc<parking_lot::RwLock<Option<Arc<PoolRateLimiter>>>>,
+
+    /// Per-message-group rate limiter, only set when the rate limit scope is `Group`
+    group_rate_limiter: Arc<parking_lot::RwLock<Option<Arc<GroupRateLimiter>>>>,
 
     /// Current rate limit value for comparison during updates
     rate_limit_per_minute: Arc<parking_lot::RwLock<Option<u32>>>,
 
+    /// Whether the rate limit applies to the whole pool or to each message group
+    rate_limit_scope: parking_lot::RwLock<RateLimitScope>,
+
+    /// Token bucket size; `None` uses the per-minute rate
+    rate_limit_burst: parking_lot::RwLock<Option<u32>>,
+
+    /// When the pool rate limiter next has a permit, as last seen by a waiting
+    /// worker. Lets callers ask without consuming a permit themselves.
+    next_permit_at: Arc<parking_lot::Mutex<Option<Instant>>>,
+
+    /// Whether 4xx responses are ACKed (default) or NACKed for retry
+    ack_on_4xx: Arc<AtomicBool>,
+
     /// Running state
     running: AtomicBool,
 
@@ -107,21 +141,48 @@ pub struct ProcessPool {
 
     /// Warning service for generating warnings (optional)
     warning_service: Option<Arc<crate::warning::WarningService>>,
+
+    /// Receives permanently failed messages before they are ACKed (optional)
+    dead_letter_sink: Option<Arc<dyn DeadLetterSink>>,
+
+    /// Group workers restarted after panicking or dying, across all groups
+    group_thread_restarts: Arc<AtomicU64>,
+
+    /// Restarts per message group, for the restart count in warnings
+    group_restart_counts: Arc<DashMap<Arc<str>, u32>>,
+}
+
+/// Decrements the active worker count when dropped, so a panicking mediation
+/// doesn't leave the count raised
+struct ActiveWorkerGuard(Arc<AtomicU32>);
+
+impl ActiveWorkerGuard {
+    fn new(active_workers: Arc<AtomicU32>) -> Self {
+        active_workers.fetch_add(1, Ordering::SeqCst);
+        Self(active_workers)
+    }
+}
+
+impl Drop for ActiveWorkerGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl ProcessPool {
     pub fn new(config: PoolConfig, mediator: Arc<dyn Mediator>) -> Self {
         let concurrency_val = config.concurrency;
 
-        let rate_limiter = config.rate_limit_per_minute.and_then(|rpm| {
-            NonZeroU32::new(rpm).map(|nz| {
-                Arc::new(RateLimiter::direct(Quota::per_minute(nz)))
-            })
-        });
+        let (rate_limiter, group_rate_limiter) = Self::build_rate_limiters(
+            config.rate_limit_per_minute,
+            config.rate_limit_burst,
+            config.rate_limit_scope,
+        );
 
         Self {
             config: config.clone(),
             mediator,
+            mediators: Arc::new(HashMap::new()),
             concurrency: AtomicU32::new(concurrency_val),
             semaphore: Arc::new(Semaphore::new(concurrency_val as usize)),
             message_group_queues: DashMap::new(),
@@ -130,15 +191,62 @@ impl ProcessPool {
             failed_batch_groups: DashSet::new(),
             batch_group_message_count: Arc::new(DashMap::new()),
             rate_limiter: Arc::new(parking_lot::RwLock::new(rate_limiter)),
+            group_rate_limiter: Arc::new(parking_lot::RwLock::new(group_rate_limiter)),
             rate_limit_per_minute: Arc::new(parking_lot::RwLock::new(config.rate_limit_per_minute)),
+            rate_limit_scope: parking_lot::RwLock::new(config.rate_limit_scope),
+            rate_limit_burst: parking_lot::RwLock::new(config.rate_limit_burst),
+            next_permit_at: Arc::new(parking_lot::Mutex::new(None)),
+            ack_on_4xx: Arc::new(AtomicBool::new(config.ack_on_4xx)),
             running: AtomicBool::new(false),
             queue_size: Arc::new(AtomicU32::new(0)),
             active_workers: Arc::new(AtomicU32::new(0)),
             metrics_collector: Arc::new(PoolMetricsCollector::new()),
             warning_service: None,
+            dead_letter_sink: None,
+            group_thread_restarts: Arc::new(AtomicU64::new(0)),
+            group_restart_counts: Arc::new(DashMap::new()),
         }
     }
 
+    /// Build the limiter for the given scope. A missing or zero rate disables limiting.
+    ///
+    /// The limiter is a token bucket refilling at `rate_limit_per_minute / 60` per
+    /// second and holding up to `burst` permits (the per-minute rate when unset).
+    fn build_rate_limiters(
+        rate_limit_per_minute: Option<u32>,
+        burst: Option<u32>,
+        scope: RateLimitScope,
+    ) -> (Option<Arc<PoolRateLimiter>>, Option<Arc<GroupRateLimiter>>) {
+        let mut quota = match rate_limit_per_minute.and_then(NonZeroU32::new) {
+            Some(rpm) => Quota::per_minute(rpm),
+            None => return (None, None),
+        };
+        if let Some(burst) = burst.and_then(NonZeroU32::new) {
+            quota = quota.allow_burst(burst);
+        }
+
+        match scope {
+            RateLimitScope::Pool => (Some(Arc::new(RateLimiter::direct(quota))), None),
+            RateLimitScope::Group => (None, Some(Arc::new(RateLimiter::keyed(quota)))),
+        }
+    }
+
+    /// Register a dedicated mediator for a mediation type.
+    /// Messages of other types keep going to the default mediator.
+    pub fn with_mediator(mut self, mediation_type: MediationType, mediator: Arc<dyn Mediator>) -> Self {
+        Arc::make_mut(&mut self.mediators).insert(mediation_type, mediator);
+        self
+    }
+
+    /// Select the mediator for a message based on its `mediation_type`
+    fn select_mediator<'a>(
+        mediator: &'a Arc<dyn Mediator>,
+        mediators: &'a HashMap<MediationType, Arc<dyn Mediator>>,
+        mediation_type: MediationType,
+    ) -> &'a Arc<dyn Mediator> {
+        mediators.get(&mediation_type).unwrap_or(mediator)
+    }
+
     /// Set the warning service for generating warnings
     pub fn with_warning_service(mut self, warning_service: Arc<crate::warning::WarningService>) -> Self {
         self.warning_service = Some(warning_service);
@@ -150,6 +258,12 @@ impl ProcessPool {
         self.warning_service = Some(warning_service);
     }
 
+    /// Hand permanently failed messages to `sink` before ACKing them
+    pub fn with_dead_letter_sink(mut self, sink: Arc<dyn DeadLetterSink>) -> Self {
+        self.dead_letter_sink = Some(sink);
+        self
+    }
+
     /// Start the pool
     pub async fn start(&self) {
         if self.running.swap(true, Ordering::SeqCst) {
@@ -160,6 +274,9 @@ impl ProcessPool {
             pool_code = %self.config.code,
             concurrency = self.config.concurrency,
             rate_limit = ?self.config.rate_limit_per_minute,
+            rate_limit_scope = ?self.config.rate_limit_scope,
+            rate_limit_burst = ?self.config.rate_limit_burst,
+            ack_on_4xx = self.config.ack_on_4xx,
             "Starting process pool"
         );
     }
@@ -191,6 +308,7 @@ impl ProcessPool {
 
         // Increment queue size
         self.queue_size.fetch_add(1, Ordering::SeqCst);
+        router_metrics::record_message_submitted(&self.config.code, batch_msg.message.client_id.as_deref());
 
         // Get message group - use Cow to avoid allocation when group_id exists
         let group_id: Arc<str> = batch_msg.message.message_group_id
@@ -219,11 +337,11 @@ impl ProcessPool {
                     message_id = %batch_msg.message.id,
                     batch_id = %key.batch_id,
                     group_id = %key.group_id,
-                    "Batch+group failed, NACKing for FIFO"
+                    "Batch+group failed, deferring for FIFO"
                 );
                 self.queue_size.fetch_sub(1, Ordering::SeqCst);
                 self.decrement_and_cleanup_batch_group(key);
-                let _ = batch_msg.ack_tx.send(AckNack::Nack { delay_seconds: Some(1) });
+                let _ = batch_msg.ack_tx.send(AckNack::Defer { delay_seconds: Some(1) });
                 return Ok(());
             }
         }
@@ -234,6 +352,12 @@ impl ProcessPool {
         // Clone batch_group_key before moving into task (for error handling)
         let batch_group_key_for_error = batch_group_key.clone();
 
+        // Mediation continues the submitter's span; callers without one get a fresh message span
+        let span = match Span::current() {
+            span if span.is_none() => message_span(&batch_msg.message),
+            span => span,
+        };
+
         // Send to group queue
         let task = PoolTask {
             message: batch_msg.message,
@@ -241,6 +365,7 @@ impl ProcessPool {
             ack_tx: batch_msg.ack_tx,
             batch_id: batch_msg.batch_id.map(|s| Arc::from(s.as_str())),
             batch_group_key,
+            span,
         };
 
         if let Err(e) = group_tx.send(task).await {
@@ -260,6 +385,7 @@ impl ProcessPool {
                 ack_tx: e.0.ack_tx,
                 batch_id: e.0.batch_id,
                 batch_group_key: e.0.batch_group_key,
+                span: e.0.span,
             };
 
             if let Err(e2) = new_tx.send(retry_task).await {
@@ -295,21 +421,14 @@ impl ProcessPool {
 
         // Generate warning on worker restart (Java: GROUP_THREAD_RESTART)
         if is_restart {
-            warn!(
-                group_id = %group_id,
-                pool_code = %self.config.code,
-                "Virtual thread for message group appears to have died - restarting"
+            Self::record_group_restart(
+                group_id,
+                &self.config.code,
+                "died",
+                &self.group_thread_restarts,
+                &self.group_restart_counts,
+                &self.warning_service,
             );
-            if let Some(ref ws) = self.warning_service {
-                use fc_common::{WarningCategory, WarningSeverity};
-                ws.add_warning(
-                    WarningCategory::PoolHealth,
-                    WarningSeverity::Warn,
-                    format!("Virtual thread for group [{}] in pool [{}] died and was restarted",
-                        group_id, self.config.code),
-                    format!("ProcessPool:{}", self.config.code),
-                );
-            }
         }
 
         let (tx, rx) = mpsc::channel(100);
@@ -331,54 +450,156 @@ impl ProcessPool {
         let pool_code: Arc<str> = Arc::from(self.config.code.as_str());
         let semaphore = self.semaphore.clone();
         let mediator = self.mediator.clone();
+        let mediators = self.mediators.clone();
         let queue_size = self.queue_size.clone();
         let active_workers = self.active_workers.clone();
         let in_flight_groups = self.in_flight_groups.clone();
         let failed_batch_groups = self.failed_batch_groups.clone();
         let batch_group_message_count = self.batch_group_message_count.clone();
         let rate_limiter = self.rate_limiter.clone(); // Share Arc with worker for config updates
+        let group_rate_limiter = self.group_rate_limiter.clone();
+        let next_permit_at = self.next_permit_at.clone();
+        let ack_on_4xx = self.ack_on_4xx.clone();
         let message_group_queues = self.message_group_queues.clone();
         let active_group_threads = self.active_group_threads.clone();
         let metrics_collector = self.metrics_collector.clone();
+        let dead_letter_sink = self.dead_letter_sink.clone();
+        let group_thread_restarts = self.group_thread_restarts.clone();
+        let group_restart_counts = self.group_restart_counts.clone();
+        let warning_service = self.warning_service.clone();
 
         debug!(group_id = %group_id, pool_code = %self.config.code, "Spawning group worker task");
 
         tokio::spawn(async move {
-            Self::run_group_worker(
-                group_id_clone,
-                pool_code,
-                rx,
-                semaphore,
-                mediator,
-                queue_size,
-                active_workers,
-                in_flight_groups,
-                failed_batch_groups,
-                batch_group_message_count,
-                rate_limiter,
-                message_group_queues,
-                active_group_threads,
-                metrics_collector,
-            ).await;
+            let mut rx = rx;
+            // Batch+group of the message being mediated, so a panic can fail it
+            let current_batch_group = parking_lot::Mutex::new(None);
+            loop {
+                let worker = Self::run_group_worker(
+                    group_id_clone.clone(),
+                    pool_code.clone(),
+                    &mut rx,
+                    semaphore.clone(),
+                    mediator.clone(),
+                    mediators.clone(),
+                    queue_size.clone(),
+                    active_workers.clone(),
+                    in_flight_groups.clone(),
+                    failed_batch_groups.clone(),
+                    batch_group_message_count.clone(),
+                    rate_limiter.clone(),
+                    group_rate_limiter.clone(),
+                    next_permit_at.clone(),
+                    message_group_queues.clone(),
+                    active_group_threads.clone(),
+                    metrics_collector.clone(),
+                    dead_letter_sink.clone(),
+                    ack_on_4xx.clone(),
+                    &current_batch_group,
+                );
+
+                // The panicked message's ACK sender is dropped, so it is NACKed. Everything
+                // queued behind it is NACKed too and its batch+group marked failed, so later
+                // messages of the group can't be delivered ahead of it. The worker then
+                // restarts on the same queue.
+                match AssertUnwindSafe(worker).catch_unwind().await {
+                    Ok(()) => break,
+                    Err(panic) => {
+                        let reason = panic.downcast_ref::<&str>().map(|s| s.to_string())
+                            .or_else(|| panic.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "unknown panic".to_string());
+                        error!(group_id = %group_id_clone, pool_code = %pool_code, panic = %reason, "Group worker panicked");
+                        in_flight_groups.remove(&group_id_clone);
+                        if let Some(key) = current_batch_group.lock().take() {
+                            failed_batch_groups.insert(key.clone());
+                            Self::decrement_and_cleanup_batch_group_static(
+                                &key,
+                                &batch_group_message_count,
+                                &failed_batch_groups,
+                            );
+                        }
+                        while let Ok(task) = rx.try_recv() {
+                            queue_size.fetch_sub(1, Ordering::SeqCst);
+                            if let Some(ref key) = task.batch_group_key {
+                                Self::decrement_and_cleanup_batch_group_static(
+                                    key,
+                                    &batch_group_message_count,
+                                    &failed_batch_groups,
+                                );
+                            }
+                            let _ = task.ack_tx.send(AckNack::Nack { delay_seconds: Some(1) });
+                        }
+                        Self::record_group_restart(
+                            &group_id_clone,
+                            &pool_code,
+                            "panicked",
+                            &group_thread_restarts,
+                            &group_restart_counts,
+                            &warning_service,
+                        );
+                    }
+                }
+            }
+            group_restart_counts.remove(&group_id_clone);
         });
     }
 
+    /// Count a group worker restart and raise a GROUP_THREAD_RESTART warning
+    fn record_group_restart(
+        group_id: &Arc<str>,
+        pool_code: &str,
+        cause: &str,
+        group_thread_restarts: &AtomicU64,
+        group_restart_counts: &DashMap<Arc<str>, u32>,
+        warning_service: &Option<Arc<crate::warning::WarningService>>,
+    ) {
+        group_thread_restarts.fetch_add(1, Ordering::SeqCst);
+        let restart_count = {
+            let mut count = group_restart_counts.entry(Arc::clone(group_id)).or_insert(0);
+            *count += 1;
+            *count
+        };
+
+        warn!(
+            group_id = %group_id,
+            pool_code = %pool_code,
+            restart_count,
+            "Worker for message group {} - restarting", cause
+        );
+        if let Some(ws) = warning_service {
+            use fc_common::{WarningCategory, WarningSeverity};
+            ws.add_warning(
+                WarningCategory::GroupThreadRestart,
+                WarningSeverity::Warn,
+                format!("Worker for group [{}] in pool [{}] {} and was restarted (restart {})",
+                    group_id, pool_code, cause, restart_count),
+                format!("ProcessPool:{}", pool_code),
+            );
+        }
+    }
+
     /// Worker loop for a message group
     async fn run_group_worker(
         group_id: Arc<str>,
         pool_code: Arc<str>,
-        mut rx: mpsc::Receiver<PoolTask>,
+        rx: &mut mpsc::Receiver<PoolTask>,
         semaphore: Arc<Semaphore>,
         mediator: Arc<dyn Mediator>,
+        mediators: Arc<HashMap<MediationType, Arc<dyn Mediator>>>,
         queue_size: Arc<AtomicU32>,
         active_workers: Arc<AtomicU32>,
         in_flight_groups: DashSet<Arc<str>>,
         failed_batch_groups: DashSet<BatchGroupKey>,
         batch_group_message_count: Arc<DashMap<BatchGroupKey, AtomicU32>>,
-        rate_limiter: Arc<parking_lot::RwLock<Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>>>,
+        rate_limiter: Arc<parking_lot::RwLock<Option<Arc<PoolRateLimiter>>>>,
+        group_rate_limiter: Arc<parking_lot::RwLock<Option<Arc<GroupRateLimiter>>>>,
+        next_permit_at: Arc<parking_lot::Mutex<Option<Instant>>>,
         message_group_queues: DashMap<Arc<str>, mpsc::Sender<PoolTask>>,
         active_group_threads: DashSet<Arc<str>>,
         metrics_collector: Arc<PoolMetricsCollector>,
+        dead_letter_sink: Option<Arc<dyn DeadLetterSink>>,
+        ack_on_4xx: Arc<AtomicBool>,
+        current_batch_group: &parking_lot::Mutex<Option<BatchGroupKey>>,
     ) {
         info!(group_id = %group_id, pool_code = %pool_code, "Group worker started");
 
@@ -417,21 +638,22 @@ impl ProcessPool {
                     warn!(
                         message_id = %task.message.id,
                         batch_group = %batch_group_key,
-                        "Message from failed batch+group, NACKing to preserve FIFO ordering"
+                        "Message from failed batch+group, deferring to preserve FIFO ordering"
                     );
                     Self::decrement_and_cleanup_batch_group_static(
                         batch_group_key,
                         &batch_group_message_count,
                         &failed_batch_groups,
                     );
-                    let _ = task.ack_tx.send(AckNack::Nack { delay_seconds: Some(1) });
+                    // Never attempted, so not a failed delivery
+                    let _ = task.ack_tx.send(AckNack::Defer { delay_seconds: Some(1) });
                     continue;
                 }
             }
 
             // Wait for rate limit permit (blocking with config-change awareness)
             // Messages stay in memory instead of being NACKed back to SQS
-            Self::wait_for_rate_limit_permit(&rate_limiter, &metrics_collector).await;
+            Self::wait_for_rate_limit_permit(&rate_limiter, &group_rate_limiter, &next_permit_at, &group_id, &metrics_collector).await;
 
             // Acquire semaphore permit
             let permit = match semaphore.acquire().await {
@@ -451,16 +673,51 @@ impl ProcessPool {
                 }
             };
 
-            active_workers.fetch_add(1, Ordering::SeqCst);
+            let active_worker = ActiveWorkerGuard::new(active_workers.clone());
+            *current_batch_group.lock() = task.batch_group_key.clone();
             in_flight_groups.insert(group_id.clone());
 
             // Process the message
             let start = std::time::Instant::now();
-            let outcome = mediator.mediate(&task.message).await;
-            let duration_ms = start.elapsed().as_millis() as u64;
+            let outcome = Self::select_mediator(&mediator, &mediators, task.message.mediation_type)
+                .mediate(&task.message)
+                .instrument(task.span.clone())
+                .await;
+            let elapsed = start.elapsed();
+            let duration_ms = elapsed.as_millis() as u64;
+
+            // Deferred messages weren't attempted, so they don't count as processed
+            if outcome.result != MediationResult::Deferred {
+                router_metrics::record_mediation_latency(&pool_code, elapsed);
+                router_metrics::record_message_processed(
+                    &pool_code,
+                    task.message.client_id.as_deref(),
+                    outcome.result == MediationResult::Success,
+                    router_metrics::mediation_result_label(outcome.result),
+                );
+            }
 
             // Handle outcome and record metrics
+            let nack_client_error = outcome.result == MediationResult::ErrorConfig
+                && !ack_on_4xx.load(Ordering::Relaxed)
+                && outcome.status_code.is_some_and(|code| (400..500).contains(&code));
+
             let ack_nack = match outcome.result {
+                _ if nack_client_error => {
+                    warn!(
+                        message_id = %task.message.id,
+                        error = ?outcome.error_message,
+                        "Client error in pool with ack_on_4xx disabled, NACKing for retry"
+                    );
+                    metrics_collector.record_failure(duration_ms);
+
+                    // Mark batch+group as failed to trigger cascading NACKs
+                    if let Some(ref key) = task.batch_group_key {
+                        failed_batch_groups.insert(key.clone());
+                    }
+
+                    AckNack::Nack { delay_seconds: Some(CLIENT_ERROR_RETRY_DELAY_SECONDS) }
+                }
                 MediationResult::Success => {
                     debug!(
                         message_id = %task.message.id,
@@ -479,7 +736,7 @@ impl ProcessPool {
                     );
                     // Config errors count as failures for metrics
                     metrics_collector.record_failure(duration_ms);
-                    AckNack::Ack
+                    Self::dead_letter(&dead_letter_sink, &task.message, &pool_code, &outcome).await
                 }
                 MediationResult::ErrorProcess => {
                     warn!(
@@ -501,7 +758,7 @@ impl ProcessPool {
                         }
                     }
 
-                    AckNack::Nack { delay_seconds: outcome.delay_seconds }
+                    AckNack::Failed { delay_seconds: outcome.delay_seconds }
                 }
                 MediationResult::ErrorConnection => {
                     warn!(
@@ -525,6 +782,21 @@ impl ProcessPool {
 
                     AckNack::Nack { delay_seconds: Some(5) }
                 }
+                MediationResult::Deferred => {
+                    debug!(
+                        message_id = %task.message.id,
+                        reason = ?outcome.error_message,
+                        "Mediation deferred, handing message back"
+                    );
+
+                    // Later messages of the batch+group are held back to keep FIFO order,
+                    // but nothing is recorded as a failure
+                    if let Some(ref key) = task.batch_group_key {
+                        failed_batch_groups.insert(key.clone());
+                    }
+
+                    AckNack::Defer { delay_seconds: outcome.delay_seconds }
+                }
             };
 
             // Send ACK/NACK
@@ -540,16 +812,51 @@ impl ProcessPool {
             }
 
             // Cleanup
+            *current_batch_group.lock() = None;
             in_flight_groups.remove(&group_id);
-            active_workers.fetch_sub(1, Ordering::SeqCst);
+            drop(active_worker);
             drop(permit);
         }
 
         // Worker exiting - remove from active threads so it can be restarted if needed
         active_group_threads.remove(&group_id);
+
+        // Drop per-group limiter state that has fully refilled so idle groups don't accumulate
+        if let Some(ref rl) = *group_rate_limiter.read() {
+            rl.retain_recent();
+        }
         info!(group_id = %group_id, pool_code = %pool_code, "Group worker exited");
     }
 
+    /// ACK a permanently failed message, first handing it to the dead-letter sink if one
+    /// is configured. If the sink fails the message is NACKed so it isn't lost.
+    async fn dead_letter(
+        sink: &Option<Arc<dyn DeadLetterSink>>,
+        message: &Message,
+        pool_code: &str,
+        outcome: &MediationOutcome,
+    ) -> AckNack {
+        let Some(sink) = sink else {
+            return AckNack::Ack;
+        };
+
+        match sink.send(DeadLetter::from_outcome(message.clone(), pool_code, outcome)).await {
+            Ok(()) => {
+                info!(message_id = %message.id, pool_code = %pool_code, "Message sent to dead-letter sink");
+                AckNack::Ack
+            }
+            Err(e) => {
+                error!(
+                    message_id = %message.id,
+                    pool_code = %pool_code,
+                    error = %e,
+                    "Failed to send message to dead-letter sink, NACKing"
+                );
+                AckNack::Nack { delay_seconds: Some(DEAD_LETTER_RETRY_DELAY_SECONDS) }
+            }
+        }
+    }
+
     /// Decrement batch+group message count and cleanup tracking maps when count reaches zero.
     /// Instance version for use in submit().
     fn decrement_and_cleanup_batch_group(&self, batch_group_key: &BatchGroupKey) {
@@ -597,6 +904,8 @@ impl ProcessPool {
     }
 
     /// Check if rate limited
+    /// With `Group` scope the pool itself is never rate limited - each group
+    /// waits for its own permits inside its worker.
     pub fn is_rate_limited(&self) -> bool {
         self.rate_limiter
             .read()
@@ -605,6 +914,23 @@ impl ProcessPool {
             .unwrap_or(false)
     }
 
+    /// Seconds until the pool rate limiter has capacity again, rounded up.
+    /// `None` when no worker is currently waiting on the pool limiter. Unlike
+    /// [`is_rate_limited`](Self::is_rate_limited) this never consumes a permit.
+    pub fn seconds_until_capacity(&self) -> Option<u64> {
+        if self.rate_limiter.read().is_none() {
+            return None;
+        }
+
+        let next = (*self.next_permit_at.lock())?;
+        let remaining = next.checked_duration_since(Instant::now())?;
+        if remaining.is_zero() {
+            return None;
+        }
+
+        Some(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0))
+    }
+
     /// Waits for a rate limit permit, handling config changes gracefully.
     /// Uses a timed poll loop to detect when rate limiter is replaced or removed.
     ///
@@ -612,35 +938,51 @@ impl ProcessPool {
     /// - Rate limit removed (100→null): Returns immediately on next check
     /// - Rate limit changed (100→200): Uses new limiter on next poll
     /// - Permits available: check() succeeds immediately
+    /// - Group scope: checks the permit for this worker's message group only
+    ///
+    /// While waiting on the pool limiter, records when its next permit is due
+    /// in `next_permit_at`.
     async fn wait_for_rate_limit_permit(
-        rate_limiter: &Arc<parking_lot::RwLock<Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>>>,
+        rate_limiter: &Arc<parking_lot::RwLock<Option<Arc<PoolRateLimiter>>>>,
+        group_rate_limiter: &Arc<parking_lot::RwLock<Option<Arc<GroupRateLimiter>>>>,
+        next_permit_at: &Arc<parking_lot::Mutex<Option<Instant>>>,
+        group_id: &Arc<str>,
         metrics_collector: &Arc<PoolMetricsCollector>,
     ) {
         let mut recorded_rate_limit = false;
 
         loop {
-            // Read current rate limiter (may have changed via config update)
-            let limiter = rate_limiter.read().clone();
-
-            match limiter {
-                None => return, // No rate limiting configured, proceed immediately
-                Some(rl) => {
-                    if rl.check().is_ok() {
-                        return; // Got permit, proceed with processing
+            // Read current rate limiters (may have changed via config update)
+            let pool_limiter = rate_limiter.read().clone();
+            let group_limiter = group_rate_limiter.read().clone();
+
+            let permitted = match (pool_limiter, group_limiter) {
+                (Some(rl), _) => match rl.check() {
+                    Ok(()) => true,
+                    Err(not_until) => {
+                        let wait = not_until.wait_time_from(DefaultClock::default().now());
+                        *next_permit_at.lock() = Some(Instant::now() + wait);
+                        false
                     }
+                },
+                (None, Some(rl)) => rl.check_key(group_id).is_ok(),
+                (None, None) => true, // No rate limiting configured
+            };
 
-                    // Record rate limit event once per wait (not every poll)
-                    if !recorded_rate_limit {
-                        metrics_collector.record_rate_limited();
-                        recorded_rate_limit = true;
-                        debug!("Rate limited - waiting for permit");
-                    }
+            if permitted {
+                return; // Got permit, proceed with processing
+            }
 
-                    // No permit available - wait briefly then re-check
-                    // This handles: rate limit removed, rate limit changed, permits available
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                }
+            // Record rate limit event once per wait (not every poll)
+            if !recorded_rate_limit {
+                metrics_collector.record_rate_limited();
+                recorded_rate_limit = true;
+                debug!(group_id = %group_id, "Rate limited - waiting for permit");
             }
+
+            // No permit available - wait briefly then re-check
+            // This handles: rate limit removed, rate limit changed, permits available
+            tokio::time::sleep(Duration::from_millis(100)).await;
         }
     }
 
@@ -670,13 +1012,15 @@ impl ProcessPool {
             concurrency: current_concurrency,
             active_workers: self.active_workers.load(Ordering::SeqCst),
             queue_size: self.queue_size.load(Ordering::SeqCst),
-            queue_capacity: std::cmp::max(
-                current_concurrency * QUEUE_CAPACITY_MULTIPLIER,
-                MIN_QUEUE_CAPACITY,
-            ),
+            queue_capacity: self.queue_capacity(),
             message_group_count: self.message_group_queues.len() as u32,
             rate_limit_per_minute: *self.rate_limit_per_minute.read(),
+            rate_limit_scope: *self.rate_limit_scope.read(),
+            rate_limit_burst: *self.rate_limit_burst.read(),
+            ack_on_4xx: self.ack_on_4xx(),
             is_rate_limited: self.is_rate_limited(),
+            is_draining: false,
+            group_thread_restarts: self.group_thread_restarts.load(Ordering::SeqCst),
             metrics: Some(self.metrics_collector.get_metrics()),
         }
     }
@@ -686,6 +1030,11 @@ impl ProcessPool {
         self.metrics_collector.get_metrics()
     }
 
+    /// Get the metrics collector, for recording end-to-end latency outside the pool
+    pub fn metrics_collector(&self) -> Arc<PoolMetricsCollector> {
+        self.metrics_collector.clone()
+    }
+
     /// Reset metrics (useful for testing)
     pub fn reset_metrics(&self) {
         self.metrics_collector.reset();
@@ -706,11 +1055,31 @@ impl ProcessPool {
         *self.rate_limit_per_minute.read()
     }
 
+    /// Get current rate limit scope
+    pub fn rate_limit_scope(&self) -> RateLimitScope {
+        *self.rate_limit_scope.read()
+    }
+
+    /// Get current rate limit burst size
+    pub fn rate_limit_burst(&self) -> Option<u32> {
+        *self.rate_limit_burst.read()
+    }
+
+    /// Whether 4xx responses are ACKed rather than retried
+    pub fn ack_on_4xx(&self) -> bool {
+        self.ack_on_4xx.load(Ordering::Relaxed)
+    }
+
     /// Get current queue size
     pub fn queue_size(&self) -> u32 {
         self.queue_size.load(Ordering::SeqCst)
     }
 
+    /// Most messages the pool buffers at its current concurrency
+    pub fn queue_capacity(&self) -> u32 {
+        std::cmp::max(self.concurrency() * QUEUE_CAPACITY_MULTIPLIER, MIN_QUEUE_CAPACITY)
+    }
+
     /// Get current active worker count
     pub fn active_workers(&self) -> u32 {
         self.active_workers.load(Ordering::SeqCst)
@@ -801,19 +1170,16 @@ impl ProcessPool {
             return;
         }
 
-        // Create new rate limiter
-        let new_limiter = new_rate_limit.and_then(|rpm| {
-            if rpm == 0 {
-                None // Disable rate limiting
-            } else {
-                NonZeroU32::new(rpm).map(|nz| {
-                    Arc::new(RateLimiter::direct(Quota::per_minute(nz)))
-                })
-            }
-        });
+        // Create new rate limiter (0 disables rate limiting)
+        let (new_limiter, new_group_limiter) = Self::build_rate_limiters(
+            new_rate_limit,
+            *self.rate_limit_burst.read(),
+            *self.rate_limit_scope.read(),
+        );
 
         // Atomically replace
         *self.rate_limiter.write() = new_limiter;
+        *self.group_rate_limiter.write() = new_group_limiter;
         *self.rate_limit_per_minute.write() = new_rate_limit;
 
         info!(
@@ -823,6 +1189,72 @@ impl ProcessPool {
             "Rate limit updated in-place"
         );
     }
+
+    /// Switch between pool-wide and per-group rate limiting at runtime.
+    /// Existing permit state is discarded and the limiter is rebuilt for the new scope.
+    pub fn update_rate_limit_scope(&self, new_scope: RateLimitScope) {
+        let old_scope = *self.rate_limit_scope.read();
+        if old_scope == new_scope {
+            return;
+        }
+
+        let (new_limiter, new_group_limiter) = Self::build_rate_limiters(
+            *self.rate_limit_per_minute.read(),
+            *self.rate_limit_burst.read(),
+            new_scope,
+        );
+
+        *self.rate_limiter.write() = new_limiter;
+        *self.group_rate_limiter.write() = new_group_limiter;
+        *self.rate_limit_scope.write() = new_scope;
+
+        info!(
+            pool_code = %self.config.code,
+            old = ?old_scope,
+            new = ?new_scope,
+            "Rate limit scope updated in-place"
+        );
+    }
+
+    /// Change the token bucket size at runtime. Like a scope change, this rebuilds
+    /// the limiter, so the bucket starts full.
+    pub fn update_rate_limit_burst(&self, new_burst: Option<u32>) {
+        let old_burst = *self.rate_limit_burst.read();
+        if old_burst == new_burst {
+            return;
+        }
+
+        let (new_limiter, new_group_limiter) = Self::build_rate_limiters(
+            *self.rate_limit_per_minute.read(),
+            new_burst,
+            *self.rate_limit_scope.read(),
+        );
+
+        *self.rate_limiter.write() = new_limiter;
+        *self.group_rate_limiter.write() = new_group_limiter;
+        *self.rate_limit_burst.write() = new_burst;
+
+        info!(
+            pool_code = %self.config.code,
+            old = ?old_burst,
+            new = ?new_burst,
+            "Rate limit burst updated in-place"
+        );
+    }
+
+    /// Switch between ACKing and retrying 4xx responses at runtime.
+    /// Applies to the next message each worker processes.
+    pub fn update_ack_on_4xx(&self, ack_on_4xx: bool) {
+        let old = self.ack_on_4xx.swap(ack_on_4xx, Ordering::Relaxed);
+        if old != ack_on_4xx {
+            info!(
+                pool_code = %self.config.code,
+                old = old,
+                new = ack_on_4xx,
+                "ack_on_4xx updated in-place"
+            );
+        }
+    }
 }
 
 /// Configuration update that can be applied at runtime
@@ -5,15 +5,127 @@
 //! - Mediation latency
 //! - Pool statistics
 //! - Queue sizes
+//! - Per-client throughput, with the `client` label capped to a bounded set of values
 
+use fc_common::MediationResult;
 use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::{LazyLock, OnceLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
+use tracing::warn;
+
+/// `client` label for messages that carry no client ID
+pub const NO_CLIENT_LABEL: &str = "none";
+/// `client` label shared by all clients beyond the cardinality limit
+pub const OTHER_CLIENT_LABEL: &str = "other";
+/// Default maximum number of distinct `client` label values
+pub const DEFAULT_MAX_CLIENT_LABELS: usize = 100;
+
+static CLIENT_LABELS: LazyLock<ClientLabels> =
+    LazyLock::new(|| ClientLabels::new(DEFAULT_MAX_CLIENT_LABELS));
+
+static PROMETHEUS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the Prometheus recorder as the global `metrics` recorder.
+///
+/// Its rendered output is appended to the router's `/metrics` endpoint.
+/// Safe to call more than once; only the first call installs.
+pub fn install_prometheus_recorder() -> Option<&'static PrometheusHandle> {
+    if let Some(handle) = PROMETHEUS_HANDLE.get() {
+        return Some(handle);
+    }
+
+    match PrometheusBuilder::new().install_recorder() {
+        Ok(handle) => Some(PROMETHEUS_HANDLE.get_or_init(|| handle)),
+        Err(e) => {
+            warn!(error = %e, "Failed to install Prometheus recorder");
+            None
+        }
+    }
+}
+
+/// Handle to the installed Prometheus recorder, if any
+pub fn prometheus_handle() -> Option<&'static PrometheusHandle> {
+    PROMETHEUS_HANDLE.get()
+}
+
+/// Bounded set of client IDs used as metric label values.
+///
+/// The first `max_clients` distinct client IDs seen get their own label;
+/// any further clients are reported as [`OTHER_CLIENT_LABEL`].
+pub struct ClientLabels {
+    max_clients: AtomicUsize,
+    known: RwLock<HashSet<String>>,
+}
+
+impl ClientLabels {
+    pub fn new(max_clients: usize) -> Self {
+        Self {
+            max_clients: AtomicUsize::new(max_clients),
+            known: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Change the cardinality limit. Clients already labelled keep their label.
+    pub fn set_max_clients(&self, max_clients: usize) {
+        self.max_clients.store(max_clients, Ordering::Relaxed);
+    }
+
+    /// Resolve the label value for a client ID
+    pub fn label(&self, client_id: Option<&str>) -> String {
+        let client_id = match client_id.filter(|c| !c.is_empty()) {
+            Some(c) => c,
+            None => return NO_CLIENT_LABEL.to_string(),
+        };
+
+        if self.known.read().contains(client_id) {
+            return client_id.to_string();
+        }
+
+        let mut known = self.known.write();
+        if known.contains(client_id) || known.len() < self.max_clients.load(Ordering::Relaxed) {
+            known.insert(client_id.to_string());
+            client_id.to_string()
+        } else {
+            OTHER_CLIENT_LABEL.to_string()
+        }
+    }
+
+    /// Number of clients that have their own label
+    pub fn len(&self) -> usize {
+        self.known.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Process-wide client label set used by the recording functions below
+pub fn client_labels() -> &'static ClientLabels {
+    &CLIENT_LABELS
+}
+
+/// `result` label value for a mediation result
+pub fn mediation_result_label(result: MediationResult) -> &'static str {
+    match result {
+        MediationResult::Success => "SUCCESS",
+        MediationResult::ErrorConfig => "ERROR_CONFIG",
+        MediationResult::ErrorProcess => "ERROR_PROCESS",
+        MediationResult::ErrorConnection => "ERROR_CONNECTION",
+        MediationResult::Deferred => "DEFERRED",
+    }
+}
 
 /// Record a message being processed
-pub fn record_message_processed(pool_code: &str, success: bool, result: &str) {
+pub fn record_message_processed(pool_code: &str, client_id: Option<&str>, success: bool, result: &str) {
     counter!(
         "fc_messages_processed_total",
         "pool" => pool_code.to_string(),
+        "client" => client_labels().label(client_id),
         "success" => success.to_string(),
         "result" => result.to_string()
     )
@@ -66,10 +178,11 @@ pub fn set_pool_message_groups(pool_code: &str, count: u32) {
 }
 
 /// Record a message being submitted to a pool
-pub fn record_message_submitted(pool_code: &str) {
+pub fn record_message_submitted(pool_code: &str, client_id: Option<&str>) {
     counter!(
         "fc_messages_submitted_total",
-        "pool" => pool_code.to_string()
+        "pool" => pool_code.to_string(),
+        "client" => client_labels().label(client_id)
     )
     .increment(1);
 }
@@ -115,3 +228,44 @@ pub fn record_consumer_error(consumer: &str, error_type: &str) {
     )
     .increment(1);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    #[test]
+    fn test_client_labels_capped() {
+        let labels = ClientLabels::new(2);
+
+        assert_eq!(labels.label(Some("acme")), "acme");
+        assert_eq!(labels.label(Some("globex")), "globex");
+        assert_eq!(labels.label(Some("initech")), OTHER_CLIENT_LABEL);
+        // Already-known clients keep their label once the cap is reached
+        assert_eq!(labels.label(Some("acme")), "acme");
+        assert_eq!(labels.label(None), NO_CLIENT_LABEL);
+        assert_eq!(labels.label(Some("")), NO_CLIENT_LABEL);
+        assert_eq!(labels.len(), 2);
+
+        labels.set_max_clients(3);
+        assert_eq!(labels.label(Some("initech")), "initech");
+    }
+
+    #[test]
+    fn test_per_client_counters() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_message_processed("POOL-A", Some("client-a"), true, "SUCCESS");
+            record_message_processed("POOL-A", Some("client-a"), true, "SUCCESS");
+            record_message_processed("POOL-A", Some("client-b"), false, "ERROR_PROCESS");
+            record_message_submitted("POOL-A", None);
+        });
+
+        let output = handle.render();
+        assert!(output.contains(r#"fc_messages_processed_total{pool="POOL-A",client="client-a",success="true",result="SUCCESS"} 2"#));
+        assert!(output.contains(r#"fc_messages_processed_total{pool="POOL-A",client="client-b",success="false",result="ERROR_PROCESS"} 1"#));
+        assert!(output.contains(r#"fc_messages_submitted_total{pool="POOL-A",client="none"} 1"#));
+    }
+}
@@ -2,19 +2,24 @@
 //!
 //! Provides:
 //! - Warning storage with categories and severity levels
-//! - Automatic cleanup of old warnings
+//! - Automatic cleanup of old warnings, and expiry of acknowledged ones
 //! - Warning acknowledgment
 //! - Filtering by severity/category
 //! - Optional notification integration (Teams, email, etc.)
+//! - Warning sinks (e.g. [`HttpWarningSink`]) notified of severe warnings as they are raised
+//! - Optional persistence through a [`WarningRepository`]
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
 use chrono::Utc;
 use parking_lot::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use fc_common::{Warning, WarningCategory, WarningSeverity};
 use crate::notification::NotificationService;
+use crate::warning_repository::WarningRepository;
 
 /// Configuration for warning service
 #[derive(Debug, Clone)]
@@ -25,6 +30,10 @@ pub struct WarningServiceConfig {
     pub max_warnings: usize,
     /// Auto-acknowledge warnings older than this (hours)
     pub auto_acknowledge_hours: i64,
+    /// Remove acknowledged warnings this many seconds after they were acknowledged
+    pub acknowledged_ttl_seconds: u64,
+    /// Minimum severity passed to warning sinks
+    pub sink_min_severity: WarningSeverity,
 }
 
 impl Default for WarningServiceConfig {
@@ -33,15 +42,112 @@ impl Default for WarningServiceConfig {
             max_warning_age_hours: 24,
             max_warnings: 1000,
             auto_acknowledge_hours: 8,
+            acknowledged_ttl_seconds: 3600,
+            sink_min_severity: WarningSeverity::Critical,
+        }
+    }
+}
+
+/// Receives each new warning at or above `sink_min_severity`
+#[async_trait]
+pub trait WarningSink: Send + Sync {
+    async fn notify(&self, warning: &Warning);
+}
+
+/// Warning sink that POSTs the warning as JSON to a webhook URL,
+/// retrying failed deliveries with exponential backoff
+pub struct HttpWarningSink {
+    client: reqwest::Client,
+    url: String,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl HttpWarningSink {
+    /// Sink with 3 attempts, backing off 1s then 2s
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url: url.into(),
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the number of attempts and the delay before the first retry (doubled each retry)
+    pub fn with_retry(mut self, max_attempts: u32, initial_backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    async fn post(&self, warning: &Warning) -> Result<(), String> {
+        let response = self.client
+            .post(&self.url)
+            .json(warning)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status()))
+        }
+    }
+}
+
+#[async_trait]
+impl WarningSink for HttpWarningSink {
+    async fn notify(&self, warning: &Warning) {
+        let mut backoff = self.initial_backoff;
+
+        for attempt in 1..=self.max_attempts {
+            match self.post(warning).await {
+                Ok(()) => {
+                    debug!(id = %warning.id, attempt = attempt, "Warning delivered to webhook");
+                    return;
+                }
+                Err(e) if attempt < self.max_attempts => {
+                    warn!(
+                        id = %warning.id,
+                        attempt = attempt,
+                        error = %e,
+                        retry_in_ms = backoff.as_millis() as u64,
+                        "Warning webhook failed, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    warn!(
+                        id = %warning.id,
+                        attempts = attempt,
+                        error = %e,
+                        "Warning webhook failed, giving up"
+                    );
+                }
+            }
         }
     }
 }
 
 /// In-memory warning service
+///
+/// Warnings are kept in memory. When a repository is attached they are also
+/// written through to it, and warnings stored by other instances (or before a
+/// restart) are merged in on [`refresh_persisted`](Self::refresh_persisted).
 pub struct WarningService {
     warnings: RwLock<HashMap<String, Warning>>,
     config: WarningServiceConfig,
     notification_service: RwLock<Option<Arc<dyn NotificationService>>>,
+    repository: RwLock<Option<Arc<dyn WarningRepository>>>,
+    /// Warnings loaded from the repository on the last refresh
+    persisted: RwLock<HashMap<String, Warning>>,
+    sinks: RwLock<Vec<Arc<dyn WarningSink>>>,
 }
 
 impl WarningService {
@@ -50,6 +156,9 @@ impl WarningService {
             warnings: RwLock::new(HashMap::new()),
             config,
             notification_service: RwLock::new(None),
+            repository: RwLock::new(None),
+            persisted: RwLock::new(HashMap::new()),
+            sinks: RwLock::new(Vec::new()),
         }
     }
 
@@ -59,12 +168,27 @@ impl WarningService {
         info!("Notification service attached to WarningService");
     }
 
+    /// Notify `sink` of new warnings at or above `sink_min_severity`
+    pub fn add_sink(&self, sink: Arc<dyn WarningSink>) {
+        self.sinks.write().push(sink);
+        info!(min_severity = ?self.config.sink_min_severity, "Warning sink attached to WarningService");
+    }
+
+    /// Set the repository warnings are persisted to
+    pub fn set_repository(&self, repository: Arc<dyn WarningRepository>) {
+        *self.repository.write() = Some(repository);
+        info!("Warning repository attached to WarningService");
+    }
+
     /// Create a new warning service with notification support
     pub fn with_notification(config: WarningServiceConfig, notification: Arc<dyn NotificationService>) -> Self {
         Self {
             warnings: RwLock::new(HashMap::new()),
             config,
             notification_service: RwLock::new(Some(notification)),
+            repository: RwLock::new(None),
+            persisted: RwLock::new(HashMap::new()),
+            sinks: RwLock::new(Vec::new()),
         }
     }
 
@@ -83,7 +207,7 @@ impl WarningService {
 
         // Enforce max warnings limit
         if warnings.len() >= self.config.max_warnings {
-            self.cleanup_oldest_internal(&mut warnings);
+            Self::evict_for_capacity(&mut warnings);
         }
 
         debug!(
@@ -94,6 +218,27 @@ impl WarningService {
         );
 
         warnings.insert(id.clone(), warning.clone());
+        drop(warnings);
+
+        if let Some(repository) = self.repository.read().clone() {
+            let warning = warning.clone();
+            tokio::spawn(async move {
+                if let Err(e) = repository.insert(&warning).await {
+                    warn!(id = %warning.id, error = %e, "Failed to persist warning");
+                }
+            });
+        }
+
+        // Sinks are notified in the background so a slow one never blocks the caller
+        if severity >= self.config.sink_min_severity {
+            for sink in self.sinks.read().iter() {
+                let sink = sink.clone();
+                let warning = warning.clone();
+                tokio::spawn(async move {
+                    sink.notify(&warning).await;
+                });
+            }
+        }
 
         // Send notification if service is configured
         if let Some(ref notification_service) = *self.notification_service.read() {
@@ -117,9 +262,51 @@ impl WarningService {
         self.add_warning(category, severity, message.into(), source.into())
     }
 
-    /// Get all warnings
+    /// Get all warnings, including persisted warnings not held in memory
     pub fn get_all_warnings(&self) -> Vec<Warning> {
-        self.warnings.read().values().cloned().collect()
+        let warnings = self.warnings.read();
+        let mut all: Vec<Warning> = warnings.values().cloned().collect();
+        all.extend(
+            self.persisted
+                .read()
+                .values()
+                .filter(|w| !warnings.contains_key(&w.id))
+                .cloned(),
+        );
+        all
+    }
+
+    /// Reload persisted warnings from the repository
+    ///
+    /// Loads warnings newer than `max_warning_age_hours` (up to `max_warnings`)
+    /// and applies acknowledgments made elsewhere to the in-memory copies.
+    /// Returns the number of warnings loaded; 0 without a repository.
+    pub async fn refresh_persisted(&self) -> anyhow::Result<usize> {
+        let Some(repository) = self.repository.read().clone() else {
+            return Ok(0);
+        };
+
+        let since = Utc::now() - chrono::Duration::hours(self.config.max_warning_age_hours);
+        let loaded = repository
+            .fetch_recent(since, self.config.max_warnings as u32)
+            .await?;
+
+        {
+            let mut warnings = self.warnings.write();
+            for stored in loaded.iter().filter(|w| w.acknowledged) {
+                if let Some(warning) = warnings.get_mut(&stored.id) {
+                    if !warning.acknowledged {
+                        warning.acknowledged = true;
+                        warning.acknowledged_at = stored.acknowledged_at;
+                    }
+                }
+            }
+        }
+
+        let count = loaded.len();
+        *self.persisted.write() = loaded.into_iter().map(|w| (w.id.clone(), w)).collect();
+        debug!(count = count, "Refreshed persisted warnings");
+        Ok(count)
     }
 
     /// Get warnings by severity
@@ -167,17 +354,27 @@ impl WarningService {
         self.get_warnings_by_severity(WarningSeverity::Critical)
     }
 
-    /// Acknowledge a warning
+    /// Acknowledge a warning, in memory and in the repository
     pub fn acknowledge_warning(&self, id: &str) -> bool {
-        let mut warnings = self.warnings.write();
-        if let Some(warning) = warnings.get_mut(id) {
+        let now = Utc::now();
+        let mut found = false;
+
+        if let Some(warning) = self.warnings.write().get_mut(id) {
+            warning.acknowledged = true;
+            warning.acknowledged_at = Some(now);
+            found = true;
+        }
+        if let Some(warning) = self.persisted.write().get_mut(id) {
             warning.acknowledged = true;
-            warning.acknowledged_at = Some(Utc::now());
+            warning.acknowledged_at = Some(now);
+            found = true;
+        }
+
+        if found {
             debug!(id = %id, "Warning acknowledged");
-            true
-        } else {
-            false
+            self.persist_acknowledgments(vec![id.to_string()], now);
         }
+        found
     }
 
     /// Acknowledge all warnings matching a predicate
@@ -185,24 +382,49 @@ impl WarningService {
     where
         F: Fn(&Warning) -> bool,
     {
-        let mut warnings = self.warnings.write();
         let now = Utc::now();
-        let mut count = 0;
+        let mut acknowledged = Vec::new();
 
-        for warning in warnings.values_mut() {
+        for warning in self.warnings.write().values_mut() {
+            if !warning.acknowledged && predicate(warning) {
+                warning.acknowledged = true;
+                warning.acknowledged_at = Some(now);
+                acknowledged.push(warning.id.clone());
+            }
+        }
+        for warning in self.persisted.write().values_mut() {
             if !warning.acknowledged && predicate(warning) {
                 warning.acknowledged = true;
                 warning.acknowledged_at = Some(now);
-                count += 1;
+                if !acknowledged.contains(&warning.id) {
+                    acknowledged.push(warning.id.clone());
+                }
             }
         }
 
+        let count = acknowledged.len();
         if count > 0 {
             debug!(count = count, "Acknowledged warnings");
+            self.persist_acknowledgments(acknowledged, now);
         }
         count
     }
 
+    /// Write acknowledgments through to the repository, if one is attached
+    fn persist_acknowledgments(&self, ids: Vec<String>, acknowledged_at: chrono::DateTime<Utc>) {
+        let Some(repository) = self.repository.read().clone() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            for id in ids {
+                if let Err(e) = repository.acknowledge(&id, acknowledged_at).await {
+                    warn!(id = %id, error = %e, "Failed to persist warning acknowledgment");
+                }
+            }
+        });
+    }
+
     /// Auto-acknowledge old warnings
     pub fn auto_acknowledge_old_warnings(&self) -> usize {
         let threshold_hours = self.config.auto_acknowledge_hours;
@@ -224,6 +446,29 @@ impl WarningService {
         removed
     }
 
+    /// Remove warnings acknowledged more than `acknowledged_ttl_seconds` ago
+    pub fn expire_acknowledged(&self) -> usize {
+        let cutoff = Utc::now() - chrono::Duration::seconds(self.config.acknowledged_ttl_seconds as i64);
+        let expired = |w: &Warning| w.acknowledged && w.acknowledged_at.is_none_or(|at| at < cutoff);
+
+        let mut removed = 0;
+        self.warnings.write().retain(|id, w| {
+            if expired(w) {
+                debug!(id = %id, severity = ?w.severity, "Expired acknowledged warning");
+                removed += 1;
+                false
+            } else {
+                true
+            }
+        });
+        self.persisted.write().retain(|_, w| !expired(w));
+
+        if removed > 0 {
+            info!(removed = removed, "Expired acknowledged warnings");
+        }
+        removed
+    }
+
     /// Clear all acknowledged warnings
     pub fn clear_acknowledged(&self) -> usize {
         let mut warnings = self.warnings.write();
@@ -277,27 +522,58 @@ impl WarningService {
 
         // Clear very old warnings
         self.clear_old_warnings(self.config.max_warning_age_hours);
-    }
 
-    /// Internal helper to remove oldest warnings
-    fn cleanup_oldest_internal(&self, warnings: &mut HashMap<String, Warning>) {
-        // Remove oldest 10% when at capacity
-        let to_remove = warnings.len() / 10;
-        if to_remove == 0 {
-            return;
+        let threshold_minutes = self.config.max_warning_age_hours * 60;
+        self.persisted.write().retain(|_, w| w.age_minutes() <= threshold_minutes);
+
+        if let Some(repository) = self.repository.read().clone() {
+            let cutoff = Utc::now() - chrono::Duration::hours(self.config.max_warning_age_hours);
+            tokio::spawn(async move {
+                match repository.delete_older_than(cutoff).await {
+                    Ok(removed) if removed > 0 => info!(removed = removed, "Deleted old persisted warnings"),
+                    Ok(_) => {}
+                    Err(e) => warn!(error = %e, "Failed to delete old persisted warnings"),
+                }
+            });
         }
+    }
 
-        let mut sorted: Vec<_> = warnings.iter().collect();
-        sorted.sort_by_key(|(_, w)| w.created_at);
+    /// Make room when at capacity by evicting 10% of warnings (at least one):
+    /// oldest acknowledged first, then oldest INFO, then other non-critical ones.
+    /// Unacknowledged CRITICAL warnings are never evicted.
+    fn evict_for_capacity(warnings: &mut HashMap<String, Warning>) {
+        let to_remove = (warnings.len() / 10).max(1);
 
-        let ids_to_remove: Vec<String> = sorted
+        let mut candidates: Vec<&Warning> = warnings
+            .values()
+            .filter(|w| w.acknowledged || w.severity != WarningSeverity::Critical)
+            .collect();
+        candidates.sort_by_key(|w| {
+            let tier = if w.acknowledged {
+                0
+            } else if w.severity == WarningSeverity::Info {
+                1
+            } else {
+                2
+            };
+            (tier, w.created_at)
+        });
+
+        let ids_to_remove: Vec<String> = candidates
             .into_iter()
             .take(to_remove)
-            .map(|(id, _)| id.clone())
+            .map(|w| w.id.clone())
             .collect();
 
         for id in ids_to_remove {
-            warnings.remove(&id);
+            if let Some(evicted) = warnings.remove(&id) {
+                debug!(
+                    id = %id,
+                    severity = ?evicted.severity,
+                    acknowledged = evicted.acknowledged,
+                    "Evicted warning to stay within max_warnings"
+                );
+            }
         }
     }
 }
@@ -308,6 +584,9 @@ impl Default for WarningService {
             warnings: RwLock::new(HashMap::new()),
             config: WarningServiceConfig::default(),
             notification_service: RwLock::new(None),
+            repository: RwLock::new(None),
+            persisted: RwLock::new(HashMap::new()),
+            sinks: RwLock::new(Vec::new()),
         }
     }
 }
@@ -315,6 +594,63 @@ impl Default for WarningService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::DateTime;
+
+    #[derive(Default)]
+    struct InMemoryWarningRepository {
+        rows: parking_lot::Mutex<HashMap<String, Warning>>,
+    }
+
+    #[async_trait]
+    impl WarningRepository for InMemoryWarningRepository {
+        async fn insert(&self, warning: &Warning) -> anyhow::Result<()> {
+            self.rows.lock().entry(warning.id.clone()).or_insert_with(|| warning.clone());
+            Ok(())
+        }
+
+        async fn acknowledge(&self, id: &str, acknowledged_at: DateTime<Utc>) -> anyhow::Result<bool> {
+            match self.rows.lock().get_mut(id) {
+                Some(warning) if !warning.acknowledged => {
+                    warning.acknowledged = true;
+                    warning.acknowledged_at = Some(acknowledged_at);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn fetch_recent(&self, since: DateTime<Utc>, limit: u32) -> anyhow::Result<Vec<Warning>> {
+            let mut warnings: Vec<Warning> = self.rows.lock()
+                .values()
+                .filter(|w| w.created_at >= since)
+                .cloned()
+                .collect();
+            warnings.sort_by_key(|w| std::cmp::Reverse(w.created_at));
+            warnings.truncate(limit as usize);
+            Ok(warnings)
+        }
+
+        async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> anyhow::Result<u64> {
+            let mut rows = self.rows.lock();
+            let before = rows.len();
+            rows.retain(|_, w| w.created_at >= cutoff);
+            Ok((before - rows.len()) as u64)
+        }
+
+        async fn init_schema(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("condition not met");
+    }
 
     #[test]
     fn test_add_and_get_warning() {
@@ -371,4 +707,256 @@ mod tests {
         assert_eq!(critical.len(), 1);
         assert_eq!(critical[0].message, "Critical");
     }
+
+    #[tokio::test]
+    async fn test_warnings_written_through_to_repository() {
+        let repository = Arc::new(InMemoryWarningRepository::default());
+        let service = WarningService::default();
+        service.set_repository(repository.clone());
+
+        let id = service.add_warning(
+            WarningCategory::Processing,
+            WarningSeverity::Error,
+            "Delivery failed".to_string(),
+            "test".to_string(),
+        );
+
+        wait_until(|| repository.rows.lock().contains_key(&id)).await;
+
+        assert!(service.acknowledge_warning(&id));
+        wait_until(|| repository.rows.lock()[&id].acknowledged).await;
+    }
+
+    #[tokio::test]
+    async fn test_persisted_warnings_merged_and_acknowledged() {
+        let repository = Arc::new(InMemoryWarningRepository::default());
+        let stored = Warning::new(
+            WarningCategory::Configuration,
+            WarningSeverity::Warn,
+            "Raised by another instance".to_string(),
+            "other".to_string(),
+        );
+        repository.insert(&stored).await.unwrap();
+
+        let service = WarningService::default();
+        service.set_repository(repository.clone());
+        service.add_warning(
+            WarningCategory::Processing,
+            WarningSeverity::Error,
+            "Local".to_string(),
+            "test".to_string(),
+        );
+
+        // Local warning is written through before the refresh runs
+        wait_until(|| repository.rows.lock().len() == 2).await;
+        assert_eq!(service.refresh_persisted().await.unwrap(), 2);
+
+        // Each warning appears once, whether held locally, persisted, or both
+        let all = service.get_all_warnings();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|w| w.id == stored.id));
+
+        // Persisted-only warnings don't affect local health counts
+        assert_eq!(service.unacknowledged_count(), 1);
+
+        assert!(service.acknowledge_warning(&stored.id));
+        assert!(service.get_all_warnings().iter().find(|w| w.id == stored.id).unwrap().acknowledged);
+        wait_until(|| repository.rows.lock()[&stored.id].acknowledged).await;
+    }
+
+    #[tokio::test]
+    async fn test_refresh_applies_acknowledgments_from_repository() {
+        let repository = Arc::new(InMemoryWarningRepository::default());
+        let service = WarningService::default();
+        service.set_repository(repository.clone());
+
+        let id = service.add_warning(
+            WarningCategory::Processing,
+            WarningSeverity::Error,
+            "Delivery failed".to_string(),
+            "test".to_string(),
+        );
+        wait_until(|| repository.rows.lock().contains_key(&id)).await;
+
+        // Acknowledged by another instance
+        repository.acknowledge(&id, Utc::now()).await.unwrap();
+        service.refresh_persisted().await.unwrap();
+
+        assert_eq!(service.unacknowledged_count(), 0);
+    }
+
+    fn add(service: &WarningService, severity: WarningSeverity, message: &str) -> String {
+        service.add_warning(
+            WarningCategory::Processing,
+            severity,
+            message.to_string(),
+            "test".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_expire_acknowledged_after_ttl() {
+        let service = WarningService::new(WarningServiceConfig {
+            acknowledged_ttl_seconds: 60,
+            ..Default::default()
+        });
+
+        let expired = add(&service, WarningSeverity::Warn, "Acknowledged long ago");
+        let recent = add(&service, WarningSeverity::Warn, "Acknowledged just now");
+        let open = add(&service, WarningSeverity::Warn, "Not acknowledged");
+
+        service.acknowledge_warning(&expired);
+        service.acknowledge_warning(&recent);
+        service.warnings.write().get_mut(&expired).unwrap().acknowledged_at =
+            Some(Utc::now() - chrono::Duration::seconds(120));
+
+        assert_eq!(service.expire_acknowledged(), 1);
+
+        let remaining: Vec<String> = service.get_all_warnings().into_iter().map(|w| w.id).collect();
+        assert!(!remaining.contains(&expired));
+        assert!(remaining.contains(&recent));
+        assert!(remaining.contains(&open));
+    }
+
+    #[test]
+    fn test_capacity_evicts_acknowledged_then_info() {
+        let service = WarningService::new(WarningServiceConfig {
+            max_warnings: 10,
+            ..Default::default()
+        });
+
+        let acknowledged = add(&service, WarningSeverity::Error, "Acknowledged");
+        service.acknowledge_warning(&acknowledged);
+        let info = add(&service, WarningSeverity::Info, "Info");
+        for i in 0..8 {
+            add(&service, WarningSeverity::Error, &format!("Error {}", i));
+        }
+
+        // At capacity: the acknowledged warning goes first, even though it's no older
+        add(&service, WarningSeverity::Error, "Error 8");
+        let ids: Vec<String> = service.get_all_warnings().into_iter().map(|w| w.id).collect();
+        assert!(!ids.contains(&acknowledged));
+        assert!(ids.contains(&info));
+
+        // Then the INFO warning
+        add(&service, WarningSeverity::Error, "Error 9");
+        let ids: Vec<String> = service.get_all_warnings().into_iter().map(|w| w.id).collect();
+        assert!(!ids.contains(&info));
+        assert_eq!(service.warning_count(), 10);
+    }
+
+    #[test]
+    fn test_unacknowledged_critical_never_evicted() {
+        let service = WarningService::new(WarningServiceConfig {
+            max_warnings: 10,
+            ..Default::default()
+        });
+
+        let critical: Vec<String> = (0..5)
+            .map(|i| add(&service, WarningSeverity::Critical, &format!("Critical {}", i)))
+            .collect();
+        for i in 0..50 {
+            let severity = if i % 2 == 0 { WarningSeverity::Info } else { WarningSeverity::Error };
+            let id = add(&service, severity, &format!("Noise {}", i));
+            if i % 3 == 0 {
+                service.acknowledge_warning(&id);
+            }
+        }
+
+        let ids: Vec<String> = service.get_all_warnings().into_iter().map(|w| w.id).collect();
+        for id in &critical {
+            assert!(ids.contains(id), "critical warning {} was evicted", id);
+        }
+        assert!(service.warning_count() <= 10);
+
+        // With only critical warnings left to evict, the cap is exceeded rather than dropping them
+        for i in 0..10 {
+            add(&service, WarningSeverity::Critical, &format!("More critical {}", i));
+        }
+        assert_eq!(service.critical_count(), 15);
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        received: parking_lot::Mutex<Vec<Warning>>,
+    }
+
+    #[async_trait]
+    impl WarningSink for RecordingSink {
+        async fn notify(&self, warning: &Warning) {
+            self.received.lock().push(warning.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sink_notified_at_threshold() {
+        let service = WarningService::default();
+        let sink = Arc::new(RecordingSink::default());
+        service.add_sink(sink.clone());
+
+        add(&service, WarningSeverity::Error, "Below threshold");
+        let critical = add(&service, WarningSeverity::Critical, "Critical");
+
+        wait_until(|| sink.received.lock().len() == 1).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let received = sink.received.lock();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].id, critical);
+    }
+
+    #[tokio::test]
+    async fn test_slow_sink_does_not_block_add_warning() {
+        struct StuckSink;
+
+        #[async_trait]
+        impl WarningSink for StuckSink {
+            async fn notify(&self, _warning: &Warning) {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        }
+
+        let service = WarningService::default();
+        service.add_sink(Arc::new(StuckSink));
+
+        let start = std::time::Instant::now();
+        add(&service, WarningSeverity::Critical, "Critical");
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_http_sink_retries_until_delivered() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/alerts"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/alerts"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let sink = HttpWarningSink::new(format!("{}/alerts", server.uri()))
+            .with_retry(3, Duration::from_millis(10));
+        let warning = Warning::new(
+            WarningCategory::Processing,
+            WarningSeverity::Critical,
+            "Pool stalled".to_string(),
+            "test".to_string(),
+        );
+        sink.notify(&warning).await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 3);
+        let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+        assert_eq!(body["id"], warning.id.as_str());
+        assert_eq!(body["message"], "Pool stalled");
+    }
 }
+
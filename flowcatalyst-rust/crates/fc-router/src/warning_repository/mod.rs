@@ -0,0 +1,78 @@
+//! Warning Repository Trait
+//!
+//! Optional persistence for [`WarningService`](crate::WarningService), so warnings
+//! survive restarts and are visible across router instances. Backends mirror the
+//! outbox repositories: SQLite, PostgreSQL and MongoDB, each behind its feature.
+
+use async_trait::async_trait;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use fc_common::{Warning, WarningCategory, WarningSeverity};
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "mongo")]
+pub mod mongo;
+
+/// Default table (or collection) name for persisted warnings
+pub const DEFAULT_WARNINGS_TABLE: &str = "router_warnings";
+
+/// Storage for warnings raised by the router
+#[async_trait]
+pub trait WarningRepository: Send + Sync {
+    /// Store a new warning. Storing the same ID twice keeps the first copy.
+    async fn insert(&self, warning: &Warning) -> Result<()>;
+
+    /// Mark a warning acknowledged. Returns false if it is unknown or already acknowledged.
+    async fn acknowledge(&self, id: &str, acknowledged_at: DateTime<Utc>) -> Result<bool>;
+
+    /// Fetch warnings created since `since`, newest first
+    async fn fetch_recent(&self, since: DateTime<Utc>, limit: u32) -> Result<Vec<Warning>>;
+
+    /// Delete warnings created before `cutoff`, returning how many were removed
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<u64>;
+
+    /// Create the table/collection and indexes if they don't exist
+    async fn init_schema(&self) -> Result<()>;
+}
+
+/// Stored name of a category or severity (its serialized variant name)
+#[cfg_attr(not(any(feature = "sqlite", feature = "postgres", feature = "mongo")), allow(dead_code))]
+pub(crate) fn variant_name<T: serde::Serialize>(value: T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        other => Err(anyhow::anyhow!("Expected a unit variant, got {}", other)),
+    }
+}
+
+/// Rebuild a warning from its stored columns
+#[cfg_attr(not(any(feature = "sqlite", feature = "postgres", feature = "mongo")), allow(dead_code))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn warning_from_row(
+    id: String,
+    category: &str,
+    severity: &str,
+    message: String,
+    source: String,
+    created_at_ms: i64,
+    acknowledged: bool,
+    acknowledged_at_ms: Option<i64>,
+) -> Result<Warning> {
+    let category: WarningCategory = serde_json::from_value(serde_json::Value::String(category.to_string()))?;
+    let severity: WarningSeverity = serde_json::from_value(serde_json::Value::String(severity.to_string()))?;
+    let created_at = DateTime::from_timestamp_millis(created_at_ms)
+        .ok_or_else(|| anyhow::anyhow!("Invalid created_at timestamp"))?;
+
+    Ok(Warning {
+        id,
+        category,
+        severity,
+        message,
+        source,
+        created_at,
+        acknowledged,
+        acknowledged_at: acknowledged_at_ms.and_then(DateTime::from_timestamp_millis),
+    })
+}
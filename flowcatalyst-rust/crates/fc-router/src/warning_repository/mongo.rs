@@ -0,0 +1,132 @@
+//! MongoDB Warning Repository Implementation
+
+use async_trait::async_trait;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use fc_common::Warning;
+use futures::stream::TryStreamExt;
+use mongodb::{Client, Collection, Database, IndexModel};
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::options::{FindOptions, IndexOptions};
+use tracing::{debug, info};
+
+use super::{variant_name, warning_from_row, WarningRepository, DEFAULT_WARNINGS_TABLE};
+
+/// MongoDB implementation of WarningRepository
+pub struct MongoWarningRepository {
+    database: Database,
+    collection_name: String,
+}
+
+impl MongoWarningRepository {
+    /// Create a repository using the default `router_warnings` collection
+    pub fn new(client: Client, db_name: &str) -> Self {
+        Self::with_collection(client, db_name, DEFAULT_WARNINGS_TABLE)
+    }
+
+    /// Create a repository using a custom collection name
+    pub fn with_collection(client: Client, db_name: &str, collection_name: &str) -> Self {
+        Self {
+            database: client.database(db_name),
+            collection_name: collection_name.to_string(),
+        }
+    }
+
+    fn collection(&self) -> Collection<Document> {
+        self.database.collection(&self.collection_name)
+    }
+
+    fn parse_doc(doc: &Document) -> Result<Warning> {
+        warning_from_row(
+            doc.get_str("id")?.to_string(),
+            doc.get_str("category")?,
+            doc.get_str("severity")?,
+            doc.get_str("message")?.to_string(),
+            doc.get_str("source")?.to_string(),
+            doc.get_i64("created_at")?,
+            doc.get_bool("acknowledged").unwrap_or(false),
+            doc.get_i64("acknowledged_at").ok(),
+        )
+    }
+}
+
+#[async_trait]
+impl WarningRepository for MongoWarningRepository {
+    async fn insert(&self, warning: &Warning) -> Result<()> {
+        let acknowledged_at = warning.acknowledged_at
+            .map(|t| Bson::Int64(t.timestamp_millis()))
+            .unwrap_or(Bson::Null);
+        let document = doc! {
+            "id": &warning.id,
+            "category": variant_name(warning.category)?,
+            "severity": variant_name(warning.severity)?,
+            "message": &warning.message,
+            "source": &warning.source,
+            "created_at": warning.created_at.timestamp_millis(),
+            "acknowledged": warning.acknowledged,
+            "acknowledged_at": acknowledged_at,
+        };
+
+        // Upsert on id so storing the same warning twice keeps the first copy
+        self.collection()
+            .update_one(doc! { "id": &warning.id }, doc! { "$setOnInsert": document })
+            .upsert(true)
+            .await?;
+
+        debug!(collection = %self.collection_name, id = %warning.id, "Stored warning");
+        Ok(())
+    }
+
+    async fn acknowledge(&self, id: &str, acknowledged_at: DateTime<Utc>) -> Result<bool> {
+        let result = self.collection()
+            .update_one(
+                doc! { "id": id, "acknowledged": false },
+                doc! { "$set": { "acknowledged": true, "acknowledged_at": acknowledged_at.timestamp_millis() } },
+            )
+            .await?;
+
+        Ok(result.modified_count > 0)
+    }
+
+    async fn fetch_recent(&self, since: DateTime<Utc>, limit: u32) -> Result<Vec<Warning>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(limit as i64)
+            .build();
+
+        let mut cursor = self.collection()
+            .find(doc! { "created_at": { "$gte": since.timestamp_millis() } })
+            .with_options(find_options)
+            .await?;
+
+        let mut warnings = Vec::new();
+        while let Some(doc) = cursor.try_next().await? {
+            warnings.push(Self::parse_doc(&doc)?);
+        }
+        Ok(warnings)
+    }
+
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = self.collection()
+            .delete_many(doc! { "created_at": { "$lt": cutoff.timestamp_millis() } })
+            .await?;
+
+        Ok(result.deleted_count)
+    }
+
+    async fn init_schema(&self) -> Result<()> {
+        let id_index = IndexModel::builder()
+            .keys(doc! { "id": 1 })
+            .options(IndexOptions::builder().name("idx_id".to_string()).unique(true).build())
+            .build();
+        let created_at_index = IndexModel::builder()
+            .keys(doc! { "created_at": 1 })
+            .options(IndexOptions::builder().name("idx_created_at".to_string()).build())
+            .build();
+
+        self.collection().create_indexes([id_index, created_at_index]).await?;
+
+        info!(collection = %self.collection_name, "Initialized MongoDB warning indexes");
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! PostgreSQL Warning Repository Implementation
+
+use async_trait::async_trait;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use fc_common::Warning;
+use sqlx::{PgPool, Row};
+use tracing::{debug, info};
+
+use super::{variant_name, warning_from_row, WarningRepository, DEFAULT_WARNINGS_TABLE};
+
+/// PostgreSQL implementation of WarningRepository
+pub struct PostgresWarningRepository {
+    pool: PgPool,
+    table: String,
+}
+
+impl PostgresWarningRepository {
+    /// Create a repository using the default `router_warnings` table
+    pub fn new(pool: PgPool) -> Self {
+        Self::with_table(pool, DEFAULT_WARNINGS_TABLE)
+    }
+
+    /// Create a repository using a custom table name
+    pub fn with_table(pool: PgPool, table: &str) -> Self {
+        Self { pool, table: table.to_string() }
+    }
+
+    fn parse_row(row: &sqlx::postgres::PgRow) -> Result<Warning> {
+        warning_from_row(
+            row.get("id"),
+            row.get("category"),
+            row.get("severity"),
+            row.get("message"),
+            row.get("source"),
+            row.get("created_at"),
+            row.get("acknowledged"),
+            row.get("acknowledged_at"),
+        )
+    }
+}
+
+#[async_trait]
+impl WarningRepository for PostgresWarningRepository {
+    async fn insert(&self, warning: &Warning) -> Result<()> {
+        let query = format!(
+            "INSERT INTO {} (id, category, severity, message, source, created_at, acknowledged, acknowledged_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (id) DO NOTHING",
+            self.table
+        );
+
+        sqlx::query(&query)
+            .bind(&warning.id)
+            .bind(variant_name(warning.category)?)
+            .bind(variant_name(warning.severity)?)
+            .bind(&warning.message)
+            .bind(&warning.source)
+            .bind(warning.created_at.timestamp_millis())
+            .bind(warning.acknowledged)
+            .bind(warning.acknowledged_at.map(|t| t.timestamp_millis()))
+            .execute(&self.pool)
+            .await?;
+
+        debug!(table = %self.table, id = %warning.id, "Stored warning");
+        Ok(())
+    }
+
+    async fn acknowledge(&self, id: &str, acknowledged_at: DateTime<Utc>) -> Result<bool> {
+        let query = format!(
+            "UPDATE {} SET acknowledged = TRUE, acknowledged_at = $1 WHERE id = $2 AND acknowledged = FALSE",
+            self.table
+        );
+
+        let result = sqlx::query(&query)
+            .bind(acknowledged_at.timestamp_millis())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_recent(&self, since: DateTime<Utc>, limit: u32) -> Result<Vec<Warning>> {
+        let query = format!(
+            "SELECT id, category, severity, message, source, created_at, acknowledged, acknowledged_at \
+             FROM {} WHERE created_at >= $1 ORDER BY created_at DESC LIMIT $2",
+            self.table
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(since.timestamp_millis())
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_row).collect()
+    }
+
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let query = format!("DELETE FROM {} WHERE created_at < $1", self.table);
+
+        let result = sqlx::query(&query)
+            .bind(cutoff.timestamp_millis())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn init_schema(&self) -> Result<()> {
+        let schema = format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS {} (
+                id VARCHAR(64) PRIMARY KEY,
+                category VARCHAR(64) NOT NULL,
+                severity VARCHAR(32) NOT NULL,
+                message TEXT NOT NULL,
+                source VARCHAR(255) NOT NULL,
+                created_at BIGINT NOT NULL,
+                acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+                acknowledged_at BIGINT
+            )
+            "#,
+            self.table,
+        );
+        sqlx::query(&schema)
+            .execute(&self.pool)
+            .await?;
+
+        let index = format!(
+            "CREATE INDEX IF NOT EXISTS idx_{}_created_at ON {}(created_at)",
+            self.table.replace('.', "_"),
+            self.table,
+        );
+        sqlx::query(&index)
+            .execute(&self.pool)
+            .await?;
+
+        info!(table = %self.table, "Initialized PostgreSQL warning schema");
+        Ok(())
+    }
+}
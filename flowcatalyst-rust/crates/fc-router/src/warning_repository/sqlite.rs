@@ -0,0 +1,212 @@
+//! SQLite Warning Repository Implementation
+
+use async_trait::async_trait;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use fc_common::Warning;
+use sqlx::{SqlitePool, Row};
+use tracing::{debug, info};
+
+use super::{variant_name, warning_from_row, WarningRepository, DEFAULT_WARNINGS_TABLE};
+
+/// SQLite implementation of WarningRepository
+pub struct SqliteWarningRepository {
+    pool: SqlitePool,
+    table: String,
+}
+
+impl SqliteWarningRepository {
+    /// Create a repository using the default `router_warnings` table
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::with_table(pool, DEFAULT_WARNINGS_TABLE)
+    }
+
+    /// Create a repository using a custom table name
+    pub fn with_table(pool: SqlitePool, table: &str) -> Self {
+        Self { pool, table: table.to_string() }
+    }
+
+    fn parse_row(row: &sqlx::sqlite::SqliteRow) -> Result<Warning> {
+        warning_from_row(
+            row.get("id"),
+            row.get("category"),
+            row.get("severity"),
+            row.get("message"),
+            row.get("source"),
+            row.get("created_at"),
+            row.get("acknowledged"),
+            row.get("acknowledged_at"),
+        )
+    }
+}
+
+#[async_trait]
+impl WarningRepository for SqliteWarningRepository {
+    async fn insert(&self, warning: &Warning) -> Result<()> {
+        let query = format!(
+            "INSERT OR IGNORE INTO {} (id, category, severity, message, source, created_at, acknowledged, acknowledged_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            self.table
+        );
+
+        sqlx::query(&query)
+            .bind(&warning.id)
+            .bind(variant_name(warning.category)?)
+            .bind(variant_name(warning.severity)?)
+            .bind(&warning.message)
+            .bind(&warning.source)
+            .bind(warning.created_at.timestamp_millis())
+            .bind(warning.acknowledged)
+            .bind(warning.acknowledged_at.map(|t| t.timestamp_millis()))
+            .execute(&self.pool)
+            .await?;
+
+        debug!(table = %self.table, id = %warning.id, "Stored warning");
+        Ok(())
+    }
+
+    async fn acknowledge(&self, id: &str, acknowledged_at: DateTime<Utc>) -> Result<bool> {
+        let query = format!(
+            "UPDATE {} SET acknowledged = 1, acknowledged_at = ? WHERE id = ? AND acknowledged = 0",
+            self.table
+        );
+
+        let result = sqlx::query(&query)
+            .bind(acknowledged_at.timestamp_millis())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_recent(&self, since: DateTime<Utc>, limit: u32) -> Result<Vec<Warning>> {
+        let query = format!(
+            "SELECT id, category, severity, message, source, created_at, acknowledged, acknowledged_at \
+             FROM {} WHERE created_at >= ? ORDER BY created_at DESC LIMIT ?",
+            self.table
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(since.timestamp_millis())
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_row).collect()
+    }
+
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let query = format!("DELETE FROM {} WHERE created_at < ?", self.table);
+
+        let result = sqlx::query(&query)
+            .bind(cutoff.timestamp_millis())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn init_schema(&self) -> Result<()> {
+        let schema = format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS {} (
+                id TEXT PRIMARY KEY,
+                category TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                message TEXT NOT NULL,
+                source TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                acknowledged INTEGER NOT NULL DEFAULT 0,
+                acknowledged_at INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_{}_created_at ON {}(created_at);
+            "#,
+            self.table,
+            self.table.replace('.', "_"),
+            self.table,
+        );
+
+        sqlx::query(&schema)
+            .execute(&self.pool)
+            .await?;
+
+        info!(table = %self.table, "Initialized SQLite warning schema");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fc_common::{WarningCategory, WarningSeverity};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn repository() -> SqliteWarningRepository {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let repository = SqliteWarningRepository::new(pool);
+        repository.init_schema().await.unwrap();
+        repository
+    }
+
+    fn warning(message: &str) -> Warning {
+        Warning::new(
+            WarningCategory::Processing,
+            WarningSeverity::Error,
+            message.to_string(),
+            "test".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_fetch_round_trip() {
+        let repository = repository().await;
+        let stored = warning("Delivery failed");
+        repository.insert(&stored).await.unwrap();
+        // Inserting again is a no-op
+        repository.insert(&stored).await.unwrap();
+
+        let fetched = repository.fetch_recent(Utc::now() - chrono::Duration::hours(1), 10).await.unwrap();
+        assert_eq!(fetched.len(), 1);
+        assert_eq!(fetched[0].id, stored.id);
+        assert_eq!(fetched[0].category, WarningCategory::Processing);
+        assert_eq!(fetched[0].severity, WarningSeverity::Error);
+        assert_eq!(fetched[0].created_at.timestamp_millis(), stored.created_at.timestamp_millis());
+        assert!(!fetched[0].acknowledged);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_updates_row_once() {
+        let repository = repository().await;
+        let stored = warning("Delivery failed");
+        repository.insert(&stored).await.unwrap();
+
+        assert!(repository.acknowledge(&stored.id, Utc::now()).await.unwrap());
+        assert!(!repository.acknowledge(&stored.id, Utc::now()).await.unwrap());
+        assert!(!repository.acknowledge("unknown", Utc::now()).await.unwrap());
+
+        let fetched = repository.fetch_recent(Utc::now() - chrono::Duration::hours(1), 10).await.unwrap();
+        assert!(fetched[0].acknowledged);
+        assert!(fetched[0].acknowledged_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_older_than() {
+        let repository = repository().await;
+        let mut old = warning("Old");
+        old.created_at = Utc::now() - chrono::Duration::hours(48);
+        repository.insert(&old).await.unwrap();
+        repository.insert(&warning("New")).await.unwrap();
+
+        let removed = repository.delete_older_than(Utc::now() - chrono::Duration::hours(24)).await.unwrap();
+        assert_eq!(removed, 1);
+
+        let fetched = repository.fetch_recent(Utc::now() - chrono::Duration::hours(72), 10).await.unwrap();
+        assert_eq!(fetched.len(), 1);
+        assert_eq!(fetched[0].message, "New");
+    }
+}
@@ -117,11 +117,10 @@ fn create_message_with_group(id: &str, pool_code: &str, group_id: Option<&str>)
     Message {
         id: id.to_string(),
         pool_code: pool_code.to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: "http://localhost:8080/test".to_string(),
         message_group_id: group_id.map(|s| s.to_string()),
+        ..Default::default()
     }
 }
 
@@ -144,7 +143,7 @@ async fn test_fifo_single_group_ordering() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5, // Multiple workers, but group should still be sequential
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -187,7 +186,7 @@ async fn test_fifo_different_groups_parallel() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -237,7 +236,7 @@ async fn test_fifo_mixed_groups() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -309,8 +308,8 @@ async fn test_fifo_multiple_pools_same_group() {
 
     let config = RouterConfig {
         processing_pools: vec![
-            PoolConfig { code: "POOL_A".to_string(), concurrency: 5, rate_limit_per_minute: None },
-            PoolConfig { code: "POOL_B".to_string(), concurrency: 5, rate_limit_per_minute: None },
+            PoolConfig { code: "POOL_A".to_string(), concurrency: 5, ..Default::default() },
+            PoolConfig { code: "POOL_B".to_string(), concurrency: 5, ..Default::default() },
         ],
         queues: vec![],
     };
@@ -369,7 +368,7 @@ async fn test_fifo_large_group() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -413,7 +412,7 @@ async fn test_fifo_unique_groups_parallel() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -462,7 +461,7 @@ async fn test_fifo_group_throughput() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -96,11 +96,9 @@ fn create_test_message(id: &str, pool_code: &str, target: &str) -> Message {
     Message {
         id: id.to_string(),
         pool_code: pool_code.to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: target.to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
@@ -138,7 +136,7 @@ async fn test_end_to_end_successful_delivery() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -187,7 +185,7 @@ async fn test_end_to_end_failed_delivery() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -234,7 +232,7 @@ async fn test_end_to_end_config_error_no_retry() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -280,8 +278,8 @@ async fn test_end_to_end_multiple_pools() {
 
     let router_config = RouterConfig {
         processing_pools: vec![
-            PoolConfig { code: "POOL_A".to_string(), concurrency: 5, rate_limit_per_minute: None },
-            PoolConfig { code: "POOL_B".to_string(), concurrency: 5, rate_limit_per_minute: None },
+            PoolConfig { code: "POOL_A".to_string(), concurrency: 5, ..Default::default() },
+            PoolConfig { code: "POOL_B".to_string(), concurrency: 5, ..Default::default() },
         ],
         queues: vec![],
     };
@@ -331,7 +329,7 @@ async fn test_end_to_end_custom_delay_response() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -376,7 +374,7 @@ async fn test_end_to_end_batch_processing() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -423,7 +421,7 @@ async fn test_end_to_end_connection_error() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -459,7 +457,7 @@ async fn test_end_to_end_shutdown() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -488,7 +486,7 @@ async fn test_end_to_end_auth_token() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
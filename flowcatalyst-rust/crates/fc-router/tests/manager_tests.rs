@@ -3,11 +3,25 @@
 //! Tests for:
 //! - Message routing and batch processing
 //! - Duplicate detection
-//! - Pool creation and management
-//! - Consumer management
+//! - Pool creation and management, including in-place concurrency changes and
+//!   rejecting invalid reloads before any pool is touched, keeping other
+//!   setting changes when a concurrency change fails, and deferring a pool's
+//!   removal while an earlier pool with the same code drains
+//! - System pressure across pools
+//! - Consumer management, including parallel poll tasks per queue, restarts,
+//!   poll settings changed by a config reload, replaced poll tasks exiting before
+//!   their replacements start, and joining restarted poll tasks on shutdown
+//! - Consumer poll stall detection and last poll error
 //! - Receipt handle updates
-//! - Shutdown behavior
-
+//! - Queue connectivity checks
+//! - Cancelling in-flight messages
+//! - Drain mode
+//! - FIFO delivery of message groups from the embedded SQLite queue
+//! - Dead-lettering after max delivery attempts, counting only processing failures
+//! - Queue settings of queues configured by URI only
+//! - Shutdown behavior, including the drain timeout
+
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, AtomicBool, Ordering};
 use std::time::Duration;
@@ -15,11 +29,15 @@ use async_trait::async_trait;
 
 use fc_common::{
     Message, QueuedMessage, MediationType, MediationOutcome,
-    PoolConfig, RouterConfig,
+    PoolConfig, QueueConfig, RouterConfig, StallConfig, WarningCategory, DEFAULT_POOL_CODE,
+    LEGACY_DEFAULT_POOL_CODE,
+};
+use fc_queue::{EmbeddedQueue, QueueConsumer, QueueError, QueuePublisher};
+use fc_queue::sqlite::{connect_pool, SqliteQueue};
+use fc_router::{
+    QueueManager, Mediator, HealthService, HealthServiceConfig, RouterError, WarningService, CancelOutcome,
+    DeadLetter, DeadLetterSink,
 };
-use fc_queue::{QueueConsumer, QueueError};
-use fc_router::{QueueManager, Mediator};
-use chrono::Utc;
 
 /// Mock mediator for testing
 struct MockMediator {
@@ -61,6 +79,7 @@ struct MockQueueConsumer {
     acked: parking_lot::Mutex<Vec<String>>,
     nacked: parking_lot::Mutex<Vec<(String, Option<u32>)>>,
     running: AtomicBool,
+    metrics_calls: AtomicU32,
 }
 
 impl MockQueueConsumer {
@@ -71,6 +90,7 @@ impl MockQueueConsumer {
             acked: parking_lot::Mutex::new(Vec::new()),
             nacked: parking_lot::Mutex::new(Vec::new()),
             running: AtomicBool::new(true),
+            metrics_calls: AtomicU32::new(0),
         }
     }
 
@@ -81,6 +101,7 @@ impl MockQueueConsumer {
             acked: parking_lot::Mutex::new(Vec::new()),
             nacked: parking_lot::Mutex::new(Vec::new()),
             running: AtomicBool::new(true),
+            metrics_calls: AtomicU32::new(0),
         }
     }
 }
@@ -123,17 +144,23 @@ impl QueueConsumer for MockQueueConsumer {
     async fn stop(&self) {
         self.running.store(false, Ordering::SeqCst);
     }
+
+    async fn get_metrics(&self) -> fc_queue::Result<Option<fc_queue::QueueMetrics>> {
+        self.metrics_calls.fetch_add(1, Ordering::SeqCst);
+        if !self.running.load(Ordering::SeqCst) {
+            return Err(QueueError::Stopped);
+        }
+        Ok(None)
+    }
 }
 
 fn create_test_message(id: &str, pool_code: &str) -> Message {
     Message {
         id: id.to_string(),
         pool_code: pool_code.to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: "http://localhost:8080/test".to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
@@ -166,12 +193,13 @@ async fn test_apply_config() {
             PoolConfig {
                 code: "DEFAULT".to_string(),
                 concurrency: 10,
-                rate_limit_per_minute: None,
+                ..Default::default()
             },
             PoolConfig {
                 code: "HIGH_PRIORITY".to_string(),
                 concurrency: 20,
                 rate_limit_per_minute: Some(1000),
+                ..Default::default()
             },
         ],
         queues: vec![],
@@ -200,7 +228,7 @@ async fn test_route_single_message() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -231,7 +259,7 @@ async fn test_route_batch_multiple_messages() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -261,12 +289,12 @@ async fn test_route_to_different_pools() {
             PoolConfig {
                 code: "POOL_A".to_string(),
                 concurrency: 5,
-                rate_limit_per_minute: None,
+                ..Default::default()
             },
             PoolConfig {
                 code: "POOL_B".to_string(),
                 concurrency: 5,
-                rate_limit_per_minute: None,
+                ..Default::default()
             },
         ],
         queues: vec![],
@@ -295,15 +323,15 @@ async fn test_default_pool_for_empty_pool_code() {
 
     let config = RouterConfig {
         processing_pools: vec![PoolConfig {
-            code: "DEFAULT".to_string(),
+            code: DEFAULT_POOL_CODE.to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
     manager.apply_config(config).await.unwrap();
 
-    // Message with empty pool code should go to DEFAULT
+    // Message with empty pool code should go to the configured default pool
     let messages = vec![create_queued_message("msg-1", "", "test-queue")];
     let consumer = Arc::new(MockQueueConsumer::with_messages("test-queue", messages));
     let poll_result = consumer.poll(10).await.unwrap();
@@ -312,6 +340,35 @@ async fn test_default_pool_for_empty_pool_code() {
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     assert_eq!(mediator.call_count(), 1);
+    // No pool was auto-created for it
+    assert_eq!(manager.pool_codes(), vec![DEFAULT_POOL_CODE.to_string()]);
+}
+
+#[tokio::test]
+async fn test_legacy_default_pool_code_routes_to_default_pool() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    let config = RouterConfig {
+        processing_pools: vec![PoolConfig {
+            code: DEFAULT_POOL_CODE.to_string(),
+            concurrency: 10,
+            ..Default::default()
+        }],
+        queues: vec![],
+    };
+    manager.apply_config(config).await.unwrap();
+
+    // Queued before the default pool code changed
+    let messages = vec![create_queued_message("msg-1", LEGACY_DEFAULT_POOL_CODE, "test-queue")];
+    let consumer = Arc::new(MockQueueConsumer::with_messages("test-queue", messages));
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(mediator.call_count(), 1);
+    assert_eq!(manager.pool_codes(), vec![DEFAULT_POOL_CODE.to_string()]);
 }
 
 #[tokio::test]
@@ -335,6 +392,143 @@ async fn test_memory_health_check() {
     assert!(manager.check_memory_health());
 }
 
+fn pool_config(code: &str, concurrency: u32) -> PoolConfig {
+    PoolConfig {
+        code: code.to_string(),
+        concurrency,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_apply_config_rejects_duplicate_pool_codes() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator));
+
+    let config = RouterConfig {
+        processing_pools: vec![
+            pool_config("B", 5),
+            pool_config("A", 5),
+            pool_config("B", 10),
+            pool_config("C", 5),
+            pool_config("A", 10),
+        ],
+        queues: vec![],
+    };
+
+    let err = manager.apply_config(config).await.unwrap_err();
+    assert!(matches!(err, RouterError::Config(_)));
+    let message = err.to_string();
+    assert!(message.contains("duplicate pool code B (also at processing_pools[0])"), "unexpected error: {}", message);
+    assert!(message.contains("duplicate pool code A (also at processing_pools[1])"), "unexpected error: {}", message);
+
+    // Nothing is applied from a rejected config
+    assert!(manager.get_pool_stats().is_empty());
+}
+
+#[tokio::test]
+async fn test_apply_config_rejects_invalid_pool_settings() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator));
+
+    let config = RouterConfig {
+        processing_pools: vec![
+            pool_config("ZERO", 0),
+            PoolConfig { rate_limit_per_minute: Some(0), ..pool_config("UNLIMITED", 5) },
+        ],
+        queues: vec![],
+    };
+
+    let err = manager.apply_config(config).await.unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("processing_pools[0].concurrency"), "unexpected error: {}", message);
+    assert!(message.contains("processing_pools[1].rate_limit_per_minute"), "unexpected error: {}", message);
+    assert!(manager.get_pool_stats().is_empty());
+}
+
+#[tokio::test]
+async fn test_reload_config_rejects_duplicate_pool_codes() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator));
+
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 5)],
+        queues: vec![],
+    }).await.unwrap();
+
+    let config = RouterConfig {
+        processing_pools: vec![pool_config("TEST", 20), pool_config("TEST", 30), pool_config("NEW", 5)],
+        queues: vec![],
+    };
+
+    let err = manager.reload_config(config).await.unwrap_err();
+    assert!(matches!(err, RouterError::Config(_)));
+    assert!(err.to_string().contains("TEST"), "unexpected error: {}", err);
+
+    // Existing pool is untouched and no new pool was created
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].pool_code, "TEST");
+    assert_eq!(stats[0].concurrency, 5);
+}
+
+#[tokio::test]
+async fn test_failed_concurrency_change_still_applies_other_settings() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator));
+
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 5)],
+        queues: vec![],
+    }).await.unwrap();
+
+    // The pool refuses a zero limit, but the rate limit change still goes in
+    let update = PoolConfig { rate_limit_per_minute: Some(60), ack_on_4xx: false, ..pool_config("TEST", 0) };
+    assert!(manager.update_pool_config("TEST", update).await.is_err());
+
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats[0].concurrency, 5);
+    assert_eq!(stats[0].rate_limit_per_minute, Some(60));
+
+    // The stored config is what the pool runs with, not what was requested
+    let stored = manager.current_config().await.config.processing_pools;
+    assert_eq!(stored[0].concurrency, 5);
+    assert_eq!(stored[0].rate_limit_per_minute, Some(60));
+    assert!(!stored[0].ack_on_4xx);
+}
+
+#[tokio::test]
+async fn test_reload_config_rejects_invalid_pools_before_applying() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::with_limits(mediator, 2, 2));
+
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 5)],
+        queues: vec![],
+    }).await.unwrap();
+
+    // Zero concurrency alongside a valid change to the existing pool
+    let zero = RouterConfig {
+        processing_pools: vec![pool_config("TEST", 20), pool_config("NEW", 0)],
+        queues: vec![],
+    };
+    let err = manager.reload_config(zero).await.unwrap_err();
+    assert!(matches!(err, RouterError::Config(_)));
+    assert!(err.to_string().contains("processing_pools[1].concurrency"), "unexpected error: {}", err);
+
+    // More pools than the manager allows
+    let too_many = RouterConfig {
+        processing_pools: vec![pool_config("TEST", 20), pool_config("B", 5), pool_config("C", 5)],
+        queues: vec![],
+    };
+    let err = manager.reload_config(too_many).await.unwrap_err();
+    assert!(matches!(err, RouterError::Config(_)));
+
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].concurrency, 5);
+}
+
 #[tokio::test]
 async fn test_pool_hot_reload() {
     let mediator = Arc::new(MockMediator::new());
@@ -345,7 +539,7 @@ async fn test_pool_hot_reload() {
         processing_pools: vec![PoolConfig {
             code: "TEST".to_string(),
             concurrency: 5,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -356,6 +550,7 @@ async fn test_pool_hot_reload() {
         code: "TEST".to_string(),
         concurrency: 20,
         rate_limit_per_minute: Some(500),
+        ..Default::default()
     };
     manager.update_pool_config("TEST", new_config).await.unwrap();
 
@@ -365,6 +560,150 @@ async fn test_pool_hot_reload() {
     assert_eq!(pool_stats.rate_limit_per_minute, Some(500));
 }
 
+#[tokio::test]
+async fn test_pool_counters_survive_config_update() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    let config = RouterConfig {
+        processing_pools: vec![PoolConfig {
+            code: "TEST".to_string(),
+            concurrency: 5,
+            ..Default::default()
+        }],
+        queues: vec![],
+    };
+    manager.apply_config(config).await.unwrap();
+
+    let messages = (0..3)
+        .map(|i| create_queued_message(&format!("msg-{}", i), "TEST", "test-queue"))
+        .collect();
+    let consumer = Arc::new(MockQueueConsumer::with_messages("test-queue", messages));
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let total_success = |manager: &QueueManager| {
+        let stats = manager.get_pool_stats();
+        let pool_stats = stats.iter().find(|s| s.pool_code == "TEST").unwrap();
+        pool_stats.metrics.as_ref().unwrap().total_success
+    };
+    assert_eq!(total_success(&manager), 3);
+
+    manager.update_pool_config("TEST", PoolConfig {
+        code: "TEST".to_string(),
+        concurrency: 10,
+        rate_limit_per_minute: Some(600),
+        ..Default::default()
+    }).await.unwrap();
+
+    let stats = manager.get_pool_stats();
+    let pool_stats = stats.iter().find(|s| s.pool_code == "TEST").unwrap();
+    assert_eq!(pool_stats.concurrency, 10);
+    assert_eq!(pool_stats.rate_limit_per_minute, Some(600));
+    assert_eq!(total_success(&manager), 3);
+}
+
+#[tokio::test]
+async fn test_concurrency_changes_keep_in_flight_work() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 2)],
+        queues: vec![],
+    }).await.unwrap();
+
+    let messages = (0..20)
+        .map(|i| create_queued_message(&format!("msg-{}", i), "TEST", "test-queue"))
+        .collect();
+    let consumer = Arc::new(MockQueueConsumer::with_messages("test-queue", messages));
+    let poll_result = consumer.poll(20).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    // Resize while messages are queued and being processed
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    manager.update_pool_config("TEST", pool_config("TEST", 8)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    manager.update_pool_config("TEST", pool_config("TEST", 1)).await.unwrap();
+
+    // Every message completes on the same pool
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while consumer.acked.lock().len() < 20 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }).await.expect("in-flight messages should complete after concurrency changes");
+
+    assert_eq!(mediator.call_count(), 20);
+    assert!(consumer.nacked.lock().is_empty());
+
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].concurrency, 1);
+    assert_eq!(stats[0].metrics.as_ref().unwrap().total_success, 20);
+}
+
+#[tokio::test]
+async fn test_system_pressure_tracks_buffered_messages() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+    assert_eq!(manager.system_pressure(), 0.0);
+
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 2), pool_config("IDLE", 2)],
+        queues: vec![],
+    }).await.unwrap();
+    assert_eq!(manager.system_pressure(), 0.0);
+
+    let messages = (0..20)
+        .map(|i| create_queued_message(&format!("msg-{}", i), "TEST", "test-queue"))
+        .collect();
+    let consumer = Arc::new(MockQueueConsumer::with_messages("test-queue", messages));
+    let poll_result = consumer.poll(20).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    // Two pools of 50 slots each, with TEST holding most of the batch
+    let pressure = manager.system_pressure();
+    assert!(pressure > 0.0 && pressure <= 0.2, "pressure = {}", pressure);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while manager.system_pressure() > 0.0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }).await.expect("pressure should return to zero once the batch is processed");
+}
+
+#[tokio::test]
+async fn test_get_pool_metrics_windowed_counts() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 5), pool_config("IDLE", 5)],
+        queues: vec![],
+    }).await.unwrap();
+
+    let messages = (0..4)
+        .map(|i| create_queued_message(&format!("msg-{}", i), "TEST", "test-queue"))
+        .collect();
+    let consumer = Arc::new(MockQueueConsumer::with_messages("test-queue", messages));
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let metrics = manager.get_pool_metrics();
+    assert_eq!(metrics.len(), 2);
+
+    let test = &metrics["TEST"];
+    assert_eq!(test.last_5_min.success_count, 4);
+    assert_eq!(test.last_5_min.success_rate, 1.0);
+    assert_eq!(test.last_30_min.success_count, 4);
+
+    let idle = &metrics["IDLE"];
+    assert_eq!(idle.last_5_min.success_count + idle.last_5_min.failure_count, 0);
+    assert_eq!(idle.last_5_min.success_rate, 1.0);
+}
+
 #[tokio::test]
 async fn test_shutdown() {
     let mediator = Arc::new(MockMediator::new());
@@ -374,7 +713,7 @@ async fn test_shutdown() {
         processing_pools: vec![PoolConfig {
             code: "DEFAULT".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -396,6 +735,50 @@ async fn test_consumer_health_check() {
     assert!(is_healthy);
 }
 
+#[tokio::test]
+async fn test_consumer_poll_stall_reported_to_health_service() {
+    let mediator = Arc::new(MockMediator::new());
+    let warning_service = Arc::new(WarningService::default());
+    let health_service = Arc::new(HealthService::new(
+        HealthServiceConfig {
+            consumer_startup_grace_secs: 0,
+            ..HealthServiceConfig::default()
+        },
+        warning_service.clone(),
+    ));
+
+    let mut manager = QueueManager::new(mediator);
+    manager.set_health_service(health_service.clone());
+    let manager = Arc::new(manager);
+
+    let polling = Arc::new(MockQueueConsumer::new("polling-consumer"));
+    let failing = Arc::new(MockQueueConsumer::new("failing-consumer"));
+    failing.stop().await; // Every poll now errors
+    manager.add_consumer(polling).await;
+    manager.add_consumer(failing).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert!(health_service.get_consumer_health("polling-consumer").is_healthy);
+    let failing_health = health_service.get_consumer_health("failing-consumer");
+    assert!(failing_health.is_running);
+    assert!(!failing_health.is_healthy);
+    assert_eq!(failing_health.last_error.as_deref(), Some("Queue is stopped"));
+    assert!(failing_health.last_error_at.is_some());
+    assert_eq!(health_service.get_consumer_health("polling-consumer").last_error, None);
+
+    assert_eq!(health_service.check_consumer_stalls(), vec!["failing-consumer".to_string()]);
+    assert_eq!(warning_service.get_warnings_by_category(WarningCategory::ConsumerHealth).len(), 1);
+
+    let report = health_service.get_health_report(&[]);
+    assert_eq!(report.consumers_healthy, 1);
+    assert_eq!(report.consumers_unhealthy, 1);
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
 #[tokio::test]
 async fn test_pool_codes() {
     let mediator = Arc::new(MockMediator::new());
@@ -403,9 +786,9 @@ async fn test_pool_codes() {
 
     let config = RouterConfig {
         processing_pools: vec![
-            PoolConfig { code: "A".to_string(), concurrency: 5, rate_limit_per_minute: None },
-            PoolConfig { code: "B".to_string(), concurrency: 5, rate_limit_per_minute: None },
-            PoolConfig { code: "C".to_string(), concurrency: 5, rate_limit_per_minute: None },
+            PoolConfig { code: "A".to_string(), concurrency: 5, ..Default::default() },
+            PoolConfig { code: "B".to_string(), concurrency: 5, ..Default::default() },
+            PoolConfig { code: "C".to_string(), concurrency: 5, ..Default::default() },
         ],
         queues: vec![],
     };
@@ -417,3 +800,852 @@ async fn test_pool_codes() {
     assert!(codes.contains(&"B".to_string()));
     assert!(codes.contains(&"C".to_string()));
 }
+
+/// Mediator that never completes, leaving its message in flight
+struct HangingMediator;
+
+#[async_trait]
+impl Mediator for HangingMediator {
+    async fn mediate(&self, _message: &Message) -> MediationOutcome {
+        std::future::pending().await
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_gives_up_after_drain_timeout() {
+    let mut manager = QueueManager::new(Arc::new(HangingMediator));
+    manager.set_shutdown_drain_timeout(Duration::from_secs(1));
+    let manager = Arc::new(manager);
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![],
+    }).await.unwrap();
+
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    manager.route_batch(vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")], consumer).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(manager.in_flight_count(), 1);
+
+    tokio::time::timeout(Duration::from_secs(5), manager.shutdown())
+        .await
+        .expect("shutdown should stop waiting once the drain timeout passes");
+    assert_eq!(manager.in_flight_count(), 0);
+}
+
+/// Mediator that holds each message in flight until `release` is set
+#[derive(Default)]
+struct GatedMediator {
+    release: AtomicBool,
+}
+
+#[async_trait]
+impl Mediator for GatedMediator {
+    async fn mediate(&self, _message: &Message) -> MediationOutcome {
+        while !self.release.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        MediationOutcome::success()
+    }
+}
+
+#[tokio::test]
+async fn test_reload_defers_removing_pool_while_earlier_one_drains() {
+    let mediator = Arc::new(GatedMediator::default());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+    let with_pool = RouterConfig {
+        processing_pools: vec![pool_config("TEST", 1)],
+        queues: vec![],
+    };
+    let without_pool = RouterConfig { processing_pools: vec![], queues: vec![] };
+    manager.apply_config(with_pool.clone()).await.unwrap();
+
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    manager.route_batch(vec![create_queued_message("msg-1", "TEST", "test-queue")], consumer.clone()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Removed with a message in flight, then added back
+    manager.reload_config(without_pool.clone()).await.unwrap();
+    manager.reload_config(with_pool).await.unwrap();
+    assert_eq!(manager.pool_codes(), vec!["TEST".to_string()]);
+
+    // Removed again while the first pool is still draining: both stay tracked
+    manager.reload_config(without_pool.clone()).await.unwrap();
+    assert_eq!(manager.pool_codes(), vec!["TEST".to_string()]);
+    let draining: Vec<bool> = manager.get_pool_stats().iter().map(|s| s.is_draining).collect();
+    assert_eq!(draining.iter().filter(|&&d| d).count(), 1);
+    assert_eq!(draining.len(), 2);
+
+    // Once the first pool finishes, the next reload retires it and drains the second
+    mediator.release.store(true, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(*consumer.acked.lock(), vec!["receipt-msg-1".to_string()]);
+    manager.reload_config(without_pool).await.unwrap();
+    assert!(manager.pool_codes().is_empty());
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats.len(), 1);
+    assert!(stats[0].is_draining);
+
+    manager.cleanup_draining_pools().await;
+    assert!(manager.get_pool_stats().is_empty());
+}
+
+#[tokio::test]
+async fn test_stuck_in_flight_message_recovered() {
+    let mut manager = QueueManager::new(Arc::new(HangingMediator));
+    // The maximum age applies even when it is below the (default) stall threshold
+    manager.update_stall_config(StallConfig {
+        force_nack_stalled: true,
+        force_nack_after_seconds: 0,
+        nack_delay_seconds: 15,
+        ..StallConfig::default()
+    });
+    let warning_service = Arc::new(WarningService::default());
+    manager.set_warning_service(warning_service.clone());
+    let manager = Arc::new(manager);
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![],
+    }).await.unwrap();
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(manager.in_flight_count(), 1);
+
+    let recovered = manager.check_and_handle_stalled_messages().await;
+
+    assert_eq!(recovered, 1);
+    assert_eq!(manager.in_flight_count(), 0);
+    assert_eq!(*consumer.nacked.lock(), vec![("receipt-msg-1".to_string(), Some(15))]);
+    assert!(warning_service.get_all_warnings().iter()
+        .any(|w| w.category == WarningCategory::Processing && w.message.contains("maximum age")));
+}
+
+#[tokio::test]
+async fn test_stuck_in_flight_message_flagged_and_warned_once() {
+    let mut manager = QueueManager::new(Arc::new(HangingMediator));
+    manager.update_stall_config(StallConfig {
+        stall_threshold_seconds: 0,
+        force_nack_stalled: false,
+        stuck_threshold_seconds: 0,
+        ..StallConfig::default()
+    });
+    let warning_service = Arc::new(WarningService::default());
+    manager.set_warning_service(warning_service.clone());
+    let manager = Arc::new(manager);
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let in_flight = manager.get_in_flight_messages(10, None);
+    assert_eq!(in_flight.len(), 1);
+    assert!(in_flight[0].stuck);
+
+    manager.check_and_handle_stalled_messages().await;
+    manager.check_and_handle_stalled_messages().await;
+
+    // Still in flight (force-NACK disabled), reported once
+    assert_eq!(manager.in_flight_count(), 1);
+    let stuck_warnings: Vec<_> = warning_service.get_all_warnings().into_iter()
+        .filter(|w| w.category == WarningCategory::Processing && w.message.contains("msg-1"))
+        .collect();
+    assert_eq!(stuck_warnings.len(), 1);
+    assert!(stuck_warnings[0].message.contains(DEFAULT_POOL_CODE));
+}
+
+#[tokio::test]
+async fn test_stuck_flag_independent_of_stall_detection() {
+    let mut manager = QueueManager::new(Arc::new(HangingMediator));
+    manager.update_stall_config(StallConfig {
+        enabled: false,
+        stall_threshold_seconds: 600,
+        stuck_threshold_seconds: 0,
+        ..StallConfig::default()
+    });
+    let warning_service = Arc::new(WarningService::default());
+    manager.set_warning_service(warning_service.clone());
+    let manager = Arc::new(manager);
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Flagged by its own threshold, while disabled stall detection raises no warning
+    let in_flight = manager.get_in_flight_messages(10, None);
+    assert_eq!(in_flight.len(), 1);
+    assert!(in_flight[0].stuck);
+    assert_eq!(manager.check_and_handle_stalled_messages().await, 0);
+    assert!(warning_service.get_all_warnings().is_empty());
+}
+
+#[tokio::test]
+async fn test_in_flight_message_not_stuck_below_stuck_threshold() {
+    let mut manager = QueueManager::new(Arc::new(HangingMediator));
+    manager.update_stall_config(StallConfig {
+        stall_threshold_seconds: 0,
+        stuck_threshold_seconds: 600,
+        ..StallConfig::default()
+    });
+    let manager = Arc::new(manager);
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let in_flight = manager.get_in_flight_messages(10, None);
+    assert_eq!(in_flight.len(), 1);
+    assert!(!in_flight[0].stuck);
+}
+
+#[tokio::test]
+async fn test_in_flight_message_kept_below_max_age() {
+    let mut manager = QueueManager::new(Arc::new(HangingMediator));
+    manager.update_stall_config(StallConfig {
+        force_nack_stalled: true,
+        force_nack_after_seconds: 600,
+        ..StallConfig::default()
+    });
+    let manager = Arc::new(manager);
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(manager.check_and_handle_stalled_messages().await, 0);
+    assert_eq!(manager.in_flight_count(), 1);
+    assert!(consumer.nacked.lock().is_empty());
+}
+
+#[tokio::test]
+async fn test_cancel_in_flight_message() {
+    let manager = Arc::new(QueueManager::new(Arc::new(HangingMediator)));
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(manager.in_flight_count(), 1);
+
+    assert_eq!(manager.cancel_in_flight("msg-1").await, CancelOutcome::Cancelled);
+    assert_eq!(manager.in_flight_count(), 0);
+    assert_eq!(*consumer.nacked.lock(), vec![("receipt-msg-1".to_string(), Some(StallConfig::default().nack_delay_seconds))]);
+
+    assert_eq!(manager.cancel_in_flight("msg-1").await, CancelOutcome::NotFound);
+    assert_eq!(manager.cancel_in_flight("unknown").await, CancelOutcome::NotFound);
+}
+
+#[tokio::test]
+async fn test_cancel_completed_message_conflicts() {
+    let manager = Arc::new(QueueManager::new(Arc::new(MockMediator::new())));
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(manager.in_flight_count(), 0);
+
+    assert_eq!(manager.cancel_in_flight("msg-1").await, CancelOutcome::AlreadyCompleted);
+    assert_eq!(*consumer.acked.lock(), vec!["receipt-msg-1".to_string()]);
+    assert!(consumer.nacked.lock().is_empty());
+}
+
+#[tokio::test]
+async fn test_queue_connectivity_reports_unreachable_queues() {
+    let manager = Arc::new(QueueManager::new(Arc::new(MockMediator::new())));
+    let reachable = Arc::new(MockQueueConsumer::new("queue-up"));
+    let unreachable = Arc::new(MockQueueConsumer::new("queue-down"));
+    unreachable.stop().await;
+    manager.add_consumer(reachable).await;
+    manager.add_consumer(unreachable).await;
+
+    assert_eq!(manager.check_queue_connectivity().await, vec!["queue-down".to_string()]);
+}
+
+#[tokio::test]
+async fn test_queue_connectivity_check_is_cached() {
+    let manager = Arc::new(QueueManager::new(Arc::new(MockMediator::new())));
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    manager.add_consumer(consumer.clone()).await;
+
+    assert!(manager.check_queue_connectivity().await.is_empty());
+
+    // A failure right after a check is served from the cache
+    consumer.stop().await;
+    assert!(manager.check_queue_connectivity().await.is_empty());
+    assert_eq!(consumer.metrics_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_draining_stops_polling_until_resumed() {
+    let warning_service = Arc::new(WarningService::default());
+    let health_service = Arc::new(HealthService::new(
+        HealthServiceConfig::default(),
+        warning_service,
+    ));
+    let mut manager = QueueManager::new(Arc::new(MockMediator::new()));
+    manager.set_health_service(health_service.clone());
+    let manager = Arc::new(manager);
+    manager.set_draining(true);
+
+    let consumer = Arc::new(MockQueueConsumer::with_messages(
+        "test-queue",
+        vec![create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue")],
+    ));
+    manager.add_consumer(consumer.clone()).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // Nothing polled, but the idle consumer isn't reported as stalled
+    assert_eq!(consumer.messages.lock().len(), 1);
+    assert!(health_service.get_consumer_health("test-queue").is_healthy);
+
+    manager.set_draining(false);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(consumer.messages.lock().is_empty());
+    assert_eq!(*consumer.acked.lock(), vec!["receipt-msg-1".to_string()]);
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+#[tokio::test]
+async fn test_embedded_queue_delivers_message_group_in_order() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 4)],
+        queues: vec![],
+    }).await.unwrap();
+
+    let pool = connect_pool("sqlite::memory:", 4).await.unwrap();
+    let queue = Arc::new(SqliteQueue::new(pool, "fifo-queue".to_string(), 30));
+    queue.init_schema().await.unwrap();
+    for i in 1..=3 {
+        let mut message = create_test_message(&format!("msg-{}", i), "TEST");
+        message.message_group_id = Some("order-42".to_string());
+        queue.publish(message).await.unwrap();
+    }
+    manager.add_consumer(queue.clone()).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while mediator.call_count() < 3 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }).await.expect("all three messages should be delivered");
+
+    assert_eq!(mediator.processed_ids(), vec!["msg-1", "msg-2", "msg-3"]);
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+/// Consumer that records how many polls run at once and the batch sizes requested
+#[derive(Default)]
+struct ParallelPollConsumer {
+    active_polls: AtomicU32,
+    max_active_polls: AtomicU32,
+    batch_sizes: parking_lot::Mutex<HashSet<u32>>,
+    /// Batch size of each poll, in the order the polls started
+    poll_order: parking_lot::Mutex<Vec<u32>>,
+}
+
+/// Decrements a poll counter when the poll finishes or is cancelled
+struct ActivePoll<'a>(&'a AtomicU32);
+
+impl Drop for ActivePoll<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl QueueConsumer for ParallelPollConsumer {
+    fn identifier(&self) -> &str {
+        "parallel-queue"
+    }
+
+    async fn poll(&self, max_messages: u32) -> fc_queue::Result<Vec<QueuedMessage>> {
+        self.batch_sizes.lock().insert(max_messages);
+        self.poll_order.lock().push(max_messages);
+        let active = self.active_polls.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_active_polls.fetch_max(active, Ordering::SeqCst);
+        // Restarts cancel polls mid-flight, so count the poll as finished on drop
+        let _active = ActivePoll(&self.active_polls);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok(vec![])
+    }
+
+    async fn ack(&self, _receipt_handle: &str) -> fc_queue::Result<()> {
+        Ok(())
+    }
+
+    async fn nack(&self, _receipt_handle: &str, _delay_seconds: Option<u32>) -> fc_queue::Result<()> {
+        Ok(())
+    }
+
+    async fn extend_visibility(&self, _receipt_handle: &str, _seconds: u32) -> fc_queue::Result<()> {
+        Ok(())
+    }
+
+    fn is_healthy(&self) -> bool {
+        true
+    }
+
+    async fn stop(&self) {}
+}
+
+#[tokio::test]
+async fn test_queue_connections_run_parallel_polls() {
+    let manager = Arc::new(QueueManager::new(Arc::new(MockMediator::new())));
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![],
+        queues: vec![QueueConfig {
+            connections: 3,
+            poll_batch_size: Some(7),
+            ..queue_config("parallel-queue", None)
+        }],
+    }).await.unwrap();
+
+    let consumer = Arc::new(ParallelPollConsumer::default());
+    manager.add_consumer(consumer.clone()).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert_eq!(consumer.max_active_polls.load(Ordering::SeqCst), 3);
+    assert_eq!(*consumer.batch_sizes.lock(), HashSet::from([7]));
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+#[tokio::test]
+async fn test_reload_config_restarts_polls_with_new_settings() {
+    let manager = Arc::new(QueueManager::new(Arc::new(MockMediator::new())));
+    let config = RouterConfig {
+        processing_pools: vec![],
+        queues: vec![QueueConfig {
+            poll_batch_size: Some(7),
+            ..queue_config("parallel-queue", None)
+        }],
+    };
+    manager.apply_config(config.clone()).await.unwrap();
+
+    let consumer = Arc::new(ParallelPollConsumer::default());
+    manager.add_consumer(consumer.clone()).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(consumer.max_active_polls.load(Ordering::SeqCst), 1);
+
+    let mut reloaded = config;
+    reloaded.queues[0].connections = 3;
+    reloaded.queues[0].poll_batch_size = Some(9);
+    manager.reload_config(reloaded).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert_eq!(consumer.max_active_polls.load(Ordering::SeqCst), 3);
+    assert!(consumer.batch_sizes.lock().contains(&9));
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+#[tokio::test]
+async fn test_restarted_polls_start_after_replaced_ones_exit() {
+    let manager = Arc::new(QueueManager::new(Arc::new(MockMediator::new())));
+    let config = RouterConfig {
+        processing_pools: vec![],
+        queues: vec![QueueConfig {
+            connections: 3,
+            poll_batch_size: Some(7),
+            ..queue_config("parallel-queue", None)
+        }],
+    };
+    manager.apply_config(config.clone()).await.unwrap();
+
+    let consumer = Arc::new(ParallelPollConsumer::default());
+    manager.add_consumer(consumer.clone()).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(120)).await;
+
+    let mut reloaded = config;
+    reloaded.queues[0].poll_batch_size = Some(9);
+    manager.reload_config(reloaded).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // Once a new poll has started, no replaced task polls again
+    let order = consumer.poll_order.lock().clone();
+    let first_new = order.iter().position(|&size| size == 9).expect("new poll tasks started");
+    assert!(order[first_new..].iter().all(|&size| size == 9), "old and new polls overlapped: {:?}", order);
+    assert_eq!(consumer.max_active_polls.load(Ordering::SeqCst), 3);
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_pollers_admit_redelivery_once() {
+    let mediator = Arc::new(MockMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config("TEST", 4)],
+        queues: vec![],
+    }).await.unwrap();
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+
+    // Two pollers receive the same broker message (a visibility timeout redelivery)
+    let mut redelivered = create_queued_message("msg-1", "TEST", "test-queue");
+    redelivered.receipt_handle = "receipt-msg-1-redelivered".to_string();
+    let (first, second) = tokio::join!(
+        manager.route_batch(vec![create_queued_message("msg-1", "TEST", "test-queue")], consumer.clone()),
+        manager.route_batch(vec![redelivered], consumer.clone()),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(mediator.call_count(), 1);
+    assert_eq!(consumer.nacked.lock().len(), 1, "the second delivery is deferred");
+    assert_eq!(consumer.acked.lock().len(), 1);
+}
+
+/// Consumer whose polls hang forever while `hang` is set, like a stuck connection
+#[derive(Default)]
+struct HangingPollConsumer {
+    polls: AtomicU32,
+    hang: AtomicBool,
+}
+
+#[async_trait]
+impl QueueConsumer for HangingPollConsumer {
+    fn identifier(&self) -> &str {
+        "hanging-queue"
+    }
+
+    async fn poll(&self, _max_messages: u32) -> fc_queue::Result<Vec<QueuedMessage>> {
+        self.polls.fetch_add(1, Ordering::SeqCst);
+        if self.hang.load(Ordering::SeqCst) {
+            std::future::pending::<()>().await;
+        }
+        Ok(vec![])
+    }
+
+    async fn ack(&self, _receipt_handle: &str) -> fc_queue::Result<()> {
+        Ok(())
+    }
+
+    async fn nack(&self, _receipt_handle: &str, _delay_seconds: Option<u32>) -> fc_queue::Result<()> {
+        Ok(())
+    }
+
+    async fn extend_visibility(&self, _receipt_handle: &str, _seconds: u32) -> fc_queue::Result<()> {
+        Ok(())
+    }
+
+    fn is_healthy(&self) -> bool {
+        true
+    }
+
+    async fn stop(&self) {}
+}
+
+#[tokio::test]
+async fn test_restart_consumer_resumes_polling() {
+    let health = Arc::new(HealthService::new(HealthServiceConfig::default(), Arc::new(WarningService::default())));
+    let mut manager = QueueManager::new(Arc::new(MockMediator::new()));
+    manager.set_health_service(health.clone());
+    let manager = Arc::new(manager);
+
+    let consumer = Arc::new(HangingPollConsumer::default());
+    manager.add_consumer(consumer.clone()).await;
+    assert!(!manager.restart_consumer("missing").await);
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(consumer.polls.load(Ordering::SeqCst) > 0);
+
+    // The poll loop gets stuck in a poll that never returns
+    consumer.hang.store(true, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let stuck_at = consumer.polls.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(consumer.polls.load(Ordering::SeqCst), stuck_at, "polling stopped");
+
+    consumer.hang.store(false, Ordering::SeqCst);
+    assert!(manager.restart_consumer("hanging-queue").await);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(consumer.polls.load(Ordering::SeqCst) > stuck_at + 1, "polling resumed");
+    assert!(health.get_consumer_health("hanging-queue").is_running);
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+#[tokio::test]
+async fn test_start_waits_for_restarted_poll_tasks() {
+    let health = Arc::new(HealthService::new(HealthServiceConfig::default(), Arc::new(WarningService::default())));
+    let mut manager = QueueManager::new(Arc::new(MockMediator::new()));
+    manager.set_health_service(health.clone());
+    let manager = Arc::new(manager);
+
+    let consumer = Arc::new(HangingPollConsumer::default());
+    manager.add_consumer(consumer.clone()).await;
+
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(manager.restart_consumer("hanging-queue").await);
+    assert!(manager.restart_consumer("hanging-queue").await);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    manager.shutdown().await;
+    tokio::time::timeout(Duration::from_secs(5), handle).await
+        .expect("start returns after shutdown")
+        .unwrap()
+        .unwrap();
+
+    // Only the last generation of poll tasks marks the consumer stopped, so this
+    // holds only if start() waited for the restarted tasks
+    assert!(!health.get_consumer_health("hanging-queue").is_running);
+    let polls = consumer.polls.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(consumer.polls.load(Ordering::SeqCst), polls, "no poll task outlives start()");
+}
+
+/// Mediator that always fails with a retryable error
+struct FailingMediator;
+
+#[async_trait]
+impl Mediator for FailingMediator {
+    async fn mediate(&self, _message: &Message) -> MediationOutcome {
+        MediationOutcome::error_process(Some(1), "target unavailable".to_string())
+    }
+}
+
+/// Mediator whose target is unreachable
+struct UnreachableMediator;
+
+#[async_trait]
+impl Mediator for UnreachableMediator {
+    async fn mediate(&self, _message: &Message) -> MediationOutcome {
+        MediationOutcome::error_connection("connection refused".to_string())
+    }
+}
+
+/// Dead-letter sink that records what it receives
+#[derive(Default)]
+struct RecordingSink {
+    received: parking_lot::Mutex<Vec<DeadLetter>>,
+}
+
+#[async_trait]
+impl DeadLetterSink for RecordingSink {
+    async fn send(&self, dead_letter: DeadLetter) -> fc_router::Result<()> {
+        self.received.lock().push(dead_letter);
+        Ok(())
+    }
+}
+
+fn queue_config(name: &str, max_delivery_attempts: Option<u32>) -> QueueConfig {
+    QueueConfig {
+        name: name.to_string(),
+        uri: format!("http://localhost:4566/000000000000/{}", name),
+        connections: 1,
+        visibility_timeout: 30,
+        max_delivery_attempts,
+        poll_batch_size: None,
+    }
+}
+
+/// Deliver the same broker message `deliveries` times, waiting for each outcome
+async fn redeliver(manager: &QueueManager, consumer: &Arc<MockQueueConsumer>, deliveries: usize) {
+    for _ in 0..deliveries {
+        let message = create_queued_message("msg-1", DEFAULT_POOL_CODE, "test-queue");
+        manager.route_batch(vec![message], consumer.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_message_dead_lettered_after_max_delivery_attempts() {
+    let mut manager = QueueManager::new(Arc::new(FailingMediator));
+    let warning_service = Arc::new(WarningService::default());
+    manager.set_warning_service(warning_service.clone());
+    let sink = Arc::new(RecordingSink::default());
+    manager.set_dead_letter_sink(sink.clone());
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![queue_config("test-queue", Some(3))],
+    }).await.unwrap();
+
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    redeliver(&manager, &consumer, 2).await;
+
+    assert_eq!(consumer.nacked.lock().len(), 2);
+    assert!(consumer.acked.lock().is_empty());
+    assert!(sink.received.lock().is_empty());
+
+    redeliver(&manager, &consumer, 1).await;
+
+    assert_eq!(consumer.nacked.lock().len(), 2);
+    assert_eq!(*consumer.acked.lock(), vec!["receipt-msg-1".to_string()]);
+    let received = sink.received.lock();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].message.id, "msg-1");
+    assert_eq!(received[0].reason, "exhausted 3 delivery attempts");
+    assert!(warning_service.get_warnings_by_category(WarningCategory::Processing).iter()
+        .any(|w| w.message.contains("msg-1") && w.message.contains("dead-letter")));
+}
+
+#[tokio::test]
+async fn test_uri_only_queue_settings_apply_to_its_consumer() {
+    let mut manager = QueueManager::new(Arc::new(FailingMediator));
+    let sink = Arc::new(RecordingSink::default());
+    manager.set_dead_letter_sink(sink.clone());
+    let manager = Arc::new(manager);
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![
+            QueueConfig { name: String::new(), uri: "test-queue".to_string(), ..queue_config("unused", Some(2)) },
+            QueueConfig {
+                name: String::new(),
+                uri: "parallel-queue".to_string(),
+                connections: 2,
+                poll_batch_size: Some(4),
+                ..queue_config("unused", None)
+            },
+        ],
+    }).await.unwrap();
+
+    // Consumers of unnamed queues are identified by URI
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    redeliver(&manager, &consumer, 2).await;
+    assert_eq!(sink.received.lock().len(), 1);
+    assert_eq!(sink.received.lock()[0].reason, "exhausted 2 delivery attempts");
+
+    let poller = Arc::new(ParallelPollConsumer::default());
+    manager.add_consumer(poller.clone()).await;
+    let handle = tokio::spawn(manager.clone().start());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(poller.max_active_polls.load(Ordering::SeqCst), 2);
+    assert_eq!(*poller.batch_sizes.lock(), HashSet::from([4]));
+
+    manager.shutdown().await;
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+}
+
+#[tokio::test]
+async fn test_message_retried_without_max_delivery_attempts() {
+    let mut manager = QueueManager::new(Arc::new(FailingMediator));
+    let sink = Arc::new(RecordingSink::default());
+    manager.set_dead_letter_sink(sink.clone());
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![queue_config("test-queue", None)],
+    }).await.unwrap();
+
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    redeliver(&manager, &consumer, 4).await;
+
+    assert_eq!(consumer.nacked.lock().len(), 4);
+    assert!(consumer.acked.lock().is_empty());
+    assert!(sink.received.lock().is_empty());
+}
+
+#[tokio::test]
+async fn test_connection_errors_not_counted_as_delivery_attempts() {
+    let mut manager = QueueManager::new(Arc::new(UnreachableMediator));
+    let sink = Arc::new(RecordingSink::default());
+    manager.set_dead_letter_sink(sink.clone());
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![queue_config("test-queue", Some(2))],
+    }).await.unwrap();
+
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    redeliver(&manager, &consumer, 4).await;
+
+    assert_eq!(consumer.nacked.lock().len(), 4);
+    assert!(consumer.acked.lock().is_empty());
+    assert!(sink.received.lock().is_empty());
+}
+
+/// Mediator that fails every other delivery, starting with the first
+#[derive(Default)]
+struct AlternatingMediator {
+    calls: AtomicU32,
+}
+
+#[async_trait]
+impl Mediator for AlternatingMediator {
+    async fn mediate(&self, _message: &Message) -> MediationOutcome {
+        if self.calls.fetch_add(1, Ordering::SeqCst).is_multiple_of(2) {
+            MediationOutcome::error_process(Some(1), "target unavailable".to_string())
+        } else {
+            MediationOutcome::success()
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_successful_delivery_resets_delivery_attempts() {
+    let mut manager = QueueManager::new(Arc::new(AlternatingMediator::default()));
+    let sink = Arc::new(RecordingSink::default());
+    manager.set_dead_letter_sink(sink.clone());
+    manager.apply_config(RouterConfig {
+        processing_pools: vec![pool_config(DEFAULT_POOL_CODE, 5)],
+        queues: vec![queue_config("test-queue", Some(2))],
+    }).await.unwrap();
+
+    // Failed, delivered, failed: the ACK in between forgets the first failure
+    let consumer = Arc::new(MockQueueConsumer::new("test-queue"));
+    redeliver(&manager, &consumer, 3).await;
+
+    assert_eq!(consumer.nacked.lock().len(), 2);
+    assert_eq!(consumer.acked.lock().len(), 1);
+    assert!(sink.received.lock().is_empty());
+}
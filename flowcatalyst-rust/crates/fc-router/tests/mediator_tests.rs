@@ -3,28 +3,40 @@
 //! Tests for:
 //! - Successful message delivery
 //! - HTTP status code handling
-//! - Circuit breaker behavior
-//! - Retry logic
+//! - Circuit breaker behavior, shared and per endpoint host
+//! - Retry logic, including per-message timeout and retry overrides and their bounds
+//! - Per-endpoint concurrency limits
+//! - Injected shared HTTP client
 //! - Custom delay parsing from response
 //! - Auth token handling
-
+//! - Webhook signing headers
+//! - Correlation ID forwarding
+//! - Target URL templating from the message payload
+//! - Raw base64 bodies, with invalid bodies and content types as config errors
+//! - CloudEvents structured-mode wrapping
+//! - Rejection of non-HTTP mediation types
+
+use std::sync::Arc;
 use std::time::Duration;
 use wiremock::{MockServer, Mock, ResponseTemplate};
-use wiremock::matchers::{method, path, header, body_json};
+use wiremock::matchers::{method, path, query_param, header, body_json, body_bytes};
 
 use fc_common::{Message, MediationType, MediationResult};
-use fc_router::{HttpMediator, HttpMediatorConfig, Mediator, CircuitState};
+use fc_common::http::HttpClientConfig;
+use fc_router::{
+    AuthScheme, CircuitBreakerRegistry, CircuitBreakerState, CircuitState, HttpMediator, HttpMediatorConfig, Mediator,
+    PayloadFormat, CORRELATION_ID_HEADER, MAX_MESSAGE_RETRIES,
+};
+use fc_secrets::{EncryptedProvider, Provider, SecretService, SecretsConfig};
 use chrono::Utc;
 
 fn create_test_message(target: &str) -> Message {
     Message {
         id: "msg-1".to_string(),
         pool_code: "DEFAULT".to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: target.to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
@@ -33,10 +45,9 @@ fn create_test_message_with_auth(target: &str, token: &str) -> Message {
         id: "msg-auth".to_string(),
         pool_code: "DEFAULT".to_string(),
         auth_token: Some(token.to_string()),
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: target.to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
@@ -102,6 +113,157 @@ async fn test_auth_token_sent() {
     assert_eq!(outcome.result, MediationResult::Success);
 }
 
+#[tokio::test]
+async fn test_correlation_id_forwarded() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .and(header(CORRELATION_ID_HEADER, "trace-42"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.correlation_id = Some("trace-42".to_string());
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test]
+async fn test_auth_token_sent_raw_scheme() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/secure-webhook"))
+        .and(header("Authorization", "Token test-token-123"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::with_config(HttpMediatorConfig {
+        auth_scheme: AuthScheme::Raw,
+        ..HttpMediatorConfig::dev()
+    });
+    let message = create_test_message_with_auth(
+        &format!("{}/secure-webhook", mock_server.uri()),
+        "Token test-token-123",
+    );
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+// EncryptedProvider loads its store with block_in_place, which needs the multi-threaded runtime
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auth_token_resolved_from_secret_reference() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/secure-webhook"))
+        .and(header("Authorization", "Bearer resolved-token"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let key = fc_secrets::generate_key();
+    EncryptedProvider::new(&key, &data_dir.path().to_path_buf()).unwrap()
+        .set("acme-webhook", "resolved-token").await.unwrap();
+    let secrets = SecretService::new(&SecretsConfig {
+        encryption_key: Some(key),
+        data_dir: data_dir.path().to_path_buf(),
+        ..SecretsConfig::default()
+    }).await.unwrap();
+
+    let mediator = HttpMediator::dev().with_secret_service(Arc::new(secrets));
+    let message = create_test_message_with_auth(
+        &format!("{}/secure-webhook", mock_server.uri()),
+        "encrypted:acme-webhook",
+    );
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unresolvable_auth_token_reference_not_sent() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let secrets = SecretService::new(&SecretsConfig {
+        encryption_key: Some(fc_secrets::generate_key()),
+        data_dir: data_dir.path().to_path_buf(),
+        ..SecretsConfig::default()
+    }).await.unwrap();
+
+    let mediator = HttpMediator::dev().with_secret_service(Arc::new(secrets));
+    let message = create_test_message_with_auth(
+        &format!("{}/secure-webhook", mock_server.uri()),
+        "encrypted:missing",
+    );
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorProcess);
+    assert_eq!(outcome.delay_seconds, Some(30));
+}
+
+#[tokio::test]
+async fn test_signing_headers_sent() {
+    use hmac::{Hmac, Mac};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.signing_secret = Some("whsec_test".to_string());
+
+    let outcome = mediator.mediate(&message).await;
+    assert_eq!(outcome.result, MediationResult::Success);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let request = &requests[0];
+    let header = |name: &str| {
+        request.headers.iter()
+            .find(|(key, _)| key.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, values)| values.last().as_str().to_string())
+    };
+
+    assert!(header("X-FLOWCATALYST-SIGNATURE").is_some());
+    assert!(header("X-FLOWCATALYST-TIMESTAMP").is_some());
+
+    // X-FC-Signature covers `timestamp + "." + body`
+    let timestamp = header("X-FC-Timestamp").unwrap();
+    assert!((Utc::now().timestamp() - timestamp.parse::<i64>().unwrap()).abs() < 60);
+
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"whsec_test").unwrap();
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(&request.body);
+    let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(header("X-FC-Signature").unwrap(), expected);
+}
+
 #[tokio::test]
 async fn test_ack_false_with_custom_delay() {
     let mock_server = MockServer::start().await;
@@ -369,6 +531,112 @@ async fn test_circuit_breaker_resets_on_success() {
     assert_eq!(mediator.circuit_state(), CircuitState::Closed);
 }
 
+#[tokio::test]
+async fn test_circuit_breakers_tracked_per_endpoint_host() {
+    let failing_server = MockServer::start().await;
+    let healthy_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&failing_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&healthy_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        max_retries: 1,
+        circuit_breaker_threshold: 3,
+        circuit_breaker_timeout: Duration::from_secs(60),
+        ..Default::default()
+    };
+    let registry = Arc::new(CircuitBreakerRegistry::new(config.circuit_breaker_config()));
+    let mediator = HttpMediator::with_config(config).with_circuit_breaker_registry(registry.clone());
+
+    let failing = create_test_message(&format!("{}/webhook", failing_server.uri()));
+    let healthy = create_test_message(&format!("{}/webhook", healthy_server.uri()));
+
+    for _ in 0..3 {
+        mediator.mediate(&failing).await;
+    }
+    let outcome = mediator.mediate(&failing).await;
+    assert!(outcome.error_message.as_ref().unwrap().contains("Circuit breaker"));
+
+    // The open breaker on one host doesn't block another
+    let outcome = mediator.mediate(&healthy).await;
+    assert_eq!(outcome.result, MediationResult::Success);
+
+    let failing_host = failing_server.uri().trim_start_matches("http://").to_string();
+    let healthy_host = healthy_server.uri().trim_start_matches("http://").to_string();
+    let stats = registry.get_all_stats();
+    assert_eq!(stats.len(), 2);
+
+    let failing_stats = &stats[&failing_host];
+    assert_eq!(failing_stats.state, CircuitBreakerState::Open);
+    assert_eq!(failing_stats.failed_calls, 3);
+    assert_eq!(failing_stats.rejected_calls, 1);
+    assert_eq!(failing_stats.failure_rate, 1.0);
+
+    let healthy_stats = &stats[&healthy_host];
+    assert_eq!(healthy_stats.state, CircuitBreakerState::Closed);
+    assert_eq!(healthy_stats.successful_calls, 1);
+    assert_eq!(healthy_stats.failure_rate, 0.0);
+}
+
+#[tokio::test]
+async fn test_saturated_endpoint_does_not_block_other_targets() {
+    let slow_server = MockServer::start().await;
+    let fast_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+        .mount(&slow_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&fast_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        max_retries: 1,
+        max_concurrent_per_endpoint: Some(1),
+        ..Default::default()
+    };
+    let mediator = Arc::new(HttpMediator::with_config(config));
+    let slow = create_test_message(&format!("{}/webhook", slow_server.uri()));
+    let fast = create_test_message(&format!("{}/webhook", fast_server.uri()));
+
+    // Occupy the slow endpoint's only permit
+    let in_flight = {
+        let mediator = mediator.clone();
+        let slow = slow.clone();
+        tokio::spawn(async move { mediator.mediate(&slow).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Further slow messages are deferred immediately for redelivery
+    let start = std::time::Instant::now();
+    let outcome = mediator.mediate(&slow).await;
+    assert_eq!(outcome.result, MediationResult::Deferred);
+    assert!(outcome.delay_seconds.is_some());
+    assert!(outcome.error_message.as_ref().unwrap().contains("concurrency limit"));
+
+    // The fast target is unaffected
+    let outcome = mediator.mediate(&fast).await;
+    assert_eq!(outcome.result, MediationResult::Success);
+    assert!(start.elapsed() < Duration::from_millis(300));
+
+    assert_eq!(in_flight.await.unwrap().result, MediationResult::Success);
+
+    // Permit is released once the in-flight delivery completes
+    let outcome = mediator.mediate(&slow).await;
+    assert_eq!(outcome.result, MediationResult::Success);
+
+    // Idle endpoints are no longer tracked
+    assert_eq!(mediator.tracked_endpoint_count(), 0);
+}
+
 #[tokio::test]
 async fn test_timeout_handling() {
     let mock_server = MockServer::start().await;
@@ -396,6 +664,140 @@ async fn test_timeout_handling() {
     assert!(outcome.error_message.as_ref().unwrap().contains("timeout"));
 }
 
+#[tokio::test]
+async fn test_message_timeout_overrides_config() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(3)))
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        timeout: Duration::from_secs(30),
+        max_retries: 1,
+        ..Default::default()
+    };
+    let mediator = HttpMediator::with_config(config);
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.timeout_seconds = Some(1);
+
+    let start = std::time::Instant::now();
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorConnection);
+    assert!(outcome.error_message.as_ref().unwrap().contains("timeout"));
+    assert!(start.elapsed() < Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn test_zero_message_timeout_uses_config_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(100)))
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        timeout: Duration::from_secs(30),
+        max_retries: 1,
+        ..Default::default()
+    };
+    let mediator = HttpMediator::with_config(config);
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.timeout_seconds = Some(0);
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test]
+async fn test_message_max_retries_capped() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(MAX_MESSAGE_RETRIES as u64)
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        retry_delays: vec![Duration::from_millis(1); MAX_MESSAGE_RETRIES as usize],
+        circuit_breaker_threshold: 100,
+        ..Default::default()
+    };
+    let mediator = HttpMediator::with_config(config);
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.max_retries = Some(u32::MAX);
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorProcess);
+}
+
+#[tokio::test]
+async fn test_message_max_retries_overrides_config() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        max_retries: 3,
+        retry_delays: vec![Duration::from_millis(10); 3],
+        ..Default::default()
+    };
+    let mediator = HttpMediator::with_config(config);
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.max_retries = Some(0);
+
+    // A single attempt; the transient failure is returned for the pool to NACK
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorProcess);
+    assert_eq!(outcome.status_code, Some(500));
+}
+
+#[tokio::test]
+async fn test_shared_client_timeout_honored() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(10))
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Mediator config keeps its long default timeout; the shared client's applies
+    let client = HttpClientConfig::default()
+        .with_timeout(Duration::from_millis(100))
+        .build_client()
+        .unwrap();
+    let config = HttpMediatorConfig {
+        max_retries: 1,
+        ..Default::default()
+    };
+    let mediator = HttpMediator::with_client(config, client);
+    let message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorConnection);
+    assert!(outcome.error_message.as_ref().unwrap().contains("timeout"));
+}
+
 #[tokio::test]
 async fn test_payload_sent_correctly() {
     let mock_server = MockServer::start().await;
@@ -492,3 +894,235 @@ async fn test_mediator_default_config() {
     let mediator = HttpMediator::new();
     assert_eq!(mediator.circuit_state(), CircuitState::Closed);
 }
+
+#[tokio::test]
+async fn test_target_placeholders_resolved_from_payload() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/tenants/acme/orders/ord%2042"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/tenants/{{tenant}}/orders/{{order.id}}", mock_server.uri()));
+    message.payload = Some(serde_json::json!({"tenant": "acme", "order": {"id": "ord 42"}}));
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test]
+async fn test_missing_target_placeholder_is_config_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/orders/{{orderId}}", mock_server.uri()));
+    message.payload = Some(serde_json::json!({"customerId": "cust-1"}));
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorConfig);
+    assert!(outcome.error_message.as_ref().unwrap().contains("{orderId}"));
+}
+
+#[tokio::test]
+async fn test_braces_that_are_not_placeholders_kept_in_target() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/orders/ord-1"))
+        .and(query_param("filter", r#"{"status":"open"}"#))
+        .and(query_param("empty", "{}"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!(
+        r#"{}/orders/{{order.id}}?filter={{"status":"open"}}&empty={{}}"#,
+        mock_server.uri()
+    ));
+    message.payload = Some(serde_json::json!({"order": {"id": "ord-1"}}));
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test]
+async fn test_raw_body_sent_verbatim() {
+    let mock_server = MockServer::start().await;
+    let raw: Vec<u8> = vec![0x00, 0xFF, 0x10, 0x80, b'o', b'k'];
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .and(header("Content-Type", "application/x-protobuf"))
+        .and(body_bytes(raw.clone()))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.raw_body_base64 = Some("AP8QgG9r".to_string());
+    message.content_type = Some("application/x-protobuf".to_string());
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test]
+async fn test_cloudevents_payload_format_wraps_and_signs_final_body() {
+    use hmac::{Hmac, Mac};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .and(header("Content-Type", "application/cloudevents+json"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::with_config(HttpMediatorConfig {
+        payload_format: PayloadFormat::CloudEvents,
+        cloudevents_source: "/flowcatalyst/test".to_string(),
+        ..HttpMediatorConfig::dev()
+    });
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.signing_secret = Some("whsec_test".to_string());
+
+    let outcome = mediator.mediate(&message).await;
+    assert_eq!(outcome.result, MediationResult::Success);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let request = &requests[0];
+    let event: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(event["specversion"], "1.0");
+    assert_eq!(event["id"], "msg-1");
+    assert_eq!(event["source"], "/flowcatalyst/test");
+    assert_eq!(event["type"], "flowcatalyst.message");
+    assert_eq!(event["datacontenttype"], "application/json");
+    assert!(event["time"].is_string());
+    assert_eq!(event["data"], serde_json::json!({"messageId": "msg-1"}));
+
+    // The signature covers the wrapped body as sent
+    let header = |name: &str| {
+        request.headers.iter()
+            .find(|(key, _)| key.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, values)| values.last().as_str().to_string())
+            .unwrap()
+    };
+    let timestamp = header("X-FC-Timestamp");
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"whsec_test").unwrap();
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(&request.body);
+    assert_eq!(header("X-FC-Signature"), format!("sha256={}", hex::encode(mac.finalize().into_bytes())));
+}
+
+#[tokio::test]
+async fn test_raw_body_defaults_to_octet_stream() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .and(header("Content-Type", "application/octet-stream"))
+        .and(body_bytes(b"hello".to_vec()))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.raw_body_base64 = Some("aGVsbG8=".to_string());
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::Success);
+}
+
+#[tokio::test]
+async fn test_invalid_raw_body_is_config_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.raw_body_base64 = Some("not base64!".to_string());
+
+    let outcome = mediator.mediate(&message).await;
+
+    assert_eq!(outcome.result, MediationResult::ErrorConfig);
+}
+
+#[tokio::test]
+async fn test_invalid_content_type_is_config_error_without_tripping_breaker() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpMediatorConfig {
+        max_retries: 1,
+        circuit_breaker_threshold: 2,
+        circuit_breaker_timeout: Duration::from_secs(60),
+        ..Default::default()
+    };
+    let mediator = HttpMediator::with_config(config);
+    let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+    message.raw_body_base64 = Some("aGVsbG8=".to_string());
+    message.content_type = Some("text/plain\r\nX-Injected: 1".to_string());
+
+    for _ in 0..3 {
+        let outcome = mediator.mediate(&message).await;
+        assert_eq!(outcome.result, MediationResult::ErrorConfig);
+    }
+
+    assert_eq!(mediator.circuit_state(), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn test_non_http_mediation_type_rejected() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mediator = HttpMediator::new();
+
+    for mediation_type in [MediationType::GRPC, MediationType::SQS] {
+        let mut message = create_test_message(&format!("{}/webhook", mock_server.uri()));
+        message.mediation_type = mediation_type;
+
+        let outcome = mediator.mediate(&message).await;
+
+        assert_eq!(outcome.result, MediationResult::ErrorConfig);
+        assert!(outcome.error_message.unwrap().contains("Unsupported mediation type"));
+    }
+}
@@ -5,8 +5,12 @@
 //! - Concurrent message processing
 //! - Rate limiting behavior
 //! - Message group ordering (FIFO)
-//! - Capacity management
+//! - Capacity management, and rejections not counting as failed deliveries
 //! - Shutdown behavior
+//! - Mediator selection by mediation type
+//! - Dead-lettering of permanently failed messages
+//! - ACK vs NACK of 4xx responses (`ack_on_4xx`)
+//! - Restart of panicked message group workers
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -16,9 +20,12 @@ use async_trait::async_trait;
 
 use fc_common::{
     Message, BatchMessage, AckNack, PoolConfig, MediationType,
-    MediationResult, MediationOutcome,
+    MediationResult, MediationOutcome, WarningCategory,
+};
+use fc_router::{
+    ProcessPool, Mediator, DeadLetter, DeadLetterSink, RouterError,
+    WarningService, WarningServiceConfig,
 };
-use fc_router::{ProcessPool, Mediator};
 
 /// Mock mediator that tracks calls and can simulate delays/failures
 struct MockMediator {
@@ -93,11 +100,10 @@ fn create_test_message(id: &str, group_id: Option<&str>) -> Message {
     Message {
         id: id.to_string(),
         pool_code: "TEST".to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: "http://localhost:8080/test".to_string(),
         message_group_id: group_id.map(|s| s.to_string()),
+        ..Default::default()
     }
 }
 
@@ -119,7 +125,7 @@ async fn test_pool_creation() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 5,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = ProcessPool::new(config, mediator);
@@ -135,6 +141,7 @@ async fn test_pool_with_rate_limit() {
         code: "RATE_LIMITED".to_string(),
         concurrency: 10,
         rate_limit_per_minute: Some(100),
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = ProcessPool::new(config, mediator);
@@ -147,7 +154,7 @@ async fn test_single_message_processing() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 5,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = Arc::new(ProcessPool::new(config, mediator.clone()));
@@ -171,7 +178,7 @@ async fn test_multiple_messages_concurrent() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 10,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::with_delay(50));
     let pool = Arc::new(ProcessPool::new(config, mediator.clone()));
@@ -201,7 +208,7 @@ async fn test_message_group_fifo_ordering() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 1, // Force sequential processing per group
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::with_delay(10));
     let pool = Arc::new(ProcessPool::new(config, mediator.clone()));
@@ -235,7 +242,7 @@ async fn test_different_groups_parallel() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 10,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::with_delay(50));
     let pool = Arc::new(ProcessPool::new(config, mediator.clone()));
@@ -271,7 +278,7 @@ async fn test_failed_message_nack() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 5,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::failing());
     let pool = Arc::new(ProcessPool::new(config, mediator.clone()));
@@ -284,8 +291,33 @@ async fn test_failed_message_nack() {
     let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
     assert!(result.is_ok());
 
+    // Processing failures count toward the queue's delivery attempts
     let ack_nack = result.unwrap().unwrap();
-    assert!(matches!(ack_nack, AckNack::Nack { .. }));
+    assert!(matches!(ack_nack, AckNack::Failed { delay_seconds: Some(1) }));
+}
+
+#[tokio::test]
+async fn test_full_pool_nack_is_not_a_failed_delivery() {
+    let config = PoolConfig {
+        code: "TEST".to_string(),
+        concurrency: 1,
+        ..Default::default()
+    };
+    let pool = Arc::new(ProcessPool::new(config, Arc::new(MockMediator::with_delay(10_000))));
+    pool.start().await;
+
+    // Fill the queue behind the one message being processed
+    let mut receivers = Vec::new();
+    while pool.available_capacity() > 0 {
+        let (batch_msg, rx) = create_batch_message(&format!("fill-{}", receivers.len()), None);
+        pool.submit(batch_msg).await.unwrap();
+        receivers.push(rx);
+    }
+
+    let (batch_msg, rx) = create_batch_message("rejected", None);
+    pool.submit(batch_msg).await.unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(1), rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Nack { delay_seconds: Some(5) }));
 }
 
 #[tokio::test]
@@ -293,7 +325,7 @@ async fn test_pool_capacity() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 2,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = Arc::new(ProcessPool::new(config, mediator));
@@ -311,6 +343,7 @@ async fn test_pool_stats() {
         code: "STATS_TEST".to_string(),
         concurrency: 10,
         rate_limit_per_minute: Some(500),
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = Arc::new(ProcessPool::new(config, mediator));
@@ -330,7 +363,7 @@ async fn test_pool_shutdown() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 5,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = Arc::new(ProcessPool::new(config, mediator));
@@ -353,7 +386,7 @@ async fn test_pool_fully_drained() {
     let config = PoolConfig {
         code: "TEST".to_string(),
         concurrency: 5,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     let mediator = Arc::new(MockMediator::new());
     let pool = Arc::new(ProcessPool::new(config, mediator));
@@ -366,3 +399,226 @@ async fn test_pool_fully_drained() {
     pool.drain().await;
     pool.shutdown().await;
 }
+
+#[tokio::test]
+async fn test_mediator_selected_by_mediation_type() {
+    let config = PoolConfig {
+        code: "TEST".to_string(),
+        concurrency: 5,
+        ..Default::default()
+    };
+    let http_mediator = Arc::new(MockMediator::new());
+    let grpc_mediator = Arc::new(MockMediator::new());
+    let pool = Arc::new(
+        ProcessPool::new(config, http_mediator.clone())
+            .with_mediator(MediationType::GRPC, grpc_mediator.clone()),
+    );
+
+    pool.start().await;
+
+    let (http_msg, http_rx) = create_batch_message("msg-http", None);
+    let (mut grpc_msg, grpc_rx) = create_batch_message("msg-grpc", None);
+    grpc_msg.message.mediation_type = MediationType::GRPC;
+    let (mut sqs_msg, sqs_rx) = create_batch_message("msg-sqs", None);
+    sqs_msg.message.mediation_type = MediationType::SQS;
+
+    pool.submit(http_msg).await.unwrap();
+    pool.submit(grpc_msg).await.unwrap();
+    pool.submit(sqs_msg).await.unwrap();
+
+    for rx in [http_rx, grpc_rx, sqs_rx] {
+        let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
+        assert!(matches!(result.unwrap().unwrap(), AckNack::Ack));
+    }
+
+    // SQS has no dedicated mediator, so it falls back to the default
+    let mut default_ids = http_mediator.processed_ids();
+    default_ids.sort();
+    assert_eq!(default_ids, vec!["msg-http", "msg-sqs"]);
+    assert_eq!(grpc_mediator.processed_ids(), vec!["msg-grpc"]);
+}
+
+/// Mediator that rejects every message as a configuration error
+struct ConfigErrorMediator;
+
+#[async_trait]
+impl Mediator for ConfigErrorMediator {
+    async fn mediate(&self, _message: &Message) -> MediationOutcome {
+        MediationOutcome::error_config(400, "HTTP 400: Bad request".to_string())
+    }
+}
+
+/// Dead-letter sink that records what it receives, or rejects everything
+#[derive(Default)]
+struct RecordingSink {
+    should_fail: bool,
+    received: parking_lot::Mutex<Vec<DeadLetter>>,
+}
+
+#[async_trait]
+impl DeadLetterSink for RecordingSink {
+    async fn send(&self, dead_letter: DeadLetter) -> fc_router::Result<()> {
+        if self.should_fail {
+            return Err(RouterError::Queue("dead-letter queue unavailable".to_string()));
+        }
+        self.received.lock().push(dead_letter);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_config_error_sent_to_dead_letter_sink() {
+    let config = PoolConfig {
+        code: "TEST".to_string(),
+        concurrency: 5,
+        ..Default::default()
+    };
+    let sink = Arc::new(RecordingSink::default());
+    let pool = Arc::new(
+        ProcessPool::new(config, Arc::new(ConfigErrorMediator))
+            .with_dead_letter_sink(sink.clone()),
+    );
+
+    pool.start().await;
+
+    let (batch_msg, rx) = create_batch_message("msg-1", None);
+    pool.submit(batch_msg).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Ack));
+
+    let received = sink.received.lock();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].message.id, "msg-1");
+    assert_eq!(received[0].pool_code, "TEST");
+    assert_eq!(received[0].reason, "HTTP 400: Bad request");
+    assert_eq!(received[0].status_code, Some(400));
+}
+
+#[tokio::test]
+async fn test_dead_letter_sink_failure_nacks() {
+    let config = PoolConfig {
+        code: "TEST".to_string(),
+        concurrency: 5,
+        ..Default::default()
+    };
+    let sink = Arc::new(RecordingSink { should_fail: true, ..Default::default() });
+    let pool = Arc::new(
+        ProcessPool::new(config, Arc::new(ConfigErrorMediator))
+            .with_dead_letter_sink(sink),
+    );
+
+    pool.start().await;
+
+    let (batch_msg, rx) = create_batch_message("msg-1", None);
+    pool.submit(batch_msg).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Nack { delay_seconds: Some(30) }));
+}
+
+fn ack_on_4xx_config(ack_on_4xx: bool) -> PoolConfig {
+    PoolConfig {
+        code: "TEST".to_string(),
+        concurrency: 5,
+        ack_on_4xx,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_client_error_acked_by_default() {
+    let sink = Arc::new(RecordingSink::default());
+    let pool = Arc::new(
+        ProcessPool::new(ack_on_4xx_config(true), Arc::new(ConfigErrorMediator))
+            .with_dead_letter_sink(sink.clone()),
+    );
+    assert!(pool.ack_on_4xx());
+
+    pool.start().await;
+
+    let (batch_msg, rx) = create_batch_message("msg-1", None);
+    pool.submit(batch_msg).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Ack));
+    assert_eq!(sink.received.lock().len(), 1);
+}
+
+#[tokio::test]
+async fn test_client_error_nacked_when_ack_on_4xx_disabled() {
+    let sink = Arc::new(RecordingSink::default());
+    let pool = Arc::new(
+        ProcessPool::new(ack_on_4xx_config(false), Arc::new(ConfigErrorMediator))
+            .with_dead_letter_sink(sink.clone()),
+    );
+    assert!(!pool.get_stats().ack_on_4xx);
+
+    pool.start().await;
+
+    let (batch_msg, rx) = create_batch_message("msg-1", None);
+    pool.submit(batch_msg).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Nack { delay_seconds: Some(30) }));
+    // Retried, not dead-lettered
+    assert!(sink.received.lock().is_empty());
+
+    // Re-enabling applies to the next message
+    pool.update_ack_on_4xx(true);
+    let (batch_msg, rx) = create_batch_message("msg-2", None);
+    pool.submit(batch_msg).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Ack));
+}
+
+/// Mediator that panics on messages with id "boom"
+struct PanickingMediator;
+
+#[async_trait]
+impl Mediator for PanickingMediator {
+    async fn mediate(&self, message: &Message) -> MediationOutcome {
+        if message.id == "boom" {
+            panic!("injected group worker panic");
+        }
+        MediationOutcome::success()
+    }
+}
+
+#[tokio::test]
+async fn test_group_worker_panic_restarts_and_raises_warning() {
+    let warning_service = Arc::new(WarningService::new(WarningServiceConfig::default()));
+    let pool = Arc::new(
+        ProcessPool::new(ack_on_4xx_config(true), Arc::new(PanickingMediator))
+            .with_warning_service(warning_service.clone()),
+    );
+
+    pool.start().await;
+
+    let (boom_msg, boom_rx) = create_batch_message("boom", Some("group-a"));
+    let (after_msg, after_rx) = create_batch_message("after", Some("group-a"));
+    pool.submit(boom_msg).await.unwrap();
+    pool.submit(after_msg).await.unwrap();
+
+    // The panicking message is never ACKed, and the message queued behind it is NACKed
+    // so the group stays in order
+    let result = tokio::time::timeout(Duration::from_secs(5), after_rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Nack { .. }));
+    assert!(boom_rx.await.is_err());
+
+    // The restarted worker serves the group's next batch
+    let (mut next_msg, next_rx) = create_batch_message("next", Some("group-a"));
+    next_msg.batch_id = Some("batch-2".to_string());
+    pool.submit(next_msg).await.unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(5), next_rx).await;
+    assert!(matches!(result.unwrap().unwrap(), AckNack::Ack));
+
+    assert_eq!(pool.get_stats().group_thread_restarts, 1);
+    assert_eq!(pool.active_workers(), 0);
+
+    let warnings = warning_service.get_warnings_by_category(WarningCategory::GroupThreadRestart);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("group-a"));
+    assert!(warnings[0].message.contains("restart 1"));
+}
@@ -5,6 +5,7 @@
 //! - Rate limit enforcement
 //! - Rate limit updates via hot reload
 //! - Multiple pools with different rate limits
+//! - Burst size (token bucket capacity)
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, AtomicBool, Ordering};
@@ -13,6 +14,7 @@ use async_trait::async_trait;
 
 use fc_common::{
     Message, QueuedMessage, MediationType, MediationOutcome, PoolConfig, RouterConfig,
+    RateLimitScope,
 };
 use fc_queue::{QueueConsumer, QueueError};
 use fc_router::{QueueManager, Mediator};
@@ -112,11 +114,9 @@ fn create_test_message(id: &str, pool_code: &str) -> Message {
     Message {
         id: id.to_string(),
         pool_code: pool_code.to_string(),
-        auth_token: None,
-        signing_secret: None,
         mediation_type: MediationType::HTTP,
         mediation_target: "http://localhost:8080/test".to_string(),
-        message_group_id: None,
+        ..Default::default()
     }
 }
 
@@ -139,6 +139,7 @@ async fn test_pool_without_rate_limit() {
             code: "DEFAULT".to_string(),
             concurrency: 10,
             rate_limit_per_minute: None, // No rate limit
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -183,6 +184,7 @@ async fn test_pool_with_rate_limit() {
             code: "RATE_LIMITED".to_string(),
             concurrency: 10,
             rate_limit_per_minute: Some(60), // 1 per second
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -222,11 +224,13 @@ async fn test_multiple_pools_different_rates() {
                 code: "FAST".to_string(),
                 concurrency: 10,
                 rate_limit_per_minute: None, // No limit
+                ..Default::default()
             },
             PoolConfig {
                 code: "SLOW".to_string(),
                 concurrency: 10,
                 rate_limit_per_minute: Some(60), // 1 per second
+                ..Default::default()
             },
         ],
         queues: vec![],
@@ -260,7 +264,7 @@ async fn test_rate_limit_hot_reload() {
         processing_pools: vec![PoolConfig {
             code: "DYNAMIC".to_string(),
             concurrency: 10,
-            rate_limit_per_minute: None,
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -276,6 +280,7 @@ async fn test_rate_limit_hot_reload() {
         code: "DYNAMIC".to_string(),
         concurrency: 10,
         rate_limit_per_minute: Some(600), // 10 per second
+        ..Default::default()
     };
     manager.update_pool_config("DYNAMIC", new_config).await.unwrap();
 
@@ -295,6 +300,7 @@ async fn test_rate_limit_stats() {
             code: "TEST".to_string(),
             concurrency: 5,
             rate_limit_per_minute: Some(300),
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -318,6 +324,7 @@ async fn test_high_rate_limit() {
             code: "HIGH_RATE".to_string(),
             concurrency: 20,
             rate_limit_per_minute: Some(6000), // 100 per second
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -360,6 +367,7 @@ async fn test_rate_limit_combined_with_concurrency() {
             code: "LIMITED".to_string(),
             concurrency: 2, // Only 2 concurrent workers
             rate_limit_per_minute: Some(120), // 2 per second
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -387,9 +395,9 @@ async fn test_pool_codes_with_rate_limits() {
 
     let config = RouterConfig {
         processing_pools: vec![
-            PoolConfig { code: "A".to_string(), concurrency: 5, rate_limit_per_minute: Some(100) },
-            PoolConfig { code: "B".to_string(), concurrency: 5, rate_limit_per_minute: Some(200) },
-            PoolConfig { code: "C".to_string(), concurrency: 5, rate_limit_per_minute: None },
+            PoolConfig { code: "A".to_string(), concurrency: 5, rate_limit_per_minute: Some(100), ..Default::default() },
+            PoolConfig { code: "B".to_string(), concurrency: 5, rate_limit_per_minute: Some(200), ..Default::default() },
+            PoolConfig { code: "C".to_string(), concurrency: 5, ..Default::default() },
         ],
         queues: vec![],
     };
@@ -413,6 +421,7 @@ async fn test_remove_rate_limit() {
             code: "REMOVE_LIMIT".to_string(),
             concurrency: 10,
             rate_limit_per_minute: Some(60),
+            ..Default::default()
         }],
         queues: vec![],
     };
@@ -427,7 +436,7 @@ async fn test_remove_rate_limit() {
     let new_config = PoolConfig {
         code: "REMOVE_LIMIT".to_string(),
         concurrency: 10,
-        rate_limit_per_minute: None,
+        ..Default::default()
     };
     manager.update_pool_config("REMOVE_LIMIT", new_config).await.unwrap();
 
@@ -436,3 +445,171 @@ async fn test_remove_rate_limit() {
     let pool_stats = stats.iter().find(|s| s.pool_code == "REMOVE_LIMIT").unwrap();
     assert_eq!(pool_stats.rate_limit_per_minute, None);
 }
+
+/// Mediator that counts calls per message group
+struct GroupCountingMediator {
+    counts: parking_lot::Mutex<std::collections::HashMap<String, u32>>,
+}
+
+impl GroupCountingMediator {
+    fn new() -> Self {
+        Self {
+            counts: parking_lot::Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+
+    fn count(&self, group: &str) -> u32 {
+        self.counts.lock().get(group).copied().unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl Mediator for GroupCountingMediator {
+    async fn mediate(&self, message: &Message) -> MediationOutcome {
+        let group = message.message_group_id.clone().unwrap_or_default();
+        *self.counts.lock().entry(group).or_insert(0) += 1;
+        MediationOutcome::success()
+    }
+}
+
+fn create_grouped_message(id: &str, pool_code: &str, group: &str) -> QueuedMessage {
+    let mut queued = create_queued_message(id, pool_code);
+    queued.message.message_group_id = Some(group.to_string());
+    queued
+}
+
+/// Route a hot group (10 messages) and three quiet groups (1 message each)
+/// through a pool allowing a burst of 2 per minute.
+async fn route_hot_and_quiet_groups(scope: RateLimitScope) -> Arc<GroupCountingMediator> {
+    let mediator = Arc::new(GroupCountingMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    let config = RouterConfig {
+        processing_pools: vec![PoolConfig {
+            code: "SCOPED".to_string(),
+            concurrency: 10,
+            rate_limit_per_minute: Some(2),
+            rate_limit_scope: scope,
+            ..Default::default()
+        }],
+        queues: vec![],
+    };
+    manager.apply_config(config).await.unwrap();
+
+    let consumer = Arc::new(TestQueueConsumer::new("test-queue"));
+    for i in 0..10 {
+        consumer.add_message(create_grouped_message(&format!("hot-{}", i), "SCOPED", "hot"));
+    }
+    for group in ["quiet-1", "quiet-2", "quiet-3"] {
+        consumer.add_message(create_grouped_message(&format!("{}-0", group), "SCOPED", group));
+    }
+
+    let poll_result = consumer.poll(20).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    mediator
+}
+
+#[tokio::test]
+async fn test_group_scope_throttles_hot_group_only() {
+    let mediator = route_hot_and_quiet_groups(RateLimitScope::Group).await;
+
+    // Hot group is held to its own burst of 2...
+    assert_eq!(mediator.count("hot"), 2);
+
+    // ...while every other group still gets through
+    assert_eq!(mediator.count("quiet-1"), 1);
+    assert_eq!(mediator.count("quiet-2"), 1);
+    assert_eq!(mediator.count("quiet-3"), 1);
+}
+
+#[tokio::test]
+async fn test_pool_scope_shares_budget_across_groups() {
+    let mediator = route_hot_and_quiet_groups(RateLimitScope::Pool).await;
+
+    // Whole pool shares a burst of 2, so most messages are still waiting
+    let total = mediator.count("hot")
+        + mediator.count("quiet-1")
+        + mediator.count("quiet-2")
+        + mediator.count("quiet-3");
+    assert!(total <= 2, "Expected pool-wide limit of 2, processed {}", total);
+}
+
+#[tokio::test]
+async fn test_rate_limit_scope_in_pool_stats() {
+    let mediator = Arc::new(TimingMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    let config = RouterConfig {
+        processing_pools: vec![PoolConfig {
+            code: "SCOPED".to_string(),
+            concurrency: 5,
+            rate_limit_per_minute: Some(100),
+            rate_limit_scope: RateLimitScope::Group,
+            ..Default::default()
+        }],
+        queues: vec![],
+    };
+    manager.apply_config(config.clone()).await.unwrap();
+
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats[0].rate_limit_scope, RateLimitScope::Group);
+    assert!(!stats[0].is_rate_limited);
+
+    // Hot reload back to pool scope
+    let mut reloaded = config;
+    reloaded.processing_pools[0].rate_limit_scope = RateLimitScope::Pool;
+    manager.reload_config(reloaded).await.unwrap();
+
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats[0].rate_limit_scope, RateLimitScope::Pool);
+}
+
+#[tokio::test]
+async fn test_burst_passes_then_steady_rate_enforced() {
+    let mediator = Arc::new(TimingMediator::new());
+    let manager = Arc::new(QueueManager::new(mediator.clone()));
+
+    // 1 per second, but up to 3 at once
+    let config = RouterConfig {
+        processing_pools: vec![PoolConfig {
+            code: "BURSTY".to_string(),
+            concurrency: 10,
+            rate_limit_per_minute: Some(60),
+            rate_limit_burst: Some(3),
+            ..Default::default()
+        }],
+        queues: vec![],
+    };
+    manager.apply_config(config).await.unwrap();
+
+    let consumer = Arc::new(TestQueueConsumer::new("test-queue"));
+    for i in 0..6 {
+        consumer.add_message(create_queued_message(&format!("msg-{}", i), "BURSTY"));
+    }
+    let start = Instant::now();
+    let poll_result = consumer.poll(10).await.unwrap();
+    manager.route_batch(poll_result, consumer.clone()).await.unwrap();
+
+    // The full bucket goes out immediately
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(mediator.call_count(), 3);
+
+    // Then one more per second as the bucket refills
+    tokio::time::sleep(Duration::from_millis(2000)).await;
+    let refilled = start.elapsed().as_secs() as u32;
+    let processed = mediator.call_count();
+    assert!(
+        (4..=3 + refilled).contains(&processed),
+        "Expected steady-state pacing after the burst, got {} in {}s", processed, refilled
+    );
+
+    let calls = mediator.call_times.lock().clone();
+    for pair in calls[3..].windows(2) {
+        assert!(pair[1] - pair[0] >= Duration::from_millis(900), "Post-burst calls should be ~1s apart");
+    }
+
+    let stats = manager.get_pool_stats();
+    assert_eq!(stats[0].rate_limit_burst, Some(3));
+}
@@ -47,11 +47,17 @@ impl JobDispatcher {
         let collection: Collection<bson::Document> = self.db.collection("dispatch_jobs");
 
         let filter = doc! { "_id": job_id };
-        let Some(doc) = collection.find_one(filter.clone()).await? else {
+        let Some(doc) = collection.find_one(filter).await? else {
             warn!(job_id = %job_id, "Job not found");
             return Ok(false);
         };
 
+        // Cancelled or already dispatched jobs must not be published
+        if doc.get_str("status").unwrap_or("PENDING") != "PENDING" {
+            debug!(job_id = %job_id, status = doc.get_str("status").unwrap_or_default(), "Job not pending, skipping");
+            return Ok(false);
+        }
+
         let message_group = doc.get_str("messageGroup").ok().map(|s| s.to_string());
         let dispatch_pool_id = doc.get_str("dispatchPoolId").ok().map(|s| s.to_string());
 
@@ -94,7 +100,8 @@ impl JobDispatcher {
                         "updatedAt": bson::DateTime::now()
                     }
                 };
-                collection.update_one(filter, update).await?;
+                let pending = doc! { "_id": job_id, "status": "PENDING" };
+                collection.update_one(pending, update).await?;
                 debug!(job_id = %job_id, "Job dispatched successfully");
                 metrics::counter!("scheduler.jobs.queued_total").increment(1);
                 Ok(true)
@@ -68,7 +68,7 @@ impl From<&str> for DispatchMode {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DispatchStatus {
-    Pending, Queued, Processing, Completed, Error,
+    Pending, Queued, Processing, Completed, Error, Cancelled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
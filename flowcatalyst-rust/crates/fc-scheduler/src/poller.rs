@@ -109,6 +109,7 @@ impl PendingJobPoller {
             "PROCESSING" => DispatchStatus::Processing,
             "COMPLETED" => DispatchStatus::Completed,
             "ERROR" => DispatchStatus::Error,
+            "CANCELLED" => DispatchStatus::Cancelled,
             _ => DispatchStatus::Pending,
         };
 
@@ -177,25 +178,40 @@ impl PendingJobPoller {
                 body: serde_json::to_string(&pointer)?,
             };
 
+            // Claim the job before publishing, so one cancelled since it was polled
+            // is skipped and one cancelled from here on is no longer PENDING
+            let queued_at = bson::DateTime::now();
+            let claim = doc! {
+                "$set": { "status": "QUEUED", "queuedAt": queued_at, "updatedAt": queued_at }
+            };
+            let claimed = collection
+                .update_one(doc! { "_id": &job.id, "status": "PENDING" }, claim)
+                .await?;
+            if claimed.modified_count == 0 {
+                debug!(job_id = %job.id, "Job no longer pending, skipping");
+                continue;
+            }
+
             metrics::counter!("scheduler.jobs.dispatched_total").increment(1);
 
             match self.queue_publisher.publish(message).await {
                 Ok(_) => {
-                    let filter = doc! { "_id": &job.id };
-                    let update = doc! {
-                        "$set": {
-                            "status": "QUEUED",
-                            "queuedAt": bson::DateTime::now(),
-                            "updatedAt": bson::DateTime::now()
-                        }
-                    };
-                    collection.update_one(filter, update).await?;
                     debug!(job_id = %job.id, "Job dispatched");
                     metrics::counter!("scheduler.jobs.queued_total").increment(1);
                 }
                 Err(e) => {
                     warn!(job_id = %job.id, error = %e, "Failed to dispatch job");
                     metrics::counter!("scheduler.jobs.dispatch_errors_total").increment(1);
+
+                    // Release the claim so the next poll retries it; if this fails too,
+                    // stale recovery resets the job once the claim is old enough
+                    let release = doc! {
+                        "$set": { "status": "PENDING", "updatedAt": bson::DateTime::now() },
+                        "$unset": { "queuedAt": "" }
+                    };
+                    collection
+                        .update_one(doc! { "_id": &job.id, "status": "QUEUED", "queuedAt": queued_at }, release)
+                        .await?;
                 }
             }
         }
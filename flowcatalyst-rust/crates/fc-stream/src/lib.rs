@@ -28,7 +28,7 @@ pub use batch_dispatcher::{
     DispatchTarget, DispatchError, InMemoryDispatchTarget, MongoDispatchTarget,
 };
 pub use projection::{
-    EventReadProjection, DispatchJobReadProjection, ProjectionBuilder,
+    EventReadProjection, DispatchJobReadProjection, ProjectionBuilder, ProjectionBuildResult,
     ProjectionLookup, ProjectionStore, EventData, DispatchJobData,
     MongoProjectionStore, InMemoryProjectionStore, InMemoryLookup,
     BatchWriteResult, ChangeOperationType, ProjectionMapResult,
@@ -2,7 +2,10 @@
 //!
 //! Creates denormalized read projections for events and dispatch jobs.
 
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use bson::serde_helpers::chrono_datetime_as_bson_datetime;
@@ -109,7 +112,9 @@ pub trait ProjectionStore: Send + Sync {
     async fn save_event_projection(&self, projection: &EventReadProjection) -> Result<(), String>;
     async fn save_dispatch_job_projection(&self, projection: &DispatchJobReadProjection) -> Result<(), String>;
     async fn update_dispatch_job_projection(&self, projection: &DispatchJobReadProjection) -> Result<(), String>;
-    async fn increment_event_dispatch_count(&self, event_id: &str) -> Result<(), String>;
+    /// Count `dispatch_job_id` towards the event's dispatch job count.
+    /// Counting the same job again (e.g. on replay) is a no-op.
+    async fn increment_event_dispatch_count(&self, event_id: &str, dispatch_job_id: &str) -> Result<(), String>;
 
     /// Batch save event projections with idempotency
     async fn save_event_projections_batch(&self, projections: &[EventReadProjection]) -> BatchWriteResult {
@@ -196,11 +201,118 @@ pub struct DispatchJobData {
 pub struct ProjectionBuilder {
     lookup: Arc<dyn ProjectionLookup>,
     store: Arc<dyn ProjectionStore>,
+    /// Maximum projections built at once by the batch methods
+    concurrency: usize,
+}
+
+/// Results of [`ProjectionBuilder::build_projections`], in input order
+#[derive(Debug)]
+pub struct ProjectionBuildResult {
+    pub events: Vec<Result<EventReadProjection, String>>,
+    pub dispatch_jobs: Vec<Result<DispatchJobReadProjection, String>>,
 }
 
 impl ProjectionBuilder {
     pub fn new(lookup: Arc<dyn ProjectionLookup>, store: Arc<dyn ProjectionStore>) -> Self {
-        Self { lookup, store }
+        Self { lookup, store, concurrency: 1 }
+    }
+
+    /// Build up to `concurrency` projections at once in the batch methods (default 1)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Backfill projections for a batch of events and their dispatch jobs.
+    ///
+    /// All event projections are built before any dispatch job, so each job's
+    /// dispatch count increment lands on an existing event projection.
+    pub async fn build_projections(
+        &self,
+        events: &[EventData],
+        jobs: &[DispatchJobData],
+    ) -> ProjectionBuildResult {
+        let events = self.create_event_projections(events).await;
+        let dispatch_jobs = self.create_dispatch_job_projections(jobs).await;
+        ProjectionBuildResult { events, dispatch_jobs }
+    }
+
+    /// Create projections for a batch of events, up to `concurrency` at a time
+    pub async fn create_event_projections(
+        &self,
+        events: &[EventData],
+    ) -> Vec<Result<EventReadProjection, String>> {
+        self.build_per_event(events, |event| &event.id, |event| self.create_event_projection(event))
+            .await
+    }
+
+    /// Create projections for a batch of dispatch jobs, up to `concurrency` at a time.
+    /// Jobs of the same event are built one after another, in input order.
+    pub async fn create_dispatch_job_projections(
+        &self,
+        jobs: &[DispatchJobData],
+    ) -> Vec<Result<DispatchJobReadProjection, String>> {
+        self.build_per_event(jobs, |job| &job.event_id, |job| self.create_dispatch_job_projection(job))
+            .await
+    }
+
+    /// Update projections for a batch of dispatch jobs, up to `concurrency` at a time.
+    /// Updates for jobs of the same event are applied in input order, so the
+    /// last update to a job wins as it would serially.
+    pub async fn update_dispatch_job_projections(
+        &self,
+        jobs: &[DispatchJobData],
+    ) -> Vec<Result<DispatchJobReadProjection, String>> {
+        self.build_per_event(jobs, |job| &job.event_id, |job| self.update_dispatch_job_projection(job))
+            .await
+    }
+
+    /// Run `build` over `items` with bounded concurrency. Items with the same
+    /// event ID form a chain that runs sequentially; chains run in parallel.
+    /// Results are returned in input order.
+    async fn build_per_event<'a, T, R, K, F, Fut>(
+        &self,
+        items: &'a [T],
+        event_id: K,
+        build: F,
+    ) -> Vec<Result<R, String>>
+    where
+        K: Fn(&'a T) -> &'a str,
+        F: Fn(&'a T) -> Fut,
+        Fut: Future<Output = Result<R, String>>,
+    {
+        let mut chains: Vec<Vec<usize>> = Vec::new();
+        let mut chain_by_event: HashMap<&str, usize> = HashMap::new();
+        for (index, item) in items.iter().enumerate() {
+            let chain = *chain_by_event.entry(event_id(item)).or_insert_with(|| {
+                chains.push(Vec::new());
+                chains.len() - 1
+            });
+            chains[chain].push(index);
+        }
+
+        let build = &build;
+        let mut completed = stream::iter(chains)
+            .map(|chain| async move {
+                let mut built = Vec::with_capacity(chain.len());
+                for index in chain {
+                    built.push((index, build(&items[index]).await));
+                }
+                built
+            })
+            .buffer_unordered(self.concurrency);
+
+        let mut results: Vec<Option<Result<R, String>>> = (0..items.len()).map(|_| None).collect();
+        while let Some(built) = completed.next().await {
+            for (index, result) in built {
+                results[index] = Some(result);
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every item belongs to a chain"))
+            .collect()
     }
 
     /// Create projection for a new event
@@ -324,7 +436,7 @@ impl ProjectionBuilder {
         self.store.save_dispatch_job_projection(&projection).await?;
 
         // Increment event's dispatch job count
-        self.store.increment_event_dispatch_count(&job.event_id).await?;
+        self.store.increment_event_dispatch_count(&job.event_id, &job.id).await?;
 
         debug!("Created dispatch job projection: {}", job.id);
 
@@ -586,17 +698,26 @@ impl ProjectionStore for MongoProjectionStore {
         Ok(())
     }
 
-    async fn increment_event_dispatch_count(&self, event_id: &str) -> Result<(), String> {
+    async fn increment_event_dispatch_count(&self, event_id: &str, dispatch_job_id: &str) -> Result<(), String> {
         use mongodb::bson::doc;
 
-        self.events_read
+        // Jobs already counted are recorded on the event, so a replayed job
+        // doesn't match the filter and isn't counted twice
+        let result = self.events_read
             .update_one(
-                doc! { "_id": event_id },
-                doc! { "$inc": { "dispatchJobCount": 1 } },
+                doc! { "_id": event_id, "countedDispatchJobIds": { "$ne": dispatch_job_id } },
+                doc! {
+                    "$inc": { "dispatchJobCount": 1 },
+                    "$addToSet": { "countedDispatchJobIds": dispatch_job_id },
+                },
             )
             .await
             .map_err(|e| format!("MongoDB update error: {}", e))?;
 
+        if result.matched_count == 0 {
+            tracing::debug!("Dispatch job {} already counted for event {} (or event missing)", dispatch_job_id, event_id);
+        }
+
         Ok(())
     }
 
@@ -778,6 +899,8 @@ impl ProjectionStore for MongoProjectionStore {
 pub struct InMemoryProjectionStore {
     events: Arc<tokio::sync::RwLock<std::collections::HashMap<String, EventReadProjection>>>,
     jobs: Arc<tokio::sync::RwLock<std::collections::HashMap<String, DispatchJobReadProjection>>>,
+    /// Dispatch jobs already counted towards their event's dispatch job count
+    counted_jobs: Arc<tokio::sync::RwLock<std::collections::HashSet<String>>>,
 }
 
 impl InMemoryProjectionStore {
@@ -785,6 +908,7 @@ impl InMemoryProjectionStore {
         Self {
             events: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
             jobs: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            counted_jobs: Arc::new(tokio::sync::RwLock::new(std::collections::HashSet::new())),
         }
     }
 
@@ -805,11 +929,14 @@ impl Default for InMemoryProjectionStore {
 
 #[async_trait]
 impl ProjectionStore for InMemoryProjectionStore {
+    // Saves keep an existing projection, like the MongoDB store's `$setOnInsert`
+
     async fn save_event_projection(&self, projection: &EventReadProjection) -> Result<(), String> {
         self.events
             .write()
             .await
-            .insert(projection.id.clone(), projection.clone());
+            .entry(projection.id.clone())
+            .or_insert_with(|| projection.clone());
         Ok(())
     }
 
@@ -820,7 +947,8 @@ impl ProjectionStore for InMemoryProjectionStore {
         self.jobs
             .write()
             .await
-            .insert(projection.id.clone(), projection.clone());
+            .entry(projection.id.clone())
+            .or_insert_with(|| projection.clone());
         Ok(())
     }
 
@@ -835,9 +963,11 @@ impl ProjectionStore for InMemoryProjectionStore {
         Ok(())
     }
 
-    async fn increment_event_dispatch_count(&self, event_id: &str) -> Result<(), String> {
+    async fn increment_event_dispatch_count(&self, event_id: &str, dispatch_job_id: &str) -> Result<(), String> {
         if let Some(event) = self.events.write().await.get_mut(event_id) {
-            event.dispatch_job_count += 1;
+            if self.counted_jobs.write().await.insert(dispatch_job_id.to_string()) {
+                event.dispatch_job_count += 1;
+            }
         }
         Ok(())
     }
@@ -1322,4 +1452,187 @@ mod tests {
         assert_eq!(projection.subject, "shipment");
         assert_eq!(projection.action, "shipped");
     }
+
+    /// Lookup that takes a varying time per call, so concurrent builds interleave
+    struct SlowLookup;
+
+    #[async_trait]
+    impl ProjectionLookup for SlowLookup {
+        async fn get_event_type_name(&self, code: &str) -> Option<String> {
+            tokio::time::sleep(std::time::Duration::from_millis((code.len() % 3) as u64)).await;
+            Some(format!("Name of {}", code))
+        }
+
+        async fn get_client_name(&self, id: &str) -> Option<String> {
+            Some(format!("Client {}", id))
+        }
+
+        async fn get_subscription_name(&self, id: &str) -> Option<String> {
+            tokio::time::sleep(std::time::Duration::from_millis((id.len() % 4) as u64)).await;
+            Some(format!("Subscription {}", id))
+        }
+
+        async fn get_dispatch_pool_name(&self, _id: &str) -> Option<String> {
+            None
+        }
+    }
+
+    /// Fixed timestamp so projections from separate runs compare equal
+    fn backfill_time() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    fn backfill_events() -> Vec<EventData> {
+        (0..20)
+            .map(|i| EventData {
+                id: format!("evt-{}", i),
+                event_type_code: format!("app:domain:subject:action{}", i),
+                client_id: Some("client-1".to_string()),
+                source_id: None,
+                source_type: None,
+                correlation_id: None,
+                data: serde_json::json!({"index": i}),
+                created_at: backfill_time(),
+            })
+            .collect()
+    }
+
+    fn backfill_job(id: usize, event: usize, status: &str) -> DispatchJobData {
+        DispatchJobData {
+            id: format!("job-{}", id),
+            event_id: format!("evt-{}", event),
+            event_type_code: format!("app:domain:subject:action{}", event),
+            subscription_id: format!("sub-{}", "x".repeat(id % 5)),
+            client_id: Some("client-1".to_string()),
+            target: "http://localhost/hook".to_string(),
+            status: status.to_string(),
+            attempt_count: 0,
+            max_retries: 3,
+            last_error: None,
+            last_attempt_at: None,
+            next_retry_at: None,
+            completed_at: None,
+            correlation_id: None,
+            dispatch_pool_id: None,
+            created_at: backfill_time(),
+            updated_at: backfill_time(),
+        }
+    }
+
+    fn backfill_jobs() -> Vec<DispatchJobData> {
+        // Uneven spread: event i gets i % 4 jobs
+        let mut jobs = Vec::new();
+        for event in 0..20 {
+            for _ in 0..event % 4 {
+                jobs.push(backfill_job(jobs.len(), event, "PENDING"));
+            }
+        }
+        jobs
+    }
+
+    async fn backfill(concurrency: usize) -> (InMemoryProjectionStore, ProjectionBuildResult) {
+        let store = InMemoryProjectionStore::new();
+        let builder = ProjectionBuilder::new(Arc::new(SlowLookup), Arc::new(store.clone()))
+            .with_concurrency(concurrency);
+        let result = builder.build_projections(&backfill_events(), &backfill_jobs()).await;
+        (store, result)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_build_matches_serial() {
+        let (serial_store, serial) = backfill(1).await;
+        let (concurrent_store, concurrent) = backfill(8).await;
+
+        // Results come back in input order
+        let ids = |result: &ProjectionBuildResult| -> (Vec<String>, Vec<String>) {
+            (
+                result.events.iter().map(|r| r.as_ref().unwrap().id.clone()).collect(),
+                result.dispatch_jobs.iter().map(|r| r.as_ref().unwrap().id.clone()).collect(),
+            )
+        };
+        assert_eq!(ids(&concurrent), ids(&serial));
+
+        for event in backfill_events() {
+            let serial_event = serial_store.get_event(&event.id).await.unwrap();
+            let concurrent_event = concurrent_store.get_event(&event.id).await.unwrap();
+            assert_eq!(
+                serde_json::to_value(&concurrent_event).unwrap(),
+                serde_json::to_value(&serial_event).unwrap(),
+            );
+        }
+        assert_eq!(concurrent_store.get_event("evt-3").await.unwrap().dispatch_job_count, 3);
+
+        for job in backfill_jobs() {
+            let serial_job = serial_store.get_job(&job.id).await.unwrap();
+            let concurrent_job = concurrent_store.get_job(&job.id).await.unwrap();
+            assert_eq!(
+                serde_json::to_value(&concurrent_job).unwrap(),
+                serde_json::to_value(&serial_job).unwrap(),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_updates_keep_per_event_order() {
+        let store = InMemoryProjectionStore::new();
+        let builder = ProjectionBuilder::new(Arc::new(SlowLookup), Arc::new(store.clone()))
+            .with_concurrency(8);
+
+        // Each job is updated three times; the slow lookup would reorder them if
+        // updates to the same event ran concurrently
+        let updates: Vec<DispatchJobData> = ["QUEUED", "IN_PROGRESS", "COMPLETED"]
+            .iter()
+            .flat_map(|status| (0..10).map(move |job| backfill_job(job, job % 3, status)))
+            .collect();
+
+        let results = builder.update_dispatch_job_projections(&updates).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+
+        for job in 0..10 {
+            let stored = store.get_job(&format!("job-{}", job)).await.unwrap();
+            assert_eq!(stored.status, "COMPLETED");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replayed_event_and_job_are_idempotent() {
+        let store = InMemoryProjectionStore::new();
+        let builder = ProjectionBuilder::new(Arc::new(SlowLookup), Arc::new(store.clone()));
+
+        let event = &backfill_events()[0];
+        let job = backfill_job(1, 0, "PENDING");
+
+        builder.create_event_projection(event).await.unwrap();
+        builder.create_dispatch_job_projection(&job).await.unwrap();
+
+        // Change stream resumes and replays both
+        builder.create_event_projection(event).await.unwrap();
+        builder.create_dispatch_job_projection(&job).await.unwrap();
+
+        let stored = store.get_event(&event.id).await.unwrap();
+        assert_eq!(stored.dispatch_job_count, 1);
+
+        // A different job for the same event still counts
+        builder.create_dispatch_job_projection(&backfill_job(2, 0, "PENDING")).await.unwrap();
+        assert_eq!(store.get_event(&event.id).await.unwrap().dispatch_job_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_backfill_does_not_double_count() {
+        let store = InMemoryProjectionStore::new();
+        let builder = ProjectionBuilder::new(Arc::new(SlowLookup), Arc::new(store.clone()))
+            .with_concurrency(4);
+
+        for _ in 0..2 {
+            let result = builder.build_projections(&backfill_events(), &backfill_jobs()).await;
+            assert!(result.events.iter().all(|r| r.is_ok()));
+            assert!(result.dispatch_jobs.iter().all(|r| r.is_ok()));
+        }
+
+        for event in 0..20 {
+            let stored = store.get_event(&format!("evt-{}", event)).await.unwrap();
+            assert_eq!(stored.dispatch_job_count, (event % 4) as u32);
+        }
+    }
 }
+